@@ -0,0 +1,88 @@
+// Support for debugging: the natvis, gdb and lldb visualizers of the bridged
+// types, which cxx_build writes into target/cxxbridge/rust/.
+
+use crate::gen::include::{GDB_SCRIPT, LLDB_SCRIPT, NATVIS};
+use std::mem;
+
+pub(crate) const NATVIS_FILE: &str = "cxx.natvis";
+
+// File name and contents of each visualizer.
+pub(crate) fn visualizers() -> [(&'static str, String); 3] {
+    let layout = Layout::probe();
+    [
+        (NATVIS_FILE, layout.fill(NATVIS)),
+        ("cxx_gdb.py", layout.fill(GDB_SCRIPT)),
+        ("cxx_lldb.py", layout.fill(LLDB_SCRIPT)),
+    ]
+}
+
+// Which word of a Rust String, Vec<T>, and slice reference holds the pointer
+// and which holds the length. Rust does not guarantee the order of these, so
+// it is read off of the same standard library that the build script is built
+// against. That comes from the same compiler as the program being debugged.
+struct Layout {
+    string_ptr: usize,
+    string_len: usize,
+    vec_ptr: usize,
+    vec_len: usize,
+    slice_ptr: usize,
+    slice_len: usize,
+}
+
+impl Layout {
+    fn probe() -> Self {
+        let mut string = String::with_capacity(3);
+        string.push('.');
+        let words = unsafe { mem::transmute_copy::<String, [usize; 3]>(&string) };
+        let string_ptr = position(&words, string.as_ptr() as usize);
+        let string_len = position(&words, string.len());
+
+        let mut vec = Vec::<u64>::with_capacity(3);
+        vec.push(0);
+        let words = unsafe { mem::transmute_copy::<Vec<u64>, [usize; 3]>(&vec) };
+        let vec_ptr = position(&words, vec.as_ptr() as usize);
+        let vec_len = position(&words, vec.len());
+
+        let slice = vec.as_slice();
+        let words = unsafe { mem::transmute::<&[u64], [usize; 2]>(slice) };
+        let slice_ptr = position(&words, slice.as_ptr() as usize);
+        let slice_len = 1 - slice_ptr;
+
+        Layout {
+            string_ptr,
+            string_len,
+            vec_ptr,
+            vec_len,
+            slice_ptr,
+            slice_len,
+        }
+    }
+
+    fn fill(&self, template: &str) -> String {
+        template
+            .replace("@STRING_PTR@", &self.string_ptr.to_string())
+            .replace("@STRING_LEN@", &self.string_len.to_string())
+            .replace("@VEC_PTR@", &self.vec_ptr.to_string())
+            .replace("@VEC_LEN@", &self.vec_len.to_string())
+            .replace("@SLICE_PTR@", &self.slice_ptr.to_string())
+            .replace("@SLICE_LEN@", &self.slice_len.to_string())
+    }
+}
+
+fn position(words: &[usize], word: usize) -> usize {
+    words.iter().position(|&w| w == word).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::debugger::visualizers;
+
+    #[test]
+    fn test_visualizers() {
+        for (file_name, contents) in visualizers() {
+            let unfilled = ["_PTR@", "_LEN@"];
+            let unfilled = unfilled.iter().find(|&&p| contents.contains(p));
+            assert!(unfilled.is_none(), "{}", file_name);
+        }
+    }
+}
@@ -18,6 +18,8 @@ pub(super) enum Error {
     ExportedLinksWithoutLinks,
     UnusedExportedPrefix(&'static str),
     UnusedExportedLinks(&'static str),
+    InvalidTestName(String),
+    DuplicateTestName(String),
 }
 
 macro_rules! expr {
@@ -78,6 +80,16 @@ impl Display for Error {
                 expr!(CFG.exported_header_links),
                 unused,
             ),
+            Error::InvalidTestName(name) => write!(
+                f,
+                "CXX_TEST({}) cannot be the name of a Rust #[test] function; use an identifier",
+                name,
+            ),
+            Error::DuplicateTestName(name) => write!(
+                f,
+                "CXX_TEST({}) is defined more than once among the files passed to cxx_build::tests",
+                name,
+            ),
         }
     }
 }
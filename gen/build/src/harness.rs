@@ -0,0 +1,100 @@
+// Support for cxx_build::tests: finds the CXX_TEST cases of the C++ test files
+// and generates the Rust #[test] functions that cxx::cxx_tests! includes.
+
+use crate::error::{Error, Result};
+use std::collections::BTreeSet as Set;
+use std::fmt::Write;
+use syn::Ident;
+
+// Names of the `CXX_TEST(name)` cases in a C++ source file, in order. This is a
+// textual search, so a test case inside of a comment or a disabled #if block is
+// found too, and will fail to link.
+pub(crate) fn test_names(source: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = source;
+    while let Some(i) = rest.find("CXX_TEST(") {
+        let preceded_by_ident = rest[..i]
+            .chars()
+            .next_back()
+            .is_some_and(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+        rest = &rest[i + "CXX_TEST(".len()..];
+        if preceded_by_ident {
+            continue;
+        }
+        let args = rest.trim_start();
+        let len = args
+            .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_')
+            .unwrap_or(args.len());
+        let name = &args[..len];
+        if !name.is_empty() && args[len..].trim_start().starts_with(')') {
+            names.push(name);
+        }
+    }
+    names
+}
+
+pub(crate) fn gen(names: &[&str]) -> Result<String> {
+    let mut seen = Set::new();
+    let mut out = String::new();
+    out += "// Generated by cxx_build::tests.\n";
+    for name in names {
+        if !seen.insert(*name) {
+            return Err(Error::DuplicateTestName((*name).to_owned()));
+        }
+        let ident = rust_ident(name).ok_or_else(|| Error::InvalidTestName((*name).to_owned()))?;
+        out += "\n#[test]\n";
+        let _ = writeln!(out, "fn {}() {{", ident);
+        out += "    #[allow(unknown_lints, missing_unsafe_on_extern)]\n";
+        out += "    extern \"C\" {\n";
+        let _ = writeln!(out, "        #[link_name = \"cxxbridge1$test${}\"]", name);
+        out += "        fn test(message: *mut *const u8, len: *mut usize) -> bool;\n";
+        out += "    }\n";
+        out += "    ::cxx::private::run_cxx_test(test);\n";
+        out += "}\n";
+    }
+    Ok(out)
+}
+
+// The name as a Rust identifier, raw if it is a keyword such as `match`, or
+// None for what cannot be a function name even as a raw identifier, like
+// `self` or `1st`.
+fn rust_ident(name: &str) -> Option<String> {
+    if syn::parse_str::<Ident>(name).is_ok() {
+        Some(name.to_owned())
+    } else {
+        let raw = format!("r#{}", name);
+        syn::parse_str::<Ident>(&raw).ok().map(|_| raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::harness;
+
+    #[test]
+    fn test_raw_keyword() {
+        let out = harness::gen(&["roundtrip", "match"]).unwrap();
+        assert!(out.contains("\nfn roundtrip() {\n"));
+        assert!(out.contains("\nfn r#match() {\n"));
+        assert!(out.contains("\"cxxbridge1$test$match\""));
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        let err = harness::gen(&["roundtrip", "roundtrip"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CXX_TEST(roundtrip) is defined more than once among the files passed to cxx_build::tests",
+        );
+        for name in ["self", "1st", "_"] {
+            let err = harness::gen(&[name]).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "CXX_TEST({}) cannot be the name of a Rust #[test] function; use an identifier",
+                    name
+                ),
+            );
+        }
+    }
+}
@@ -79,9 +79,11 @@
 
 mod cargo;
 mod cfg;
+mod debugger;
 mod deps;
 mod error;
 mod gen;
+mod harness;
 mod intern;
 mod out;
 mod paths;
@@ -137,6 +139,51 @@ pub fn bridges(rust_source_files: impl IntoIterator<Item = impl AsRef<Path>>) ->
     })
 }
 
+/// Compiles C++ test files which exercise the bindings of this crate's
+/// bridges, so that `cargo test` runs their test cases.
+///
+/// Each test case is defined in C++ using the `CXX_TEST` macro from
+/// `"rust/cxx_test.h"`, and becomes one Rust `#[test]` in whichever test target
+/// invokes [`cxx::cxx_tests!`]. The test cases can `#include` the headers
+/// generated for the bridges of this crate in the usual way.
+///
+/// [`cxx::cxx_tests!`]: https://docs.rs/cxx/*/cxx/macro.cxx_tests.html
+///
+/// ```no_run
+/// // build.rs
+///
+/// fn main() {
+///     cxx_build::bridge("src/main.rs")
+///         .file("src/demo.cc")
+///         .std("c++11")
+///         .compile("cxxbridge-demo");
+///
+///     cxx_build::tests(["tests/demo_test.cc"])
+///         .std("c++11")
+///         .compile("cxxbridge-demo-tests");
+/// }
+/// ```
+///
+/// ```cpp
+/// // tests/demo_test.cc
+///
+/// #include "demo/src/main.rs.h"
+/// #include "rust/cxx_test.h"
+///
+/// CXX_TEST(blobstore_put) {
+///   auto client = org::blobstore::new_blobstore_client();
+///   CXX_ASSERT(client != nullptr);
+/// }
+/// ```
+#[must_use]
+pub fn tests(cxx_test_files: impl IntoIterator<Item = impl AsRef<Path>>) -> Build {
+    let ref mut cxx_test_files = cxx_test_files.into_iter();
+    build_tests(cxx_test_files).unwrap_or_else(|err| {
+        let _ = writeln!(io::stderr(), "\n\ncxxbridge error: {}\n\n", report(err));
+        process::exit(1);
+    })
+}
+
 struct Project {
     include_prefix: PathBuf,
     manifest_dir: PathBuf,
@@ -219,6 +266,7 @@ fn build(rust_source_files: &mut dyn Iterator<Item = impl AsRef<Path>>) -> Resul
     for path in rust_source_files {
         generate_bridge(prj, &mut build, path.as_ref())?;
     }
+    write_debugger_visualizers(prj)?;
 
     this_crate.print_to_cargo();
     eprintln!("\nCXX include path:");
@@ -234,6 +282,37 @@ fn build(rust_source_files: &mut dyn Iterator<Item = impl AsRef<Path>>) -> Resul
     Ok(build)
 }
 
+// In addition to the layout above, cxx_build::tests writes:
+//
+//     $OUT_DIR/
+//        cxxbridge/
+//           include/
+//              rust/
+//                 cxx_test.h
+//           tests.rs
+fn build_tests(cxx_test_files: &mut dyn Iterator<Item = impl AsRef<Path>>) -> Result<Build> {
+    let mut build = build(&mut iter::empty::<&Path>())?;
+    let ref prj = Project::init()?;
+    let cxxbridge = prj.out_dir.join("cxxbridge");
+    let cxx_test_h = cxxbridge.join("include").join("rust").join("cxx_test.h");
+    out::write(cxx_test_h, gen::include::TEST_HEADER.as_bytes())?;
+
+    let mut sources = Vec::new();
+    for path in cxx_test_files {
+        let path = path.as_ref();
+        println!("cargo:rerun-if-changed={}", path.display());
+        let source = gen::fs::read(path)?;
+        sources.push(String::from_utf8_lossy(&source).into_owned());
+        build.file(path);
+    }
+    let names: Vec<&str> = sources
+        .iter()
+        .flat_map(|source| harness::test_names(source))
+        .collect();
+    out::write(cxxbridge.join("tests.rs"), harness::gen(&names)?.as_bytes())?;
+    Ok(build)
+}
+
 fn validate_cfg(prj: &Project) -> Result<()> {
     for exported_dir in &CFG.exported_header_dirs {
         if !exported_dir.is_absolute() {
@@ -388,9 +467,30 @@ fn make_include_dir(prj: &Project) -> Result<PathBuf> {
         out::write(shared_cxx_h, gen::include::HEADER.as_bytes())?;
         out::relative_symlink_file(shared_cxx_h, cxx_h)?;
     }
+    let cxx_format_h = include_dir.join("rust").join("cxx_format.h");
+    let ref shared_cxx_format_h = prj.shared_dir.join("rust").join("cxx_format.h");
+    out::write(shared_cxx_format_h, gen::include::FORMAT_HEADER.as_bytes())?;
+    out::relative_symlink_file(shared_cxx_format_h, cxx_format_h)?;
     Ok(include_dir)
 }
 
+// The debugger visualizers go next to rust/cxx.h in the shared directory, for
+// loading into gdb or lldb from there. For MSVC, the natvis file also gets
+// embedded into the PDB of every binary that this package links.
+fn write_debugger_visualizers(prj: &Project) -> Result<()> {
+    let dir = prj.shared_dir.join("rust");
+    for (file_name, contents) in debugger::visualizers() {
+        out::write(dir.join(file_name), contents.as_bytes())?;
+    }
+    let msvc = env::var_os("CARGO_CFG_TARGET_ENV").is_some_and(|env| env == "msvc");
+    let debuginfo = env::var_os("DEBUG").is_some_and(|debug| debug != "false");
+    if msvc && debuginfo {
+        let natvis = dir.join(debugger::NATVIS_FILE);
+        println!("cargo:rustc-link-arg=/NATVIS:{}", natvis.display());
+    }
+    Ok(())
+}
+
 fn generate_bridge(prj: &Project, build: &mut Build, rust_source_file: &Path) -> Result<()> {
     let opt = Opt {
         allow_dot_includes: false,
@@ -400,6 +500,10 @@ fn generate_bridge(prj: &Project, build: &mut Build, rust_source_file: &Path) ->
     };
     println!("cargo:rerun-if-changed={}", rust_source_file.display());
     let generated = gen::generate_from_path(rust_source_file, &opt);
+    for included_file in &generated.included_files {
+        println!("cargo:rerun-if-changed={}", included_file.display());
+    }
+    let generated = generated.code;
     let ref rel_path = paths::local_relative_path(rust_source_file);
 
     let cxxbridge = prj.out_dir.join("cxxbridge");
@@ -2,11 +2,14 @@
 #[path = "test.rs"]
 mod test;
 
-use super::{Opt, Output};
+use super::{Emit, Opt, Output};
 use crate::cfg::{self, CfgValue};
+use crate::gen::error::MessageFormat;
 use crate::gen::include::Include;
+use crate::syntax::namespace::Namespace;
 use crate::syntax::IncludeKind;
 use clap::builder::{ArgAction, ValueParser};
+use clap::error::ErrorKind;
 use clap::{Arg, Command};
 use std::collections::{BTreeMap as Map, BTreeSet as Set};
 use std::path::PathBuf;
@@ -17,7 +20,8 @@ use syn::parse::Parser;
 const USAGE: &str = "\
     cxxbridge <input>.rs              Emit .cc file for bridge to stdout
     cxxbridge <input>.rs --header     Emit .h file for bridge to stdout
-    cxxbridge --header                Emit \"rust/cxx.h\" header to stdout\
+    cxxbridge --header                Emit \"rust/cxx.h\" header to stdout
+    cxxbridge <input>.rs --lint       Warn about suspicious bridge declarations\
 ";
 
 const TEMPLATE: &str = "\
@@ -39,12 +43,25 @@ fn app() -> Command {
         .disable_help_flag(true)
         .disable_version_flag(true)
         .arg(arg_input())
+        .arg(arg_abi_diff())
         .arg(arg_cfg())
+        .arg(arg_clang_arg())
         .arg(arg_cxx_impl_annotations())
+        .arg(arg_emit())
         .arg(arg_header())
         .arg(arg_help())
         .arg(arg_include())
-        .arg(arg_output());
+        .arg(arg_include_prefix())
+        .arg(arg_lint())
+        .arg(arg_manifest_path())
+        .arg(arg_map_namespace())
+        .arg(arg_message_format())
+        .arg(arg_out_dir())
+        .arg(arg_output())
+        .arg(arg_rewrite_include())
+        .arg(arg_target())
+        .arg(arg_verify())
+        .arg(arg_watch());
     if let Some(version) = option_env!("CARGO_PKG_VERSION") {
         app = app.arg(arg_version()).version(version);
     }
@@ -52,13 +69,26 @@ fn app() -> Command {
 }
 
 const INPUT: &str = "input";
+const ABI_DIFF: &str = "abi-diff";
 const CFG: &str = "cfg";
+const CLANG_ARG: &str = "clang-arg";
 const CXX_IMPL_ANNOTATIONS: &str = "cxx-impl-annotations";
+const EMIT: &str = "emit";
 const HELP: &str = "help";
 const HEADER: &str = "header";
 const INCLUDE: &str = "include";
+const INCLUDE_PREFIX: &str = "include-prefix";
+const LINT: &str = "lint";
+const MANIFEST_PATH: &str = "manifest-path";
+const MAP_NAMESPACE: &str = "map-namespace";
+const MESSAGE_FORMAT: &str = "message-format";
+const OUT_DIR: &str = "out-dir";
 const OUTPUT: &str = "output";
+const REWRITE_INCLUDE: &str = "rewrite-include";
+const TARGET: &str = "target";
+const VERIFY: &str = "verify";
 const VERSION: &str = "version";
+const WATCH: &str = "watch";
 
 pub(super) fn from_args() -> Opt {
     let matches = app().get_matches();
@@ -69,10 +99,65 @@ pub(super) fn from_args() -> Opt {
     }
 
     let input = matches.get_one::<PathBuf>(INPUT).cloned();
+    let abi_diff = matches.get_one::<PathBuf>(ABI_DIFF).cloned();
+    let manifest_path = matches.get_one::<PathBuf>(MANIFEST_PATH).cloned();
+    let out_dir = matches.get_one::<PathBuf>(OUT_DIR).cloned();
     let cxx_impl_annotations = matches
         .get_one::<String>(CXX_IMPL_ANNOTATIONS)
         .map(String::clone);
     let header = matches.get_flag(HEADER);
+    let emit = matches
+        .get_one::<String>(EMIT)
+        .map(|emit| match emit.as_str() {
+            "symbols" => Emit::Symbols,
+            "rust" => Emit::Rust,
+            "stubs" => Emit::Stubs,
+            "mocks" => Emit::Mocks,
+            "docs" => Emit::Docs,
+            "fuzz" => Emit::Fuzz,
+            _ => unreachable!(),
+        });
+    let watch = matches.get_flag(WATCH);
+    let verify = matches.get_flag(VERIFY);
+    let lint = matches.get_flag(LINT);
+    let message_format = match matches.get_one::<String>(MESSAGE_FORMAT) {
+        Some(format) if format == "json" => MessageFormat::Json,
+        _ => MessageFormat::Human,
+    };
+    let clang_args = matches
+        .get_many::<String>(CLANG_ARG)
+        .unwrap_or_default()
+        .cloned()
+        .collect();
+    if watch && input.as_ref().is_some_and(|input| input.as_os_str() == "-") {
+        app()
+            .error(ErrorKind::ArgumentConflict, "cannot --watch standard input")
+            .exit();
+    }
+    if header && emit.is_some_and(|emit| !matches!(emit, Emit::Mocks)) {
+        app()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--header cannot be used with --emit other than `mocks`",
+            )
+            .exit();
+    }
+    if manifest_path.is_some() && out_dir.is_none() && !lint {
+        app()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "--manifest-path requires --out-dir unless used with --lint",
+            )
+            .exit();
+    }
+    if lint && input.is_none() && manifest_path.is_none() {
+        app()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "--lint requires an input file or --manifest-path",
+            )
+            .exit();
+    }
     let include = matches
         .get_many::<String>(INCLUDE)
         .unwrap_or_default()
@@ -91,6 +176,24 @@ pub(super) fn from_args() -> Opt {
         })
         .collect();
 
+    let mut include_rewrites = Vec::new();
+    for arg in matches
+        .get_many::<String>(REWRITE_INCLUDE)
+        .unwrap_or_default()
+    {
+        let (from, to) = arg.split_once('=').unwrap();
+        include_rewrites.push((from.to_owned(), to.to_owned()));
+    }
+    if let Some(prefix) = matches.get_one::<String>(INCLUDE_PREFIX) {
+        include_rewrites.push((String::new(), prefix.clone()));
+    }
+
+    let namespace_map = matches
+        .get_many::<String>(MAP_NAMESPACE)
+        .unwrap_or_default()
+        .map(|arg| parse_namespace_mapping(arg).unwrap())
+        .collect();
+
     let mut outputs = Vec::new();
     for path in matches.get_many::<PathBuf>(OUTPUT).unwrap_or_default() {
         outputs.push(if path.as_os_str() == "-" {
@@ -103,29 +206,87 @@ pub(super) fn from_args() -> Opt {
         outputs.push(Output::Stdout);
     }
 
-    let mut cfg = Map::new();
+    let mut flags_cfg = Map::new();
     for arg in matches.get_many::<String>(CFG).unwrap_or_default() {
         let (name, value) = cfg::parse.parse_str(arg).unwrap();
-        cfg.entry(name).or_insert_with(Set::new).insert(value);
+        flags_cfg.entry(name).or_insert_with(Set::new).insert(value);
     }
 
+    // Explicit --cfg flags take precedence over whatever the target implies for
+    // the same name.
+    let target = matches.get_one::<String>(TARGET);
+    let cfg_is_complete = target.is_some();
+    let mut cfg = match target {
+        Some(target) => cfg::target(target).unwrap_or_else(|msg| {
+            app()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("--target {}: {}", target, msg),
+                )
+                .exit()
+        }),
+        None => Map::new(),
+    };
+    cfg.extend(flags_cfg);
+
     Opt {
         input,
+        abi_diff,
+        manifest_path,
+        out_dir,
         header,
+        emit,
         cxx_impl_annotations,
         include,
+        include_rewrites,
+        namespace_map,
         outputs,
         cfg,
+        cfg_is_complete,
+        watch,
+        verify,
+        lint,
+        clang_args,
+        message_format,
+    }
+}
+
+fn parse_namespace_mapping(arg: &str) -> Result<(Namespace, Namespace), String> {
+    let Some((from, to)) = arg.split_once('=') else {
+        return Err("expected old::path=new::path".to_owned());
+    };
+    let from: Namespace = syn::parse_str(from).map_err(|err| err.to_string())?;
+    let to: Namespace = syn::parse_str(to).map_err(|err| err.to_string())?;
+    if from == Namespace::ROOT {
+        return Err("the root namespace cannot be remapped".to_owned());
     }
+    Ok((from, to))
 }
 
 fn arg_input() -> Arg {
     Arg::new(INPUT)
         .help("Input Rust source file containing #[cxx::bridge].")
-        .required_unless_present_any([HEADER, HELP])
+        .required_unless_present_any([HEADER, HELP, MANIFEST_PATH])
         .value_parser(ValueParser::path_buf())
 }
 
+fn arg_abi_diff() -> Arg {
+    const HELP: &str = "\
+Instead of generating code, compare the bridge in the input file
+against an older version of it, and print the functions, types,
+and symbols that were added or removed or whose signature or
+layout changed. Exits with failure if any change would break C++
+or Rust code built against the old version.";
+    Arg::new(ABI_DIFF)
+        .long(ABI_DIFF)
+        .num_args(1)
+        .value_name("old.rs")
+        .value_parser(ValueParser::path_buf())
+        .requires(INPUT)
+        .conflicts_with_all([EMIT, HEADER, LINT, MANIFEST_PATH, OUTPUT, VERIFY, WATCH])
+        .help(HELP)
+}
+
 fn arg_cfg() -> Arg {
     const HELP: &str = "\
 Compilation configuration matching what will be used to build
@@ -153,6 +314,21 @@ the Rust side of the bridge.";
         .help(HELP)
 }
 
+fn arg_clang_arg() -> Arg {
+    const HELP: &str = "\
+Additional argument to pass to the C++ compiler for --verify,
+such as -I or -std flags needed to parse the C++ headers.";
+    Arg::new(CLANG_ARG)
+        .long(CLANG_ARG)
+        .num_args(1)
+        .value_name("arg")
+        .action(ArgAction::Append)
+        .allow_hyphen_values(true)
+        .value_parser(ValueParser::string())
+        .requires(VERIFY)
+        .help(HELP)
+}
+
 fn arg_cxx_impl_annotations() -> Arg {
     const HELP: &str = "\
 Optional annotation for implementations of C++ function wrappers
@@ -168,6 +344,39 @@ these C++ functions in another.";
         .help(HELP)
 }
 
+fn arg_emit() -> Arg {
+    const HELP: &str = "\
+Emit something other than C++ code for the bridge. `symbols`
+lists every extern \"C\" symbol through which the C++ and Rust
+sides call each other, one per line, each preceded by `cxx` or
+`rust` according to which side defines it. `rust` prints the
+input file with each bridge module replaced by the code that
+the #[cxx::bridge] macro would expand it to. `stubs` writes a
+skeleton .cc defining every function of the extern \"C++\"
+blocks, to fill in when starting the C++ side of a new bridge.
+It includes the bridge's generated header by the input path with
+.h appended, such as src/lib.rs.h.
+`mocks` writes gMock classes for the extern \"Rust\" blocks that
+have a #[mock] attribute, as a header with `--header` or `-o`
+with a path ending in `.h` and otherwise as a .cc defining the
+functions to call the mocks. Pass the bridge's generated header
+with `--include`.
+`docs` prints a Markdown reference of the bridge's types and
+functions with their Rust and C++ signatures, ownership of the
+arguments and return values, and doc comments.
+`fuzz` writes a cargo-fuzz target that calls the extern \"C++\"
+functions with arguments from the `arbitrary` crate, for shared
+types deriving `Arbitrary` and Rust's own types.";
+    Arg::new(EMIT)
+        .long(EMIT)
+        .num_args(1)
+        .value_name("kind")
+        .value_parser(["symbols", "rust", "stubs", "mocks", "docs", "fuzz"])
+        .hide_possible_values(true)
+        .requires(INPUT)
+        .help(HELP)
+}
+
 fn arg_header() -> Arg {
     const HELP: &str = "\
 Emit header with declarations only. Optional if using `-o` with
@@ -196,6 +405,94 @@ into the generated C++ code as #include lines.";
         .help(HELP)
 }
 
+fn arg_include_prefix() -> Arg {
+    const HELP: &str = "\
+Prefix to prepend to the path of every quoted #include in the
+generated code, for when headers are installed under a different
+layout than the one used at build time. Paths matched by a
+--rewrite-include are not additionally prefixed.";
+    Arg::new(INCLUDE_PREFIX)
+        .long(INCLUDE_PREFIX)
+        .num_args(1)
+        .value_name("prefix")
+        .value_parser(ValueParser::string())
+        .help(HELP)
+}
+
+fn arg_lint() -> Arg {
+    const HELP: &str = "\
+Instead of writing any output, print warnings about extern C++
+declarations never used from Rust, C++ entities bound more than
+once, cxx_name or rust_name renames that only change case, and
+signatures that let safe Rust create aliasing or unpinned mutable
+references, along with the declaration to use instead. Uses are
+searched for in the input file, or in every file of the crate with
+--manifest-path. Exits with failure if any are found.";
+    Arg::new(LINT)
+        .long(LINT)
+        .num_args(0)
+        .conflicts_with_all([EMIT, HEADER, OUTPUT, OUT_DIR, VERIFY, WATCH])
+        .help(HELP)
+}
+
+fn arg_manifest_path() -> Arg {
+    const HELP: &str = "\
+Generate code for every bridge in a crate instead of a single
+input file. Bridges are found by following `mod` declarations
+from the lib and bin targets of the given Cargo.toml, as listed
+by `cargo metadata`, or of every member of a workspace. Requires
+--out-dir, unless used with --lint.";
+    Arg::new(MANIFEST_PATH)
+        .long(MANIFEST_PATH)
+        .num_args(1)
+        .value_name("Cargo.toml")
+        .value_parser(ValueParser::path_buf())
+        .conflicts_with_all([INPUT, EMIT, HEADER, OUTPUT, VERIFY, WATCH])
+        .help(HELP)
+}
+
+fn arg_map_namespace() -> Arg {
+    const HELP: &str = "\
+Emit C++ items that the bridge places in namespace `old` or any
+namespace nested in it under `new` instead. The symbols that
+link the C++ side with the Rust side are unaffected.";
+    Arg::new(MAP_NAMESPACE)
+        .long(MAP_NAMESPACE)
+        .num_args(1)
+        .value_name("old::path=new::path")
+        .action(ArgAction::Append)
+        .value_parser(|arg: &str| parse_namespace_mapping(arg).map(|_| arg.to_owned()))
+        .help(HELP)
+}
+
+fn arg_message_format() -> Arg {
+    const HELP: &str = "\
+Format of the errors printed to stderr. With `json`, each error
+is a single line JSON object with the file, message, span, and
+suggestion if any, for consumption by build systems and IDEs.";
+    Arg::new(MESSAGE_FORMAT)
+        .long(MESSAGE_FORMAT)
+        .num_args(1)
+        .value_name("human|json")
+        .value_parser(["human", "json"])
+        .hide_possible_values(true)
+        .help(HELP)
+}
+
+fn arg_out_dir() -> Arg {
+    const HELP: &str = "\
+Directory to write the outputs of --manifest-path into. Each
+bridge `src/path.rs` produces `src/path.rs.h` and
+`src/path.rs.cc` under this directory.";
+    Arg::new(OUT_DIR)
+        .long(OUT_DIR)
+        .num_args(1)
+        .value_name("dir")
+        .value_parser(ValueParser::path_buf())
+        .requires(MANIFEST_PATH)
+        .help(HELP)
+}
+
 fn arg_output() -> Arg {
     const HELP: &str = "\
 Path of file to write as output. Output goes to stdout if -o is
@@ -209,6 +506,64 @@ not specified.";
         .help(HELP)
 }
 
+fn arg_rewrite_include() -> Arg {
+    const HELP: &str = "\
+Replace the leading `old` portion of the path of any quoted
+#include in the generated code with `new`. If more than one
+rewrite matches, the longest `old` wins.";
+    Arg::new(REWRITE_INCLUDE)
+        .long(REWRITE_INCLUDE)
+        .num_args(1)
+        .value_name("old=new")
+        .action(ArgAction::Append)
+        .value_parser(|arg: &str| match arg.split_once('=') {
+            Some(_) => Ok(arg.to_owned()),
+            None => Err("expected old=new".to_owned()),
+        })
+        .help(HELP)
+}
+
+fn arg_target() -> Arg {
+    const HELP: &str = "\
+Target triple that the Rust side of the bridge will be built for.
+Cfgs implied by the target such as target_os and unix are enabled
+the same way that Cargo enables them for build scripts, and any
+cfg not implied by the target or passed via --cfg is disabled.
+The target's cfgs are obtained from $RUSTC or rustc.";
+    Arg::new(TARGET)
+        .long(TARGET)
+        .num_args(1)
+        .value_name("triple")
+        .value_parser(ValueParser::string())
+        .help(HELP)
+}
+
+fn arg_verify() -> Arg {
+    const HELP: &str = "\
+Instead of writing any output, check that the extern \"C++\"
+declarations of the bridge match the C++ headers it includes, by
+parsing the generated code with clang++ (or $CXX). Mismatched
+names, arity, constness, or types are reported as errors.";
+    Arg::new(VERIFY)
+        .long(VERIFY)
+        .num_args(0)
+        .requires(INPUT)
+        .conflicts_with_all([EMIT, HEADER, OUTPUT, WATCH])
+        .help(HELP)
+}
+
+fn arg_watch() -> Arg {
+    const HELP: &str = "\
+Keep running after the first generation, regenerating the outputs
+whenever the input file is modified. Errors are reported without
+exiting, and outputs whose content is unchanged are not rewritten.";
+    Arg::new(WATCH)
+        .long(WATCH)
+        .num_args(0)
+        .requires(INPUT)
+        .help(HELP)
+}
+
 fn arg_version() -> Arg {
     Arg::new(VERSION)
         .long(VERSION)
@@ -1,7 +1,10 @@
 use crate::gen::{CfgEvaluator, CfgResult};
 use std::collections::{BTreeMap as Map, BTreeSet as Set};
+use std::env;
+use std::ffi::OsString;
 use std::fmt::{self, Debug};
-use syn::parse::ParseStream;
+use std::process::Command;
+use syn::parse::{ParseStream, Parser};
 use syn::{Ident, LitBool, LitStr, Token};
 
 #[derive(Ord, PartialOrd, Eq, PartialEq)]
@@ -17,11 +20,18 @@ impl CfgValue {
 
 pub(crate) struct FlagsCfgEvaluator {
     map: Map<String, Set<CfgValue>>,
+    // Whether the map holds the complete set of cfgs of some target, in which
+    // case anything not in the map is disabled, same as in rustc.
+    complete: bool,
 }
 
+// Cfgs which depend on the build profile rather than the target, and so are not
+// implied by `--target`.
+const PROFILE_CFGS: &[&str] = &["debug_assertions"];
+
 impl FlagsCfgEvaluator {
-    pub(crate) fn new(map: Map<String, Set<CfgValue>>) -> Self {
-        FlagsCfgEvaluator { map }
+    pub(crate) fn new(map: Map<String, Set<CfgValue>>, complete: bool) -> Self {
+        FlagsCfgEvaluator { map, complete }
     }
 }
 
@@ -31,7 +41,7 @@ impl CfgEvaluator for FlagsCfgEvaluator {
         if let Some(value) = value {
             if let Some(set) = set {
                 CfgResult::from(set.contains(&CfgValue::Str(value.to_owned())))
-            } else if name == "feature" {
+            } else if name == "feature" || self.complete {
                 CfgResult::False
             } else {
                 let msg = format!(
@@ -53,6 +63,8 @@ impl CfgEvaluator for FlagsCfgEvaluator {
                 CfgResult::False
             } else if is_true {
                 CfgResult::True
+            } else if self.complete && !PROFILE_CFGS.contains(&name) {
+                CfgResult::False
             } else {
                 let msg = format!(
                     "pass either `--cfg {0}=true` or `--cfg {0}=false` to be able to use this cfg attribute",
@@ -91,3 +103,28 @@ pub(crate) fn parse(input: ParseStream) -> syn::Result<(String, CfgValue)> {
         Err(lookahead.error())
     }
 }
+
+// Loads the same cfgs that Cargo would expose to a build script when building
+// for the given target, by asking rustc.
+pub(crate) fn target(target: &str) -> Result<Map<String, Set<CfgValue>>, String> {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| OsString::from("rustc"));
+    let output = Command::new(&rustc)
+        .args(["--print", "cfg", "--target", target])
+        .output()
+        .map_err(|err| format!("failed to run {}: {}", rustc.to_string_lossy(), err))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.trim_end().to_owned());
+    }
+
+    let mut map = Map::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let (name, value) = parse
+            .parse_str(line)
+            .map_err(|_| format!("unrecognized rustc cfg: {}", line))?;
+        if !PROFILE_CFGS.contains(&name.as_str()) {
+            map.entry(name).or_insert_with(Set::new).insert(value);
+        }
+    }
+    Ok(map)
+}
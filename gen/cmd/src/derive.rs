@@ -0,0 +1 @@
+../../../macro/src/derive.rs
\ No newline at end of file
@@ -0,0 +1,243 @@
+// Support for `--manifest-path`: finds every source file of a crate that
+// contains a #[cxx::bridge] module, starting from the crate's lib and bin
+// targets as listed by `cargo metadata` and following `mod` declarations the
+// way rustc would.
+//
+// Cfg attributes on `mod` declarations are not evaluated, so modules that are
+// only compiled on some targets are searched too.
+
+use crate::gen::error::{print_err, print_message, Error, MessageFormat};
+use crate::gen::fs;
+use serde_json::Value;
+use std::collections::BTreeSet as Set;
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use syn::{Attribute, Expr, Item, Lit, Meta};
+
+// Errors have already been printed to stderr if this returns None.
+pub(crate) fn bridges(manifest_path: &Path, format: MessageFormat) -> Option<Vec<PathBuf>> {
+    Some(discover(manifest_path, format)?.bridges)
+}
+
+// The files containing a bridge, followed by every source file of the crate
+// including those. Errors have already been printed to stderr if this returns
+// None.
+pub(crate) fn sources(
+    manifest_path: &Path,
+    format: MessageFormat,
+) -> Option<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let discovery = discover(manifest_path, format)?;
+    let sources = discovery.visited.into_iter().collect();
+    Some((discovery.bridges, sources))
+}
+
+fn discover(manifest_path: &Path, format: MessageFormat) -> Option<Discovery> {
+    let roots = match target_roots(manifest_path) {
+        Ok(roots) => roots,
+        Err(msg) => {
+            print_message(manifest_path, &msg, format);
+            return None;
+        }
+    };
+
+    let mut discovery = Discovery {
+        format,
+        visited: Set::new(),
+        bridges: Vec::new(),
+    };
+    for root in roots {
+        let dir = root.parent().unwrap().to_owned();
+        discovery.visit_file(&root, &dir)?;
+    }
+    Some(discovery)
+}
+
+// The root source files of the lib and bin targets of the package, or of every
+// member if the manifest is that of a virtual workspace, as reported by `cargo
+// metadata`. This takes into account the `path` of each target as well as the
+// targets that Cargo discovers by itself, such as src/bin/*.rs.
+fn target_roots(manifest_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let output = Command::new(&cargo)
+        .args([
+            "metadata",
+            "--format-version",
+            "1",
+            "--no-deps",
+            "--manifest-path",
+        ])
+        .arg(manifest_path)
+        .output()
+        .map_err(|err| {
+            format!(
+                "failed to run {} metadata: {}",
+                cargo.to_string_lossy(),
+                err
+            )
+        })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.trim_end().to_owned());
+    }
+    let metadata: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|err| format!("failed to parse output of cargo metadata: {}", err))?;
+
+    let packages = metadata["packages"]
+        .as_array()
+        .map_or(&[][..], Vec::as_slice);
+    let manifest_path = std::fs::canonicalize(manifest_path).unwrap_or_default();
+    let is_manifest = |package: &&Value| {
+        package["manifest_path"]
+            .as_str()
+            .and_then(|path| std::fs::canonicalize(path).ok())
+            .is_some_and(|path| path == manifest_path)
+    };
+    let packages: Vec<&Value> = match packages.iter().find(is_manifest) {
+        Some(package) => vec![package],
+        None => packages.iter().collect(),
+    };
+
+    let mut roots = Vec::new();
+    for package in packages {
+        let targets = package["targets"].as_array().map_or(&[][..], Vec::as_slice);
+        for target in targets {
+            let kinds = target["kind"].as_array().map_or(&[][..], Vec::as_slice);
+            let is_lib_or_bin = kinds.iter().any(|kind| {
+                matches!(
+                    kind.as_str(),
+                    Some("lib" | "rlib" | "dylib" | "cdylib" | "staticlib" | "bin"),
+                )
+            });
+            if let (true, Some(src_path)) = (is_lib_or_bin, target["src_path"].as_str()) {
+                roots.push(PathBuf::from(src_path));
+            }
+        }
+    }
+    Ok(roots)
+}
+
+struct Discovery {
+    format: MessageFormat,
+    visited: Set<PathBuf>,
+    bridges: Vec<PathBuf>,
+}
+
+impl Discovery {
+    // The `dir` is where out-of-line modules declared at the top level of this
+    // file are looked up.
+    fn visit_file(&mut self, path: &Path, dir: &Path) -> Option<()> {
+        if !self.visited.insert(path.to_owned()) {
+            return Some(());
+        }
+
+        let source = match fs::read(path) {
+            Ok(source) => source,
+            Err(err) => {
+                print_err(path, "", Error::Fs(err), self.format);
+                return None;
+            }
+        };
+        let source = match String::from_utf8(source) {
+            Ok(source) => source,
+            Err(err) => {
+                let err = Error::Utf8(path.to_owned(), err.utf8_error());
+                print_err(path, "", err, self.format);
+                return None;
+            }
+        };
+        let file = match syn::parse_file(&source) {
+            Ok(file) => file,
+            Err(err) => {
+                print_err(path, &source, Error::Syn(err), self.format);
+                return None;
+            }
+        };
+
+        let mut has_bridge = false;
+        let mut children = Vec::new();
+        let path_dir = path.parent().unwrap();
+        find_modules(&file.items, dir, path_dir, &mut has_bridge, &mut children);
+        if has_bridge {
+            self.bridges.push(path.to_owned());
+        }
+
+        for (candidates, has_path_attr) in children {
+            let Some(child) = candidates.into_iter().find(|path| fs::exists(path)) else {
+                continue;
+            };
+            let child_dir = if has_path_attr || child.ends_with("mod.rs") {
+                child.parent().unwrap().to_owned()
+            } else {
+                child.with_extension("")
+            };
+            self.visit_file(&child, &child_dir)?;
+        }
+        Some(())
+    }
+}
+
+// Collects the candidate paths of each out-of-line `mod m;` along with whether
+// it was given by a #[path] attribute, in which case the file's children are
+// looked up next to it as if it were a mod.rs. Paths from #[path] are relative
+// to `path_dir`, which differs from `dir` at the top level of a non-mod.rs file.
+fn find_modules(
+    items: &[Item],
+    dir: &Path,
+    path_dir: &Path,
+    has_bridge: &mut bool,
+    children: &mut Vec<(Vec<PathBuf>, bool)>,
+) {
+    for item in items {
+        let Item::Mod(item_mod) = item else {
+            continue;
+        };
+        if item_mod.attrs.iter().any(is_cxx_bridge) {
+            *has_bridge = true;
+            continue;
+        }
+        let name = item_mod.ident.to_string();
+        let path_attr = item_mod.attrs.iter().find_map(path_attr);
+        match &item_mod.content {
+            Some((_brace, content)) => {
+                let dir = match &path_attr {
+                    Some(path) => path_dir.join(path),
+                    None => dir.join(&name),
+                };
+                find_modules(content, &dir, &dir, has_bridge, children);
+            }
+            None => match path_attr {
+                Some(path) => children.push((vec![path_dir.join(path)], true)),
+                None => {
+                    let candidates = vec![
+                        dir.join(format!("{}.rs", name)),
+                        dir.join(&name).join("mod.rs"),
+                    ];
+                    children.push((candidates, false));
+                }
+            },
+        }
+    }
+}
+
+fn is_cxx_bridge(attr: &Attribute) -> bool {
+    let path = &attr.path().segments;
+    path.len() == 2 && path[0].ident == "cxx" && path[1].ident == "bridge"
+}
+
+fn path_attr(attr: &Attribute) -> Option<String> {
+    if !attr.path().is_ident("path") {
+        return None;
+    }
+    match &attr.meta {
+        Meta::NameValue(meta) => match &meta.value {
+            Expr::Lit(expr) => match &expr.lit {
+                Lit::Str(lit) => Some(lit.value()),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1 @@
+../../../macro/src/expand.rs
\ No newline at end of file
@@ -0,0 +1,96 @@
+// Support for `--emit rust`: the input file with every #[cxx::bridge] module
+// replaced by the code that the cxx::bridge attribute macro would have expanded
+// it to, for builds that are unable to run procedural macros.
+
+use crate::expand;
+use crate::gen::error::{print_err, Error, MessageFormat, Result};
+use crate::gen::fs;
+use crate::syntax::file::{BridgeArgs, Includes, Module};
+use crate::syntax::namespace::Namespace;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use std::path::Path;
+use syn::parse::{ParseStream, Parser};
+use syn::{Attribute, Item, Meta};
+
+// Errors have already been printed to stderr if this returns None.
+pub(crate) fn expand_from_path(path: &Path, format: MessageFormat) -> Option<Vec<u8>> {
+    let source = match read_to_string(path) {
+        Ok(source) => source,
+        Err(err) => {
+            print_err(path, "", err, format);
+            return None;
+        }
+    };
+    let dir = if path == Path::new("-") {
+        None
+    } else {
+        path.parent()
+    };
+    match expand_from_string(&source, dir) {
+        Ok(expanded) => Some(expanded.to_string().into_bytes()),
+        Err(err) => {
+            print_err(path, &source, err, format);
+            None
+        }
+    }
+}
+
+fn read_to_string(path: &Path) -> Result<String> {
+    let bytes = if path == Path::new("-") {
+        fs::read_stdin()
+    } else {
+        fs::read(path)
+    }?;
+    match String::from_utf8(bytes) {
+        Ok(string) => Ok(string),
+        Err(err) => Err(Error::Utf8(path.to_owned(), err.utf8_error())),
+    }
+}
+
+fn expand_from_string(source: &str, dir: Option<&Path>) -> Result<TokenStream> {
+    let mut file = syn::parse_file(source)?;
+    let mut found = false;
+    expand_items(&mut file.items, dir, &mut found)?;
+    if !found {
+        return Err(Error::NoBridgeMod);
+    }
+    Ok(file.into_token_stream())
+}
+
+fn expand_items(items: &mut [Item], dir: Option<&Path>, found: &mut bool) -> syn::Result<()> {
+    for item in items {
+        let Item::Mod(item_mod) = item else {
+            continue;
+        };
+        let bridge_attr = item_mod.attrs.iter().position(|attr| {
+            let path = &attr.path().segments;
+            path.len() == 2 && path[0].ident == "cxx" && path[1].ident == "bridge"
+        });
+        if let Some(i) = bridge_attr {
+            let attr = item_mod.attrs.remove(i);
+            let includes = Includes {
+                dir,
+                unknown_dir: false,
+                respan: false,
+            };
+            let parse = |input: ParseStream| Module::parse(input, &includes);
+            let mut module = parse.parse2(item_mod.to_token_stream())?;
+            module.namespace = parse_args(&attr)?;
+            *item = Item::Verbatim(expand::bridge(module)?);
+            *found = true;
+        } else if let Some((_brace, content)) = &mut item_mod.content {
+            expand_items(content, dir, found)?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_args(attr: &Attribute) -> syn::Result<Namespace> {
+    if let Meta::Path(_) = attr.meta {
+        Ok(Namespace::ROOT)
+    } else {
+        attr.parse_args_with(BridgeArgs::parse)
+            .map(|args| args.namespace)
+    }
+}
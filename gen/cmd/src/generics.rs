@@ -0,0 +1 @@
+../../../macro/src/generics.rs
\ No newline at end of file
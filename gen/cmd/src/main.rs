@@ -28,52 +28,95 @@
 
 mod app;
 mod cfg;
+mod derive;
+mod discover;
+mod expand;
+mod expansion;
 mod gen;
+mod generics;
 mod output;
 mod syntax;
+mod tokens;
+mod type_id;
+mod verify;
 
 use crate::cfg::{CfgValue, FlagsCfgEvaluator};
-use crate::gen::error::{report, Result};
-use crate::gen::fs;
+use crate::gen::error::{print_err, MessageFormat, Result};
 use crate::gen::include::{self, Include};
+use crate::gen::{fs, GeneratedCode};
 use crate::output::Output;
+use crate::syntax::namespace::Namespace;
 use std::collections::{BTreeMap as Map, BTreeSet as Set};
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 use std::process;
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 #[derive(Debug)]
 struct Opt {
     input: Option<PathBuf>,
+    abi_diff: Option<PathBuf>,
+    manifest_path: Option<PathBuf>,
+    out_dir: Option<PathBuf>,
     header: bool,
+    emit: Option<Emit>,
     cxx_impl_annotations: Option<String>,
     include: Vec<Include>,
+    include_rewrites: Vec<(String, String)>,
+    namespace_map: Vec<(Namespace, Namespace)>,
     outputs: Vec<Output>,
     cfg: Map<String, Set<CfgValue>>,
+    cfg_is_complete: bool,
+    watch: bool,
+    verify: bool,
+    lint: bool,
+    clang_args: Vec<String>,
+    message_format: MessageFormat,
 }
 
 fn main() {
-    if let Err(err) = try_main() {
-        let _ = writeln!(io::stderr(), "cxxbridge: {}", report(err));
+    let opt = app::from_args();
+    let message_format = opt.message_format;
+    if let Err(err) = try_main(opt) {
+        print_err(Path::new(""), "", err, message_format);
         process::exit(1);
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+enum Emit {
+    Symbols,
+    Rust,
+    Stubs,
+    Mocks,
+    Docs,
+    Fuzz,
+}
+
 enum Kind {
     GeneratedHeader,
     GeneratedImplementation,
     Header,
+    Emitted,
 }
 
-fn try_main() -> Result<()> {
-    let opt = app::from_args();
+#[derive(Default)]
+struct Generated {
+    code: GeneratedCode,
+    emitted: Vec<u8>,
+    included_files: Vec<PathBuf>,
+}
 
+fn try_main(opt: Opt) -> Result<()> {
     let mut outputs = Vec::new();
     let mut gen_header = false;
     let mut gen_implementation = false;
     for output in opt.outputs {
         let kind = if opt.input.is_none() {
             Kind::Header
+        } else if opt.emit.is_some_and(|emit| !matches!(emit, Emit::Mocks)) {
+            Kind::Emitted
         } else if opt.header
             || output.ends_with(".h")
             || output.ends_with(".hh")
@@ -88,32 +131,211 @@ fn try_main() -> Result<()> {
         outputs.push((output, kind));
     }
 
+    if opt.manifest_path.is_some() {
+        gen_header = true;
+        gen_implementation = true;
+    }
+
     let gen = gen::Opt {
         include: opt.include,
         cxx_impl_annotations: opt.cxx_impl_annotations,
         gen_header,
         gen_implementation,
-        cfg_evaluator: Box::new(FlagsCfgEvaluator::new(opt.cfg)),
+        cfg_evaluator: Box::new(FlagsCfgEvaluator::new(opt.cfg, opt.cfg_is_complete)),
+        namespace_map: opt.namespace_map,
+        include_rewrites: opt.include_rewrites,
+        message_format: opt.message_format,
         ..Default::default()
     };
 
-    let generated_code = if let Some(input) = opt.input {
-        gen::generate_from_path(&input, &gen)
+    if let Some(old) = opt.abi_diff {
+        let new = opt.input.unwrap();
+        let (report, breaking) =
+            gen::abi_diff_from_paths(&old, &new, &gen).unwrap_or_else(|| process::exit(1));
+        drop(io::stdout().write_all(&report));
+        if breaking {
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if opt.lint {
+        let (bridges, sources) = match (opt.input, opt.manifest_path) {
+            (Some(input), _) => (vec![input], Vec::new()),
+            (None, Some(manifest_path)) => discover::sources(&manifest_path, gen.message_format)
+                .unwrap_or_else(|| process::exit(1)),
+            (None, None) => unreachable!(),
+        };
+        return lint(&bridges, &sources, &gen);
+    }
+
+    if let Some(manifest_path) = opt.manifest_path {
+        let out_dir = opt.out_dir.unwrap();
+        return generate_crate(&manifest_path, &out_dir, &gen);
+    }
+
+    if opt.verify {
+        let input = opt.input.unwrap();
+        let generated_code = gen::generate_from_path(&input, &gen);
+        return verify::verify(&generated_code.code.implementation, &opt.clang_args);
+    }
+
+    if opt.watch {
+        let input = opt.input.unwrap();
+        return watch(&input, &gen, opt.emit, &outputs);
+    }
+
+    let generated = if let Some(input) = opt.input {
+        generate(&input, &gen, opt.emit).unwrap_or_else(|| process::exit(1))
     } else {
         Default::default()
     };
 
+    write_outputs(&outputs, &generated, false)
+}
+
+// Errors have already been printed to stderr if this returns None.
+fn generate(input: &Path, gen: &gen::Opt, emit: Option<Emit>) -> Option<Generated> {
+    let mut generated = Generated::default();
+    match emit {
+        None => {
+            let gen::Generated {
+                code,
+                included_files,
+            } = gen::try_generate_from_path(input, gen)?;
+            generated.code = code;
+            generated.included_files = included_files;
+        }
+        Some(Emit::Symbols) => generated.emitted = gen::symbols_from_path(input, gen)?,
+        Some(Emit::Rust) => {
+            generated.emitted = expansion::expand_from_path(input, gen.message_format)?
+        }
+        Some(Emit::Stubs) => generated.emitted = gen::stubs_from_path(input, gen)?,
+        Some(Emit::Mocks) => generated.code = gen::mocks_from_path(input, gen)?,
+        Some(Emit::Docs) => generated.emitted = gen::docs_from_path(input, gen)?,
+        Some(Emit::Fuzz) => generated.emitted = gen::fuzz_from_path(input, gen)?,
+    }
+    Some(generated)
+}
+
+fn generate_crate(manifest_path: &Path, out_dir: &Path, gen: &gen::Opt) -> Result<()> {
+    // Cargo reports the targets by absolute path. A bridge outside of the
+    // crate's directory is written under its own absolute path in `out_dir`.
+    let crate_dir = match manifest_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let crate_dir = std::fs::canonicalize(crate_dir).unwrap_or_else(|_| crate_dir.to_owned());
+    let mut failed = false;
+    let bridges =
+        discover::bridges(manifest_path, gen.message_format).unwrap_or_else(|| process::exit(1));
+    for input in bridges {
+        let Some(generated) = gen::try_generate_from_path(&input, gen) else {
+            failed = true;
+            continue;
+        };
+        let relative = match input.strip_prefix(&crate_dir) {
+            Ok(relative) => relative.to_owned(),
+            Err(_) => input
+                .components()
+                .filter(|component| matches!(component, Component::Normal(_)))
+                .collect(),
+        };
+        let output = out_dir.join(relative);
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut header = output.clone().into_os_string();
+        header.push(".h");
+        fs::write(header, &generated.code.header)?;
+        let mut implementation = output.into_os_string();
+        implementation.push(".cc");
+        fs::write(implementation, &generated.code.implementation)?;
+    }
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+// Exits with failure if any bridge has errors or lints, all of which have
+// already been printed to stderr.
+fn lint(bridges: &[PathBuf], sources: &[PathBuf], gen: &gen::Opt) -> Result<()> {
+    let mut failed = false;
+    for bridge in bridges {
+        match gen::lint_from_path(bridge, sources, gen) {
+            Some(0) => {}
+            Some(_) | None => failed = true,
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn write_outputs(
+    outputs: &[(Output, Kind)],
+    generated: &Generated,
+    skip_unchanged: bool,
+) -> Result<()> {
     for (output, kind) in outputs {
         let content = match kind {
-            Kind::GeneratedHeader => &generated_code.header,
-            Kind::GeneratedImplementation => &generated_code.implementation,
+            Kind::GeneratedHeader => &generated.code.header,
+            Kind::GeneratedImplementation => &generated.code.implementation,
             Kind::Header => include::HEADER.as_bytes(),
+            Kind::Emitted => &generated.emitted,
         };
         match output {
             Output::Stdout => drop(io::stdout().write_all(content)),
-            Output::File(path) => fs::write(path, content)?,
+            Output::File(path) => {
+                // Leave the file untouched if nothing changed, so as not to
+                // trigger a rebuild of everything that includes it.
+                if skip_unchanged && fs::read(path).is_ok_and(|prev| prev == *content) {
+                    continue;
+                }
+                fs::write(path, content)?;
+            }
         }
     }
-
     Ok(())
 }
+
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+fn watch(
+    input: &Path,
+    gen: &gen::Opt,
+    emit: Option<Emit>,
+    outputs: &[(Output, Kind)],
+) -> Result<()> {
+    // The input, followed by the files that its bridges include items from as
+    // of the last successful generation.
+    let mut watched = vec![input.to_owned()];
+    let mut last_modified = Vec::new();
+    loop {
+        // The input may briefly disappear while an editor is saving it, in
+        // which case just try again on the next tick.
+        let times: Vec<Option<SystemTime>> = watched.iter().map(|path| modified(path)).collect();
+        if times[0].is_some() && times != last_modified {
+            if let Some(generated) = generate(input, gen, emit) {
+                write_outputs(outputs, &generated, true)?;
+                let _ = writeln!(
+                    io::stderr(),
+                    "cxxbridge: generated from {}",
+                    input.display()
+                );
+                watched.truncate(1);
+                watched.extend(generated.included_files.iter().cloned());
+            }
+            last_modified = watched.iter().map(|path| modified(path)).collect();
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
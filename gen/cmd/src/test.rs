@@ -7,16 +7,28 @@ Usage:
     cxxbridge <input>.rs              Emit .cc file for bridge to stdout
     cxxbridge <input>.rs --header     Emit .h file for bridge to stdout
     cxxbridge --header                Emit \"rust/cxx.h\" header to stdout
+    cxxbridge <input>.rs --lint       Warn about suspicious bridge declarations
 
 Arguments:
   [input]
           Input Rust source file containing #[cxx::bridge].
 
 Options:
+      --abi-diff <old.rs>
+          Instead of generating code, compare the bridge in the input file
+          against an older version of it, and print the functions, types,
+          and symbols that were added or removed or whose signature or
+          layout changed. Exits with failure if any change would break C++
+          or Rust code built against the old version.
+
       --cfg <name=\"value\" | name[=true] | name=false>
           Compilation configuration matching what will be used to build
           the Rust side of the bridge.
 
+      --clang-arg <arg>
+          Additional argument to pass to the C++ compiler for --verify,
+          such as -I or -std flags needed to parse the C++ headers.
+
       --cxx-impl-annotations <annotation>
           Optional annotation for implementations of C++ function wrappers
           that may be exposed to Rust. You may for example need to provide
@@ -24,6 +36,29 @@ Options:
           if Rust code from one shared object or executable depends on
           these C++ functions in another.
 
+      --emit <kind>
+          Emit something other than C++ code for the bridge. `symbols`
+          lists every extern \"C\" symbol through which the C++ and Rust
+          sides call each other, one per line, each preceded by `cxx` or
+          `rust` according to which side defines it. `rust` prints the
+          input file with each bridge module replaced by the code that
+          the #[cxx::bridge] macro would expand it to. `stubs` writes a
+          skeleton .cc defining every function of the extern \"C++\"
+          blocks, to fill in when starting the C++ side of a new bridge.
+          It includes the bridge's generated header by the input path with
+          .h appended, such as src/lib.rs.h.
+          `mocks` writes gMock classes for the extern \"Rust\" blocks that
+          have a #[mock] attribute, as a header with `--header` or `-o`
+          with a path ending in `.h` and otherwise as a .cc defining the
+          functions to call the mocks. Pass the bridge's generated header
+          with `--include`.
+          `docs` prints a Markdown reference of the bridge's types and
+          functions with their Rust and C++ signatures, ownership of the
+          arguments and return values, and doc comments.
+          `fuzz` writes a cargo-fuzz target that calls the extern \"C++\"
+          functions with arguments from the `arbitrary` crate, for shared
+          types deriving `Arbitrary` and Rust's own types.
+
       --header
           Emit header with declarations only. Optional if using `-o` with
           a path ending in `.h`.
@@ -36,10 +71,70 @@ Options:
           parse or even require the given paths to exist; they simply go
           into the generated C++ code as #include lines.
 
+      --include-prefix <prefix>
+          Prefix to prepend to the path of every quoted #include in the
+          generated code, for when headers are installed under a different
+          layout than the one used at build time. Paths matched by a
+          --rewrite-include are not additionally prefixed.
+
+      --lint
+          Instead of writing any output, print warnings about extern C++
+          declarations never used from Rust, C++ entities bound more than
+          once, cxx_name or rust_name renames that only change case, and
+          signatures that let safe Rust create aliasing or unpinned mutable
+          references, along with the declaration to use instead. Uses are
+          searched for in the input file, or in every file of the crate with
+          --manifest-path. Exits with failure if any are found.
+
+      --manifest-path <Cargo.toml>
+          Generate code for every bridge in a crate instead of a single
+          input file. Bridges are found by following `mod` declarations
+          from the lib and bin targets of the given Cargo.toml, as listed
+          by `cargo metadata`, or of every member of a workspace. Requires
+          --out-dir, unless used with --lint.
+
+      --map-namespace <old::path=new::path>
+          Emit C++ items that the bridge places in namespace `old` or any
+          namespace nested in it under `new` instead. The symbols that
+          link the C++ side with the Rust side are unaffected.
+
+      --message-format <human|json>
+          Format of the errors printed to stderr. With `json`, each error
+          is a single line JSON object with the file, message, span, and
+          suggestion if any, for consumption by build systems and IDEs.
+
+      --out-dir <dir>
+          Directory to write the outputs of --manifest-path into. Each
+          bridge `src/path.rs` produces `src/path.rs.h` and
+          `src/path.rs.cc` under this directory.
+
   -o, --output <output>
           Path of file to write as output. Output goes to stdout if -o is
           not specified.
 
+      --rewrite-include <old=new>
+          Replace the leading `old` portion of the path of any quoted
+          #include in the generated code with `new`. If more than one
+          rewrite matches, the longest `old` wins.
+
+      --target <triple>
+          Target triple that the Rust side of the bridge will be built for.
+          Cfgs implied by the target such as target_os and unix are enabled
+          the same way that Cargo enables them for build scripts, and any
+          cfg not implied by the target or passed via --cfg is disabled.
+          The target's cfgs are obtained from $RUSTC or rustc.
+
+      --verify
+          Instead of writing any output, check that the extern \"C++\"
+          declarations of the bridge match the C++ headers it includes, by
+          parsing the generated code with clang++ (or $CXX). Mismatched
+          names, arity, constness, or types are reported as errors.
+
+      --watch
+          Keep running after the first generation, regenerating the outputs
+          whenever the input file is modified. Errors are reported without
+          exiting, and outputs whose content is unchanged are not rewritten.
+
       --version
           Print version information.
 ";
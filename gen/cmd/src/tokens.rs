@@ -0,0 +1 @@
+../../../macro/src/tokens.rs
\ No newline at end of file
@@ -0,0 +1 @@
+../../../macro/src/type_id.rs
\ No newline at end of file
@@ -0,0 +1,90 @@
+// Support for `--verify`: checks the bridge's extern "C++" declarations against
+// the real C++ headers by running the generated implementation file through the
+// clang frontend in syntax-only mode.
+//
+// Every shim in the generated code binds the C++ function it wraps to a
+// function pointer or pointer-to-member of exactly the type implied by the
+// bridge, so any difference in name, arity, constness, or parameter and return
+// types is reported as a compile error here rather than surfacing later as a
+// linker error or undefined behavior.
+//
+// This deliberately runs the C++ compiler instead of walking the headers with
+// libclang. It needs no libclang at build or run time, works with any compiler
+// that has -fsyntax-only, and cannot disagree with the compiler about overload
+// resolution or templates. The cost is that only what the generated code uses
+// is checked: opaque types only need to exist, and the errors are the
+// compiler's own rather than a list of mismatched declarations.
+
+use crate::gen::error::Result;
+use crate::gen::{fs, include};
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+
+pub(crate) fn verify(implementation: &[u8], clang_args: &[String]) -> Result<()> {
+    let dir = TempDir(env::temp_dir().join(format!("cxxbridge-verify-{}", process::id())));
+    let rust_dir = dir.0.join("rust");
+    fs::create_dir_all(&rust_dir)?;
+    fs::write(rust_dir.join("cxx.h"), include::HEADER)?;
+
+    let status = compile(implementation, clang_args, dir.0.as_os_str());
+    drop(dir);
+
+    match status {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            let _ = writeln!(
+                io::stderr(),
+                "cxxbridge: bridge does not match the C++ declarations",
+            );
+            process::exit(1);
+        }
+        Err((compiler, err)) => {
+            let _ = writeln!(
+                io::stderr(),
+                "cxxbridge: failed to run {}: {}",
+                compiler.to_string_lossy(),
+                err,
+            );
+            process::exit(1);
+        }
+    }
+}
+
+// Removed along with its contents when dropped, including on the early return
+// of an error in setting it up.
+struct TempDir(PathBuf);
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn compile(
+    implementation: &[u8],
+    clang_args: &[String],
+    include_dir: &OsStr,
+) -> Result<bool, (OsString, io::Error)> {
+    let compiler = env::var_os("CXX").unwrap_or_else(|| OsString::from("clang++"));
+    let mut include_flag = OsString::from("-I");
+    include_flag.push(include_dir);
+
+    let mut child = Command::new(&compiler)
+        .arg("-fsyntax-only")
+        .arg(include_flag)
+        .args(clang_args)
+        .args(["-x", "c++", "-"])
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|err| (compiler.clone(), err))?;
+
+    let mut stdin = child.stdin.take().unwrap();
+    let written = stdin.write_all(implementation);
+    drop(stdin);
+    let status = child.wait().map_err(|err| (compiler.clone(), err))?;
+    written.map_err(|err| (compiler, err))?;
+    Ok(status.success())
+}
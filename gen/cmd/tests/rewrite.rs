@@ -0,0 +1,80 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const BRIDGE: &str = r#"
+#[cxx::bridge(namespace = "corp::engine")]
+mod ffi {
+    struct Point {
+        x: i32,
+    }
+    unsafe extern "C++" {
+        include!("engine/core.h");
+        include!("engine/detail/impl.h");
+        include!(<vector>);
+        fn center() -> Point;
+    }
+}
+"#;
+
+fn generate(args: &[&str]) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cxxbridge"))
+        .arg("-")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(BRIDGE.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_map_namespace() {
+    let header = generate(&["--header", "--map-namespace", "corp=product::vendor"]);
+    assert!(header.contains("namespace product {\nnamespace vendor {\nnamespace engine {\n"));
+    assert!(header.contains("struct Point final {"));
+    assert!(!header.contains("namespace corp"));
+
+    let implementation = generate(&["--map-namespace", "corp=product::vendor"]);
+    assert!(implementation.contains("::product::vendor::engine::Point"));
+    assert!(!implementation.contains("::corp::"));
+    // The symbols still have to agree with the Rust side of the bridge.
+    assert!(implementation.contains("void corp$engine$cxxbridge1$center("));
+
+    // The longest matching namespace wins.
+    let header = generate(&[
+        "--header",
+        "--map-namespace",
+        "corp=product",
+        "--map-namespace",
+        "corp::engine=core",
+    ]);
+    assert!(header.contains("namespace core {\n"));
+    assert!(!header.contains("namespace product"));
+}
+
+#[test]
+fn test_rewrite_include() {
+    let header = generate(&["--header"]);
+    assert!(header.contains("#include \"engine/core.h\"\n"));
+    assert!(header.contains("#include \"engine/detail/impl.h\"\n"));
+
+    let header = generate(&[
+        "--header",
+        "--include-prefix",
+        "sdk/",
+        "--rewrite-include",
+        "engine/detail/=private/",
+    ]);
+    assert!(header.contains("#include \"sdk/engine/core.h\"\n"));
+    assert!(header.contains("#include \"private/impl.h\"\n"));
+    assert!(header.contains("#include <vector>\n"));
+    assert!(!header.contains("\"engine/"));
+}
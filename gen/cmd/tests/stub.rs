@@ -0,0 +1,50 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const BRIDGE: &str = r#"
+#[cxx::bridge]
+mod ffi {
+    extern "Rust" {
+        fn square(n: u32) -> u32;
+    }
+}
+"#;
+
+const STUB_LINKED: &str = "cxxbridge1$stub$CXXBRIDGE_STUB_is_set_in_a_build_that_links";
+
+fn expand(stub: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cxxbridge"))
+        .args(["-", "--emit", "rust"])
+        .env("CXXBRIDGE_STUB", stub)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(BRIDGE.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_stub() {
+    let expanded = expand("1");
+    assert!(expanded.contains("\"cxxbridge1$square\""));
+    assert!(expanded.contains(STUB_LINKED));
+    assert!(!expanded.contains("prevent_unwind"));
+    assert!(expanded.contains("option_env ! (\"CXXBRIDGE_STUB\")"));
+}
+
+#[test]
+fn test_no_stub() {
+    let expanded = expand("0");
+    assert!(expanded.contains("\"cxxbridge1$square\""));
+    assert!(!expanded.contains(STUB_LINKED));
+    assert!(expanded.contains("prevent_unwind"));
+    assert!(expanded.contains("option_env ! (\"CXXBRIDGE_STUB\")"));
+}
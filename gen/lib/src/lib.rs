@@ -45,7 +45,7 @@ mod syntax;
 
 pub use crate::error::Error;
 pub use crate::gen::include::{Include, HEADER};
-pub use crate::gen::{CfgEvaluator, CfgResult, GeneratedCode, Opt};
+pub use crate::gen::{CfgEvaluator, CfgResult, GeneratedCode, GeneratedSections, Opt, Sections};
 pub use crate::syntax::IncludeKind;
 use proc_macro2::TokenStream;
 use std::path::Path;
@@ -56,11 +56,23 @@ pub fn generate_header_and_cc(rust_source: TokenStream, opt: &Opt) -> Result<Gen
     let syntax = syn::parse2(rust_source)
         .map_err(crate::gen::Error::from)
         .map_err(Error::from)?;
-    gen::generate(syntax, opt).map_err(Error::from)
+    gen::generate(syntax, opt)
+        .map(|generated| generated.code)
+        .map_err(Error::from)
 }
 
 /// Generate C++ bindings code from a file.
 /// This should be a Rust file containing a `#[cxx::bridge] mod {}`.
 pub fn generate_header_and_cc_with_path<P: AsRef<Path>>(path: P, opt: &Opt) -> GeneratedCode {
-    gen::generate_from_path(path.as_ref(), opt)
+    gen::generate_from_path(path.as_ref(), opt).code
+}
+
+/// Generate the same C++ code as [`generate_header_and_cc`], split into
+/// [`Sections`] for build systems that want to put together their own file
+/// layout out of the generated code.
+pub fn generate_sections(rust_source: TokenStream, opt: &Opt) -> Result<GeneratedSections, Error> {
+    let syntax = syn::parse2(rust_source)
+        .map_err(crate::gen::Error::from)
+        .map_err(Error::from)?;
+    gen::generate_sections(syntax, opt).map_err(Error::from)
 }
@@ -0,0 +1,14 @@
+// Included by the bridge in test_include.
+
+#![namespace = "demo::audio"]
+
+include!("device.rs");
+
+unsafe extern "C++" {
+    type Player;
+    fn play(self: Pin<&mut Player>, volume: f32);
+}
+
+extern "Rust" {
+    fn volume() -> f32;
+}
@@ -0,0 +1,5 @@
+// Included by audio.rs, relative to that file.
+
+extern "Rust" {
+    fn device_count() -> usize;
+}
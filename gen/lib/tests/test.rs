@@ -1,5 +1,6 @@
-use cxx_gen::Opt;
+use cxx_gen::{IncludeKind, Opt};
 use quote::quote;
+use std::path::Path;
 
 #[test]
 fn test_positive() {
@@ -26,3 +27,86 @@ fn test_negative() {
     let opt = Opt::default();
     assert!(cxx_gen::generate_header_and_cc(rs, &opt).is_err());
 }
+
+#[test]
+fn test_sections() {
+    let rs = quote! {
+        #[cxx::bridge(namespace = "demo")]
+        mod ffi {
+            struct Point {
+                x: i32,
+                y: i32,
+            }
+            unsafe extern "C++" {
+                include!("demo/include/point.h");
+                fn origin() -> Point;
+            }
+            extern "Rust" {
+                fn norm(point: &Point) -> f64;
+            }
+        }
+    };
+    let opt = Opt::default();
+    let generated = cxx_gen::generate_sections(rs.clone(), &opt).unwrap();
+    let sections = &generated.header;
+
+    assert_eq!(sections.includes.len(), 1);
+    assert_eq!(sections.includes[0].path, "demo/include/point.h");
+    assert_eq!(sections.includes[0].kind, IncludeKind::Quoted);
+
+    let standard_includes = String::from_utf8_lossy(&sections.standard_includes);
+    assert_eq!(
+        standard_includes,
+        "#include <cstdint>\n#include <type_traits>\n"
+    );
+
+    let forward_declarations = String::from_utf8_lossy(&sections.forward_declarations);
+    assert!(forward_declarations.contains("struct Point;"));
+    assert!(forward_declarations.trim_end().ends_with('}'));
+
+    let declarations = String::from_utf8_lossy(&sections.declarations);
+    assert!(declarations.contains("struct Point final {"));
+    assert!(declarations.contains("} // namespace demo"));
+
+    let definitions = String::from_utf8_lossy(&sections.definitions);
+    assert!(definitions.contains("double norm(::demo::Point const &point) noexcept;"));
+    assert!(definitions.contains("} // namespace demo"));
+
+    let implementation = String::from_utf8_lossy(&generated.implementation.definitions);
+    assert!(implementation.contains("demo$cxxbridge1$origin"));
+
+    let code = cxx_gen::generate_header_and_cc(rs, &opt).unwrap();
+    let header = String::from_utf8_lossy(&code.header);
+    assert!(header.starts_with("#pragma once\n#include \"demo/include/point.h\"\n"));
+    assert!(header.contains("struct Point final {"));
+}
+
+#[test]
+fn test_include() {
+    let rs = quote! {
+        #[cxx::bridge(namespace = "demo")]
+        mod ffi {
+            include!("tests/bridge/audio.rs");
+
+            extern "Rust" {
+                fn muted(player: &Player) -> bool;
+            }
+        }
+    };
+    let opt = Opt::default();
+    let code = cxx_gen::generate_header_and_cc(rs.clone(), &opt).unwrap();
+
+    let header = String::from_utf8_lossy(&code.header);
+    assert!(header.contains("namespace audio {"));
+    assert!(header.contains("float volume() noexcept;"));
+    assert!(header.contains("bool muted(::demo::audio::Player const &player) noexcept;"));
+    assert!(header.contains("::std::size_t device_count() noexcept;"));
+
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let bridge = manifest_dir.join("tests").join("bridge");
+    let generated = cxx_gen::generate_sections(rs, &opt).unwrap();
+    assert_eq!(
+        generated.included_files,
+        [bridge.join("audio.rs"), bridge.join("device.rs")],
+    );
+}
@@ -0,0 +1,334 @@
+// Comparison of two versions of a bridge, for `cxxbridge --abi-diff`. Each
+// version is reduced to a set of entities that compiled code on the other side
+// of the bridge depends on, every one with a description of its shape. Types
+// go by their C++ name, since renaming only the Rust side of a type does not
+// affect what gets linked. Functions go by their Rust name, which is what their
+// symbol is derived from.
+//
+// An entity which is removed or whose shape changes breaks code built against
+// the old version. An added entity does not.
+
+use crate::gen::Opt;
+use crate::syntax::atom::Atom;
+use crate::syntax::{mangle, Api, ExternFn, Lang, Pair, Signature, Type, Types};
+use std::collections::BTreeMap as Map;
+use std::fmt::Write;
+
+pub(super) struct Abi {
+    // Keyed by symbol for the things that have one, and otherwise by what.
+    entities: Map<String, Entity>,
+}
+
+struct Entity {
+    what: String,
+    shape: String,
+}
+
+pub(super) fn describe(apis: &[Api], types: &Types, opt: &Opt) -> Abi {
+    let mut abi = Abi {
+        entities: Map::new(),
+    };
+
+    // Symbols which are not declared directly by the bridge, such as those of
+    // the UniquePtr<T> and Vec<T> instantiations, are only compared by whether
+    // they exist. Functions below replace the entry of their own symbol.
+    let manifest = super::symbols::gen(apis, types);
+    for line in String::from_utf8(manifest).unwrap().lines() {
+        let (side, symbol) = line.split_once(' ').unwrap();
+        let shape = match side {
+            "cxx" => "defined by C++",
+            _ => "defined by Rust",
+        };
+        abi.insert(symbol.to_owned(), format!("symbol {}", symbol), shape);
+    }
+
+    for api in apis {
+        match api {
+            Api::Struct(strct) => {
+                let mut shape = String::new();
+                if let Some(align) = &strct.align {
+                    let _ = write!(shape, "align({}) ", align);
+                }
+                shape += "{";
+                for (i, field) in strct.fields.iter().enumerate() {
+                    if i > 0 {
+                        shape += ", ";
+                    }
+                    shape += &describe_type(&field.ty, types, opt);
+                }
+                shape += "}";
+                let what = format!("struct {}", qualified(&strct.name, opt));
+                abi.insert(what.clone(), what, &shape);
+            }
+            Api::Enum(enm) => {
+                let name = qualified(&enm.name, opt);
+                let what = format!("enum {}", name);
+                abi.insert(what.clone(), what, enm.repr.atom.as_ref());
+                for variant in &enm.variants {
+                    let what = format!("variant {}::{}", name, variant.name.cxx);
+                    abi.insert(what.clone(), what, &variant.discriminant.to_string());
+                }
+            }
+            Api::CxxType(ety) | Api::RustType(ety) => {
+                let what = format!("type {}", qualified(&ety.name, opt));
+                abi.insert(what.clone(), what, lang(ety.lang));
+            }
+            Api::TypeAlias(alias) => {
+                let what = format!("type {}", qualified(&alias.name, opt));
+                abi.insert(what.clone(), what, lang(Lang::Cxx));
+            }
+            Api::CxxFunction(efn) | Api::RustFunction(efn) => {
+                let symbol = mangle::extern_fn(efn, types).to_string();
+                let what = format!("fn {}", function_name(efn, types, opt));
+                let shape = format!(
+                    "{} {}",
+                    lang(efn.lang),
+                    describe_signature(&efn.sig, types, opt),
+                );
+                abi.insert(symbol, what, &shape);
+            }
+            Api::Include(_) | Api::Impl(_) => {}
+        }
+    }
+
+    abi
+}
+
+impl Abi {
+    fn insert(&mut self, key: String, what: String, shape: &str) {
+        let shape = shape.to_owned();
+        self.entities.insert(key, Entity { what, shape });
+    }
+}
+
+// Returns the report, one change per line, and whether any of the changes are
+// breaking.
+pub(super) fn diff(old: &Abi, new: &Abi) -> (Vec<u8>, bool) {
+    let mut out = String::new();
+    let mut breaking = false;
+    for (key, old) in &old.entities {
+        match new.entities.get(key) {
+            None => {
+                breaking = true;
+                let _ = writeln!(out, "breaking: removed {}: {}", old.what, old.shape);
+            }
+            Some(new) if new.shape != old.shape => {
+                breaking = true;
+                let _ = writeln!(
+                    out,
+                    "breaking: changed {} from `{}` to `{}`",
+                    new.what, old.shape, new.shape,
+                );
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, new) in &new.entities {
+        if !old.entities.contains_key(key) {
+            let _ = writeln!(out, "added {}: {}", new.what, new.shape);
+        }
+    }
+    (out.into_bytes(), breaking)
+}
+
+fn lang(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Cxx | Lang::CxxUnwind => "extern \"C++\"",
+        Lang::Rust => "extern \"Rust\"",
+    }
+}
+
+fn qualified(name: &Pair, opt: &Opt) -> String {
+    name.to_fully_qualified(opt)
+        .trim_start_matches("::")
+        .to_owned()
+}
+
+fn function_name(efn: &ExternFn, types: &Types, opt: &Opt) -> String {
+    match efn.self_type() {
+        Some(self_type) => {
+            let self_type = qualified(types.resolve(self_type).name, opt);
+            format!("{}::{}", self_type, efn.name.rust)
+        }
+        None => {
+            let namespace = qualified(&efn.name, opt);
+            let namespace = namespace.rsplit_once("::").map(|(namespace, _)| namespace);
+            match namespace {
+                Some(namespace) => format!("{}::{}", namespace, efn.name.rust),
+                None => efn.name.rust.to_string(),
+            }
+        }
+    }
+}
+
+fn describe_signature(sig: &Signature, types: &Types, opt: &Opt) -> String {
+    let mut out = String::from("fn(");
+    let mut first = true;
+    if let Some(receiver) = sig.receiver() {
+        let self_type = qualified(types.resolve(&receiver.ty).name, opt);
+        let _ = match (receiver.pinned, receiver.mutable) {
+            (true, _) => write!(out, "self: Pin<&mut {}>", self_type),
+            (false, true) => write!(out, "self: &mut {}", self_type),
+            (false, false) => write!(out, "self: &{}", self_type),
+        };
+        first = false;
+    }
+    for arg in &sig.args {
+        if !first {
+            out += ", ";
+        }
+        out += &describe_type(&arg.ty, types, opt);
+        first = false;
+    }
+    out += ")";
+    let ret = match &sig.ret {
+        Some(ret) => describe_type(ret, types, opt),
+        None => "()".to_owned(),
+    };
+    if sig.hresult.is_some() {
+        let _ = write!(out, " -> Result<{}, HRESULT>", ret);
+    } else if sig.throws {
+        let _ = write!(out, " -> Result<{}>", ret);
+    } else if sig.ret.is_some() {
+        let _ = write!(out, " -> {}", ret);
+    }
+    out
+}
+
+fn describe_type(ty: &Type, types: &Types, opt: &Opt) -> String {
+    match ty {
+        Type::Ident(ident) => match Atom::from(&ident.rust) {
+            Some(atom) => atom.to_string(),
+            None => qualified(types.resolve(ident).name, opt),
+        },
+        Type::RustBox(ty)
+        | Type::RustVec(ty)
+        | Type::UniquePtr(ty)
+        | Type::UniqueArray(ty)
+        | Type::SharedPtr(ty)
+        | Type::SharedArray(ty)
+        | Type::SharedConstPtr(ty)
+        | Type::WeakPtr(ty)
+        | Type::CxxVector(ty)
+        | Type::CxxPmrVector(ty)
+        | Type::ComPtr(ty) => format!("{}<{}>", ty.name, describe_type(&ty.inner, types, opt)),
+        Type::Ref(ty) => {
+            let inner = describe_type(&ty.inner, types, opt);
+            match (ty.pinned, ty.mutable) {
+                (true, _) => format!("Pin<&mut {}>", inner),
+                (false, true) => format!("&mut {}", inner),
+                (false, false) => format!("&{}", inner),
+            }
+        }
+        Type::Ptr(ty) => {
+            let inner = describe_type(&ty.inner, types, opt);
+            match ty.mutable {
+                true => format!("*mut {}", inner),
+                false => format!("*const {}", inner),
+            }
+        }
+        Type::Str(ty) => match ty.mutable {
+            true => "&mut str".to_owned(),
+            false => "&str".to_owned(),
+        },
+        Type::Fn(sig) => describe_signature(sig, types, opt),
+        Type::Void(_) => "()".to_owned(),
+        Type::SliceRef(ty) => {
+            let inner = describe_type(&ty.inner, types, opt);
+            match ty.mutable {
+                true => format!("&mut [{}]", inner),
+                false => format!("&[{}]", inner),
+            }
+        }
+        Type::Array(ty) => format!("[{}; {}]", describe_type(&ty.inner, types, opt), ty.len),
+        Type::HashMapRef(ty) => format!(
+            "&HashMap<{}, {}>",
+            describe_type(&ty.key, types, opt),
+            describe_type(&ty.value, types, opt),
+        ),
+        Type::CustomPtr(ty) => format!(
+            "CustomPtr<{}, {}>",
+            qualified(types.resolve(&ty.family).name, opt),
+            describe_type(&ty.inner, types, opt),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::{self, abi, file::File, Opt};
+
+    fn describe(source: &str) -> abi::Abi {
+        let syntax: File = syn::parse_str(source).unwrap();
+        let opt = Opt::default();
+        gen::analyze(syntax, &opt, |apis, types| abi::describe(apis, types, &opt)).unwrap()
+    }
+
+    #[test]
+    fn test_abi_diff() {
+        let old = describe(
+            r#"
+            #[cxx::bridge(namespace = "plugin")]
+            mod ffi {
+                struct Point {
+                    x: i32,
+                    y: i32,
+                }
+
+                enum Mode {
+                    Fast,
+                    Slow,
+                }
+
+                unsafe extern "C++" {
+                    type Engine;
+                    fn start(self: Pin<&mut Engine>, mode: Mode);
+                    fn locate(engine: &Engine) -> Point;
+                    fn legacy();
+                }
+            }
+            "#,
+        );
+        let new = describe(
+            r#"
+            #[cxx::bridge(namespace = "plugin")]
+            mod ffi {
+                struct Point {
+                    x: i64,
+                    y: i32,
+                }
+
+                enum Mode {
+                    Fast,
+                    Slow,
+                    Balanced,
+                }
+
+                unsafe extern "C++" {
+                    type Engine;
+                    fn start(self: Pin<&mut Engine>, mode: Mode);
+                    fn locate(engine: &Engine) -> Point;
+                    fn stop(engine: Pin<&mut Engine>);
+                }
+            }
+            "#,
+        );
+
+        let (report, breaking) = abi::diff(&old, &new);
+        let report = String::from_utf8(report).unwrap();
+        assert!(breaking);
+        assert_eq!(
+            report,
+            "\
+breaking: removed fn plugin::legacy: extern \"C++\" fn()
+breaking: changed struct plugin::Point from `{i32, i32}` to `{i64, i32}`
+added fn plugin::stop: extern \"C++\" fn(Pin<&mut plugin::Engine>)
+added variant plugin::Mode::Balanced: 2
+",
+        );
+
+        let (report, breaking) = abi::diff(&old, &old);
+        assert!(report.is_empty());
+        assert!(!breaking);
+    }
+}
@@ -9,8 +9,10 @@ pub(crate) struct Builtins<'a> {
     pub rust_str: bool,
     pub rust_slice: bool,
     pub rust_box: bool,
+    pub rust_com_ptr: bool,
     pub rust_vec: bool,
     pub rust_fn: bool,
+    pub rust_hashmap: bool,
     pub rust_isize: bool,
     pub opaque: bool,
     pub layout: bool,
@@ -20,6 +22,7 @@ pub(crate) struct Builtins<'a> {
     pub manually_drop: bool,
     pub maybe_uninit: bool,
     pub trycatch: bool,
+    pub foreign_exception: bool,
     pub ptr_len: bool,
     pub repr_fat: bool,
     pub rust_str_new_unchecked: bool,
@@ -28,6 +31,7 @@ pub(crate) struct Builtins<'a> {
     pub rust_slice_repr: bool,
     pub relocatable: bool,
     pub relocatable_or_array: bool,
+    pub send_sync: bool,
     pub friend_impl: bool,
     pub is_complete: bool,
     pub destroy: bool,
@@ -52,13 +56,18 @@ pub(super) fn write(out: &mut OutFile) {
     let out = &mut builtin.content;
 
     if builtin.rust_string {
+        include.algorithm = true;
         include.array = true;
+        include.compare = true;
         include.cstdint = true;
         include.string = true;
+        include.string_view = true;
     }
 
     if builtin.rust_str {
+        include.algorithm = true;
         include.array = true;
+        include.compare = true;
         include.cstdint = true;
         include.string = true;
         include.string_view = true;
@@ -69,6 +78,7 @@ pub(super) fn write(out: &mut OutFile) {
         include.algorithm = true;
         include.array = true;
         include.cassert = true;
+        include.compare = true;
         include.cstddef = true;
         include.cstdint = true;
         include.initializer_list = true;
@@ -77,18 +87,22 @@ pub(super) fn write(out: &mut OutFile) {
         include.stdexcept = true;
         include.type_traits = true;
         include.utility = true;
+        include.vector = true;
         builtin.panic = true;
         builtin.rust_slice = true;
         builtin.unsafe_bitcopy_t = true;
     }
 
     if builtin.rust_slice {
+        include.algorithm = true;
         include.array = true;
         include.cassert = true;
+        include.compare = true;
         include.cstddef = true;
         include.cstdint = true;
         include.iterator = true;
         include.ranges = true;
+        include.span = true;
         include.stdexcept = true;
         include.type_traits = true;
         builtin.friend_impl = true;
@@ -102,12 +116,28 @@ pub(super) fn write(out: &mut OutFile) {
         include.utility = true;
     }
 
+    if builtin.rust_com_ptr {
+        include.cstddef = true;
+        include.type_traits = true;
+        include.utility = true;
+    }
+
     if builtin.rust_fn {
         include.utility = true;
     }
 
+    if builtin.rust_hashmap {
+        include.cstddef = true;
+        include.stdexcept = true;
+        include.type_traits = true;
+        builtin.panic = true;
+    }
+
     if builtin.rust_error {
+        include.cstdint = true;
         include.exception = true;
+        include.string = true;
+        include.vector = true;
         builtin.friend_impl = true;
     }
 
@@ -121,6 +151,10 @@ pub(super) fn write(out: &mut OutFile) {
         builtin.relocatable = true;
     }
 
+    if builtin.send_sync {
+        builtin.relocatable = true;
+    }
+
     if builtin.relocatable {
         include.type_traits = true;
     }
@@ -185,16 +219,19 @@ pub(super) fn write(out: &mut OutFile) {
         ifndef::write(out, builtin.rust_str, "CXXBRIDGE1_RUST_STR");
         ifndef::write(out, builtin.rust_slice, "CXXBRIDGE1_RUST_SLICE");
         ifndef::write(out, builtin.rust_box, "CXXBRIDGE1_RUST_BOX");
+        ifndef::write(out, builtin.rust_com_ptr, "CXXBRIDGE1_RUST_COMPTR");
         ifndef::write(out, builtin.unsafe_bitcopy_t, "CXXBRIDGE1_RUST_BITCOPY_T");
         ifndef::write(out, builtin.unsafe_bitcopy, "CXXBRIDGE1_RUST_BITCOPY");
         ifndef::write(out, builtin.rust_vec, "CXXBRIDGE1_RUST_VEC");
         ifndef::write(out, builtin.rust_fn, "CXXBRIDGE1_RUST_FN");
+        ifndef::write(out, builtin.rust_hashmap, "CXXBRIDGE1_RUST_HASHMAP");
         ifndef::write(out, builtin.rust_error, "CXXBRIDGE1_RUST_ERROR");
         ifndef::write(out, builtin.rust_isize, "CXXBRIDGE1_RUST_ISIZE");
         ifndef::write(out, builtin.opaque, "CXXBRIDGE1_RUST_OPAQUE");
         ifndef::write(out, builtin.is_complete, "CXXBRIDGE1_IS_COMPLETE");
         ifndef::write(out, builtin.layout, "CXXBRIDGE1_LAYOUT");
         ifndef::write(out, builtin.relocatable, "CXXBRIDGE1_RELOCATABLE");
+        ifndef::write(out, builtin.send_sync, "CXXBRIDGE1_SEND_SYNC");
     }
 
     if builtin.rust_str_new_unchecked {
@@ -286,7 +323,11 @@ pub(super) fn write(out: &mut OutFile) {
     }
 
     if builtin.trycatch {
+        include.cstddef = true;
+        include.cstdint = true;
+        include.exception = true;
         include.string = true;
+        include.vector = true;
         out.next_section();
         writeln!(out, "class Fail final {{");
         writeln!(out, "  ::rust::repr::PtrLen &throw$;");
@@ -297,9 +338,24 @@ pub(super) fn write(out: &mut OutFile) {
         );
         writeln!(out, "  void operator()(char const *) noexcept;");
         writeln!(out, "  void operator()(std::string const &) noexcept;");
+        writeln!(out, "  void operator()(::std::exception const &) noexcept;");
+        writeln!(out);
+        writeln!(out, "private:");
+        writeln!(
+            out,
+            "  void fail(char const *, ::std::size_t, ::std::string const &, ::std::int32_t, ::std::vector<::std::string> const &) noexcept;",
+        );
         writeln!(out, "}};");
     }
 
+    if builtin.foreign_exception {
+        out.next_section();
+        writeln!(
+            out,
+            "void foreign_exception(char const *function) noexcept;",
+        );
+    }
+
     out.end_block(Block::Namespace("detail"));
 
     if builtin.manually_drop {
@@ -426,12 +482,15 @@ pub(super) fn write(out: &mut OutFile) {
     out.end_block(Block::AnonymousNamespace);
     out.end_block(Block::InlineNamespace("cxxbridge1"));
 
-    if builtin.trycatch {
+    if builtin.trycatch || builtin.foreign_exception {
         out.begin_block(Block::Namespace("behavior"));
-        include.exception = true;
         include.type_traits = true;
         include.utility = true;
         writeln!(out, "class missing {{}};");
+    }
+
+    if builtin.trycatch {
+        include.exception = true;
         writeln!(out, "missing trycatch(...);");
         writeln!(out);
         writeln!(out, "template <typename Try, typename Fail>");
@@ -444,8 +503,27 @@ pub(super) fn write(out: &mut OutFile) {
         writeln!(out, "trycatch(Try &&func, Fail &&fail) noexcept try {{");
         writeln!(out, "  func();");
         writeln!(out, "}} catch (::std::exception const &e) {{");
-        writeln!(out, "  fail(e.what());");
+        writeln!(out, "  fail(e);");
         writeln!(out, "}}");
+    }
+
+    if builtin.foreign_exception {
+        out.next_section();
+        writeln!(out, "missing foreign_exception(...);");
+        writeln!(out);
+        writeln!(out, "template <typename Function>");
+        writeln!(out, "static typename ::std::enable_if<");
+        writeln!(
+            out,
+            "    ::std::is_same<decltype(foreign_exception(::std::declval<Function>())),",
+        );
+        writeln!(out, "                 missing>::value>::type");
+        writeln!(out, "foreign_exception(Function function) noexcept {{");
+        writeln!(out, "  ::rust::detail::foreign_exception(function);");
+        writeln!(out, "}}");
+    }
+
+    if builtin.trycatch || builtin.foreign_exception {
         out.end_block(Block::Namespace("behavior"));
     }
 
@@ -0,0 +1,444 @@
+// Markdown reference of a bridge, for `cxxbridge --emit docs`. Every shared
+// type, opaque type and function is listed with its Rust and C++ spelling, its
+// doc comment, and for functions, what happens to the ownership of each value
+// that crosses the bridge.
+
+use crate::gen::{write, Opt};
+use crate::syntax::atom::Atom::{self, *};
+use crate::syntax::{Api, Doc, ExternFn, Lang, Lifetimes, Signature, Type, Types};
+use std::fmt::Write;
+
+pub(super) fn gen(apis: &[Api], types: &Types, opt: &Opt) -> Vec<u8> {
+    let mut out = String::new();
+    out += "# Bridge reference\n";
+
+    let structs = apis.iter().filter_map(|api| match api {
+        Api::Struct(strct) => Some(strct),
+        _ => None,
+    });
+    for (i, strct) in structs.enumerate() {
+        if i == 0 {
+            out += "\n## Shared structs\n";
+        }
+        let _ = writeln!(out, "\n### `{}`\n", strct.name.rust);
+        let _ = writeln!(out, "C++: `{}`", strct.name.to_fully_qualified(opt));
+        write_doc(&mut out, &strct.doc);
+        out += "\n| Field | Rust | C++ |\n| --- | --- | --- |\n";
+        for field in &strct.fields {
+            let _ = writeln!(
+                out,
+                "| `{}` | `{}` | `{}` |",
+                field.name.rust,
+                rust_type(&field.ty),
+                write::cxx_type(&field.ty, types, opt),
+            );
+        }
+    }
+
+    let enums = apis.iter().filter_map(|api| match api {
+        Api::Enum(enm) => Some(enm),
+        _ => None,
+    });
+    for (i, enm) in enums.enumerate() {
+        if i == 0 {
+            out += "\n## Shared enums\n";
+        }
+        let _ = writeln!(out, "\n### `{}`\n", enm.name.rust);
+        let _ = writeln!(
+            out,
+            "C++: `{}`, with underlying type `{}`",
+            enm.name.to_fully_qualified(opt),
+            enm.repr.atom,
+        );
+        write_doc(&mut out, &enm.doc);
+        out += "\n| Variant | C++ | Value |\n| --- | --- | --- |\n";
+        for variant in &enm.variants {
+            let _ = writeln!(
+                out,
+                "| `{}` | `{}` | {} |",
+                variant.name.rust, variant.name.cxx, variant.discriminant,
+            );
+        }
+    }
+
+    let cxx_types = apis.iter().filter(|api| match api {
+        Api::CxxType(ety) => {
+            ety.vocabulary.is_none() && !types.structs.contains_key(&ety.name.rust)
+        }
+        Api::TypeAlias(_) => true,
+        _ => false,
+    });
+    for (i, api) in cxx_types.enumerate() {
+        if i == 0 {
+            out += "\n## Opaque C++ types\n";
+        }
+        let (name, doc) = match api {
+            Api::CxxType(ety) => (&ety.name, &ety.doc),
+            Api::TypeAlias(alias) => (&alias.name, &alias.doc),
+            _ => unreachable!(),
+        };
+        let _ = writeln!(out, "\n### `{}`\n", name.rust);
+        let _ = writeln!(out, "C++: `{}`\n", name.to_fully_qualified(opt));
+        if let Api::TypeAlias(_) = api {
+            out += "Alias of the same C++ type bound by another bridge.\n";
+        }
+        if types.required_trivial.contains_key(&name.rust) {
+            out += "Passed by value, so it must be trivially movable and destructible in C++.\n";
+        } else {
+            out += "Rust only holds it behind a reference or pointer, such as `UniquePtr`.\n";
+        }
+        write_doc(&mut out, doc);
+    }
+
+    let rust_types = apis.iter().filter_map(|api| match api {
+        Api::RustType(ety) => Some(ety),
+        _ => None,
+    });
+    for (i, ety) in rust_types.enumerate() {
+        if i == 0 {
+            out += "\n## Opaque Rust types\n";
+        }
+        let _ = writeln!(out, "\n### `{}`\n", ety.name.rust);
+        let _ = writeln!(out, "C++: `{}`\n", ety.name.to_fully_qualified(opt));
+        out += "C++ only holds it behind a reference or `rust::Box`.\n";
+        write_doc(&mut out, &ety.doc);
+    }
+
+    for lang in [Lang::Cxx, Lang::Rust] {
+        let functions = apis.iter().filter_map(|api| match api {
+            Api::CxxFunction(efn) if lang == Lang::Cxx => Some(efn),
+            Api::RustFunction(efn) if lang == Lang::Rust => Some(efn),
+            _ => None,
+        });
+        for (i, efn) in functions.enumerate() {
+            if i == 0 {
+                out += match lang {
+                    Lang::Cxx | Lang::CxxUnwind => "\n## Functions implemented in C++\n",
+                    Lang::Rust => "\n## Functions implemented in Rust\n",
+                };
+            }
+            write_function(&mut out, efn, types, opt);
+        }
+    }
+
+    out.into_bytes()
+}
+
+fn write_function(out: &mut String, efn: &ExternFn, types: &Types, opt: &Opt) {
+    match efn.self_type() {
+        Some(self_type) => {
+            let self_type = &types.resolve(self_type).name.rust;
+            let _ = writeln!(out, "\n### `{}::{}`\n", self_type, efn.name.rust);
+        }
+        None => {
+            let _ = writeln!(out, "\n### `{}`\n", efn.name.rust);
+        }
+    }
+    let _ = writeln!(out, "- Rust: `{}`", rust_signature(efn));
+    let _ = writeln!(
+        out,
+        "- C++: `{}`",
+        write::cxx_function_signature(efn, types, opt),
+    );
+
+    out.push_str("- Ownership:\n");
+    if let Some(receiver) = efn.receiver() {
+        let _ = match receiver.mutable {
+            true => writeln!(out, "  - `self`: borrowed mutably"),
+            false => writeln!(out, "  - `self`: borrowed"),
+        };
+    }
+    for arg in &efn.args {
+        let _ = writeln!(
+            out,
+            "  - `{}`: {}",
+            arg.name.rust,
+            ownership(&arg.ty, types, false)
+        );
+    }
+    if let Some(ret) = &efn.ret {
+        let _ = writeln!(out, "  - return value: {}", ownership(ret, types, true));
+    }
+    if efn.throws {
+        out.push_str(match efn.lang {
+            Lang::Cxx | Lang::CxxUnwind => {
+                "- Errors: a C++ exception is returned to Rust as `Err(cxx::Exception)`\n"
+            }
+            Lang::Rust => "- Errors: an `Err` is thrown to C++ as `rust::Error`\n",
+        });
+    } else if efn.hresult.is_some() {
+        out.push_str("- Errors: a failed HRESULT is returned to Rust as `Err(cxx::HRESULT)`\n");
+    }
+    write_doc(out, &efn.doc);
+}
+
+fn write_doc(out: &mut String, doc: &Doc) {
+    if doc.is_empty() {
+        return;
+    }
+    out.push('\n');
+    for line in doc.to_string().lines() {
+        out.push_str(line.strip_prefix(' ').unwrap_or(line));
+        out.push('\n');
+    }
+}
+
+fn ownership(ty: &Type, types: &Types, ret: bool) -> &'static str {
+    match ty {
+        Type::Ref(ty) if ty.mutable => "borrowed mutably",
+        Type::Str(ty) if ty.mutable => "borrowed mutably",
+        Type::SliceRef(ty) if ty.mutable => "borrowed mutably",
+        Type::Ref(_) | Type::Str(_) | Type::SliceRef(_) | Type::HashMapRef(_) => "borrowed",
+        Type::Ptr(_) => "raw pointer, not owned by either side",
+        Type::Fn(_) if ret => "function pointer",
+        Type::Fn(_) => "callback, only valid during the call",
+        Type::SharedPtr(_) | Type::SharedArray(_) | Type::SharedConstPtr(_) | Type::WeakPtr(_) => {
+            match ret {
+                true => "shared, the caller holds its own reference",
+                false => "shared, the callee holds its own reference",
+            }
+        }
+        Type::Ident(ident) if Atom::from(&ident.rust).is_some_and(|atom| atom != RustString) => {
+            "copied"
+        }
+        Type::Ident(ident) if types.enums.contains_key(&ident.rust) => "copied",
+        _ => match ret {
+            true => "moved to the caller",
+            false => "moved to the callee",
+        },
+    }
+}
+
+fn rust_signature(efn: &ExternFn) -> String {
+    let mut out = String::new();
+    if efn.asyncness.is_some() {
+        out += "async ";
+    }
+    if efn.unsafety.is_some() {
+        out += "unsafe ";
+    }
+    let _ = write!(out, "fn {}", efn.name.rust);
+    let lifetimes: Vec<String> = efn
+        .generics
+        .lifetimes()
+        .map(|param| param.lifetime.to_string())
+        .collect();
+    if !lifetimes.is_empty() {
+        let _ = write!(out, "<{}>", lifetimes.join(", "));
+    }
+    out += "(";
+    let mut args = Vec::new();
+    if let Some(receiver) = efn.receiver() {
+        let self_type = format!("{}{}", receiver.ty.rust, generics(&receiver.ty.generics));
+        let lifetime = receiver.lifetime.as_ref();
+        args.push(format!(
+            "self: {}",
+            reference(receiver.pinned, lifetime, receiver.mutable, &self_type),
+        ));
+    }
+    for arg in &efn.args {
+        args.push(format!("{}: {}", arg.name.rust, rust_type(&arg.ty)));
+    }
+    out += &args.join(", ");
+    out += ")";
+    out += &rust_return(efn, &efn.sig);
+    out
+}
+
+fn rust_return(efn: &ExternFn, sig: &Signature) -> String {
+    let ret = sig.ret.as_ref().map(|ret| match ret {
+        Type::UniqueArray(ty) if efn.array_len.is_some() => {
+            format!("UniqueSlice<{}>", rust_type(&ty.inner))
+        }
+        Type::SharedArray(ty) if efn.array_len.is_some() => {
+            format!("SharedSlice<{}>", rust_type(&ty.inner))
+        }
+        _ => rust_type(ret),
+    });
+    if sig.throws {
+        format!(" -> Result<{}>", ret.as_deref().unwrap_or("()"))
+    } else if sig.hresult.is_some() {
+        format!(" -> Result<{}, HRESULT>", ret.as_deref().unwrap_or("()"))
+    } else {
+        match ret {
+            Some(ret) => format!(" -> {}", ret),
+            None => String::new(),
+        }
+    }
+}
+
+pub(super) fn rust_type(ty: &Type) -> String {
+    match ty {
+        Type::Ident(ident) => format!("{}{}", ident.rust, generics(&ident.generics)),
+        Type::RustBox(ty)
+        | Type::RustVec(ty)
+        | Type::UniquePtr(ty)
+        | Type::UniqueArray(ty)
+        | Type::SharedPtr(ty)
+        | Type::SharedArray(ty)
+        | Type::SharedConstPtr(ty)
+        | Type::WeakPtr(ty)
+        | Type::CxxVector(ty)
+        | Type::CxxPmrVector(ty)
+        | Type::ComPtr(ty) => format!("{}<{}>", ty.name, rust_type(&ty.inner)),
+        Type::Ref(ty) => reference(
+            ty.pinned,
+            ty.lifetime.as_ref(),
+            ty.mutable,
+            &rust_type(&ty.inner),
+        ),
+        Type::Ptr(ty) => match ty.mutable {
+            true => format!("*mut {}", rust_type(&ty.inner)),
+            false => format!("*const {}", rust_type(&ty.inner)),
+        },
+        Type::Str(ty) => reference(false, ty.lifetime.as_ref(), ty.mutable, "str"),
+        Type::Fn(sig) => {
+            let args: Vec<String> = sig.args.iter().map(|arg| rust_type(&arg.ty)).collect();
+            let ret = match &sig.ret {
+                Some(ret) => format!(" -> {}", rust_type(ret)),
+                None => String::new(),
+            };
+            format!("fn({}){}", args.join(", "), ret)
+        }
+        Type::Void(_) => "()".to_owned(),
+        Type::SliceRef(ty) => reference(
+            false,
+            ty.lifetime.as_ref(),
+            ty.mutable,
+            &format!("[{}]", rust_type(&ty.inner)),
+        ),
+        Type::Array(ty) => format!("[{}; {}]", rust_type(&ty.inner), ty.len),
+        Type::HashMapRef(ty) => {
+            let map = format!(
+                "{}<{}, {}>",
+                ty.name,
+                rust_type(&ty.key),
+                rust_type(&ty.value),
+            );
+            reference(false, ty.lifetime.as_ref(), false, &map)
+        }
+        Type::CustomPtr(ty) => format!("{}<{}, {}>", ty.name, ty.family, rust_type(&ty.inner),),
+    }
+}
+
+pub(super) fn reference(
+    pinned: bool,
+    lifetime: Option<&syn::Lifetime>,
+    mutable: bool,
+    inner: &str,
+) -> String {
+    let mut reference = String::from("&");
+    if let Some(lifetime) = lifetime {
+        let _ = write!(reference, "{} ", lifetime);
+    }
+    if mutable {
+        reference += "mut ";
+    }
+    reference += inner;
+    match pinned {
+        true => format!("Pin<{}>", reference),
+        false => reference,
+    }
+}
+
+pub(super) fn generics(generics: &Lifetimes) -> String {
+    if generics.lt_token.is_none() {
+        return String::new();
+    }
+    let lifetimes: Vec<String> = generics.lifetimes.iter().map(ToString::to_string).collect();
+    format!("<{}>", lifetimes.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::{self, docs, file::File, Opt};
+
+    #[test]
+    fn test_docs() {
+        let source = r#"
+            #[cxx::bridge(namespace = "geo")]
+            mod ffi {
+                /// A point on the plane.
+                struct Point {
+                    x: i32,
+                    label: String,
+                }
+
+                unsafe extern "C++" {
+                    type Engine;
+
+                    /// Moves the engine to the given point.
+                    fn go(self: Pin<&mut Engine>, to: &Point) -> Result<()>;
+                    fn new_engine(name: &str) -> UniquePtr<Engine>;
+                }
+
+                extern "Rust" {
+                    fn report(points: Vec<Point>) -> usize;
+                }
+            }
+        "#;
+
+        let syntax: File = syn::parse_str(source).unwrap();
+        let opt = Opt::default();
+        let docs = gen::analyze(syntax, &opt, |apis, types| docs::gen(apis, types, &opt)).unwrap();
+        let docs = String::from_utf8(docs).unwrap();
+        assert_eq!(
+            docs,
+            "\
+# Bridge reference
+
+## Shared structs
+
+### `Point`
+
+C++: `::geo::Point`
+
+A point on the plane.
+
+| Field | Rust | C++ |
+| --- | --- | --- |
+| `x` | `i32` | `::std::int32_t` |
+| `label` | `String` | `::rust::String` |
+
+## Opaque C++ types
+
+### `Engine`
+
+C++: `::geo::Engine`
+
+Rust only holds it behind a reference or pointer, such as `UniquePtr`.
+
+## Functions implemented in C++
+
+### `Engine::go`
+
+- Rust: `fn go(self: Pin<&mut Engine>, to: &Point) -> Result<()>`
+- C++: `void ::geo::Engine::go(::geo::Point const &to)`
+- Ownership:
+  - `self`: borrowed mutably
+  - `to`: borrowed
+- Errors: a C++ exception is returned to Rust as `Err(cxx::Exception)`
+
+Moves the engine to the given point.
+
+### `new_engine`
+
+- Rust: `fn new_engine(name: &str) -> UniquePtr<Engine>`
+- C++: `::std::unique_ptr<::geo::Engine> ::geo::new_engine(::rust::Str name)`
+- Ownership:
+  - `name`: borrowed
+  - return value: moved to the caller
+
+## Functions implemented in Rust
+
+### `report`
+
+- Rust: `fn report(points: Vec<Point>) -> usize`
+- C++: `::std::size_t ::geo::report(::rust::Vec<::geo::Point> points) noexcept`
+- Ownership:
+  - `points`: moved to the callee
+  - return value: copied
+",
+        );
+    }
+}
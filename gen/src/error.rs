@@ -1,20 +1,31 @@
 use crate::gen::fs;
+use crate::gen::lint::Lint;
 use crate::syntax;
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::{Diagnostic, Label, Severity};
 use codespan_reporting::files::SimpleFiles;
 use codespan_reporting::term::termcolor::{ColorChoice, StandardStream, WriteColor};
 use codespan_reporting::term::{self, Config};
+use proc_macro2::{LineColumn, Span};
 use std::borrow::Cow;
 use std::error::Error as StdError;
 use std::fmt::{self, Display};
 use std::io::{self, Write};
 use std::ops::Range;
 use std::path::{Path, PathBuf};
-use std::process;
 use std::str::Utf8Error;
 
 pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// How errors are reported on stderr.
+#[derive(Copy, Clone, Default, PartialEq, Debug)]
+pub(crate) enum MessageFormat {
+    /// Rendered diagnostics with source snippets, for humans.
+    #[default]
+    Human,
+    /// One JSON object per line, for build systems and editors.
+    Json,
+}
+
 #[derive(Debug)]
 pub(crate) enum Error {
     NoBridgeMod,
@@ -57,7 +68,12 @@ impl From<syn::Error> for Error {
     }
 }
 
-pub(super) fn format_err(path: &Path, source: &str, error: Error) -> ! {
+pub(crate) fn print_err(path: &Path, source: &str, error: Error, format: MessageFormat) {
+    if format == MessageFormat::Json {
+        print_json_err(path, error);
+        return;
+    }
+
     match error {
         Error::Syn(syn_error) => {
             let syn_error = sort_syn_errors(syn_error);
@@ -65,7 +81,7 @@ pub(super) fn format_err(path: &Path, source: &str, error: Error) -> ! {
             let ref mut stderr = writer.lock();
             for error in syn_error {
                 let _ = writeln!(stderr);
-                display_syn_error(stderr, path, source, error);
+                display_syn_error(stderr, path, source, error, None, Severity::Error);
             }
         }
         Error::NoBridgeMod => {
@@ -79,7 +95,19 @@ pub(super) fn format_err(path: &Path, source: &str, error: Error) -> ! {
             let _ = writeln!(io::stderr(), "cxxbridge: {}", report(error));
         }
     }
-    process::exit(1);
+}
+
+// An error that did not come from code generation, such as a tool that
+// cxxbridge runs failing, printed the same way as print_err would.
+#[allow(dead_code)] // only used by cxxbridge-cmd, not cxx-build or cxx-gen
+pub(crate) fn print_message(path: &Path, message: &str, format: MessageFormat) {
+    if format == MessageFormat::Json {
+        let file = (!path.as_os_str().is_empty()).then(|| path.to_string_lossy());
+        let diagnostic = JsonDiagnostic::message(file.as_deref(), message);
+        let _ = writeln!(io::stderr(), "{}", diagnostic);
+    } else {
+        let _ = writeln!(io::stderr(), "cxxbridge: {}", message);
+    }
 }
 
 pub(crate) fn report(error: impl StdError) -> impl Display {
@@ -102,6 +130,182 @@ pub(crate) fn report(error: impl StdError) -> impl Display {
     Report(error)
 }
 
+// Lints are printed like errors, but with a severity of warning, and with the
+// replacement that fixes the lint if there is one.
+#[allow(dead_code)] // only used by cxxbridge-cmd
+pub(super) fn print_warnings(
+    path: &Path,
+    source: &str,
+    warnings: Vec<Lint>,
+    format: MessageFormat,
+) {
+    let mut warnings = warnings;
+    warnings.sort_by_key(|lint| {
+        let start = lint.error.span().start();
+        (start.line, start.column)
+    });
+    if format == MessageFormat::Json {
+        let file = (!path.as_os_str().is_empty()).then(|| path.to_string_lossy());
+        let ref mut stderr = io::stderr().lock();
+        for warning in warnings {
+            let message = warning.error.to_string();
+            let span = warning.error.span();
+            let included = included_source(span);
+            let file = match &included {
+                Some((path, _)) => Some(path.to_string_lossy()),
+                None => file.clone(),
+            };
+            let diagnostic = JsonDiagnostic {
+                span: Some((span.start(), span.end())),
+                severity: "warning",
+                replacement: warning.fix.as_deref(),
+                ..JsonDiagnostic::message(file.as_deref(), &message)
+            };
+            let _ = writeln!(stderr, "{}", diagnostic);
+        }
+        return;
+    }
+
+    let writer = StandardStream::stderr(ColorChoice::Auto);
+    let ref mut stderr = writer.lock();
+    for warning in warnings {
+        let _ = writeln!(stderr);
+        let fix = warning.fix.as_deref();
+        display_syn_error(stderr, path, source, warning.error, fix, Severity::Warning);
+    }
+}
+
+// Each diagnostic is printed on one line as:
+//
+//     {"file":"src/main.rs","severity":"error","message":"...","label":"...",
+//      "span":{"line_start":1,"column_start":1,"line_end":1,"column_end":5},
+//      "suggestion":"...","replacement":"..."}
+//
+// where the file is null if the error is not specific to a file, and the span,
+// label, and suggestion are null if unknown. Lines and columns are 1-based. The
+// severity is "warning" for lints. The replacement is the source code that the
+// span should be replaced with to fix a lint, or null if there is no automatic
+// fix; it is null for every error.
+fn print_json_err(path: &Path, error: Error) {
+    let file = (!path.as_os_str().is_empty()).then(|| path.to_string_lossy());
+    let ref mut stderr = io::stderr().lock();
+    match error {
+        Error::Syn(syn_error) => {
+            for error in sort_syn_errors(syn_error) {
+                let message = error.to_string();
+                let info = syntax::error::ERRORS
+                    .iter()
+                    .find(|e| message.contains(e.msg));
+                let span = error.span();
+                let included = included_source(span);
+                let file = match &included {
+                    Some((path, _)) => Some(path.to_string_lossy()),
+                    None => file.clone(),
+                };
+                let diagnostic = JsonDiagnostic {
+                    file: file.as_deref(),
+                    severity: "error",
+                    message: &message,
+                    label: info.and_then(|info| info.label),
+                    span: Some((span.start(), span.end())),
+                    suggestion: info.and_then(|info| info.note),
+                    replacement: None,
+                };
+                let _ = writeln!(stderr, "{}", diagnostic);
+            }
+        }
+        Error::NoBridgeMod => {
+            let message = Error::NoBridgeMod.to_string();
+            let _ = writeln!(
+                stderr,
+                "{}",
+                JsonDiagnostic::message(file.as_deref(), &message)
+            );
+        }
+        _ => {
+            let message = report(error).to_string();
+            let _ = writeln!(
+                stderr,
+                "{}",
+                JsonDiagnostic::message(file.as_deref(), &message)
+            );
+        }
+    }
+}
+
+struct JsonDiagnostic<'a> {
+    file: Option<&'a str>,
+    severity: &'static str,
+    message: &'a str,
+    label: Option<&'a str>,
+    span: Option<(LineColumn, LineColumn)>,
+    suggestion: Option<&'a str>,
+    replacement: Option<&'a str>,
+}
+
+impl<'a> JsonDiagnostic<'a> {
+    fn message(file: Option<&'a str>, message: &'a str) -> Self {
+        JsonDiagnostic {
+            file,
+            severity: "error",
+            message,
+            label: None,
+            span: None,
+            suggestion: None,
+            replacement: None,
+        }
+    }
+}
+
+impl<'a> Display for JsonDiagnostic<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{{\"file\":{}", JsonStr(self.file))?;
+        write!(formatter, ",\"severity\":\"{}\"", self.severity)?;
+        write!(formatter, ",\"message\":{}", JsonStr(Some(self.message)))?;
+        write!(formatter, ",\"label\":{}", JsonStr(self.label))?;
+        match self.span {
+            Some((start, end)) => write!(
+                formatter,
+                ",\"span\":{{\"line_start\":{},\"column_start\":{},\"line_end\":{},\"column_end\":{}}}",
+                start.line,
+                start.column + 1,
+                end.line,
+                end.column + 1,
+            )?,
+            None => write!(formatter, ",\"span\":null")?,
+        }
+        write!(formatter, ",\"suggestion\":{}", JsonStr(self.suggestion))?;
+        write!(
+            formatter,
+            ",\"replacement\":{}}}",
+            JsonStr(self.replacement)
+        )
+    }
+}
+
+struct JsonStr<'a>(Option<&'a str>);
+
+impl<'a> Display for JsonStr<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let Some(string) = self.0 else {
+            return formatter.write_str("null");
+        };
+        formatter.write_str("\"")?;
+        for ch in string.chars() {
+            match ch {
+                '"' => formatter.write_str("\\\"")?,
+                '\\' => formatter.write_str("\\\\")?,
+                '\n' => formatter.write_str("\\n")?,
+                '\r' => formatter.write_str("\\r")?,
+                '\t' => formatter.write_str("\\t")?,
+                ch if ch.is_control() => write!(formatter, "\\u{:04x}", ch as u32)?,
+                ch => write!(formatter, "{}", ch)?,
+            }
+        }
+        formatter.write_str("\"")
+    }
+}
+
 fn sort_syn_errors(error: syn::Error) -> Vec<syn::Error> {
     let mut errors: Vec<_> = error.into_iter().collect();
     errors.sort_by_key(|e| {
@@ -111,8 +315,32 @@ fn sort_syn_errors(error: syn::Error) -> Vec<syn::Error> {
     errors
 }
 
-fn display_syn_error(stderr: &mut dyn WriteColor, path: &Path, source: &str, error: syn::Error) {
+// The path and source of the file that a span points into, if the bridge
+// included items from that file rather than having them in its own.
+fn included_source(span: Span) -> Option<(PathBuf, String)> {
+    let file = span.file();
+    syntax::file::with_included_sources(|included| {
+        let included = included
+            .iter()
+            .find(|included| included.span.file() == file)?;
+        Some((included.path.clone(), included.source.clone()))
+    })
+}
+
+fn display_syn_error(
+    stderr: &mut dyn WriteColor,
+    path: &Path,
+    source: &str,
+    error: syn::Error,
+    fix: Option<&str>,
+    severity: Severity,
+) {
     let span = error.span();
+    let included = included_source(span);
+    let (path, source) = match &included {
+        Some((path, source)) => (path.as_path(), source.as_str()),
+        None => (path, source),
+    };
     let start = span.start();
     let end = span.end();
 
@@ -149,18 +377,27 @@ fn display_syn_error(stderr: &mut dyn WriteColor, path: &Path, source: &str, err
     let mut files = SimpleFiles::new();
     let file = files.add(path, source);
 
-    let diagnostic = diagnose(file, start_offset..end_offset, error);
+    let mut diagnostic = diagnose(file, start_offset..end_offset, error, severity);
+    if let Some(fix) = fix {
+        let help = format!("help: replace with `{}`", fix);
+        diagnostic.notes.push(help);
+    }
 
     let config = Config::default();
     let _ = term::emit(stderr, &config, &files, &diagnostic);
 }
 
-fn diagnose(file: usize, range: Range<usize>, error: syn::Error) -> Diagnostic<usize> {
+fn diagnose(
+    file: usize,
+    range: Range<usize>,
+    error: syn::Error,
+    severity: Severity,
+) -> Diagnostic<usize> {
     let message = error.to_string();
     let info = syntax::error::ERRORS
         .iter()
         .find(|e| message.contains(e.msg));
-    let mut diagnostic = Diagnostic::error().with_message(&message);
+    let mut diagnostic = Diagnostic::new(severity).with_message(&message);
     let mut label = Label::primary(file, range);
     if let Some(info) = info {
         label.message = info.label.map_or(message, str::to_owned);
@@ -170,6 +407,5 @@ fn diagnose(file: usize, range: Range<usize>, error: syn::Error) -> Diagnostic<u
         label.message = message;
         diagnostic.labels.push(label);
     }
-    diagnostic.code = Some("cxxbridge".to_owned());
     diagnostic
 }
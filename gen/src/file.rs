@@ -1,5 +1,6 @@
-use crate::syntax::file::Module;
+use crate::syntax::file::{BridgeArgs, Includes, Module};
 use crate::syntax::namespace::Namespace;
+use std::path::{Path, PathBuf};
 use syn::parse::discouraged::Speculative;
 use syn::parse::{Error, Parse, ParseStream, Result};
 use syn::{braced, Attribute, Ident, Item, Meta, Token, Visibility};
@@ -8,15 +9,37 @@ pub(crate) struct File {
     pub modules: Vec<Module>,
 }
 
+impl File {
+    pub(super) fn included_files(&self) -> Vec<PathBuf> {
+        self.modules
+            .iter()
+            .flat_map(Module::included_files)
+            .collect()
+    }
+}
+
 impl Parse for File {
     fn parse(input: ParseStream) -> Result<Self> {
+        File::parse_in(input, None)
+    }
+}
+
+impl File {
+    // Parses the file at a path in the given directory, which the paths of
+    // include!("...") in its bridges are relative to.
+    pub(super) fn parse_in(input: ParseStream, dir: Option<&Path>) -> Result<Self> {
+        let includes = Includes {
+            dir,
+            unknown_dir: false,
+            respan: false,
+        };
         let mut modules = Vec::new();
-        parse(input, &mut modules)?;
+        parse(input, &includes, &mut modules)?;
         Ok(File { modules })
     }
 }
 
-fn parse(input: ParseStream, modules: &mut Vec<Module>) -> Result<()> {
+fn parse(input: ParseStream, includes: &Includes, modules: &mut Vec<Module>) -> Result<()> {
     input.call(Attribute::parse_inner)?;
 
     while !input.is_empty() {
@@ -44,7 +67,7 @@ fn parse(input: ParseStream, modules: &mut Vec<Module>) -> Result<()> {
         }
 
         if cxx_bridge {
-            let mut module: Module = input.parse()?;
+            let mut module = Module::parse(input, includes)?;
             module.namespace = namespace;
             attrs.extend(module.attrs);
             module.attrs = attrs;
@@ -57,7 +80,7 @@ fn parse(input: ParseStream, modules: &mut Vec<Module>) -> Result<()> {
             if semi.is_none() {
                 let content;
                 braced!(content in input);
-                parse(&content, modules)?;
+                parse(&content, includes, modules)?;
             }
         }
     }
@@ -69,6 +92,7 @@ fn parse_args(attr: &Attribute) -> Result<Namespace> {
     if let Meta::Path(_) = attr.meta {
         Ok(Namespace::ROOT)
     } else {
-        attr.parse_args_with(Namespace::parse_bridge_attr_namespace)
+        attr.parse_args_with(BridgeArgs::parse)
+            .map(|args| args.namespace)
     }
 }
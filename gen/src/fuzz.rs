@@ -0,0 +1,305 @@
+// Fuzz target for `cxxbridge --emit fuzz`: a cargo-fuzz harness skeleton that
+// decodes a sequence of calls to the bridge's extern "C++" functions from the
+// fuzzer's input, with arguments made by the `arbitrary` crate. It exercises
+// the conversions of the arguments and return values on both sides and the
+// translation of C++ exceptions into Result.
+
+use crate::gen::docs::rust_type;
+use crate::syntax::atom::Atom::{self, *};
+use crate::syntax::{derive, Api, ExternFn, Trait, Type, Types};
+use proc_macro2::Ident;
+use std::fmt::Write;
+
+pub(super) fn gen(apis: &[Api], types: &Types) -> Vec<u8> {
+    let mut calls = Vec::new();
+    let mut skipped = Vec::new();
+    let mut bounded = false;
+    for api in apis {
+        if let Api::CxxFunction(efn) = api {
+            match call(efn, types, &mut bounded) {
+                Ok(call) => calls.push(call),
+                Err(reason) => skipped.push((name(efn, types), reason)),
+            }
+        }
+    }
+
+    let mut out = String::new();
+    out += "\
+// Fuzz target for the extern \"C++\" functions of a CXX bridge, generated by
+// `cxxbridge --emit fuzz`. Each input is decoded into a sequence of calls with
+// arbitrary arguments. It belongs in fuzz/fuzz_targets/ of a cargo-fuzz
+// project depending on libfuzzer-sys, arbitrary and the crate containing the
+// bridge, whose module path goes in the `use` below.
+
+#![no_main]
+
+use arbitrary::{Result, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use your_crate::ffi;
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+    while !u.is_empty() && call(&mut u).is_ok() {}
+});
+";
+
+    if bounded {
+        out += "\n\
+// Upper bound of usize arguments, which are usually a length or count that the
+// C++ side allocates or loops over. TODO bound: adjust to what the functions
+// are expected to handle.
+const MAX_USIZE: usize = 1 << 16;
+";
+    }
+
+    if !skipped.is_empty() {
+        out += "\n// Not fuzzed:\n";
+        for (name, reason) in &skipped {
+            let _ = writeln!(out, "// - `{}`: {}", name, reason);
+        }
+    }
+
+    if calls.is_empty() {
+        out += "\nfn call(_u: &mut Unstructured) -> Result<()> {\n";
+        out += "    Err(arbitrary::Error::IncorrectFormat)\n";
+        out += "}\n";
+        return out.into_bytes();
+    }
+
+    out += "\nfn call(u: &mut Unstructured) -> Result<()> {\n";
+    let _ = writeln!(out, "    match u.choose_index({})? {{", calls.len());
+    for (i, call) in calls.iter().enumerate() {
+        let _ = writeln!(out, "        {} => {{", i);
+        for line in call.lines() {
+            let _ = writeln!(out, "            {}", line);
+        }
+        out += "        }\n";
+    }
+    out += "        _ => unreachable!(),\n";
+    out += "    }\n";
+    out += "    Ok(())\n";
+    out += "}\n";
+    out.into_bytes()
+}
+
+// The body of one arm of the match, or why the function cannot be called with
+// arbitrary inputs. Sets `bounded` if an argument is drawn from 0..=MAX_USIZE.
+fn call(efn: &ExternFn, types: &Types, bounded: &mut bool) -> Result<String, String> {
+    if efn.unsafety.is_some() {
+        return Err("unsafe to call".to_owned());
+    }
+    if efn.asyncness.is_some() {
+        return Err("async".to_owned());
+    }
+
+    let mut body = String::new();
+    let callee = match efn.receiver() {
+        Some(receiver) => {
+            let self_type = &receiver.ty.rust;
+            let Some(ty) = named(self_type, types).filter(|_| !receiver.pinned) else {
+                return Err(format!(
+                    "method of `{}`, which cannot be arbitrary",
+                    self_type
+                ));
+            };
+            let mutability = if receiver.mutable { "mut " } else { "" };
+            let _ = writeln!(body, "let {}this: {} = u.arbitrary()?;", mutability, ty);
+            format!("this.{}", efn.name.rust)
+        }
+        None => match efn.self_type() {
+            Some(self_type) => format!("ffi::{}::{}", self_type, efn.name.rust),
+            None => format!("ffi::{}", efn.name.rust),
+        },
+    };
+
+    let mut vars = Vec::new();
+    for arg in &efn.args {
+        let Some((ty, pass)) = argument(&arg.ty, types) else {
+            let ty = rust_type(&arg.ty);
+            return Err(format!("`{}: {}` cannot be arbitrary", arg.name.rust, ty));
+        };
+        let var = match arg.name.rust.to_string().as_str() {
+            var @ ("u" | "this") => format!("{}_", var),
+            var => var.to_owned(),
+        };
+        let mutability = if pass == Pass::RefMut { "mut " } else { "" };
+        if ty == "usize" {
+            let _ = writeln!(
+                body,
+                "let {}{}: usize = u.int_in_range(0..=MAX_USIZE)?;",
+                mutability, var,
+            );
+            *bounded = true;
+        } else {
+            let _ = writeln!(body, "let {}{}: {} = u.arbitrary()?;", mutability, var, ty);
+        }
+        vars.push(match pass {
+            Pass::Value => var,
+            Pass::Ref => format!("&{}", var),
+            Pass::RefMut => format!("&mut {}", var),
+        });
+    }
+
+    let call = format!("{}({})", callee, vars.join(", "));
+    if efn.ret.is_none() && !efn.throws && efn.hresult.is_none() {
+        let _ = writeln!(body, "{};", call);
+    } else {
+        let _ = writeln!(body, "let _ = {};", call);
+    }
+    Ok(body)
+}
+
+#[derive(PartialEq)]
+enum Pass {
+    Value,
+    Ref,
+    RefMut,
+}
+
+// The type of the variable to generate for an argument of type `ty`, and how
+// to pass the variable.
+fn argument(ty: &Type, types: &Types) -> Option<(String, Pass)> {
+    let pass = |mutable| match mutable {
+        false => Pass::Ref,
+        true => Pass::RefMut,
+    };
+    match ty {
+        Type::Str(ty) if !ty.mutable => Some(("&str".to_owned(), Pass::Value)),
+        Type::SliceRef(ty) => {
+            let inner = value(&ty.inner, types)?;
+            Some((format!("Vec<{}>", inner), pass(ty.mutable)))
+        }
+        Type::Ref(ty) if !ty.pinned => Some((value(&ty.inner, types)?, pass(ty.mutable))),
+        Type::HashMapRef(ty) => {
+            let key = value(&ty.key, types)?;
+            let value = value(&ty.value, types)?;
+            let map = format!("std::collections::HashMap<{}, {}>", key, value);
+            Some((map, Pass::Ref))
+        }
+        _ => Some((value(ty, types)?, Pass::Value)),
+    }
+}
+
+// The Rust spelling of `ty`, if it is an owned type that implements Arbitrary.
+fn value(ty: &Type, types: &Types) -> Option<String> {
+    match ty {
+        Type::Ident(ident) => match Atom::from(&ident.rust) {
+            Some(Char) => Some("std::ffi::c_char".to_owned()),
+            Some(CxxString) => None,
+            Some(atom) => Some(atom.to_string()),
+            None => named(&ident.rust, types),
+        },
+        Type::RustBox(ty) => Some(format!("Box<{}>", value(&ty.inner, types)?)),
+        Type::RustVec(ty) => Some(format!("Vec<{}>", value(&ty.inner, types)?)),
+        Type::Array(ty) => Some(format!("[{}; {}]", value(&ty.inner, types)?, ty.len)),
+        _ => None,
+    }
+}
+
+// Shared structs and enums are Arbitrary if they derive it in the bridge.
+fn named(ident: &Ident, types: &Types) -> Option<String> {
+    let derives = match types.structs.get(ident) {
+        Some(strct) => &strct.derives,
+        None => &types.enums.get(ident)?.derives,
+    };
+    if derive::contains(derives, Trait::Arbitrary) {
+        Some(format!("ffi::{}", ident))
+    } else {
+        None
+    }
+}
+
+fn name(efn: &ExternFn, types: &Types) -> String {
+    match efn.self_type() {
+        Some(self_type) => {
+            let self_type = &types.resolve(self_type).name.rust;
+            format!("{}::{}", self_type, efn.name.rust)
+        }
+        None => efn.name.rust.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::{self, file::File, fuzz, Opt};
+
+    #[test]
+    fn test_fuzz() {
+        let source = r#"
+            #[cxx::bridge]
+            mod ffi {
+                #[derive(Arbitrary)]
+                struct Point {
+                    x: i32,
+                    label: String,
+                }
+
+                #[derive(Arbitrary)]
+                enum Level {
+                    Low,
+                    High,
+                }
+
+                struct Unfuzzable {
+                    n: usize,
+                }
+
+                unsafe extern "C++" {
+                    type Engine;
+
+                    fn parse(input: &str, level: Level) -> Result<Vec<Point>>;
+                    fn norm(self: &Point) -> f64;
+                    fn sum(values: &mut [u32], u: Unfuzzable);
+                    fn run(self: Pin<&mut Engine>, steps: usize);
+                    fn reset(points: &mut Vec<Point>);
+                    fn nearest(origin: &Box<Point>, within: &[u8; 4], limit: usize) -> usize;
+                }
+            }
+        "#;
+
+        let syntax: File = syn::parse_str(source).unwrap();
+        let opt = Opt::default();
+        let fuzz = gen::analyze(syntax, &opt, fuzz::gen).unwrap();
+        let fuzz = String::from_utf8(fuzz).unwrap();
+        let (_header, body) = fuzz.split_once("});\n\n").unwrap();
+        assert_eq!(
+            body,
+            "\
+// Upper bound of usize arguments, which are usually a length or count that the
+// C++ side allocates or loops over. TODO bound: adjust to what the functions
+// are expected to handle.
+const MAX_USIZE: usize = 1 << 16;
+
+// Not fuzzed:
+// - `sum`: `u: Unfuzzable` cannot be arbitrary
+// - `Engine::run`: method of `Engine`, which cannot be arbitrary
+
+fn call(u: &mut Unstructured) -> Result<()> {
+    match u.choose_index(4)? {
+        0 => {
+            let input: &str = u.arbitrary()?;
+            let level: ffi::Level = u.arbitrary()?;
+            let _ = ffi::parse(input, level);
+        }
+        1 => {
+            let this: ffi::Point = u.arbitrary()?;
+            let _ = this.norm();
+        }
+        2 => {
+            let mut points: Vec<ffi::Point> = u.arbitrary()?;
+            ffi::reset(&mut points);
+        }
+        3 => {
+            let origin: Box<ffi::Point> = u.arbitrary()?;
+            let within: [u8; 4] = u.arbitrary()?;
+            let limit: usize = u.int_in_range(0..=MAX_USIZE)?;
+            let _ = ffi::nearest(&origin, &within, limit);
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+",
+        );
+    }
+}
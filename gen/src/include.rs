@@ -1,10 +1,30 @@
 use crate::gen::out::{Content, OutFile};
+use crate::gen::Opt;
 use crate::syntax::{self, IncludeKind};
+use std::borrow::Cow;
 use std::ops::{Deref, DerefMut};
 
 /// The complete contents of the "rust/cxx.h" header.
 pub static HEADER: &str = include_str!("include/cxx.h");
 
+// The "rust/cxx_format.h" header of std::format and fmtlib formatters, which
+// code that formats the bridged types opts into by including it.
+#[allow(dead_code)] // only used by cxx-build, not cxxbridge-cmd or cxx-gen
+pub(crate) static FORMAT_HEADER: &str = include_str!("include/cxx_format.h");
+
+// The "rust/cxx_test.h" header of the C++ test harness.
+#[allow(dead_code)] // only used by cxx-build, not cxxbridge-cmd or cxx-gen
+pub(crate) static TEST_HEADER: &str = include_str!("include/cxx_test.h");
+
+// Debugger visualizers for the bridged types, before filling in the layout of
+// Rust's String, Vec and slices.
+#[allow(dead_code)] // only used by cxx-build, not cxxbridge-cmd or cxx-gen
+pub(crate) static NATVIS: &str = include_str!("include/cxx.natvis");
+#[allow(dead_code)] // only used by cxx-build, not cxxbridge-cmd or cxx-gen
+pub(crate) static GDB_SCRIPT: &str = include_str!("include/cxx_gdb.py");
+#[allow(dead_code)] // only used by cxx-build, not cxxbridge-cmd or cxx-gen
+pub(crate) static LLDB_SCRIPT: &str = include_str!("include/cxx_lldb.py");
+
 /// A header to #include.
 ///
 /// The cxxbridge tool does not parse or even require the given paths to exist;
@@ -24,6 +44,7 @@ pub(crate) struct Includes<'a> {
     pub algorithm: bool,
     pub array: bool,
     pub cassert: bool,
+    pub compare: bool,
     pub cstddef: bool,
     pub cstdint: bool,
     pub cstring: bool,
@@ -32,8 +53,10 @@ pub(crate) struct Includes<'a> {
     pub initializer_list: bool,
     pub iterator: bool,
     pub memory: bool,
+    pub memory_resource: bool,
     pub new: bool,
     pub ranges: bool,
+    pub span: bool,
     pub stdexcept: bool,
     pub string: bool,
     pub string_view: bool,
@@ -63,30 +86,56 @@ impl<'a> Includes<'a> {
 
 pub(super) fn write(out: &mut OutFile) {
     let header = out.header;
+    let opt = out.opt;
     let include = &mut out.include;
-    let cxx_header = include.has_cxx_header();
-    let out = &mut include.content;
 
     if header {
-        writeln!(out, "#pragma once");
+        writeln!(include.content, "#pragma once");
     }
 
-    for include in &include.custom {
-        match include.kind {
-            IncludeKind::Quoted => {
-                writeln!(out, "#include \"{}\"", include.path.escape_default());
-            }
-            IncludeKind::Bracketed => {
-                writeln!(out, "#include <{}>", include.path);
-            }
-        }
+    for custom in custom(opt, include) {
+        writeln!(include.content, "{}", line(&custom));
     }
 
+    write_standard(include);
+}
+
+// The #include line of a header, without the newline.
+fn line(include: &Include) -> String {
+    match include.kind {
+        IncludeKind::Quoted => format!("#include \"{}\"", include.path.escape_default()),
+        IncludeKind::Bracketed => format!("#include <{}>", include.path),
+    }
+}
+
+// The includes from the bridge and from Opt::include, with the rewrites of
+// Opt::include_rewrites applied.
+pub(super) fn custom(opt: &Opt, include: &Includes) -> Vec<Include> {
+    include
+        .custom
+        .iter()
+        .map(|include| match include.kind {
+            IncludeKind::Quoted => Include {
+                path: rewrite_path(opt, &include.path).into_owned(),
+                kind: IncludeKind::Quoted,
+            },
+            IncludeKind::Bracketed => include.clone(),
+        })
+        .collect()
+}
+
+// The standard library and system headers that the generated code needs. Most
+// of them come with rust/cxx.h, so they are left out if that is included.
+pub(super) fn write_standard(include: &mut Includes) {
+    let cxx_header = include.has_cxx_header();
+    let out = &mut include.content;
+
     let Includes {
         custom: _,
         algorithm,
         array,
         cassert,
+        compare,
         cstddef,
         cstdint,
         cstring,
@@ -95,8 +144,10 @@ pub(super) fn write(out: &mut OutFile) {
         initializer_list,
         iterator,
         memory,
+        memory_resource,
         new,
         ranges,
+        span,
         stdexcept,
         string,
         string_view,
@@ -141,6 +192,9 @@ pub(super) fn write(out: &mut OutFile) {
     if memory {
         writeln!(out, "#include <memory>");
     }
+    if memory_resource {
+        writeln!(out, "#include <memory_resource>");
+    }
     if new && !cxx_header {
         writeln!(out, "#include <new>");
     }
@@ -181,13 +235,39 @@ pub(super) fn write(out: &mut OutFile) {
         writeln!(out, "#include <string_view>");
         writeln!(out, "#endif");
     }
-    if ranges && !cxx_header {
+    if (compare || ranges || span) && !cxx_header {
         writeln!(out, "#if __cplusplus >= 202002L");
-        writeln!(out, "#include <ranges>");
+        if compare {
+            writeln!(out, "#include <compare>");
+        }
+        if ranges {
+            writeln!(out, "#include <ranges>");
+        }
+        if span {
+            writeln!(out, "#include <span>");
+        }
         writeln!(out, "#endif");
     }
 }
 
+// Applies the longest matching prefix rewrite, if any, to the path of a quoted
+// include. This lets the generated code refer to headers by their installed
+// location rather than the one used at build time.
+fn rewrite_path<'a>(opt: &Opt, path: &'a str) -> Cow<'a, str> {
+    let mut best_match: Option<&(String, String)> = None;
+    for rewrite in &opt.include_rewrites {
+        if path.starts_with(&rewrite.0)
+            && best_match.map_or(true, |best| rewrite.0.len() > best.0.len())
+        {
+            best_match = Some(rewrite);
+        }
+    }
+    match best_match {
+        Some((from, to)) => Cow::Owned(format!("{}{}", to, &path[from.len()..])),
+        None => Cow::Borrowed(path),
+    }
+}
+
 impl<'i, 'a> Extend<&'i Include> for Includes<'a> {
     fn extend<I: IntoIterator<Item = &'i Include>>(&mut self, iter: I) {
         self.custom.extend(iter.into_iter().cloned());
@@ -0,0 +1,639 @@
+// Warnings about bridge declarations that are likely to be mistakes, printed by
+// `cxxbridge --lint`. Each of these is legal, so none of them is an error for
+// code generation.
+
+use crate::gen::docs::{generics, reference, rust_type};
+use crate::gen::Opt;
+use crate::syntax::trivial::TrivialReason;
+use crate::syntax::{Api, ExternFn, Pair, Receiver, Type, Types};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use std::collections::{BTreeMap as Map, BTreeSet as Set};
+use syn::{Attribute, Error, Item, Lifetime};
+
+pub(super) struct Lint {
+    pub error: Error,
+    // Source code to replace the span of the error with, for lints that have a
+    // safe alternative to what the bridge declares.
+    pub fix: Option<String>,
+}
+
+impl From<Error> for Lint {
+    fn from(error: Error) -> Self {
+        Lint { error, fix: None }
+    }
+}
+
+pub(super) fn lint(apis: &[Api], types: &Types, opt: &Opt, references: &Set<String>) -> Vec<Lint> {
+    let mut errors = Vec::new();
+    check_unused(&mut errors, apis, types, references);
+    check_duplicates(&mut errors, apis, opt);
+    check_case_only_renames(&mut errors, apis);
+    let mut lints: Vec<Lint> = errors.into_iter().map(Lint::from).collect();
+    check_soundness(&mut lints, apis, types);
+    lints
+}
+
+// Every identifier that occurs outside of the #[cxx::bridge] modules of the
+// given Rust source files.
+pub(super) fn references(files: &[syn::File]) -> Set<String> {
+    let mut references = Set::new();
+    for file in files {
+        collect_items(&mut references, &file.items);
+    }
+    references
+}
+
+fn collect_items(references: &mut Set<String>, items: &[Item]) {
+    for item in items {
+        match item {
+            Item::Mod(item) if item.attrs.iter().any(is_cxx_bridge) => {}
+            Item::Mod(item) => {
+                for attr in &item.attrs {
+                    collect_tokens(references, attr.to_token_stream());
+                }
+                if let Some((_brace, content)) = &item.content {
+                    collect_items(references, content);
+                }
+            }
+            _ => collect_tokens(references, item.to_token_stream()),
+        }
+    }
+}
+
+fn collect_tokens(references: &mut Set<String>, tokens: TokenStream) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                references.insert(ident.to_string());
+            }
+            TokenTree::Group(group) => collect_tokens(references, group.stream()),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => {}
+        }
+    }
+}
+
+fn is_cxx_bridge(attr: &Attribute) -> bool {
+    let path = &attr.path().segments;
+    path.len() == 2 && path[0].ident == "cxx" && path[1].ident == "bridge"
+}
+
+// Extern C++ functions which Rust code never calls, and extern C++ types which
+// are named neither by Rust code nor by any signature in the bridge. Rust code
+// is searched by name only, so a same-named identifier anywhere else is enough
+// to count as a use.
+fn check_unused(lints: &mut Vec<Error>, apis: &[Api], types: &Types, references: &Set<String>) {
+    let mut mentioned = Set::new();
+    for ty in types {
+        if let Type::Ident(ident) = ty {
+            mentioned.insert(&ident.rust);
+        }
+    }
+    for api in apis {
+        if let Api::CxxFunction(efn) | Api::RustFunction(efn) = api {
+            if let Some(self_type) = efn.self_type() {
+                mentioned.insert(self_type);
+            }
+        }
+    }
+
+    let is_used =
+        |ident: &Ident| mentioned.contains(ident) || references.contains(&ident.to_string());
+    for api in apis {
+        match api {
+            Api::CxxType(ety)
+                if !ety.smart_ptr && ety.vocabulary.is_none() && !is_used(&ety.name.rust) =>
+            {
+                let msg = format!("extern C++ type `{}` is never used", ety.name.rust);
+                lints.push(Error::new_spanned(&ety.name.rust, msg));
+            }
+            Api::TypeAlias(alias) if !is_used(&alias.name.rust) => {
+                let msg = format!("type alias `{}` is never used", alias.name.rust);
+                lints.push(Error::new_spanned(&alias.name.rust, msg));
+            }
+            Api::CxxFunction(efn) if !references.contains(&efn.name.rust.to_string()) => {
+                let msg = format!(
+                    "extern C++ function `{}` is never called from Rust",
+                    function_name(efn),
+                );
+                lints.push(Error::new_spanned(&efn.name.rust, msg));
+            }
+            _ => {}
+        }
+    }
+}
+
+// The same C++ type bound to more than one Rust type, which Rust then treats as
+// unrelated, and the same C++ function declared more than once with the same
+// signature.
+fn check_duplicates(lints: &mut Vec<Error>, apis: &[Api], opt: &Opt) {
+    let mut cxx_types = Map::new();
+    let mut cxx_functions: Vec<&ExternFn> = Vec::new();
+    for api in apis {
+        match api {
+            Api::CxxType(ety) if !ety.smart_ptr && ety.vocabulary.is_none() => {
+                check_duplicate_type(lints, &mut cxx_types, &ety.name, opt);
+            }
+            Api::TypeAlias(alias) => {
+                check_duplicate_type(lints, &mut cxx_types, &alias.name, opt);
+            }
+            Api::CxxFunction(efn) => {
+                let qualified = efn.name.to_fully_qualified(opt);
+                if let Some(prev) = cxx_functions.iter().find(|prev| {
+                    prev.name.to_fully_qualified(opt) == qualified && same_signature(prev, efn)
+                }) {
+                    let msg = format!(
+                        "C++ function `{}` is already declared as `{}` with the same signature",
+                        qualified.trim_start_matches("::"),
+                        prev.name.rust,
+                    );
+                    lints.push(Error::new_spanned(&efn.name.rust, msg));
+                }
+                cxx_functions.push(efn);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_duplicate_type<'a>(
+    lints: &mut Vec<Error>,
+    cxx_types: &mut Map<String, &'a Ident>,
+    name: &'a Pair,
+    opt: &Opt,
+) {
+    let qualified = name.to_fully_qualified(opt);
+    if let Some(prev) = cxx_types.get(&qualified) {
+        let msg = format!(
+            "C++ type `{}` is already bound as `{}`",
+            qualified.trim_start_matches("::"),
+            prev,
+        );
+        lints.push(Error::new_spanned(&name.rust, msg));
+    } else {
+        cxx_types.insert(qualified, &name.rust);
+    }
+}
+
+fn same_signature(a: &ExternFn, b: &ExternFn) -> bool {
+    let receiver = |receiver: Option<&Receiver>| {
+        receiver.map(|receiver| (receiver.ty.rust.to_string(), receiver.mutable))
+    };
+    receiver(a.receiver()) == receiver(b.receiver())
+        && a.args.len() == b.args.len()
+        && a.args.iter().zip(&b.args).all(|(a, b)| a.ty == b.ty)
+        && a.ret == b.ret
+}
+
+// A cxx_name or rust_name which only changes the case of some letters other
+// than the first. Renaming `area` to `Area` is usual to follow each language's
+// convention, but `getValue` to `getvalue` is more likely a typo.
+fn check_case_only_renames(lints: &mut Vec<Error>, apis: &[Api]) {
+    for api in apis {
+        let name = match api {
+            Api::Struct(strct) => &strct.name,
+            Api::Enum(enm) => &enm.name,
+            Api::CxxType(ety) | Api::RustType(ety) => &ety.name,
+            Api::CxxFunction(efn) | Api::RustFunction(efn) => &efn.name,
+            Api::TypeAlias(alias) => &alias.name,
+            Api::Include(_) | Api::Impl(_) => continue,
+        };
+        let cxx = name.cxx.to_string();
+        let rust = name.rust.to_string();
+        if cxx != rust
+            && cxx.eq_ignore_ascii_case(&rust)
+            && !cxx.chars().skip(1).eq(rust.chars().skip(1))
+        {
+            let msg = format!(
+                "C++ name `{}` differs from the Rust name `{}` only by case",
+                cxx, rust,
+            );
+            lints.push(Error::new_spanned(&name.rust, msg));
+        }
+    }
+}
+
+fn function_name(efn: &ExternFn) -> String {
+    match efn.self_type() {
+        Some(self_type) => format!("{}::{}", self_type, efn.name.rust),
+        None => efn.name.rust.to_string(),
+    }
+}
+
+// Extern C++ signatures which safe Rust code can use to break Rust's aliasing
+// or pinning rules, depending on what the C++ side does. Most of these are
+// fine for some C++ implementations, which is why they are warnings, but each
+// comes with a declaration that is sound whatever C++ does.
+fn check_soundness(lints: &mut Vec<Lint>, apis: &[Api], types: &Types) {
+    for api in apis {
+        if let Api::CxxFunction(efn) = api {
+            if let Some(ret) = &efn.ret {
+                check_unbounded_lifetime(lints, efn, ret);
+                check_aliasing_mut(lints, efn, ret);
+            }
+            check_unpinned_mut(lints, efn, types);
+        }
+    }
+}
+
+// A returned reference whose lifetime comes from nowhere: either 'static out
+// of a member function, which is usually a pointer into the object, or a
+// lifetime parameter that no argument mentions, which the caller is free to
+// pick as long as it likes.
+fn check_unbounded_lifetime(lints: &mut Vec<Lint>, efn: &ExternFn, ret: &Type) {
+    let Some((Some(lifetime), _mutable)) = returned_reference(ret) else {
+        return;
+    };
+    let msg = if lifetime.ident == "static" {
+        if efn.receiver().is_none() {
+            return;
+        }
+        format!(
+            "`{}` returns a 'static reference from a member function, which lets it outlive `self`",
+            function_name(efn),
+        )
+    } else if mentioned_by_inputs(efn, lifetime) {
+        return;
+    } else {
+        format!(
+            "lifetime `{}` of the reference returned by `{}` is not tied to any argument, so the caller can choose any lifetime for it",
+            lifetime,
+            function_name(efn),
+        )
+    };
+    // Without a lifetime, the reference borrows from `self`.
+    let fix = efn.receiver().map(|_| render_reference(ret, None, None));
+    let error = Error::new_spanned(ret, msg);
+    lints.push(Lint { error, fix });
+}
+
+// A returned mutable reference which borrows only from shared references. Two
+// calls with the same arguments then give out two mutable references to the
+// same thing. Through a `&SharedPtr<T>` this does not even take two calls
+// with the same reference, since every clone of the pointer can make one.
+//
+// Code generation already requires a safe function with a mutable return type
+// to have some mutable argument, so what is left for this check is a mutable
+// argument that the returned reference does not borrow from because of an
+// explicit lifetime or because `&self` takes precedence in lifetime elision.
+// An unsafe function is left to its callers.
+fn check_aliasing_mut(lints: &mut Vec<Lint>, efn: &ExternFn, ret: &Type) {
+    let Some((lifetime, true)) = returned_reference(ret) else {
+        return;
+    };
+    if efn.unsafety.is_some() {
+        return;
+    }
+
+    // Whether each of the inputs that the returned reference borrows from is a
+    // shared reference, and whether it is a shared reference to a SharedPtr.
+    let mut sources = Vec::new();
+    match lifetime {
+        Some(lifetime) if lifetime.ident == "static" => return,
+        Some(lifetime) => {
+            if let Some(receiver) = efn.receiver() {
+                if receiver.lifetime.as_ref() == Some(lifetime)
+                    || receiver
+                        .ty
+                        .generics
+                        .lifetimes
+                        .iter()
+                        .any(|lt| lt == lifetime)
+                {
+                    sources.push((!receiver.mutable, false));
+                }
+            }
+            for arg in &efn.args {
+                if mentions_lifetime(&arg.ty, lifetime) {
+                    sources.push(shared_borrow(&arg.ty, Some(lifetime)));
+                }
+            }
+        }
+        // By the lifetime elision rules, the returned reference borrows from
+        // `self` if there is one, and otherwise from the one argument that is
+        // a reference.
+        None => {
+            if let Some(receiver) = efn.receiver() {
+                sources.push((!receiver.mutable, false));
+            } else {
+                let mut refs = efn.args.iter().filter(|arg| is_reference(&arg.ty));
+                if let (Some(arg), None) = (refs.next(), refs.next()) {
+                    sources.push(shared_borrow(&arg.ty, None));
+                }
+            }
+        }
+    }
+    if sources.is_empty() || !sources.iter().all(|&(shared, _)| shared) {
+        return;
+    }
+
+    let msg = if sources.iter().any(|&(_, shared_ptr)| shared_ptr) {
+        format!(
+            "`{}` returns a mutable reference borrowed from a shared SharedPtr, and every clone of the SharedPtr can make another mutable reference to the same object",
+            function_name(efn),
+        )
+    } else {
+        format!(
+            "`{}` returns a mutable reference borrowed only from shared references, so two calls can make aliasing mutable references",
+            function_name(efn),
+        )
+    };
+    let fix = render_reference(ret, lifetime, Some(false));
+    let error = Error::new_spanned(ret, msg);
+    lints.push(Lint {
+        error,
+        fix: Some(fix),
+    });
+}
+
+// An unpinned `&mut` to a C++ type alias, when nothing else about the alias
+// needs it to be trivial. Only the `&mut` makes the generated code require the
+// C++ type to be trivially movable, because from Rust, `&mut` is enough to
+// move the object out with mem::swap. A type that may point into itself, like
+// many standard library types do, needs a Pin<&mut T> instead.
+fn check_unpinned_mut(lints: &mut Vec<Lint>, efn: &ExternFn, types: &Types) {
+    let only_unpinned_mut = |ident: &Ident| {
+        types.aliases.contains_key(ident)
+            && types.required_trivial.get(ident).is_some_and(|reasons| {
+                reasons
+                    .iter()
+                    .all(|reason| matches!(reason, TrivialReason::UnpinnedMut(_)))
+            })
+    };
+    let msg = |ident: &Ident| {
+        format!(
+            "`&mut {}` requires the C++ type to be trivially movable, so it must not be self-referential",
+            ident,
+        )
+    };
+
+    if let Some(receiver) = efn.receiver() {
+        if receiver.mutable && !receiver.pinned && only_unpinned_mut(&receiver.ty.rust) {
+            let ampersand = receiver.ampersand;
+            let lifetime = &receiver.lifetime;
+            let mutability = receiver.mutability;
+            let self_type = format!("{}{}", receiver.ty.rust, generics(&receiver.ty.generics));
+            let fix = reference(true, lifetime.as_ref(), true, &self_type);
+            let (span, fix) = if receiver.shorthand {
+                let var = receiver.var;
+                (
+                    quote!(#ampersand #lifetime #mutability #var),
+                    format!("self: {}", fix),
+                )
+            } else {
+                let ty = &receiver.ty;
+                (quote!(#ampersand #lifetime #mutability #ty), fix)
+            };
+            let error = Error::new_spanned(span, msg(&receiver.ty.rust));
+            lints.push(Lint {
+                error,
+                fix: Some(fix),
+            });
+        }
+    }
+
+    let args = efn.args.iter().map(|arg| &arg.ty);
+    for ty in args.chain(&efn.ret) {
+        if let Type::Ref(ty) = ty {
+            if let Type::Ident(ident) = &ty.inner {
+                if ty.mutable && !ty.pinned && only_unpinned_mut(&ident.rust) {
+                    let fix = reference(true, ty.lifetime.as_ref(), true, &rust_type(&ty.inner));
+                    let error = Error::new_spanned(ty, msg(&ident.rust));
+                    lints.push(Lint {
+                        error,
+                        fix: Some(fix),
+                    });
+                }
+            }
+        }
+    }
+}
+
+// The lifetime of a reference type, and whether it is a mutable reference.
+fn returned_reference(ty: &Type) -> Option<(Option<&Lifetime>, bool)> {
+    match ty {
+        Type::Ref(ty) | Type::Str(ty) => Some((ty.lifetime.as_ref(), ty.mutable)),
+        Type::SliceRef(ty) => Some((ty.lifetime.as_ref(), ty.mutable)),
+        _ => None,
+    }
+}
+
+// The same reference type as `ty`, with a different lifetime, and with a
+// different mutability if one is given.
+fn render_reference(ty: &Type, lifetime: Option<&Lifetime>, mutable: Option<bool>) -> String {
+    match ty {
+        Type::Ref(ty) => {
+            let mutable = mutable.unwrap_or(ty.mutable);
+            reference(
+                ty.pinned && mutable,
+                lifetime,
+                mutable,
+                &rust_type(&ty.inner),
+            )
+        }
+        Type::Str(ty) => reference(false, lifetime, mutable.unwrap_or(ty.mutable), "str"),
+        Type::SliceRef(ty) => {
+            let inner = format!("[{}]", rust_type(&ty.inner));
+            reference(false, lifetime, mutable.unwrap_or(ty.mutable), &inner)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn mentioned_by_inputs(efn: &ExternFn, lifetime: &Lifetime) -> bool {
+    let by_receiver = efn.receiver().is_some_and(|receiver| {
+        receiver.lifetime.as_ref() == Some(lifetime)
+            || receiver
+                .ty
+                .generics
+                .lifetimes
+                .iter()
+                .any(|lt| lt == lifetime)
+    });
+    by_receiver
+        || efn
+            .args
+            .iter()
+            .any(|arg| mentions_lifetime(&arg.ty, lifetime))
+}
+
+fn mentions_lifetime(ty: &Type, lifetime: &Lifetime) -> bool {
+    match ty {
+        Type::Ident(ident) => ident.generics.lifetimes.iter().any(|lt| lt == lifetime),
+        Type::RustBox(ty)
+        | Type::RustVec(ty)
+        | Type::UniquePtr(ty)
+        | Type::UniqueArray(ty)
+        | Type::SharedPtr(ty)
+        | Type::SharedArray(ty)
+        | Type::SharedConstPtr(ty)
+        | Type::WeakPtr(ty)
+        | Type::CxxVector(ty)
+        | Type::CxxPmrVector(ty)
+        | Type::ComPtr(ty) => mentions_lifetime(&ty.inner, lifetime),
+        Type::Ref(ty) | Type::Str(ty) => {
+            ty.lifetime.as_ref() == Some(lifetime) || mentions_lifetime(&ty.inner, lifetime)
+        }
+        Type::Ptr(ty) => mentions_lifetime(&ty.inner, lifetime),
+        Type::SliceRef(ty) => {
+            ty.lifetime.as_ref() == Some(lifetime) || mentions_lifetime(&ty.inner, lifetime)
+        }
+        Type::Array(ty) => mentions_lifetime(&ty.inner, lifetime),
+        Type::HashMapRef(ty) => {
+            ty.lifetime.as_ref() == Some(lifetime)
+                || mentions_lifetime(&ty.key, lifetime)
+                || mentions_lifetime(&ty.value, lifetime)
+        }
+        Type::CustomPtr(ty) => mentions_lifetime(&ty.inner, lifetime),
+        Type::Fn(_) | Type::Void(_) => false,
+    }
+}
+
+fn is_reference(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Ref(_) | Type::Str(_) | Type::SliceRef(_) | Type::HashMapRef(_),
+    )
+}
+
+// Whether an argument that the returned reference borrows from is a shared
+// reference with the given lifetime, and whether it is a `&SharedPtr<T>`.
+fn shared_borrow(ty: &Type, lifetime: Option<&Lifetime>) -> (bool, bool) {
+    match ty {
+        Type::Ref(ty) => {
+            let shared = !ty.mutable && ty.lifetime.as_ref() == lifetime;
+            (shared, shared && matches!(ty.inner, Type::SharedPtr(_)))
+        }
+        Type::Str(ty) => (!ty.mutable && ty.lifetime.as_ref() == lifetime, false),
+        Type::SliceRef(ty) => (!ty.mutable && ty.lifetime.as_ref() == lifetime, false),
+        Type::HashMapRef(ty) => (ty.lifetime.as_ref() == lifetime, false),
+        _ => (false, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::{self, file::File, Opt};
+    use std::collections::BTreeSet as Set;
+
+    #[test]
+    fn test_lint() {
+        let source = r#"
+            #[cxx::bridge(namespace = "tests")]
+            mod ffi {
+                unsafe extern "C++" {
+                    type Used;
+                    type Unused;
+                    type Receiver;
+                    #[cxx_name = "Used"]
+                    type Again;
+
+                    fn make() -> UniquePtr<Used>;
+                    fn forgotten(again: &Again);
+                    fn method(self: &Receiver);
+                    #[cxx_name = "make"]
+                    fn make_again() -> UniquePtr<Used>;
+                    #[cxx_name = "getvalue"]
+                    fn getValue() -> i32;
+                    #[cxx_name = "Area"]
+                    fn area() -> i32;
+                    #[cxx_name = "éCount"]
+                    fn écount() -> i32;
+                }
+            }
+
+            fn main() {
+                let _ = ffi::make();
+                let _ = ffi::make_again();
+                let _ = ffi::getValue() + ffi::area() + ffi::écount();
+            }
+        "#;
+
+        let file = syn::parse_file(source).unwrap();
+        let references = gen::lint::references(&[file]);
+        let syntax: File = syn::parse_str(source).unwrap();
+        let opt = Opt::default();
+        let lints = gen::analyze(syntax, &opt, |apis, types| {
+            gen::lint::lint(apis, types, &opt, &references)
+        })
+        .unwrap();
+        let messages: Set<String> = lints.iter().map(|lint| lint.error.to_string()).collect();
+
+        let expected = [
+            "extern C++ type `Unused` is never used",
+            "extern C++ function `forgotten` is never called from Rust",
+            "extern C++ function `Receiver::method` is never called from Rust",
+            "C++ type `tests::Used` is already bound as `Used`",
+            "C++ function `tests::make` is already declared as `make` with the same signature",
+            "C++ name `getvalue` differs from the Rust name `getValue` only by case",
+            "C++ name `éCount` differs from the Rust name `écount` only by case",
+        ];
+        let expected: Set<String> = expected.iter().map(|&msg| msg.to_owned()).collect();
+        assert_eq!(messages, expected);
+    }
+
+    #[test]
+    fn test_soundness() {
+        let source = r#"
+            #[cxx::bridge]
+            mod ffi {
+                unsafe extern "C++" {
+                    type Engine;
+                    type Buffer = crate::Buffer;
+
+                    fn name(self: &Engine) -> &'static str;
+                    fn any<'a>(engine: &Engine) -> &'a [u8];
+                    fn state(self: &Engine, buffer: Pin<&mut Buffer>) -> Pin<&mut Buffer>;
+                    fn shared<'a>(
+                        engine: &'a SharedPtr<Engine>,
+                        buffer: Pin<&mut Buffer>,
+                    ) -> Pin<&'a mut Engine>;
+                    fn fill(buffer: &mut Buffer);
+                    fn reset(engine: Pin<&mut Engine>) -> Pin<&mut Engine>;
+                    unsafe fn raw(self: &Engine) -> Pin<&mut Engine>;
+                }
+            }
+        "#;
+
+        let syntax: File = syn::parse_str(source).unwrap();
+        let opt = Opt::default();
+        let references = ["name", "any", "state", "shared", "fill", "reset", "raw"];
+        let references = references.iter().map(|&name| name.to_owned()).collect();
+        let lints = gen::analyze(syntax, &opt, |apis, types| {
+            gen::lint::lint(apis, types, &opt, &references)
+        })
+        .unwrap();
+        let lints: Set<(String, Option<String>)> = lints
+            .into_iter()
+            .map(|lint| (lint.error.to_string(), lint.fix))
+            .collect();
+
+        let expected = [
+            (
+                "`Engine::name` returns a 'static reference from a member function, which lets it outlive `self`",
+                Some("&str"),
+            ),
+            (
+                "lifetime `'a` of the reference returned by `any` is not tied to any argument, so the caller can choose any lifetime for it",
+                None,
+            ),
+            (
+                "`Engine::state` returns a mutable reference borrowed only from shared references, so two calls can make aliasing mutable references",
+                Some("&Buffer"),
+            ),
+            (
+                "`shared` returns a mutable reference borrowed from a shared SharedPtr, and every clone of the SharedPtr can make another mutable reference to the same object",
+                Some("&'a Engine"),
+            ),
+            (
+                "`&mut Buffer` requires the C++ type to be trivially movable, so it must not be self-referential",
+                Some("Pin<&mut Buffer>"),
+            ),
+        ];
+        let expected: Set<(String, Option<String>)> = expected
+            .iter()
+            .map(|&(msg, fix)| (msg.to_owned(), fix.map(str::to_owned)))
+            .collect();
+        assert_eq!(lints, expected);
+    }
+}
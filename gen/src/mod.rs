@@ -1,30 +1,38 @@
 // Functionality that is shared between the cxx_build::bridge entry point and
 // the cxxbridge CLI command.
 
+mod abi;
 mod block;
 mod builtin;
 mod cfg;
 mod check;
+mod docs;
 pub(super) mod error;
 mod file;
 pub(super) mod fs;
+mod fuzz;
 mod ifndef;
 pub(super) mod include;
+mod lint;
 mod names;
 mod namespace;
 mod nested;
 pub(super) mod out;
+mod symbols;
 mod write;
 
 use self::cfg::UnsupportedCfgEvaluator;
-use self::error::{format_err, Result};
+use self::error::{print_err, print_warnings, MessageFormat, Result};
 use self::file::File;
 use self::include::Include;
 use crate::syntax::cfg::CfgExpr;
+use crate::syntax::namespace::Namespace;
 use crate::syntax::report::Errors;
-use crate::syntax::{self, attrs, Types};
+use crate::syntax::{self, attrs, Api, Types};
 use std::collections::BTreeSet as Set;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process;
+use syn::parse::{ParseStream, Parser};
 
 pub(super) use self::error::Error;
 
@@ -61,6 +69,9 @@ pub struct Opt {
     pub(super) gen_implementation: bool,
     pub(super) allow_dot_includes: bool,
     pub(super) doxygen: bool,
+    pub(super) namespace_map: Vec<(Namespace, Namespace)>,
+    pub(super) include_rewrites: Vec<(String, String)>,
+    pub(super) message_format: MessageFormat,
 }
 
 /// Logic to decide whether a conditional compilation attribute is enabled or
@@ -93,6 +104,65 @@ pub struct GeneratedCode {
     pub implementation: Vec<u8>,
 }
 
+/// Results of code generation split into sections, for build systems that lay
+/// out the generated code in files of their own.
+///
+/// More results may be added over time, so this is a non-exhaustive struct.
+#[derive(Default)]
+#[non_exhaustive]
+#[allow(dead_code)] // only read by users of cxx-gen
+pub struct GeneratedSections {
+    /// The parts that the header is made of.
+    pub header: Sections,
+    /// The parts that the implementation file is made of.
+    pub implementation: Sections,
+    /// The files that the bridge includes items from with `include!("...")`.
+    /// The code needs to be generated again when one of them changes, the same
+    /// as when the file containing the bridge changes.
+    pub included_files: Vec<PathBuf>,
+}
+
+// The generated code of a bridge file, along with the files that it includes
+// items from, for cxx-build and cxxbridge to regenerate the code when those
+// change.
+#[derive(Default)]
+pub(super) struct Generated {
+    pub code: GeneratedCode,
+    #[allow(dead_code)] // only used by cxx-build and cxxbridge-cmd, not cxx-gen
+    pub included_files: Vec<PathBuf>,
+}
+
+/// The parts of a generated C++ file.
+///
+/// Concatenating the `#include` lines for `standard_includes` and `includes`
+/// followed by the code sections in the order of the fields gives a file with
+/// the same meaning as the generated one. The `#pragma once` of a header is not
+/// part of any section. Each code section closes every namespace that it opens.
+#[derive(Default)]
+#[non_exhaustive]
+#[allow(dead_code)] // only read by users of cxx-gen
+pub struct Sections {
+    /// The `#include` lines of the standard library and system headers that the
+    /// generated code needs. Some of them are inside of `#if` blocks, such as
+    /// `<string_view>` which is only included in C++17 and newer.
+    pub standard_includes: Vec<u8>,
+    /// The headers from the bridge's `include!` and from [`Opt::include`].
+    pub includes: Vec<Include>,
+    /// Definitions of the parts of "rust/cxx.h" and the internal helpers that
+    /// the generated code uses. These are guarded by `#ifndef`, so they can be
+    /// repeated in any number of files.
+    pub builtins: Vec<u8>,
+    /// Forward declarations of the bridge's structs, enums and opaque types.
+    pub forward_declarations: Vec<u8>,
+    /// Definitions of the bridge's shared structs and enums and of the classes
+    /// of its opaque Rust types, with declarations of their member functions.
+    pub declarations: Vec<u8>,
+    /// The bridge's functions and the glue code behind them. In a header these
+    /// are declarations and inline definitions, and in an implementation file
+    /// the out-of-line definitions and their extern "C" shims.
+    pub definitions: Vec<u8>,
+}
+
 impl Default for Opt {
     fn default() -> Self {
         Opt {
@@ -103,18 +173,141 @@ impl Default for Opt {
             allow_dot_includes: true,
             cfg_evaluator: Box::new(UnsupportedCfgEvaluator),
             doxygen: false,
+            namespace_map: Vec::new(),
+            include_rewrites: Vec::new(),
+            message_format: MessageFormat::Human,
         }
     }
 }
 
-pub(super) fn generate_from_path(path: &Path, opt: &Opt) -> GeneratedCode {
+pub(super) fn generate_from_path(path: &Path, opt: &Opt) -> Generated {
+    match try_generate_from_path(path, opt) {
+        Some(out) => out,
+        None => process::exit(1),
+    }
+}
+
+// Same as generate_from_path, except that errors are printed to stderr without
+// exiting the process, for callers that want to keep going after a failure.
+pub(super) fn try_generate_from_path(path: &Path, opt: &Opt) -> Option<Generated> {
+    from_path(path, opt, generate)
+}
+
+// Writes skeleton C++ definitions of the bridge's extern "C++" functions,
+// including the header generated from the same file, which is named after it.
+#[allow(dead_code)] // only used by cxxbridge-cmd, not cxx-build or cxx-gen
+pub(super) fn stubs_from_path(path: &Path, opt: &Opt) -> Option<Vec<u8>> {
+    let header = if path == Path::new("-") {
+        None
+    } else {
+        Some(format!("{}.h", path.to_string_lossy().replace('\\', "/")))
+    };
+    from_path(path, opt, |syntax, opt| {
+        generate_stubs(syntax, opt, header.as_deref())
+    })
+}
+
+// Writes a Markdown reference of the bridge's types and functions.
+#[allow(dead_code)] // only used by cxxbridge-cmd, not cxx-build or cxx-gen
+pub(super) fn docs_from_path(path: &Path, opt: &Opt) -> Option<Vec<u8>> {
+    let docs = |syntax, opt: &Opt| analyze(syntax, opt, |apis, types| docs::gen(apis, types, opt));
+    from_path(path, opt, docs)
+}
+
+// Writes a cargo-fuzz target calling the bridge's extern "C++" functions.
+#[allow(dead_code)] // only used by cxxbridge-cmd, not cxx-build or cxx-gen
+pub(super) fn fuzz_from_path(path: &Path, opt: &Opt) -> Option<Vec<u8>> {
+    from_path(path, opt, |syntax, opt| analyze(syntax, opt, fuzz::gen))
+}
+
+// Generates gMock classes for the bridge's #[mock] extern "Rust" blocks, split
+// into header and implementation the same as the generated code.
+#[allow(dead_code)] // only used by cxxbridge-cmd, not cxx-build or cxx-gen
+pub(super) fn mocks_from_path(path: &Path, opt: &Opt) -> Option<GeneratedCode> {
+    from_path(path, opt, generate_mocks)
+}
+
+// Lists the extern "C" symbols of the bridge instead of generating C++ code.
+#[allow(dead_code)] // only used by cxxbridge-cmd, not cxx-build or cxx-gen
+pub(super) fn symbols_from_path(path: &Path, opt: &Opt) -> Option<Vec<u8>> {
+    from_path(path, opt, generate_symbols)
+}
+
+// Compares the old and new versions of a bridge, returning a report of the
+// changes and whether any of them break code built against the old version.
+#[allow(dead_code)] // only used by cxxbridge-cmd, not cxx-build or cxx-gen
+pub(super) fn abi_diff_from_paths(old: &Path, new: &Path, opt: &Opt) -> Option<(Vec<u8>, bool)> {
+    let describe =
+        |syntax, opt: &Opt| analyze(syntax, opt, |apis, types| abi::describe(apis, types, opt));
+    let old = from_path(old, opt, describe)?;
+    let new = from_path(new, opt, describe)?;
+    Some(abi::diff(&old, &new))
+}
+
+// Prints lints about the bridge in `path` as warnings, returning how many were
+// found, or None if the bridge has errors. Besides the bridge file itself, the
+// Rust files in `sources` are searched for uses of the extern declarations.
+#[allow(dead_code)] // only used by cxxbridge-cmd, not cxx-build or cxx-gen
+pub(super) fn lint_from_path(path: &Path, sources: &[PathBuf], opt: &Opt) -> Option<usize> {
     let source = match read_to_string(path) {
         Ok(source) => source,
-        Err(err) => format_err(path, "", err),
+        Err(err) => {
+            print_err(path, "", err, opt.message_format);
+            return None;
+        }
     };
-    match generate_from_string(&source, opt) {
-        Ok(out) => out,
-        Err(err) => format_err(path, &source, err),
+    let mut files = Vec::new();
+    files.extend(syn::parse_file(&source).ok());
+    for other in sources {
+        if other != path {
+            // Files which do not parse cannot refer to anything, and rustc will
+            // be the one to report them.
+            let other = fs::read(other)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok());
+            files.extend(other.and_then(|other| syn::parse_file(&other).ok()));
+        }
+    }
+    let references = lint::references(&files);
+    let lints = from_string(&source, source_dir(path), opt, |syntax, opt| {
+        analyze(syntax, opt, |apis, types| {
+            lint::lint(apis, types, opt, &references)
+        })
+    });
+    match lints {
+        Ok(lints) => {
+            let count = lints.len();
+            print_warnings(path, &source, lints, opt.message_format);
+            Some(count)
+        }
+        Err(err) => {
+            print_err(path, &source, err, opt.message_format);
+            None
+        }
+    }
+}
+
+// Every *_from_path function goes through here. Any error in reading the file,
+// parsing the bridge, or generating from it is printed to stderr in the format
+// of opt.message_format, and the result is None.
+fn from_path<T>(
+    path: &Path,
+    opt: &Opt,
+    generate: impl FnOnce(File, &Opt) -> Result<T>,
+) -> Option<T> {
+    let source = match read_to_string(path) {
+        Ok(source) => source,
+        Err(err) => {
+            print_err(path, "", err, opt.message_format);
+            return None;
+        }
+    };
+    match from_string(&source, source_dir(path), opt, generate) {
+        Ok(out) => Some(out),
+        Err(err) => {
+            print_err(path, &source, err, opt.message_format);
+            None
+        }
     }
 }
 
@@ -130,17 +323,98 @@ fn read_to_string(path: &Path) -> Result<String> {
     }
 }
 
-fn generate_from_string(source: &str, opt: &Opt) -> Result<GeneratedCode> {
+// The directory that include!("...") paths in the bridge at `path` are relative
+// to, which for standard input is the directory of the crate's Cargo.toml.
+fn source_dir(path: &Path) -> Option<&Path> {
+    if path == Path::new("-") {
+        None
+    } else {
+        path.parent()
+    }
+}
+
+fn from_string<T>(
+    source: &str,
+    dir: Option<&Path>,
+    opt: &Opt,
+    generate: impl FnOnce(File, &Opt) -> Result<T>,
+) -> Result<T> {
     let mut source = source;
     if source.starts_with("#!") && !source.starts_with("#![") {
         let shebang_end = source.find('\n').unwrap_or(source.len());
         source = &source[shebang_end..];
     }
-    let syntax: File = syn::parse_str(source)?;
+    let syntax = (|input: ParseStream| File::parse_in(input, dir)).parse_str(source)?;
     generate(syntax, opt)
 }
 
-pub(super) fn generate(syntax: File, opt: &Opt) -> Result<GeneratedCode> {
+pub(super) fn generate(syntax: File, opt: &Opt) -> Result<Generated> {
+    let included_files = syntax.included_files();
+    analyze(syntax, opt, |apis, types| {
+        // Some callers may wish to generate both header and implementation from
+        // the same token stream to avoid parsing twice. Others only need to
+        // generate one or the other.
+        let (mut header, mut implementation) = Default::default();
+        if opt.gen_header {
+            header = write::gen(apis, types, opt, true);
+        }
+        if opt.gen_implementation {
+            implementation = write::gen(apis, types, opt, false);
+        }
+        Generated {
+            code: GeneratedCode {
+                header,
+                implementation,
+            },
+            included_files,
+        }
+    })
+}
+
+#[allow(dead_code)] // only used by cxx-gen, not cxx-build or cxxbridge-cmd
+pub(super) fn generate_sections(syntax: File, opt: &Opt) -> Result<GeneratedSections> {
+    let included_files = syntax.included_files();
+    analyze(syntax, opt, |apis, types| {
+        let mut generated = GeneratedSections {
+            included_files,
+            ..GeneratedSections::default()
+        };
+        if opt.gen_header {
+            generated.header = write::gen_sections(apis, types, opt, true);
+        }
+        if opt.gen_implementation {
+            generated.implementation = write::gen_sections(apis, types, opt, false);
+        }
+        generated
+    })
+}
+
+fn generate_stubs(syntax: File, opt: &Opt, header: Option<&str>) -> Result<Vec<u8>> {
+    analyze(syntax, opt, |apis, types| {
+        write::gen_stubs(apis, types, opt, header)
+    })
+}
+
+fn generate_mocks(syntax: File, opt: &Opt) -> Result<GeneratedCode> {
+    analyze(syntax, opt, |apis, types| {
+        let mut generated = GeneratedCode::default();
+        if opt.gen_header {
+            generated.header = write::gen_mocks(apis, types, opt, true);
+        }
+        if opt.gen_implementation {
+            generated.implementation = write::gen_mocks(apis, types, opt, false);
+        }
+        generated
+    })
+}
+
+fn generate_symbols(syntax: File, opt: &Opt) -> Result<Vec<u8>> {
+    analyze(syntax, opt, symbols::gen)
+}
+
+// Parses and checks the bridge modules of the file, then hands the checked
+// APIs to one of the backends above.
+fn analyze<T>(syntax: File, opt: &Opt, backend: impl FnOnce(&[Api], &Types) -> T) -> Result<T> {
     if syntax.modules.is_empty() {
         return Err(Error::NoBridgeMod);
     }
@@ -182,18 +456,5 @@ pub(super) fn generate(syntax: File, opt: &Opt) -> Result<GeneratedCode> {
     check::typecheck(errors, apis, types, generator);
     errors.propagate()?;
 
-    // Some callers may wish to generate both header and implementation from the
-    // same token stream to avoid parsing twice. Others only need to generate
-    // one or the other.
-    let (mut header, mut implementation) = Default::default();
-    if opt.gen_header {
-        header = write::gen(apis, types, opt, true);
-    }
-    if opt.gen_implementation {
-        implementation = write::gen(apis, types, opt, false);
-    }
-    Ok(GeneratedCode {
-        header,
-        implementation,
-    })
+    Ok(backend(apis, types))
 }
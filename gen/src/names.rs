@@ -1,9 +1,10 @@
+use crate::gen::Opt;
 use crate::syntax::Pair;
 
 impl Pair {
-    pub(crate) fn to_fully_qualified(&self) -> String {
+    pub(crate) fn to_fully_qualified(&self, opt: &Opt) -> String {
         let mut fully_qualified = String::new();
-        for segment in &self.namespace {
+        for segment in opt.cxx_namespace(&self.namespace) {
             fully_qualified += "::";
             fully_qualified += &segment.to_string();
         }
@@ -1,5 +1,7 @@
+use crate::gen::Opt;
 use crate::syntax::namespace::Namespace;
 use crate::syntax::Api;
+use proc_macro2::Ident;
 
 impl Api {
     pub(crate) fn namespace(&self) -> &Namespace {
@@ -12,3 +14,28 @@ impl Api {
         }
     }
 }
+
+impl Opt {
+    // The C++ namespace in which to emit an item from the given bridge
+    // namespace. Only the generated C++ code is affected; mangled symbols keep
+    // using the namespace written in the bridge so that they continue to agree
+    // with the Rust side. If more than one mapping applies, the one with the
+    // longest prefix wins.
+    pub(crate) fn cxx_namespace<'a>(&'a self, namespace: &'a Namespace) -> Vec<&'a Ident> {
+        let segments: Vec<&Ident> = namespace.iter().collect();
+        let mut best_match = None;
+        for (from, to) in &self.namespace_map {
+            let len = from.iter().len();
+            if len <= segments.len()
+                && from.iter().eq(segments[..len].iter().copied())
+                && best_match.map_or(true, |(_, best_len)| len > best_len)
+            {
+                best_match = Some((to, len));
+            }
+        }
+        match best_match {
+            Some((to, len)) => to.iter().chain(segments[len..].iter().copied()).collect(),
+            None => segments,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::gen::Opt;
 use crate::syntax::map::UnorderedMap as Map;
 use crate::syntax::Api;
 use proc_macro2::Ident;
@@ -8,8 +9,8 @@ pub(crate) struct NamespaceEntries<'a> {
 }
 
 impl<'a> NamespaceEntries<'a> {
-    pub(crate) fn new(apis: Vec<&'a Api>) -> Self {
-        sort_by_inner_namespace(apis, 0)
+    pub(crate) fn new(apis: Vec<&'a Api>, opt: &'a Opt) -> Self {
+        sort_by_inner_namespace(apis, 0, opt)
     }
 
     pub(crate) fn direct_content(&self) -> &[&'a Api] {
@@ -23,13 +24,17 @@ impl<'a> NamespaceEntries<'a> {
     }
 }
 
-fn sort_by_inner_namespace(apis: Vec<&Api>, depth: usize) -> NamespaceEntries {
+fn sort_by_inner_namespace<'a>(
+    apis: Vec<&'a Api>,
+    depth: usize,
+    opt: &'a Opt,
+) -> NamespaceEntries<'a> {
     let mut direct = Vec::new();
     let mut nested_namespaces = Vec::new();
     let mut index_of_namespace = Map::new();
 
     for api in &apis {
-        if let Some(first_ns_elem) = api.namespace().iter().nth(depth) {
+        if let Some(&first_ns_elem) = opt.cxx_namespace(api.namespace()).get(depth) {
             match index_of_namespace.get(first_ns_elem) {
                 None => {
                     index_of_namespace.insert(first_ns_elem, nested_namespaces.len());
@@ -44,7 +49,7 @@ fn sort_by_inner_namespace(apis: Vec<&Api>, depth: usize) -> NamespaceEntries {
 
     let nested = nested_namespaces
         .into_iter()
-        .map(|(k, apis)| (k, sort_by_inner_namespace(apis, depth + 1)))
+        .map(|(k, apis)| (k, sort_by_inner_namespace(apis, depth + 1, opt)))
         .collect();
 
     NamespaceEntries { direct, nested }
@@ -53,6 +58,7 @@ fn sort_by_inner_namespace(apis: Vec<&Api>, depth: usize) -> NamespaceEntries {
 #[cfg(test)]
 mod tests {
     use super::NamespaceEntries;
+    use crate::gen::Opt;
     use crate::syntax::attrs::OtherAttrs;
     use crate::syntax::cfg::CfgExpr;
     use crate::syntax::namespace::Namespace;
@@ -76,7 +82,8 @@ mod tests {
             make_api(Some("D"), "J"),
         ];
 
-        let root = NamespaceEntries::new(Vec::from_iter(apis));
+        let opt = Opt::default();
+        let root = NamespaceEntries::new(Vec::from_iter(apis), &opt);
 
         // ::
         let root_direct = root.direct_content();
@@ -151,6 +158,12 @@ mod tests {
             bounds: Vec::new(),
             semi_token: Token![;](Span::call_site()),
             trusted: false,
+            incomplete: false,
+            send: false,
+            sync: false,
+            smart_ptr: false,
+            com_iid: None,
+            vocabulary: None,
         })
     }
 }
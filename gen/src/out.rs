@@ -4,8 +4,10 @@ use crate::gen::include::Includes;
 use crate::gen::Opt;
 use crate::syntax::namespace::Namespace;
 use crate::syntax::Types;
+use proc_macro2::Ident;
 use std::cell::RefCell;
 use std::fmt::{self, Arguments, Write};
+use std::mem;
 
 pub(crate) struct OutFile<'a> {
     pub header: bool,
@@ -19,7 +21,7 @@ pub(crate) struct OutFile<'a> {
 #[derive(Default)]
 pub(crate) struct Content<'a> {
     bytes: String,
-    namespace: &'a Namespace,
+    namespace: Vec<&'a Ident>,
     blocks: Vec<BlockBoundary<'a>>,
     section_pending: bool,
     blocks_pending: usize,
@@ -57,6 +59,7 @@ impl<'a> OutFile<'a> {
     }
 
     pub(crate) fn set_namespace(&mut self, namespace: &'a Namespace) {
+        let namespace = self.opt.cxx_namespace(namespace);
         self.content.get_mut().set_namespace(namespace);
     }
 
@@ -87,6 +90,13 @@ impl<'a> OutFile<'a> {
         out.into_bytes()
     }
 
+    // Everything written since the previous call, or since the start, as a
+    // piece of code that can stand on its own: any namespaces and blocks that
+    // are still open get closed at the end.
+    pub(crate) fn take_content(&mut self) -> Vec<u8> {
+        self.content.get_mut().take()
+    }
+
     fn flush(&mut self) {
         self.include.content.flush();
         self.builtin.content.flush();
@@ -124,11 +134,11 @@ impl<'a> Content<'a> {
         self.push_block_boundary(BlockBoundary::End(block));
     }
 
-    pub(crate) fn set_namespace(&mut self, namespace: &'a Namespace) {
-        for name in self.namespace.iter().rev() {
+    pub(crate) fn set_namespace(&mut self, namespace: Vec<&'a Ident>) {
+        for name in mem::take(&mut self.namespace).into_iter().rev() {
             self.end_block(Block::UserDefinedNamespace(name));
         }
-        for name in namespace {
+        for name in &namespace {
             self.begin_block(Block::UserDefinedNamespace(name));
         }
         self.namespace = namespace;
@@ -162,8 +172,13 @@ impl<'a> Content<'a> {
         }
     }
 
+    pub(crate) fn take(&mut self) -> Vec<u8> {
+        self.flush();
+        mem::take(self).bytes.into_bytes()
+    }
+
     fn flush(&mut self) {
-        self.set_namespace(Default::default());
+        self.set_namespace(Vec::new());
         if self.blocks_pending > 0 {
             self.flush_blocks();
         }
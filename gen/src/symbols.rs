@@ -0,0 +1,298 @@
+// Manifest of the extern "C" symbols through which the two halves of a bridge
+// call each other. Each line names the side whose generated code defines the
+// symbol, either `cxx` or `rust`, followed by the symbol; the other side only
+// references it.
+
+use crate::syntax::instantiate::ImplKey;
+use crate::syntax::symbol::Symbol;
+use crate::syntax::{derive, mangle, Api, Trait, Type, Types};
+use std::fmt::{Display, Write};
+
+pub(super) fn gen(apis: &[Api], types: &Types) -> Vec<u8> {
+    let mut out = Manifest::default();
+
+    for api in apis {
+        match api {
+            Api::Struct(strct) => {
+                let derives = &strct.derives;
+                if derive::contains(derives, Trait::PartialEq) {
+                    out.rust(mangle::operator(&strct.name, "eq"));
+                    if !derive::contains(derives, Trait::Eq) {
+                        out.rust(mangle::operator(&strct.name, "ne"));
+                    }
+                }
+                if derive::contains(derives, Trait::PartialOrd) {
+                    out.rust(mangle::operator(&strct.name, "lt"));
+                    out.rust(mangle::operator(&strct.name, "le"));
+                    if !derive::contains(derives, Trait::Ord) {
+                        out.rust(mangle::operator(&strct.name, "gt"));
+                        out.rust(mangle::operator(&strct.name, "ge"));
+                    }
+                }
+                if derive::contains(derives, Trait::Hash) {
+                    out.rust(mangle::operator(&strct.name, "hash"));
+                }
+            }
+            Api::CxxType(ety) => {
+                if derive::contains(&ety.derives, Trait::Hash)
+                    && !types.structs.contains_key(&ety.name.rust)
+                    && !types.enums.contains_key(&ety.name.rust)
+                {
+                    out.cxx(mangle::operator(&ety.name, "hash"));
+                }
+                if ety.com_iid.is_some() {
+                    out.cxx(mangle::operator(&ety.name, "add_ref"));
+                    out.cxx(mangle::operator(&ety.name, "release"));
+                    out.cxx(mangle::operator(&ety.name, "query_interface"));
+                }
+            }
+            Api::RustType(ety) => {
+                out.rust(mangle::operator(&ety.name, "sizeof"));
+                out.rust(mangle::operator(&ety.name, "alignof"));
+                if derive::contains(&ety.derives, Trait::Hash) {
+                    out.rust(mangle::operator(&ety.name, "hash"));
+                }
+            }
+            Api::CxxFunction(efn) => {
+                out.cxx(mangle::extern_fn(efn, types));
+                for arg in &efn.args {
+                    if let Type::Fn(_) = arg.ty {
+                        out.rust(mangle::r_trampoline(efn, &arg.name, types));
+                        out.cxx(mangle::c_trampoline(efn, &arg.name, types));
+                    }
+                }
+            }
+            Api::RustFunction(efn) => out.rust(mangle::extern_fn(efn, types)),
+            _ => {}
+        }
+    }
+
+    for impl_key in types.impls.keys() {
+        match impl_key {
+            ImplKey::RustBox(ident) => {
+                let instance = types.resolve(ident.rust).name.to_symbol();
+                out.rust_instance("box", &instance, &["alloc", "dealloc", "drop"]);
+                if types.rust_default.contains(ident.rust) {
+                    out.rust_instance("box", &instance, &["default"]);
+                }
+            }
+            ImplKey::RustVec(ident) => {
+                let instance = types.resolve(ident.rust).name.to_symbol();
+                out.rust_instance(
+                    "rust_vec",
+                    &instance,
+                    &[
+                        "new",
+                        "drop",
+                        "len",
+                        "capacity",
+                        "data",
+                        "reserve_total",
+                        "set_len",
+                        "truncate",
+                    ],
+                );
+            }
+            ImplKey::UniquePtr(ident) => {
+                let instance = types.resolve(ident.rust).name.to_symbol();
+                let maybe_trivial = types.is_maybe_trivial(ident.rust);
+                unique_ptr(&mut out, &instance, maybe_trivial);
+            }
+            ImplKey::UniqueArray(ident) => {
+                let element = types.resolve(ident.rust).name.to_symbol();
+                out.cxx_instance(
+                    "unique_ptr$array",
+                    &element,
+                    &["null", "raw", "get", "release", "drop"],
+                );
+            }
+            ImplKey::SharedPtr(ident) => {
+                let instance = types.resolve(ident.rust).name.to_symbol();
+                out.cxx_instance("shared_ptr", &instance, &["null"]);
+                if types.is_maybe_trivial(ident.rust) {
+                    out.cxx_instance("shared_ptr", &instance, &["uninit"]);
+                }
+                out.cxx_instance(
+                    "shared_ptr",
+                    &instance,
+                    &["raw", "clone", "get", "use_count", "drop"],
+                );
+            }
+            ImplKey::SharedArray(ident) => {
+                let element = types.resolve(ident.rust).name.to_symbol();
+                out.cxx_instance(
+                    "shared_ptr$array",
+                    &element,
+                    &["null", "raw", "clone", "alias", "get", "use_count", "drop"],
+                );
+            }
+            ImplKey::SharedConstPtr(ident) => {
+                let target = types.resolve(ident.rust).name.to_symbol();
+                out.cxx_instance(
+                    "shared_ptr$const",
+                    &target,
+                    &["null", "from_mut", "clone", "get", "use_count", "drop"],
+                );
+            }
+            ImplKey::WeakPtr(ident) => {
+                let instance = types.resolve(ident.rust).name.to_symbol();
+                out.cxx_instance(
+                    "weak_ptr",
+                    &instance,
+                    &["null", "clone", "downgrade", "upgrade", "use_count", "drop"],
+                );
+            }
+            ImplKey::CxxVector(ident) => {
+                let element = types.resolve(ident.rust).name.to_symbol();
+                out.cxx_instance(
+                    "std$vector",
+                    &element,
+                    &[
+                        "new",
+                        "size",
+                        "capacity",
+                        "get_unchecked",
+                        "reserve",
+                        "truncate",
+                    ],
+                );
+                if types.is_maybe_trivial(ident.rust) {
+                    out.cxx_instance("std$vector", &element, &["push_back", "pop_back"]);
+                }
+                let instance = format!("std$vector${}", element);
+                unique_ptr(&mut out, &instance, false);
+            }
+            ImplKey::CxxPmrVector(ident) => {
+                let element = types.resolve(ident.rust).name.to_symbol();
+                out.cxx_instance(
+                    "std$pmr$vector",
+                    &element,
+                    &[
+                        "new_in",
+                        "size",
+                        "capacity",
+                        "get_unchecked",
+                        "reserve",
+                        "truncate",
+                        "resource",
+                    ],
+                );
+                if types.is_maybe_trivial(ident.rust) {
+                    out.cxx_instance("std$pmr$vector", &element, &["push_back", "pop_back"]);
+                }
+                let instance = format!("std$pmr$vector${}", element);
+                unique_ptr(&mut out, &instance, false);
+            }
+            ImplKey::CustomPtr(family, ident) => {
+                let family = types.resolve(*family).name.to_symbol();
+                let target = types.resolve(ident.rust).name.to_symbol();
+                let instance = format!("{}${}", family, target);
+                out.cxx_instance("custom_ptr", &instance, &["null", "clone", "get", "drop"]);
+            }
+        }
+    }
+
+    out.0.into_bytes()
+}
+
+fn unique_ptr(out: &mut Manifest, instance: &dyn Display, can_construct_from_value: bool) {
+    out.cxx_instance("unique_ptr", instance, &["null"]);
+    if can_construct_from_value {
+        out.cxx_instance("unique_ptr", instance, &["uninit"]);
+    }
+    out.cxx_instance("unique_ptr", instance, &["raw", "get", "release", "drop"]);
+}
+
+#[derive(Default)]
+struct Manifest(String);
+
+impl Manifest {
+    fn cxx(&mut self, symbol: Symbol) {
+        let _ = writeln!(self.0, "cxx {}", symbol);
+    }
+
+    fn rust(&mut self, symbol: Symbol) {
+        let _ = writeln!(self.0, "rust {}", symbol);
+    }
+
+    fn cxx_instance(&mut self, builtin: &str, instance: &dyn Display, names: &[&str]) {
+        self.instance("cxx", builtin, instance, names);
+    }
+
+    fn rust_instance(&mut self, builtin: &str, instance: &dyn Display, names: &[&str]) {
+        self.instance("rust", builtin, instance, names);
+    }
+
+    fn instance(&mut self, side: &str, builtin: &str, instance: &dyn Display, names: &[&str]) {
+        for name in names {
+            let _ = writeln!(
+                self.0,
+                "{} cxxbridge1${}${}${}",
+                side, builtin, instance, name,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::{self, file::File, Opt};
+    use std::collections::BTreeSet as Set;
+
+    #[test]
+    fn test_symbols_match_implementation() {
+        let source = r#"
+            #[cxx::bridge(namespace = "tests")]
+            mod ffi {
+                #[derive(PartialEq, PartialOrd, Hash)]
+                struct Shared {
+                    z: usize,
+                }
+
+                extern "Rust" {
+                    type R;
+                    fn r_fn(r: &R, v: Vec<Shared>) -> Box<R>;
+                }
+
+                unsafe extern "C++" {
+                    type C;
+                    fn c_fn(f: fn(usize)) -> UniquePtr<C>;
+                    fn c_vec() -> UniquePtr<CxxVector<Shared>>;
+                    fn c_pmr_vec() -> UniquePtr<CxxPmrVector<Shared>>;
+                    fn c_shared() -> SharedPtr<C>;
+                    fn c_weak(c: &SharedPtr<C>) -> WeakPtr<C>;
+
+                    #[namespace = "boost"]
+                    #[cxx_name = "intrusive_ptr"]
+                    #[smart_ptr]
+                    type IntrusivePtr;
+                    fn c_custom() -> CustomPtr<IntrusivePtr, C>;
+
+                    #[com_interface = "00000000-0000-0000-C000-000000000046"]
+                    type IUnknown;
+                    fn QueryFirst(self: &IUnknown) -> Result<ComPtr<IUnknown>, HRESULT>;
+                }
+            }
+        "#;
+
+        let syntax: File = syn::parse_str(source).unwrap();
+        let opt = Opt::default();
+        let implementation = gen::generate(syntax, &opt).unwrap().code.implementation;
+        let implementation = String::from_utf8(implementation).unwrap();
+
+        let syntax: File = syn::parse_str(source).unwrap();
+        let manifest = gen::generate_symbols(syntax, &opt).unwrap();
+        let manifest = String::from_utf8(manifest).unwrap();
+        let listed: Set<&str> = manifest
+            .lines()
+            .map(|line| line.split_once(' ').unwrap().1)
+            .collect();
+
+        let referenced: Set<&str> = implementation
+            .split(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_' && ch != '$')
+            .filter(|word| word.contains("cxxbridge1$"))
+            .collect();
+
+        assert_eq!(listed, referenced);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::gen::block::Block;
+use crate::gen::include::{self, Include};
 use crate::gen::nested::NamespaceEntries;
 use crate::gen::out::OutFile;
-use crate::gen::{builtin, include, Opt};
+use crate::gen::{builtin, Opt, Sections};
 use crate::syntax::atom::Atom::{self, *};
 use crate::syntax::instantiate::{ImplKey, NamedImplKey};
 use crate::syntax::map::UnorderedMap as Map;
@@ -11,8 +12,8 @@ use crate::syntax::set::UnorderedSet;
 use crate::syntax::symbol::{self, Symbol};
 use crate::syntax::trivial::{self, TrivialReason};
 use crate::syntax::{
-    derive, mangle, Api, Doc, Enum, ExternFn, ExternType, FnKind, Lang, Pair, Signature, Struct,
-    Trait, Type, TypeAlias, Types, Var,
+    derive, mangle, Api, Doc, Enum, ExternFn, ExternType, FnKind, IncludeKind, Lang, Pair,
+    Signature, Struct, Trait, Type, TypeAlias, Types, Var,
 };
 use proc_macro2::Ident;
 
@@ -35,6 +36,243 @@ pub(super) fn gen(apis: &[Api], types: &Types, opt: &Opt, header: bool) -> Vec<u
     out_file.content()
 }
 
+// The same code as gen, split into the parts that it is assembled from. Each of
+// the code sections closes any namespace that it opens, so that they can be
+// spliced into a file separately.
+pub(super) fn gen_sections(apis: &[Api], types: &Types, opt: &Opt, header: bool) -> Sections {
+    let mut out_file = OutFile::new(header, opt, types);
+    let out = &mut out_file;
+
+    pick_includes_and_builtins(out, apis);
+    out.include.extend(&opt.include);
+
+    write_forward_declarations(out, apis);
+    let forward_declarations = out.take_content();
+    write_macros(out, apis);
+    write_data_structures(out, apis);
+    let declarations = out.take_content();
+    write_functions(out, apis);
+    write_generic_instantiations(out);
+    let definitions = out.take_content();
+
+    builtin::write(out);
+    let builtins = out.builtin.content.take();
+    include::write_standard(&mut out.include);
+    let standard_includes = out.include.content.take();
+    let includes = include::custom(opt, &out.include);
+
+    Sections {
+        standard_includes,
+        includes,
+        builtins,
+        forward_declarations,
+        declarations,
+        definitions,
+    }
+}
+
+// Skeleton definitions of the functions in the bridge's extern "C++" blocks,
+// as a starting point for writing the C++ side of a new bridge. The bridge's
+// generated header, if its path is known, is included first, ahead of the
+// headers named by the bridge.
+pub(super) fn gen_stubs(apis: &[Api], types: &Types, opt: &Opt, header: Option<&str>) -> Vec<u8> {
+    let mut out_file = OutFile::new(false, opt, types);
+    let out = &mut out_file;
+
+    if let Some(header) = header {
+        out.include.insert(Include {
+            path: header.to_owned(),
+            kind: IncludeKind::Quoted,
+        });
+    }
+    for api in apis {
+        if let Api::Include(include) = api {
+            out.include.insert(include);
+        }
+    }
+    out.include.extend(&opt.include);
+    if !out.include.has_cxx_header() {
+        out.include.insert(Include {
+            path: "rust/cxx.h".to_owned(),
+            kind: IncludeKind::Quoted,
+        });
+    }
+
+    for api in apis {
+        if let Api::CxxFunction(efn) = api {
+            write_cxx_function_stub(out, efn);
+        }
+    }
+
+    include::write(out);
+
+    out_file.content()
+}
+
+// gMock classes standing in for the functions of the bridge's extern "Rust"
+// blocks that have a #[mock = Name] attribute, so that C++ code calling them
+// can be tested without linking the Rust implementation. The header defines a
+// class `MockName` with a MOCK_METHOD for every function of the blocks, and the
+// implementation file defines each of the functions to call the corresponding
+// method of whichever `MockName` currently exists.
+pub(super) fn gen_mocks(apis: &[Api], types: &Types, opt: &Opt, header: bool) -> Vec<u8> {
+    let mut out_file = OutFile::new(header, opt, types);
+    let out = &mut out_file;
+
+    let mut mocks: Vec<(&Ident, Vec<&ExternFn>)> = Vec::new();
+    for api in apis {
+        match api {
+            Api::Include(include) => out.include.insert(include),
+            Api::RustFunction(efn) if !is_main(efn) => {
+                if let Some(mock) = &efn.mock {
+                    match mocks.iter_mut().find(|(name, _)| *name == mock) {
+                        Some((_, efns)) => efns.push(efn),
+                        None => mocks.push((mock, vec![efn])),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    out.include.insert(Include {
+        path: "gmock/gmock.h".to_owned(),
+        kind: IncludeKind::Bracketed,
+    });
+    out.include.extend(&opt.include);
+    if !out.include.has_cxx_header() {
+        out.include.insert(Include {
+            path: "rust/cxx.h".to_owned(),
+            kind: IncludeKind::Quoted,
+        });
+    }
+    out.include.stdexcept = true;
+    out.include.string = true;
+    out.include.utility = true;
+
+    for (mock, efns) in &mocks {
+        let class = format!("Mock{}", mock);
+        let namespace = &efns[0].name.namespace;
+        write_mock_class(out, &class, namespace, efns);
+        if !header {
+            let mut qualified = String::new();
+            for segment in opt.cxx_namespace(namespace) {
+                qualified += &format!("::{}", segment);
+            }
+            qualified += &format!("::{}", class);
+            for efn in efns {
+                write_mock_function(out, &qualified, efn);
+            }
+        }
+    }
+
+    include::write(out);
+
+    out_file.content()
+}
+
+fn write_mock_class<'a>(
+    out: &mut OutFile<'a>,
+    class: &str,
+    namespace: &'a Namespace,
+    efns: &[&ExternFn],
+) {
+    out.next_section();
+    out.set_namespace(namespace);
+    let guard = format!("CXXBRIDGE1_MOCK_{}", symbol::join(&[namespace, &class]));
+    writeln!(out, "#ifndef {}", guard);
+    writeln!(out, "#define {}", guard);
+    writeln!(out, "class {} {{", class);
+    writeln!(out, "public:");
+    writeln!(out, "  {}() noexcept {{ instance() = this; }}", class);
+    writeln!(out, "  ~{}() {{ instance() = nullptr; }}", class);
+    writeln!(out, "  {0}(const {0} &) = delete;", class);
+    writeln!(out, "  {0} &operator=(const {0} &) = delete;", class);
+    writeln!(out);
+    for efn in efns {
+        write!(out, "  MOCK_METHOD((");
+        match &efn.ret {
+            None => write!(out, "void"),
+            Some(ret) => write_type(out, ret),
+        }
+        write!(out, "), {}, (", efn.name.cxx);
+        let mut params = 0;
+        if let Some(receiver) = efn.receiver() {
+            let self_type = out.types.resolve(&receiver.ty).name;
+            let constness = if receiver.mutable { "" } else { "const " };
+            let self_type = self_type.to_fully_qualified(out.opt);
+            write!(out, "({}{} &)", constness, self_type);
+            params += 1;
+        }
+        for arg in &efn.args {
+            if params > 0 {
+                write!(out, ", ");
+            }
+            write!(out, "(");
+            write_type(out, &arg.ty);
+            write!(out, ")");
+            params += 1;
+        }
+        writeln!(out, "), ());");
+    }
+    writeln!(out);
+    writeln!(out, "  static {} &get(const char *function) {{", class);
+    writeln!(out, "    if (instance() == nullptr) {{");
+    writeln!(
+        out,
+        "      throw ::std::logic_error(::std::string(function) + \" called while no {} exists\");",
+        class,
+    );
+    writeln!(out, "    }}");
+    writeln!(out, "    return *instance();");
+    writeln!(out, "  }}");
+    writeln!(out);
+    writeln!(out, "private:");
+    writeln!(out, "  static {} *&instance() noexcept {{", class);
+    writeln!(out, "    static {} *mock = nullptr;", class);
+    writeln!(out, "    return mock;");
+    writeln!(out, "  }}");
+    writeln!(out, "}};");
+    writeln!(out, "#endif // {}", guard);
+}
+
+fn write_mock_function<'a>(out: &mut OutFile<'a>, class: &str, efn: &'a ExternFn) {
+    out.next_section();
+    out.set_namespace(&efn.name.namespace);
+    let local_name = match efn.self_type() {
+        None => efn.name.cxx.to_string(),
+        Some(self_type) => format!(
+            "{}::{}",
+            out.types.resolve(self_type).name.cxx,
+            efn.name.cxx,
+        ),
+    };
+    let in_class = false;
+    let indirect_call = false;
+    let main = false;
+    write_rust_function_shim_decl(out, &local_name, efn, in_class, indirect_call, main);
+    writeln!(out, " {{");
+    write!(
+        out,
+        "  return {}::get(\"{}\").{}(",
+        class, local_name, efn.name.cxx,
+    );
+    let mut args = 0;
+    if efn.receiver().is_some() {
+        write!(out, "*this");
+        args += 1;
+    }
+    for arg in &efn.args {
+        if args > 0 {
+            write!(out, ", ");
+        }
+        write!(out, "::std::move({})", arg.name.cxx);
+        args += 1;
+    }
+    writeln!(out, ");");
+    writeln!(out, "}}");
+}
+
 fn write_macros(out: &mut OutFile, apis: &[Api]) {
     let mut needs_default_value = false;
     for api in apis {
@@ -59,13 +297,16 @@ fn write_macros(out: &mut OutFile, apis: &[Api]) {
 
 fn write_forward_declarations(out: &mut OutFile, apis: &[Api]) {
     let needs_forward_declaration = |api: &&Api| match api {
-        Api::Struct(_) | Api::CxxType(_) | Api::RustType(_) => true,
+        Api::Struct(_) | Api::RustType(_) => true,
+        Api::CxxType(ety) => !ety.smart_ptr && ety.vocabulary.is_none(),
         Api::Enum(enm) => !out.types.cxx.contains(&enm.name.rust),
         _ => false,
     };
 
-    let apis_by_namespace =
-        NamespaceEntries::new(apis.iter().filter(needs_forward_declaration).collect());
+    let apis_by_namespace = NamespaceEntries::new(
+        apis.iter().filter(needs_forward_declaration).collect(),
+        out.opt,
+    );
 
     out.next_section();
     write(out, &apis_by_namespace, 0);
@@ -153,10 +394,14 @@ fn write_data_structures<'a>(out: &mut OutFile<'a>, apis: &'a [Api]) {
 
     out.next_section();
     for api in apis {
-        if let Api::TypeAlias(ety) = api {
-            if let Some(reasons) = out.types.required_trivial.get(&ety.name.rust) {
-                check_trivial_extern_type(out, ety, reasons);
+        match api {
+            Api::TypeAlias(ety) => {
+                if let Some(reasons) = out.types.required_trivial.get(&ety.name.rust) {
+                    check_trivial_extern_type(out, ety, reasons);
+                }
             }
+            Api::CxxType(ety) if ety.send || ety.sync => check_send_sync(out, ety),
+            _ => {}
         }
     }
 }
@@ -169,13 +414,36 @@ fn write_functions<'a>(out: &mut OutFile<'a>, apis: &'a [Api]) {
                 Api::RustType(ety) => write_opaque_type_layout_decls(out, ety),
                 Api::CxxFunction(efn) => write_cxx_function_shim(out, efn),
                 Api::RustFunction(efn) => write_rust_function_decl(out, efn),
+                Api::CxxType(ety) => {
+                    if derive::contains(&ety.derives, Trait::Hash)
+                        && !out.types.structs.contains_key(&ety.name.rust)
+                        && !out.types.enums.contains_key(&ety.name.rust)
+                    {
+                        write_cxx_type_hash(out, ety);
+                    }
+                    if ety.com_iid.is_some() {
+                        write_com_interface(out, ety);
+                    }
+                }
                 _ => {}
             }
         }
+    }
 
-        write_std_specializations(out, apis);
+    for api in apis {
+        match api {
+            Api::Struct(strct) if derive::contains(&strct.derives, Trait::Hash) => {
+                write_hash_decl(out, &strct.name);
+            }
+            Api::RustType(ety) if derive::contains(&ety.derives, Trait::Hash) => {
+                write_hash_decl(out, &ety.name);
+            }
+            _ => {}
+        }
     }
 
+    write_std_specializations(out, apis);
+
     for api in apis {
         match api {
             Api::Struct(strct) => write_struct_operators(out, strct),
@@ -194,27 +462,30 @@ fn write_std_specializations(out: &mut OutFile, apis: &[Api]) {
     out.begin_block(Block::Namespace("std"));
 
     for api in apis {
-        if let Api::Struct(strct) = api {
-            if derive::contains(&strct.derives, Trait::Hash) {
-                out.next_section();
-                out.include.cstddef = true;
-                out.include.functional = true;
-                let qualified = strct.name.to_fully_qualified();
-                writeln!(out, "template <> struct hash<{}> {{", qualified);
-                writeln!(
-                    out,
-                    "  ::std::size_t operator()({} const &self) const noexcept {{",
-                    qualified,
-                );
-                let link_name = mangle::operator(&strct.name, "hash");
-                write!(out, "    return ::");
-                for name in &strct.name.namespace {
-                    write!(out, "{}::", name);
-                }
-                writeln!(out, "{}(self);", link_name);
-                writeln!(out, "  }}");
-                writeln!(out, "}};");
+        let (name, derives) = match api {
+            Api::Struct(strct) => (&strct.name, &strct.derives),
+            Api::RustType(ety) => (&ety.name, &ety.derives),
+            _ => continue,
+        };
+        if derive::contains(derives, Trait::Hash) {
+            out.next_section();
+            out.include.cstddef = true;
+            out.include.functional = true;
+            let qualified = name.to_fully_qualified(out.opt);
+            writeln!(out, "template <> struct hash<{}> {{", qualified);
+            writeln!(
+                out,
+                "  ::std::size_t operator()({} const &self) const noexcept {{",
+                qualified,
+            );
+            let link_name = mangle::operator(name, "hash");
+            write!(out, "    return ::");
+            for name in out.opt.cxx_namespace(&name.namespace) {
+                write!(out, "{}::", name);
             }
+            writeln!(out, "{}(self);", link_name);
+            writeln!(out, "  }}");
+            writeln!(out, "}};");
         }
     }
 
@@ -224,7 +495,12 @@ fn write_std_specializations(out: &mut OutFile, apis: &[Api]) {
 fn pick_includes_and_builtins(out: &mut OutFile, apis: &[Api]) {
     for api in apis {
         if let Api::Include(include) = api {
-            out.include.insert(include);
+            // A #[private] include holds definitions that only the generated
+            // implementation file gets to see, such as the pimpl type behind an
+            // #[incomplete] extern type.
+            if !(out.header && include.private) {
+                out.include.insert(include);
+            }
         }
     }
 
@@ -240,14 +516,31 @@ fn pick_includes_and_builtins(out: &mut OutFile, apis: &[Api]) {
             },
             Type::RustBox(_) => out.builtin.rust_box = true,
             Type::RustVec(_) => out.builtin.rust_vec = true,
-            Type::UniquePtr(_) => out.include.memory = true,
-            Type::SharedPtr(_) | Type::WeakPtr(_) => out.include.memory = true,
+            Type::UniquePtr(_) | Type::UniqueArray(_) => out.include.memory = true,
+            Type::ComPtr(_) => out.builtin.rust_com_ptr = true,
+            Type::SharedPtr(_)
+            | Type::SharedArray(_)
+            | Type::SharedConstPtr(_)
+            | Type::WeakPtr(_) => {
+                out.include.memory = true;
+            }
             Type::Str(_) => out.builtin.rust_str = true,
             Type::CxxVector(_) => out.include.vector = true,
+            Type::CxxPmrVector(_) => {
+                out.include.memory_resource = true;
+                out.include.vector = true;
+            }
             Type::Fn(_) => out.builtin.rust_fn = true,
             Type::SliceRef(_) => out.builtin.rust_slice = true,
+            Type::HashMapRef(_) => out.builtin.rust_hashmap = true,
             Type::Array(_) => out.include.array = true,
-            Type::Ref(_) | Type::Void(_) | Type::Ptr(_) => {}
+            Type::Ref(_) | Type::Void(_) | Type::Ptr(_) | Type::CustomPtr(_) => {}
+        }
+    }
+
+    for impl_key in out.types.impls.keys() {
+        if let ImplKey::RustBox(_) = impl_key {
+            out.builtin.rust_box = true;
         }
     }
 }
@@ -370,6 +663,14 @@ fn write_struct<'a>(out: &mut OutFile<'a>, strct: &'a Struct, methods: &[&Extern
         );
     }
 
+    if has_incomplete_unique_ptr(out, strct) {
+        out.next_section();
+        writeln!(out, "  {}() noexcept;", strct.name.cxx);
+        writeln!(out, "  ~{}() noexcept;", strct.name.cxx);
+        writeln!(out, "  {0}({0} &&) noexcept;", strct.name.cxx);
+        writeln!(out, "  {0} &operator=({0} &&) noexcept;", strct.name.cxx);
+    }
+
     out.include.type_traits = true;
     writeln!(out, "  using IsRelocatable = ::std::true_type;");
 
@@ -377,6 +678,20 @@ fn write_struct<'a>(out: &mut OutFile<'a>, strct: &'a Struct, methods: &[&Extern
     writeln!(out, "#endif // {}", guard);
 }
 
+// A struct holding a UniquePtr to an #[incomplete] type can only be
+// constructed, destroyed, or assigned where that type is complete, so its
+// special member functions are declared in the header but defined in the
+// generated implementation file next to the #[private] includes.
+fn has_incomplete_unique_ptr(out: &OutFile, strct: &Struct) -> bool {
+    strct.fields.iter().any(|field| match &field.ty {
+        Type::UniquePtr(ptr) => match &ptr.inner {
+            Type::Ident(inner) => out.types.incomplete.contains(&inner.rust),
+            _ => false,
+        },
+        _ => false,
+    })
+}
+
 fn write_struct_decl(out: &mut OutFile, ident: &Pair) {
     writeln!(out, "struct {};", ident.cxx);
 }
@@ -388,7 +703,12 @@ fn write_enum_decl(out: &mut OutFile, enm: &Enum) {
 }
 
 fn write_struct_using(out: &mut OutFile, ident: &Pair) {
-    writeln!(out, "using {} = {};", ident.cxx, ident.to_fully_qualified());
+    writeln!(
+        out,
+        "using {} = {};",
+        ident.cxx,
+        ident.to_fully_qualified(out.opt)
+    );
 }
 
 fn write_opaque_type<'a>(out: &mut OutFile<'a>, ety: &'a ExternType, methods: &[&ExternFn]) {
@@ -426,6 +746,12 @@ fn write_opaque_type<'a>(out: &mut OutFile<'a>, ety: &'a ExternType, methods: &[
     writeln!(out, "  ~{}() = delete;", ety.name.cxx);
     writeln!(out);
 
+    if out.types.rust_default.contains(&ety.name.rust) {
+        out.include.type_traits = true;
+        writeln!(out, "  using IsRustDefault = ::std::true_type;");
+        writeln!(out);
+    }
+
     out.builtin.layout = true;
     out.include.cstddef = true;
     writeln!(out, "private:");
@@ -511,7 +837,7 @@ fn check_trivial_extern_type(out: &mut OutFile, alias: &TypeAlias, reasons: &[Tr
     //    + struct rust::IsRelocatable<MyType> : std::true_type {};
     //
 
-    let id = alias.name.to_fully_qualified();
+    let id = alias.name.to_fully_qualified(out.opt);
     out.builtin.relocatable = true;
     writeln!(out, "static_assert(");
     if reasons
@@ -541,6 +867,32 @@ fn check_trivial_extern_type(out: &mut OutFile, alias: &TypeAlias, reasons: &[Tr
     );
 }
 
+fn check_send_sync(out: &mut OutFile, ety: &ExternType) {
+    // The bridge's #[send] and #[sync] attributes generate unsafe impls of
+    // Send and Sync on the Rust side. The C++ side has no notion of these
+    // traits, so the claim is only accepted if the C++ type makes the same
+    // one; see the comment on rust::IsSend in cxx.h.
+
+    let id = ety.name.to_fully_qualified(out.opt);
+    out.builtin.send_sync = true;
+    for (present, trait_name, what) in [
+        (ety.send, "IsSend", "sent to another thread"),
+        (ety.sync, "IsSync", "shared between threads"),
+    ] {
+        if present {
+            writeln!(out, "static_assert(");
+            writeln!(out, "    ::rust::{}<{}>::value,", trait_name, id);
+            writeln!(
+                out,
+                "    \"type {} is declared in the bridge as safe to be {}, which the C++ type needs to confirm with `using {} = std::true_type;`\");",
+                id.trim_start_matches("::"),
+                what,
+                trait_name,
+            );
+        }
+    }
+}
+
 fn write_struct_operator_decls<'a>(out: &mut OutFile<'a>, strct: &'a Struct) {
     out.set_namespace(&strct.name.namespace);
     out.begin_block(Block::ExternC);
@@ -595,16 +947,80 @@ fn write_struct_operator_decls<'a>(out: &mut OutFile<'a>, strct: &'a Struct) {
         }
     }
 
-    if derive::contains(&strct.derives, Trait::Hash) {
-        out.include.cstddef = true;
-        let link_name = mangle::operator(&strct.name, "hash");
+    out.end_block(Block::ExternC);
+}
+
+// Declared in the header too, for the std::hash specialization that calls it.
+fn write_hash_decl<'a>(out: &mut OutFile<'a>, name: &'a Pair) {
+    out.set_namespace(&name.namespace);
+    out.begin_block(Block::ExternC);
+    out.include.cstddef = true;
+    let link_name = mangle::operator(name, "hash");
+    writeln!(
+        out,
+        "::std::size_t {}({} const &) noexcept;",
+        link_name, name.cxx,
+    );
+    out.end_block(Block::ExternC);
+}
+
+// The other direction: a Rust Hash impl for an opaque C++ type in terms of its
+// std::hash specialization.
+fn write_cxx_type_hash<'a>(out: &mut OutFile<'a>, ety: &'a ExternType) {
+    out.next_section();
+    out.set_namespace(&ety.name.namespace);
+    out.begin_block(Block::ExternC);
+    out.include.cstddef = true;
+    out.include.functional = true;
+    let link_name = mangle::operator(&ety.name, "hash");
+    let qualified = ety.name.to_fully_qualified(out.opt);
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::size_t {}({} const &self) noexcept {{",
+        link_name, ety.name.cxx,
+    );
+    writeln!(out, "  return ::std::hash<{}>{{}}(self);", qualified);
+    writeln!(out, "}}");
+    out.end_block(Block::ExternC);
+}
+
+// Reference counting and QueryInterface of a #[com_interface] type, for
+// ComPtr<T> on the Rust side. Resolved against whatever IUnknown and GUID the
+// includes of the bridge declare.
+fn write_com_interface<'a>(out: &mut OutFile<'a>, ety: &'a ExternType) {
+    out.next_section();
+    out.set_namespace(&ety.name.namespace);
+    out.begin_block(Block::ExternC);
+    out.include.cstdint = true;
+    for (op, method) in [("add_ref", "AddRef"), ("release", "Release")] {
+        let link_name = mangle::operator(&ety.name, op);
+        begin_function_definition(out);
         writeln!(
             out,
-            "::std::size_t {}({} const &) noexcept;",
-            link_name, strct.name.cxx,
+            "::std::uint32_t {}({} *self) noexcept {{",
+            link_name, ety.name.cxx,
+        );
+        writeln!(
+            out,
+            "  return static_cast<::std::uint32_t>(self->{}());",
+            method,
         );
+        writeln!(out, "}}");
     }
-
+    let link_name = mangle::operator(&ety.name, "query_interface");
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::int32_t {}({} *self, void const *iid, void **out) noexcept {{",
+        link_name, ety.name.cxx,
+    );
+    writeln!(out, "  return static_cast<::std::int32_t>(");
+    writeln!(
+        out,
+        "      self->QueryInterface(*static_cast<::GUID const *>(iid), out));",
+    );
+    writeln!(out, "}}");
     out.end_block(Block::ExternC);
 }
 
@@ -615,6 +1031,18 @@ fn write_struct_operators<'a>(out: &mut OutFile<'a>, strct: &'a Struct) {
 
     out.set_namespace(&strct.name.namespace);
 
+    if has_incomplete_unique_ptr(out, strct) {
+        out.next_section();
+        writeln!(out, "{0}::{0}() noexcept = default;", strct.name.cxx);
+        writeln!(out, "{0}::~{0}() noexcept = default;", strct.name.cxx);
+        writeln!(out, "{0}::{0}({0} &&) noexcept = default;", strct.name.cxx,);
+        writeln!(
+            out,
+            "{0} &{0}::operator=({0} &&) noexcept = default;",
+            strct.name.cxx,
+        );
+    }
+
     if derive::contains(&strct.derives, Trait::PartialEq) {
         out.next_section();
         writeln!(
@@ -740,6 +1168,11 @@ fn begin_function_definition(out: &mut OutFile) {
 }
 
 fn write_cxx_function_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
+    if efn.hresult.is_some() {
+        write_cxx_hresult_shim(out, efn);
+        return;
+    }
+
     out.next_section();
     out.set_namespace(&efn.name.namespace);
     out.begin_block(Block::ExternC);
@@ -756,7 +1189,10 @@ fn write_cxx_function_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
         write!(
             out,
             "{}",
-            out.types.resolve(&receiver.ty).name.to_fully_qualified(),
+            out.types
+                .resolve(&receiver.ty)
+                .name
+                .to_fully_qualified(out.opt),
         );
         if !receiver.mutable {
             write!(out, " const");
@@ -793,13 +1229,19 @@ fn write_cxx_function_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
     }
     writeln!(out, " {{");
     write!(out, "  ");
-    write_return_type(out, &efn.ret);
+    match &efn.ret_vocabulary {
+        Some(vocabulary) => write!(out, "{} ", vocabulary.to_fully_qualified(out.opt)),
+        None => write_return_type(out, &efn.ret),
+    }
     match efn.receiver() {
         None => write!(out, "(*{}$)(", efn.name.rust),
         Some(receiver) => write!(
             out,
             "({}::*{}$)(",
-            out.types.resolve(&receiver.ty).name.to_fully_qualified(),
+            out.types
+                .resolve(&receiver.ty)
+                .name
+                .to_fully_qualified(out.opt),
             efn.name.rust,
         ),
     }
@@ -807,7 +1249,10 @@ fn write_cxx_function_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
         if i > 0 {
             write!(out, ", ");
         }
-        write_type(out, &arg.ty);
+        match &arg.vocabulary {
+            Some(vocabulary) => write!(out, "{}", vocabulary.to_fully_qualified(out.opt)),
+            None => write_type(out, &arg.ty),
+        }
     }
     write!(out, ")");
     if let Some(receiver) = efn.receiver() {
@@ -817,24 +1262,49 @@ fn write_cxx_function_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
     }
     write!(out, " = ");
     match efn.self_type() {
-        None => write!(out, "{}", efn.name.to_fully_qualified()),
+        None => write!(out, "{}", efn.name.to_fully_qualified(out.opt)),
         Some(self_type) => write!(
             out,
             "&{}::{}",
-            out.types.resolve(self_type).name.to_fully_qualified(),
+            out.types
+                .resolve(self_type)
+                .name
+                .to_fully_qualified(out.opt),
             efn.name.cxx,
         ),
     }
     writeln!(out, ";");
-    write!(out, "  ");
+    // The name of the function as reported for an exception that escapes it,
+    // with #[catch_foreign_exceptions].
+    let catch_foreign_exceptions = if efn.catch_foreign_exceptions {
+        Some(match efn.self_type() {
+            None => efn.name.to_fully_qualified(out.opt),
+            Some(self_type) => format!(
+                "{}::{}",
+                out.types
+                    .resolve(self_type)
+                    .name
+                    .to_fully_qualified(out.opt),
+                efn.name.cxx,
+            ),
+        })
+    } else {
+        None
+    };
+    let indent = if efn.throws { "        " } else { "  " };
     if efn.throws {
         out.builtin.ptr_len = true;
         out.builtin.trycatch = true;
-        writeln!(out, "::rust::repr::PtrLen throw$;");
+        writeln!(out, "  ::rust::repr::PtrLen throw$;");
         writeln!(out, "  ::rust::behavior::trycatch(");
         writeln!(out, "      [&] {{");
-        write!(out, "        ");
     }
+    if catch_foreign_exceptions.is_some() {
+        writeln!(out, "#if defined(__cpp_exceptions)");
+        writeln!(out, "{}try {{", indent);
+        writeln!(out, "#endif // __cpp_exceptions");
+    }
+    write!(out, "{}", indent);
     if indirect_return {
         out.include.new = true;
         write!(out, "new (return$) ");
@@ -857,42 +1327,23 @@ fn write_cxx_function_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
         }
         _ => {}
     }
+    if let Some(vocabulary) = &efn.ret_vocabulary {
+        write_vocabulary_conversion(out, vocabulary, "to_rust");
+    }
     match efn.receiver() {
         None => write!(out, "{}$(", efn.name.rust),
         Some(_) => write!(out, "(self.*{}$)(", efn.name.rust),
     }
-    for (i, arg) in efn.args.iter().enumerate() {
-        if i > 0 {
-            write!(out, ", ");
-        }
-        if let Type::RustBox(_) = &arg.ty {
-            write_type(out, &arg.ty);
-            write!(out, "::from_raw({})", arg.name.cxx);
-        } else if let Type::UniquePtr(_) = &arg.ty {
-            write_type(out, &arg.ty);
-            write!(out, "({})", arg.name.cxx);
-        } else if arg.ty == RustString {
-            out.builtin.unsafe_bitcopy = true;
-            write!(
-                out,
-                "::rust::String(::rust::unsafe_bitcopy, *{})",
-                arg.name.cxx,
-            );
-        } else if let Type::RustVec(_) = arg.ty {
-            out.builtin.unsafe_bitcopy = true;
-            write_type(out, &arg.ty);
-            write!(out, "(::rust::unsafe_bitcopy, *{})", arg.name.cxx);
-        } else if out.types.needs_indirect_abi(&arg.ty) {
-            out.include.utility = true;
-            write!(out, "::std::move(*{})", arg.name.cxx);
-        } else {
-            write!(out, "{}", arg.name.cxx);
-        }
-    }
+    write_cxx_function_call_args(out, efn);
     write!(out, ")");
+    if efn.ret_vocabulary.is_some() {
+        write!(out, ")");
+    }
     match &efn.ret {
         Some(Type::RustBox(_)) => write!(out, ".into_raw()"),
-        Some(Type::UniquePtr(_)) => write!(out, ".release()"),
+        Some(Type::UniquePtr(_) | Type::UniqueArray(_) | Type::ComPtr(_)) => {
+            write!(out, ".release()");
+        }
         Some(Type::Str(_) | Type::SliceRef(_)) if !indirect_return => write!(out, ")"),
         _ => {}
     }
@@ -902,20 +1353,294 @@ fn write_cxx_function_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
     writeln!(out, ";");
     if efn.throws {
         writeln!(out, "        throw$.ptr = nullptr;");
-        writeln!(out, "      }},");
-        writeln!(out, "      ::rust::detail::Fail(throw$));");
-        writeln!(out, "  return throw$;");
     }
-    writeln!(out, "}}");
-    for arg in &efn.args {
-        if let Type::Fn(f) = &arg.ty {
-            let var = &arg.name;
-            write_function_pointer_trampoline(out, efn, var, f);
-        }
+    if let Some(function) = &catch_foreign_exceptions {
+        // Exceptions that rust::behavior::trycatch handles are rethrown for it
+        // to turn into the error. Anything else becomes an error here for a
+        // function returning Result, and goes to the foreign exception handler
+        // otherwise.
+        writeln!(out, "#if defined(__cpp_exceptions)");
+        if efn.throws {
+            writeln!(out, "        }} catch (::std::exception const &) {{");
+            writeln!(out, "          throw;");
+            writeln!(out, "        }} catch (...) {{");
+            writeln!(out, "          ::rust::detail::Fail fail(throw$);");
+            writeln!(
+                out,
+                "          fail(\"{} threw an exception that is not a std::exception\");",
+                function,
+            );
+            writeln!(out, "        }}");
+        } else {
+            out.builtin.foreign_exception = true;
+            out.include.exception = true;
+            writeln!(out, "  }} catch (...) {{");
+            writeln!(
+                out,
+                "    ::rust::behavior::foreign_exception(\"{}\");",
+                function,
+            );
+            writeln!(out, "    ::std::terminate();");
+            writeln!(out, "  }}");
+        }
+        writeln!(out, "#endif // __cpp_exceptions");
+    }
+    if efn.throws {
+        writeln!(out, "      }},");
+        writeln!(out, "      ::rust::detail::Fail(throw$));");
+        writeln!(out, "  return throw$;");
+    }
+    writeln!(out, "}}");
+    for arg in &efn.args {
+        if let Type::Fn(f) = &arg.ty {
+            let var = &arg.name;
+            write_function_pointer_trampoline(out, efn, var, f);
+        }
+    }
+    out.end_block(Block::ExternC);
+}
+
+fn write_cxx_function_call_args(out: &mut OutFile, efn: &ExternFn) {
+    for (i, arg) in efn.args.iter().enumerate() {
+        if i > 0 {
+            write!(out, ", ");
+        }
+        if let Some(vocabulary) = &arg.vocabulary {
+            write_vocabulary_conversion(out, vocabulary, "from_rust");
+        }
+        if let Type::RustBox(_) = &arg.ty {
+            write_type(out, &arg.ty);
+            write!(out, "::from_raw({})", arg.name.cxx);
+        } else if let Type::UniquePtr(_) | Type::UniqueArray(_) | Type::ComPtr(_) = &arg.ty {
+            write_type(out, &arg.ty);
+            write!(out, "({})", arg.name.cxx);
+        } else if arg.ty == RustString {
+            out.builtin.unsafe_bitcopy = true;
+            write!(
+                out,
+                "::rust::String(::rust::unsafe_bitcopy, *{})",
+                arg.name.cxx,
+            );
+        } else if let Type::RustVec(_) = arg.ty {
+            out.builtin.unsafe_bitcopy = true;
+            write_type(out, &arg.ty);
+            write!(out, "(::rust::unsafe_bitcopy, *{})", arg.name.cxx);
+        } else if out.types.needs_indirect_abi(&arg.ty) {
+            out.include.utility = true;
+            write!(out, "::std::move(*{})", arg.name.cxx);
+        } else {
+            write!(out, "{}", arg.name.cxx);
+        }
+        if arg.vocabulary.is_some() {
+            write!(out, ")");
+        }
+    }
+}
+
+// Opens a call to the conversion between a #[vocabulary] type and its binding,
+// which the library providing the C++ type defines by specializing
+// rust::Vocabulary.
+fn write_vocabulary_conversion(out: &mut OutFile, vocabulary: &Pair, conversion: &str) {
+    write!(
+        out,
+        "::rust::Vocabulary<{}>::{}(",
+        vocabulary.to_fully_qualified(out.opt),
+        conversion,
+    );
+}
+
+// A function returning Result<T, HRESULT> follows the COM calling convention:
+// the C++ function returns its status code, and writes the success value
+// through a trailing T* parameter which is passed straight through from Rust.
+// Member functions are called directly rather than through a member function
+// pointer, so that the __stdcall convention of COM methods on 32-bit Windows
+// need not be spelled out, and a const receiver is cast away because COM
+// interface methods are never const.
+fn write_cxx_hresult_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
+    out.next_section();
+    out.set_namespace(&efn.name.namespace);
+    out.begin_block(Block::ExternC);
+    out.include.cstdint = true;
+    begin_function_definition(out);
+    let mangled = mangle::extern_fn(efn, out.types);
+    write!(out, "::std::int32_t {}(", mangled);
+    if let FnKind::Method(receiver) = &efn.kind {
+        write!(
+            out,
+            "{}",
+            out.types
+                .resolve(&receiver.ty)
+                .name
+                .to_fully_qualified(out.opt),
+        );
+        if !receiver.mutable {
+            write!(out, " const");
+        }
+        write!(out, " &self");
+    }
+    for (i, arg) in efn.args.iter().enumerate() {
+        if i > 0 || matches!(efn.kind, FnKind::Method(_)) {
+            write!(out, ", ");
+        }
+        if arg.ty == RustString {
+            write_type_space(out, &arg.ty);
+            write!(out, "const *{}", arg.name.cxx);
+        } else if let Type::RustVec(_) = arg.ty {
+            write_type_space(out, &arg.ty);
+            write!(out, "const *{}", arg.name.cxx);
+        } else {
+            write_extern_arg(out, arg);
+        }
+    }
+    if let Some(ret) = &efn.ret {
+        if !efn.args.is_empty() || matches!(efn.kind, FnKind::Method(_)) {
+            write!(out, ", ");
+        }
+        write_indirect_return_type_space(out, ret);
+        write!(out, "*return$");
+    }
+    write!(out, ")");
+    match efn.lang {
+        Lang::Cxx => write!(out, " noexcept"),
+        Lang::CxxUnwind => {}
+        Lang::Rust => unreachable!(),
+    }
+    writeln!(out, " {{");
+    write!(out, "  return static_cast<::std::int32_t>(");
+    match &efn.kind {
+        FnKind::Method(receiver) => {
+            let receiver_type = out.types.resolve(&receiver.ty).name;
+            if receiver.mutable || !out.types.com_interfaces.contains(&receiver.ty.rust) {
+                write!(out, "self.");
+            } else {
+                write!(
+                    out,
+                    "const_cast<{} &>(self).",
+                    receiver_type.to_fully_qualified(out.opt),
+                );
+            }
+            write!(out, "{}", efn.name.cxx);
+        }
+        FnKind::Assoc(self_type) => write!(
+            out,
+            "{}::{}",
+            out.types
+                .resolve(self_type)
+                .name
+                .to_fully_qualified(out.opt),
+            efn.name.cxx,
+        ),
+        FnKind::Free => write!(out, "{}", efn.name.to_fully_qualified(out.opt)),
+    }
+    write!(out, "(");
+    write_cxx_function_call_args(out, efn);
+    if efn.ret.is_some() {
+        if !efn.args.is_empty() {
+            write!(out, ", ");
+        }
+        write!(out, "return$");
     }
+    writeln!(out, "));");
+    writeln!(out, "}}");
     out.end_block(Block::ExternC);
 }
 
+fn write_cxx_function_stub<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
+    out.next_section();
+    let self_type = efn.self_type().map(|ty| out.types.resolve(ty).name);
+    match self_type {
+        Some(self_type) => out.set_namespace(&self_type.namespace),
+        None => out.set_namespace(&efn.name.namespace),
+    }
+    let local_name = match self_type {
+        Some(self_type) => format!("{}::{}", self_type.cxx, efn.name.cxx),
+        None => efn.name.cxx.to_string(),
+    };
+    write_cxx_function_stub_decl(out, efn, &local_name);
+    writeln!(out, " {{");
+    writeln!(out, "  // TODO");
+    writeln!(
+        out,
+        "  throw ::std::logic_error(\"{} is not implemented\");",
+        efn.name.cxx,
+    );
+    writeln!(out, "}}");
+}
+
+fn write_cxx_function_stub_decl(out: &mut OutFile, efn: &ExternFn, local_name: &str) {
+    if efn.hresult.is_some() {
+        write!(out, "::HRESULT ");
+    } else if let Some(vocabulary) = &efn.ret_vocabulary {
+        write!(out, "{} ", vocabulary.to_fully_qualified(out.opt));
+    } else {
+        write_return_type(out, &efn.ret);
+    }
+    write!(out, "{}(", local_name);
+    for (i, arg) in efn.args.iter().enumerate() {
+        if i > 0 {
+            write!(out, ", ");
+        }
+        match &arg.vocabulary {
+            Some(vocabulary) => write!(out, "{} ", vocabulary.to_fully_qualified(out.opt)),
+            None => write_type_space(out, &arg.ty),
+        }
+        write!(out, "{}", arg.name.cxx);
+    }
+    if let (Some(ret), Some(_)) = (&efn.ret, &efn.hresult) {
+        if !efn.args.is_empty() {
+            write!(out, ", ");
+        }
+        write_indirect_return_type_space(out, ret);
+        write!(out, "*out");
+    }
+    write!(out, ")");
+    if let Some(receiver) = efn.receiver() {
+        if !receiver.mutable {
+            write!(out, " const");
+        }
+    }
+}
+
+// The C++ declaration of an extern function, which for extern "C++" functions
+// is the one that the C++ side has to provide, and the C++ spelling of a type.
+// These are for `cxxbridge --emit docs`.
+#[allow(dead_code)] // only used by cxxbridge-cmd, not cxx-build or cxx-gen
+pub(super) fn cxx_function_signature(efn: &ExternFn, types: &Types, opt: &Opt) -> String {
+    let mut out = OutFile::new(true, opt, types);
+    let local_name = match efn.self_type() {
+        Some(self_type) => format!(
+            "{}::{}",
+            types.resolve(self_type).name.to_fully_qualified(opt),
+            efn.name.cxx,
+        ),
+        None => efn.name.to_fully_qualified(opt),
+    };
+    match efn.lang {
+        Lang::Cxx | Lang::CxxUnwind => write_cxx_function_stub_decl(&mut out, efn, &local_name),
+        Lang::Rust => {
+            let in_class = false;
+            let indirect_call = false;
+            let main = is_main(efn);
+            write_rust_function_shim_decl(
+                &mut out,
+                &local_name,
+                efn,
+                in_class,
+                indirect_call,
+                main,
+            );
+        }
+    }
+    String::from_utf8(out.content()).unwrap()
+}
+
+#[allow(dead_code)] // only used by cxxbridge-cmd, not cxx-build or cxx-gen
+pub(super) fn cxx_type(ty: &Type, types: &Types, opt: &Opt) -> String {
+    let mut out = OutFile::new(true, opt, types);
+    write_type(&mut out, ty);
+    String::from_utf8(out.content()).unwrap()
+}
+
 fn write_function_pointer_trampoline(out: &mut OutFile, efn: &ExternFn, var: &Pair, f: &Signature) {
     let r_trampoline = mangle::r_trampoline(efn, var, out.types);
     let indirect_call = true;
@@ -964,7 +1689,10 @@ fn write_rust_function_decl_impl(
         write!(
             out,
             "{}",
-            out.types.resolve(&receiver.ty).name.to_fully_qualified(),
+            out.types
+                .resolve(&receiver.ty)
+                .name
+                .to_fully_qualified(out.opt),
         );
         if !receiver.mutable {
             write!(out, " const");
@@ -1018,14 +1746,19 @@ fn write_rust_function_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
     let doc = &efn.doc;
     let invoke = mangle::extern_fn(efn, out.types);
     let indirect_call = false;
-    let main = efn.name.cxx == *"main"
+    let main = is_main(efn);
+    write_rust_function_shim_impl(out, &local_name, efn, doc, &invoke, indirect_call, main);
+}
+
+// An extern "Rust" `fn main()` becomes the C++ program's `int main()`.
+fn is_main(efn: &ExternFn) -> bool {
+    efn.name.cxx == *"main"
         && efn.name.namespace == Namespace::ROOT
         && efn.sig.asyncness.is_none()
         && matches!(efn.kind, FnKind::Free)
         && efn.sig.args.is_empty()
         && efn.sig.ret.is_none()
-        && !efn.sig.throws;
-    write_rust_function_shim_impl(out, &local_name, efn, doc, &invoke, indirect_call, main);
+        && !efn.sig.throws
 }
 
 fn write_rust_function_shim_decl(
@@ -1130,7 +1863,7 @@ fn write_rust_function_shim_impl(
                 write_type(out, ret);
                 write!(out, "::from_raw(");
             }
-            Type::UniquePtr(_) => {
+            Type::UniquePtr(_) | Type::UniqueArray(_) | Type::ComPtr(_) => {
                 write_type(out, ret);
                 write!(out, "(");
             }
@@ -1168,7 +1901,9 @@ fn write_rust_function_shim_impl(
         write!(out, "{}", arg.name.cxx);
         match &arg.ty {
             Type::RustBox(_) => write!(out, ".into_raw()"),
-            Type::UniquePtr(_) => write!(out, ".release()"),
+            Type::UniquePtr(_) | Type::UniqueArray(_) | Type::ComPtr(_) => {
+                write!(out, ".release()");
+            }
             ty if ty != RustString && out.types.needs_indirect_abi(ty) => write!(out, "$.value"),
             _ => {}
         }
@@ -1189,8 +1924,14 @@ fn write_rust_function_shim_impl(
     }
     write!(out, ")");
     if !indirect_return {
-        if let Some(Type::RustBox(_) | Type::UniquePtr(_) | Type::Str(_) | Type::SliceRef(_)) =
-            &sig.ret
+        if let Some(
+            Type::RustBox(_)
+            | Type::UniquePtr(_)
+            | Type::UniqueArray(_)
+            | Type::ComPtr(_)
+            | Type::Str(_)
+            | Type::SliceRef(_),
+        ) = &sig.ret
         {
             write!(out, ")");
         }
@@ -1226,12 +1967,12 @@ fn write_return_type(out: &mut OutFile, ty: &Option<Type>) {
 fn indirect_return(sig: &Signature, types: &Types) -> bool {
     sig.ret
         .as_ref()
-        .is_some_and(|ret| sig.throws || types.needs_indirect_abi(ret))
+        .is_some_and(|ret| sig.throws || sig.hresult.is_some() || types.needs_indirect_abi(ret))
 }
 
 fn write_indirect_return_type(out: &mut OutFile, ty: &Type) {
     match ty {
-        Type::RustBox(ty) | Type::UniquePtr(ty) => {
+        Type::RustBox(ty) | Type::UniquePtr(ty) | Type::UniqueArray(ty) | Type::ComPtr(ty) => {
             write_type_space(out, &ty.inner);
             write!(out, "*");
         }
@@ -1249,7 +1990,11 @@ fn write_indirect_return_type(out: &mut OutFile, ty: &Type) {
 fn write_indirect_return_type_space(out: &mut OutFile, ty: &Type) {
     write_indirect_return_type(out, ty);
     match ty {
-        Type::RustBox(_) | Type::UniquePtr(_) | Type::Ref(_) => {}
+        Type::RustBox(_)
+        | Type::UniquePtr(_)
+        | Type::UniqueArray(_)
+        | Type::ComPtr(_)
+        | Type::Ref(_) => {}
         Type::Str(_) | Type::SliceRef(_) => write!(out, " "),
         _ => write_space_after_type(out, ty),
     }
@@ -1257,7 +2002,9 @@ fn write_indirect_return_type_space(out: &mut OutFile, ty: &Type) {
 
 fn write_extern_return_type_space(out: &mut OutFile, ty: &Option<Type>) {
     match ty {
-        Some(Type::RustBox(ty) | Type::UniquePtr(ty)) => {
+        Some(
+            Type::RustBox(ty) | Type::UniquePtr(ty) | Type::UniqueArray(ty) | Type::ComPtr(ty),
+        ) => {
             write_type_space(out, &ty.inner);
             write!(out, "*");
         }
@@ -1279,7 +2026,11 @@ fn write_extern_return_type_space(out: &mut OutFile, ty: &Option<Type>) {
 
 fn write_extern_arg(out: &mut OutFile, arg: &Var) {
     match &arg.ty {
-        Type::RustBox(ty) | Type::UniquePtr(ty) | Type::CxxVector(ty) => {
+        Type::RustBox(ty)
+        | Type::UniquePtr(ty)
+        | Type::UniqueArray(ty)
+        | Type::ComPtr(ty)
+        | Type::CxxVector(ty) => {
             write_type_space(out, &ty.inner);
             write!(out, "*");
         }
@@ -1298,7 +2049,7 @@ fn write_type(out: &mut OutFile, ty: &Type) {
             None => write!(
                 out,
                 "{}",
-                out.types.resolve(ident).name.to_fully_qualified(),
+                out.types.resolve(ident).name.to_fully_qualified(out.opt),
             ),
         },
         Type::RustBox(ty) => {
@@ -1316,11 +2067,31 @@ fn write_type(out: &mut OutFile, ty: &Type) {
             write_type(out, &ptr.inner);
             write!(out, ">");
         }
+        Type::UniqueArray(ptr) => {
+            write!(out, "::std::unique_ptr<");
+            write_type(out, &ptr.inner);
+            write!(out, "[]>");
+        }
+        Type::ComPtr(ptr) => {
+            write!(out, "::rust::ComPtr<");
+            write_type(out, &ptr.inner);
+            write!(out, ">");
+        }
         Type::SharedPtr(ptr) => {
             write!(out, "::std::shared_ptr<");
             write_type(out, &ptr.inner);
             write!(out, ">");
         }
+        Type::SharedArray(ptr) => {
+            write!(out, "::std::shared_ptr<");
+            write_type(out, &ptr.inner);
+            write!(out, "[]>");
+        }
+        Type::SharedConstPtr(ptr) => {
+            write!(out, "::std::shared_ptr<");
+            write_type(out, &ptr.inner);
+            write!(out, " const>");
+        }
         Type::WeakPtr(ptr) => {
             write!(out, "::std::weak_ptr<");
             write_type(out, &ptr.inner);
@@ -1331,6 +2102,11 @@ fn write_type(out: &mut OutFile, ty: &Type) {
             write_type(out, &ty.inner);
             write!(out, ">");
         }
+        Type::CxxPmrVector(ty) => {
+            write!(out, "::std::pmr::vector<");
+            write_type(out, &ty.inner);
+            write!(out, ">");
+        }
         Type::Ref(r) => {
             write_type_space(out, &r.inner);
             if !r.mutable {
@@ -1376,6 +2152,19 @@ fn write_type(out: &mut OutFile, ty: &Type) {
             write_type(out, &a.inner);
             write!(out, ", {}>", &a.len);
         }
+        Type::HashMapRef(map) => {
+            write!(out, "::rust::HashMap<");
+            write_type(out, &map.key);
+            write!(out, ", ");
+            write_type(out, &map.value);
+            write!(out, ">");
+        }
+        Type::CustomPtr(ptr) => {
+            let family = out.types.resolve(&ptr.family);
+            write!(out, "{}<", family.name.to_fully_qualified(out.opt));
+            write_type(out, &ptr.inner);
+            write!(out, ">");
+        }
         Type::Void(_) => unreachable!(),
     }
 }
@@ -1411,14 +2200,21 @@ fn write_space_after_type(out: &mut OutFile, ty: &Type) {
         Type::Ident(_)
         | Type::RustBox(_)
         | Type::UniquePtr(_)
+        | Type::UniqueArray(_)
         | Type::SharedPtr(_)
+        | Type::SharedArray(_)
+        | Type::SharedConstPtr(_)
         | Type::WeakPtr(_)
         | Type::Str(_)
         | Type::CxxVector(_)
+        | Type::CxxPmrVector(_)
         | Type::RustVec(_)
         | Type::SliceRef(_)
         | Type::Fn(_)
-        | Type::Array(_) => write!(out, " "),
+        | Type::Array(_)
+        | Type::HashMapRef(_)
+        | Type::CustomPtr(_)
+        | Type::ComPtr(_) => write!(out, " "),
         Type::Ref(_) | Type::Ptr(_) => {}
         Type::Void(_) => unreachable!(),
     }
@@ -1428,24 +2224,28 @@ fn write_space_after_type(out: &mut OutFile, ty: &Type) {
 enum UniquePtr<'a> {
     Ident(&'a Ident),
     CxxVector(&'a Ident),
+    CxxPmrVector(&'a Ident),
 }
 
 trait ToTypename {
-    fn to_typename(&self, types: &Types) -> String;
+    fn to_typename(&self, types: &Types, opt: &Opt) -> String;
 }
 
 impl ToTypename for Ident {
-    fn to_typename(&self, types: &Types) -> String {
-        types.resolve(self).name.to_fully_qualified()
+    fn to_typename(&self, types: &Types, opt: &Opt) -> String {
+        types.resolve(self).name.to_fully_qualified(opt)
     }
 }
 
 impl<'a> ToTypename for UniquePtr<'a> {
-    fn to_typename(&self, types: &Types) -> String {
+    fn to_typename(&self, types: &Types, opt: &Opt) -> String {
         match self {
-            UniquePtr::Ident(ident) => ident.to_typename(types),
+            UniquePtr::Ident(ident) => ident.to_typename(types, opt),
             UniquePtr::CxxVector(element) => {
-                format!("::std::vector<{}>", element.to_typename(types))
+                format!("::std::vector<{}>", element.to_typename(types, opt))
+            }
+            UniquePtr::CxxPmrVector(element) => {
+                format!("::std::pmr::vector<{}>", element.to_typename(types, opt))
             }
         }
     }
@@ -1468,6 +2268,9 @@ impl<'a> ToMangled for UniquePtr<'a> {
             UniquePtr::CxxVector(element) => {
                 symbol::join(&[&"std", &"vector", &element.to_mangled(types)])
             }
+            UniquePtr::CxxPmrVector(element) => {
+                symbol::join(&[&"std", &"pmr", &"vector", &element.to_mangled(types)])
+            }
         }
     }
 }
@@ -1486,9 +2289,14 @@ fn write_generic_instantiations(out: &mut OutFile) {
             ImplKey::RustBox(ident) => write_rust_box_extern(out, ident),
             ImplKey::RustVec(ident) => write_rust_vec_extern(out, ident),
             ImplKey::UniquePtr(ident) => write_unique_ptr(out, ident),
+            ImplKey::UniqueArray(ident) => write_unique_array(out, ident),
             ImplKey::SharedPtr(ident) => write_shared_ptr(out, ident),
+            ImplKey::SharedArray(ident) => write_shared_array(out, ident),
+            ImplKey::SharedConstPtr(ident) => write_shared_const_ptr(out, ident),
             ImplKey::WeakPtr(ident) => write_weak_ptr(out, ident),
             ImplKey::CxxVector(ident) => write_cxx_vector(out, ident),
+            ImplKey::CxxPmrVector(ident) => write_cxx_pmr_vector(out, ident),
+            ImplKey::CustomPtr(family, ident) => write_custom_ptr(out, family, ident),
         }
     }
     out.end_block(Block::ExternC);
@@ -1508,7 +2316,7 @@ fn write_generic_instantiations(out: &mut OutFile) {
 
 fn write_rust_box_extern(out: &mut OutFile, key: &NamedImplKey) {
     let resolve = out.types.resolve(key);
-    let inner = resolve.name.to_fully_qualified();
+    let inner = resolve.name.to_fully_qualified(out.opt);
     let instance = resolve.name.to_symbol();
 
     writeln!(
@@ -1526,11 +2334,18 @@ fn write_rust_box_extern(out: &mut OutFile, key: &NamedImplKey) {
         "void cxxbridge1$box${}$drop(::rust::Box<{}> *ptr) noexcept;",
         instance, inner,
     );
+    if out.types.rust_default.contains(key.rust) {
+        writeln!(
+            out,
+            "void cxxbridge1$box${}$default(::rust::Box<{}> *ptr) noexcept;",
+            instance, inner,
+        );
+    }
 }
 
 fn write_rust_vec_extern(out: &mut OutFile, key: &NamedImplKey) {
     let element = key.rust;
-    let inner = element.to_typename(out.types);
+    let inner = element.to_typename(out.types, out.opt);
     let instance = element.to_mangled(out.types);
 
     out.include.cstddef = true;
@@ -1579,7 +2394,7 @@ fn write_rust_vec_extern(out: &mut OutFile, key: &NamedImplKey) {
 
 fn write_rust_box_impl(out: &mut OutFile, key: &NamedImplKey) {
     let resolve = out.types.resolve(key);
-    let inner = resolve.name.to_fully_qualified();
+    let inner = resolve.name.to_fully_qualified(out.opt);
     let instance = resolve.name.to_symbol();
 
     writeln!(out, "template <>");
@@ -1607,11 +2422,27 @@ fn write_rust_box_impl(out: &mut OutFile, key: &NamedImplKey) {
     writeln!(out, "void Box<{}>::drop() noexcept {{", inner);
     writeln!(out, "  cxxbridge1$box${}$drop(this);", instance);
     writeln!(out, "}}");
+
+    if out.types.rust_default.contains(key.rust) {
+        out.include.type_traits = true;
+        writeln!(out, "template <>");
+        writeln!(out, "template <>");
+        begin_function_definition(out);
+        writeln!(
+            out,
+            "Box<{}> Box<{}>::make<{}, ::std::true_type>() noexcept {{",
+            inner, inner, inner,
+        );
+        writeln!(out, "  Box<{}> box = uninit{{}};", inner);
+        writeln!(out, "  cxxbridge1$box${}$default(&box);", instance);
+        writeln!(out, "  return box;");
+        writeln!(out, "}}");
+    }
 }
 
 fn write_rust_vec_impl(out: &mut OutFile, key: &NamedImplKey) {
     let element = key.rust;
-    let inner = element.to_typename(out.types);
+    let inner = element.to_typename(out.types, out.opt);
     let instance = element.to_mangled(out.types);
 
     out.include.cstddef = true;
@@ -1702,11 +2533,12 @@ fn write_unique_ptr(out: &mut OutFile, key: &NamedImplKey) {
     write_unique_ptr_common(out, ty);
 }
 
-// Shared by UniquePtr<T> and UniquePtr<CxxVector<T>>.
+// Shared by UniquePtr<T>, UniquePtr<CxxVector<T>>, and
+// UniquePtr<CxxPmrVector<T>>.
 fn write_unique_ptr_common(out: &mut OutFile, ty: UniquePtr) {
     out.include.new = true;
     out.include.utility = true;
-    let inner = ty.to_typename(out.types);
+    let inner = ty.to_typename(out.types, out.opt);
     let instance = ty.to_mangled(out.types);
 
     let can_construct_from_value = match ty {
@@ -1715,7 +2547,7 @@ fn write_unique_ptr_common(out: &mut OutFile, ty: UniquePtr) {
         // bindings for a "new" method anyway. But the Rust code can't be called
         // for Opaque types because the 'new' method is not implemented.
         UniquePtr::Ident(ident) => out.types.is_maybe_trivial(ident),
-        UniquePtr::CxxVector(_) => false,
+        UniquePtr::CxxVector(_) | UniquePtr::CxxPmrVector(_) => false,
     };
 
     out.builtin.is_complete = true;
@@ -1804,84 +2636,257 @@ fn write_unique_ptr_common(out: &mut OutFile, ty: UniquePtr) {
     writeln!(out, "}}");
 }
 
-fn write_shared_ptr(out: &mut OutFile, key: &NamedImplKey) {
-    let ident = key.rust;
-    let resolve = out.types.resolve(ident);
-    let inner = resolve.name.to_fully_qualified();
-    let instance = resolve.name.to_symbol();
-
+fn write_unique_array(out: &mut OutFile, key: &NamedImplKey) {
     out.include.new = true;
     out.include.utility = true;
+    let element = key.rust.to_typename(out.types, out.opt);
+    let inner = format!("{}[]", element);
+    let instance = symbol::join(&[&"array", &key.rust.to_mangled(out.types)]);
 
-    // Some aliases are to opaque types; some are to trivial types. We can't
-    // know at code generation time, so we generate both C++ and Rust side
-    // bindings for a "new" method anyway. But the Rust code can't be called for
-    // Opaque types because the 'new' method is not implemented.
-    let can_construct_from_value = out.types.is_maybe_trivial(ident);
-
+    out.builtin.is_complete = true;
     writeln!(
         out,
-        "static_assert(sizeof(::std::shared_ptr<{}>) == 2 * sizeof(void *), \"\");",
+        "static_assert(::rust::detail::is_complete<{}>::value, \"definition of `{}` is required\");",
+        element, element,
+    );
+    writeln!(
+        out,
+        "static_assert(sizeof(::std::unique_ptr<{}>) == sizeof(void *), \"\");",
         inner,
     );
     writeln!(
         out,
-        "static_assert(alignof(::std::shared_ptr<{}>) == alignof(void *), \"\");",
+        "static_assert(alignof(::std::unique_ptr<{}>) == alignof(void *), \"\");",
         inner,
     );
 
     begin_function_definition(out);
     writeln!(
         out,
-        "void cxxbridge1$shared_ptr${}$null(::std::shared_ptr<{}> *ptr) noexcept {{",
+        "void cxxbridge1$unique_ptr${}$null(::std::unique_ptr<{}> *ptr) noexcept {{",
         instance, inner,
     );
-    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>();", inner);
+    writeln!(out, "  ::new (ptr) ::std::unique_ptr<{}>();", inner);
     writeln!(out, "}}");
 
-    if can_construct_from_value {
-        out.builtin.maybe_uninit = true;
-        begin_function_definition(out);
-        writeln!(
-            out,
-            "{} *cxxbridge1$shared_ptr${}$uninit(::std::shared_ptr<{}> *ptr) noexcept {{",
-            inner, instance, inner,
-        );
-        writeln!(
-            out,
-            "  {} *uninit = reinterpret_cast<{} *>(new ::rust::MaybeUninit<{}>);",
-            inner, inner, inner,
-        );
-        writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>(uninit);", inner);
-        writeln!(out, "  return uninit;");
-        writeln!(out, "}}");
-    }
-
     begin_function_definition(out);
     writeln!(
         out,
-        "void cxxbridge1$shared_ptr${}$raw(::std::shared_ptr<{}> *ptr, {} *raw) noexcept {{",
-        instance, inner, inner,
+        "void cxxbridge1$unique_ptr${}$raw(::std::unique_ptr<{}> *ptr, {} *raw) noexcept {{",
+        instance, inner, element,
     );
-    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>(raw);", inner);
+    writeln!(out, "  ::new (ptr) ::std::unique_ptr<{}>(raw);", inner);
     writeln!(out, "}}");
 
     begin_function_definition(out);
     writeln!(
         out,
-        "void cxxbridge1$shared_ptr${}$clone(::std::shared_ptr<{}> const &self, ::std::shared_ptr<{}> *ptr) noexcept {{",
-        instance, inner, inner,
+        "{} const *cxxbridge1$unique_ptr${}$get(::std::unique_ptr<{}> const &ptr) noexcept {{",
+        element, instance, inner,
     );
-    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>(self);", inner);
+    writeln!(out, "  return ptr.get();");
     writeln!(out, "}}");
 
     begin_function_definition(out);
     writeln!(
         out,
-        "{} const *cxxbridge1$shared_ptr${}$get(::std::shared_ptr<{}> const &self) noexcept {{",
-        inner, instance, inner,
+        "{} *cxxbridge1$unique_ptr${}$release(::std::unique_ptr<{}> &ptr) noexcept {{",
+        element, instance, inner,
+    );
+    writeln!(out, "  return ptr.release();");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$unique_ptr${}$drop(::std::unique_ptr<{}> *ptr) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  ptr->~unique_ptr();");
+    writeln!(out, "}}");
+}
+
+fn write_shared_ptr(out: &mut OutFile, key: &NamedImplKey) {
+    let ident = key.rust;
+    let resolve = out.types.resolve(ident);
+    let inner = resolve.name.to_fully_qualified(out.opt);
+    let instance = resolve.name.to_symbol();
+
+    out.include.new = true;
+    out.include.utility = true;
+
+    // Some aliases are to opaque types; some are to trivial types. We can't
+    // know at code generation time, so we generate both C++ and Rust side
+    // bindings for a "new" method anyway. But the Rust code can't be called for
+    // Opaque types because the 'new' method is not implemented.
+    let can_construct_from_value = out.types.is_maybe_trivial(ident);
+
+    writeln!(
+        out,
+        "static_assert(sizeof(::std::shared_ptr<{}>) == 2 * sizeof(void *), \"\");",
+        inner,
+    );
+    writeln!(
+        out,
+        "static_assert(alignof(::std::shared_ptr<{}>) == alignof(void *), \"\");",
+        inner,
+    );
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$null(::std::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>();", inner);
+    writeln!(out, "}}");
+
+    if can_construct_from_value {
+        out.builtin.maybe_uninit = true;
+        begin_function_definition(out);
+        writeln!(
+            out,
+            "{} *cxxbridge1$shared_ptr${}$uninit(::std::shared_ptr<{}> *ptr) noexcept {{",
+            inner, instance, inner,
+        );
+        writeln!(
+            out,
+            "  {} *uninit = reinterpret_cast<{} *>(new ::rust::MaybeUninit<{}>);",
+            inner, inner, inner,
+        );
+        writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>(uninit);", inner);
+        writeln!(out, "  return uninit;");
+        writeln!(out, "}}");
+    }
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$raw(::std::shared_ptr<{}> *ptr, {} *raw) noexcept {{",
+        instance, inner, inner,
+    );
+    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>(raw);", inner);
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$clone(::std::shared_ptr<{}> const &self, ::std::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>(self);", inner);
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "{} const *cxxbridge1$shared_ptr${}$get(::std::shared_ptr<{}> const &self) noexcept {{",
+        inner, instance, inner,
+    );
+    writeln!(out, "  return self.get();");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::size_t cxxbridge1$shared_ptr${}$use_count(::std::shared_ptr<{}> const &self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(
+        out,
+        "  return static_cast<::std::size_t>(self.use_count());",
+    );
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$drop(::std::shared_ptr<{}> *self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  self->~shared_ptr();");
+    writeln!(out, "}}");
+}
+
+fn write_shared_array(out: &mut OutFile, key: &NamedImplKey) {
+    let resolve = out.types.resolve(key);
+    let element = resolve.name.to_fully_qualified(out.opt);
+    let inner = format!("{}[]", element);
+    let instance = symbol::join(&[&"array", &resolve.name.to_symbol()]);
+
+    out.include.new = true;
+    out.include.utility = true;
+
+    writeln!(
+        out,
+        "static_assert(sizeof(::std::shared_ptr<{}>) == 2 * sizeof(void *), \"\");",
+        inner,
+    );
+    writeln!(
+        out,
+        "static_assert(alignof(::std::shared_ptr<{}>) == alignof(void *), \"\");",
+        inner,
+    );
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$null(::std::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>();", inner);
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$raw(::std::shared_ptr<{}> *ptr, {} *raw) noexcept {{",
+        instance, inner, element,
+    );
+    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>(raw);", inner);
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$clone(::std::shared_ptr<{}> const &self, ::std::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>(self);", inner);
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$alias(::std::shared_ptr<{}> const &self, ::std::size_t offset, ::std::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    writeln!(
+        out,
+        "  ::new (ptr) ::std::shared_ptr<{}>(self, self.get() + offset);",
+        inner,
+    );
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "{} const *cxxbridge1$shared_ptr${}$get(::std::shared_ptr<{}> const &self) noexcept {{",
+        element, instance, inner,
+    );
+    writeln!(out, "  return self.get();");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::size_t cxxbridge1$shared_ptr${}$use_count(::std::shared_ptr<{}> const &self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(
+        out,
+        "  return static_cast<::std::size_t>(self.use_count());",
     );
-    writeln!(out, "  return self.get();");
     writeln!(out, "}}");
 
     begin_function_definition(out);
@@ -1894,9 +2899,154 @@ fn write_shared_ptr(out: &mut OutFile, key: &NamedImplKey) {
     writeln!(out, "}}");
 }
 
+fn write_shared_const_ptr(out: &mut OutFile, key: &NamedImplKey) {
+    let resolve = out.types.resolve(key);
+    let target = resolve.name.to_fully_qualified(out.opt);
+    let inner = format!("{} const", target);
+    let instance = symbol::join(&[&"const", &resolve.name.to_symbol()]);
+
+    out.include.new = true;
+    out.include.utility = true;
+
+    writeln!(
+        out,
+        "static_assert(sizeof(::std::shared_ptr<{}>) == 2 * sizeof(void *), \"\");",
+        inner,
+    );
+    writeln!(
+        out,
+        "static_assert(alignof(::std::shared_ptr<{}>) == alignof(void *), \"\");",
+        inner,
+    );
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$null(::std::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>();", inner);
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$from_mut(::std::shared_ptr<{}> *mut, ::std::shared_ptr<{}> *ptr) noexcept {{",
+        instance, target, inner,
+    );
+    writeln!(
+        out,
+        "  ::new (ptr) ::std::shared_ptr<{}>(::std::move(*mut));",
+        inner,
+    );
+    writeln!(out, "  mut->~shared_ptr();");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$clone(::std::shared_ptr<{}> const &self, ::std::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    writeln!(out, "  ::new (ptr) ::std::shared_ptr<{}>(self);", inner);
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "{} *cxxbridge1$shared_ptr${}$get(::std::shared_ptr<{}> const &self) noexcept {{",
+        inner, instance, inner,
+    );
+    writeln!(out, "  return self.get();");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::size_t cxxbridge1$shared_ptr${}$use_count(::std::shared_ptr<{}> const &self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(
+        out,
+        "  return static_cast<::std::size_t>(self.use_count());",
+    );
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$shared_ptr${}$drop(::std::shared_ptr<{}> *self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  self->~shared_ptr();");
+    writeln!(out, "}}");
+}
+
+fn write_custom_ptr(out: &mut OutFile, family: &Ident, key: &NamedImplKey) {
+    let family = out.types.resolve(family);
+    let resolve = out.types.resolve(key);
+    let ptr = format!(
+        "{}<{}>",
+        family.name.to_fully_qualified(out.opt),
+        resolve.name.to_fully_qualified(out.opt),
+    );
+    let inner = resolve.name.to_fully_qualified(out.opt);
+    let instance = symbol::join(&[&family.name.to_symbol(), &resolve.name.to_symbol()]);
+
+    out.include.new = true;
+    out.builtin.destroy = true;
+
+    writeln!(
+        out,
+        "static_assert(sizeof({}) == sizeof(void *), \"\");",
+        ptr,
+    );
+    writeln!(
+        out,
+        "static_assert(alignof({}) == alignof(void *), \"\");",
+        ptr,
+    );
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$custom_ptr${}$null({} *ptr) noexcept {{",
+        instance, ptr,
+    );
+    writeln!(out, "  ::new (ptr) {}();", ptr);
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$custom_ptr${}$clone({} const &self, {} *ptr) noexcept {{",
+        instance, ptr, ptr,
+    );
+    writeln!(out, "  ::new (ptr) {}(self);", ptr);
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "{} const *cxxbridge1$custom_ptr${}$get({} const &self) noexcept {{",
+        inner, instance, ptr,
+    );
+    writeln!(out, "  return self.get();");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$custom_ptr${}$drop({} *self) noexcept {{",
+        instance, ptr,
+    );
+    writeln!(out, "  ::rust::destroy(self);");
+    writeln!(out, "}}");
+}
+
 fn write_weak_ptr(out: &mut OutFile, key: &NamedImplKey) {
     let resolve = out.types.resolve(key);
-    let inner = resolve.name.to_fully_qualified();
+    let inner = resolve.name.to_fully_qualified(out.opt);
     let instance = resolve.name.to_symbol();
 
     out.include.new = true;
@@ -1953,6 +3103,18 @@ fn write_weak_ptr(out: &mut OutFile, key: &NamedImplKey) {
     );
     writeln!(out, "}}");
 
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::size_t cxxbridge1$weak_ptr${}$use_count(::std::weak_ptr<{}> const &self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(
+        out,
+        "  return static_cast<::std::size_t>(self.use_count());",
+    );
+    writeln!(out, "}}");
+
     begin_function_definition(out);
     writeln!(
         out,
@@ -1965,7 +3127,7 @@ fn write_weak_ptr(out: &mut OutFile, key: &NamedImplKey) {
 
 fn write_cxx_vector(out: &mut OutFile, key: &NamedImplKey) {
     let element = key.rust;
-    let inner = element.to_typename(out.types);
+    let inner = element.to_typename(out.types, out.opt);
     let instance = element.to_mangled(out.types);
 
     out.include.cstddef = true;
@@ -2017,6 +3179,17 @@ fn write_cxx_vector(out: &mut OutFile, key: &NamedImplKey) {
     writeln!(out, "  s->reserve(new_cap);");
     writeln!(out, "}}");
 
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$std$vector${}$truncate(::std::vector<{}> *s, ::std::size_t len) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  while (s->size() > len) {{");
+    writeln!(out, "    s->pop_back();");
+    writeln!(out, "  }}");
+    writeln!(out, "}}");
+
     if out.types.is_maybe_trivial(element) {
         begin_function_definition(out);
         writeln!(
@@ -2042,3 +3215,105 @@ fn write_cxx_vector(out: &mut OutFile, key: &NamedImplKey) {
     out.include.memory = true;
     write_unique_ptr_common(out, UniquePtr::CxxVector(element));
 }
+
+fn write_cxx_pmr_vector(out: &mut OutFile, key: &NamedImplKey) {
+    let element = key.rust;
+    let inner = element.to_typename(out.types, out.opt);
+    let instance = element.to_mangled(out.types);
+
+    out.include.cstddef = true;
+    out.include.memory_resource = true;
+    out.include.utility = true;
+    out.include.vector = true;
+    out.builtin.destroy = true;
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::pmr::vector<{}> *cxxbridge1$std$pmr$vector${}$new_in(::std::pmr::memory_resource *resource) noexcept {{",
+        inner, instance,
+    );
+    writeln!(out, "  return new ::std::pmr::vector<{}>(resource);", inner);
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::size_t cxxbridge1$std$pmr$vector${}$size(::std::pmr::vector<{}> const &s) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  return s.size();");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::size_t cxxbridge1$std$pmr$vector${}$capacity(::std::pmr::vector<{}> const &s) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  return s.capacity();");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "{} *cxxbridge1$std$pmr$vector${}$get_unchecked(::std::pmr::vector<{}> *s, ::std::size_t pos) noexcept {{",
+        inner, instance, inner,
+    );
+    writeln!(out, "  return &(*s)[pos];");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$std$pmr$vector${}$reserve(::std::pmr::vector<{}> *s, ::std::size_t new_cap) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  s->reserve(new_cap);");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void cxxbridge1$std$pmr$vector${}$truncate(::std::pmr::vector<{}> *s, ::std::size_t len) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  while (s->size() > len) {{");
+    writeln!(out, "    s->pop_back();");
+    writeln!(out, "  }}");
+    writeln!(out, "}}");
+
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::pmr::memory_resource *cxxbridge1$std$pmr$vector${}$resource(::std::pmr::vector<{}> const &s) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  return s.get_allocator().resource();");
+    writeln!(out, "}}");
+
+    if out.types.is_maybe_trivial(element) {
+        begin_function_definition(out);
+        writeln!(
+            out,
+            "void cxxbridge1$std$pmr$vector${}$push_back(::std::pmr::vector<{}> *v, {} *value) noexcept {{",
+            instance, inner, inner,
+        );
+        writeln!(out, "  v->push_back(::std::move(*value));");
+        writeln!(out, "  ::rust::destroy(value);");
+        writeln!(out, "}}");
+
+        begin_function_definition(out);
+        writeln!(
+            out,
+            "void cxxbridge1$std$pmr$vector${}$pop_back(::std::pmr::vector<{}> *v, {} *out) noexcept {{",
+            instance, inner, inner,
+        );
+        writeln!(out, "  ::new (out) {}(::std::move(v->back()));", inner);
+        writeln!(out, "  v->pop_back();");
+        writeln!(out, "}}");
+    }
+
+    out.include.memory = true;
+    write_unique_ptr_common(out, UniquePtr::CxxPmrVector(element));
+}
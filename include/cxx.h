@@ -5,6 +5,7 @@
 #include <cstddef>
 #include <cstdint>
 #include <exception>
+#include <functional>
 #include <initializer_list>
 #include <iosfwd>
 #include <iterator>
@@ -25,7 +26,9 @@
 #endif
 
 #if __cplusplus >= 202002L
+#include <compare>
 #include <ranges>
+#include <span>
 #endif
 
 namespace rust {
@@ -41,6 +44,8 @@ class impl;
 #ifndef CXXBRIDGE1_RUST_STRING
 #define CXXBRIDGE1_RUST_STRING
 // https://cxx.rs/binding/string.html
+class Str;
+
 class String final {
 public:
   String() noexcept;
@@ -53,6 +58,7 @@ public:
   String(const char *, std::size_t);
   String(const char16_t *);
   String(const char16_t *, std::size_t);
+  String(const std::u16string &);
 #ifdef __cpp_char8_t
   String(const char8_t *s);
   String(const char8_t *s, std::size_t len);
@@ -64,11 +70,17 @@ public:
   static String lossy(const char *, std::size_t) noexcept;
   static String lossy(const char16_t *) noexcept;
   static String lossy(const char16_t *, std::size_t) noexcept;
+  static String lossy(const std::u16string &) noexcept;
+
+  // Same as the UTF-16 constructors, throwing if not valid UTF-16.
+  static String from_utf16(const char16_t *, std::size_t);
+  static String from_utf16(const std::u16string &);
 
   String &operator=(const String &) & noexcept;
   String &operator=(String &&) & noexcept;
 
   explicit operator std::string() const;
+  std::u16string to_u16string() const;
 
   // Note: no null terminator.
   const char *data() const noexcept;
@@ -97,6 +109,31 @@ public:
   bool operator<=(const String &) const noexcept;
   bool operator>(const String &) const noexcept;
   bool operator>=(const String &) const noexcept;
+#ifdef __cpp_lib_three_way_comparison
+  std::strong_ordering operator<=>(const String &rhs) const noexcept {
+    // Bytes compare as unsigned, the same as the ordering of str in Rust.
+    return std::lexicographical_compare_three_way(
+        this->begin(), this->end(), rhs.begin(), rhs.end(),
+        [](char lhs, char rhs) {
+          return static_cast<unsigned char>(lhs) <=>
+                 static_cast<unsigned char>(rhs);
+        });
+  }
+#endif // __cpp_lib_three_way_comparison
+
+  String &operator+=(const String &);
+  String &operator+=(Str);
+  String &operator+=(const std::string &);
+  String &operator+=(const char *);
+#if __cplusplus >= 201703L
+  String &operator+=(std::string_view);
+#endif
+
+  bool starts_with(Str) const noexcept;
+  bool ends_with(Str) const noexcept;
+  // Byte offset of the first occurrence at or after `pos`, or
+  // std::string::npos if there is none.
+  std::size_t find(Str, std::size_t pos = 0) const noexcept;
 
   void swap(String &) noexcept;
 
@@ -107,6 +144,7 @@ private:
   struct lossy_t;
   String(lossy_t, const char *, std::size_t) noexcept;
   String(lossy_t, const char16_t *, std::size_t) noexcept;
+  String &append(const char *, std::size_t);
   friend void swap(String &lhs, String &rhs) noexcept { lhs.swap(rhs); }
 
   // Size and alignment statically verified by rust_string.rs.
@@ -117,6 +155,12 @@ private:
 #ifndef CXXBRIDGE1_RUST_STR
 #define CXXBRIDGE1_RUST_STR
 // https://cxx.rs/binding/str.html
+class Str;
+// Opt in with `using namespace rust::literals;`.
+namespace literals {
+Str operator""_rs(const char *, std::size_t) noexcept;
+} // namespace literals
+
 class Str final {
 public:
   Str() noexcept;
@@ -125,6 +169,11 @@ public:
   Str(const char *);
   Str(const char *, std::size_t);
 
+  // Construction from a string literal, which is trusted to be UTF-8 and is
+  // not validated.
+  template <std::size_t N>
+  static Str from_literal(const char (&)[N]) noexcept;
+
   Str &operator=(const Str &) & noexcept = default;
 
   explicit operator std::string() const;
@@ -155,16 +204,36 @@ public:
   bool operator<=(const Str &) const noexcept;
   bool operator>(const Str &) const noexcept;
   bool operator>=(const Str &) const noexcept;
+#ifdef __cpp_lib_three_way_comparison
+  std::strong_ordering operator<=>(const Str &rhs) const noexcept {
+    // Bytes compare as unsigned, the same as the ordering of str in Rust.
+    return std::lexicographical_compare_three_way(
+        this->begin(), this->end(), rhs.begin(), rhs.end(),
+        [](char lhs, char rhs) {
+          return static_cast<unsigned char>(lhs) <=>
+                 static_cast<unsigned char>(rhs);
+        });
+  }
+#endif // __cpp_lib_three_way_comparison
 
   void swap(Str &) noexcept;
 
 private:
   class uninit;
   Str(uninit) noexcept;
+  struct unchecked_t {};
+  Str(unchecked_t, const char *, std::size_t) noexcept;
   friend impl<Str>;
+  friend Str literals::operator""_rs(const char *, std::size_t) noexcept;
 
   std::array<std::uintptr_t, 2> repr;
 };
+
+template <std::size_t N>
+Str Str::from_literal(const char (&literal)[N]) noexcept {
+  assert(literal[N - 1] == '\0');
+  return Str(unchecked_t{}, literal, N - 1);
+}
 #endif // CXXBRIDGE1_RUST_STR
 
 #ifndef CXXBRIDGE1_RUST_SLICE
@@ -193,6 +262,13 @@ public:
 
   template <typename C>
   explicit Slice(C &c) : Slice(c.data(), c.size()) {}
+  template <typename U, std::size_t N>
+  explicit Slice(U (&array)[N]) noexcept : Slice(array, N) {}
+#ifdef __cpp_lib_span
+  template <std::size_t Extent>
+  Slice(std::span<T, Extent>) noexcept;
+  operator std::span<T>() const noexcept;
+#endif // __cpp_lib_span
 
   Slice &operator=(const Slice<T> &) & noexcept = default;
   Slice &operator=(Slice<T> &&) & noexcept = default;
@@ -217,6 +293,22 @@ public:
 
   void swap(Slice &) noexcept;
 
+#ifdef __cpp_lib_three_way_comparison
+  // Compares the elements, not the addresses, like Rust's Eq and Ord for
+  // slices.
+  friend bool operator==(const Slice &lhs, const Slice &rhs)
+    requires std::equality_comparable<T>
+  {
+    return std::equal(lhs.begin(), lhs.end(), rhs.begin(), rhs.end());
+  }
+  friend auto operator<=>(const Slice &lhs, const Slice &rhs)
+    requires std::three_way_comparable<T>
+  {
+    return std::lexicographical_compare_three_way(lhs.begin(), lhs.end(),
+                                                  rhs.begin(), rhs.end());
+  }
+#endif // __cpp_lib_three_way_comparison
+
 private:
   class uninit;
   Slice(uninit) noexcept;
@@ -232,6 +324,9 @@ private:
 template <typename C>
 explicit Slice(C &c)
     -> Slice<std::remove_reference_t<decltype(*std::declval<C>().data())>>;
+
+template <typename T, std::size_t N>
+explicit Slice(T (&)[N]) -> Slice<T>;
 #endif // __cpp_deduction_guides
 
 template <typename T>
@@ -312,6 +407,12 @@ public:
   template <typename... Fields>
   static Box in_place(Fields &&...);
 
+  // Only for opaque Rust types declared with #[derive(Default)], whose
+  // generated class declares `using IsRustDefault = std::true_type;`.
+  // Constructs the value by calling its Default impl.
+  template <typename U = T, typename = typename U::IsRustDefault>
+  static Box make() noexcept;
+
   void swap(Box &) noexcept;
 
   // Important: requires that `raw` came from an into_raw call. Do not pass a
@@ -334,6 +435,47 @@ private:
 };
 #endif // CXXBRIDGE1_RUST_BOX
 
+#ifndef CXXBRIDGE1_RUST_COMPTR
+// https://cxx.rs/binding/comptr.html
+template <typename T>
+class ComPtr final {
+public:
+  using element_type = T;
+  using pointer = typename std::add_pointer<T>::type;
+
+  ComPtr() noexcept;
+  ComPtr(std::nullptr_t) noexcept;
+  ComPtr(const ComPtr &) noexcept;
+  ComPtr(ComPtr &&) noexcept;
+  ~ComPtr() noexcept;
+
+  // Adopts one reference count of `raw`, without calling AddRef.
+  explicit ComPtr(T *raw) noexcept;
+
+  // Takes a new reference count of `raw` by calling AddRef.
+  static ComPtr copy_from(T *raw) noexcept;
+
+  ComPtr &operator=(const ComPtr &) & noexcept;
+  ComPtr &operator=(ComPtr &&) & noexcept;
+
+  T *operator->() const noexcept;
+  T &operator*() const noexcept;
+  explicit operator bool() const noexcept;
+
+  T *get() const noexcept;
+  // Gives up the reference count owned by this ComPtr, without calling
+  // Release, and leaves it null.
+  T *release() noexcept;
+  void reset() noexcept;
+  void swap(ComPtr &) noexcept;
+
+private:
+  friend void swap(ComPtr &lhs, ComPtr &rhs) noexcept { lhs.swap(rhs); }
+
+  T *ptr;
+};
+#endif // CXXBRIDGE1_RUST_COMPTR
+
 #ifndef CXXBRIDGE1_RUST_VEC
 // https://cxx.rs/binding/vec.html
 template <typename T>
@@ -371,8 +513,11 @@ public:
   void push_back(T &&value);
   template <typename... Args>
   void emplace_back(Args &&...args);
+  void pop_back();
   void truncate(std::size_t len);
   void clear();
+  void resize(std::size_t count);
+  void resize(std::size_t count, const T &value);
 
   using iterator = typename Slice<T>::iterator;
   iterator begin() noexcept;
@@ -384,8 +529,25 @@ public:
   const_iterator cbegin() const noexcept;
   const_iterator cend() const noexcept;
 
+  iterator insert(iterator pos, const T &value);
+  iterator insert(iterator pos, T &&value);
+  template <typename InputIt>
+  iterator insert(iterator pos, InputIt first, InputIt last);
+  iterator insert(iterator pos, std::initializer_list<T>);
+  template <typename... Args>
+  iterator emplace(iterator pos, Args &&...args);
+  iterator erase(iterator pos);
+  iterator erase(iterator first, iterator last);
+
   void swap(Vec &) noexcept;
 
+  // Moves the elements out of a std::vector, or into a new std::vector,
+  // freeing the source buffer. The two containers cannot adopt each other's
+  // allocation, so this costs one bulk copy of the elements but no per-element
+  // calls into Rust.
+  static Vec from_std_vector(std::vector<T> &&);
+  std::vector<T> into_std_vector();
+
   // Internal API only intended for the cxxbridge code generator.
   Vec(unsafe_bitcopy_t, const Vec &) noexcept;
 
@@ -393,9 +555,24 @@ private:
   void reserve_total(std::size_t new_cap) noexcept;
   void set_len(std::size_t len) noexcept;
   void drop() noexcept;
+  void rotate(std::size_t first, std::size_t middle, std::size_t last) noexcept;
 
   friend void swap(Vec &lhs, Vec &rhs) noexcept { lhs.swap(rhs); }
 
+#ifdef __cpp_lib_three_way_comparison
+  friend bool operator==(const Vec &lhs, const Vec &rhs)
+    requires std::equality_comparable<T>
+  {
+    return std::equal(lhs.begin(), lhs.end(), rhs.begin(), rhs.end());
+  }
+  friend auto operator<=>(const Vec &lhs, const Vec &rhs)
+    requires std::three_way_comparable<T>
+  {
+    return std::lexicographical_compare_three_way(lhs.begin(), lhs.end(),
+                                                  rhs.begin(), rhs.end());
+  }
+#endif // __cpp_lib_three_way_comparison
+
   // Size and alignment statically verified by rust_vec.rs.
   std::array<std::uintptr_t, 3> repr;
 };
@@ -418,6 +595,42 @@ private:
 };
 #endif // CXXBRIDGE1_RUST_FN
 
+#ifndef CXXBRIDGE1_RUST_HASHMAP
+// https://cxx.rs/binding/hashmap.html
+template <typename K, typename V>
+class HashMap final {
+public:
+  using key_type = K;
+  using mapped_type = V;
+
+  HashMap(const HashMap &) noexcept = default;
+  HashMap &operator=(const HashMap &) & noexcept = default;
+
+  std::size_t size() const noexcept;
+  bool empty() const noexcept;
+
+  bool contains(const K &key) const noexcept;
+  const V *get(const K &key) const noexcept;
+  const V &at(const K &key) const;
+
+  // Calls f(const K &, const V &) for every entry, in the map's unspecified
+  // iteration order. The callback must not throw.
+  template <typename F>
+  void for_each(F &&f) const;
+
+private:
+  template <typename F>
+  static void visit(void *f, const K *key, const V *value) noexcept;
+
+  // Layout statically verified by rust_hashmap.rs.
+  const void *map;
+  std::size_t (*len)(const void *map);
+  const V *(*lookup)(const void *map, const K *key);
+  void (*iterate)(const void *map, void *f,
+                  void (*visit)(void *f, const K *key, const V *value));
+};
+#endif // CXXBRIDGE1_RUST_HASHMAP
+
 #ifndef CXXBRIDGE1_RUST_ERROR
 #define CXXBRIDGE1_RUST_ERROR
 // https://cxx.rs/binding/result.html
@@ -432,6 +645,12 @@ public:
 
   const char *what() const noexcept override;
 
+  // The code of a cxx::Exception returned by the Rust function, or 0.
+  std::int32_t code() const noexcept;
+  // The messages of the Rust error's source() chain, starting with the
+  // immediate cause.
+  std::vector<std::string> sources() const;
+
 private:
   Error() noexcept = default;
   friend impl<Error>;
@@ -440,6 +659,39 @@ private:
 };
 #endif // CXXBRIDGE1_RUST_ERROR
 
+// https://cxx.rs/binding/result.html#exceptions-that-cannot-be-returned
+//
+// Called with the name of the C++ function from within the catch block, so
+// std::current_exception() and `throw;` refer to the exception, when an
+// exception escapes a non-Result extern "C++" function declared with
+// #[catch_foreign_exceptions]. The process is terminated once the handler
+// returns. Returns the previously registered handler, if any.
+using ForeignExceptionHandler = void (*)(const char *function);
+ForeignExceptionHandler
+set_foreign_exception_handler(ForeignExceptionHandler) noexcept;
+
+// https://docs.rs/cxx/*/cxx/trace/index.html
+//
+// With the `trace` feature of the cxx crate, the hook is called at the start
+// and end of every call across a bridge, in place of any hook installed from
+// Rust. Returns the previously installed hook if it was still installed.
+namespace trace {
+enum class Direction : std::uint8_t {
+  RustToCxx,
+  CxxToRust,
+};
+
+struct Call final {
+  Str function;
+  Direction direction;
+  bool exit;
+  std::uint64_t elapsed_nanos;
+};
+
+using Hook = void (*)(const Call &);
+Hook set_hook(Hook) noexcept;
+} // namespace trace
+
 #ifndef CXXBRIDGE1_RUST_ISIZE
 #define CXXBRIDGE1_RUST_ISIZE
 #if defined(_WIN32)
@@ -489,6 +741,45 @@ std::size_t align_of();
 template <typename T>
 struct IsRelocatable;
 
+// IsSend<T> and IsSync<T> are used in assertions backing a #[send] or #[sync]
+// attribute on an opaque C++ type in the bridge. Both are false unless the C++
+// side opts in, so a bridge cannot claim thread safety for a type whose author
+// never did. A type that is safe to be moved or shared across threads confirms
+// it by doing either of the following things in its header.
+//
+//      --- if you define the type:
+//      struct MyType {
+//        ...
+//    +   using IsSend = std::true_type;
+//      };
+//
+//      --- otherwise:
+//    + template <>
+//    + struct rust::IsSync<MyType> : std::true_type {};
+template <typename T>
+struct IsSend;
+template <typename T>
+struct IsSync;
+
+// Vocabulary<T> converts a third-party C++ type that a bridge declares with
+// #[vocabulary = ...] to and from the builtin binding that Rust sees instead,
+// such as rust::String for #[vocabulary = String] or rust::Vec<uint8_t> for
+// #[vocabulary = "Vec<u8>"]. It has no definition here; the library that
+// provides T specializes it in a header which bridges then `include!`.
+//
+//      template <>
+//      struct rust::Vocabulary<QString> {
+//        static QString from_rust(rust::String s);
+//        static rust::String to_rust(const QString &s);
+//      };
+//
+// A binding of rust::Str or rust::Slice is only suitable for types that are
+// views themselves, such as absl::string_view. The view returned by to_rust
+// must not point into storage owned by the T, because the T returned by a C++
+// function is destroyed before Rust gets to look at the view.
+template <typename T>
+struct Vocabulary;
+
 using u8 = std::uint8_t;
 using u16 = std::uint16_t;
 using u32 = std::uint32_t;
@@ -510,11 +801,17 @@ template <typename T>
 using box = Box<T>;
 template <typename T>
 using vec = Vec<T>;
+template <typename K, typename V>
+using hash_map = HashMap<K, V>;
 using error = Error;
 template <typename Signature>
 using fn = Fn<Signature>;
 template <typename T>
 using is_relocatable = IsRelocatable<T>;
+template <typename T>
+using is_send = IsSend<T>;
+template <typename T>
+using is_sync = IsSync<T>;
 
 
 
@@ -569,6 +866,18 @@ Slice<T>::Slice(T *s, std::size_t count) noexcept {
             count);
 }
 
+#ifdef __cpp_lib_span
+template <typename T>
+template <std::size_t Extent>
+Slice<T>::Slice(std::span<T, Extent> span) noexcept
+    : Slice(span.data(), span.size()) {}
+
+template <typename T>
+Slice<T>::operator std::span<T>() const noexcept {
+  return std::span<T>(this->data(), this->size());
+}
+#endif // __cpp_lib_span
+
 template <typename T>
 T *Slice<T>::data() const noexcept {
   return reinterpret_cast<T *>(slicePtr(this));
@@ -863,6 +1172,95 @@ template <typename T>
 Box<T>::Box(uninit) noexcept {}
 #endif // CXXBRIDGE1_RUST_BOX
 
+#ifndef CXXBRIDGE1_RUST_COMPTR
+#define CXXBRIDGE1_RUST_COMPTR
+template <typename T>
+ComPtr<T>::ComPtr() noexcept : ptr(nullptr) {}
+
+template <typename T>
+ComPtr<T>::ComPtr(std::nullptr_t) noexcept : ptr(nullptr) {}
+
+template <typename T>
+ComPtr<T>::ComPtr(const ComPtr &other) noexcept : ptr(other.ptr) {
+  if (this->ptr) {
+    this->ptr->AddRef();
+  }
+}
+
+template <typename T>
+ComPtr<T>::ComPtr(ComPtr &&other) noexcept : ptr(other.ptr) {
+  other.ptr = nullptr;
+}
+
+template <typename T>
+ComPtr<T>::~ComPtr() noexcept {
+  if (this->ptr) {
+    this->ptr->Release();
+  }
+}
+
+template <typename T>
+ComPtr<T>::ComPtr(T *raw) noexcept : ptr(raw) {}
+
+template <typename T>
+ComPtr<T> ComPtr<T>::copy_from(T *raw) noexcept {
+  if (raw) {
+    raw->AddRef();
+  }
+  return ComPtr(raw);
+}
+
+template <typename T>
+ComPtr<T> &ComPtr<T>::operator=(const ComPtr &other) & noexcept {
+  ComPtr(other).swap(*this);
+  return *this;
+}
+
+template <typename T>
+ComPtr<T> &ComPtr<T>::operator=(ComPtr &&other) & noexcept {
+  ComPtr(std::move(other)).swap(*this);
+  return *this;
+}
+
+template <typename T>
+T *ComPtr<T>::operator->() const noexcept {
+  return this->ptr;
+}
+
+template <typename T>
+T &ComPtr<T>::operator*() const noexcept {
+  return *this->ptr;
+}
+
+template <typename T>
+ComPtr<T>::operator bool() const noexcept {
+  return this->ptr != nullptr;
+}
+
+template <typename T>
+T *ComPtr<T>::get() const noexcept {
+  return this->ptr;
+}
+
+template <typename T>
+T *ComPtr<T>::release() noexcept {
+  T *raw = this->ptr;
+  this->ptr = nullptr;
+  return raw;
+}
+
+template <typename T>
+void ComPtr<T>::reset() noexcept {
+  ComPtr().swap(*this);
+}
+
+template <typename T>
+void ComPtr<T>::swap(ComPtr &rhs) noexcept {
+  using std::swap;
+  swap(this->ptr, rhs.ptr);
+}
+#endif // CXXBRIDGE1_RUST_COMPTR
+
 #ifndef CXXBRIDGE1_RUST_VEC
 #define CXXBRIDGE1_RUST_VEC
 template <typename T>
@@ -994,11 +1392,45 @@ void Vec<T>::emplace_back(Args &&...args) {
   this->set_len(size + 1);
 }
 
+template <typename T>
+void Vec<T>::pop_back() {
+  assert(!this->empty());
+  this->truncate(this->size() - 1);
+}
+
 template <typename T>
 void Vec<T>::clear() {
   this->truncate(0);
 }
 
+template <typename T>
+void Vec<T>::resize(std::size_t count) {
+  auto size = this->size();
+  if (count <= size) {
+    this->truncate(count);
+    return;
+  }
+  this->reserve_total(count);
+  for (; size < count; ++size) {
+    this->emplace_back();
+  }
+}
+
+template <typename T>
+void Vec<T>::resize(std::size_t count, const T &value) {
+  auto size = this->size();
+  if (count <= size) {
+    this->truncate(count);
+    return;
+  }
+  // Copy first, as `value` may refer to an element of this vector.
+  const T copy(value);
+  this->reserve_total(count);
+  for (; size < count; ++size) {
+    this->emplace_back(copy);
+  }
+}
+
 template <typename T>
 typename Vec<T>::iterator Vec<T>::begin() noexcept {
   return Slice<T>(this->data(), this->size()).begin();
@@ -1029,17 +1461,153 @@ typename Vec<T>::const_iterator Vec<T>::cend() const noexcept {
   return Slice<const T>(this->data(), this->size()).end();
 }
 
+template <typename T>
+typename Vec<T>::iterator Vec<T>::insert(iterator pos, const T &value) {
+  return this->emplace(pos, value);
+}
+
+template <typename T>
+typename Vec<T>::iterator Vec<T>::insert(iterator pos, T &&value) {
+  return this->emplace(pos, std::move(value));
+}
+
+template <typename T>
+template <typename InputIt>
+typename Vec<T>::iterator Vec<T>::insert(iterator pos, InputIt first,
+                                         InputIt last) {
+  auto index = static_cast<std::size_t>(pos - this->begin());
+  auto size = this->size();
+  for (; first != last; ++first) {
+    this->emplace_back(*first);
+  }
+  this->rotate(index, size, this->size());
+  return this->begin() + index;
+}
+
+template <typename T>
+typename Vec<T>::iterator Vec<T>::insert(iterator pos,
+                                         std::initializer_list<T> init) {
+  return this->insert(pos, init.begin(), init.end());
+}
+
+template <typename T>
+template <typename... Args>
+typename Vec<T>::iterator Vec<T>::emplace(iterator pos, Args &&...args) {
+  auto index = static_cast<std::size_t>(pos - this->begin());
+  auto size = this->size();
+  this->emplace_back(std::forward<Args>(args)...);
+  this->rotate(index, size, size + 1);
+  return this->begin() + index;
+}
+
+template <typename T>
+Vec<T> Vec<T>::from_std_vector(std::vector<T> &&other) {
+  Vec vec;
+  vec.reserve_total(other.size());
+  auto data = reinterpret_cast<char *>(vec.data());
+  std::size_t len = 0;
+  for (auto it = other.begin(); it != other.end(); ++it) {
+    ::new (reinterpret_cast<T *>(data + len * size_of<T>())) T(std::move(*it));
+    ++len;
+  }
+  vec.set_len(len);
+  std::vector<T>().swap(other);
+  return vec;
+}
+
+template <typename T>
+std::vector<T> Vec<T>::into_std_vector() {
+  std::vector<T> vec(std::make_move_iterator(this->begin()),
+                     std::make_move_iterator(this->end()));
+  Vec().swap(*this);
+  return vec;
+}
+
+template <typename T>
+typename Vec<T>::iterator Vec<T>::erase(iterator pos) {
+  return this->erase(pos, pos + 1);
+}
+
+template <typename T>
+typename Vec<T>::iterator Vec<T>::erase(iterator first, iterator last) {
+  auto begin = this->begin();
+  auto index = static_cast<std::size_t>(first - begin);
+  auto end = static_cast<std::size_t>(last - begin);
+  auto size = this->size();
+  // Move the erased elements to the back, then let Rust drop them.
+  this->rotate(index, end, size);
+  this->truncate(size - (end - index));
+  return this->begin() + index;
+}
+
 template <typename T>
 void Vec<T>::swap(Vec &rhs) noexcept {
   using std::swap;
   swap(this->repr, rhs.repr);
 }
 
+// Elements of a Rust Vec are always trivially relocatable, so they can be
+// reordered bytewise the same way Rust itself moves them.
+template <typename T>
+void Vec<T>::rotate(std::size_t first, std::size_t middle,
+                    std::size_t last) noexcept {
+  auto data = reinterpret_cast<char *>(this->data());
+  auto stride = size_of<T>();
+  std::rotate(data + first * stride, data + middle * stride,
+              data + last * stride);
+}
+
 // Internal API only intended for the cxxbridge code generator.
 template <typename T>
 Vec<T>::Vec(unsafe_bitcopy_t, const Vec &bits) noexcept : repr(bits.repr) {}
 #endif // CXXBRIDGE1_RUST_VEC
 
+#ifndef CXXBRIDGE1_RUST_HASHMAP
+#define CXXBRIDGE1_RUST_HASHMAP
+template <typename K, typename V>
+std::size_t HashMap<K, V>::size() const noexcept {
+  return this->len(this->map);
+}
+
+template <typename K, typename V>
+bool HashMap<K, V>::empty() const noexcept {
+  return this->size() == 0;
+}
+
+template <typename K, typename V>
+bool HashMap<K, V>::contains(const K &key) const noexcept {
+  return this->get(key) != nullptr;
+}
+
+template <typename K, typename V>
+const V *HashMap<K, V>::get(const K &key) const noexcept {
+  return this->lookup(this->map, &key);
+}
+
+template <typename K, typename V>
+const V &HashMap<K, V>::at(const K &key) const {
+  auto value = this->get(key);
+  if (value == nullptr) {
+    panic<std::out_of_range>("rust::HashMap key not found");
+  }
+  return *value;
+}
+
+template <typename K, typename V>
+template <typename F>
+void HashMap<K, V>::for_each(F &&f) const {
+  auto fn = const_cast<void *>(static_cast<const void *>(&f));
+  this->iterate(this->map, fn, &HashMap::visit<F>);
+}
+
+template <typename K, typename V>
+template <typename F>
+void HashMap<K, V>::visit(void *f, const K *key, const V *value) noexcept {
+  using Fn = typename std::remove_reference<F>::type;
+  (*static_cast<Fn *>(f))(*key, *value);
+}
+#endif // CXXBRIDGE1_RUST_HASHMAP
+
 #ifndef CXXBRIDGE1_IS_COMPLETE
 #define CXXBRIDGE1_IS_COMPLETE
 namespace detail {
@@ -1145,5 +1713,53 @@ struct IsRelocatable
                         std::is_trivially_destructible<T>::value>>::type {};
 #endif // CXXBRIDGE1_RELOCATABLE
 
+#ifndef CXXBRIDGE1_SEND_SYNC
+#define CXXBRIDGE1_SEND_SYNC
+namespace detail {
+template <typename T>
+using detect_IsSend = typename T::IsSend;
+template <typename T>
+using detect_IsSync = typename T::IsSync;
+
+template <typename T>
+struct get_IsSend : std::is_same<typename T::IsSend, std::true_type> {};
+template <typename T>
+struct get_IsSync : std::is_same<typename T::IsSync, std::true_type> {};
+} // namespace detail
+
+template <typename T>
+struct IsSend
+    : std::conditional<detail::is_detected<detail::detect_IsSend, T>::value,
+                       detail::get_IsSend<T>, std::false_type>::type {};
+
+template <typename T>
+struct IsSync
+    : std::conditional<detail::is_detected<detail::detect_IsSync, T>::value,
+                       detail::get_IsSync<T>, std::false_type>::type {};
+#endif // CXXBRIDGE1_SEND_SYNC
+
 } // namespace cxxbridge1
 } // namespace rust
+
+namespace std {
+template <>
+struct hash<rust::Str> {
+  std::size_t operator()(rust::Str) const noexcept;
+};
+
+template <>
+struct hash<rust::String> {
+  std::size_t operator()(const rust::String &) const noexcept;
+};
+
+template <typename T>
+struct hash<rust::Vec<T>> {
+  std::size_t operator()(const rust::Vec<T> &vec) const noexcept {
+    std::size_t seed = vec.size();
+    for (const T &element : vec) {
+      seed ^= hash<T>{}(element) + 0x9e3779b9 + (seed << 6) + (seed >> 2);
+    }
+    return seed;
+  }
+};
+} // namespace std
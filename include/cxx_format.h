@@ -0,0 +1,127 @@
+#pragma once
+#include "rust/cxx.h"
+
+#ifdef __has_include
+#if __cplusplus >= 202002L && __has_include(<format>)
+#include <format>
+#endif
+#if __has_include(<fmt/format.h>)
+#include <fmt/format.h>
+#endif
+#endif
+
+// Formatter specializations for printing rust::Str, rust::String and byte
+// slices with std::format and fmtlib. This header is separate from rust/cxx.h
+// so that only code which formats these types pays for including <format> or
+// fmtlib. The std::format ones need C++20, and the fmtlib ones are available
+// whenever <fmt/format.h> is on the include path.
+#if defined(__cpp_lib_format) || defined(FMT_VERSION)
+namespace rust {
+inline namespace cxxbridge1 {
+namespace detail {
+// Formats as a list of the bytes, each according to the format spec given for
+// the slice, like "[1, 2, 3]" for "{}" or "[01, 02, 03]" for "{:02x}".
+template <typename Formatter, typename FormatContext>
+auto format_bytes(const Formatter &formatter, Slice<const std::uint8_t> bytes,
+                  FormatContext &ctx) -> decltype(ctx.out()) {
+  auto out = ctx.out();
+  *out++ = '[';
+  for (std::size_t i = 0; i < bytes.size(); i++) {
+    if (i != 0) {
+      *out++ = ',';
+      *out++ = ' ';
+    }
+    ctx.advance_to(out);
+    out = formatter.format(bytes[i], ctx);
+  }
+  *out++ = ']';
+  return out;
+}
+} // namespace detail
+} // namespace cxxbridge1
+} // namespace rust
+#endif
+
+#ifdef __cpp_lib_format
+namespace std {
+template <>
+struct formatter<rust::Str> : formatter<std::string_view> {
+  template <typename FormatContext>
+  auto format(rust::Str s, FormatContext &ctx) const {
+    return formatter<std::string_view>::format(
+        std::string_view(s.data(), s.size()), ctx);
+  }
+};
+
+template <>
+struct formatter<rust::String> : formatter<rust::Str> {
+  template <typename FormatContext>
+  auto format(const rust::String &s, FormatContext &ctx) const {
+    return formatter<rust::Str>::format(s, ctx);
+  }
+};
+
+template <>
+struct formatter<rust::Slice<const std::uint8_t>> : formatter<std::uint8_t> {
+  template <typename FormatContext>
+  auto format(rust::Slice<const std::uint8_t> bytes,
+              FormatContext &ctx) const {
+    const formatter<std::uint8_t> &element = *this;
+    return rust::detail::format_bytes(element, bytes, ctx);
+  }
+};
+
+template <>
+struct formatter<rust::Slice<std::uint8_t>>
+    : formatter<rust::Slice<const std::uint8_t>> {
+  template <typename FormatContext>
+  auto format(rust::Slice<std::uint8_t> bytes, FormatContext &ctx) const {
+    return formatter<rust::Slice<const std::uint8_t>>::format(
+        rust::Slice<const std::uint8_t>(bytes.data(), bytes.size()), ctx);
+  }
+};
+} // namespace std
+#endif // __cpp_lib_format
+
+#ifdef FMT_VERSION
+namespace fmt {
+template <>
+struct formatter<rust::Str> : formatter<fmt::string_view> {
+  template <typename FormatContext>
+  auto format(rust::Str s, FormatContext &ctx) const -> decltype(ctx.out()) {
+    return formatter<fmt::string_view>::format(
+        fmt::string_view(s.data(), s.size()), ctx);
+  }
+};
+
+template <>
+struct formatter<rust::String> : formatter<rust::Str> {
+  template <typename FormatContext>
+  auto format(const rust::String &s, FormatContext &ctx) const
+      -> decltype(ctx.out()) {
+    return formatter<rust::Str>::format(s, ctx);
+  }
+};
+
+template <>
+struct formatter<rust::Slice<const std::uint8_t>> : formatter<std::uint8_t> {
+  template <typename FormatContext>
+  auto format(rust::Slice<const std::uint8_t> bytes, FormatContext &ctx) const
+      -> decltype(ctx.out()) {
+    const formatter<std::uint8_t> &element = *this;
+    return rust::detail::format_bytes(element, bytes, ctx);
+  }
+};
+
+template <>
+struct formatter<rust::Slice<std::uint8_t>>
+    : formatter<rust::Slice<const std::uint8_t>> {
+  template <typename FormatContext>
+  auto format(rust::Slice<std::uint8_t> bytes, FormatContext &ctx) const
+      -> decltype(ctx.out()) {
+    return formatter<rust::Slice<const std::uint8_t>>::format(
+        rust::Slice<const std::uint8_t>(bytes.data(), bytes.size()), ctx);
+  }
+};
+} // namespace fmt
+#endif // FMT_VERSION
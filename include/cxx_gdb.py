@@ -0,0 +1,169 @@
+# GDB pretty-printers for the types of a CXX bridge, on the C++ side
+# rust::String, rust::Str, rust::Vec<T>, rust::Slice<T> and rust::Box<T>, and
+# on the Rust side CxxString, CxxVector<T>, UniquePtr<T> and SharedPtr<T>.
+#
+# cxx_build writes this file to target/cxxbridge/rust/cxx_gdb.py, with the
+# @...@ placeholders replaced by which word of the representation of Rust's
+# String, Vec<T> and slices holds the pointer and which the length, since Rust
+# does not guarantee the order of those. Load it with:
+#
+#     (gdb) source target/cxxbridge/rust/cxx_gdb.py
+
+import gdb
+import gdb.printing
+
+STRING_PTR, STRING_LEN = @STRING_PTR@, @STRING_LEN@
+VEC_PTR, VEC_LEN = @VEC_PTR@, @VEC_LEN@
+SLICE_PTR, SLICE_LEN = @SLICE_PTR@, @SLICE_LEN@
+
+STD_STRING = [
+    "std::string",
+    "std::__cxx11::basic_string<char, std::char_traits<char>, std::allocator<char> >",
+    "std::__1::basic_string<char, std::__1::char_traits<char>, std::__1::allocator<char> >",
+]
+
+
+def _pointers(address, count):
+    # The words at the address, as integers.
+    word = gdb.lookup_type("char").pointer()
+    words = address.cast(word.pointer())
+    return [int(words[i]) for i in range(count)]
+
+
+def _pointer(address, target):
+    return gdb.Value(address).cast(target.pointer())
+
+
+class _StringPrinter:
+    def __init__(self, ptr, length):
+        self.ptr = ptr
+        self.length = length
+
+    def to_string(self):
+        return self.ptr.lazy_string(encoding="utf-8", length=self.length)
+
+    def display_hint(self):
+        return "string"
+
+
+class _ArrayPrinter:
+    def __init__(self, name, ptr, length):
+        self.name = name
+        self.ptr = ptr
+        self.length = length
+
+    def to_string(self):
+        return "{}(size={})".format(self.name, self.length)
+
+    def children(self):
+        for i in range(self.length):
+            yield "[{}]".format(i), (self.ptr + i).dereference()
+
+    def display_hint(self):
+        return "array"
+
+
+class _PointerPrinter:
+    def __init__(self, name, ptr):
+        self.name = name
+        self.ptr = ptr
+
+    def to_string(self):
+        if int(self.ptr) == 0:
+            return "{}(nullptr)".format(self.name)
+        return "{}({})".format(self.name, self.ptr)
+
+    def children(self):
+        if int(self.ptr) != 0:
+            yield "*", self.ptr.dereference()
+
+
+class _ValuePrinter:
+    def __init__(self, value):
+        self.value = value
+
+    def to_string(self):
+        return self.value
+
+
+def rust_string(val):
+    words = _pointers(val["repr"].address, 3)
+    ptr = _pointer(words[STRING_PTR], gdb.lookup_type("char"))
+    return _StringPrinter(ptr, words[STRING_LEN])
+
+
+def rust_str(val):
+    return _StringPrinter(val["ptr"], int(val["len"]))
+
+
+def rust_vec(val):
+    words = _pointers(val["repr"].address, 3)
+    ptr = _pointer(words[VEC_PTR], val.type.template_argument(0))
+    return _ArrayPrinter("rust::Vec", ptr, words[VEC_LEN])
+
+
+def rust_slice(val):
+    words = _pointers(val["repr"].address, 2)
+    ptr = _pointer(words[SLICE_PTR], val.type.template_argument(0))
+    return _ArrayPrinter("rust::Slice", ptr, words[SLICE_LEN])
+
+
+def rust_box(val):
+    return _PointerPrinter("rust::Box", val["ptr"])
+
+
+def cxx_string(val):
+    # A CxxString is the std::string at the same address.
+    for name in STD_STRING:
+        try:
+            std_string = gdb.lookup_type(name)
+        except gdb.error:
+            continue
+        return _ValuePrinter(val.address.cast(std_string.pointer()).dereference())
+    return None
+
+
+def cxx_vector(val):
+    # A std::vector begins with pointers to its first and past its last element.
+    element = val.type.template_argument(0)
+    if element.sizeof == 0:
+        return None
+    begin, end = _pointers(val.address, 2)
+    return _ArrayPrinter("CxxVector", _pointer(begin, element), (end - begin) // element.sizeof)
+
+
+def cxx_smart_pointer(name):
+    # Both smart pointers begin with the pointer to the object they own.
+    def printer(val):
+        target = val.type.template_argument(0)
+        ptr = val["repr"].address.cast(target.pointer().pointer()).dereference()
+        return _PointerPrinter(name, ptr)
+
+    return printer
+
+
+PRINTERS = {
+    "rust::cxxbridge1::String": rust_string,
+    "rust::cxxbridge1::Str": rust_str,
+    "rust::cxxbridge1::Vec": rust_vec,
+    "rust::cxxbridge1::Slice": rust_slice,
+    "rust::cxxbridge1::Box": rust_box,
+    "cxx::cxx_string::CxxString": cxx_string,
+    "cxx::cxx_vector::CxxVector": cxx_vector,
+    "cxx::unique_ptr::UniquePtr": cxx_smart_pointer("UniquePtr"),
+    "cxx::shared_ptr::SharedPtr": cxx_smart_pointer("SharedPtr"),
+}
+
+
+def cxx_lookup(val):
+    ty = val.type.strip_typedefs().unqualified()
+    name = ty.tag or ty.name
+    if name is None:
+        return None
+    printer = PRINTERS.get(name.split("<", 1)[0])
+    if printer is None:
+        return None
+    return printer(val)
+
+
+gdb.printing.register_pretty_printer(gdb.current_objfile(), cxx_lookup)
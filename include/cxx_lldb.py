@@ -0,0 +1,198 @@
+# LLDB formatters for the types of a CXX bridge, on the C++ side rust::String,
+# rust::Str, rust::Vec<T>, rust::Slice<T> and rust::Box<T>, and on the Rust
+# side CxxString, CxxVector<T>, UniquePtr<T> and SharedPtr<T>.
+#
+# cxx_build writes this file to target/cxxbridge/rust/cxx_lldb.py, with the
+# @...@ placeholders replaced by which word of the representation of Rust's
+# String, Vec<T> and slices holds the pointer and which the length, since Rust
+# does not guarantee the order of those. Load it with:
+#
+#     (lldb) command script import target/cxxbridge/rust/cxx_lldb.py
+
+import lldb
+
+STRING_PTR, STRING_LEN = @STRING_PTR@, @STRING_LEN@
+VEC_PTR, VEC_LEN = @VEC_PTR@, @VEC_LEN@
+SLICE_PTR, SLICE_LEN = @SLICE_PTR@, @SLICE_LEN@
+
+# Longest string to read from the process for a summary.
+MAX_STRING = 1024
+
+STD_STRING = [
+    "std::string",
+    "std::__cxx11::basic_string<char, std::char_traits<char>, std::allocator<char> >",
+    "std::__1::basic_string<char, std::__1::char_traits<char>, std::__1::allocator<char> >",
+]
+
+
+def _pointers(valobj, address, count):
+    # The words at the address, as integers.
+    process = valobj.GetProcess()
+    size = valobj.GetTarget().GetAddressByteSize()
+    error = lldb.SBError()
+    return [process.ReadPointerFromMemory(address + i * size, error) for i in range(count)]
+
+
+def _repr(valobj, count):
+    address = valobj.GetChildMemberWithName("repr").GetLoadAddress()
+    return _pointers(valobj, address, count)
+
+
+def _string(valobj, ptr, length):
+    if length == 0:
+        return '""'
+    error = lldb.SBError()
+    data = valobj.GetProcess().ReadMemory(ptr, min(length, MAX_STRING), error)
+    if error.Fail():
+        return "<error: {}>".format(error.GetCString())
+    string = data.decode("utf-8", "replace")
+    if length > MAX_STRING:
+        string += "..."
+    return '"{}"'.format(string.replace("\\", "\\\\").replace('"', '\\"'))
+
+
+def rust_string_summary(valobj, internal_dict):
+    words = _repr(valobj, 3)
+    return _string(valobj, words[STRING_PTR], words[STRING_LEN])
+
+
+def rust_str_summary(valobj, internal_dict):
+    ptr = valobj.GetChildMemberWithName("ptr").GetValueAsUnsigned()
+    length = valobj.GetChildMemberWithName("len").GetValueAsUnsigned()
+    return _string(valobj, ptr, length)
+
+
+def cxx_string_summary(valobj, internal_dict):
+    # A CxxString is the std::string at the same address.
+    target = valobj.GetTarget()
+    for name in STD_STRING:
+        std_string = target.FindFirstType(name)
+        if std_string.IsValid():
+            address = valobj.GetLoadAddress()
+            string = valobj.CreateValueFromAddress("string", address, std_string)
+            return string.GetSummary()
+    return None
+
+
+def array_summary(valobj, internal_dict):
+    return "size={}".format(valobj.GetNumChildren())
+
+
+def pointer_summary(valobj, internal_dict):
+    if valobj.GetNumChildren() == 0:
+        return "nullptr"
+    return "0x{:x}".format(valobj.GetChildAtIndex(0).GetLoadAddress())
+
+
+class _ArrayProvider:
+    def __init__(self, valobj, internal_dict):
+        self.valobj = valobj
+        self.ptr = 0
+        self.length = 0
+        self.element = None
+
+    def update(self):
+        self.ptr, self.length, self.element = self.locate()
+        return False
+
+    def has_children(self):
+        return True
+
+    def num_children(self):
+        return self.length
+
+    def get_child_index(self, name):
+        try:
+            return int(name.lstrip("[").rstrip("]"))
+        except ValueError:
+            return -1
+
+    def get_child_at_index(self, index):
+        address = self.ptr + index * self.element.GetByteSize()
+        name = "[{}]".format(index)
+        return self.valobj.CreateValueFromAddress(name, address, self.element)
+
+
+class RustVecProvider(_ArrayProvider):
+    def locate(self):
+        words = _repr(self.valobj, 3)
+        element = self.valobj.GetType().GetTemplateArgumentType(0)
+        return words[VEC_PTR], words[VEC_LEN], element
+
+
+class RustSliceProvider(_ArrayProvider):
+    def locate(self):
+        words = _repr(self.valobj, 2)
+        element = self.valobj.GetType().GetTemplateArgumentType(0)
+        return words[SLICE_PTR], words[SLICE_LEN], element
+
+
+class CxxVectorProvider(_ArrayProvider):
+    # A std::vector begins with pointers to its first and past its last element.
+    def locate(self):
+        element = self.valobj.GetType().GetTemplateArgumentType(0)
+        if element.GetByteSize() == 0:
+            return 0, 0, element
+        begin, end = _pointers(self.valobj, self.valobj.GetLoadAddress(), 2)
+        return begin, (end - begin) // element.GetByteSize(), element
+
+
+class _PointerProvider:
+    def __init__(self, valobj, internal_dict):
+        self.valobj = valobj
+        self.ptr = 0
+        self.target = None
+
+    def update(self):
+        self.ptr, self.target = self.locate()
+        return False
+
+    def has_children(self):
+        return self.ptr != 0
+
+    def num_children(self):
+        return 1 if self.ptr != 0 else 0
+
+    def get_child_index(self, name):
+        return 0 if name == "*" else -1
+
+    def get_child_at_index(self, index):
+        return self.valobj.CreateValueFromAddress("*", self.ptr, self.target)
+
+
+class RustBoxProvider(_PointerProvider):
+    def locate(self):
+        ptr = self.valobj.GetChildMemberWithName("ptr")
+        return ptr.GetValueAsUnsigned(), ptr.GetType().GetPointeeType()
+
+
+class CxxSmartPointerProvider(_PointerProvider):
+    # Both smart pointers begin with the pointer to the object they own.
+    def locate(self):
+        (ptr,) = _repr(self.valobj, 1)
+        return ptr, self.valobj.GetType().GetTemplateArgumentType(0)
+
+
+FORMATTERS = [
+    ("^rust::cxxbridge1::String$", "rust_string_summary", None),
+    ("^rust::cxxbridge1::Str$", "rust_str_summary", None),
+    ("^rust::cxxbridge1::Vec<.+>$", "array_summary", "RustVecProvider"),
+    ("^rust::cxxbridge1::Slice<.+>$", "array_summary", "RustSliceProvider"),
+    ("^rust::cxxbridge1::Box<.+>$", "pointer_summary", "RustBoxProvider"),
+    ("^cxx::cxx_string::CxxString$", "cxx_string_summary", None),
+    ("^cxx::cxx_vector::CxxVector<.+>$", "array_summary", "CxxVectorProvider"),
+    ("^cxx::unique_ptr::UniquePtr<.+>$", "pointer_summary", "CxxSmartPointerProvider"),
+    ("^cxx::shared_ptr::SharedPtr<.+>$", "pointer_summary", "CxxSmartPointerProvider"),
+]
+
+
+def __lldb_init_module(debugger, internal_dict):
+    for regex, summary, synthetic in FORMATTERS:
+        debugger.HandleCommand(
+            'type summary add -w cxx -F {}.{} -x "{}"'.format(__name__, summary, regex)
+        )
+        if synthetic is not None:
+            debugger.HandleCommand(
+                'type synthetic add -w cxx -l {}.{} -x "{}"'.format(__name__, synthetic, regex)
+            )
+    debugger.HandleCommand("type category enable cxx")
@@ -0,0 +1,109 @@
+#pragma once
+#include <cstddef>
+#include <cstdio>
+#include <cstdlib>
+#include <exception>
+#include <string>
+
+// Test cases written in C++ against the generated bindings, run by `cargo
+// test` through the Rust tests that cxx_build::tests and cxx::cxx_tests!
+// define for each CXX_TEST in the test files.
+//
+//     CXX_TEST(roundtrip) {
+//       auto value = make_value(1);
+//       CXX_ASSERT(value->is_valid());
+//       CXX_ASSERT_EQ(value->get(), 1);
+//     }
+//
+// A test case fails if an assertion fails or if any exception escapes it, and
+// the Rust test panics with the assertion's or exception's message. Built with
+// -fno-exceptions, a failed assertion instead prints its message to stderr and
+// aborts the test process.
+
+// Detected the same way as in cxx.cc; define RUST_CXX_NO_EXCEPTIONS by hand on
+// compilers where this does not work.
+#if defined(__cpp_attributes) && !defined(__cpp_exceptions) &&                 \
+    (!defined(_MSC_VER) || defined(__llvm__)) &&                               \
+    !defined(RUST_CXX_NO_EXCEPTIONS)
+#define RUST_CXX_NO_EXCEPTIONS
+#endif
+
+namespace rust {
+inline namespace cxxbridge1 {
+namespace test {
+
+class failure final : public std::exception {
+public:
+  failure(const char *file, int line, const char *message)
+      : msg(std::string(file) + ':' + std::to_string(line) + ": " + message) {}
+  const char *what() const noexcept override { return msg.c_str(); }
+
+private:
+  std::string msg;
+};
+
+[[noreturn]] inline void fail(const char *file, int line,
+                              const char *message) {
+#if defined(RUST_CXX_NO_EXCEPTIONS)
+  std::fprintf(stderr, "%s\n", failure(file, line, message).what());
+  std::fflush(stderr);
+  std::abort();
+#else
+  throw failure(file, line, message);
+#endif
+}
+
+template <typename L, typename R>
+void assert_eq(const L &left, const R &right, const char *file, int line,
+               const char *message) {
+  if (!(left == right)) {
+    fail(file, line, message);
+  }
+}
+
+// The message of a failed test stays valid until the next test case runs on
+// the same thread, which gives the Rust side time to copy it into its panic.
+inline bool run(void (*test)(), const char **message,
+                std::size_t *len) noexcept {
+#if defined(RUST_CXX_NO_EXCEPTIONS)
+  (void)message;
+  (void)len;
+  test();
+  return true;
+#else
+  thread_local std::string last_failure;
+  try {
+    test();
+    return true;
+  } catch (const std::exception &e) {
+    last_failure = e.what();
+  } catch (...) {
+    last_failure = "test threw an exception not derived from std::exception";
+  }
+  *message = last_failure.data();
+  *len = last_failure.size();
+  return false;
+#endif
+}
+
+} // namespace test
+} // namespace cxxbridge1
+} // namespace rust
+
+#define CXX_TEST(name)                                                         \
+  static void cxx_test_##name();                                               \
+  extern "C" bool cxxbridge1$test$##name(const char **message,                 \
+                                         std::size_t *len) noexcept {          \
+    return ::rust::test::run(cxx_test_##name, message, len);                   \
+  }                                                                            \
+  static void cxx_test_##name()
+
+#define CXX_ASSERT(condition)                                                  \
+  (static_cast<bool>(condition)                                                \
+       ? void()                                                                \
+       : ::rust::test::fail(__FILE__, __LINE__,                                \
+                            "assertion failed: " #condition))
+
+#define CXX_ASSERT_EQ(left, right)                                             \
+  ::rust::test::assert_eq((left), (right), __FILE__, __LINE__,                 \
+                          "assertion failed: " #left " == " #right)
@@ -6,6 +6,7 @@ pub(crate) use crate::syntax::derive::*;
 
 pub(crate) fn expand_struct(
     strct: &Struct,
+    arbitrary: bool,
     actual_derives: &mut Option<TokenStream>,
 ) -> TokenStream {
     let mut expanded = TokenStream::new();
@@ -26,6 +27,10 @@ pub(crate) fn expand_struct(
             Trait::PartialOrd => expanded.extend(struct_partial_ord(strct, span)),
             Trait::Serialize => traits.push(quote_spanned!(span=> ::serde::Serialize)),
             Trait::Deserialize => traits.push(quote_spanned!(span=> ::serde::Deserialize)),
+            Trait::Arbitrary if arbitrary => {
+                traits.push(quote_spanned!(span=> ::arbitrary::Arbitrary));
+            }
+            Trait::Arbitrary => traits.push(quote_spanned!(span=> super::Arbitrary)),
             Trait::Other(ident) => traits.push(quote_spanned!(span=> super::#ident)),
         }
     }
@@ -39,7 +44,11 @@ pub(crate) fn expand_struct(
     expanded
 }
 
-pub(crate) fn expand_enum(enm: &Enum, actual_derives: &mut Option<TokenStream>) -> TokenStream {
+pub(crate) fn expand_enum(
+    enm: &Enum,
+    arbitrary: bool,
+    actual_derives: &mut Option<TokenStream>,
+) -> TokenStream {
     let mut expanded = TokenStream::new();
     let mut traits = Vec::new();
     let mut has_copy = false;
@@ -72,8 +81,18 @@ pub(crate) fn expand_enum(enm: &Enum, actual_derives: &mut Option<TokenStream>)
                 has_partial_eq = true;
             }
             Trait::PartialOrd => expanded.extend(enum_partial_ord(enm, span)),
+            Trait::Serialize if enm.serde_variant_names => {
+                expanded.extend(enum_serialize(enm, span));
+            }
+            Trait::Deserialize if enm.serde_variant_names => {
+                expanded.extend(enum_deserialize(enm, span));
+            }
             Trait::Serialize => traits.push(quote_spanned!(span=> ::serde::Serialize)),
             Trait::Deserialize => traits.push(quote_spanned!(span=> ::serde::Deserialize)),
+            Trait::Arbitrary if arbitrary => {
+                expanded.extend(enum_arbitrary(enm, span));
+            }
+            Trait::Arbitrary => traits.push(quote_spanned!(span=> super::Arbitrary)),
             Trait::Other(ident) => traits.push(quote_spanned!(span=> super::#ident)),
         }
     }
@@ -279,6 +298,132 @@ fn enum_debug(enm: &Enum, span: Span) -> TokenStream {
     }
 }
 
+// With #[serde_variant_names], human-readable formats get the variant name, the
+// same as serde's derive on a Rust fieldless enum would produce, and compact
+// formats get the discriminant. Discriminants without a corresponding variant,
+// which can come from C++, are written as the number in either case.
+fn enum_serialize(enm: &Enum, span: Span) -> TokenStream {
+    let ident = &enm.name.rust;
+    let name = ident.to_string();
+    let variants = enm.variants.iter().enumerate().map(|(i, variant)| {
+        let variant_index = i as u32;
+        let variant = &variant.name.rust;
+        let variant_name = variant.to_string();
+        quote_spanned! {span=>
+            #ident::#variant => ::serde::Serializer::serialize_unit_variant(serializer, #name, #variant_index, #variant_name),
+        }
+    });
+
+    quote_spanned! {span=>
+        #[automatically_derived]
+        impl ::serde::Serialize for #ident {
+            fn serialize<__S>(&self, serializer: __S) -> ::cxx::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: ::serde::Serializer,
+            {
+                if !::serde::Serializer::is_human_readable(&serializer) {
+                    return ::serde::Serialize::serialize(&self.repr, serializer);
+                }
+                match *self {
+                    #(#variants)*
+                    _ => ::serde::Serialize::serialize(&self.repr, serializer),
+                }
+            }
+        }
+    }
+}
+
+fn enum_deserialize(enm: &Enum, span: Span) -> TokenStream {
+    let ident = &enm.name.rust;
+    let repr = &enm.repr;
+    let expecting = format!("variant name or discriminant of enum {}", ident);
+    let variant_names = enm
+        .variants
+        .iter()
+        .map(|variant| variant.name.rust.to_string())
+        .collect::<Vec<_>>();
+    let variants = enm.variants.iter().map(|variant| {
+        let variant = &variant.name.rust;
+        let variant_name = variant.to_string();
+        quote_spanned! {span=>
+            #variant_name => ::cxx::core::result::Result::Ok(#ident::#variant),
+        }
+    });
+
+    quote_spanned! {span=>
+        #[automatically_derived]
+        impl<'de> ::serde::Deserialize<'de> for #ident {
+            fn deserialize<__D>(deserializer: __D) -> ::cxx::core::result::Result<Self, __D::Error>
+            where
+                __D: ::serde::Deserializer<'de>,
+            {
+                struct __Visitor;
+
+                impl<'de> ::serde::de::Visitor<'de> for __Visitor {
+                    type Value = #ident;
+
+                    fn expecting(&self, formatter: &mut ::cxx::core::fmt::Formatter<'_>) -> ::cxx::core::fmt::Result {
+                        formatter.write_str(#expecting)
+                    }
+
+                    fn visit_str<__E>(self, v: &str) -> ::cxx::core::result::Result<Self::Value, __E>
+                    where
+                        __E: ::serde::de::Error,
+                    {
+                        match v {
+                            #(#variants)*
+                            _ => ::cxx::core::result::Result::Err(__E::unknown_variant(v, &[#(#variant_names),*])),
+                        }
+                    }
+
+                    fn visit_u64<__E>(self, v: u64) -> ::cxx::core::result::Result<Self::Value, __E>
+                    where
+                        __E: ::serde::de::Error,
+                    {
+                        match <#repr as ::cxx::core::convert::TryFrom<u64>>::try_from(v) {
+                            ::cxx::core::result::Result::Ok(repr) => ::cxx::core::result::Result::Ok(#ident { repr }),
+                            ::cxx::core::result::Result::Err(_) => ::cxx::core::result::Result::Err(__E::invalid_value(::serde::de::Unexpected::Unsigned(v), &self)),
+                        }
+                    }
+
+                    fn visit_i64<__E>(self, v: i64) -> ::cxx::core::result::Result<Self::Value, __E>
+                    where
+                        __E: ::serde::de::Error,
+                    {
+                        match <#repr as ::cxx::core::convert::TryFrom<i64>>::try_from(v) {
+                            ::cxx::core::result::Result::Ok(repr) => ::cxx::core::result::Result::Ok(#ident { repr }),
+                            ::cxx::core::result::Result::Err(_) => ::cxx::core::result::Result::Err(__E::invalid_value(::serde::de::Unexpected::Signed(v), &self)),
+                        }
+                    }
+                }
+
+                if ::serde::Deserializer::is_human_readable(&deserializer) {
+                    ::serde::Deserializer::deserialize_any(deserializer, __Visitor)
+                } else {
+                    let repr = <#repr as ::serde::Deserialize>::deserialize(deserializer)?;
+                    ::cxx::core::result::Result::Ok(#ident { repr })
+                }
+            }
+        }
+    }
+}
+
+// Only the variants of the bridge are generated, although C++ may hand over
+// other discriminants too.
+fn enum_arbitrary(enm: &Enum, span: Span) -> TokenStream {
+    let ident = &enm.name.rust;
+    let variants = enm.variants.iter().map(|variant| &variant.name.rust);
+
+    quote_spanned! {span=>
+        #[automatically_derived]
+        impl<'arbitrary> ::arbitrary::Arbitrary<'arbitrary> for #ident {
+            fn arbitrary(u: &mut ::arbitrary::Unstructured<'arbitrary>) -> ::arbitrary::Result<Self> {
+                ::cxx::core::result::Result::Ok(*u.choose(&[#(#ident::#variants),*])?)
+            }
+        }
+    }
+}
+
 fn enum_ord(enm: &Enum, span: Span) -> TokenStream {
     let ident = &enm.name.rust;
 
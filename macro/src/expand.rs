@@ -14,8 +14,10 @@ use crate::type_id::Crate;
 use crate::{derive, generics};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
+use std::env;
 use std::mem;
-use syn::{parse_quote, punctuated, Generics, Lifetime, Result, Token};
+use std::path::PathBuf;
+use syn::{parse_quote, punctuated, Generics, Lifetime, LitInt, Path, Result, Token};
 
 pub(crate) fn bridge(mut ffi: Module) -> Result<TokenStream> {
     let ref mut errors = Errors::new();
@@ -32,6 +34,7 @@ pub(crate) fn bridge(mut ffi: Module) -> Result<TokenStream> {
         },
     );
 
+    let included_files = ffi.included_files();
     let content = mem::take(&mut ffi.content);
     let trusted = ffi.unsafety.is_some();
     let namespace = &ffi.namespace;
@@ -43,13 +46,22 @@ pub(crate) fn bridge(mut ffi: Module) -> Result<TokenStream> {
     check::typecheck(errors, apis, types, generator);
     errors.propagate()?;
 
-    Ok(expand(ffi, doc, attrs, apis, types))
+    Ok(expand(ffi, doc, attrs, apis, types, &included_files))
 }
 
-fn expand(ffi: Module, doc: Doc, attrs: OtherAttrs, apis: &[Api], types: &Types) -> TokenStream {
+fn expand(
+    ffi: Module,
+    doc: Doc,
+    attrs: OtherAttrs,
+    apis: &[Api],
+    types: &Types,
+    included_files: &[PathBuf],
+) -> TokenStream {
     let mut expanded = TokenStream::new();
     let mut hidden = TokenStream::new();
     let mut forbid = TokenStream::new();
+    let mut inherent_impls = Vec::new();
+    let stub = stub_mode();
 
     for api in apis {
         if let Api::RustType(ety) = api {
@@ -62,26 +74,44 @@ fn expand(ffi: Module, doc: Doc, attrs: OtherAttrs, apis: &[Api], types: &Types)
         match api {
             Api::Include(_) | Api::Impl(_) => {}
             Api::Struct(strct) => {
-                expanded.extend(expand_struct(strct));
+                expanded.extend(expand_struct(strct, ffi.arbitrary));
                 hidden.extend(expand_struct_operators(strct));
                 forbid.extend(expand_struct_forbid_drop(strct));
             }
-            Api::Enum(enm) => expanded.extend(expand_enum(enm)),
+            Api::Enum(enm) => expanded.extend(expand_enum(enm, ffi.arbitrary)),
+            Api::CxxType(ety) if ety.smart_ptr => {
+                expanded.extend(expand_smart_ptr_family(ety));
+            }
+            Api::CxxType(ety) if ety.vocabulary.is_some() => {}
             Api::CxxType(ety) => {
                 let ident = &ety.name.rust;
                 if !types.structs.contains_key(ident) && !types.enums.contains_key(ident) {
                     expanded.extend(expand_cxx_type(ety));
                     hidden.extend(expand_cxx_type_assert_pinned(ety, types));
+                    if let Some(iid) = ety.com_iid {
+                        expanded.extend(expand_com_interface(ety, iid));
+                    }
                 }
             }
             Api::CxxFunction(efn) => {
-                expanded.extend(expand_cxx_function_shim(efn, types));
+                let fake = ffi.fake.as_ref();
+                expanded.extend(expand_cxx_function_shim(
+                    efn,
+                    types,
+                    fake,
+                    ffi.trace,
+                    &mut inherent_impls,
+                ));
             }
             Api::RustType(ety) => {
                 expanded.extend(expand_rust_type_impl(ety));
                 hidden.extend(expand_rust_type_layout(ety, types));
+                hidden.extend(expand_rust_type_operators(ety));
+            }
+            Api::RustFunction(efn) if stub => hidden.extend(expand_rust_function_stub(efn, types)),
+            Api::RustFunction(efn) => {
+                hidden.extend(expand_rust_function_shim(efn, types, ffi.trace));
             }
-            Api::RustFunction(efn) => hidden.extend(expand_rust_function_shim(efn, types)),
             Api::TypeAlias(alias) => {
                 expanded.extend(expand_type_alias(alias));
                 hidden.extend(expand_type_alias_verify(alias, types));
@@ -89,6 +119,12 @@ fn expand(ffi: Module, doc: Doc, attrs: OtherAttrs, apis: &[Api], types: &Types)
         }
     }
 
+    for inherent_impl in inherent_impls {
+        expanded.extend(inherent_impl.into_token_stream());
+    }
+
+    expanded.extend(expand_cxx_mocks(apis, types));
+
     for (impl_key, &explicit_impl) in &types.impls {
         match impl_key {
             ImplKey::RustBox(ident) => {
@@ -98,17 +134,33 @@ fn expand(ffi: Module, doc: Doc, attrs: OtherAttrs, apis: &[Api], types: &Types)
                 hidden.extend(expand_rust_vec(ident, types, explicit_impl));
             }
             ImplKey::UniquePtr(ident) => {
-                expanded.extend(expand_unique_ptr(ident, types, explicit_impl));
+                let fake = ffi.fake.is_some();
+                expanded.extend(expand_unique_ptr(ident, types, explicit_impl, fake));
+            }
+            ImplKey::UniqueArray(ident) => {
+                expanded.extend(expand_unique_array(ident, types, explicit_impl));
             }
             ImplKey::SharedPtr(ident) => {
                 expanded.extend(expand_shared_ptr(ident, types, explicit_impl));
             }
+            ImplKey::SharedArray(ident) => {
+                expanded.extend(expand_shared_array(ident, types, explicit_impl));
+            }
+            ImplKey::SharedConstPtr(ident) => {
+                expanded.extend(expand_shared_const_ptr(ident, types, explicit_impl));
+            }
             ImplKey::WeakPtr(ident) => {
                 expanded.extend(expand_weak_ptr(ident, types, explicit_impl));
             }
             ImplKey::CxxVector(ident) => {
                 expanded.extend(expand_cxx_vector(ident, explicit_impl, types));
             }
+            ImplKey::CxxPmrVector(ident) => {
+                expanded.extend(expand_cxx_pmr_vector(ident, explicit_impl, types));
+            }
+            ImplKey::CustomPtr(family, ident) => {
+                expanded.extend(expand_custom_ptr(family, ident, types, explicit_impl));
+            }
         }
     }
 
@@ -116,6 +168,21 @@ fn expand(ffi: Module, doc: Doc, attrs: OtherAttrs, apis: &[Api], types: &Types)
         hidden.extend(expand_forbid(forbid));
     }
 
+    // Makes rustc rebuild the crate when stub mode is turned on or off, which
+    // a procedural macro reading the environment would not do on its own.
+    hidden.extend(quote! {
+        const _: ::cxx::core::option::Option<&str> = ::cxx::core::option_env!("CXXBRIDGE_STUB");
+    });
+
+    // Makes the crate get rebuilt when a file that the bridge includes items
+    // from changes, the same as for the file containing the bridge.
+    for file in included_files {
+        let file = file.to_string_lossy();
+        hidden.extend(quote! {
+            const _: &str = ::cxx::core::include_str!(#file);
+        });
+    }
+
     // Work around https://github.com/rust-lang/rust/issues/67851.
     if !hidden.is_empty() {
         expanded.extend(quote! {
@@ -152,7 +219,19 @@ fn expand(ffi: Module, doc: Doc, attrs: OtherAttrs, apis: &[Api], types: &Types)
     }
 }
 
-fn expand_struct(strct: &Struct) -> TokenStream {
+// In stub mode, which is meant for IDEs, the extern "Rust" functions are
+// expanded without the glue converting their arguments and return values for
+// calls from C++, which only matters to an actual build and takes up most of
+// the time that rust-analyzer spends analyzing a bridge. Each of them keeps a
+// plain Rust wrapper that calls it, so that its signature is still checked and
+// go-to-definition leads from the bridge to the implementation.
+//
+// Stub mode is on while CXXBRIDGE_STUB is set to anything other than 0.
+fn stub_mode() -> bool {
+    env::var_os("CXXBRIDGE_STUB").is_some_and(|value| value != "0")
+}
+
+fn expand_struct(strct: &Struct, arbitrary: bool) -> TokenStream {
     let ident = &strct.name.rust;
     let doc = &strct.doc;
     let attrs = &strct.attrs;
@@ -167,7 +246,7 @@ fn expand_struct(strct: &Struct) -> TokenStream {
         quote!(#doc #attrs #vis #field)
     });
     let mut derives = None;
-    let derived_traits = derive::expand_struct(strct, &mut derives);
+    let derived_traits = derive::expand_struct(strct, arbitrary, &mut derives);
 
     let span = ident.span();
     let visibility = strct.visibility;
@@ -318,7 +397,7 @@ fn expand_struct_forbid_drop(strct: &Struct) -> TokenStream {
     }
 }
 
-fn expand_enum(enm: &Enum) -> TokenStream {
+fn expand_enum(enm: &Enum, arbitrary: bool) -> TokenStream {
     let ident = &enm.name.rust;
     let doc = &enm.doc;
     let attrs = &enm.attrs;
@@ -338,7 +417,7 @@ fn expand_enum(enm: &Enum) -> TokenStream {
         })
     });
     let mut derives = None;
-    let derived_traits = derive::expand_enum(enm, &mut derives);
+    let derived_traits = derive::expand_enum(enm, arbitrary, &mut derives);
 
     let span = ident.span();
     let visibility = enm.visibility;
@@ -402,6 +481,39 @@ fn expand_cxx_type(ety: &ExternType) -> TokenStream {
         }
     };
 
+    let mut derives = TokenStream::new();
+    for derive in &ety.derives {
+        if derive.what == Trait::Hash {
+            let link_name = mangle::operator(&ety.name, "hash");
+            let span = derive.span;
+            derives.extend(quote_spanned! {span=>
+                #[automatically_derived]
+                impl #generics ::cxx::core::hash::Hash for #ident #generics {
+                    fn hash<__H: ::cxx::core::hash::Hasher>(&self, state: &mut __H) {
+                        #UnsafeExtern extern "C" {
+                            #[link_name = #link_name]
+                            fn __hash(this: *const ::cxx::core::ffi::c_void) -> usize;
+                        }
+                        let this = self as *const Self as *const ::cxx::core::ffi::c_void;
+                        state.write_usize(unsafe { __hash(this) });
+                    }
+                }
+            });
+        }
+    }
+
+    let mut auto_traits = TokenStream::new();
+    if ety.send {
+        auto_traits.extend(quote_spanned! {span=>
+            unsafe impl #generics ::cxx::core::marker::Send for #ident #generics {}
+        });
+    }
+    if ety.sync {
+        auto_traits.extend(quote_spanned! {span=>
+            unsafe impl #generics ::cxx::core::marker::Sync for #ident #generics {}
+        });
+    }
+
     quote! {
         #doc
         #attrs
@@ -415,6 +527,74 @@ fn expand_cxx_type(ety: &ExternType) -> TokenStream {
             type Id = #type_id;
             type Kind = ::cxx::kind::Opaque;
         }
+
+        #auto_traits
+        #derives
+    }
+}
+
+fn expand_smart_ptr_family(ety: &ExternType) -> TokenStream {
+    let ident = &ety.name.rust;
+    let doc = &ety.doc;
+    let attrs = &ety.attrs;
+    let visibility = &ety.visibility;
+    let enum_token = Token![enum](ety.type_token.span);
+
+    // Uninhabited: the family only ever appears as the first type argument of
+    // CustomPtr<P, T>, never as a value.
+    quote_spanned! {ident.span()=>
+        #doc
+        #attrs
+        #visibility #enum_token #ident {}
+    }
+}
+
+fn expand_com_interface(ety: &ExternType, iid: u128) -> TokenStream {
+    let ident = &ety.name.rust;
+    let name = ident.to_string();
+    let span = ident.span();
+    let iid = LitInt::new(&format!("0x{:032X}", iid), span);
+    let link_add_ref = mangle::operator(&ety.name, "add_ref");
+    let link_release = mangle::operator(&ety.name, "release");
+    let link_query_interface = mangle::operator(&ety.name, "query_interface");
+
+    quote_spanned! {span=>
+        #[automatically_derived]
+        unsafe impl ::cxx::com::ComInterface for #ident {
+            const IID: ::cxx::com::Guid = ::cxx::com::Guid::from_u128(#iid);
+            fn __typename(f: &mut ::cxx::core::fmt::Formatter<'_>) -> ::cxx::core::fmt::Result {
+                f.write_str(#name)
+            }
+            unsafe fn __add_ref(this: *mut ::cxx::core::ffi::c_void) -> u32 {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_add_ref]
+                    fn __add_ref(this: *mut ::cxx::core::ffi::c_void) -> u32;
+                }
+                unsafe { __add_ref(this) }
+            }
+            unsafe fn __release(this: *mut ::cxx::core::ffi::c_void) -> u32 {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_release]
+                    fn __release(this: *mut ::cxx::core::ffi::c_void) -> u32;
+                }
+                unsafe { __release(this) }
+            }
+            unsafe fn __query_interface(
+                this: *mut ::cxx::core::ffi::c_void,
+                iid: *const ::cxx::com::Guid,
+                out: *mut *mut ::cxx::core::ffi::c_void,
+            ) -> ::cxx::HRESULT {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_query_interface]
+                    fn __query_interface(
+                        this: *mut ::cxx::core::ffi::c_void,
+                        iid: *const ::cxx::com::Guid,
+                        out: *mut *mut ::cxx::core::ffi::c_void,
+                    ) -> ::cxx::HRESULT;
+                }
+                unsafe { __query_interface(this, iid, out) }
+            }
+        }
     }
 }
 
@@ -425,34 +605,10 @@ fn expand_cxx_type_assert_pinned(ety: &ExternType, types: &Types) -> TokenStream
     let resolve = types.resolve(ident);
     let lifetimes = resolve.generics.to_underscore_lifetimes();
 
+    // Fails to infer `_` if the user has added a manual Unpin impl for their
+    // opaque C++ type, as then `AmbiguousIfUnpin` is implemented twice for it.
     quote! {
-        let _: fn() = {
-            // Derived from https://github.com/nvzqz/static-assertions-rs.
-            trait __AmbiguousIfImpl<A> {
-                fn infer() {}
-            }
-
-            #[automatically_derived]
-            impl<T> __AmbiguousIfImpl<()> for T
-            where
-                T: ?::cxx::core::marker::Sized
-            {}
-
-            #[allow(dead_code)]
-            struct __Invalid;
-
-            #[automatically_derived]
-            impl<T> __AmbiguousIfImpl<__Invalid> for T
-            where
-                T: ?::cxx::core::marker::Sized + ::cxx::core::marker::Unpin,
-            {}
-
-            // If there is only one specialized trait impl, type inference with
-            // `_` can be resolved and this can compile. Fails to compile if
-            // user has added a manual Unpin impl for their opaque C++ type as
-            // then `__AmbiguousIfImpl<__Invalid>` also exists.
-            <#ident #lifetimes as __AmbiguousIfImpl<#infer>>::infer
-        };
+        let _: fn() = <#ident #lifetimes as ::cxx::private::AmbiguousIfUnpin<#infer>>::infer;
     }
 }
 
@@ -481,6 +637,8 @@ fn expand_cxx_function_decl(efn: &ExternFn, types: &Types) -> TokenStream {
     let all_args = receiver.chain(args);
     let ret = if efn.throws {
         quote!(-> ::cxx::private::Result)
+    } else if efn.hresult.is_some() {
+        quote!(-> ::cxx::HRESULT)
     } else {
         expand_extern_return_type(&efn.ret, types, true)
     };
@@ -497,7 +655,31 @@ fn expand_cxx_function_decl(efn: &ExternFn, types: &Types) -> TokenStream {
     }
 }
 
-fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
+// The methods of an extern C++ type all go into one impl block, rather than one
+// impl block per method. Rustc checks each of the inherent impls of a type
+// against the others, which for a type with hundreds of methods adds up.
+struct InherentImpl {
+    key: String,
+    header: TokenStream,
+    span: Span,
+    methods: TokenStream,
+}
+
+impl InherentImpl {
+    fn into_token_stream(self) -> TokenStream {
+        let header = self.header;
+        let methods = self.methods;
+        quote_spanned!(self.span=> #header { #methods })
+    }
+}
+
+fn expand_cxx_function_shim(
+    efn: &ExternFn,
+    types: &Types,
+    fake: Option<&Path>,
+    trace: bool,
+    inherent_impls: &mut Vec<InherentImpl>,
+) -> TokenStream {
     let doc = &efn.doc;
     let attrs = &efn.attrs;
     let decl = expand_cxx_function_decl(efn, types);
@@ -516,15 +698,7 @@ fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
     });
     let args = efn.args.iter().map(|arg| quote!(#arg));
     let all_args = receiver.chain(args);
-    let ret = if efn.throws {
-        let ok = match &efn.ret {
-            Some(ret) => quote!(#ret),
-            None => quote!(()),
-        };
-        quote!(-> ::cxx::core::result::Result<#ok, ::cxx::Exception>)
-    } else {
-        expand_return_type(&efn.ret)
-    };
+    let ret = expand_cxx_function_ret(efn);
     let indirect_return = indirect_return(efn, types);
     let receiver_var = efn
         .receiver()
@@ -551,6 +725,20 @@ fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
                     quote_spanned!(span=> ::cxx::UniquePtr::into_raw(#var))
                 }
             }
+            Type::UniqueArray(ty) => {
+                if types.is_considered_improper_ctype(&ty.inner) {
+                    quote_spanned!(span=> ::cxx::UniqueArray::into_raw(#var).cast())
+                } else {
+                    quote_spanned!(span=> ::cxx::UniqueArray::into_raw(#var))
+                }
+            }
+            Type::ComPtr(ty) => {
+                if types.is_considered_improper_ctype(&ty.inner) {
+                    quote_spanned!(span=> ::cxx::ComPtr::into_raw(#var).cast())
+                } else {
+                    quote_spanned!(span=> ::cxx::ComPtr::into_raw(#var))
+                }
+            }
             Type::RustVec(_) => quote_spanned!(span=> #var.as_mut_ptr() as *const ::cxx::private::RustVec<_>),
             Type::Ref(ty) => match &ty.inner {
                 Type::Ident(ident) if ident.rust == RustString => match ty.mutable {
@@ -615,6 +803,12 @@ fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
         .map(|arg| {
             let var = &arg.name.rust;
             let span = var.span();
+            if let Type::HashMapRef(_) = &arg.ty {
+                // C++ receives a view of the borrowed map, not ownership.
+                return quote_spanned! {span=>
+                    let mut #var = ::cxx::core::mem::MaybeUninit::new(::cxx::private::RustHashMap::from(#var));
+                };
+            }
             // These are arguments for which C++ has taken ownership of the data
             // behind the mut reference it received.
             quote_spanned! {span=>
@@ -633,6 +827,10 @@ fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
             quote_spanned! {span=>
                 #local_name(#(#vars,)* __return.as_mut_ptr()).exception()?;
             }
+        } else if efn.hresult.is_some() {
+            quote_spanned! {span=>
+                #local_name(#(#vars,)* __return.as_mut_ptr()).ok()?;
+            }
         } else {
             quote_spanned! {span=>
                 #local_name(#(#vars,)* __return.as_mut_ptr());
@@ -643,6 +841,10 @@ fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
         quote_spanned! {span=>
             #local_name(#(#vars),*).exception()
         }
+    } else if efn.hresult.is_some() {
+        quote_spanned! {span=>
+            #local_name(#(#vars),*).ok()
+        }
     } else {
         quote_spanned! {span=>
             #local_name(#(#vars),*)
@@ -675,6 +877,32 @@ fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
                     quote_spanned!(span=> ::cxx::UniquePtr::from_raw(#call))
                 }
             }
+            Type::ComPtr(ty) => {
+                if types.is_considered_improper_ctype(&ty.inner) {
+                    quote_spanned!(span=> ::cxx::ComPtr::from_raw(#call.cast()))
+                } else {
+                    quote_spanned!(span=> ::cxx::ComPtr::from_raw(#call))
+                }
+            }
+            Type::UniqueArray(ty) => {
+                let array = if types.is_considered_improper_ctype(&ty.inner) {
+                    quote_spanned!(span=> ::cxx::UniqueArray::from_raw(#call.cast()))
+                } else {
+                    quote_spanned!(span=> ::cxx::UniqueArray::from_raw(#call))
+                };
+                match &efn.array_len {
+                    Some(len) => {
+                        quote_spanned!(span=> ::cxx::memory::UniqueSlice::from_raw_parts(#array, #len))
+                    }
+                    None => array,
+                }
+            }
+            Type::SharedArray(_) => match &efn.array_len {
+                Some(len) => {
+                    quote_spanned!(span=> ::cxx::memory::SharedSlice::from_raw_parts(#call, #len))
+                }
+                None => call,
+            },
             Type::Ref(ty) => match &ty.inner {
                 Type::Ident(ident) if ident.rust == RustString => match ty.mutable {
                     false => quote_spanned!(span=> #call.as_string()),
@@ -717,10 +945,10 @@ fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
             }
             _ => call,
         };
-        if efn.throws {
+        if efn.throws || efn.hresult.is_some() {
             expr = quote_spanned!(span=> ::cxx::core::result::Result::Ok(#expr));
         }
-    } else if efn.throws {
+    } else if efn.throws || efn.hresult.is_some() {
         expr = call;
     } else {
         expr = quote! { #call; };
@@ -737,19 +965,51 @@ fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
         Lang::CxxUnwind => quote_spanned!(span=> "C-unwind"),
         Lang::Rust => unreachable!(),
     };
+    let trace = if trace {
+        let label = match efn.self_type() {
+            None => format!("::{}", efn.name.rust),
+            Some(self_type) => format!("::{}::{}", self_type, efn.name.rust),
+        };
+        Some(quote_spanned! {span=>
+            let __span = ::cxx::private::TraceSpan::enter(
+                ::cxx::private::concat!(::cxx::private::module_path!(), #label),
+                ::cxx::private::TraceDirection::RustToCxx,
+            );
+        })
+    } else {
+        None
+    };
     let fn_body = quote_spanned!(span=> {
         #UnsafeExtern extern #calling_conv {
             #decl
         }
         #trampolines
+        #trace
         #dispatch
     });
+    // A bridge with a fake gets a second copy of each function for cfg(test),
+    // which doesn't call into C++.
+    let fn_bodies = match fake {
+        None => vec![(None, fn_body)],
+        Some(fake) => vec![
+            (Some(quote_spanned!(span=> #[cfg(not(test))])), fn_body),
+            (
+                Some(quote_spanned!(span=> #[cfg(test)])),
+                expand_cxx_function_fake(efn, fake),
+            ),
+        ],
+    };
+    let fn_cfgs = fn_bodies.iter().map(|(cfg, _)| cfg);
+    let fn_bodies = fn_bodies.iter().map(|(_, body)| body);
     match efn.self_type() {
         None => {
             quote! {
-                #doc
-                #attrs
-                #visibility #unsafety #fn_token #ident #generics #arg_list #ret #fn_body
+                #(
+                    #doc
+                    #attrs
+                    #fn_cfgs
+                    #visibility #unsafety #fn_token #ident #generics #arg_list #ret #fn_bodies
+                )*
             }
         }
         Some(self_type) => {
@@ -777,13 +1037,306 @@ fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
                     &elided_generics
                 }
             };
-            quote_spanned! {ident.span()=>
-                impl #generics #self_type #self_type_generics {
+            let header =
+                quote_spanned!(ident.span()=> impl #generics #self_type #self_type_generics);
+            let method = quote! {
+                #(
                     #doc
                     #attrs
-                    #visibility #unsafety #fn_token #ident #arg_list #ret #fn_body
+                    #fn_cfgs
+                    #visibility #unsafety #fn_token #ident #arg_list #ret #fn_bodies
+                )*
+            };
+            let key = header.to_string();
+            match inherent_impls.iter_mut().find(|imp| imp.key == key) {
+                Some(imp) => imp.methods.extend(method),
+                None => inherent_impls.push(InherentImpl {
+                    key,
+                    header,
+                    span: ident.span(),
+                    methods: method,
+                }),
+            }
+            TokenStream::new()
+        }
+    }
+}
+
+// In test builds of a bridge with a `fake` argument, nothing calls into C++. An
+// extern "C++" function of a #[mock = Name] block calls the same method of the
+// mock trait on the unit struct given as the `fake` argument, and other extern
+// "C++" functions panic.
+fn expand_cxx_function_fake(efn: &ExternFn, fake: &Path) -> TokenStream {
+    let span = efn.semi_token.span;
+    let ident = &efn.name.rust;
+    let this_var = efn
+        .receiver()
+        .map(|receiver| receiver.var.to_token_stream());
+    let arg_vars = efn.args.iter().map(|arg| arg.name.rust.to_token_stream());
+    let vars = this_var.into_iter().chain(arg_vars);
+
+    if let Some(mock) = &efn.mock {
+        let call = quote_spanned!(span=> #mock::#ident(&#fake, #(#vars),*));
+        return match &efn.unsafety {
+            None => quote_spanned!(span=> { #call }),
+            Some(_) => quote_spanned!(span=> { unsafe { #call } }),
+        };
+    }
+
+    let name = match efn.self_type() {
+        Some(self_type) => format!("{}::{}", self_type, ident),
+        None => ident.to_string(),
+    };
+    let msg = format!(
+        "`{}` is an extern \"C++\" function outside of the #[mock] blocks, which cannot be called in a test build of a bridge with a `fake`",
+        name,
+    );
+    quote_spanned!(span=> {
+        let _ = (#(#vars,)*);
+        ::cxx::core::panic!(#msg)
+    })
+}
+
+// The extern "C++" functions of the blocks with #[mock = Name] are gathered
+// into a trait `Name`, with one method for each function that takes the same
+// arguments following `&self`, and methods taking their receiver as a `this`
+// argument. Rust code written against the trait can be unit tested with a
+// mockall mock of the trait in place of linking the C++ implementation, which
+// is available as the unit struct `CxxName` implementing the trait.
+fn expand_cxx_mocks(apis: &[Api], types: &Types) -> TokenStream {
+    let mut mocks: Vec<(&Ident, Vec<&ExternFn>)> = Vec::new();
+    for api in apis {
+        if let Api::CxxFunction(efn) = api {
+            if let Some(mock) = &efn.mock {
+                match mocks.iter_mut().find(|(name, _)| *name == mock) {
+                    Some((_, efns)) => efns.push(efn),
+                    None => mocks.push((mock, vec![efn])),
+                }
+            }
+        }
+    }
+
+    let mut expanded = TokenStream::new();
+    for (mock, efns) in mocks {
+        let real = format_ident!("Cxx{}", mock, span = mock.span());
+        let mut methods = TokenStream::new();
+        let mut impls = TokenStream::new();
+        for efn in efns {
+            let doc = &efn.doc;
+            let attrs = &efn.attrs;
+            let unsafety = &efn.unsafety;
+            let ident = &efn.name.rust;
+            let (generics, receiver, params) = expand_mock_params(efn, types);
+            let ret = expand_cxx_function_ret(efn);
+            let sig = quote! {
+                #unsafety fn #ident #generics(#receiver, #(#params),*) #ret
+            };
+            let this_var = efn.receiver().map(|_| quote!(this));
+            let arg_vars = efn.args.iter().map(|arg| arg.name.rust.to_token_stream());
+            let vars = this_var.into_iter().chain(arg_vars);
+            let mut call = match efn.self_type() {
+                Some(self_type) => quote!(#self_type::#ident(#(#vars),*)),
+                None => quote!(#ident(#(#vars),*)),
+            };
+            if unsafety.is_some() {
+                call = quote!(unsafe { #call });
+            }
+            methods.extend(quote! {
+                #doc
+                #attrs
+                #sig;
+            });
+            impls.extend(quote! {
+                #attrs
+                #sig {
+                    #call
                 }
+            });
+        }
+        let trait_doc = format!(
+            " The functions of the `#[mock = {}]` extern \"C++\" blocks, for mocking in tests.",
+            mock,
+        );
+        let real_doc = format!(
+            " Implementation of [`{}`] that calls the C++ functions.",
+            mock,
+        );
+        expanded.extend(quote! {
+            #[doc = #trait_doc]
+            pub trait #mock {
+                #methods
+            }
+
+            #[doc = #real_doc]
+            #[derive(Copy, Clone, Default)]
+            pub struct #real;
+
+            impl #mock for #real {
+                #impls
+            }
+        });
+    }
+    expanded
+}
+
+// Putting `&self` in front of the arguments would make it what elided lifetimes
+// in the return type borrow from. If there are any, `&self` instead gets the
+// same lifetime as the receiver or the single reference argument that they
+// borrow from in the extern function, naming that lifetime if it was elided.
+fn expand_mock_params(efn: &ExternFn, types: &Types) -> (Generics, TokenStream, Vec<TokenStream>) {
+    let mut generics = efn.generics.clone();
+    let fresh = Lifetime::new("'__self", efn.name.rust.span());
+    let borrows = efn
+        .ret
+        .as_ref()
+        .is_some_and(|ret| has_lifetime(ret, types, true));
+    let mut self_lifetime = None;
+    let mut params = Vec::new();
+
+    if let Some(receiver) = efn.receiver() {
+        if borrows && receiver.lifetime.is_none() {
+            let ampersand = receiver.ampersand;
+            let mutability = receiver.mutability;
+            let ty = &receiver.ty;
+            let reference = quote!(#ampersand #fresh #mutability #ty);
+            params.push(match receiver.pinned {
+                false => quote!(this: #reference),
+                true => quote!(this: ::cxx::core::pin::Pin<#reference>),
+            });
+            self_lifetime = Some(fresh.clone());
+        } else {
+            let ty = receiver.ty();
+            params.push(quote!(this: #ty));
+            self_lifetime = receiver.lifetime.clone();
+        }
+    }
+
+    let mut borrowed = efn
+        .args
+        .iter()
+        .filter(|arg| has_lifetime(&arg.ty, types, false));
+    let single = match (borrowed.next(), borrowed.next()) {
+        (Some(arg), None) if borrows && efn.receiver().is_none() => Some(arg),
+        _ => None,
+    };
+    for arg in &efn.args {
+        let name = &arg.name.rust;
+        let param = match &arg.ty {
+            _ if !single.is_some_and(|single| single.name.rust == *name) => quote!(#arg),
+            Type::Ref(ty) | Type::Str(ty) if ty.lifetime.is_some() => {
+                self_lifetime = ty.lifetime.clone();
+                quote!(#arg)
+            }
+            Type::SliceRef(ty) if ty.lifetime.is_some() => {
+                self_lifetime = ty.lifetime.clone();
+                quote!(#arg)
+            }
+            Type::Ref(ty) => {
+                let ampersand = ty.ampersand;
+                let mutability = ty.mutability;
+                let inner = &ty.inner;
+                let reference = quote!(#ampersand #fresh #mutability #inner);
+                self_lifetime = Some(fresh.clone());
+                match ty.pinned {
+                    false => quote!(#name: #reference),
+                    true => quote!(#name: ::cxx::core::pin::Pin<#reference>),
+                }
+            }
+            Type::Str(ty) => {
+                let ampersand = ty.ampersand;
+                let mutability = ty.mutability;
+                self_lifetime = Some(fresh.clone());
+                quote!(#name: #ampersand #fresh #mutability str)
             }
+            Type::SliceRef(ty) => {
+                let ampersand = ty.ampersand;
+                let mutability = ty.mutability;
+                let inner = &ty.inner;
+                self_lifetime = Some(fresh.clone());
+                quote!(#name: #ampersand #fresh #mutability [#inner])
+            }
+            _ => quote!(#arg),
+        };
+        params.push(param);
+    }
+
+    if self_lifetime.as_ref() == Some(&fresh) {
+        let param = syn::LifetimeParam::new(fresh);
+        generics
+            .params
+            .insert(0, syn::GenericParam::Lifetime(param));
+    }
+    let receiver = match self_lifetime {
+        Some(lifetime) if borrows => quote!(&#lifetime self),
+        _ => quote!(&self),
+    };
+    (generics, receiver, params)
+}
+
+// Whether the type contains a lifetime, or with `elided_only`, a lifetime that
+// is not written out.
+fn has_lifetime(ty: &Type, types: &Types, elided_only: bool) -> bool {
+    match ty {
+        Type::Ident(ident) => {
+            let has_lifetimes = types
+                .try_resolve(&ident.rust)
+                .is_some_and(|resolve| !resolve.generics.lifetimes.is_empty());
+            let explicit = ident.generics.lt_token.is_some();
+            has_lifetimes && !(elided_only && explicit)
+        }
+        Type::Ref(ty) => {
+            !(elided_only && ty.lifetime.is_some()) || has_lifetime(&ty.inner, types, elided_only)
+        }
+        Type::Str(ty) => !(elided_only && ty.lifetime.is_some()),
+        Type::SliceRef(ty) => {
+            !(elided_only && ty.lifetime.is_some()) || has_lifetime(&ty.inner, types, elided_only)
+        }
+        Type::RustBox(ty)
+        | Type::RustVec(ty)
+        | Type::UniquePtr(ty)
+        | Type::UniqueArray(ty)
+        | Type::SharedPtr(ty)
+        | Type::SharedArray(ty)
+        | Type::SharedConstPtr(ty)
+        | Type::WeakPtr(ty)
+        | Type::CxxVector(ty)
+        | Type::CxxPmrVector(ty)
+        | Type::ComPtr(ty) => has_lifetime(&ty.inner, types, elided_only),
+        _ => false,
+    }
+}
+
+fn expand_cxx_function_ret(efn: &ExternFn) -> TokenStream {
+    let ret = efn.ret.as_ref().map(|ret| match ret {
+        Type::UniqueArray(ty) if efn.array_len.is_some() => {
+            let span = ty.name.span();
+            let inner = &ty.inner;
+            quote_spanned!(span=> ::cxx::memory::UniqueSlice<#inner>)
+        }
+        Type::SharedArray(ty) if efn.array_len.is_some() => {
+            let span = ty.name.span();
+            let inner = &ty.inner;
+            quote_spanned!(span=> ::cxx::memory::SharedSlice<#inner>)
+        }
+        _ => quote!(#ret),
+    });
+    if efn.throws {
+        let ok = match ret {
+            Some(ret) => ret,
+            None => quote!(()),
+        };
+        quote!(-> ::cxx::core::result::Result<#ok, ::cxx::Exception>)
+    } else if let Some((_, error)) = &efn.hresult {
+        let ok = match ret {
+            Some(ret) => ret,
+            None => quote!(()),
+        };
+        let error = quote_spanned!(error.span()=> ::cxx::HRESULT);
+        quote!(-> ::cxx::core::result::Result<#ok, #error>)
+    } else {
+        match ret {
+            Some(ret) => quote!(-> #ret),
+            None => TokenStream::new(),
         }
     }
 }
@@ -809,6 +1362,7 @@ fn expand_function_pointer_trampoline(
         Some(&efn.generics),
         &efn.attrs,
         body_span,
+        false,
     );
     let calling_conv = match efn.lang {
         Lang::Cxx => "C",
@@ -872,6 +1426,32 @@ fn expand_rust_type_impl(ety: &ExternType) -> TokenStream {
     impls
 }
 
+fn expand_rust_type_operators(ety: &ExternType) -> TokenStream {
+    let ident = &ety.name.rust;
+    let generics = &ety.generics;
+    let mut operators = TokenStream::new();
+
+    for derive in &ety.derives {
+        if derive.what == Trait::Hash {
+            let span = derive.span;
+            let link_name = mangle::operator(&ety.name, "hash");
+            let local_name = format_ident!("__operator_hash_{}", ety.name.rust);
+            let prevent_unwind_label = format!("::{} as Hash>::hash", ety.name.rust);
+            operators.extend(quote_spanned! {span=>
+                #[doc(hidden)]
+                #[#UnsafeAttr(#ExportNameAttr = #link_name)]
+                #[allow(clippy::cast_possible_truncation)]
+                extern "C" fn #local_name #generics(this: &#ident #generics) -> usize {
+                    let __fn = concat!("<", module_path!(), #prevent_unwind_label);
+                    ::cxx::private::prevent_unwind(__fn, || ::cxx::private::hash(this))
+                }
+            });
+        }
+    }
+
+    operators
+}
+
 fn expand_rust_type_assert_unpin(ety: &ExternType, types: &Types) -> TokenStream {
     let ident = &ety.name.rust;
     let begin_span = Token![::](ety.type_token.span);
@@ -947,7 +1527,7 @@ fn expand_forbid(impls: TokenStream) -> TokenStream {
     }
 }
 
-fn expand_rust_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
+fn expand_rust_function_shim(efn: &ExternFn, types: &Types, trace: bool) -> TokenStream {
     let link_name = mangle::extern_fn(efn, types);
     let local_name = match efn.self_type() {
         None => format_ident!("__{}", efn.name.rust),
@@ -969,9 +1549,39 @@ fn expand_rust_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
         None,
         &efn.attrs,
         body_span,
+        trace,
     )
 }
 
+// The symbol that C++ calls is still defined in stub mode, but refers to a
+// symbol that nothing defines, so that a build linking C++ against the stub
+// expansion fails with an error naming the cause rather than with the missing
+// symbols of the bridge.
+fn expand_rust_function_stub(efn: &ExternFn, types: &Types) -> TokenStream {
+    let link_name = mangle::extern_fn(efn, types);
+    let local_name = match efn.self_type() {
+        None => format_ident!("__{}", efn.name.rust),
+        Some(self_type) => format_ident!("__{}__{}", self_type, efn.name.rust),
+    };
+    let wrapper = expand_rust_function_shim_super(efn, &local_name, &efn.name.rust);
+    quote! {
+        #[allow(dead_code)]
+        #wrapper
+
+        const _: () = {
+            #[doc(hidden)]
+            #[#UnsafeAttr(#ExportNameAttr = #link_name)]
+            unsafe extern "C" fn __stub() {
+                #UnsafeExtern extern "C" {
+                    #[link_name = "cxxbridge1$stub$CXXBRIDGE_STUB_is_set_in_a_build_that_links"]
+                    fn __stub_linked();
+                }
+                unsafe { __stub_linked() }
+            }
+        };
+    }
+}
+
 fn expand_rust_function_shim_impl(
     sig: &Signature,
     types: &Types,
@@ -982,6 +1592,7 @@ fn expand_rust_function_shim_impl(
     outer_generics: Option<&Generics>,
     attrs: &OtherAttrs,
     body_span: Span,
+    trace: bool,
 ) -> TokenStream {
     let generics = outer_generics.unwrap_or(&sig.generics);
     let receiver_var = sig
@@ -1029,6 +1640,14 @@ fn expand_rust_function_shim_impl(
                 requires_unsafe = true;
                 quote_spanned!(span=> ::cxx::UniquePtr::from_raw(#var))
             }
+            Type::UniqueArray(_) => {
+                requires_unsafe = true;
+                quote_spanned!(span=> ::cxx::UniqueArray::from_raw(#var))
+            }
+            Type::ComPtr(_) => {
+                requires_unsafe = true;
+                quote_spanned!(span=> ::cxx::ComPtr::from_raw(#var))
+            }
             Type::Ref(ty) => match &ty.inner {
                 Type::Ident(i) if i.rust == RustString => match ty.mutable {
                     false => quote_spanned!(span=> #var.as_string()),
@@ -1056,6 +1675,10 @@ fn expand_rust_function_shim_impl(
                     true => quote_spanned!(span=> #var.as_mut_slice::<#inner>()),
                 }
             }
+            Type::HashMapRef(_) => {
+                requires_unsafe = true;
+                quote_spanned!(span=> ::cxx::core::ptr::read(#var).as_hash_map())
+            }
             ty if types.needs_indirect_abi(ty) => {
                 requires_unsafe = true;
                 quote_spanned!(span=> ::cxx::core::ptr::read(#var))
@@ -1096,6 +1719,8 @@ fn expand_rust_function_shim_impl(
             }
         }
         Type::UniquePtr(_) => Some(quote_spanned!(span=> ::cxx::UniquePtr::into_raw)),
+        Type::UniqueArray(_) => Some(quote_spanned!(span=> ::cxx::UniqueArray::into_raw)),
+        Type::ComPtr(_) => Some(quote_spanned!(span=> ::cxx::ComPtr::into_raw)),
         Type::Ref(ty) => match &ty.inner {
             Type::Ident(ident) if ident.rust == RustString => match ty.mutable {
                 false => Some(quote_spanned!(span=> ::cxx::private::RustString::from_ref)),
@@ -1116,6 +1741,7 @@ fn expand_rust_function_shim_impl(
             false => Some(quote_spanned!(span=> ::cxx::private::RustSlice::from_ref)),
             true => Some(quote_spanned!(span=> ::cxx::private::RustSlice::from_mut)),
         },
+        Type::HashMapRef(_) => Some(quote_spanned!(span=> ::cxx::private::RustHashMap::from)),
         _ => None,
     });
 
@@ -1144,7 +1770,9 @@ fn expand_rust_function_shim_impl(
         };
         requires_closure = true;
         requires_unsafe = true;
-        expr = quote_spanned!(span=> ::cxx::private::r#try(#out, #expr));
+        expr = quote_spanned! {span=>
+            ::cxx::private::r#try(#out, #expr)
+        };
     } else if indirect_return {
         requires_closure = true;
         requires_unsafe = true;
@@ -1155,10 +1783,25 @@ fn expand_rust_function_shim_impl(
         expr = quote_spanned!(span=> unsafe { #expr });
     }
 
-    let closure = if requires_closure {
-        quote_spanned!(span=> move || #expr)
-    } else {
-        quote!(#local_name)
+    // The span is entered inside of prevent_unwind, so that a panic in a trace
+    // hook is reported the same way as one in the function.
+    let trace = if trace {
+        Some(quote_spanned! {span=>
+            let __span = ::cxx::private::TraceSpan::enter(
+                __fn,
+                ::cxx::private::TraceDirection::CxxToRust,
+            );
+        })
+    } else {
+        None
+    };
+
+    let closure = if trace.is_some() {
+        quote_spanned!(span=> move || { #trace #expr })
+    } else if requires_closure {
+        quote_spanned!(span=> move || #expr)
+    } else {
+        quote!(#local_name)
     };
 
     expr = quote_spanned!(span=> ::cxx::private::prevent_unwind(__fn, #closure));
@@ -1213,12 +1856,12 @@ fn expand_rust_function_shim_super(
         };
         // Set spans that result in the `Result<...>` written by the user being
         // highlighted as the cause if their error type has no Display impl.
-        let result_begin = quote_spanned!(result.span=> ::cxx::core::result::Result<#ok, impl);
+        let result_begin = quote_spanned!(result.span=> ::cxx::core::result::Result<#ok, ::cxx::private::TryError<impl);
         let result_end = if rustversion::cfg!(since(1.82)) {
             // https://blog.rust-lang.org/2024/10/17/Rust-1.82.0.html#precise-capturing-use-syntax
-            quote_spanned!(rangle.span=> ::cxx::core::fmt::Display + use<>>)
+            quote_spanned!(rangle.span=> ::cxx::core::fmt::Display + use<>>>)
         } else {
-            quote_spanned!(rangle.span=> ::cxx::core::fmt::Display>)
+            quote_spanned!(rangle.span=> ::cxx::core::fmt::Display>>)
         };
         quote!(-> #result_begin #result_end)
     } else {
@@ -1240,6 +1883,19 @@ fn expand_rust_function_shim_super(
         body = quote_spanned!(span=> unsafe { #body });
         allow_unused_unsafe = Some(quote_spanned!(span=> #[allow(unused_unsafe)]));
     }
+    if sig.throws {
+        // Extract the error's code and sources here, where its concrete type
+        // is known, rather than behind the `impl Display` returned from this
+        // wrapper.
+        body = quote_spanned! {span=>
+            ::cxx::core::result::Result::map_err(#body, |err| {
+                #[allow(unused_imports)]
+                use ::cxx::private::{AsStdErrorParts as _, DisplayParts as _, ExceptionParts as _, StdErrorParts as _};
+                let parts = (&&&&::cxx::private::ErrorKind(&err)).parts();
+                ::cxx::private::TryError::new(err, parts)
+            })
+        };
+    }
 
     quote_spanned! {span=>
         #allow_unused_unsafe
@@ -1308,11 +1964,13 @@ fn expand_rust_box(key: &NamedImplKey, types: &Types, explicit_impl: Option<&Imp
     let link_alloc = format!("{}alloc", link_prefix);
     let link_dealloc = format!("{}dealloc", link_prefix);
     let link_drop = format!("{}drop", link_prefix);
+    let link_default = format!("{}default", link_prefix);
 
     let local_prefix = format_ident!("{}__box_", ident);
     let local_alloc = format_ident!("{}alloc", local_prefix);
     let local_dealloc = format_ident!("{}dealloc", local_prefix);
     let local_drop = format_ident!("{}drop", local_prefix);
+    let local_default = format_ident!("{}default", local_prefix);
 
     let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
 
@@ -1321,6 +1979,22 @@ fn expand_rust_box(key: &NamedImplKey, types: &Types, explicit_impl: Option<&Imp
     let unsafe_token = format_ident!("unsafe", span = begin_span);
     let prevent_unwind_drop_label = format!("::{} as Drop>::drop", ident);
 
+    let default_method = if types.rust_default.contains(ident) {
+        let prevent_unwind_default_label = format!("::{} as Default>::default", ident);
+        Some(quote_spanned! {end_span=>
+            #[doc(hidden)]
+            #[#UnsafeAttr(#ExportNameAttr = #link_default)]
+            unsafe extern "C" fn #local_default #impl_generics(this: *mut ::cxx::alloc::boxed::Box<#ident #ty_generics>) {
+                let __fn = concat!("<", module_path!(), #prevent_unwind_default_label);
+                ::cxx::private::prevent_unwind(__fn, || unsafe {
+                    ::cxx::core::ptr::write(this, ::cxx::alloc::boxed::Box::<#ident #ty_generics>::default());
+                });
+            }
+        })
+    } else {
+        None
+    };
+
     quote_spanned! {end_span=>
         #[automatically_derived]
         #[doc(hidden)]
@@ -1347,6 +2021,7 @@ fn expand_rust_box(key: &NamedImplKey, types: &Types, explicit_impl: Option<&Imp
             let __fn = concat!("<", module_path!(), #prevent_unwind_drop_label);
             ::cxx::private::prevent_unwind(__fn, || unsafe { ::cxx::core::ptr::drop_in_place(this) });
         }
+        #default_method
     }
 }
 
@@ -1451,6 +2126,7 @@ fn expand_unique_ptr(
     key: &NamedImplKey,
     types: &Types,
     explicit_impl: Option<&Impl>,
+    fake: bool,
 ) -> TokenStream {
     let ident = key.rust;
     let name = ident.to_string();
@@ -1492,6 +2168,57 @@ fn expand_unique_ptr(
     let begin_span = explicit_impl.map_or(key.begin_span, |explicit| explicit.impl_token.span);
     let end_span = explicit_impl.map_or(key.end_span, |explicit| explicit.brace_token.span.join());
     let unsafe_token = format_ident!("unsafe", span = begin_span);
+
+    // In test builds of a bridge with a fake, a UniquePtr holds a pointer from
+    // Box::into_raw in place of a C++ allocation.
+    let fake_impl = if fake {
+        let new_method = if can_construct_from_value {
+            Some(quote_spanned! {end_span=>
+                fn __new(value: Self) -> ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void> {
+                    let ptr = ::cxx::alloc::boxed::Box::into_raw(::cxx::alloc::boxed::Box::new(value));
+                    ::cxx::core::mem::MaybeUninit::new(ptr.cast())
+                }
+            })
+        } else {
+            None
+        };
+        Some(quote_spanned! {end_span=>
+            #[cfg(test)]
+            #[automatically_derived]
+            #unsafe_token impl #impl_generics ::cxx::private::UniquePtrTarget for #ident #ty_generics {
+                fn __typename(f: &mut ::cxx::core::fmt::Formatter<'_>) -> ::cxx::core::fmt::Result {
+                    f.write_str(#name)
+                }
+                fn __null() -> ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void> {
+                    ::cxx::core::mem::MaybeUninit::new(::cxx::core::ptr::null_mut())
+                }
+                #new_method
+                unsafe fn __raw(raw: *mut Self) -> ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void> {
+                    ::cxx::core::mem::MaybeUninit::new(raw.cast())
+                }
+                unsafe fn __get(repr: ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) -> *const Self {
+                    unsafe { repr.assume_init() }.cast()
+                }
+                unsafe fn __release(repr: ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) -> *mut Self {
+                    unsafe { repr.assume_init() }.cast()
+                }
+                unsafe fn __drop(repr: ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) {
+                    let ptr = unsafe { repr.assume_init() }.cast::<Self>();
+                    if !ptr.is_null() {
+                        ::cxx::core::mem::drop(unsafe { ::cxx::alloc::boxed::Box::from_raw(ptr) });
+                    }
+                }
+            }
+        })
+    } else {
+        None
+    };
+    let real_cfg = if fake {
+        Some(quote_spanned!(end_span=> #[cfg(not(test))]))
+    } else {
+        None
+    };
+
     let raw_const = if rustversion::cfg!(since(1.82)) {
         quote_spanned!(end_span=> &raw const)
     } else {
@@ -1504,6 +2231,8 @@ fn expand_unique_ptr(
     };
 
     quote_spanned! {end_span=>
+        #fake_impl
+        #real_cfg
         #[automatically_derived]
         #unsafe_token impl #impl_generics ::cxx::private::UniquePtrTarget for #ident #ty_generics {
             fn __typename(f: &mut ::cxx::core::fmt::Formatter<'_>) -> ::cxx::core::fmt::Result {
@@ -1559,6 +2288,92 @@ fn expand_unique_ptr(
     }
 }
 
+fn expand_unique_array(
+    key: &NamedImplKey,
+    types: &Types,
+    explicit_impl: Option<&Impl>,
+) -> TokenStream {
+    let ident = key.rust;
+    let name = ident.to_string();
+    let resolve = types.resolve(ident);
+    let prefix = format!("cxxbridge1$unique_ptr$array${}$", resolve.name.to_symbol());
+    let link_null = format!("{}null", prefix);
+    let link_raw = format!("{}raw", prefix);
+    let link_get = format!("{}get", prefix);
+    let link_release = format!("{}release", prefix);
+    let link_drop = format!("{}drop", prefix);
+
+    let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
+
+    let begin_span = explicit_impl.map_or(key.begin_span, |explicit| explicit.impl_token.span);
+    let end_span = explicit_impl.map_or(key.end_span, |explicit| explicit.brace_token.span.join());
+    let unsafe_token = format_ident!("unsafe", span = begin_span);
+    let raw_const = if rustversion::cfg!(since(1.82)) {
+        quote_spanned!(end_span=> &raw const)
+    } else {
+        quote_spanned!(end_span=> &)
+    };
+    let raw_mut = if rustversion::cfg!(since(1.82)) {
+        quote_spanned!(end_span=> &raw mut)
+    } else {
+        quote_spanned!(end_span=> &mut)
+    };
+
+    quote_spanned! {end_span=>
+        #[automatically_derived]
+        #unsafe_token impl #impl_generics ::cxx::private::UniqueArrayElement for #ident #ty_generics {
+            fn __typename(f: &mut ::cxx::core::fmt::Formatter<'_>) -> ::cxx::core::fmt::Result {
+                f.write_str(#name)
+            }
+            fn __unique_array_null() -> ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void> {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_null]
+                    fn __null(this: *mut ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>);
+                }
+                let mut repr = ::cxx::core::mem::MaybeUninit::uninit();
+                unsafe {
+                    __null(#raw_mut repr);
+                }
+                repr
+            }
+            unsafe fn __unique_array_raw(raw: *mut Self) -> ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void> {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_raw]
+                    fn __raw(this: *mut ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>, raw: *mut ::cxx::core::ffi::c_void);
+                }
+                let mut repr = ::cxx::core::mem::MaybeUninit::uninit();
+                unsafe {
+                    __raw(#raw_mut repr, raw.cast());
+                }
+                repr
+            }
+            unsafe fn __unique_array_get(repr: ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) -> *const Self {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_get]
+                    fn __get(this: *const ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) -> *const ::cxx::core::ffi::c_void;
+                }
+                unsafe { __get(#raw_const repr).cast() }
+            }
+            unsafe fn __unique_array_release(mut repr: ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) -> *mut Self {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_release]
+                    fn __release(this: *mut ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) -> *mut ::cxx::core::ffi::c_void;
+                }
+                unsafe { __release(#raw_mut repr).cast() }
+            }
+            unsafe fn __unique_array_drop(mut repr: ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_drop]
+                    fn __drop(this: *mut ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>);
+                }
+                unsafe {
+                    __drop(#raw_mut repr);
+                }
+            }
+        }
+    }
+}
+
 fn expand_shared_ptr(
     key: &NamedImplKey,
     types: &Types,
@@ -1573,6 +2388,7 @@ fn expand_shared_ptr(
     let link_raw = format!("{}raw", prefix);
     let link_clone = format!("{}clone", prefix);
     let link_get = format!("{}get", prefix);
+    let link_use_count = format!("{}use_count", prefix);
     let link_drop = format!("{}drop", prefix);
 
     let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
@@ -1639,6 +2455,13 @@ fn expand_shared_ptr(
                 }
                 unsafe { __get(this).cast() }
             }
+            unsafe fn __use_count(this: *const ::cxx::core::ffi::c_void) -> usize {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_use_count]
+                    fn __use_count(this: *const ::cxx::core::ffi::c_void) -> usize;
+                }
+                unsafe { __use_count(this) }
+            }
             unsafe fn __drop(this: *mut ::cxx::core::ffi::c_void) {
                 #UnsafeExtern extern "C" {
                     #[link_name = #link_drop]
@@ -1652,6 +2475,254 @@ fn expand_shared_ptr(
     }
 }
 
+fn expand_shared_array(
+    key: &NamedImplKey,
+    types: &Types,
+    explicit_impl: Option<&Impl>,
+) -> TokenStream {
+    let ident = key.rust;
+    let name = ident.to_string();
+    let resolve = types.resolve(ident);
+    let prefix = format!("cxxbridge1$shared_ptr$array${}$", resolve.name.to_symbol());
+    let link_null = format!("{}null", prefix);
+    let link_raw = format!("{}raw", prefix);
+    let link_clone = format!("{}clone", prefix);
+    let link_alias = format!("{}alias", prefix);
+    let link_get = format!("{}get", prefix);
+    let link_use_count = format!("{}use_count", prefix);
+    let link_drop = format!("{}drop", prefix);
+
+    let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
+
+    let begin_span = explicit_impl.map_or(key.begin_span, |explicit| explicit.impl_token.span);
+    let end_span = explicit_impl.map_or(key.end_span, |explicit| explicit.brace_token.span.join());
+    let unsafe_token = format_ident!("unsafe", span = begin_span);
+
+    quote_spanned! {end_span=>
+        #[automatically_derived]
+        #unsafe_token impl #impl_generics ::cxx::private::SharedArrayElement for #ident #ty_generics {
+            fn __typename(f: &mut ::cxx::core::fmt::Formatter<'_>) -> ::cxx::core::fmt::Result {
+                f.write_str(#name)
+            }
+            unsafe fn __shared_array_null(new: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_null]
+                    fn __null(new: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __null(new);
+                }
+            }
+            unsafe fn __shared_array_raw(new: *mut ::cxx::core::ffi::c_void, raw: *mut Self) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_raw]
+                    fn __raw(new: *const ::cxx::core::ffi::c_void, raw: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __raw(new, raw as *mut ::cxx::core::ffi::c_void);
+                }
+            }
+            unsafe fn __shared_array_clone(this: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_clone]
+                    fn __clone(this: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __clone(this, new);
+                }
+            }
+            unsafe fn __shared_array_alias(this: *const ::cxx::core::ffi::c_void, offset: usize, new: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_alias]
+                    fn __alias(this: *const ::cxx::core::ffi::c_void, offset: usize, new: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __alias(this, offset, new);
+                }
+            }
+            unsafe fn __shared_array_get(this: *const ::cxx::core::ffi::c_void) -> *const Self {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_get]
+                    fn __get(this: *const ::cxx::core::ffi::c_void) -> *const ::cxx::core::ffi::c_void;
+                }
+                unsafe { __get(this).cast() }
+            }
+            unsafe fn __shared_array_use_count(this: *const ::cxx::core::ffi::c_void) -> usize {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_use_count]
+                    fn __use_count(this: *const ::cxx::core::ffi::c_void) -> usize;
+                }
+                unsafe { __use_count(this) }
+            }
+            unsafe fn __shared_array_drop(this: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_drop]
+                    fn __drop(this: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __drop(this);
+                }
+            }
+        }
+    }
+}
+
+fn expand_shared_const_ptr(
+    key: &NamedImplKey,
+    types: &Types,
+    explicit_impl: Option<&Impl>,
+) -> TokenStream {
+    let ident = key.rust;
+    let name = ident.to_string();
+    let resolve = types.resolve(ident);
+    let prefix = format!("cxxbridge1$shared_ptr$const${}$", resolve.name.to_symbol());
+    let link_null = format!("{}null", prefix);
+    let link_from_mut = format!("{}from_mut", prefix);
+    let link_clone = format!("{}clone", prefix);
+    let link_get = format!("{}get", prefix);
+    let link_use_count = format!("{}use_count", prefix);
+    let link_drop = format!("{}drop", prefix);
+
+    let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
+
+    let begin_span = explicit_impl.map_or(key.begin_span, |explicit| explicit.impl_token.span);
+    let end_span = explicit_impl.map_or(key.end_span, |explicit| explicit.brace_token.span.join());
+    let unsafe_token = format_ident!("unsafe", span = begin_span);
+
+    quote_spanned! {end_span=>
+        #[automatically_derived]
+        #unsafe_token impl #impl_generics ::cxx::private::SharedConstPtrTarget for #ident #ty_generics {
+            fn __typename(f: &mut ::cxx::core::fmt::Formatter<'_>) -> ::cxx::core::fmt::Result {
+                f.write_str(#name)
+            }
+            unsafe fn __shared_const_ptr_null(new: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_null]
+                    fn __null(new: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __null(new);
+                }
+            }
+            unsafe fn __shared_const_ptr_from_mut(mutable: *mut ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_from_mut]
+                    fn __from_mut(mutable: *mut ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __from_mut(mutable, new);
+                }
+            }
+            unsafe fn __shared_const_ptr_clone(this: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_clone]
+                    fn __clone(this: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __clone(this, new);
+                }
+            }
+            unsafe fn __shared_const_ptr_get(this: *const ::cxx::core::ffi::c_void) -> *const Self {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_get]
+                    fn __get(this: *const ::cxx::core::ffi::c_void) -> *const ::cxx::core::ffi::c_void;
+                }
+                unsafe { __get(this).cast() }
+            }
+            unsafe fn __shared_const_ptr_use_count(this: *const ::cxx::core::ffi::c_void) -> usize {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_use_count]
+                    fn __use_count(this: *const ::cxx::core::ffi::c_void) -> usize;
+                }
+                unsafe { __use_count(this) }
+            }
+            unsafe fn __shared_const_ptr_drop(this: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_drop]
+                    fn __drop(this: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __drop(this);
+                }
+            }
+        }
+    }
+}
+
+fn expand_custom_ptr(
+    family: &Ident,
+    key: &NamedImplKey,
+    types: &Types,
+    explicit_impl: Option<&Impl>,
+) -> TokenStream {
+    let ident = key.rust;
+    let name = ident.to_string();
+    let family_name = family.to_string();
+    let resolve = types.resolve(ident);
+    let family_resolve = types.resolve(family);
+    let prefix = format!(
+        "cxxbridge1$custom_ptr${}${}$",
+        family_resolve.name.to_symbol(),
+        resolve.name.to_symbol(),
+    );
+    let link_null = format!("{}null", prefix);
+    let link_clone = format!("{}clone", prefix);
+    let link_get = format!("{}get", prefix);
+    let link_drop = format!("{}drop", prefix);
+
+    let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
+
+    let begin_span = explicit_impl.map_or(key.begin_span, |explicit| explicit.impl_token.span);
+    let end_span = explicit_impl.map_or(key.end_span, |explicit| explicit.brace_token.span.join());
+    let unsafe_token = format_ident!("unsafe", span = begin_span);
+
+    quote_spanned! {end_span=>
+        #[automatically_derived]
+        #unsafe_token impl #impl_generics ::cxx::private::CustomPtrTarget<#family> for #ident #ty_generics {
+            fn __typename(f: &mut ::cxx::core::fmt::Formatter<'_>) -> ::cxx::core::fmt::Result {
+                f.write_str(#name)
+            }
+            fn __family(f: &mut ::cxx::core::fmt::Formatter<'_>) -> ::cxx::core::fmt::Result {
+                f.write_str(#family_name)
+            }
+            unsafe fn __custom_ptr_null(new: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_null]
+                    fn __null(new: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __null(new);
+                }
+            }
+            unsafe fn __custom_ptr_clone(this: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_clone]
+                    fn __clone(this: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __clone(this, new);
+                }
+            }
+            unsafe fn __custom_ptr_get(this: *const ::cxx::core::ffi::c_void) -> *const Self {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_get]
+                    fn __get(this: *const ::cxx::core::ffi::c_void) -> *const ::cxx::core::ffi::c_void;
+                }
+                unsafe { __get(this).cast() }
+            }
+            unsafe fn __custom_ptr_drop(this: *mut ::cxx::core::ffi::c_void) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_drop]
+                    fn __drop(this: *mut ::cxx::core::ffi::c_void);
+                }
+                unsafe {
+                    __drop(this);
+                }
+            }
+        }
+    }
+}
+
 fn expand_weak_ptr(key: &NamedImplKey, types: &Types, explicit_impl: Option<&Impl>) -> TokenStream {
     let ident = key.rust;
     let name = ident.to_string();
@@ -1661,6 +2732,7 @@ fn expand_weak_ptr(key: &NamedImplKey, types: &Types, explicit_impl: Option<&Imp
     let link_clone = format!("{}clone", prefix);
     let link_downgrade = format!("{}downgrade", prefix);
     let link_upgrade = format!("{}upgrade", prefix);
+    let link_use_count = format!("{}use_count", prefix);
     let link_drop = format!("{}drop", prefix);
 
     let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
@@ -1711,6 +2783,13 @@ fn expand_weak_ptr(key: &NamedImplKey, types: &Types, explicit_impl: Option<&Imp
                     __upgrade(weak, shared);
                 }
             }
+            unsafe fn __use_count(this: *const ::cxx::core::ffi::c_void) -> usize {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_use_count]
+                    fn __use_count(this: *const ::cxx::core::ffi::c_void) -> usize;
+                }
+                unsafe { __use_count(this) }
+            }
             unsafe fn __drop(this: *mut ::cxx::core::ffi::c_void) {
                 #UnsafeExtern extern "C" {
                     #[link_name = #link_drop]
@@ -1738,6 +2817,7 @@ fn expand_cxx_vector(
     let link_capacity = format!("{}capacity", prefix);
     let link_get_unchecked = format!("{}get_unchecked", prefix);
     let link_reserve = format!("{}reserve", prefix);
+    let link_truncate = format!("{}truncate", prefix);
     let link_push_back = format!("{}push_back", prefix);
     let link_pop_back = format!("{}pop_back", prefix);
     let unique_ptr_prefix = format!(
@@ -1858,6 +2938,16 @@ fn expand_cxx_vector(
                 }
                 unsafe { __reserve(v, new_cap) }
             }
+            unsafe fn __truncate(v: ::cxx::core::pin::Pin<&mut ::cxx::CxxVector<Self>>, len: usize) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_truncate]
+                    fn __truncate #impl_generics(
+                        v: ::cxx::core::pin::Pin<&mut ::cxx::CxxVector<#elem #ty_generics>>,
+                        len: usize,
+                    );
+                }
+                unsafe { __truncate(v, len) }
+            }
             #by_value_methods
             fn __unique_ptr_null() -> ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void> {
                 #UnsafeExtern extern "C" {
@@ -1908,6 +2998,209 @@ fn expand_cxx_vector(
     }
 }
 
+fn expand_cxx_pmr_vector(
+    key: &NamedImplKey,
+    explicit_impl: Option<&Impl>,
+    types: &Types,
+) -> TokenStream {
+    let elem = key.rust;
+    let name = elem.to_string();
+    let resolve = types.resolve(elem);
+    let prefix = format!("cxxbridge1$std$pmr$vector${}$", resolve.name.to_symbol());
+    let link_new_in = format!("{}new_in", prefix);
+    let link_size = format!("{}size", prefix);
+    let link_capacity = format!("{}capacity", prefix);
+    let link_get_unchecked = format!("{}get_unchecked", prefix);
+    let link_reserve = format!("{}reserve", prefix);
+    let link_truncate = format!("{}truncate", prefix);
+    let link_resource = format!("{}resource", prefix);
+    let link_push_back = format!("{}push_back", prefix);
+    let link_pop_back = format!("{}pop_back", prefix);
+    let unique_ptr_prefix = format!(
+        "cxxbridge1$unique_ptr$std$pmr$vector${}$",
+        resolve.name.to_symbol(),
+    );
+    let link_unique_ptr_null = format!("{}null", unique_ptr_prefix);
+    let link_unique_ptr_raw = format!("{}raw", unique_ptr_prefix);
+    let link_unique_ptr_get = format!("{}get", unique_ptr_prefix);
+    let link_unique_ptr_release = format!("{}release", unique_ptr_prefix);
+    let link_unique_ptr_drop = format!("{}drop", unique_ptr_prefix);
+
+    let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
+
+    let begin_span = explicit_impl.map_or(key.begin_span, |explicit| explicit.impl_token.span);
+    let end_span = explicit_impl.map_or(key.end_span, |explicit| explicit.brace_token.span.join());
+    let unsafe_token = format_ident!("unsafe", span = begin_span);
+
+    let can_pass_element_by_value = types.is_maybe_trivial(elem);
+    let by_value_methods = if can_pass_element_by_value {
+        Some(quote_spanned! {end_span=>
+            unsafe fn __push_back(
+                this: ::cxx::core::pin::Pin<&mut ::cxx::CxxPmrVector<Self>>,
+                value: &mut ::cxx::core::mem::ManuallyDrop<Self>,
+            ) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_push_back]
+                    fn __push_back #impl_generics(
+                        this: ::cxx::core::pin::Pin<&mut ::cxx::CxxPmrVector<#elem #ty_generics>>,
+                        value: *mut ::cxx::core::ffi::c_void,
+                    );
+                }
+                unsafe {
+                    __push_back(
+                        this,
+                        value as *mut ::cxx::core::mem::ManuallyDrop<Self> as *mut ::cxx::core::ffi::c_void,
+                    );
+                }
+            }
+            unsafe fn __pop_back(
+                this: ::cxx::core::pin::Pin<&mut ::cxx::CxxPmrVector<Self>>,
+                out: &mut ::cxx::core::mem::MaybeUninit<Self>,
+            ) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_pop_back]
+                    fn __pop_back #impl_generics(
+                        this: ::cxx::core::pin::Pin<&mut ::cxx::CxxPmrVector<#elem #ty_generics>>,
+                        out: *mut ::cxx::core::ffi::c_void,
+                    );
+                }
+                unsafe {
+                    __pop_back(
+                        this,
+                        out as *mut ::cxx::core::mem::MaybeUninit<Self> as *mut ::cxx::core::ffi::c_void,
+                    );
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let raw_const = if rustversion::cfg!(since(1.82)) {
+        quote_spanned!(end_span=> &raw const)
+    } else {
+        quote_spanned!(end_span=> &)
+    };
+    let raw_mut = if rustversion::cfg!(since(1.82)) {
+        quote_spanned!(end_span=> &raw mut)
+    } else {
+        quote_spanned!(end_span=> &mut)
+    };
+
+    quote_spanned! {end_span=>
+        #[automatically_derived]
+        #unsafe_token impl #impl_generics ::cxx::private::PmrVectorElement for #elem #ty_generics {
+            fn __typename(f: &mut ::cxx::core::fmt::Formatter<'_>) -> ::cxx::core::fmt::Result {
+                f.write_str(#name)
+            }
+            unsafe fn __vector_new_in(resource: *mut ::cxx::pmr::MemoryResource) -> *mut ::cxx::CxxPmrVector<Self> {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_new_in]
+                    fn __vector_new_in #impl_generics(resource: *mut ::cxx::pmr::MemoryResource) -> *mut ::cxx::CxxPmrVector<#elem #ty_generics>;
+                }
+                unsafe { __vector_new_in(resource) }
+            }
+            fn __vector_size(v: &::cxx::CxxPmrVector<Self>) -> usize {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_size]
+                    fn __vector_size #impl_generics(_: &::cxx::CxxPmrVector<#elem #ty_generics>) -> usize;
+                }
+                unsafe { __vector_size(v) }
+            }
+            fn __vector_capacity(v: &::cxx::CxxPmrVector<Self>) -> usize {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_capacity]
+                    fn __vector_capacity #impl_generics(_: &::cxx::CxxPmrVector<#elem #ty_generics>) -> usize;
+                }
+                unsafe { __vector_capacity(v) }
+            }
+            fn __vector_resource(v: &::cxx::CxxPmrVector<Self>) -> *mut ::cxx::pmr::MemoryResource {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_resource]
+                    fn __vector_resource #impl_generics(_: &::cxx::CxxPmrVector<#elem #ty_generics>) -> *mut ::cxx::pmr::MemoryResource;
+                }
+                unsafe { __vector_resource(v) }
+            }
+            unsafe fn __get_unchecked(v: *mut ::cxx::CxxPmrVector<Self>, pos: usize) -> *mut Self {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_get_unchecked]
+                    fn __get_unchecked #impl_generics(
+                        v: *mut ::cxx::CxxPmrVector<#elem #ty_generics>,
+                        pos: usize,
+                    ) -> *mut ::cxx::core::ffi::c_void;
+                }
+                unsafe { __get_unchecked(v, pos) as *mut Self }
+            }
+            unsafe fn __reserve(v: ::cxx::core::pin::Pin<&mut ::cxx::CxxPmrVector<Self>>, new_cap: usize) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_reserve]
+                    fn __reserve #impl_generics(
+                        v: ::cxx::core::pin::Pin<&mut ::cxx::CxxPmrVector<#elem #ty_generics>>,
+                        new_cap: usize,
+                    );
+                }
+                unsafe { __reserve(v, new_cap) }
+            }
+            unsafe fn __truncate(v: ::cxx::core::pin::Pin<&mut ::cxx::CxxPmrVector<Self>>, len: usize) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_truncate]
+                    fn __truncate #impl_generics(
+                        v: ::cxx::core::pin::Pin<&mut ::cxx::CxxPmrVector<#elem #ty_generics>>,
+                        len: usize,
+                    );
+                }
+                unsafe { __truncate(v, len) }
+            }
+            #by_value_methods
+            fn __unique_ptr_null() -> ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void> {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_unique_ptr_null]
+                    fn __unique_ptr_null(this: *mut ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>);
+                }
+                let mut repr = ::cxx::core::mem::MaybeUninit::uninit();
+                unsafe {
+                    __unique_ptr_null(#raw_mut repr);
+                }
+                repr
+            }
+            unsafe fn __unique_ptr_raw(raw: *mut ::cxx::CxxPmrVector<Self>) -> ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void> {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_unique_ptr_raw]
+                    fn __unique_ptr_raw #impl_generics(this: *mut ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>, raw: *mut ::cxx::CxxPmrVector<#elem #ty_generics>);
+                }
+                let mut repr = ::cxx::core::mem::MaybeUninit::uninit();
+                unsafe {
+                    __unique_ptr_raw(#raw_mut repr, raw);
+                }
+                repr
+            }
+            unsafe fn __unique_ptr_get(repr: ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) -> *const ::cxx::CxxPmrVector<Self> {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_unique_ptr_get]
+                    fn __unique_ptr_get #impl_generics(this: *const ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) -> *const ::cxx::CxxPmrVector<#elem #ty_generics>;
+                }
+                unsafe { __unique_ptr_get(#raw_const repr) }
+            }
+            unsafe fn __unique_ptr_release(mut repr: ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) -> *mut ::cxx::CxxPmrVector<Self> {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_unique_ptr_release]
+                    fn __unique_ptr_release #impl_generics(this: *mut ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) -> *mut ::cxx::CxxPmrVector<#elem #ty_generics>;
+                }
+                unsafe { __unique_ptr_release(#raw_mut repr) }
+            }
+            unsafe fn __unique_ptr_drop(mut repr: ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>) {
+                #UnsafeExtern extern "C" {
+                    #[link_name = #link_unique_ptr_drop]
+                    fn __unique_ptr_drop(this: *mut ::cxx::core::mem::MaybeUninit<*mut ::cxx::core::ffi::c_void>);
+                }
+                unsafe {
+                    __unique_ptr_drop(#raw_mut repr);
+                }
+            }
+        }
+    }
+}
+
 fn expand_return_type(ret: &Option<Type>) -> TokenStream {
     match ret {
         Some(ret) => quote!(-> #ret),
@@ -1918,7 +3211,7 @@ fn expand_return_type(ret: &Option<Type>) -> TokenStream {
 fn indirect_return(sig: &Signature, types: &Types) -> bool {
     sig.ret
         .as_ref()
-        .is_some_and(|ret| sig.throws || types.needs_indirect_abi(ret))
+        .is_some_and(|ret| sig.throws || sig.hresult.is_some() || types.needs_indirect_abi(ret))
 }
 
 fn expand_extern_type(ty: &Type, types: &Types, proper: bool) -> TokenStream {
@@ -1927,7 +3220,7 @@ fn expand_extern_type(ty: &Type, types: &Types, proper: bool) -> TokenStream {
             let span = ident.rust.span();
             quote_spanned!(span=> ::cxx::private::RustString)
         }
-        Type::RustBox(ty) | Type::UniquePtr(ty) => {
+        Type::RustBox(ty) | Type::UniquePtr(ty) | Type::UniqueArray(ty) | Type::ComPtr(ty) => {
             let span = ty.name.span();
             if proper && types.is_considered_improper_ctype(&ty.inner) {
                 quote_spanned!(span=> *mut ::cxx::core::ffi::c_void)
@@ -1989,6 +3282,12 @@ fn expand_extern_type(ty: &Type, types: &Types, proper: bool) -> TokenStream {
             let rust_slice = Ident::new("RustSlice", ty.bracket.span.join());
             quote_spanned!(span=> ::cxx::private::#rust_slice)
         }
+        Type::HashMapRef(ty) => {
+            let span = ty.ampersand.span;
+            let key = &ty.key;
+            let value = &ty.value;
+            quote_spanned!(span=> ::cxx::private::RustHashMap<#key, #value>)
+        }
         _ => quote!(#ty),
     }
 }
@@ -29,11 +29,11 @@ mod syntax;
 mod tokens;
 mod type_id;
 
-use crate::syntax::file::Module;
-use crate::syntax::namespace::Namespace;
+use crate::syntax::file::{BridgeArgs, Includes, Module};
 use crate::syntax::qualified::QualifiedName;
 use crate::type_id::Crate;
 use proc_macro::TokenStream;
+use std::path::{Path, PathBuf};
 use syn::parse::{Parse, ParseStream, Parser, Result};
 use syn::parse_macro_input;
 
@@ -54,22 +54,63 @@ use syn::parse_macro_input;
 ///
 /// The types and functions from the `extern "Rust"` side of the bridge will be
 /// placed into that same namespace in the generated C++ code.
+///
+/// A bridge with a `fake` argument doesn't call into C++ when its crate is
+/// built with `cfg(test)`. Its extern "C++" functions of `#[mock = Name]`
+/// blocks then call the implementation of the mock trait given as the `fake`
+/// argument, and the rest panic. This lets the Rust side be unit tested under
+/// Miri or without a C++ toolchain.
+///
+/// ```
+/// # mod tests {
+/// #     pub struct FakeEngine;
+/// # }
+/// #[cxx::bridge(namespace = "engine", fake = crate::tests::FakeEngine)]
+/// # mod ffi {}
+/// ```
 #[proc_macro_attribute]
 pub fn bridge(args: TokenStream, input: TokenStream) -> TokenStream {
     let _ = syntax::error::ERRORS;
 
-    let namespace = match Namespace::parse_bridge_attr_namespace.parse(args) {
-        Ok(namespace) => namespace,
+    let args = match BridgeArgs::parse.parse(args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let dir = source_dir();
+    let includes = Includes {
+        dir: dir.as_deref(),
+        unknown_dir: dir.is_none(),
+        respan: true,
+    };
+    let parse = |input: ParseStream| Module::parse(input, &includes);
+    let mut ffi = match parse.parse(input) {
+        Ok(ffi) => ffi,
         Err(err) => return err.to_compile_error().into(),
     };
-    let mut ffi = parse_macro_input!(input as Module);
-    ffi.namespace = namespace;
+    ffi.namespace = args.namespace;
+    ffi.fake = args.fake;
+    ffi.trace = cfg!(feature = "trace");
+    ffi.arbitrary = cfg!(feature = "arbitrary");
 
     expand::bridge(ffi)
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
 
+// The directory of the file containing the bridge, which is where the paths of
+// its include!("...") are relative to. A procedural macro can only find that out
+// since Rust 1.88, and before then the bridge cannot include other files.
+#[rustversion::since(1.88)]
+fn source_dir() -> Option<PathBuf> {
+    let file = proc_macro::Span::call_site().local_file()?;
+    file.parent().map(Path::to_owned)
+}
+
+#[rustversion::before(1.88)]
+fn source_dir() -> Option<PathBuf> {
+    None
+}
+
 #[doc(hidden)]
 #[proc_macro]
 pub fn type_id(input: TokenStream) -> TokenStream {
@@ -5,6 +5,7 @@ use syn::ext::IdentExt;
 
 pub(crate) enum Crate {
     Cxx,
+    #[allow(dead_code)] // only constructed by the macro crate, not cxxbridge-cmd
     DollarCrate(TokenTree),
 }
 
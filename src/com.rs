@@ -0,0 +1,107 @@
+//! Bindings to COM-style interfaces.
+//!
+//! An opaque C++ type declared in the bridge with `#[com_interface = "..."]`
+//! is a class deriving from `IUnknown`, whose identity is the given interface
+//! id. Its reference count is managed from Rust by [`ComPtr<T>`], which can be
+//! converted to other interfaces of the same object by [`ComPtr::cast`].
+//!
+//! Nothing here depends on the Windows SDK. The C++ side is built against
+//! whichever declarations of `IUnknown`, `GUID` and `HRESULT` are in scope,
+//! which are normally those of `<unknwn.h>`.
+
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Display};
+
+#[doc(no_inline)]
+pub use cxx::{ComPtr, HRESULT};
+
+/// Binding to the COM `GUID` struct, used as an interface id.
+///
+/// The layout is that of the C struct. Equality and ordering compare the
+/// fields in declaration order.
+///
+/// ```
+/// use cxx::com::Guid;
+///
+/// const IID_IUNKNOWN: Guid = Guid::from_u128(0x00000000_0000_0000_C000_000000000046);
+/// assert_eq!(IID_IUNKNOWN.to_string(), "00000000-0000-0000-C000-000000000046");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct Guid {
+    /// The first 8 hexadecimal digits.
+    pub data1: u32,
+    /// The next 4 hexadecimal digits.
+    pub data2: u16,
+    /// The next 4 hexadecimal digits.
+    pub data3: u16,
+    /// The last 16 hexadecimal digits, as big endian bytes.
+    pub data4: [u8; 8],
+}
+
+impl Guid {
+    /// Makes a Guid from the 128-bit integer spelled by its registry format,
+    /// so that `"6B29FC40-CA47-1067-B31D-00DD010662DA"` is
+    /// `0x6B29FC40_CA47_1067_B31D_00DD010662DA`.
+    pub const fn from_u128(value: u128) -> Self {
+        Guid {
+            data1: (value >> 96) as u32,
+            data2: (value >> 80) as u16,
+            data3: (value >> 64) as u16,
+            data4: (value as u64).to_be_bytes(),
+        }
+    }
+
+    /// Inverse of [`Guid::from_u128`].
+    pub const fn to_u128(self) -> u128 {
+        (self.data1 as u128) << 96
+            | (self.data2 as u128) << 80
+            | (self.data3 as u128) << 64
+            | u64::from_be_bytes(self.data4) as u128
+    }
+}
+
+impl Display for Guid {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.to_u128();
+        write!(
+            formatter,
+            "{:08X}-{:04X}-{:04X}-{:04X}-{:012X}",
+            value >> 96,
+            (value >> 80) & 0xFFFF,
+            (value >> 64) & 0xFFFF,
+            (value >> 48) & 0xFFFF,
+            value & 0xFFFF_FFFF_FFFF,
+        )
+    }
+}
+
+impl Debug for Guid {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Guid({})", self)
+    }
+}
+
+/// Trait bound for types which may be used as the `T` inside of a
+/// `ComPtr<T>` in generic code.
+///
+/// The bridge implements this trait for every extern C++ type declared with the
+/// `#[com_interface = "..."]` attribute, whose `IID` is the interface id given
+/// there. This trait has no publicly callable or implementable methods.
+/// Implementing it outside of the CXX codebase is not supported.
+pub unsafe trait ComInterface {
+    /// The interface id passed to `QueryInterface` to obtain this interface.
+    const IID: Guid;
+    #[doc(hidden)]
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result;
+    #[doc(hidden)]
+    unsafe fn __add_ref(this: *mut c_void) -> u32;
+    #[doc(hidden)]
+    unsafe fn __release(this: *mut c_void) -> u32;
+    #[doc(hidden)]
+    unsafe fn __query_interface(
+        this: *mut c_void,
+        iid: *const Guid,
+        out: *mut *mut c_void,
+    ) -> HRESULT;
+}
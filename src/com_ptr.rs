@@ -0,0 +1,181 @@
+use crate::com::{ComInterface, Guid};
+use crate::fmt::display;
+use crate::hresult::HRESULT;
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Display};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr;
+
+/// Owning reference to a COM interface, released when dropped.
+///
+/// Holds one reference count of the object, taken with `AddRef` when the
+/// ComPtr is cloned and given back with `Release` when it is dropped. In C++
+/// the same type is `rust::ComPtr<T>`, and in a `Result<ComPtr<T>, HRESULT>`
+/// return value it is the `T**` out-parameter of a COM method.
+///
+/// Like a raw interface pointer, a `ComPtr<T>` is neither `Send` nor `Sync`,
+/// since COM objects may be bound to the apartment that created them.
+#[repr(transparent)]
+pub struct ComPtr<T>
+where
+    T: ComInterface,
+{
+    repr: *mut T,
+    ty: PhantomData<T>,
+}
+
+impl<T> ComPtr<T>
+where
+    T: ComInterface,
+{
+    /// Makes a new ComPtr holding a null pointer.
+    pub fn null() -> Self {
+        ComPtr {
+            repr: ptr::null_mut(),
+            ty: PhantomData,
+        }
+    }
+
+    /// Checks whether the ComPtr holds a null pointer.
+    pub fn is_null(&self) -> bool {
+        self.repr.is_null()
+    }
+
+    /// Returns a reference to the interface pointed to by the ComPtr if
+    /// nonnull, otherwise None.
+    pub fn as_ref(&self) -> Option<&T> {
+        unsafe { self.repr.as_ref() }
+    }
+
+    /// Returns the raw interface pointer without affecting the reference
+    /// count.
+    pub fn as_ptr(&self) -> *mut T {
+        self.repr
+    }
+
+    /// Consumes the ComPtr, returning the raw interface pointer together with
+    /// the reference count it owned.
+    pub fn into_raw(self) -> *mut T {
+        let ptr = self.repr;
+        core::mem::forget(self);
+        ptr
+    }
+
+    /// Constructs a ComPtr taking ownership of one reference count of an
+    /// interface pointer, such as the ones returned by COM out-parameters.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must either be null or point to a live object implementing
+    /// the interface `T`, and the caller must own the reference count that
+    /// is being transferred.
+    pub unsafe fn from_raw(raw: *mut T) -> Self {
+        ComPtr {
+            repr: raw,
+            ty: PhantomData,
+        }
+    }
+
+    /// Asks the object for another of its interfaces, as `QueryInterface`
+    /// with the interface id of `U`.
+    ///
+    /// Fails with `E_POINTER` if the ComPtr is null, or with the status code
+    /// returned by `QueryInterface`, normally `E_NOINTERFACE`.
+    pub fn cast<U>(&self) -> Result<ComPtr<U>, HRESULT>
+    where
+        U: ComInterface,
+    {
+        if self.is_null() {
+            return Err(HRESULT::E_POINTER);
+        }
+        let this = self.repr.cast::<c_void>();
+        let iid: *const Guid = &U::IID;
+        let mut out = ptr::null_mut::<c_void>();
+        unsafe {
+            T::__query_interface(this, iid, &mut out).ok()?;
+            Ok(ComPtr::from_raw(out.cast()))
+        }
+    }
+}
+
+impl<T> Default for ComPtr<T>
+where
+    T: ComInterface,
+{
+    fn default() -> Self {
+        ComPtr::null()
+    }
+}
+
+impl<T> Clone for ComPtr<T>
+where
+    T: ComInterface,
+{
+    fn clone(&self) -> Self {
+        if !self.is_null() {
+            unsafe {
+                T::__add_ref(self.repr.cast());
+            }
+        }
+        ComPtr {
+            repr: self.repr,
+            ty: PhantomData,
+        }
+    }
+}
+
+// ComPtr is not a self-referential type and is safe to move out of a Pin,
+// regardless whether the pointer's target is Unpin.
+impl<T> Unpin for ComPtr<T> where T: ComInterface {}
+
+impl<T> Drop for ComPtr<T>
+where
+    T: ComInterface,
+{
+    fn drop(&mut self) {
+        if !self.is_null() {
+            unsafe {
+                T::__release(self.repr.cast());
+            }
+        }
+    }
+}
+
+impl<T> Deref for ComPtr<T>
+where
+    T: ComInterface,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self.as_ref() {
+            Some(target) => target,
+            None => panic!("called deref on a null ComPtr<{}>", display(T::__typename),),
+        }
+    }
+}
+
+impl<T> Debug for ComPtr<T>
+where
+    T: Debug + ComInterface,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_ref() {
+            None => formatter.write_str("nullptr"),
+            Some(value) => Debug::fmt(value, formatter),
+        }
+    }
+}
+
+impl<T> Display for ComPtr<T>
+where
+    T: Display + ComInterface,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_ref() {
+            None => formatter.write_str("nullptr"),
+            Some(value) => Display::fmt(value, formatter),
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::fmt::display;
+use core::cmp::Ordering;
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+
+/// Binding to a third-party C++ smart pointer template `P<T>`, such as
+/// `boost::intrusive_ptr<T>`.
+///
+/// The family `P` is declared once in the bridge as an extern C++ type with
+/// the `#[smart_ptr]` attribute, naming the C++ class template. Every
+/// `CustomPtr<P, T>` that appears in the bridge is then instantiated by the
+/// code generator just like `SharedPtr<T>` is.
+///
+/// ```
+/// #[cxx::bridge]
+/// mod ffi {
+///     unsafe extern "C++" {
+/// #       /*
+///         include!("engine/handle.h");
+/// #       */
+///
+///         #[namespace = "boost"]
+///         #[cxx_name = "intrusive_ptr"]
+///         #[smart_ptr]
+///         type IntrusivePtr;
+///
+///         type Widget;
+///
+/// #       /*
+///         fn make_widget() -> CustomPtr<IntrusivePtr, Widget>;
+/// #       */
+///     }
+/// }
+/// ```
+///
+/// The C++ template must be pointer-sized, and safe to relocate by copying its
+/// bytes as Rust does with every move. Its default constructor must produce a
+/// null pointer, its copy constructor must share ownership of the same object,
+/// its move constructor must leave the source null, and `get()` must return the
+/// stored `T*`. Only the size and alignment are checked at compile time by the
+/// generated C++ code.
+///
+/// Unlike `SharedPtr<T>`, a `CustomPtr<P, T>` is neither `Send` nor `Sync`,
+/// because nothing is known about whether the C++ smart pointer's ownership
+/// bookkeeping is thread safe.
+#[repr(C)]
+pub struct CustomPtr<P, T>
+where
+    T: CustomPtrTarget<P>,
+{
+    repr: MaybeUninit<*mut c_void>,
+    ty: PhantomData<(*const P, T)>,
+}
+
+impl<P, T> CustomPtr<P, T>
+where
+    T: CustomPtrTarget<P>,
+{
+    /// Makes a new CustomPtr wrapping a default constructed C++ smart
+    /// pointer, which is required to be null.
+    pub fn null() -> Self {
+        let mut custom_ptr = MaybeUninit::<CustomPtr<P, T>>::uninit();
+        let new = custom_ptr.as_mut_ptr().cast();
+        unsafe {
+            T::__custom_ptr_null(new);
+            custom_ptr.assume_init()
+        }
+    }
+
+    /// Checks whether the CustomPtr holds a null pointer.
+    pub fn is_null(&self) -> bool {
+        self.as_ptr().is_null()
+    }
+
+    /// Returns a reference to the object pointed to by the CustomPtr if
+    /// nonnull, otherwise None.
+    pub fn as_ref(&self) -> Option<&T> {
+        let ptr = self.as_ptr();
+        unsafe { ptr.as_ref() }
+    }
+
+    /// Returns the pointer stored by the C++ smart pointer, as given by its
+    /// `get()` member function.
+    pub fn as_ptr(&self) -> *const T {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__custom_ptr_get(this) }
+    }
+}
+
+impl<P, T> Clone for CustomPtr<P, T>
+where
+    T: CustomPtrTarget<P>,
+{
+    fn clone(&self) -> Self {
+        let mut custom_ptr = MaybeUninit::<CustomPtr<P, T>>::uninit();
+        let new = custom_ptr.as_mut_ptr().cast();
+        let this = self as *const Self as *const c_void;
+        unsafe {
+            T::__custom_ptr_clone(this, new);
+            custom_ptr.assume_init()
+        }
+    }
+}
+
+// CustomPtr is not a self-referential type and is safe to move out of a Pin,
+// regardless whether the pointer's target is Unpin.
+impl<P, T> Unpin for CustomPtr<P, T> where T: CustomPtrTarget<P> {}
+
+impl<P, T> Drop for CustomPtr<P, T>
+where
+    T: CustomPtrTarget<P>,
+{
+    fn drop(&mut self) {
+        let this = self as *mut Self as *mut c_void;
+        unsafe { T::__custom_ptr_drop(this) }
+    }
+}
+
+impl<P, T> Deref for CustomPtr<P, T>
+where
+    T: CustomPtrTarget<P>,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self.as_ref() {
+            Some(target) => target,
+            None => panic!(
+                "called deref on a null CustomPtr<{}, {}>",
+                display(T::__family),
+                display(T::__typename),
+            ),
+        }
+    }
+}
+
+impl<P, T> Debug for CustomPtr<P, T>
+where
+    T: Debug + CustomPtrTarget<P>,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_ref() {
+            None => formatter.write_str("nullptr"),
+            Some(value) => Debug::fmt(value, formatter),
+        }
+    }
+}
+
+impl<P, T> Display for CustomPtr<P, T>
+where
+    T: Display + CustomPtrTarget<P>,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_ref() {
+            None => formatter.write_str("nullptr"),
+            Some(value) => Display::fmt(value, formatter),
+        }
+    }
+}
+
+impl<P, T> PartialEq for CustomPtr<P, T>
+where
+    T: PartialEq + CustomPtrTarget<P>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<P, T> Eq for CustomPtr<P, T> where T: Eq + CustomPtrTarget<P> {}
+
+impl<P, T> PartialOrd for CustomPtr<P, T>
+where
+    T: PartialOrd + CustomPtrTarget<P>,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&self.as_ref(), &other.as_ref())
+    }
+}
+
+impl<P, T> Ord for CustomPtr<P, T>
+where
+    T: Ord + CustomPtrTarget<P>,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&self.as_ref(), &other.as_ref())
+    }
+}
+
+impl<P, T> Hash for CustomPtr<P, T>
+where
+    T: Hash + CustomPtrTarget<P>,
+{
+    fn hash<H>(&self, hasher: &mut H)
+    where
+        H: Hasher,
+    {
+        self.as_ref().hash(hasher);
+    }
+}
+
+/// Trait bound for types which may be used as the `T` inside of a
+/// `CustomPtr<P, T>` in generic code.
+///
+/// The bridge implements this trait for every `CustomPtr<P, T>` mentioned in
+/// it, where `P` is an extern C++ type declared with `#[smart_ptr]`. This trait
+/// has no publicly callable or implementable methods. Implementing it outside
+/// of the CXX codebase is not supported.
+pub unsafe trait CustomPtrTarget<P> {
+    #[doc(hidden)]
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result;
+    #[doc(hidden)]
+    fn __family(f: &mut fmt::Formatter) -> fmt::Result;
+    #[doc(hidden)]
+    unsafe fn __custom_ptr_null(new: *mut c_void);
+    #[doc(hidden)]
+    unsafe fn __custom_ptr_clone(this: *const c_void, new: *mut c_void);
+    #[doc(hidden)]
+    unsafe fn __custom_ptr_get(this: *const c_void) -> *const Self;
+    #[doc(hidden)]
+    unsafe fn __custom_ptr_drop(this: *mut c_void);
+}
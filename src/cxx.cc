@@ -1,13 +1,29 @@
 #include "../include/cxx.h"
+#include <atomic>
 #include <cstdio>
 #include <cstring>
 #include <iostream>
 #include <memory>
+#include <system_error>
+#ifndef RUST_CXX_NO_RTTI
+#include <typeinfo>
+#if defined(__GNUG__)
+#include <cstdlib>
+#include <cxxabi.h>
+#endif
+#endif
 
 #ifdef __cpp_lib_bit_cast
 #include <bit>
 #endif
 
+#if (__cplusplus >= 201703L || defined(_MSVC_LANG) && _MSVC_LANG >= 201703L) && \
+    defined(__has_include)
+#if __has_include(<memory_resource>)
+#include <memory_resource>
+#endif
+#endif
+
 // Most compilers set __cpp_attributes on C++11 and up, and set __cpp_exceptions
 // if the flag `-fno-exceptions` is not set. On these compilers we detect
 // `-fno-exceptions` this way.
@@ -22,12 +38,25 @@
 #define RUST_CXX_NO_EXCEPTIONS
 #endif
 
+// Likewise `-fno-rtti`, without which a caught std::exception can be inspected
+// for the error code and source chain of more specific exception types. Define
+// `-DRUST_CXX_NO_RTTI` on compilers not detected here.
+#if defined(__GNUC__) && !defined(__GXX_RTTI) ||                              \
+    defined(_MSC_VER) && !defined(_CPPRTTI)
+#define RUST_CXX_NO_RTTI
+#endif
+
 extern "C" {
 void cxxbridge1$cxx_string$init(std::string *s, const std::uint8_t *ptr,
                                 std::size_t len) noexcept {
   new (s) std::string(reinterpret_cast<const char *>(ptr), len);
 }
 
+std::string *cxxbridge1$cxx_string$new(const std::uint8_t *ptr,
+                                      std::size_t len) noexcept {
+  return new std::string(reinterpret_cast<const char *>(ptr), len);
+}
+
 void cxxbridge1$cxx_string$destroy(std::string *s) noexcept {
   using std::string;
   s->~string();
@@ -53,6 +82,17 @@ void cxxbridge1$cxx_string$push(std::string &s, const std::uint8_t *ptr,
   s.append(reinterpret_cast<const char *>(ptr), len);
 }
 
+void cxxbridge1$cxx_string$truncate(std::string &s, std::size_t len) noexcept {
+  s.resize(len);
+}
+
+// Capacity beyond what a default constructed string holds inline, as a measure
+// of heap memory allocated on behalf of Rust.
+std::size_t cxxbridge1$cxx_string$heap_capacity(const std::string &s) noexcept {
+  std::size_t inline_capacity = std::string().capacity();
+  return s.capacity() > inline_capacity ? s.capacity() : 0;
+}
+
 // rust::String
 void cxxbridge1$string$new(rust::String *self) noexcept;
 void cxxbridge1$string$clone(rust::String *self,
@@ -73,20 +113,28 @@ void cxxbridge1$string$reserve_additional(rust::String *self,
                                           size_t additional) noexcept;
 void cxxbridge1$string$reserve_total(rust::String *self,
                                      size_t new_cap) noexcept;
+bool cxxbridge1$string$push_utf8(rust::String *self, const char *ptr,
+                                 std::size_t len) noexcept;
 
 // rust::Str
 void cxxbridge1$str$new(rust::Str *self) noexcept;
 void cxxbridge1$str$ref(rust::Str *self, const rust::String *string) noexcept;
 bool cxxbridge1$str$from(rust::Str *self, const char *ptr,
                          std::size_t len) noexcept;
+void cxxbridge1$str$from_unchecked(rust::Str *self, const char *ptr,
+                                   std::size_t len) noexcept;
 const char *cxxbridge1$str$ptr(const rust::Str *self) noexcept;
 std::size_t cxxbridge1$str$len(const rust::Str *self) noexcept;
+std::size_t cxxbridge1$str$hash(const rust::Str *self) noexcept;
 
 // rust::Slice
 void cxxbridge1$slice$new(void *self, const void *ptr,
                           std::size_t len) noexcept;
 void *cxxbridge1$slice$ptr(const void *self) noexcept;
 std::size_t cxxbridge1$slice$len(const void *self) noexcept;
+
+// rust::trace
+rust::trace::Hook cxxbridge1$trace$set_hook(rust::trace::Hook hook) noexcept;
 } // extern "C"
 
 namespace rust {
@@ -170,6 +218,10 @@ String::String(const char16_t *s, std::size_t len) {
              len);
 }
 
+String::String(const std::u16string &s) {
+  initString(this, s.data(), s.length());
+}
+
 struct String::lossy_t {};
 
 String::String(lossy_t, const char *s, std::size_t len) noexcept {
@@ -211,6 +263,16 @@ String String::lossy(const char16_t *s, std::size_t len) noexcept {
   return String(lossy_t{}, s, len);
 }
 
+String String::lossy(const std::u16string &s) noexcept {
+  return String::lossy(s.data(), s.length());
+}
+
+String String::from_utf16(const char16_t *s, std::size_t len) {
+  return String(s, len);
+}
+
+String String::from_utf16(const std::u16string &s) { return String(s); }
+
 String &String::operator=(const String &other) & noexcept {
   if (this != &other) {
     cxxbridge1$string$drop(this);
@@ -230,6 +292,36 @@ String::operator std::string() const {
   return std::string(this->data(), this->size());
 }
 
+std::u16string String::to_u16string() const {
+  std::u16string utf16;
+  utf16.reserve(this->size());
+  auto ptr = reinterpret_cast<const unsigned char *>(this->data());
+  auto end = ptr + this->size();
+  // The contents are known to be valid UTF-8, so no error handling is needed.
+  while (ptr != end) {
+    char32_t ch = *ptr++;
+    if (ch >= 0xF0) {
+      ch = (ch & 0x07) << 18 | (ptr[0] & 0x3F) << 12 | (ptr[1] & 0x3F) << 6 |
+           (ptr[2] & 0x3F);
+      ptr += 3;
+    } else if (ch >= 0xE0) {
+      ch = (ch & 0x0F) << 12 | (ptr[0] & 0x3F) << 6 | (ptr[1] & 0x3F);
+      ptr += 2;
+    } else if (ch >= 0x80) {
+      ch = (ch & 0x1F) << 6 | (ptr[0] & 0x3F);
+      ptr += 1;
+    }
+    if (ch >= 0x10000) {
+      ch -= 0x10000;
+      utf16.push_back(static_cast<char16_t>(0xD800 + (ch >> 10)));
+      utf16.push_back(static_cast<char16_t>(0xDC00 + (ch & 0x3FF)));
+    } else {
+      utf16.push_back(static_cast<char16_t>(ch));
+    }
+  }
+  return utf16;
+}
+
 const char *String::data() const noexcept {
   return cxxbridge1$string$ptr(this);
 }
@@ -302,6 +394,57 @@ bool String::operator>=(const String &rhs) const noexcept {
   return rust::Str(*this) >= rust::Str(rhs);
 }
 
+String &String::operator+=(const String &s) {
+  return this->append(s.data(), s.size());
+}
+
+String &String::operator+=(Str s) { return this->append(s.data(), s.size()); }
+
+String &String::operator+=(const std::string &s) {
+  return this->append(s.data(), s.size());
+}
+
+String &String::operator+=(const char *s) {
+  assert(s != nullptr);
+  return this->append(s, std::strlen(s));
+}
+
+#if __cplusplus >= 201703L
+String &String::operator+=(std::string_view s) {
+  return this->append(s.data(), s.size());
+}
+#endif
+
+String &String::append(const char *s, std::size_t len) {
+  if (len != 0 && !cxxbridge1$string$push_utf8(this, s, len)) {
+    panic<std::invalid_argument>("data for rust::String is not utf-8");
+  }
+  return *this;
+}
+
+bool String::starts_with(Str prefix) const noexcept {
+  return this->size() >= prefix.size() &&
+         std::equal(prefix.begin(), prefix.end(), this->begin());
+}
+
+bool String::ends_with(Str suffix) const noexcept {
+  return this->size() >= suffix.size() &&
+         std::equal(suffix.begin(), suffix.end(),
+                    this->end() - suffix.size());
+}
+
+std::size_t String::find(Str needle, std::size_t pos) const noexcept {
+  if (pos > this->size()) {
+    return std::string::npos;
+  }
+  auto found =
+      std::search(this->begin() + pos, this->end(), needle.begin(), needle.end());
+  if (found == this->end() && !needle.empty()) {
+    return std::string::npos;
+  }
+  return static_cast<std::size_t>(found - this->begin());
+}
+
 void String::swap(String &rhs) noexcept {
   using std::swap;
   swap(this->repr, rhs.repr);
@@ -339,6 +482,14 @@ Str::Str(const char *s, std::size_t len) {
           len);
 }
 
+Str::Str(unchecked_t, const char *s, std::size_t len) noexcept {
+  cxxbridge1$str$from_unchecked(this, s, len);
+}
+
+Str literals::operator""_rs(const char *s, std::size_t len) noexcept {
+  return Str(Str::unchecked_t{}, s, len);
+}
+
 Str::operator std::string() const {
   return std::string(this->data(), this->size());
 }
@@ -375,8 +526,13 @@ bool Str::operator==(const Str &rhs) const noexcept {
 bool Str::operator!=(const Str &rhs) const noexcept { return !(*this == rhs); }
 
 bool Str::operator<(const Str &rhs) const noexcept {
-  return std::lexicographical_compare(this->begin(), this->end(), rhs.begin(),
-                                      rhs.end());
+  // Bytes compare as unsigned, the same as the ordering of str in Rust.
+  return std::lexicographical_compare(
+      this->begin(), this->end(), rhs.begin(), rhs.end(),
+      [](char lhs, char rhs) {
+        return static_cast<unsigned char>(lhs) <
+               static_cast<unsigned char>(rhs);
+      });
 }
 
 bool Str::operator<=(const Str &rhs) const noexcept {
@@ -392,7 +548,8 @@ bool Str::operator<=(const Str &rhs) const noexcept {
   } else if (riter == rend) {
     return false; // rhs is a prefix of *this
   } else {
-    return *liter <= *riter;
+    return static_cast<unsigned char>(*liter) <=
+           static_cast<unsigned char>(*riter);
   }
 }
 
@@ -483,6 +640,14 @@ static_assert(std::is_trivially_move_assignable<Slice<std::uint8_t>>::value,
 static_assert(std::is_trivially_destructible<Slice<std::uint8_t>>::value,
               "trivial ~Slice()");
 
+static_assert(
+    std::is_trivially_copy_constructible<HashMap<int, int>>::value,
+    "trivial HashMap(const HashMap &)");
+static_assert(std::is_trivially_copy_assignable<HashMap<int, int>>::value,
+              "trivial HashMap::operator=(const HashMap &)");
+static_assert(std::is_trivially_destructible<HashMap<int, int>>::value,
+              "trivial ~HashMap()");
+
 static_assert(std::is_same<Vec<std::uint8_t>::const_iterator,
                            Vec<const std::uint8_t>::iterator>::value,
               "Vec<T>::const_iterator == Vec<const T>::iterator");
@@ -493,16 +658,61 @@ static_assert(!std::is_same<Vec<std::uint8_t>::const_iterator,
                             Vec<std::uint8_t>::iterator>::value,
               "Vec<T>::const_iterator != Vec<T>::iterator");
 
-static const char *errorCopy(const char *ptr, std::size_t len) {
-  char *copy = new char[len + 1];
-  std::memcpy(copy, ptr, len);
-  copy[len] = '\0';
+namespace repr {
+struct PtrLen final {
+  void *ptr;
+  std::size_t len;
+};
+} // namespace repr
+
+// The buffer behind rust::Error's msg holds the what() string and its null
+// terminator, followed by the error code and the number of sources, and then
+// each source message prefixed by its length and null terminated. The fields
+// after the what() string are unaligned and accessed with memcpy.
+static std::size_t errorSize(const char *msg, std::size_t len) noexcept {
+  const char *pos = msg + len + 1 + sizeof(std::int32_t);
+  std::size_t count;
+  std::memcpy(&count, pos, sizeof count);
+  pos += sizeof count;
+  while (count-- > 0) {
+    std::size_t source_len;
+    std::memcpy(&source_len, pos, sizeof source_len);
+    pos += sizeof source_len + source_len + 1;
+  }
+  return static_cast<std::size_t>(pos - msg);
+}
+
+static const char *errorCopy(const char *msg, std::size_t len) {
+  std::size_t size = errorSize(msg, len);
+  char *copy = new char[size];
+  std::memcpy(copy, msg, size);
   return copy;
 }
 
 extern "C" {
-const char *cxxbridge1$error(const char *ptr, std::size_t len) noexcept {
-  return errorCopy(ptr, len);
+const char *cxxbridge1$error(repr::PtrLen what, std::int32_t code,
+                             const repr::PtrLen *sources,
+                             std::size_t count) noexcept {
+  std::size_t size = what.len + 1 + sizeof code + sizeof count;
+  for (std::size_t i = 0; i < count; i++) {
+    size += sizeof(std::size_t) + sources[i].len + 1;
+  }
+  char *copy = new char[size];
+  char *pos = copy;
+  auto write = [&pos](const void *data, std::size_t len) {
+    std::memcpy(pos, data, len);
+    pos += len;
+  };
+  write(what.ptr, what.len);
+  *pos++ = '\0';
+  write(&code, sizeof code);
+  write(&count, sizeof count);
+  for (std::size_t i = 0; i < count; i++) {
+    write(&sources[i].len, sizeof(std::size_t));
+    write(sources[i].ptr, sources[i].len);
+    *pos++ = '\0';
+  }
+  return copy;
 }
 } // extern "C"
 
@@ -544,6 +754,34 @@ Error &Error::operator=(Error &&other) & noexcept {
 
 const char *Error::what() const noexcept { return this->msg; }
 
+std::int32_t Error::code() const noexcept {
+  std::int32_t code = 0;
+  if (this->msg) {
+    std::memcpy(&code, this->msg + this->len + 1, sizeof code);
+  }
+  return code;
+}
+
+std::vector<std::string> Error::sources() const {
+  std::vector<std::string> sources;
+  if (!this->msg) {
+    return sources;
+  }
+  const char *pos = this->msg + this->len + 1 + sizeof(std::int32_t);
+  std::size_t count;
+  std::memcpy(&count, pos, sizeof count);
+  pos += sizeof count;
+  sources.reserve(count);
+  while (count-- > 0) {
+    std::size_t len;
+    std::memcpy(&len, pos, sizeof len);
+    pos += sizeof len;
+    sources.emplace_back(pos, len);
+    pos += len + 1;
+  }
+  return sources;
+}
+
 namespace {
 template <typename T>
 union MaybeUninit {
@@ -553,15 +791,11 @@ union MaybeUninit {
 };
 } // namespace
 
-namespace repr {
-struct PtrLen final {
-  void *ptr;
-  std::size_t len;
-};
-} // namespace repr
-
 extern "C" {
-repr::PtrLen cxxbridge1$exception(const char *, std::size_t len) noexcept;
+repr::PtrLen cxxbridge1$exception(repr::PtrLen what, repr::PtrLen type_name,
+                                  std::int32_t code,
+                                  const repr::PtrLen *sources,
+                                  std::size_t count) noexcept;
 }
 
 namespace detail {
@@ -590,20 +824,122 @@ public:
   Fail(repr::PtrLen &throw$) noexcept : throw$(throw$) {}
   void operator()(const char *) noexcept;
   void operator()(const std::string &) noexcept;
+  void operator()(const std::exception &) noexcept;
+
+private:
+  void fail(const char *, std::size_t, const std::string &, std::int32_t,
+            const std::vector<std::string> &) noexcept;
 };
 
 void Fail::operator()(const char *catch$) noexcept {
-  throw$ = cxxbridge1$exception(catch$, std::strlen(catch$));
+  this->fail(catch$, std::strlen(catch$), {}, 0, {});
 }
 
 void Fail::operator()(const std::string &catch$) noexcept {
-  throw$ = cxxbridge1$exception(catch$.data(), catch$.length());
+  this->fail(catch$.data(), catch$.length(), {}, 0, {});
+}
+
+#ifndef RUST_CXX_NO_RTTI
+static std::string typeName(const std::exception &e) noexcept {
+  const char *name = typeid(e).name();
+#if defined(__GNUG__)
+  int status = 0;
+  char *demangled = abi::__cxa_demangle(name, nullptr, nullptr, &status);
+  if (status == 0 && demangled != nullptr) {
+    std::string result(demangled);
+    std::free(demangled);
+    return result;
+  }
+#endif
+  return name;
+}
+#endif
+
+#if !defined(RUST_CXX_NO_EXCEPTIONS) && !defined(RUST_CXX_NO_RTTI)
+static void nestedSources(const std::exception &e,
+                          std::vector<std::string> &sources) noexcept {
+  try {
+    std::rethrow_if_nested(e);
+  } catch (const std::exception &nested) {
+    sources.emplace_back(nested.what());
+    nestedSources(nested, sources);
+  } catch (...) {
+  }
+}
+#endif
+
+void Fail::operator()(const std::exception &catch$) noexcept {
+  std::string type_name;
+  std::int32_t code = 0;
+  std::vector<std::string> sources;
+#ifndef RUST_CXX_NO_RTTI
+  type_name = typeName(catch$);
+  if (auto error = dynamic_cast<const Error *>(&catch$)) {
+    code = error->code();
+    sources = error->sources();
+  } else {
+    if (auto error = dynamic_cast<const std::system_error *>(&catch$)) {
+      code = static_cast<std::int32_t>(error->code().value());
+    }
+#ifndef RUST_CXX_NO_EXCEPTIONS
+    nestedSources(catch$, sources);
+#endif
+  }
+#endif
+  const char *what = catch$.what();
+  this->fail(what, std::strlen(what), type_name, code, sources);
+}
+
+void Fail::fail(const char *what, std::size_t len, const std::string &type_name,
+                std::int32_t code,
+                const std::vector<std::string> &sources) noexcept {
+  std::vector<repr::PtrLen> repr;
+  repr.reserve(sources.size());
+  for (const std::string &source : sources) {
+    repr.push_back({const_cast<char *>(source.data()), source.length()});
+  }
+  throw$ = cxxbridge1$exception(
+      {const_cast<char *>(what), len},
+      {const_cast<char *>(type_name.data()), type_name.length()}, code,
+      repr.data(), repr.size());
 }
 } // namespace detail
 
+namespace {
+std::atomic<ForeignExceptionHandler> foreign_exception_handler{nullptr};
+} // namespace
+
+ForeignExceptionHandler
+set_foreign_exception_handler(ForeignExceptionHandler handler) noexcept {
+  return foreign_exception_handler.exchange(handler);
+}
+
+namespace detail {
+void foreign_exception(const char *function) noexcept {
+  ForeignExceptionHandler handler = foreign_exception_handler.load();
+  if (handler != nullptr) {
+    handler(function);
+  }
+}
+} // namespace detail
+
+namespace trace {
+Hook set_hook(Hook hook) noexcept { return cxxbridge1$trace$set_hook(hook); }
+} // namespace trace
+
 } // namespace cxxbridge1
 } // namespace rust
 
+// Same as the Rust hash of the str, as used for derive(Hash) on shared structs.
+std::size_t std::hash<rust::Str>::operator()(rust::Str s) const noexcept {
+  return cxxbridge1$str$hash(&s);
+}
+
+std::size_t
+std::hash<rust::String>::operator()(const rust::String &s) const noexcept {
+  return hash<rust::Str>{}(s);
+}
+
 namespace {
 template <typename T>
 void destroy(T *ptr) {
@@ -642,7 +978,290 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
               "unexpectedly large std::string size");
 } // namespace
 
-#define STD_VECTOR_OPS(RUST_TYPE, CXX_TYPE)                                    \
+#ifdef __cpp_lib_memory_resource
+extern "C" {
+std::pmr::memory_resource *cxxbridge1$pmr$new_delete_resource() noexcept {
+  return std::pmr::new_delete_resource();
+}
+
+std::pmr::memory_resource *cxxbridge1$pmr$null_memory_resource() noexcept {
+  return std::pmr::null_memory_resource();
+}
+
+std::pmr::memory_resource *cxxbridge1$pmr$get_default_resource() noexcept {
+  return std::pmr::get_default_resource();
+}
+
+std::pmr::string *
+cxxbridge1$cxx_pmr_string$new_in(std::pmr::memory_resource *resource) noexcept {
+  return new std::pmr::string(resource);
+}
+
+const char *
+cxxbridge1$cxx_pmr_string$data(const std::pmr::string &s) noexcept {
+  return s.data();
+}
+
+std::size_t
+cxxbridge1$cxx_pmr_string$length(const std::pmr::string &s) noexcept {
+  return s.length();
+}
+
+void cxxbridge1$cxx_pmr_string$clear(std::pmr::string &s) noexcept {
+  s.clear();
+}
+
+void cxxbridge1$cxx_pmr_string$reserve_total(std::pmr::string &s,
+                                             std::size_t new_cap) noexcept {
+  s.reserve(new_cap);
+}
+
+void cxxbridge1$cxx_pmr_string$push(std::pmr::string &s,
+                                    const std::uint8_t *ptr,
+                                    std::size_t len) noexcept {
+  s.append(reinterpret_cast<const char *>(ptr), len);
+}
+
+void cxxbridge1$cxx_pmr_string$truncate(std::pmr::string &s,
+                                        std::size_t len) noexcept {
+  s.resize(len);
+}
+
+std::pmr::memory_resource *
+cxxbridge1$cxx_pmr_string$resource(const std::pmr::string &s) noexcept {
+  return s.get_allocator().resource();
+}
+
+void cxxbridge1$unique_ptr$std$pmr$string$null(
+    std::unique_ptr<std::pmr::string> *ptr) noexcept {
+  new (ptr) std::unique_ptr<std::pmr::string>();
+}
+void cxxbridge1$unique_ptr$std$pmr$string$raw(
+    std::unique_ptr<std::pmr::string> *ptr, std::pmr::string *raw) noexcept {
+  new (ptr) std::unique_ptr<std::pmr::string>(raw);
+}
+const std::pmr::string *cxxbridge1$unique_ptr$std$pmr$string$get(
+    const std::unique_ptr<std::pmr::string> &ptr) noexcept {
+  return ptr.get();
+}
+std::pmr::string *cxxbridge1$unique_ptr$std$pmr$string$release(
+    std::unique_ptr<std::pmr::string> &ptr) noexcept {
+  return ptr.release();
+}
+void cxxbridge1$unique_ptr$std$pmr$string$drop(
+    std::unique_ptr<std::pmr::string> *ptr) noexcept {
+  ptr->~unique_ptr();
+}
+} // extern "C"
+#endif // __cpp_lib_memory_resource
+
+namespace {
+// A stream reports failure through its state bits, or by throwing if its
+// exception mask says to. Both are turned into a failure result for Rust.
+template <typename Op>
+bool stream_op(Op op) noexcept {
+#if defined(RUST_CXX_NO_EXCEPTIONS)
+  return op();
+#else
+  try {
+    return op();
+  } catch (...) {
+    return false;
+  }
+#endif
+}
+
+std::ios_base::seekdir seek_dir(std::uint8_t whence) noexcept {
+  switch (whence) {
+  case 0:
+    return std::ios_base::beg;
+  case 1:
+    return std::ios_base::cur;
+  default:
+    return std::ios_base::end;
+  }
+}
+
+// Input buffer refilled from a Rust std::io::Read.
+class RustReadBuf final : public std::streambuf {
+public:
+  using Read = bool (*)(void *, char *, std::size_t, std::size_t *);
+
+  RustReadBuf(void *state, Read read) noexcept : state(state), read(read) {}
+
+protected:
+  int_type underflow() override {
+    if (this->gptr() == this->egptr()) {
+      std::size_t n = 0;
+      if (!this->read(this->state, this->buf, sizeof this->buf, &n)) {
+#if defined(RUST_CXX_NO_EXCEPTIONS)
+        return traits_type::eof();
+#else
+        // Thrown to the istream, which turns it into badbit, as opposed to
+        // returning eof which would look like the end of the input.
+        throw std::ios_base::failure("failed to read from Rust reader");
+#endif
+      }
+      if (n == 0) {
+        return traits_type::eof();
+      }
+      this->setg(this->buf, this->buf, this->buf + n);
+    }
+    return traits_type::to_int_type(*this->gptr());
+  }
+
+private:
+  void *state;
+  Read read;
+  char buf[8192];
+};
+
+// Output buffer drained into a Rust std::io::Write.
+class RustWriteBuf final : public std::streambuf {
+public:
+  using Write = bool (*)(void *, const char *, std::size_t);
+  using Flush = bool (*)(void *);
+
+  RustWriteBuf(void *state, Write write, Flush flush) noexcept
+      : state(state), write(write), flush(flush) {
+    this->setp(this->buf, this->buf + sizeof this->buf);
+  }
+
+protected:
+  int_type overflow(int_type ch) override {
+    if (!this->drain()) {
+      return traits_type::eof();
+    }
+    if (!traits_type::eq_int_type(ch, traits_type::eof())) {
+      *this->pptr() = traits_type::to_char_type(ch);
+      this->pbump(1);
+    }
+    return traits_type::not_eof(ch);
+  }
+
+  int sync() override {
+    return this->drain() && this->flush(this->state) ? 0 : -1;
+  }
+
+private:
+  bool drain() noexcept {
+    std::size_t len = static_cast<std::size_t>(this->pptr() - this->pbase());
+    if (len != 0 && !this->write(this->state, this->pbase(), len)) {
+      return false;
+    }
+    this->setp(this->buf, this->buf + sizeof this->buf);
+    return true;
+  }
+
+  void *state;
+  Write write;
+  Flush flush;
+  char buf[8192];
+};
+} // namespace
+
+extern "C" {
+bool cxxbridge1$std$istream$read(std::istream &is, char *buf, std::size_t len,
+                                 std::size_t *n) noexcept {
+  *n = 0;
+  if (len == 0) {
+    return true;
+  }
+  return stream_op([&] {
+    // Like any Rust reader, return what is available rather than waiting for
+    // the whole buffer to fill, such as from an interactive stream. Only when
+    // nothing is buffered, block until at least one byte arrives.
+    auto max = static_cast<std::streamsize>(len);
+    std::streambuf *sb = is.rdbuf();
+    std::streamsize avail = sb == nullptr ? 0 : sb->in_avail();
+    if (avail > 0) {
+      *n = static_cast<std::size_t>(is.readsome(buf, std::min(avail, max)));
+    } else {
+      is.read(buf, 1);
+      *n = static_cast<std::size_t>(is.gcount());
+      if (*n == 1 && max > 1) {
+        *n += static_cast<std::size_t>(is.readsome(buf + 1, max - 1));
+      }
+    }
+    if (is.bad()) {
+      return false;
+    }
+    if (is.eof()) {
+      // A short read at the end of the stream is not an error in Rust. Keep
+      // eofbit for the C++ caller to see, but drop the failbit that came along
+      // with it so the stream can still be sought.
+      is.clear(is.rdstate() & ~std::ios_base::failbit);
+      return true;
+    }
+    return !is.fail();
+  });
+}
+
+bool cxxbridge1$std$istream$seek(std::istream &is, std::int64_t off,
+                                 std::uint8_t whence,
+                                 std::uint64_t *pos) noexcept {
+  return stream_op([&] {
+    is.seekg(static_cast<std::streamoff>(off), seek_dir(whence));
+    std::streampos result = is.tellg();
+    if (is.fail() || result == std::streampos(-1)) {
+      return false;
+    }
+    *pos = static_cast<std::uint64_t>(std::streamoff(result));
+    return true;
+  });
+}
+
+bool cxxbridge1$std$ostream$write(std::ostream &os, const char *buf,
+                                  std::size_t len) noexcept {
+  return stream_op([&] {
+    os.write(buf, static_cast<std::streamsize>(len));
+    return !os.fail();
+  });
+}
+
+bool cxxbridge1$std$ostream$flush(std::ostream &os) noexcept {
+  return stream_op([&] {
+    os.flush();
+    return !os.fail();
+  });
+}
+
+bool cxxbridge1$std$ostream$seek(std::ostream &os, std::int64_t off,
+                                 std::uint8_t whence,
+                                 std::uint64_t *pos) noexcept {
+  return stream_op([&] {
+    os.seekp(static_cast<std::streamoff>(off), seek_dir(whence));
+    std::streampos result = os.tellp();
+    if (os.fail() || result == std::streampos(-1)) {
+      return false;
+    }
+    *pos = static_cast<std::uint64_t>(std::streamoff(result));
+    return true;
+  });
+}
+
+void cxxbridge1$std$istream$with_reader(
+    void *state, RustReadBuf::Read read,
+    void (*body)(void *, std::istream &)) noexcept {
+  RustReadBuf buf(state, read);
+  std::istream is(&buf);
+  body(state, is);
+}
+
+void cxxbridge1$std$ostream$with_writer(
+    void *state, RustWriteBuf::Write write, RustWriteBuf::Flush flush,
+    void (*body)(void *, std::ostream &)) noexcept {
+  RustWriteBuf buf(state, write, flush);
+  std::ostream os(&buf);
+  body(state, os);
+  stream_op([&] {
+    os.flush();
+    return true;
+  });
+}
+} // extern "C"
+
+#define STD_VECTOR_COMMON_OPS(RUST_TYPE, CXX_TYPE)                             \
   std::vector<CXX_TYPE> *cxxbridge1$std$vector$##RUST_TYPE##$new() noexcept {  \
     return new std::vector<CXX_TYPE>();                                        \
   }                                                                            \
@@ -654,14 +1273,16 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
       const std::vector<CXX_TYPE> &s) noexcept {                               \
     return s.capacity();                                                       \
   }                                                                            \
-  CXX_TYPE *cxxbridge1$std$vector$##RUST_TYPE##$get_unchecked(                 \
-      std::vector<CXX_TYPE> *s, std::size_t pos) noexcept {                    \
-    return &(*s)[pos];                                                         \
-  }                                                                            \
   void cxxbridge1$std$vector$##RUST_TYPE##$reserve(                            \
       std::vector<CXX_TYPE> *s, std::size_t new_cap) noexcept {                \
     s->reserve(new_cap);                                                       \
   }                                                                            \
+  void cxxbridge1$std$vector$##RUST_TYPE##$truncate(                           \
+      std::vector<CXX_TYPE> *s, std::size_t len) noexcept {                    \
+    while (s->size() > len) {                                                  \
+      s->pop_back();                                                           \
+    }                                                                          \
+  }                                                                            \
   void cxxbridge1$unique_ptr$std$vector$##RUST_TYPE##$null(                    \
       std::unique_ptr<std::vector<CXX_TYPE>> *ptr) noexcept {                  \
     new (ptr) std::unique_ptr<std::vector<CXX_TYPE>>();                        \
@@ -686,6 +1307,13 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
     ptr->~unique_ptr();                                                        \
   }
 
+#define STD_VECTOR_OPS(RUST_TYPE, CXX_TYPE)                                    \
+  STD_VECTOR_COMMON_OPS(RUST_TYPE, CXX_TYPE)                                   \
+  CXX_TYPE *cxxbridge1$std$vector$##RUST_TYPE##$get_unchecked(                 \
+      std::vector<CXX_TYPE> *s, std::size_t pos) noexcept {                    \
+    return &(*s)[pos];                                                         \
+  }
+
 #define STD_VECTOR_TRIVIAL_OPS(RUST_TYPE, CXX_TYPE)                            \
   void cxxbridge1$std$vector$##RUST_TYPE##$push_back(                          \
       std::vector<CXX_TYPE> *v, CXX_TYPE *value) noexcept {                    \
@@ -698,6 +1326,73 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
     v->pop_back();                                                             \
   }
 
+#define PMR_VECTOR_OPS(RUST_TYPE, CXX_TYPE)                                    \
+  std::pmr::vector<CXX_TYPE> *cxxbridge1$std$pmr$vector$##RUST_TYPE##$new_in(  \
+      std::pmr::memory_resource *resource) noexcept {                          \
+    return new std::pmr::vector<CXX_TYPE>(resource);                           \
+  }                                                                            \
+  std::size_t cxxbridge1$std$pmr$vector$##RUST_TYPE##$size(                    \
+      const std::pmr::vector<CXX_TYPE> &s) noexcept {                          \
+    return s.size();                                                           \
+  }                                                                            \
+  std::size_t cxxbridge1$std$pmr$vector$##RUST_TYPE##$capacity(                \
+      const std::pmr::vector<CXX_TYPE> &s) noexcept {                          \
+    return s.capacity();                                                       \
+  }                                                                            \
+  CXX_TYPE *cxxbridge1$std$pmr$vector$##RUST_TYPE##$get_unchecked(             \
+      std::pmr::vector<CXX_TYPE> *s, std::size_t pos) noexcept {               \
+    return &(*s)[pos];                                                         \
+  }                                                                            \
+  void cxxbridge1$std$pmr$vector$##RUST_TYPE##$reserve(                        \
+      std::pmr::vector<CXX_TYPE> *s, std::size_t new_cap) noexcept {           \
+    s->reserve(new_cap);                                                       \
+  }                                                                            \
+  void cxxbridge1$std$pmr$vector$##RUST_TYPE##$truncate(                       \
+      std::pmr::vector<CXX_TYPE> *s, std::size_t len) noexcept {               \
+    while (s->size() > len) {                                                  \
+      s->pop_back();                                                           \
+    }                                                                          \
+  }                                                                            \
+  std::pmr::memory_resource *cxxbridge1$std$pmr$vector$##RUST_TYPE##$resource( \
+      const std::pmr::vector<CXX_TYPE> &s) noexcept {                          \
+    return s.get_allocator().resource();                                       \
+  }                                                                            \
+  void cxxbridge1$unique_ptr$std$pmr$vector$##RUST_TYPE##$null(                \
+      std::unique_ptr<std::pmr::vector<CXX_TYPE>> *ptr) noexcept {             \
+    new (ptr) std::unique_ptr<std::pmr::vector<CXX_TYPE>>();                   \
+  }                                                                            \
+  void cxxbridge1$unique_ptr$std$pmr$vector$##RUST_TYPE##$raw(                 \
+      std::unique_ptr<std::pmr::vector<CXX_TYPE>> *ptr,                        \
+      std::pmr::vector<CXX_TYPE> *raw) noexcept {                              \
+    new (ptr) std::unique_ptr<std::pmr::vector<CXX_TYPE>>(raw);                \
+  }                                                                            \
+  const std::pmr::vector<CXX_TYPE>                                             \
+      *cxxbridge1$unique_ptr$std$pmr$vector$##RUST_TYPE##$get(                 \
+          const std::unique_ptr<std::pmr::vector<CXX_TYPE>> &ptr) noexcept {   \
+    return ptr.get();                                                          \
+  }                                                                            \
+  std::pmr::vector<CXX_TYPE>                                                   \
+      *cxxbridge1$unique_ptr$std$pmr$vector$##RUST_TYPE##$release(             \
+          std::unique_ptr<std::pmr::vector<CXX_TYPE>> &ptr) noexcept {         \
+    return ptr.release();                                                      \
+  }                                                                            \
+  void cxxbridge1$unique_ptr$std$pmr$vector$##RUST_TYPE##$drop(                \
+      std::unique_ptr<std::pmr::vector<CXX_TYPE>> *ptr) noexcept {             \
+    ptr->~unique_ptr();                                                        \
+  }
+
+#define PMR_VECTOR_TRIVIAL_OPS(RUST_TYPE, CXX_TYPE)                            \
+  void cxxbridge1$std$pmr$vector$##RUST_TYPE##$push_back(                      \
+      std::pmr::vector<CXX_TYPE> *v, CXX_TYPE *value) noexcept {               \
+    v->push_back(std::move(*value));                                           \
+    destroy(value);                                                            \
+  }                                                                            \
+  void cxxbridge1$std$pmr$vector$##RUST_TYPE##$pop_back(                       \
+      std::pmr::vector<CXX_TYPE> *v, CXX_TYPE *out) noexcept {                 \
+    new (out) CXX_TYPE(std::move(v->back()));                                  \
+    v->pop_back();                                                             \
+  }
+
 #define RUST_VEC_EXTERNS(RUST_TYPE, CXX_TYPE)                                  \
   void cxxbridge1$rust_vec$##RUST_TYPE##$new(                                  \
       rust::Vec<CXX_TYPE> *ptr) noexcept;                                      \
@@ -777,6 +1472,10 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
       const std::shared_ptr<CXX_TYPE> &self) noexcept {                        \
     return self.get();                                                         \
   }                                                                            \
+  std::size_t cxxbridge1$std$shared_ptr$##RUST_TYPE##$use_count(               \
+      const std::shared_ptr<CXX_TYPE> &self) noexcept {                        \
+    return static_cast<std::size_t>(self.use_count());                         \
+  }                                                                            \
   void cxxbridge1$std$shared_ptr$##RUST_TYPE##$drop(                           \
       const std::shared_ptr<CXX_TYPE> *self) noexcept {                        \
     self->~shared_ptr();                                                       \
@@ -802,11 +1501,107 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
       std::shared_ptr<CXX_TYPE> *shared) noexcept {                            \
     new (shared) std::shared_ptr<CXX_TYPE>(weak.lock());                       \
   }                                                                            \
+  std::size_t cxxbridge1$std$weak_ptr$##RUST_TYPE##$use_count(                 \
+      const std::weak_ptr<CXX_TYPE> &self) noexcept {                          \
+    return static_cast<std::size_t>(self.use_count());                         \
+  }                                                                            \
   void cxxbridge1$std$weak_ptr$##RUST_TYPE##$drop(                             \
       const std::weak_ptr<CXX_TYPE> *self) noexcept {                          \
     self->~weak_ptr();                                                         \
   }
 
+#define SHARED_PTR_CONST_OPS(RUST_TYPE, CXX_TYPE)                              \
+  static_assert(sizeof(std::shared_ptr<const CXX_TYPE>) == 2 * sizeof(void *), \
+                "");                                                           \
+  static_assert(alignof(std::shared_ptr<const CXX_TYPE>) == alignof(void *),   \
+                "");                                                           \
+  void cxxbridge1$std$shared_ptr$const$##RUST_TYPE##$null(                     \
+      std::shared_ptr<const CXX_TYPE> *ptr) noexcept {                         \
+    new (ptr) std::shared_ptr<const CXX_TYPE>();                               \
+  }                                                                            \
+  void cxxbridge1$std$shared_ptr$const$##RUST_TYPE##$from_mut(                 \
+      std::shared_ptr<CXX_TYPE> *mut,                                          \
+      std::shared_ptr<const CXX_TYPE> *ptr) noexcept {                         \
+    new (ptr) std::shared_ptr<const CXX_TYPE>(std::move(*mut));                \
+    mut->~shared_ptr();                                                        \
+  }                                                                            \
+  void cxxbridge1$std$shared_ptr$const$##RUST_TYPE##$clone(                    \
+      const std::shared_ptr<const CXX_TYPE> &self,                             \
+      std::shared_ptr<const CXX_TYPE> *ptr) noexcept {                         \
+    new (ptr) std::shared_ptr<const CXX_TYPE>(self);                           \
+  }                                                                            \
+  const CXX_TYPE *cxxbridge1$std$shared_ptr$const$##RUST_TYPE##$get(           \
+      const std::shared_ptr<const CXX_TYPE> &self) noexcept {                  \
+    return self.get();                                                         \
+  }                                                                            \
+  std::size_t cxxbridge1$std$shared_ptr$const$##RUST_TYPE##$use_count(         \
+      const std::shared_ptr<const CXX_TYPE> &self) noexcept {                  \
+    return static_cast<std::size_t>(self.use_count());                         \
+  }                                                                            \
+  void cxxbridge1$std$shared_ptr$const$##RUST_TYPE##$drop(                     \
+      const std::shared_ptr<const CXX_TYPE> *self) noexcept {                  \
+    self->~shared_ptr();                                                       \
+  }
+
+#define UNIQUE_PTR_ARRAY_OPS(RUST_TYPE, CXX_TYPE)                              \
+  static_assert(sizeof(std::unique_ptr<CXX_TYPE[]>) == sizeof(void *), "");    \
+  static_assert(alignof(std::unique_ptr<CXX_TYPE[]>) == alignof(void *), "");  \
+  void cxxbridge1$unique_ptr$array$##RUST_TYPE##$null(                         \
+      std::unique_ptr<CXX_TYPE[]> *ptr) noexcept {                             \
+    new (ptr) std::unique_ptr<CXX_TYPE[]>();                                   \
+  }                                                                            \
+  void cxxbridge1$unique_ptr$array$##RUST_TYPE##$raw(                          \
+      std::unique_ptr<CXX_TYPE[]> *ptr, CXX_TYPE *raw) noexcept {              \
+    new (ptr) std::unique_ptr<CXX_TYPE[]>(raw);                                \
+  }                                                                            \
+  const CXX_TYPE *cxxbridge1$unique_ptr$array$##RUST_TYPE##$get(               \
+      const std::unique_ptr<CXX_TYPE[]> &ptr) noexcept {                       \
+    return ptr.get();                                                          \
+  }                                                                            \
+  CXX_TYPE *cxxbridge1$unique_ptr$array$##RUST_TYPE##$release(                 \
+      std::unique_ptr<CXX_TYPE[]> &ptr) noexcept {                             \
+    return ptr.release();                                                      \
+  }                                                                            \
+  void cxxbridge1$unique_ptr$array$##RUST_TYPE##$drop(                         \
+      std::unique_ptr<CXX_TYPE[]> *ptr) noexcept {                             \
+    ptr->~unique_ptr();                                                        \
+  }
+
+#define SHARED_PTR_ARRAY_OPS(RUST_TYPE, CXX_TYPE)                              \
+  static_assert(sizeof(std::shared_ptr<CXX_TYPE[]>) == 2 * sizeof(void *),     \
+                "");                                                           \
+  static_assert(alignof(std::shared_ptr<CXX_TYPE[]>) == alignof(void *), "");  \
+  void cxxbridge1$shared_ptr$array$##RUST_TYPE##$null(                         \
+      std::shared_ptr<CXX_TYPE[]> *ptr) noexcept {                             \
+    new (ptr) std::shared_ptr<CXX_TYPE[]>();                                   \
+  }                                                                            \
+  void cxxbridge1$shared_ptr$array$##RUST_TYPE##$raw(                          \
+      std::shared_ptr<CXX_TYPE[]> *ptr, CXX_TYPE *raw) noexcept {              \
+    new (ptr) std::shared_ptr<CXX_TYPE[]>(raw);                                \
+  }                                                                            \
+  void cxxbridge1$shared_ptr$array$##RUST_TYPE##$clone(                        \
+      const std::shared_ptr<CXX_TYPE[]> &self,                                 \
+      std::shared_ptr<CXX_TYPE[]> *ptr) noexcept {                             \
+    new (ptr) std::shared_ptr<CXX_TYPE[]>(self);                               \
+  }                                                                            \
+  void cxxbridge1$shared_ptr$array$##RUST_TYPE##$alias(                        \
+      const std::shared_ptr<CXX_TYPE[]> &self, std::size_t offset,             \
+      std::shared_ptr<CXX_TYPE[]> *ptr) noexcept {                             \
+    new (ptr) std::shared_ptr<CXX_TYPE[]>(self, self.get() + offset);          \
+  }                                                                            \
+  const CXX_TYPE *cxxbridge1$shared_ptr$array$##RUST_TYPE##$get(               \
+      const std::shared_ptr<CXX_TYPE[]> &self) noexcept {                      \
+    return self.get();                                                         \
+  }                                                                            \
+  std::size_t cxxbridge1$shared_ptr$array$##RUST_TYPE##$use_count(             \
+      const std::shared_ptr<CXX_TYPE[]> &self) noexcept {                      \
+    return static_cast<std::size_t>(self.use_count());                         \
+  }                                                                            \
+  void cxxbridge1$shared_ptr$array$##RUST_TYPE##$drop(                         \
+      const std::shared_ptr<CXX_TYPE[]> *self) noexcept {                      \
+    self->~shared_ptr();                                                       \
+  }
+
 // Usize and isize are the same type as one of the below.
 #define FOR_EACH_NUMERIC(MACRO)                                                \
   MACRO(u8, std::uint8_t)                                                      \
@@ -823,12 +1618,18 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
 #define FOR_EACH_TRIVIAL_STD_VECTOR(MACRO)                                     \
   FOR_EACH_NUMERIC(MACRO)                                                      \
   MACRO(usize, std::size_t)                                                    \
-  MACRO(isize, rust::isize)
+  MACRO(isize, rust::isize)                                                    \
+  MACRO(rust_string, rust::String)
 
 #define FOR_EACH_STD_VECTOR(MACRO)                                             \
   FOR_EACH_TRIVIAL_STD_VECTOR(MACRO)                                           \
   MACRO(string, std::string)
 
+#define FOR_EACH_PMR_VECTOR(MACRO)                                             \
+  FOR_EACH_NUMERIC(MACRO)                                                      \
+  MACRO(usize, std::size_t)                                                    \
+  MACRO(isize, rust::isize)
+
 #define FOR_EACH_RUST_VEC(MACRO)                                               \
   FOR_EACH_NUMERIC(MACRO)                                                      \
   MACRO(bool, bool)                                                            \
@@ -838,6 +1639,12 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
   MACRO(string, rust::String)                                                  \
   MACRO(str, rust::Str)
 
+#define FOR_EACH_PTR_ARRAY(MACRO)                                              \
+  FOR_EACH_NUMERIC(MACRO)                                                      \
+  MACRO(bool, bool)                                                            \
+  MACRO(usize, std::size_t)                                                    \
+  MACRO(isize, rust::isize)
+
 #define FOR_EACH_SHARED_PTR(MACRO)                                             \
   FOR_EACH_NUMERIC(MACRO)                                                      \
   MACRO(bool, bool)                                                            \
@@ -848,8 +1655,47 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
 extern "C" {
 FOR_EACH_STD_VECTOR(STD_VECTOR_OPS)
 FOR_EACH_TRIVIAL_STD_VECTOR(STD_VECTOR_TRIVIAL_OPS)
+
+// std::vector<bool> is bit-packed, so its elements have no address and are
+// accessed by value instead.
+STD_VECTOR_COMMON_OPS(bool, bool)
+bool cxxbridge1$std$vector$bool$get_unchecked(const std::vector<bool> &s,
+                                              std::size_t pos) noexcept {
+  return s[pos];
+}
+void cxxbridge1$std$vector$bool$set_unchecked(std::vector<bool> *s,
+                                              std::size_t pos,
+                                              bool value) noexcept {
+  (*s)[pos] = value;
+}
+void cxxbridge1$std$vector$bool$push_back(std::vector<bool> *v,
+                                          bool value) noexcept {
+  v->push_back(value);
+}
+bool cxxbridge1$std$vector$bool$pop_back(std::vector<bool> *v) noexcept {
+  bool value = v->back();
+  v->pop_back();
+  return value;
+}
+
 FOR_EACH_RUST_VEC(RUST_VEC_EXTERNS)
 FOR_EACH_SHARED_PTR(SHARED_PTR_OPS)
+FOR_EACH_SHARED_PTR(SHARED_PTR_CONST_OPS)
+FOR_EACH_PTR_ARRAY(UNIQUE_PTR_ARRAY_OPS)
+#ifdef __cpp_lib_shared_ptr_arrays
+FOR_EACH_PTR_ARRAY(SHARED_PTR_ARRAY_OPS)
+#endif
+#ifdef __cpp_lib_memory_resource
+FOR_EACH_PMR_VECTOR(PMR_VECTOR_OPS)
+FOR_EACH_PMR_VECTOR(PMR_VECTOR_TRIVIAL_OPS)
+PMR_VECTOR_OPS(string, std::pmr::string)
+// Constructed in place so that the new element uses the vector's resource.
+void cxxbridge1$std$pmr$vector$string$emplace_back(
+    std::pmr::vector<std::pmr::string> *v, const std::uint8_t *ptr,
+    std::size_t len) noexcept {
+  v->emplace_back(reinterpret_cast<const char *>(ptr), len);
+}
+#endif
 } // extern "C"
 
 namespace rust {
@@ -0,0 +1,253 @@
+use crate::lossy;
+use crate::pmr::{self, MemoryResource};
+use crate::unique_ptr::UniquePtr;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::{PhantomData, PhantomPinned};
+use core::pin::Pin;
+use core::slice;
+use core::str::{self, Utf8Error};
+
+extern "C" {
+    #[link_name = "cxxbridge1$cxx_pmr_string$new_in"]
+    fn pmr_string_new_in(resource: *mut MemoryResource) -> *mut CxxPmrString;
+    #[link_name = "cxxbridge1$cxx_pmr_string$data"]
+    fn pmr_string_data(this: &CxxPmrString) -> *const u8;
+    #[link_name = "cxxbridge1$cxx_pmr_string$length"]
+    fn pmr_string_length(this: &CxxPmrString) -> usize;
+    #[link_name = "cxxbridge1$cxx_pmr_string$clear"]
+    fn pmr_string_clear(this: Pin<&mut CxxPmrString>);
+    #[link_name = "cxxbridge1$cxx_pmr_string$reserve_total"]
+    fn pmr_string_reserve_total(this: Pin<&mut CxxPmrString>, new_cap: usize);
+    #[link_name = "cxxbridge1$cxx_pmr_string$push"]
+    fn pmr_string_push(this: Pin<&mut CxxPmrString>, ptr: *const u8, len: usize);
+    #[link_name = "cxxbridge1$cxx_pmr_string$truncate"]
+    fn pmr_string_truncate(this: Pin<&mut CxxPmrString>, len: usize);
+    #[link_name = "cxxbridge1$cxx_pmr_string$resource"]
+    fn pmr_string_resource(this: &CxxPmrString) -> *mut MemoryResource;
+}
+
+/// Binding to C++ `std::pmr::string`.
+///
+/// This is `std::basic_string<char, std::char_traits<char>,
+/// std::pmr::polymorphic_allocator<char>>`, which is a different C++ type
+/// than `std::string` and is not interchangeable with [`CxxString`]. The
+/// string allocates through the memory resource it was constructed with for
+/// its entire lifetime.
+///
+/// # Invariants
+///
+/// Like `CxxString`, in Rust code we can never obtain a `CxxPmrString` by
+/// value, only behind a reference or smart pointer, as in `&CxxPmrString` or
+/// `UniquePtr<CxxPmrString>`.
+///
+/// [`CxxString`]: crate::CxxString
+#[repr(C)]
+pub struct CxxPmrString {
+    _private: [u8; 0],
+    _pinned: PhantomData<PhantomPinned>,
+}
+
+impl CxxPmrString {
+    /// Constructs a new heap allocated empty string, wrapped by UniquePtr.
+    ///
+    /// The string allocates from the default memory resource, as returned by
+    /// [`pmr::get_default_resource`][pmr::get_default_resource] at the time of
+    /// this call.
+    pub fn new() -> UniquePtr<Self> {
+        unsafe { Self::new_in(pmr::get_default_resource()) }
+    }
+
+    /// Constructs a new heap allocated empty string which allocates from the
+    /// given memory resource, wrapped by UniquePtr.
+    ///
+    /// # Safety
+    ///
+    /// `resource` must point to a valid `std::pmr::memory_resource` which
+    /// outlives the string. The string object itself is allocated with global
+    /// `operator new`, not from `resource`.
+    pub unsafe fn new_in(resource: *mut MemoryResource) -> UniquePtr<Self> {
+        unsafe { UniquePtr::from_raw(pmr_string_new_in(resource)) }
+    }
+
+    /// Returns the length of the string in bytes.
+    ///
+    /// Matches the behavior of C++ [std::pmr::string::size][size].
+    ///
+    /// [size]: https://en.cppreference.com/w/cpp/string/basic_string/size
+    pub fn len(&self) -> usize {
+        unsafe { pmr_string_length(self) }
+    }
+
+    /// Returns true if `self` has a length of zero bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a byte slice of this string's contents.
+    pub fn as_bytes(&self) -> &[u8] {
+        let data = self.as_ptr();
+        let len = self.len();
+        unsafe { slice::from_raw_parts(data, len) }
+    }
+
+    /// Produces a pointer to the first character of the string.
+    ///
+    /// The same caveats apply as to [`CxxString::as_ptr`].
+    ///
+    /// [`CxxString::as_ptr`]: crate::CxxString::as_ptr
+    pub fn as_ptr(&self) -> *const u8 {
+        unsafe { pmr_string_data(self) }
+    }
+
+    /// Validates that the C++ string contains UTF-8 data and produces a view of
+    /// it as a Rust &amp;str, otherwise an error.
+    pub fn to_str(&self) -> Result<&str, Utf8Error> {
+        str::from_utf8(self.as_bytes())
+    }
+
+    /// If the contents of the C++ string are valid UTF-8, this function returns
+    /// a view as a Cow::Borrowed &amp;str. Otherwise replaces any invalid UTF-8
+    /// sequences with the U+FFFD [replacement character] and returns a
+    /// Cow::Owned String.
+    ///
+    /// [replacement character]: char::REPLACEMENT_CHARACTER
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_string_lossy(&self) -> Cow<str> {
+        String::from_utf8_lossy(self.as_bytes())
+    }
+
+    /// Removes all characters from the string.
+    ///
+    /// Matches the behavior of C++ [std::pmr::string::clear][clear].
+    ///
+    /// [clear]: https://en.cppreference.com/w/cpp/string/basic_string/clear
+    pub fn clear(self: Pin<&mut Self>) {
+        unsafe { pmr_string_clear(self) }
+    }
+
+    /// Ensures that this string's capacity is at least `additional` bytes
+    /// larger than its length.
+    ///
+    /// As with [`CxxString::reserve`], the argument is the additional
+    /// capacity, not the total capacity as in C++.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows usize.
+    ///
+    /// [`CxxString::reserve`]: crate::CxxString::reserve
+    pub fn reserve(self: Pin<&mut Self>, additional: usize) {
+        let new_cap = self
+            .len()
+            .checked_add(additional)
+            .expect("CxxPmrString capacity overflow");
+        unsafe { pmr_string_reserve_total(self, new_cap) }
+    }
+
+    /// Appends a given string slice onto the end of this C++ string.
+    pub fn push_str(self: Pin<&mut Self>, s: &str) {
+        self.push_bytes(s.as_bytes());
+    }
+
+    /// Appends arbitrary bytes onto the end of this C++ string.
+    pub fn push_bytes(self: Pin<&mut Self>, bytes: &[u8]) {
+        unsafe { pmr_string_push(self, bytes.as_ptr(), bytes.len()) }
+    }
+
+    /// Shortens this string to the specified length in bytes.
+    ///
+    /// If `new_len` is greater than or equal to the string's current length,
+    /// this has no effect. The new length does not need to lie on a char
+    /// boundary.
+    pub fn truncate(self: Pin<&mut Self>, new_len: usize) {
+        if new_len < self.len() {
+            unsafe { pmr_string_truncate(self, new_len) }
+        }
+    }
+
+    /// Returns the memory resource from which this string allocates.
+    ///
+    /// Matches the behavior of C++
+    /// `std::pmr::string::get_allocator().resource()`.
+    pub fn memory_resource(&self) -> *mut MemoryResource {
+        unsafe { pmr_string_resource(self) }
+    }
+}
+
+impl Display for CxxPmrString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        lossy::display(self.as_bytes(), f)
+    }
+}
+
+impl Debug for CxxPmrString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        lossy::debug(self.as_bytes(), f)
+    }
+}
+
+// Like std::pmr::string's operator==, equality compares contents only and not
+// the memory resource.
+impl PartialEq for CxxPmrString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<str> for CxxPmrString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<CxxPmrString> for str {
+    fn eq(&self, other: &CxxPmrString) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<[u8]> for CxxPmrString {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
+    }
+}
+
+impl Eq for CxxPmrString {}
+
+impl PartialOrd for CxxPmrString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CxxPmrString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl Hash for CxxPmrString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl AsRef<[u8]> for CxxPmrString {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Write for Pin<&mut CxxPmrString> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.as_mut().push_str(s);
+        Ok(())
+    }
+}
@@ -0,0 +1,651 @@
+use crate::cxx_pmr_string::CxxPmrString;
+use crate::extern_type::ExternType;
+use crate::kind::Trivial;
+use crate::pmr::{self, MemoryResource};
+use crate::unique_ptr::UniquePtr;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::marker::{PhantomData, PhantomPinned};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::pin::Pin;
+use core::slice;
+
+/// Binding to C++ `std::pmr::vector<T>`.
+///
+/// This is `std::vector<T, std::pmr::polymorphic_allocator<T>>`, which is a
+/// different C++ type than `std::vector<T>` and is not interchangeable with
+/// [`CxxVector`]. The vector allocates through the memory resource it was
+/// constructed with for its entire lifetime, and element types which are
+/// themselves allocator aware, such as [`CxxPmrString`], are constructed in
+/// place using the same resource.
+///
+/// # Invariants
+///
+/// Like `CxxVector`, in Rust code we can never obtain a `CxxPmrVector` by
+/// value, only behind a reference or smart pointer, as in `&CxxPmrVector<T>`
+/// or `UniquePtr<CxxPmrVector<T>>`.
+///
+/// [`CxxVector`]: crate::CxxVector
+#[repr(C, packed)]
+pub struct CxxPmrVector<T> {
+    // A thing, because repr(C) structs are not allowed to consist exclusively
+    // of PhantomData fields.
+    _void: [c_void; 0],
+    // The conceptual vector elements to ensure that autotraits are propagated
+    // correctly, e.g. CxxPmrVector is UnwindSafe iff T is.
+    _elements: PhantomData<[T]>,
+    // Prevent unpin operation from Pin<&mut CxxPmrVector<T>> to
+    // &mut CxxPmrVector<T>.
+    _pinned: PhantomData<PhantomPinned>,
+}
+
+impl<T> CxxPmrVector<T>
+where
+    T: PmrVectorElement,
+{
+    /// Constructs a new heap allocated vector, wrapped by UniquePtr.
+    ///
+    /// The vector allocates from the default memory resource, as returned by
+    /// [`pmr::get_default_resource`][pmr::get_default_resource] at the time of
+    /// this call.
+    pub fn new() -> UniquePtr<Self> {
+        unsafe { Self::new_in(pmr::get_default_resource()) }
+    }
+
+    /// Constructs a new heap allocated empty vector which allocates from the
+    /// given memory resource, wrapped by UniquePtr.
+    ///
+    /// # Safety
+    ///
+    /// `resource` must point to a valid `std::pmr::memory_resource` which
+    /// outlives the vector. The vector object itself is allocated with global
+    /// `operator new`, not from `resource`.
+    pub unsafe fn new_in(resource: *mut MemoryResource) -> UniquePtr<Self> {
+        unsafe { UniquePtr::from_raw(T::__vector_new_in(resource)) }
+    }
+
+    /// Returns the number of elements in the vector.
+    pub fn len(&self) -> usize {
+        T::__vector_size(self)
+    }
+
+    /// Returns the capacity of the vector.
+    pub fn capacity(&self) -> usize {
+        T::__vector_capacity(self)
+    }
+
+    /// Returns true if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to an element at the given position, or `None` if
+    /// out of bounds.
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        if pos < self.len() {
+            Some(unsafe { self.get_unchecked(pos) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a pinned mutable reference to an element at the given position,
+    /// or `None` if out of bounds.
+    #[doc(alias = "get_mut")]
+    pub fn index_mut(self: Pin<&mut Self>, pos: usize) -> Option<Pin<&mut T>> {
+        if pos < self.len() {
+            Some(unsafe { self.index_unchecked_mut(pos) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to an element without doing bounds checking.
+    ///
+    /// Calling this method with an out-of-bounds index is undefined behavior
+    /// even if the resulting reference is not used.
+    pub unsafe fn get_unchecked(&self, pos: usize) -> &T {
+        let this = self as *const CxxPmrVector<T> as *mut CxxPmrVector<T>;
+        unsafe {
+            let ptr = T::__get_unchecked(this, pos) as *const T;
+            &*ptr
+        }
+    }
+
+    /// Returns a pinned mutable reference to an element without doing bounds
+    /// checking.
+    ///
+    /// Calling this method with an out-of-bounds index is undefined behavior
+    /// even if the resulting reference is not used.
+    #[doc(alias = "get_unchecked_mut")]
+    pub unsafe fn index_unchecked_mut(self: Pin<&mut Self>, pos: usize) -> Pin<&mut T> {
+        unsafe {
+            let ptr = T::__get_unchecked(self.get_unchecked_mut(), pos);
+            Pin::new_unchecked(&mut *ptr)
+        }
+    }
+
+    /// Returns a slice to the underlying contiguous array of elements.
+    pub fn as_slice(&self) -> &[T]
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        let len = self.len();
+        if len == 0 {
+            // See CxxVector::as_slice for why data() of an empty vector cannot
+            // be used here.
+            &[]
+        } else {
+            let this = self as *const CxxPmrVector<T> as *mut CxxPmrVector<T>;
+            let ptr = unsafe { T::__get_unchecked(this, 0) };
+            unsafe { slice::from_raw_parts(ptr, len) }
+        }
+    }
+
+    /// Returns a slice to the underlying contiguous array of elements by
+    /// mutable reference.
+    pub fn as_mut_slice(self: Pin<&mut Self>) -> &mut [T]
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        let len = self.len();
+        if len == 0 {
+            &mut []
+        } else {
+            let ptr = unsafe { T::__get_unchecked(self.get_unchecked_mut(), 0) };
+            unsafe { slice::from_raw_parts_mut(ptr, len) }
+        }
+    }
+
+    /// Returns an iterator over elements of type `&T`.
+    pub fn iter(&self) -> Iter<T> {
+        Iter {
+            v: self,
+            index: 0,
+            end: self.len(),
+        }
+    }
+
+    /// Returns an iterator over elements of type `Pin<&mut T>`.
+    pub fn iter_mut(self: Pin<&mut Self>) -> IterMut<T> {
+        let end = self.len();
+        IterMut {
+            v: self,
+            index: 0,
+            end,
+        }
+    }
+
+    /// Appends an element to the back of the vector.
+    pub fn push(self: Pin<&mut Self>, value: T)
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        let mut value = ManuallyDrop::new(value);
+        unsafe {
+            // C++ calls move constructor followed by destructor on `value`.
+            T::__push_back(self, &mut value);
+        }
+    }
+
+    /// Removes the last element from a vector and returns it, or `None` if the
+    /// vector is empty.
+    pub fn pop(self: Pin<&mut Self>) -> Option<T>
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        if self.is_empty() {
+            None
+        } else {
+            let mut value = MaybeUninit::uninit();
+            Some(unsafe {
+                T::__pop_back(self, &mut value);
+                value.assume_init()
+            })
+        }
+    }
+
+    /// Ensures that this vector's capacity is at least `additional` elements
+    /// larger than its length.
+    ///
+    /// As with [`CxxVector::reserve`], the argument is the additional
+    /// capacity, not the total capacity as in C++.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows usize.
+    ///
+    /// [`CxxVector::reserve`]: crate::CxxVector::reserve
+    pub fn reserve(self: Pin<&mut Self>, additional: usize) {
+        let new_cap = self
+            .len()
+            .checked_add(additional)
+            .expect("CxxPmrVector capacity overflow");
+        unsafe { T::__reserve(self, new_cap) }
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and destroying
+    /// the rest.
+    ///
+    /// If `len` is greater than or equal to the vector's current length, this
+    /// has no effect. The capacity of the vector is unchanged.
+    pub fn truncate(self: Pin<&mut Self>, len: usize) {
+        unsafe { T::__truncate(self, len) }
+    }
+
+    /// Destroys all elements of the vector, leaving it empty.
+    pub fn clear(self: Pin<&mut Self>) {
+        self.truncate(0);
+    }
+
+    /// Returns the memory resource from which this vector allocates.
+    ///
+    /// Matches the behavior of C++
+    /// `std::pmr::vector<T>::get_allocator().resource()`.
+    pub fn memory_resource(&self) -> *mut MemoryResource {
+        T::__vector_resource(self)
+    }
+}
+
+extern "C" {
+    #[link_name = "cxxbridge1$std$pmr$vector$string$emplace_back"]
+    fn pmr_vector_string_emplace_back(
+        this: Pin<&mut CxxPmrVector<CxxPmrString>>,
+        ptr: *const u8,
+        len: usize,
+    );
+}
+
+impl CxxPmrVector<CxxPmrString> {
+    /// Appends a new string holding a copy of the given string slice.
+    ///
+    /// The new element is constructed in place using the vector's memory
+    /// resource, as by C++ `emplace_back`.
+    pub fn push_str(self: Pin<&mut Self>, s: &str) {
+        self.push_bytes(s.as_bytes());
+    }
+
+    /// Appends a new string holding a copy of the given bytes.
+    ///
+    /// The new element is constructed in place using the vector's memory
+    /// resource, as by C++ `emplace_back`.
+    pub fn push_bytes(self: Pin<&mut Self>, bytes: &[u8]) {
+        unsafe { pmr_vector_string_emplace_back(self, bytes.as_ptr(), bytes.len()) }
+    }
+}
+
+impl<T> Extend<T> for Pin<&mut CxxPmrVector<T>>
+where
+    T: ExternType<Kind = Trivial> + PmrVectorElement,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        self.as_mut().reserve(iter.size_hint().0);
+        for element in iter {
+            self.as_mut().push(element);
+        }
+    }
+}
+
+/// Iterator over elements of a `CxxPmrVector` by shared reference.
+///
+/// The iterator element type is `&'a T`.
+pub struct Iter<'a, T> {
+    v: &'a CxxPmrVector<T>,
+    index: usize,
+    end: usize,
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            v: self.v,
+            index: self.index,
+            end: self.end,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CxxPmrVector<T>
+where
+    T: PmrVectorElement,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PmrVectorElement,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        let next = unsafe { self.v.get_unchecked(self.index) };
+        self.index += 1;
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: PmrVectorElement,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(unsafe { self.v.get_unchecked(self.end) })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T>
+where
+    T: PmrVectorElement,
+{
+    fn len(&self) -> usize {
+        self.end - self.index
+    }
+}
+
+impl<'a, T> FusedIterator for Iter<'a, T> where T: PmrVectorElement {}
+
+/// Iterator over elements of a `CxxPmrVector` by pinned mutable reference.
+///
+/// The iterator element type is `Pin<&'a mut T>`.
+pub struct IterMut<'a, T> {
+    v: Pin<&'a mut CxxPmrVector<T>>,
+    index: usize,
+    end: usize,
+}
+
+impl<'a, T> IntoIterator for Pin<&'a mut CxxPmrVector<T>>
+where
+    T: PmrVectorElement,
+{
+    type Item = Pin<&'a mut T>;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T>
+where
+    T: PmrVectorElement,
+{
+    type Item = Pin<&'a mut T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        let next = unsafe { self.v.as_mut().index_unchecked_mut(self.index) };
+        self.index += 1;
+        // Extend lifetime to allow simultaneous holding of nonoverlapping
+        // elements, analogous to slice::split_first_mut.
+        unsafe {
+            let ptr = Pin::into_inner_unchecked(next) as *mut T;
+            Some(Pin::new_unchecked(&mut *ptr))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T>
+where
+    T: PmrVectorElement,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        self.end -= 1;
+        let next = unsafe { self.v.as_mut().index_unchecked_mut(self.end) };
+        // Extend lifetime to allow simultaneous holding of nonoverlapping
+        // elements, analogous to slice::split_last_mut.
+        unsafe {
+            let ptr = Pin::into_inner_unchecked(next) as *mut T;
+            Some(Pin::new_unchecked(&mut *ptr))
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T>
+where
+    T: PmrVectorElement,
+{
+    fn len(&self) -> usize {
+        self.end - self.index
+    }
+}
+
+impl<'a, T> FusedIterator for IterMut<'a, T> where T: PmrVectorElement {}
+
+impl<T> Debug for CxxPmrVector<T>
+where
+    T: PmrVectorElement + Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_list().entries(self).finish()
+    }
+}
+
+/// Trait bound for types which may be used as the `T` inside of a
+/// `CxxPmrVector<T>` in generic code.
+///
+/// This trait has no publicly callable or implementable methods. Implementing
+/// it outside of the CXX codebase requires using [explicit shim trait impls],
+/// adding the line `impl CxxPmrVector<MyType> {}` in the same `cxx::bridge`
+/// that defines `MyType`.
+///
+/// [explicit shim trait impls]: https://cxx.rs/extern-c++.html#explicit-shim-trait-impls
+pub unsafe trait PmrVectorElement: Sized {
+    #[doc(hidden)]
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result;
+    #[doc(hidden)]
+    unsafe fn __vector_new_in(resource: *mut MemoryResource) -> *mut CxxPmrVector<Self>;
+    #[doc(hidden)]
+    fn __vector_size(v: &CxxPmrVector<Self>) -> usize;
+    #[doc(hidden)]
+    fn __vector_capacity(v: &CxxPmrVector<Self>) -> usize;
+    #[doc(hidden)]
+    fn __vector_resource(v: &CxxPmrVector<Self>) -> *mut MemoryResource;
+    #[doc(hidden)]
+    unsafe fn __get_unchecked(v: *mut CxxPmrVector<Self>, pos: usize) -> *mut Self;
+    #[doc(hidden)]
+    unsafe fn __reserve(v: Pin<&mut CxxPmrVector<Self>>, new_cap: usize);
+    #[doc(hidden)]
+    unsafe fn __truncate(v: Pin<&mut CxxPmrVector<Self>>, len: usize);
+    #[doc(hidden)]
+    unsafe fn __push_back(v: Pin<&mut CxxPmrVector<Self>>, value: &mut ManuallyDrop<Self>) {
+        // Opaque C type vector elements do not get this method because they can
+        // never exist by value on the Rust side of the bridge.
+        let _ = v;
+        let _ = value;
+        unreachable!()
+    }
+    #[doc(hidden)]
+    unsafe fn __pop_back(v: Pin<&mut CxxPmrVector<Self>>, out: &mut MaybeUninit<Self>) {
+        // Opaque C type vector elements do not get this method because they can
+        // never exist by value on the Rust side of the bridge.
+        let _ = v;
+        let _ = out;
+        unreachable!()
+    }
+    #[doc(hidden)]
+    fn __unique_ptr_null() -> MaybeUninit<*mut c_void>;
+    #[doc(hidden)]
+    unsafe fn __unique_ptr_raw(raw: *mut CxxPmrVector<Self>) -> MaybeUninit<*mut c_void>;
+    #[doc(hidden)]
+    unsafe fn __unique_ptr_get(repr: MaybeUninit<*mut c_void>) -> *const CxxPmrVector<Self>;
+    #[doc(hidden)]
+    unsafe fn __unique_ptr_release(repr: MaybeUninit<*mut c_void>) -> *mut CxxPmrVector<Self>;
+    #[doc(hidden)]
+    unsafe fn __unique_ptr_drop(repr: MaybeUninit<*mut c_void>);
+}
+
+macro_rules! pmr_vector_element_by_value_methods {
+    (opaque, $segment:expr, $ty:ty) => {};
+    (trivial, $segment:expr, $ty:ty) => {
+        unsafe fn __push_back(v: Pin<&mut CxxPmrVector<$ty>>, value: &mut ManuallyDrop<$ty>) {
+            extern "C" {
+                #[link_name = concat!("cxxbridge1$std$pmr$vector$", $segment, "$push_back")]
+                fn __push_back(_: Pin<&mut CxxPmrVector<$ty>>, _: *mut c_void);
+            }
+            unsafe { __push_back(v, value as *mut ManuallyDrop<$ty> as *mut c_void) }
+        }
+        unsafe fn __pop_back(v: Pin<&mut CxxPmrVector<$ty>>, out: &mut MaybeUninit<$ty>) {
+            extern "C" {
+                #[link_name = concat!("cxxbridge1$std$pmr$vector$", $segment, "$pop_back")]
+                fn __pop_back(_: Pin<&mut CxxPmrVector<$ty>>, _: *mut c_void);
+            }
+            unsafe { __pop_back(v, out as *mut MaybeUninit<$ty> as *mut c_void) }
+        }
+    };
+}
+
+macro_rules! impl_pmr_vector_element {
+    ($kind:ident, $segment:expr, $name:expr, $ty:ty) => {
+        const_assert_eq!(0, mem::size_of::<CxxPmrVector<$ty>>());
+        const_assert_eq!(1, mem::align_of::<CxxPmrVector<$ty>>());
+
+        unsafe impl PmrVectorElement for $ty {
+            fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str($name)
+            }
+            unsafe fn __vector_new_in(resource: *mut MemoryResource) -> *mut CxxPmrVector<Self> {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$pmr$vector$", $segment, "$new_in")]
+                    fn __vector_new_in(_: *mut MemoryResource) -> *mut CxxPmrVector<$ty>;
+                }
+                unsafe { __vector_new_in(resource) }
+            }
+            fn __vector_size(v: &CxxPmrVector<$ty>) -> usize {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$pmr$vector$", $segment, "$size")]
+                    fn __vector_size(_: &CxxPmrVector<$ty>) -> usize;
+                }
+                unsafe { __vector_size(v) }
+            }
+            fn __vector_capacity(v: &CxxPmrVector<$ty>) -> usize {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$pmr$vector$", $segment, "$capacity")]
+                    fn __vector_capacity(_: &CxxPmrVector<$ty>) -> usize;
+                }
+                unsafe { __vector_capacity(v) }
+            }
+            fn __vector_resource(v: &CxxPmrVector<$ty>) -> *mut MemoryResource {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$pmr$vector$", $segment, "$resource")]
+                    fn __vector_resource(_: &CxxPmrVector<$ty>) -> *mut MemoryResource;
+                }
+                unsafe { __vector_resource(v) }
+            }
+            unsafe fn __get_unchecked(v: *mut CxxPmrVector<$ty>, pos: usize) -> *mut $ty {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$pmr$vector$", $segment, "$get_unchecked")]
+                    fn __get_unchecked(_: *mut CxxPmrVector<$ty>, _: usize) -> *mut c_void;
+                }
+                unsafe { __get_unchecked(v, pos) as *mut $ty }
+            }
+            unsafe fn __reserve(v: Pin<&mut CxxPmrVector<$ty>>, new_cap: usize) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$pmr$vector$", $segment, "$reserve")]
+                    fn __reserve(_: Pin<&mut CxxPmrVector<$ty>>, _: usize);
+                }
+                unsafe { __reserve(v, new_cap) }
+            }
+            unsafe fn __truncate(v: Pin<&mut CxxPmrVector<$ty>>, len: usize) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$pmr$vector$", $segment, "$truncate")]
+                    fn __truncate(_: Pin<&mut CxxPmrVector<$ty>>, _: usize);
+                }
+                unsafe { __truncate(v, len) }
+            }
+            pmr_vector_element_by_value_methods!($kind, $segment, $ty);
+            fn __unique_ptr_null() -> MaybeUninit<*mut c_void> {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$unique_ptr$std$pmr$vector$", $segment, "$null")]
+                    fn __unique_ptr_null(this: *mut MaybeUninit<*mut c_void>);
+                }
+                let mut repr = MaybeUninit::uninit();
+                unsafe { __unique_ptr_null(&mut repr) }
+                repr
+            }
+            unsafe fn __unique_ptr_raw(raw: *mut CxxPmrVector<Self>) -> MaybeUninit<*mut c_void> {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$unique_ptr$std$pmr$vector$", $segment, "$raw")]
+                    fn __unique_ptr_raw(this: *mut MaybeUninit<*mut c_void>, raw: *mut CxxPmrVector<$ty>);
+                }
+                let mut repr = MaybeUninit::uninit();
+                unsafe { __unique_ptr_raw(&mut repr, raw) }
+                repr
+            }
+            unsafe fn __unique_ptr_get(repr: MaybeUninit<*mut c_void>) -> *const CxxPmrVector<Self> {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$unique_ptr$std$pmr$vector$", $segment, "$get")]
+                    fn __unique_ptr_get(this: *const MaybeUninit<*mut c_void>) -> *const CxxPmrVector<$ty>;
+                }
+                unsafe { __unique_ptr_get(&repr) }
+            }
+            unsafe fn __unique_ptr_release(mut repr: MaybeUninit<*mut c_void>) -> *mut CxxPmrVector<Self> {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$unique_ptr$std$pmr$vector$", $segment, "$release")]
+                    fn __unique_ptr_release(this: *mut MaybeUninit<*mut c_void>) -> *mut CxxPmrVector<$ty>;
+                }
+                unsafe { __unique_ptr_release(&mut repr) }
+            }
+            unsafe fn __unique_ptr_drop(mut repr: MaybeUninit<*mut c_void>) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$unique_ptr$std$pmr$vector$", $segment, "$drop")]
+                    fn __unique_ptr_drop(this: *mut MaybeUninit<*mut c_void>);
+                }
+                unsafe { __unique_ptr_drop(&mut repr) }
+            }
+        }
+    };
+}
+
+macro_rules! impl_pmr_vector_element_for_primitive {
+    ($ty:ident) => {
+        impl_pmr_vector_element!(trivial, stringify!($ty), stringify!($ty), $ty);
+    };
+}
+
+impl_pmr_vector_element_for_primitive!(u8);
+impl_pmr_vector_element_for_primitive!(u16);
+impl_pmr_vector_element_for_primitive!(u32);
+impl_pmr_vector_element_for_primitive!(u64);
+impl_pmr_vector_element_for_primitive!(usize);
+impl_pmr_vector_element_for_primitive!(i8);
+impl_pmr_vector_element_for_primitive!(i16);
+impl_pmr_vector_element_for_primitive!(i32);
+impl_pmr_vector_element_for_primitive!(i64);
+impl_pmr_vector_element_for_primitive!(isize);
+impl_pmr_vector_element_for_primitive!(f32);
+impl_pmr_vector_element_for_primitive!(f64);
+
+impl_pmr_vector_element!(opaque, "string", "CxxPmrString", CxxPmrString);
@@ -0,0 +1,447 @@
+use alloc::boxed::Box;
+use core::any::Any;
+use core::ffi::c_void;
+use core::marker::{PhantomData, PhantomPinned};
+use core::panic::AssertUnwindSafe;
+use core::pin::Pin;
+use core::slice;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::panic;
+
+extern "C" {
+    #[link_name = "cxxbridge1$std$istream$read"]
+    fn istream_read(this: Pin<&mut CxxIstream>, ptr: *mut u8, len: usize, n: &mut usize) -> bool;
+    #[link_name = "cxxbridge1$std$istream$seek"]
+    fn istream_seek(this: Pin<&mut CxxIstream>, off: i64, whence: u8, pos: &mut u64) -> bool;
+    #[link_name = "cxxbridge1$std$ostream$write"]
+    fn ostream_write(this: Pin<&mut CxxOstream>, ptr: *const u8, len: usize) -> bool;
+    #[link_name = "cxxbridge1$std$ostream$flush"]
+    fn ostream_flush(this: Pin<&mut CxxOstream>) -> bool;
+    #[link_name = "cxxbridge1$std$ostream$seek"]
+    fn ostream_seek(this: Pin<&mut CxxOstream>, off: i64, whence: u8, pos: &mut u64) -> bool;
+    #[link_name = "cxxbridge1$std$istream$with_reader"]
+    fn istream_with_reader(
+        state: *mut c_void,
+        read: unsafe extern "C" fn(*mut c_void, *mut u8, usize, &mut usize) -> bool,
+        body: unsafe extern "C" fn(*mut c_void, Pin<&mut CxxIstream>),
+    );
+    #[link_name = "cxxbridge1$std$ostream$with_writer"]
+    fn ostream_with_writer(
+        state: *mut c_void,
+        write: unsafe extern "C" fn(*mut c_void, *const u8, usize) -> bool,
+        flush: unsafe extern "C" fn(*mut c_void) -> bool,
+        body: unsafe extern "C" fn(*mut c_void, Pin<&mut CxxOstream>),
+    );
+}
+
+/// Binding to C++ `std::istream`.
+///
+/// A C++ input stream is received from C++ by declaring it in the bridge as an
+/// extern type alias, after which `Pin<&mut istream>` implements
+/// `std::io::Read` and `std::io::Seek` on top of the stream's unformatted
+/// input functions.
+///
+/// ```
+/// # mod example {
+/// use std::io::{self, Read};
+/// use std::pin::Pin;
+///
+/// #[cxx::bridge]
+/// mod ffi {
+///     #[namespace = "std"]
+///     extern "C++" {
+///         include!(<istream>);
+///
+///         type istream = cxx::CxxIstream;
+///     }
+///
+///     extern "Rust" {
+///         fn count_lines(input: Pin<&mut istream>) -> Result<usize>;
+///     }
+/// }
+///
+/// fn count_lines(mut input: Pin<&mut ffi::istream>) -> io::Result<usize> {
+///     let mut contents = String::new();
+///     input.read_to_string(&mut contents)?;
+///     Ok(contents.lines().count())
+/// }
+/// # }
+/// ```
+///
+/// Reaching the end of the stream sets its `eofbit`, as it would in C++, but
+/// not `failbit`, so that the stream can still be repositioned with `seek`.
+/// Any other failure, including an exception thrown under the stream's
+/// exception mask, is reported as an `io::Error` and leaves the stream in the
+/// failed state that C++ put it in.
+///
+/// # Invariants
+///
+/// As with [`CxxString`][crate::CxxString], Rust code can never obtain a
+/// `CxxIstream` by value, only by reference or smart pointer.
+#[repr(C)]
+pub struct CxxIstream {
+    _private: [u8; 0],
+    _pinned: PhantomData<PhantomPinned>,
+}
+
+/// Binding to C++ `std::ostream`.
+///
+/// A C++ output stream is received from C++ by declaring it in the bridge as
+/// an extern type alias, after which `Pin<&mut ostream>` implements
+/// `std::io::Write` and `std::io::Seek` on top of the stream's unformatted
+/// output functions.
+///
+/// ```
+/// # mod example {
+/// use std::io::{self, Write};
+/// use std::pin::Pin;
+///
+/// #[cxx::bridge]
+/// mod ffi {
+///     #[namespace = "std"]
+///     extern "C++" {
+///         include!(<ostream>);
+///
+///         type ostream = cxx::CxxOstream;
+///     }
+///
+///     extern "Rust" {
+///         fn write_report(output: Pin<&mut ostream>) -> Result<()>;
+///     }
+/// }
+///
+/// fn write_report(mut output: Pin<&mut ffi::ostream>) -> io::Result<()> {
+///     writeln!(output, "all systems nominal")?;
+///     output.flush()
+/// }
+/// # }
+/// ```
+///
+/// Write failures, including an exception thrown under the stream's exception
+/// mask, are reported as an `io::Error` and leave the stream in the failed
+/// state that C++ put it in.
+///
+/// # Invariants
+///
+/// As with [`CxxString`][crate::CxxString], Rust code can never obtain a
+/// `CxxOstream` by value, only by reference or smart pointer.
+#[repr(C)]
+pub struct CxxOstream {
+    _private: [u8; 0],
+    _pinned: PhantomData<PhantomPinned>,
+}
+
+fn stream_error(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, message)
+}
+
+// Splits a SeekFrom into the offset and std::ios_base::seekdir understood by
+// the C++ side, which describes both by a signed std::streamoff.
+fn seek_args(pos: SeekFrom) -> io::Result<(i64, u8)> {
+    match pos {
+        SeekFrom::Start(offset) => match i64::try_from(offset) {
+            Ok(offset) => Ok((offset, 0)),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "seek offset out of range for std::streamoff",
+            )),
+        },
+        SeekFrom::Current(offset) => Ok((offset, 1)),
+        SeekFrom::End(offset) => Ok((offset, 2)),
+    }
+}
+
+impl Read for Pin<&mut CxxIstream> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut n = 0;
+        if unsafe { istream_read(self.as_mut(), buf.as_mut_ptr(), buf.len(), &mut n) } {
+            Ok(n)
+        } else {
+            Err(stream_error("std::istream read failed"))
+        }
+    }
+}
+
+impl Seek for Pin<&mut CxxIstream> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (off, whence) = seek_args(pos)?;
+        let mut pos = 0;
+        if unsafe { istream_seek(self.as_mut(), off, whence, &mut pos) } {
+            Ok(pos)
+        } else {
+            Err(stream_error("std::istream seek failed"))
+        }
+    }
+}
+
+impl Write for Pin<&mut CxxOstream> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if unsafe { ostream_write(self.as_mut(), buf.as_ptr(), buf.len()) } {
+            Ok(buf.len())
+        } else {
+            Err(stream_error("std::ostream write failed"))
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if unsafe { ostream_flush(self.as_mut()) } {
+            Ok(())
+        } else {
+            Err(stream_error("std::ostream flush failed"))
+        }
+    }
+}
+
+impl Seek for Pin<&mut CxxOstream> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (off, whence) = seek_args(pos)?;
+        let mut pos = 0;
+        if unsafe { ostream_seek(self.as_mut(), off, whence, &mut pos) } {
+            Ok(pos)
+        } else {
+            Err(stream_error("std::ostream seek failed"))
+        }
+    }
+}
+
+impl CxxIstream {
+    /// Presents a Rust reader to C++ as a `std::istream` for the duration of
+    /// the closure `f`.
+    ///
+    /// The stream is backed by a `std::streambuf` which pulls data from
+    /// `reader` in chunks as the C++ code consumes it, so a large input can be
+    /// passed to C++ parsing code without being read into memory up front.
+    /// Because of the chunking, the reader is usually left some way past the
+    /// point at which C++ stopped consuming.
+    ///
+    /// ```
+    /// # mod example {
+    /// use cxx::CxxIstream;
+    /// use std::error::Error;
+    /// use std::fs::File;
+    /// use std::io;
+    ///
+    /// #[cxx::bridge]
+    /// mod ffi {
+    ///     #[namespace = "std"]
+    ///     extern "C++" {
+    ///         include!(<istream>);
+    ///
+    ///         type istream = cxx::CxxIstream;
+    ///     }
+    ///
+    ///     unsafe extern "C++" {
+    ///         include!("example/include/config.h");
+    ///
+    ///         fn load_config(input: Pin<&mut istream>) -> Result<()>;
+    ///     }
+    /// }
+    ///
+    /// fn load(path: &str) -> Result<(), Box<dyn Error>> {
+    ///     let file = File::open(path)?;
+    ///     CxxIstream::with_reader(io::BufReader::new(file), ffi::load_config)??;
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// An error returned by the reader, other than `Interrupted` which is
+    /// retried, puts the stream into the bad state as seen by C++, and is
+    /// returned from `with_reader` in place of the closure's return value. A
+    /// panic in the reader or in `f` is caught before it reaches the C++
+    /// frames, and resumed once they have been exited.
+    pub fn with_reader<R, F, T>(reader: R, f: F) -> io::Result<T>
+    where
+        R: Read,
+        F: FnOnce(Pin<&mut CxxIstream>) -> T,
+    {
+        unsafe extern "C" fn read<R: Read, F, T>(
+            state: *mut c_void,
+            buf: *mut u8,
+            len: usize,
+            n: &mut usize,
+        ) -> bool {
+            let state = unsafe { &mut *state.cast::<Adapter<R, F, T>>() };
+            let buf = unsafe { slice::from_raw_parts_mut(buf, len) };
+            match state.call(|reader| loop {
+                match reader.read(buf) {
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                    result => return result,
+                }
+            }) {
+                Some(len) => {
+                    *n = len;
+                    true
+                }
+                None => false,
+            }
+        }
+
+        unsafe extern "C" fn body<R, F, T>(state: *mut c_void, stream: Pin<&mut CxxIstream>)
+        where
+            F: FnOnce(Pin<&mut CxxIstream>) -> T,
+        {
+            unsafe { Adapter::<R, F, T>::body(state, stream) }
+        }
+
+        let mut state = Adapter::new(reader, f);
+        unsafe {
+            istream_with_reader(
+                (&mut state as *mut Adapter<R, F, T>).cast(),
+                read::<R, F, T>,
+                body::<R, F, T>,
+            );
+        }
+        state.finish()
+    }
+}
+
+impl CxxOstream {
+    /// Presents a Rust writer to C++ as a `std::ostream` for the duration of
+    /// the closure `f`.
+    ///
+    /// The stream is backed by a `std::streambuf` which forwards output to
+    /// `writer` in chunks as the C++ code produces it. Anything still buffered
+    /// is flushed to the writer, followed by a flush of the writer itself,
+    /// when the closure returns.
+    ///
+    /// ```
+    /// # mod example {
+    /// use cxx::CxxOstream;
+    ///
+    /// #[cxx::bridge]
+    /// mod ffi {
+    ///     #[namespace = "std"]
+    ///     extern "C++" {
+    ///         include!(<ostream>);
+    ///
+    ///         type ostream = cxx::CxxOstream;
+    ///     }
+    ///
+    ///     unsafe extern "C++" {
+    ///         include!("example/include/report.h");
+    ///
+    ///         fn write_report(output: Pin<&mut ostream>);
+    ///     }
+    /// }
+    ///
+    /// fn report() -> std::io::Result<Vec<u8>> {
+    ///     let mut bytes = Vec::new();
+    ///     CxxOstream::with_writer(&mut bytes, ffi::write_report)?;
+    ///     Ok(bytes)
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// An error returned by the writer, other than `Interrupted` which is
+    /// retried, puts the stream into the bad state as seen by C++, and is
+    /// returned from `with_writer` in place of the closure's return value. A
+    /// panic in the writer or in `f` is caught before it reaches the C++
+    /// frames, and resumed once they have been exited.
+    pub fn with_writer<W, F, T>(writer: W, f: F) -> io::Result<T>
+    where
+        W: Write,
+        F: FnOnce(Pin<&mut CxxOstream>) -> T,
+    {
+        unsafe extern "C" fn write<W: Write, F, T>(
+            state: *mut c_void,
+            buf: *const u8,
+            len: usize,
+        ) -> bool {
+            let state = unsafe { &mut *state.cast::<Adapter<W, F, T>>() };
+            let buf = unsafe { slice::from_raw_parts(buf, len) };
+            state.call(|writer| writer.write_all(buf)).is_some()
+        }
+
+        unsafe extern "C" fn flush<W: Write, F, T>(state: *mut c_void) -> bool {
+            let state = unsafe { &mut *state.cast::<Adapter<W, F, T>>() };
+            state.call(Write::flush).is_some()
+        }
+
+        unsafe extern "C" fn body<W, F, T>(state: *mut c_void, stream: Pin<&mut CxxOstream>)
+        where
+            F: FnOnce(Pin<&mut CxxOstream>) -> T,
+        {
+            unsafe { Adapter::<W, F, T>::body(state, stream) }
+        }
+
+        let mut state = Adapter::new(writer, f);
+        unsafe {
+            ostream_with_writer(
+                (&mut state as *mut Adapter<W, F, T>).cast(),
+                write::<W, F, T>,
+                flush::<W, F, T>,
+                body::<W, F, T>,
+            );
+        }
+        state.finish()
+    }
+}
+
+// State shared between a with_reader or with_writer call and the callbacks it
+// hands to C++. Callbacks reach it one at a time through the raw pointer, and
+// no reference to it is held across a call into C++.
+struct Adapter<S, F, T> {
+    inner: S,
+    body: Option<F>,
+    ret: Option<T>,
+    error: Option<io::Error>,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<S, F, T> Adapter<S, F, T> {
+    fn new(inner: S, body: F) -> Self {
+        Adapter {
+            inner,
+            body: Some(body),
+            ret: None,
+            error: None,
+            panic: None,
+        }
+    }
+
+    // Runs a read or write on the Rust side of the stream, recording its
+    // failure for `finish` and returning None to have C++ see the stream fail.
+    fn call<U>(&mut self, f: impl FnOnce(&mut S) -> io::Result<U>) -> Option<U> {
+        if self.error.is_some() || self.panic.is_some() {
+            return None;
+        }
+        let inner = &mut self.inner;
+        match panic::catch_unwind(AssertUnwindSafe(|| f(inner))) {
+            Ok(Ok(value)) => Some(value),
+            Ok(Err(error)) => {
+                self.error = Some(error);
+                None
+            }
+            Err(payload) => {
+                self.panic = Some(payload);
+                None
+            }
+        }
+    }
+
+    unsafe fn body<P>(state: *mut c_void, stream: P)
+    where
+        F: FnOnce(P) -> T,
+    {
+        let state = state.cast::<Self>();
+        let body = unsafe { (*state).body.take() }.unwrap();
+        // The C++ code run by the body calls back into `call`, so only access
+        // the state again after it returns.
+        let result = panic::catch_unwind(AssertUnwindSafe(|| body(stream)));
+        match result {
+            Ok(ret) => unsafe { (*state).ret = Some(ret) },
+            Err(payload) => unsafe { (*state).panic = Some(payload) },
+        }
+    }
+
+    fn finish(self) -> io::Result<T> {
+        if let Some(payload) = self.panic {
+            panic::resume_unwind(payload);
+        }
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        Ok(self.ret.unwrap())
+    }
+}
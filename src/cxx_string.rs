@@ -1,9 +1,12 @@
 use crate::actually_private::Private;
 use crate::lossy;
+use crate::telemetry::{self, Origin};
+use crate::unique_ptr::UniquePtr;
 #[cfg(feature = "alloc")]
 use alloc::borrow::Cow;
 #[cfg(feature = "alloc")]
 use alloc::string::String;
+use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::ffi::{c_char, CStr};
 use core::fmt::{self, Debug, Display};
@@ -17,6 +20,8 @@ use core::str::{self, Utf8Error};
 extern "C" {
     #[link_name = "cxxbridge1$cxx_string$init"]
     fn string_init(this: &mut MaybeUninit<CxxString>, ptr: *const u8, len: usize);
+    #[link_name = "cxxbridge1$cxx_string$new"]
+    fn string_new(ptr: *const u8, len: usize) -> *mut CxxString;
     #[link_name = "cxxbridge1$cxx_string$destroy"]
     fn string_destroy(this: &mut MaybeUninit<CxxString>);
     #[link_name = "cxxbridge1$cxx_string$data"]
@@ -29,6 +34,10 @@ extern "C" {
     fn string_reserve_total(this: Pin<&mut CxxString>, new_cap: usize);
     #[link_name = "cxxbridge1$cxx_string$push"]
     fn string_push(this: Pin<&mut CxxString>, ptr: *const u8, len: usize);
+    #[link_name = "cxxbridge1$cxx_string$truncate"]
+    fn string_truncate(this: Pin<&mut CxxString>, len: usize);
+    #[link_name = "cxxbridge1$cxx_string$heap_capacity"]
+    fn string_heap_capacity(this: &CxxString) -> usize;
 }
 
 /// Binding to C++ `std::string`.
@@ -205,12 +214,14 @@ impl CxxString {
     /// Panics if the new capacity overflows usize.
     ///
     /// [reserve]: https://en.cppreference.com/w/cpp/string/basic_string/reserve
-    pub fn reserve(self: Pin<&mut Self>, additional: usize) {
+    pub fn reserve(mut self: Pin<&mut Self>, additional: usize) {
         let new_cap = self
             .len()
             .checked_add(additional)
             .expect("CxxString capacity overflow");
-        unsafe { string_reserve_total(self, new_cap) }
+        let before = telemetry::capacity_before(|| self.heap_capacity());
+        unsafe { string_reserve_total(self.as_mut(), new_cap) }
+        record_growth(before, || self.heap_capacity());
     }
 
     /// Appends a given string slice onto the end of this C++ string.
@@ -219,8 +230,53 @@ impl CxxString {
     }
 
     /// Appends arbitrary bytes onto the end of this C++ string.
-    pub fn push_bytes(self: Pin<&mut Self>, bytes: &[u8]) {
-        unsafe { string_push(self, bytes.as_ptr(), bytes.len()) }
+    pub fn push_bytes(mut self: Pin<&mut Self>, bytes: &[u8]) {
+        let before = telemetry::capacity_before(|| self.heap_capacity());
+        unsafe { string_push(self.as_mut(), bytes.as_ptr(), bytes.len()) }
+        record_growth(before, || self.heap_capacity());
+    }
+
+    /// Shortens this string to the specified length in bytes.
+    ///
+    /// If `new_len` is greater than or equal to the string's current length,
+    /// this has no effect. Unlike `String::truncate` in Rust, the new length
+    /// does not need to lie on a char boundary, since C++ strings are not
+    /// required to contain UTF-8.
+    ///
+    /// Matches the behavior of C++ [std::string::resize][resize] when
+    /// shrinking.
+    ///
+    /// [resize]: https://en.cppreference.com/w/cpp/string/basic_string/resize
+    pub fn truncate(self: Pin<&mut Self>, new_len: usize) {
+        if new_len < self.len() {
+            unsafe { string_truncate(self, new_len) }
+        }
+    }
+
+    fn heap_capacity(&self) -> usize {
+        unsafe { string_heap_capacity(self) }
+    }
+}
+
+fn record_growth(before: usize, after: impl FnOnce() -> usize) {
+    telemetry::record_growth(Origin::RustRequested, "std::string", 1, before, after);
+}
+
+/// Allocates a new C++ std::string on the heap holding a copy of the given
+/// bytes.
+impl From<&[u8]> for UniquePtr<CxxString> {
+    fn from(bytes: &[u8]) -> Self {
+        let string = unsafe { UniquePtr::from_raw(string_new(bytes.as_ptr(), bytes.len())) };
+        record_growth(0, || string.heap_capacity());
+        string
+    }
+}
+
+/// Allocates a new C++ std::string on the heap holding a copy of the given
+/// string.
+impl From<&str> for UniquePtr<CxxString> {
+    fn from(s: &str) -> Self {
+        UniquePtr::from(s.as_bytes())
     }
 }
 
@@ -254,8 +310,32 @@ impl PartialEq<str> for CxxString {
     }
 }
 
+impl PartialEq<CxxString> for [u8] {
+    fn eq(&self, other: &CxxString) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl PartialEq<[u8]> for CxxString {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
+    }
+}
+
 impl Eq for CxxString {}
 
+impl PartialOrd<str> for CxxString {
+    fn partial_cmp(&self, other: &str) -> Option<Ordering> {
+        Some(self.as_bytes().cmp(other.as_bytes()))
+    }
+}
+
+impl PartialOrd<CxxString> for str {
+    fn partial_cmp(&self, other: &CxxString) -> Option<Ordering> {
+        Some(self.as_bytes().cmp(other.as_bytes()))
+    }
+}
+
 impl PartialOrd for CxxString {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -274,6 +354,27 @@ impl Hash for CxxString {
     }
 }
 
+impl AsRef<[u8]> for CxxString {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+// Eq, Ord and Hash of CxxString all agree with those of its bytes, so a map
+// keyed by CxxString or &CxxString can be queried by &[u8] without building a
+// CxxString.
+impl Borrow<[u8]> for CxxString {
+    fn borrow(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Borrow<[u8]> for &CxxString {
+    fn borrow(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
 impl fmt::Write for Pin<&mut CxxString> {
     fn write_str(&mut self, s: &str) -> fmt::Result {
         self.as_mut().push_str(s);
@@ -282,6 +383,7 @@ impl fmt::Write for Pin<&mut CxxString> {
 }
 
 #[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl std::io::Write for Pin<&mut CxxString> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         self.as_mut().push_bytes(buf);
@@ -314,7 +416,9 @@ impl StackString {
         unsafe {
             let this = &mut *self.space.as_mut_ptr().cast::<MaybeUninit<CxxString>>();
             string_init(this, value.as_ptr(), value.len());
-            Pin::new_unchecked(&mut *this.as_mut_ptr())
+            let string = Pin::new_unchecked(&mut *this.as_mut_ptr());
+            record_growth(0, || string.heap_capacity());
+            string
         }
     }
 }
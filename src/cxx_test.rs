@@ -0,0 +1,41 @@
+use crate::fmt::display;
+use crate::lossy;
+use core::ptr;
+use core::slice;
+
+/// Defines a Rust `#[test]` for every C++ test case compiled by
+/// [`cxx_build::tests`], so that `cargo test` runs them and reports each one
+/// separately.
+///
+/// [`cxx_build::tests`]: https://docs.rs/cxx-build/*/cxx_build/fn.tests.html
+///
+/// Invoke it once, in the crate whose build script calls `cxx_build::tests`,
+/// for example at the top level of an integration test file:
+///
+/// ```ignore
+/// // tests/cxx.rs
+///
+/// cxx::cxx_tests!();
+/// ```
+///
+/// The C++ test cases are written using the `CXX_TEST` macro from
+/// `"rust/cxx_test.h"`. Each test's Rust name is the name it was given in C++,
+/// as in `CXX_TEST(roundtrip) { ... }`.
+#[macro_export]
+macro_rules! cxx_tests {
+    () => {
+        include!(concat!(env!("OUT_DIR"), "/cxxbridge/tests.rs"));
+    };
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn run(test: unsafe extern "C" fn(message: *mut *const u8, len: *mut usize) -> bool) {
+    let mut message = ptr::null();
+    let mut len = 0;
+    if unsafe { test(&mut message, &mut len) } {
+        return;
+    }
+    let message = unsafe { slice::from_raw_parts(message, len) };
+    panic!("{}", display(|f| lossy::display(message, f)));
+}
@@ -4,7 +4,12 @@
 use crate::extern_type::ExternType;
 use crate::kind::Trivial;
 use crate::string::CxxString;
+use crate::telemetry::{self, Origin};
 use crate::unique_ptr::UniquePtr;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::ffi::c_void;
 use core::fmt::{self, Debug};
 use core::iter::FusedIterator;
@@ -173,12 +178,21 @@ where
 
     /// Returns an iterator over elements of type `&T`.
     pub fn iter(&self) -> Iter<T> {
-        Iter { v: self, index: 0 }
+        Iter {
+            v: self,
+            index: 0,
+            end: self.len(),
+        }
     }
 
     /// Returns an iterator over elements of type `Pin<&mut T>`.
     pub fn iter_mut(self: Pin<&mut Self>) -> IterMut<T> {
-        IterMut { v: self, index: 0 }
+        let end = self.len();
+        IterMut {
+            v: self,
+            index: 0,
+            end,
+        }
     }
 
     /// Appends an element to the back of the vector.
@@ -186,15 +200,17 @@ where
     /// Matches the behavior of C++ [std::vector\<T\>::push_back][push_back].
     ///
     /// [push_back]: https://en.cppreference.com/w/cpp/container/vector/push_back
-    pub fn push(self: Pin<&mut Self>, value: T)
+    pub fn push(mut self: Pin<&mut Self>, value: T)
     where
         T: ExternType<Kind = Trivial>,
     {
         let mut value = ManuallyDrop::new(value);
+        let before = telemetry::capacity_before(|| self.capacity());
         unsafe {
             // C++ calls move constructor followed by destructor on `value`.
-            T::__push_back(self, &mut value);
+            T::__push_back(self.as_mut(), &mut value);
         }
+        record_growth::<T>(before, || self.capacity());
     }
 
     /// Removes the last element from a vector and returns it, or `None` if the
@@ -234,12 +250,112 @@ where
     /// Panics if the new capacity overflows usize.
     ///
     /// [reserve]: https://en.cppreference.com/w/cpp/container/vector/reserve.html
-    pub fn reserve(self: Pin<&mut Self>, additional: usize) {
+    pub fn reserve(mut self: Pin<&mut Self>, additional: usize) {
         let new_cap = self
             .len()
             .checked_add(additional)
             .expect("CxxVector capacity overflow");
-        unsafe { T::__reserve(self, new_cap) }
+        let before = telemetry::capacity_before(|| self.capacity());
+        unsafe { T::__reserve(self.as_mut(), new_cap) }
+        record_growth::<T>(before, || self.capacity());
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and destroying
+    /// the rest.
+    ///
+    /// If `len` is greater than or equal to the vector's current length, this
+    /// has no effect. The capacity of the vector is unchanged.
+    pub fn truncate(self: Pin<&mut Self>, len: usize) {
+        unsafe { T::__truncate(self, len) }
+    }
+
+    /// Destroys all elements of the vector, leaving it empty.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::clear][clear]. The
+    /// capacity of the vector is unchanged.
+    ///
+    /// [clear]: https://en.cppreference.com/w/cpp/container/vector/clear
+    pub fn clear(self: Pin<&mut Self>) {
+        self.truncate(0);
+    }
+
+    /// Resizes the vector in place so that its length is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, the vector is extended
+    /// with clones of `value`. If `new_len` is less, the vector is truncated.
+    pub fn resize(self: Pin<&mut Self>, new_len: usize, value: T)
+    where
+        T: ExternType<Kind = Trivial> + Clone,
+    {
+        let mut value = Some(value);
+        let mut remaining = new_len.saturating_sub(self.len());
+        self.resize_with(new_len, || {
+            remaining -= 1;
+            if remaining == 0 {
+                value.take().unwrap()
+            } else {
+                value.clone().unwrap()
+            }
+        });
+    }
+
+    /// Resizes the vector in place so that its length is equal to `new_len`,
+    /// filling any new elements with values returned by calling `f`.
+    ///
+    /// If `new_len` is less than the current length, the vector is truncated
+    /// and `f` is not called.
+    pub fn resize_with<F>(mut self: Pin<&mut Self>, new_len: usize, mut f: F)
+    where
+        T: ExternType<Kind = Trivial>,
+        F: FnMut() -> T,
+    {
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+        } else {
+            self.as_mut().reserve(new_len - len);
+            for _ in len..new_len {
+                self.as_mut().push(f());
+            }
+        }
+    }
+
+    /// Copies the elements of the vector into a new Rust `Vec`.
+    ///
+    /// For `Copy` element types such as the primitive integers this is a
+    /// single memcpy of the whole vector.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: ExternType<Kind = Trivial> + Clone,
+    {
+        self.as_slice().to_vec()
+    }
+
+    /// Moves all the elements of the vector into a new Rust `Vec`, leaving the
+    /// vector empty.
+    ///
+    /// Unlike [`to_vec`][CxxVector::to_vec], the element type does not need to
+    /// implement `Clone`. Each element is moved out by its C++ move
+    /// constructor, the same as by [`pop`][CxxVector::pop]. The capacity of
+    /// the vector is unchanged.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn drain_to_vec(mut self: Pin<&mut Self>) -> Vec<T>
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        let len = self.len();
+        let mut vec = Vec::with_capacity(len);
+        let spare = vec.spare_capacity_mut();
+        // Pop from the back so that the C++ vector never has to shift its
+        // remaining elements.
+        for slot in spare[..len].iter_mut().rev() {
+            unsafe { T::__pop_back(self.as_mut(), slot) }
+        }
+        unsafe { vec.set_len(len) }
+        vec
     }
 }
 
@@ -259,12 +375,71 @@ where
     }
 }
 
+// Opaque element types have a Rust size of 0, so growth of a vector of them is
+// counted without contributing any bytes.
+fn record_growth<T>(before: usize, after: impl FnOnce() -> usize) {
+    telemetry::record_growth(
+        Origin::RustRequested,
+        "std::vector",
+        mem::size_of::<T>(),
+        before,
+        after,
+    );
+}
+
+// Capacity of std::vector<bool> is in bits.
+fn record_bool_growth(before: usize, after: impl FnOnce() -> usize) {
+    telemetry::record_growth(Origin::RustRequested, "std::vector", 1, before / 8, || {
+        after() / 8
+    });
+}
+
 /// Iterator over elements of a `CxxVector` by shared reference.
 ///
 /// The iterator element type is `&'a T`.
 pub struct Iter<'a, T> {
     v: &'a CxxVector<T>,
     index: usize,
+    end: usize,
+}
+
+impl<'a, T> Iter<'a, T>
+where
+    T: VectorElement,
+{
+    /// Returns the elements not yet yielded by this iterator as a slice.
+    pub fn as_slice(&self) -> &'a [T]
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        &self.v.as_slice()[self.index..self.end]
+    }
+
+    #[cfg(feature = "rayon")]
+    pub(crate) fn split_at(self, mid: usize) -> (Self, Self) {
+        let mid = self.index + mid;
+        let left = Iter {
+            v: self.v,
+            index: self.index,
+            end: mid,
+        };
+        let right = Iter {
+            v: self.v,
+            index: mid,
+            end: self.end,
+        };
+        (left, right)
+    }
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            v: self.v,
+            index: self.index,
+            end: self.end,
+        }
+    }
 }
 
 impl<'a, T> IntoIterator for &'a CxxVector<T>
@@ -286,7 +461,10 @@ where
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next = self.v.get(self.index)?;
+        if self.index == self.end {
+            return None;
+        }
+        let next = unsafe { self.v.get_unchecked(self.index) };
         self.index += 1;
         Some(next)
     }
@@ -297,12 +475,25 @@ where
     }
 }
 
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: VectorElement,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(unsafe { self.v.get_unchecked(self.end) })
+    }
+}
+
 impl<'a, T> ExactSizeIterator for Iter<'a, T>
 where
     T: VectorElement,
 {
     fn len(&self) -> usize {
-        self.v.len() - self.index
+        self.end - self.index
     }
 }
 
@@ -314,6 +505,7 @@ impl<'a, T> FusedIterator for Iter<'a, T> where T: VectorElement {}
 pub struct IterMut<'a, T> {
     v: Pin<&'a mut CxxVector<T>>,
     index: usize,
+    end: usize,
 }
 
 impl<'a, T> IntoIterator for Pin<&'a mut CxxVector<T>>
@@ -335,7 +527,10 @@ where
     type Item = Pin<&'a mut T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next = self.v.as_mut().index_mut(self.index)?;
+        if self.index == self.end {
+            return None;
+        }
+        let next = unsafe { self.v.as_mut().index_unchecked_mut(self.index) };
         self.index += 1;
         // Extend lifetime to allow simultaneous holding of nonoverlapping
         // elements, analogous to slice::split_first_mut.
@@ -351,12 +546,31 @@ where
     }
 }
 
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T>
+where
+    T: VectorElement,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        self.end -= 1;
+        let next = unsafe { self.v.as_mut().index_unchecked_mut(self.end) };
+        // Extend lifetime to allow simultaneous holding of nonoverlapping
+        // elements, analogous to slice::split_last_mut.
+        unsafe {
+            let ptr = Pin::into_inner_unchecked(next) as *mut T;
+            Some(Pin::new_unchecked(&mut *ptr))
+        }
+    }
+}
+
 impl<'a, T> ExactSizeIterator for IterMut<'a, T>
 where
     T: VectorElement,
 {
     fn len(&self) -> usize {
-        self.v.len() - self.index
+        self.end - self.index
     }
 }
 
@@ -417,6 +631,8 @@ pub unsafe trait VectorElement: Sized {
     #[doc(hidden)]
     unsafe fn __reserve(v: Pin<&mut CxxVector<Self>>, new_cap: usize);
     #[doc(hidden)]
+    unsafe fn __truncate(v: Pin<&mut CxxVector<Self>>, len: usize);
+    #[doc(hidden)]
     unsafe fn __push_back(v: Pin<&mut CxxVector<Self>>, value: &mut ManuallyDrop<Self>) {
         // Opaque C type vector elements do not get this method because they can
         // never exist by value on the Rust side of the bridge.
@@ -450,16 +666,16 @@ macro_rules! vector_element_by_value_methods {
         unsafe fn __push_back(v: Pin<&mut CxxVector<$ty>>, value: &mut ManuallyDrop<$ty>) {
             extern "C" {
                 #[link_name = concat!("cxxbridge1$std$vector$", $segment, "$push_back")]
-                fn __push_back(_: Pin<&mut CxxVector<$ty>>, _: &mut ManuallyDrop<$ty>);
+                fn __push_back(_: Pin<&mut CxxVector<$ty>>, _: *mut c_void);
             }
-            unsafe { __push_back(v, value) }
+            unsafe { __push_back(v, value as *mut ManuallyDrop<$ty> as *mut c_void) }
         }
         unsafe fn __pop_back(v: Pin<&mut CxxVector<$ty>>, out: &mut MaybeUninit<$ty>) {
             extern "C" {
                 #[link_name = concat!("cxxbridge1$std$vector$", $segment, "$pop_back")]
-                fn __pop_back(_: Pin<&mut CxxVector<$ty>>, _: &mut MaybeUninit<$ty>);
+                fn __pop_back(_: Pin<&mut CxxVector<$ty>>, _: *mut c_void);
             }
-            unsafe { __pop_back(v, out) }
+            unsafe { __pop_back(v, out as *mut MaybeUninit<$ty> as *mut c_void) }
         }
     };
 }
@@ -497,9 +713,9 @@ macro_rules! impl_vector_element {
             unsafe fn __get_unchecked(v: *mut CxxVector<$ty>, pos: usize) -> *mut $ty {
                 extern "C" {
                     #[link_name = concat!("cxxbridge1$std$vector$", $segment, "$get_unchecked")]
-                    fn __get_unchecked(_: *mut CxxVector<$ty>, _: usize) -> *mut $ty;
+                    fn __get_unchecked(_: *mut CxxVector<$ty>, _: usize) -> *mut c_void;
                 }
-                unsafe { __get_unchecked(v, pos) }
+                unsafe { __get_unchecked(v, pos) as *mut $ty }
             }
             unsafe fn __reserve(v: Pin<&mut CxxVector<$ty>>, new_cap: usize) {
                 extern "C" {
@@ -508,6 +724,13 @@ macro_rules! impl_vector_element {
                 }
                 unsafe { __reserve(v, new_cap) }
             }
+            unsafe fn __truncate(v: Pin<&mut CxxVector<$ty>>, len: usize) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$vector$", $segment, "$truncate")]
+                    fn __truncate(_: Pin<&mut CxxVector<$ty>>, _: usize);
+                }
+                unsafe { __truncate(v, len) }
+            }
             vector_element_by_value_methods!($kind, $segment, $ty);
             fn __unique_ptr_null() -> MaybeUninit<*mut c_void> {
                 extern "C" {
@@ -571,4 +794,274 @@ impl_vector_element_for_primitive!(isize);
 impl_vector_element_for_primitive!(f32);
 impl_vector_element_for_primitive!(f64);
 
+#[cfg(feature = "alloc")]
+impl_vector_element!(trivial, "rust_string", "String", String);
 impl_vector_element!(opaque, "string", "CxxString", CxxString);
+
+// C++ std::vector<bool> is a bit-packed specialization whose elements do not
+// have an address of their own, so CxxVector<bool> is not a VectorElement
+// vector. It gets the same API, except that elements are read and written by
+// value instead of through references.
+const_assert_eq!(0, mem::size_of::<CxxVector<bool>>());
+const_assert_eq!(1, mem::align_of::<CxxVector<bool>>());
+
+extern "C" {
+    #[link_name = "cxxbridge1$std$vector$bool$new"]
+    fn vector_bool_new() -> *mut CxxVector<bool>;
+    #[link_name = "cxxbridge1$std$vector$bool$size"]
+    fn vector_bool_size(_: &CxxVector<bool>) -> usize;
+    #[link_name = "cxxbridge1$std$vector$bool$capacity"]
+    fn vector_bool_capacity(_: &CxxVector<bool>) -> usize;
+    #[link_name = "cxxbridge1$std$vector$bool$get_unchecked"]
+    fn vector_bool_get_unchecked(_: &CxxVector<bool>, pos: usize) -> bool;
+    #[link_name = "cxxbridge1$std$vector$bool$set_unchecked"]
+    fn vector_bool_set_unchecked(_: Pin<&mut CxxVector<bool>>, pos: usize, value: bool);
+    #[link_name = "cxxbridge1$std$vector$bool$reserve"]
+    fn vector_bool_reserve(_: Pin<&mut CxxVector<bool>>, new_cap: usize);
+    #[link_name = "cxxbridge1$std$vector$bool$truncate"]
+    fn vector_bool_truncate(_: Pin<&mut CxxVector<bool>>, len: usize);
+    #[link_name = "cxxbridge1$std$vector$bool$push_back"]
+    fn vector_bool_push_back(_: Pin<&mut CxxVector<bool>>, value: bool);
+    #[link_name = "cxxbridge1$std$vector$bool$pop_back"]
+    fn vector_bool_pop_back(_: Pin<&mut CxxVector<bool>>) -> bool;
+}
+
+/// Binding to the bit-packed C++ `std::vector<bool>`.
+///
+/// The elements of a `std::vector<bool>` are individual bits, which cannot be
+/// referred to by a Rust `&bool`. Instead of the reference-based accessors of
+/// other vectors, elements are read with [`get`][CxxVector::get] and written
+/// with [`set`][CxxVector::set], and iteration produces `bool` by value.
+impl CxxVector<bool> {
+    /// Constructs a new heap allocated vector, wrapped by UniquePtr.
+    ///
+    /// The C++ vector is default constructed.
+    pub fn new() -> UniquePtr<Self> {
+        unsafe { UniquePtr::from_raw(vector_bool_new()) }
+    }
+
+    /// Returns the number of elements in the vector.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::size][size].
+    ///
+    /// [size]: https://en.cppreference.com/w/cpp/container/vector/size
+    pub fn len(&self) -> usize {
+        unsafe { vector_bool_size(self) }
+    }
+
+    /// Returns the capacity of the vector.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::capacity][capacity].
+    ///
+    /// [capacity]: https://en.cppreference.com/w/cpp/container/vector/capacity
+    pub fn capacity(&self) -> usize {
+        unsafe { vector_bool_capacity(self) }
+    }
+
+    /// Returns true if the vector contains no elements.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::empty][empty].
+    ///
+    /// [empty]: https://en.cppreference.com/w/cpp/container/vector/empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at the given position, or `None` if out of bounds.
+    pub fn get(&self, pos: usize) -> Option<bool> {
+        if pos < self.len() {
+            Some(unsafe { vector_bool_get_unchecked(self, pos) })
+        } else {
+            None
+        }
+    }
+
+    /// Overwrites the element at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` is out of bounds.
+    pub fn set(self: Pin<&mut Self>, pos: usize, value: bool) {
+        let len = self.len();
+        assert!(
+            pos < len,
+            "index out of bounds: the len is {} but the index is {}",
+            len,
+            pos,
+        );
+        unsafe { vector_bool_set_unchecked(self, pos, value) }
+    }
+
+    /// Returns an iterator over the elements by value.
+    pub fn iter(&self) -> BoolIter {
+        BoolIter {
+            v: self,
+            index: 0,
+            end: self.len(),
+        }
+    }
+
+    /// Appends an element to the back of the vector.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::push_back][push_back].
+    ///
+    /// [push_back]: https://en.cppreference.com/w/cpp/container/vector/push_back
+    pub fn push(mut self: Pin<&mut Self>, value: bool) {
+        let before = telemetry::capacity_before(|| self.capacity());
+        unsafe { vector_bool_push_back(self.as_mut(), value) }
+        record_bool_growth(before, || self.capacity());
+    }
+
+    /// Removes the last element from a vector and returns it, or `None` if the
+    /// vector is empty.
+    pub fn pop(self: Pin<&mut Self>) -> Option<bool> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(unsafe { vector_bool_pop_back(self) })
+        }
+    }
+
+    /// Ensures that this vector's capacity is at least `additional` elements
+    /// larger than its length.
+    ///
+    /// Like [`CxxVector::reserve`] for other element types, the argument is
+    /// the additional capacity, not the total capacity as in C++.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows usize.
+    pub fn reserve(mut self: Pin<&mut Self>, additional: usize) {
+        let new_cap = self
+            .len()
+            .checked_add(additional)
+            .expect("CxxVector capacity overflow");
+        let before = telemetry::capacity_before(|| self.capacity());
+        unsafe { vector_bool_reserve(self.as_mut(), new_cap) }
+        record_bool_growth(before, || self.capacity());
+    }
+
+    /// Shortens the vector, keeping the first `len` elements.
+    ///
+    /// If `len` is greater than or equal to the vector's current length, this
+    /// has no effect. The capacity of the vector is unchanged.
+    pub fn truncate(self: Pin<&mut Self>, len: usize) {
+        unsafe { vector_bool_truncate(self, len) }
+    }
+
+    /// Removes all elements of the vector, leaving it empty.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::clear][clear]. The
+    /// capacity of the vector is unchanged.
+    ///
+    /// [clear]: https://en.cppreference.com/w/cpp/container/vector/clear
+    pub fn clear(self: Pin<&mut Self>) {
+        self.truncate(0);
+    }
+
+    /// Resizes the vector in place so that its length is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, the vector is extended
+    /// with copies of `value`. If `new_len` is less, the vector is truncated.
+    pub fn resize(mut self: Pin<&mut Self>, new_len: usize, value: bool) {
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+        } else {
+            self.as_mut().reserve(new_len - len);
+            for _ in len..new_len {
+                self.as_mut().push(value);
+            }
+        }
+    }
+
+    /// Copies the elements of the vector into a new Rust `Vec`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_vec(&self) -> Vec<bool> {
+        self.iter().collect()
+    }
+}
+
+impl Extend<bool> for Pin<&mut CxxVector<bool>> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = bool>,
+    {
+        let iter = iter.into_iter();
+        self.as_mut().reserve(iter.size_hint().0);
+        for element in iter {
+            self.as_mut().push(element);
+        }
+    }
+}
+
+impl Debug for CxxVector<bool> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_list().entries(self).finish()
+    }
+}
+
+/// Iterator over elements of a `CxxVector<bool>` by value.
+///
+/// The iterator element type is `bool`.
+pub struct BoolIter<'a> {
+    v: &'a CxxVector<bool>,
+    index: usize,
+    end: usize,
+}
+
+impl<'a> Clone for BoolIter<'a> {
+    fn clone(&self) -> Self {
+        BoolIter {
+            v: self.v,
+            index: self.index,
+            end: self.end,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a CxxVector<bool> {
+    type Item = bool;
+    type IntoIter = BoolIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> Iterator for BoolIter<'a> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        let next = unsafe { vector_bool_get_unchecked(self.v, self.index) };
+        self.index += 1;
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for BoolIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(unsafe { vector_bool_get_unchecked(self.v, self.end) })
+    }
+}
+
+impl<'a> ExactSizeIterator for BoolIter<'a> {
+    fn len(&self) -> usize {
+        self.end - self.index
+    }
+}
+
+impl<'a> FusedIterator for BoolIter<'a> {}
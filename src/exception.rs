@@ -1,7 +1,11 @@
 #![cfg(feature = "alloc")]
 
 use alloc::boxed::Box;
+use alloc::string::ToString;
 use core::fmt::{self, Display};
+use core::iter;
+#[cfg(feature = "std")]
+use std::backtrace::Backtrace;
 
 #[cfg(not(no_error_in_core))]
 use core::error::Error as StdError;
@@ -9,10 +13,32 @@ use core::error::Error as StdError;
 use std::error::Error as StdError;
 
 /// Exception thrown from an `extern "C++"` function.
+///
+/// Besides the message from the C++ exception's `what()`, an Exception carries
+/// an error code and a chain of source exceptions when the C++ side provided
+/// them: the code of a `std::system_error`, and the nested exceptions of one
+/// thrown by `std::throw_with_nested`. A `rust::Error` thrown by a Rust
+/// function further down the stack comes back with the code and source chain
+/// it was created with.
+///
+/// For crash triage, an Exception caught from C++ also records the dynamic
+/// type of the C++ exception object and a backtrace of the point where it was
+/// caught, which are not carried by a C++ exception's `what()` alone.
+///
+/// Exceptions may also be constructed in Rust and returned as the error of an
+/// `extern "Rust"` function, in which case C++ observes the same code and
+/// sources on the `rust::Error` that it catches.
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 #[derive(Debug)]
 pub struct Exception {
     pub(crate) what: Box<str>,
+    pub(crate) type_name: Option<Box<str>>,
+    pub(crate) code: i32,
+    pub(crate) source: Option<Box<Exception>>,
+    // Boxed to keep Result<T, Exception> small; most exceptions never have
+    // their backtrace looked at.
+    #[cfg(feature = "std")]
+    pub(crate) backtrace: Option<Box<Backtrace>>,
 }
 
 impl Display for Exception {
@@ -22,11 +48,93 @@ impl Display for Exception {
 }
 
 #[cfg(any(not(no_error_in_core), feature = "std"))]
-impl StdError for Exception {}
+impl StdError for Exception {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.source {
+            Some(source) => Some(&**source),
+            None => None,
+        }
+    }
+}
 
 impl Exception {
+    /// Creates an exception with the given message, no error code, and no
+    /// source.
+    pub fn new(what: impl Display) -> Self {
+        Exception {
+            what: what.to_string().into_boxed_str(),
+            type_name: None,
+            code: 0,
+            source: None,
+            #[cfg(feature = "std")]
+            backtrace: None,
+        }
+    }
+
+    /// Sets the error code, as seen by C++ through `rust::Error::code()`.
+    ///
+    /// A code of 0 means no code.
+    #[must_use]
+    pub fn with_code(mut self, code: i32) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Sets the exception that caused this one.
+    #[must_use]
+    pub fn with_source(mut self, source: Exception) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+
     #[allow(missing_docs)]
     pub fn what(&self) -> &str {
         &self.what
     }
+
+    /// The error code, if the exception has a nonzero one.
+    pub fn code(&self) -> Option<i32> {
+        if self.code == 0 {
+            None
+        } else {
+            Some(self.code)
+        }
+    }
+
+    /// The dynamic type of the C++ exception object, such as
+    /// `std::runtime_error`.
+    ///
+    /// This is available for exceptions derived from `std::exception` caught
+    /// in C++ code compiled with RTTI. The name is demangled on platforms whose
+    /// C++ ABI provides a demangler, and otherwise is in whatever form the
+    /// compiler's `typeid(...).name()` returns.
+    pub fn type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
+
+    /// A backtrace of the point where the C++ exception was caught and turned
+    /// into this Exception.
+    ///
+    /// By the time a C++ exception is caught, the stack between the throw and
+    /// the catch has already been unwound, so the innermost frames are those of
+    /// the C++ function called by the bridge, not of the `throw` expression.
+    ///
+    /// Whether a backtrace is captured is controlled by the `RUST_BACKTRACE`
+    /// and `RUST_LIB_BACKTRACE` environment variables in the same way as for
+    /// [`Backtrace::capture`]. Exceptions constructed in Rust have no backtrace.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn backtrace(&self) -> &Backtrace {
+        static DISABLED: Backtrace = Backtrace::disabled();
+        self.backtrace.as_deref().unwrap_or(&DISABLED)
+    }
+
+    /// The messages of the chain of source exceptions, starting with the
+    /// immediate cause.
+    pub fn sources(&self) -> impl Iterator<Item = &str> {
+        iter::successors(self.source.as_deref(), |exception| {
+            exception.source.as_deref()
+        })
+        .map(Exception::what)
+    }
 }
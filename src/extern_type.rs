@@ -1,4 +1,10 @@
 use self::kind::{Kind, Opaque, Trivial};
+#[cfg(feature = "c++17")]
+use crate::cxx_pmr_string::CxxPmrString;
+#[cfg(feature = "std")]
+use crate::cxx_stream::{CxxIstream, CxxOstream};
+#[cfg(feature = "c++17")]
+use crate::pmr::MemoryResource;
 use crate::string::CxxString;
 #[cfg(feature = "alloc")]
 use alloc::string::String;
@@ -222,4 +228,17 @@ impl_extern_type! {
 
     [Opaque]
     CxxString = "std::string"
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    CxxIstream = "std::istream"
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    CxxOstream = "std::ostream"
+
+    #[cfg(feature = "c++17")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "c++17")))]
+    CxxPmrString = "std::pmr::string"
+    #[cfg(feature = "c++17")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "c++17")))]
+    MemoryResource = "std::pmr::memory_resource"
 }
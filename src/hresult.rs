@@ -0,0 +1,87 @@
+use core::fmt::{self, Debug, Display};
+
+#[cfg(not(no_error_in_core))]
+use core::error::Error as StdError;
+#[cfg(all(feature = "std", no_error_in_core))]
+use std::error::Error as StdError;
+
+/// Binding to the COM status code `HRESULT`.
+///
+/// An HRESULT is a 32-bit value whose sign bit reports failure. Bridge
+/// functions declared with the return type `Result<T, HRESULT>` map a failing
+/// status code returned from C++ to `Err`, and any other to `Ok`.
+///
+/// ```
+/// use cxx::HRESULT;
+///
+/// assert!(HRESULT::S_FALSE.ok().is_ok());
+/// assert_eq!(HRESULT::E_FAIL.ok(), Err(HRESULT::E_FAIL));
+/// assert_eq!(HRESULT::E_FAIL.to_string(), "0x80004005");
+/// ```
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HRESULT(pub i32);
+
+impl HRESULT {
+    /// Success.
+    pub const S_OK: HRESULT = HRESULT(0);
+    /// Success, with a result that is the logical opposite of `S_OK`.
+    pub const S_FALSE: HRESULT = HRESULT(1);
+    /// Not implemented.
+    pub const E_NOTIMPL: HRESULT = HRESULT(0x80004001_u32 as i32);
+    /// No such interface supported.
+    pub const E_NOINTERFACE: HRESULT = HRESULT(0x80004002_u32 as i32);
+    /// Pointer that is not valid.
+    pub const E_POINTER: HRESULT = HRESULT(0x80004003_u32 as i32);
+    /// Unspecified failure.
+    pub const E_FAIL: HRESULT = HRESULT(0x80004005_u32 as i32);
+    /// Unexpected failure.
+    pub const E_UNEXPECTED: HRESULT = HRESULT(0x8000FFFF_u32 as i32);
+    /// Failed to allocate necessary memory.
+    pub const E_OUTOFMEMORY: HRESULT = HRESULT(0x8007000E_u32 as i32);
+    /// One or more arguments are not valid.
+    pub const E_INVALIDARG: HRESULT = HRESULT(0x80070057_u32 as i32);
+
+    /// Returns true if the status code reports success, as with the
+    /// `SUCCEEDED` macro.
+    pub const fn is_ok(self) -> bool {
+        self.0 >= 0
+    }
+
+    /// Returns true if the status code reports failure, as with the `FAILED`
+    /// macro.
+    pub const fn is_err(self) -> bool {
+        !self.is_ok()
+    }
+
+    /// Converts a failing status code to `Err(self)`, and any successful one,
+    /// including `S_FALSE`, to `Ok(())`.
+    pub fn ok(self) -> Result<(), HRESULT> {
+        if self.is_ok() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Returns the status code as its unsigned bit pattern.
+    pub const fn code(self) -> u32 {
+        self.0 as u32
+    }
+}
+
+impl Display for HRESULT {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{:#010X}", self.code())
+    }
+}
+
+impl Debug for HRESULT {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "HRESULT({:#010X})", self.code())
+    }
+}
+
+#[cfg(any(not(no_error_in_core), feature = "std"))]
+impl StdError for HRESULT {}
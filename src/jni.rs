@@ -0,0 +1,359 @@
+//! Bindings of the JNI handles, for native code of a Java or Android
+//! application that delegates to bridged Rust code.
+//!
+//! The types in this module are extern C++ types of the `jni.h` header, for use
+//! as type aliases in an extern "C++" block of a bridge. Unlike raw pointers,
+//! they keep track of which references are only valid for the duration of a
+//! call and which thread they belong to.
+//!
+//! ```
+//! # #[cfg(feature = "jni")]
+//! #[cxx::bridge]
+//! mod ffi {
+//!     unsafe extern "C++" {
+//!         include!("jni.h");
+//!
+//!         #[namespace = ""]
+//!         type JNIEnv = cxx::jni::JNIEnv;
+//!         #[namespace = ""]
+//!         type jobject<'local> = cxx::jni::JObject<'local>;
+//!     }
+//!
+//!     extern "Rust" {
+//!         fn on_create(env: Pin<&mut JNIEnv>, activity: jobject<'_>);
+//!     }
+//! }
+//! #
+//! # fn on_create(_env: core::pin::Pin<&mut cxx::jni::JNIEnv>, _activity: cxx::jni::JObject) {}
+//! #
+//! # fn main() {}
+//! ```
+//!
+//! Here the JNI entry point in C++ calls `on_create(*env, thiz)`, with `env`
+//! and `thiz` the arguments of the native method.
+//!
+//! # Lifetimes
+//!
+//! - A `JNIEnv` is bound to the thread it was obtained on, and is only seen by
+//!   Rust behind a reference for the duration of one call, so it is neither
+//!   `Send` nor `Sync`.
+//!
+//! - A [`JObject<'local>`][JObject] or [`JClass<'local>`][JClass] is a local
+//!   reference, which the JVM frees when the native method that received or
+//!   created it returns. The `'local` lifetime stands for that frame. It is
+//!   neither `Send` nor `Sync`, as local references are only valid on the
+//!   thread that owns the frame. Objects needed beyond that must be turned
+//!   into a global reference on the C++ side with `NewGlobalRef`.
+//!
+//! - A [`JavaVM`] is valid for as long as the process, and usable from any
+//!   thread. Threads not created by the JVM have no `JNIEnv` until attached
+//!   with [`JavaVM::attach_current_thread`].
+
+use crate::extern_type::{kind, ExternType};
+use crate::opaque::Opaque;
+use core::cell::Cell;
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Display};
+use core::marker::PhantomData;
+use core::mem;
+use core::pin::Pin;
+use core::ptr;
+
+#[cfg(not(no_error_in_core))]
+use core::error::Error as StdError;
+#[cfg(all(feature = "std", no_error_in_core))]
+use std::error::Error as StdError;
+
+#[allow(non_camel_case_types)]
+type jint = i32;
+
+const JNI_OK: jint = 0;
+const JNI_EDETACHED: jint = -2;
+const JNI_VERSION_1_6: jint = 0x0001_0006;
+
+// Indices into the function tables of JavaVM and JNIEnv, counting their
+// reserved entries.
+const ATTACH_CURRENT_THREAD: usize = 4;
+const DETACH_CURRENT_THREAD: usize = 5;
+const GET_ENV: usize = 6;
+const ATTACH_CURRENT_THREAD_AS_DAEMON: usize = 7;
+const GET_JAVA_VM: usize = 219;
+
+std::thread_local! {
+    // Number of live AttachGuards on this thread that share an attachment made
+    // by attach_current_thread. The thread is detached when the last of them
+    // is dropped, whichever order they are dropped in.
+    static GUARDS: Cell<usize> = const { Cell::new(0) };
+}
+
+type AttachFn = unsafe extern "system" fn(*mut JavaVM, *mut *mut JNIEnv, *mut c_void) -> jint;
+type DetachFn = unsafe extern "system" fn(*mut JavaVM) -> jint;
+type GetEnvFn = unsafe extern "system" fn(*mut JavaVM, *mut *mut JNIEnv, jint) -> jint;
+type GetJavaVmFn = unsafe extern "system" fn(*mut JNIEnv, *mut *mut JavaVM) -> jint;
+
+// Both JavaVM and JNIEnv begin with a pointer to their table of functions.
+unsafe fn function<T, F>(this: *const T, index: usize) -> F {
+    unsafe {
+        let functions = *this.cast::<*const *const c_void>();
+        mem::transmute_copy(&*functions.add(index))
+    }
+}
+
+/// Binding to the C++ type `JavaVM`, the invocation interface of a JVM.
+///
+/// A `JavaVM` is obtained by C++ from the `JNI_OnLoad` entry point, through
+/// `GetJavaVM`, or from Rust with [`JNIEnv::java_vm`]. It stays valid for the
+/// rest of the process and may be used from any thread.
+#[repr(C)]
+pub struct JavaVM {
+    _opaque: Opaque,
+}
+
+unsafe impl Send for JavaVM {}
+unsafe impl Sync for JavaVM {}
+
+impl JavaVM {
+    /// Gives the current thread a `JNIEnv`, attaching it to the JVM if it is
+    /// not attached already.
+    ///
+    /// A thread attached by this call is detached again once the returned
+    /// guard and every other guard taken on the thread since are dropped. A
+    /// thread that was attached before, such as one that is running a native
+    /// method, stays attached.
+    pub fn attach_current_thread(&self) -> Result<AttachGuard, JniError> {
+        self.attach(ATTACH_CURRENT_THREAD)
+    }
+
+    /// Same as [`attach_current_thread`](Self::attach_current_thread), except
+    /// that if the thread needs to be attached it is attached as a daemon
+    /// thread, which does not keep the JVM from exiting.
+    pub fn attach_current_thread_as_daemon(&self) -> Result<AttachGuard, JniError> {
+        self.attach(ATTACH_CURRENT_THREAD_AS_DAEMON)
+    }
+
+    fn attach(&self, index: usize) -> Result<AttachGuard, JniError> {
+        let vm = self as *const Self as *mut Self;
+        let mut env = ptr::null_mut();
+        let guards = GUARDS.with(Cell::get);
+        let get_env: GetEnvFn = unsafe { function(vm, GET_ENV) };
+        let counted = match unsafe { get_env(vm, &mut env, JNI_VERSION_1_6) } {
+            JNI_OK => guards > 0,
+            JNI_EDETACHED => {
+                let attach: AttachFn = unsafe { function(vm, index) };
+                check(unsafe { attach(vm, &mut env, ptr::null_mut()) })?;
+                true
+            }
+            code => return Err(JniError(code)),
+        };
+        if counted {
+            GUARDS.with(|count| count.set(guards + 1));
+        }
+        Ok(AttachGuard {
+            vm: self,
+            env,
+            counted,
+        })
+    }
+}
+
+unsafe impl ExternType for JavaVM {
+    type Id = crate::type_id!("JavaVM");
+    type Kind = kind::Opaque;
+}
+
+/// Binding to the C++ type `JNIEnv`, the JNI interface of one thread.
+///
+/// Received by reference, as `Pin<&mut JNIEnv>`, from a C++ native method or
+/// from an [`AttachGuard`]. Neither `Send` nor `Sync`, since a `JNIEnv` must
+/// only be used on the thread it belongs to.
+#[repr(C)]
+pub struct JNIEnv {
+    _opaque: Opaque,
+}
+
+impl JNIEnv {
+    /// Returns the JVM that this thread is attached to.
+    pub fn java_vm(self: Pin<&mut Self>) -> Result<&'static JavaVM, JniError> {
+        let env = unsafe { self.get_unchecked_mut() as *mut Self };
+        let mut vm = ptr::null_mut();
+        let get_java_vm: GetJavaVmFn = unsafe { function(env, GET_JAVA_VM) };
+        check(unsafe { get_java_vm(env, &mut vm) })?;
+        Ok(unsafe { &*vm })
+    }
+}
+
+unsafe impl ExternType for JNIEnv {
+    type Id = crate::type_id!("JNIEnv");
+    type Kind = kind::Opaque;
+}
+
+/// The current thread's attachment to a JVM, from
+/// [`JavaVM::attach_current_thread`].
+///
+/// If the thread was attached for this guard or for another one that is still
+/// alive, the thread is detached when the last such guard is dropped. Local
+/// references created through the `JNIEnv` must not be used after that.
+pub struct AttachGuard<'vm> {
+    vm: &'vm JavaVM,
+    env: *mut JNIEnv,
+    counted: bool,
+}
+
+impl<'vm> AttachGuard<'vm> {
+    /// The `JNIEnv` of the current thread.
+    pub fn env(&mut self) -> Pin<&mut JNIEnv> {
+        unsafe { Pin::new_unchecked(&mut *self.env) }
+    }
+
+    /// The JVM that the current thread is attached to.
+    pub fn java_vm(&self) -> &'vm JavaVM {
+        self.vm
+    }
+}
+
+impl<'vm> Debug for AttachGuard<'vm> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("AttachGuard")
+            .field("env", &self.env)
+            .finish()
+    }
+}
+
+impl<'vm> Drop for AttachGuard<'vm> {
+    fn drop(&mut self) {
+        if !self.counted {
+            return;
+        }
+        let guards = GUARDS.with(|count| {
+            count.set(count.get() - 1);
+            count.get()
+        });
+        if guards == 0 {
+            let vm = self.vm as *const JavaVM as *mut JavaVM;
+            let detach: DetachFn = unsafe { function(vm, DETACH_CURRENT_THREAD) };
+            let _ = unsafe { detach(vm) };
+        }
+    }
+}
+
+macro_rules! local_reference {
+    ($(#[$doc:meta])* $name:ident = $cxx:literal) => {
+        $(#[$doc])*
+        #[repr(transparent)]
+        #[derive(Copy, Clone)]
+        pub struct $name<'local> {
+            repr: *mut c_void,
+            frame: PhantomData<&'local JNIEnv>,
+        }
+
+        impl<'local> $name<'local> {
+            /// Makes a null reference.
+            pub fn null() -> Self {
+                $name {
+                    repr: ptr::null_mut(),
+                    frame: PhantomData,
+                }
+            }
+
+            /// Checks whether this is a null reference.
+            pub fn is_null(self) -> bool {
+                self.repr.is_null()
+            }
+
+            /// Returns the reference as a raw
+            #[doc = concat!("`", $cxx, "`.")]
+            pub fn as_raw(self) -> *mut c_void {
+                self.repr
+            }
+
+            /// Wraps a raw
+            #[doc = concat!("`", $cxx, "`.")]
+            ///
+            /// # Safety
+            ///
+            /// The pointer must be null or a local reference that remains
+            /// valid on the current thread for the lifetime `'local`.
+            pub unsafe fn from_raw(repr: *mut c_void) -> Self {
+                $name {
+                    repr,
+                    frame: PhantomData,
+                }
+            }
+        }
+
+        impl<'local> Debug for $name<'local> {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "{}({:p})", stringify!($name), self.repr)
+            }
+        }
+
+        unsafe impl<'local> ExternType for $name<'local> {
+            type Id = crate::type_id!($cxx);
+            type Kind = kind::Trivial;
+        }
+    };
+}
+
+local_reference! {
+    /// Binding to the C++ type `jobject`, a local reference to a Java object.
+    JObject = "jobject"
+}
+
+local_reference! {
+    /// Binding to the C++ type `jclass`, a local reference to a Java class.
+    JClass = "jclass"
+}
+
+impl<'local> From<JClass<'local>> for JObject<'local> {
+    fn from(class: JClass<'local>) -> Self {
+        JObject {
+            repr: class.repr,
+            frame: PhantomData,
+        }
+    }
+}
+
+fn check(code: jint) -> Result<(), JniError> {
+    if code == JNI_OK {
+        Ok(())
+    } else {
+        Err(JniError(code))
+    }
+}
+
+/// Failure status of a JNI invocation function, such as `JNI_EDETACHED`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct JniError(i32);
+
+impl JniError {
+    /// Returns the status code, one of the negative `JNI_E*` constants of
+    /// `jni.h`.
+    pub fn code(self) -> i32 {
+        self.0
+    }
+}
+
+impl Display for JniError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.0 {
+            -1 => "JNI_ERR",
+            -2 => "JNI_EDETACHED",
+            -3 => "JNI_EVERSION",
+            -4 => "JNI_ENOMEM",
+            -5 => "JNI_EEXIST",
+            -6 => "JNI_EINVAL",
+            code => return write!(formatter, "JNI error {}", code),
+        };
+        formatter.write_str(name)
+    }
+}
+
+impl Debug for JniError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "JniError({})", self)
+    }
+}
+
+#[cfg(any(not(no_error_in_core), feature = "std"))]
+impl StdError for JniError {}
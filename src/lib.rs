@@ -335,13 +335,21 @@
 //! <tr><td><a href="struct.CxxString.html">CxxString</a></td><td>std::string</td><td><sup><i>cannot be passed by value</i></sup></td></tr>
 //! <tr><td>Box&lt;T&gt;</td><td>rust::Box&lt;T&gt;</td><td><sup><i>cannot hold opaque C++ type</i></sup></td></tr>
 //! <tr><td><a href="struct.UniquePtr.html">UniquePtr&lt;T&gt;</a></td><td>std::unique_ptr&lt;T&gt;</td><td><sup><i>cannot hold opaque Rust type</i></sup></td></tr>
+//! <tr><td><a href="struct.UniqueArray.html">UniqueArray&lt;T&gt;</a></td><td>std::unique_ptr&lt;T[]&gt;</td><td><sup><i>cannot hold opaque Rust type</i></sup></td></tr>
 //! <tr><td><a href="struct.SharedPtr.html">SharedPtr&lt;T&gt;</a></td><td>std::shared_ptr&lt;T&gt;</td><td><sup><i>cannot hold opaque Rust type</i></sup></td></tr>
+//! <tr><td><a href="struct.SharedArray.html">SharedArray&lt;T&gt;</a></td><td>std::shared_ptr&lt;T[]&gt;</td><td><sup><i>cannot hold opaque Rust type</i></sup></td></tr>
+//! <tr><td><a href="struct.SharedConstPtr.html">SharedConstPtr&lt;T&gt;</a></td><td>std::shared_ptr&lt;const T&gt;</td><td><sup><i>cannot hold opaque Rust type</i></sup></td></tr>
+//! <tr><td><a href="struct.CustomPtr.html">CustomPtr&lt;P, T&gt;</a></td><td>P&lt;T&gt;</td><td><sup><i>P declared with #[smart_ptr], cannot hold opaque Rust type</i></sup></td></tr>
+//! <tr><td><a href="struct.ComPtr.html">ComPtr&lt;T&gt;</a></td><td>rust::ComPtr&lt;T&gt;</td><td><sup><i>T declared with #[com_interface]</i></sup></td></tr>
 //! <tr><td>[T; N]</td><td>std::array&lt;T, N&gt;</td><td><sup><i>cannot hold opaque C++ type</i></sup></td></tr>
 //! <tr><td>Vec&lt;T&gt;</td><td>rust::Vec&lt;T&gt;</td><td><sup><i>cannot hold opaque C++ type</i></sup></td></tr>
+//! <tr><td>&amp;HashMap&lt;K, V&gt;</td><td>rust::HashMap&lt;K, V&gt;</td><td><sup><i>read-only, cannot hold opaque C++ type</i></sup></td></tr>
 //! <tr><td><a href="struct.CxxVector.html">CxxVector&lt;T&gt;</a></td><td>std::vector&lt;T&gt;</td><td><sup><i>cannot be passed by value, cannot hold opaque Rust type</i></sup></td></tr>
+//! <tr><td><a href="struct.CxxPmrVector.html">CxxPmrVector&lt;T&gt;</a></td><td>std::pmr::vector&lt;T&gt;</td><td><sup><i>cannot be passed by value, cannot hold opaque Rust type, requires the c++17 feature</i></sup></td></tr>
 //! <tr><td>*mut T, *const T</td><td>T*, const T*</td><td><sup><i>fn with a raw pointer argument must be declared unsafe to call</i></sup></td></tr>
 //! <tr><td>fn(T, U) -&gt; V</td><td>rust::Fn&lt;V(T, U)&gt;</td><td><sup><i>only passing from Rust to C++ is implemented so far</i></sup></td></tr>
 //! <tr><td>Result&lt;T&gt;</td><td>throw/catch</td><td><sup><i>allowed as return type only</i></sup></td></tr>
+//! <tr><td>Result&lt;T, HRESULT&gt;</td><td>HRESULT with T* out-parameter</td><td><sup><i>allowed as return type of extern C++ functions only</i></sup></td></tr>
 //! </table>
 //!
 //! The C++ API of the `rust` namespace is defined by the *include/cxx.h* file
@@ -355,7 +363,7 @@
 //! <table>
 //! <tr><th>name in Rust</th><th>name in C++</th></tr>
 //! <tr><td>BTreeMap&lt;K, V&gt;</td><td><sup><i>tbd</i></sup></td></tr>
-//! <tr><td>HashMap&lt;K, V&gt;</td><td><sup><i>tbd</i></sup></td></tr>
+//! <tr><td>HashMap&lt;K, V&gt; <sup><i>by value or &amp;mut</i></sup></td><td><sup><i>tbd</i></sup></td></tr>
 //! <tr><td>Arc&lt;T&gt;</td><td><sup><i>tbd</i></sup></td></tr>
 //! <tr><td>Option&lt;T&gt;</td><td><sup><i>tbd</i></sup></td></tr>
 //! <tr><td><sup><i>tbd</i></sup></td><td>std::map&lt;K, V&gt;</td></tr>
@@ -439,38 +447,89 @@ compile_error! {
 #[macro_use]
 mod macros;
 
+pub mod com;
+mod com_ptr;
+mod custom_ptr;
+#[cfg(feature = "c++17")]
+mod cxx_pmr_string;
+#[cfg(feature = "c++17")]
+mod cxx_pmr_vector;
+#[cfg(feature = "std")]
+mod cxx_stream;
+mod cxx_test;
 mod cxx_vector;
 mod exception;
 mod extern_type;
 mod fmt;
 mod function;
 mod hash;
+mod hresult;
+#[cfg(feature = "jni")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jni")))]
+pub mod jni;
 mod lossy;
 pub mod memory;
 mod opaque;
+#[cfg(feature = "rayon")]
+mod par_iter;
+mod pin_mut;
+#[cfg(feature = "c++17")]
+#[cfg_attr(docsrs, doc(cfg(feature = "c++17")))]
+pub mod pmr;
 mod result;
+mod rust_hashmap;
 mod rust_slice;
 mod rust_str;
 mod rust_string;
 mod rust_type;
 mod rust_vec;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod shared_array;
+mod shared_const_ptr;
 mod shared_ptr;
 #[path = "cxx_string.rs"]
 mod string;
 mod symbols;
+#[cfg(feature = "telemetry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "telemetry")))]
+pub mod telemetry;
+#[cfg(not(feature = "telemetry"))]
+mod telemetry;
+#[cfg(feature = "trace")]
+#[cfg_attr(docsrs, doc(cfg(feature = "trace")))]
+pub mod trace;
+#[cfg(not(feature = "trace"))]
+mod trace;
 mod type_id;
+mod unique_array;
 mod unique_ptr;
 mod unwind;
 pub mod vector;
 mod weak_ptr;
 
+pub use crate::com_ptr::ComPtr;
+pub use crate::custom_ptr::CustomPtr;
+#[cfg(feature = "c++17")]
+#[cfg_attr(docsrs, doc(cfg(feature = "c++17")))]
+pub use crate::cxx_pmr_string::CxxPmrString;
+#[cfg(feature = "c++17")]
+#[cfg_attr(docsrs, doc(cfg(feature = "c++17")))]
+pub use crate::cxx_pmr_vector::CxxPmrVector;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use crate::cxx_stream::{CxxIstream, CxxOstream};
 pub use crate::cxx_vector::CxxVector;
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub use crate::exception::Exception;
 pub use crate::extern_type::{kind, ExternType};
+pub use crate::hresult::HRESULT;
+pub use crate::shared_array::SharedArray;
+pub use crate::shared_const_ptr::SharedConstPtr;
 pub use crate::shared_ptr::SharedPtr;
 pub use crate::string::CxxString;
+pub use crate::unique_array::UniqueArray;
 pub use crate::unique_ptr::UniquePtr;
 pub use crate::weak_ptr::WeakPtr;
 pub use cxxbridge_macro::bridge;
@@ -492,13 +551,23 @@ pub type Vector<T> = CxxVector<T>;
 // Not public API.
 #[doc(hidden)]
 pub mod private {
+    pub use crate::custom_ptr::CustomPtrTarget;
+    #[cfg(feature = "c++17")]
+    pub use crate::cxx_pmr_vector::PmrVectorElement;
+    pub use crate::cxx_test::run as run_cxx_test;
     pub use crate::cxx_vector::VectorElement;
     pub use crate::extern_type::{verify_extern_kind, verify_extern_type};
     pub use crate::function::FatFunction;
     pub use crate::hash::hash;
-    pub use crate::opaque::Opaque;
+    pub use crate::opaque::{AmbiguousIfUnpin, Opaque};
+    pub use crate::pin_mut::AsPinMut;
     #[cfg(feature = "alloc")]
-    pub use crate::result::{r#try, Result};
+    pub use crate::result::{
+        r#try, AsStdErrorParts, DisplayParts, ErrorKind, ExceptionParts, Result, StdErrorParts,
+        TryError,
+    };
+    #[cfg(feature = "std")]
+    pub use crate::rust_hashmap::RustHashMap;
     pub use crate::rust_slice::RustSlice;
     pub use crate::rust_str::RustStr;
     #[cfg(feature = "alloc")]
@@ -506,13 +575,20 @@ pub mod private {
     pub use crate::rust_type::{ImplBox, ImplVec, RustType};
     #[cfg(feature = "alloc")]
     pub use crate::rust_vec::RustVec;
+    pub use crate::shared_array::SharedArrayElement;
+    pub use crate::shared_const_ptr::SharedConstPtrTarget;
     pub use crate::shared_ptr::SharedPtrTarget;
     pub use crate::string::StackString;
+    #[cfg(feature = "trace")]
+    pub use crate::trace::{Direction as TraceDirection, Span as TraceSpan};
+    pub use crate::unique_array::UniqueArrayElement;
     pub use crate::unique_ptr::UniquePtrTarget;
     pub use crate::unwind::prevent_unwind;
     pub use crate::weak_ptr::WeakPtrTarget;
     pub use core::{concat, module_path};
     pub use cxxbridge_macro::type_id;
+    #[cfg(feature = "std")]
+    pub use std::collections::HashMap;
 }
 
 mod actually_private {
@@ -1,9 +1,14 @@
-//! Less used details of `UniquePtr` and `SharedPtr`.
+//! Less used details of `UniquePtr`, `UniqueArray`, `SharedPtr`,
+//! `SharedArray`, `SharedConstPtr`, and `CustomPtr`.
 //!
 //! The pointer types themselves are exposed at the crate root.
 
+pub use crate::custom_ptr::CustomPtrTarget;
+pub use crate::shared_array::{SharedArrayElement, SharedSlice};
+pub use crate::shared_const_ptr::SharedConstPtrTarget;
 pub use crate::shared_ptr::SharedPtrTarget;
+pub use crate::unique_array::{UniqueArrayElement, UniqueSlice};
 pub use crate::unique_ptr::UniquePtrTarget;
 pub use crate::weak_ptr::WeakPtrTarget;
 #[doc(no_inline)]
-pub use cxx::{SharedPtr, UniquePtr};
+pub use cxx::{CustomPtr, SharedArray, SharedConstPtr, SharedPtr, UniqueArray, UniquePtr};
@@ -2,7 +2,7 @@
 
 use crate::void;
 use core::cell::UnsafeCell;
-use core::marker::{PhantomData, PhantomPinned};
+use core::marker::{PhantomData, PhantomPinned, Unpin};
 use core::mem;
 use core::panic::RefUnwindSafe;
 
@@ -23,6 +23,20 @@ pub struct Opaque {
 
 impl RefUnwindSafe for Opaque {}
 
+// Used by the generated code to assert that an opaque C++ type has not been
+// given an Unpin impl. If there is only one impl of this trait for the type,
+// `<T as AmbiguousIfUnpin<_>>::infer` can infer `_` and compiles. Derived from
+// https://github.com/nvzqz/static-assertions-rs.
+pub trait AmbiguousIfUnpin<A> {
+    fn infer() {}
+}
+
+impl<T> AmbiguousIfUnpin<()> for T where T: ?Sized {}
+
+pub enum Invalid {}
+
+impl<T> AmbiguousIfUnpin<Invalid> for T where T: ?Sized + Unpin {}
+
 // TODO: https://github.com/rust-lang/rust/issues/95439
 #[repr(transparent)]
 struct SyncUnsafeCell<T>(UnsafeCell<T>);
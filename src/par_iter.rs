@@ -0,0 +1,99 @@
+use crate::cxx_vector::{CxxVector, Iter, VectorElement};
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+/// Parallel iterator over elements of a `CxxVector` by shared reference.
+///
+/// The iterator element type is `&'a T`. This is usually obtained through
+/// Rayon's `par_iter()` method on `&CxxVector<T>`.
+///
+/// Only read access to the vector happens during the iteration, which the C++
+/// standard library guarantees is safe to perform concurrently from multiple
+/// threads, so the elements only need to be `Sync`.
+///
+/// ```
+/// # #[cfg(feature = "rayon")]
+/// # mod example {
+/// use cxx::CxxVector;
+/// use rayon::prelude::*;
+///
+/// fn sum(vector: &CxxVector<u64>) -> u64 {
+///     vector.par_iter().sum()
+/// }
+/// # }
+/// ```
+pub struct ParIter<'a, T> {
+    v: &'a CxxVector<T>,
+}
+
+impl<'a, T> IntoParallelIterator for &'a CxxVector<T>
+where
+    T: VectorElement + Sync,
+{
+    type Item = &'a T;
+    type Iter = ParIter<'a, T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter { v: self }
+    }
+}
+
+impl<'a, T> ParallelIterator for ParIter<'a, T>
+where
+    T: VectorElement + Sync,
+{
+    type Item = &'a T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.v.len())
+    }
+}
+
+impl<'a, T> IndexedParallelIterator for ParIter<'a, T>
+where
+    T: VectorElement + Sync,
+{
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.v.len()
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.callback(IterProducer(self.v.iter()))
+    }
+}
+
+struct IterProducer<'a, T>(Iter<'a, T>);
+
+impl<'a, T> Producer for IterProducer<'a, T>
+where
+    T: VectorElement + Sync,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.0.split_at(index);
+        (IterProducer(left), IterProducer(right))
+    }
+}
@@ -0,0 +1,101 @@
+#![allow(missing_docs)]
+
+use crate::unique_ptr::{UniquePtr, UniquePtrTarget};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::pin::Pin;
+
+/// Calls one or more methods taking `self: Pin<&mut Self>` on the same C++
+/// object.
+///
+/// # Syntax
+///
+/// ```
+/// # use cxx::{pin_mut, CxxString, UniquePtr};
+/// # let mut receiver = UniquePtr::<CxxString>::from("");
+/// # let (a, b) = ("a", "b");
+/// pin_mut!(receiver => push_str(a), push_str(b));
+/// ```
+///
+/// The `receiver` is a mutable place of type `UniquePtr<T>`, `Pin<&mut T>`, or
+/// `Pin<Box<T>>`, or a mutable reference to one of those. Each method
+/// is called on a fresh reborrow of the same pinned reference, in order, which
+/// takes the place of writing `receiver.pin_mut()` or `receiver.as_mut()` in
+/// front of every call. The value of the macro is the return value of the last
+/// method.
+///
+/// # Panics
+///
+/// Panics if the receiver is a null `UniquePtr`, in the same way as
+/// [`UniquePtr::pin_mut`].
+///
+/// # Example
+///
+/// ```
+/// use cxx::{pin_mut, CxxString, UniquePtr};
+///
+/// fn greet(mut s: UniquePtr<CxxString>) -> UniquePtr<CxxString> {
+///     pin_mut!(s => clear(), push_str("hello"), push_str(" world"));
+///     s
+/// }
+/// #
+/// # fn main() {
+/// #     let s = greet(UniquePtr::from("..."));
+/// #     assert_eq!(*s, *"hello world");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! pin_mut {
+    (@calls $pin:ident $method:ident($($arg:expr),*)) => {
+        $pin.as_mut().$method($($arg),*)
+    };
+    (@calls $pin:ident $method:ident($($arg:expr),*), $($rest:tt)+) => {{
+        $pin.as_mut().$method($($arg),*);
+        $crate::pin_mut!(@calls $pin $($rest)+)
+    }};
+    ($receiver:expr => $($method:ident($($arg:expr),* $(,)?)),+ $(,)?) => {{
+        let mut pinned = {
+            use $crate::private::AsPinMut as _;
+            $receiver.as_pin_mut()
+        };
+        $crate::pin_mut!(@calls pinned $($method($($arg),*)),+)
+    }};
+}
+
+// Not public API. Used by the pin_mut macro to accept the various owners of a
+// pinned C++ object as the receiver.
+pub trait AsPinMut {
+    type Target: ?Sized;
+    fn as_pin_mut(&mut self) -> Pin<&mut Self::Target>;
+}
+
+impl<T> AsPinMut for UniquePtr<T>
+where
+    T: UniquePtrTarget,
+{
+    type Target = T;
+    fn as_pin_mut(&mut self) -> Pin<&mut T> {
+        self.pin_mut()
+    }
+}
+
+impl<'a, T> AsPinMut for Pin<&'a mut T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+    fn as_pin_mut(&mut self) -> Pin<&mut T> {
+        self.as_mut()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> AsPinMut for Pin<Box<T>>
+where
+    T: ?Sized,
+{
+    type Target = T;
+    fn as_pin_mut(&mut self) -> Pin<&mut T> {
+        self.as_mut()
+    }
+}
@@ -0,0 +1,80 @@
+//! Bindings to the C++17 polymorphic allocator containers.
+//!
+//! `std::pmr::string` and `std::pmr::vector<T>` are distinct C++ types from
+//! `std::string` and `std::vector<T>`, and are bound by the distinct Rust types
+//! [`CxxPmrString`] and [`CxxPmrVector`]. Every such container remembers the
+//! `std::pmr::memory_resource` it was constructed with and performs all of its
+//! allocations through it.
+//!
+//! These bindings are only available with the `c++17` feature of the cxx
+//! crate, which compiles cxx's C++ runtime as C++17 so that it provides the
+//! built in element types and `CxxPmrString`.
+
+use core::fmt::{self, Debug};
+use core::marker::{PhantomData, PhantomPinned};
+
+pub use crate::cxx_pmr_vector::{Iter, IterMut, PmrVectorElement};
+#[doc(no_inline)]
+pub use cxx::{CxxPmrString, CxxPmrVector};
+
+extern "C" {
+    #[link_name = "cxxbridge1$pmr$new_delete_resource"]
+    fn pmr_new_delete_resource() -> *mut MemoryResource;
+    #[link_name = "cxxbridge1$pmr$null_memory_resource"]
+    fn pmr_null_memory_resource() -> *mut MemoryResource;
+    #[link_name = "cxxbridge1$pmr$get_default_resource"]
+    fn pmr_get_default_resource() -> *mut MemoryResource;
+}
+
+/// Binding to C++ `std::pmr::memory_resource`.
+///
+/// A memory resource is an abstract interface to an allocator, which C++ code
+/// implements by subclassing. Rust code only ever handles a memory resource by
+/// raw pointer, as returned by the functions of this module or passed in from
+/// C++, because the containers that allocate from it do not keep it alive.
+#[repr(C)]
+pub struct MemoryResource {
+    _private: [u8; 0],
+    _pinned: PhantomData<PhantomPinned>,
+}
+
+impl Debug for MemoryResource {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("MemoryResource")
+    }
+}
+
+/// Returns the memory resource which allocates using global `operator new`.
+///
+/// Matches the behavior of C++
+/// [std::pmr::new_delete_resource][new_delete_resource].
+///
+/// [new_delete_resource]: https://en.cppreference.com/w/cpp/memory/new_delete_resource
+pub fn new_delete_resource() -> *mut MemoryResource {
+    unsafe { pmr_new_delete_resource() }
+}
+
+/// Returns a memory resource which fails every allocation.
+///
+/// Matches the behavior of C++
+/// [std::pmr::null_memory_resource][null_memory_resource]. A container
+/// constructed with this resource can never hold any heap allocated contents.
+/// Growing it from Rust terminates the process, because the
+/// `std::bad_alloc` thrown by the resource cannot cross the bridge.
+///
+/// [null_memory_resource]: https://en.cppreference.com/w/cpp/memory/null_memory_resource
+pub fn null_memory_resource() -> *mut MemoryResource {
+    unsafe { pmr_null_memory_resource() }
+}
+
+/// Returns the memory resource used by default constructed pmr containers.
+///
+/// Matches the behavior of C++
+/// [std::pmr::get_default_resource][get_default_resource]. Unless C++ code has
+/// called `std::pmr::set_default_resource`, this is the same as
+/// [`new_delete_resource()`].
+///
+/// [get_default_resource]: https://en.cppreference.com/w/cpp/memory/get_default_resource
+pub fn get_default_resource() -> *mut MemoryResource {
+    unsafe { pmr_get_default_resource() }
+}
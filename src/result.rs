@@ -4,11 +4,15 @@
 use crate::exception::Exception;
 use alloc::boxed::Box;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt::Display;
 use core::ptr::{self, NonNull};
 use core::result::Result as StdResult;
-use core::slice;
-use core::str;
+
+#[cfg(not(no_error_in_core))]
+use core::error::Error as StdError;
+#[cfg(all(feature = "std", no_error_in_core))]
+use std::error::Error as StdError;
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -23,7 +27,27 @@ pub union Result {
     ok: *const u8, // null
 }
 
-pub unsafe fn r#try<T, E>(ret: *mut T, result: StdResult<T, E>) -> Result
+// Everything about a Rust error that is carried over into the C++ rust::Error.
+pub struct ErrorParts {
+    what: String,
+    code: i32,
+    sources: Vec<String>,
+}
+
+// The error returned by an extern "Rust" function, together with whatever
+// parts were extracted from it while its concrete type was still known.
+pub struct TryError<E> {
+    err: E,
+    parts: Option<ErrorParts>,
+}
+
+impl<E> TryError<E> {
+    pub fn new(err: E, parts: Option<ErrorParts>) -> Self {
+        TryError { err, parts }
+    }
+}
+
+pub unsafe fn r#try<T, E>(ret: *mut T, result: StdResult<T, TryError<E>>) -> Result
 where
     E: Display,
 {
@@ -32,37 +56,130 @@ where
             unsafe { ptr::write(ret, ok) }
             Result { ok: ptr::null() }
         }
-        Err(err) => unsafe { to_c_error(err.to_string()) },
+        Err(TryError { err, parts }) => {
+            let parts = parts.unwrap_or_else(|| ErrorParts {
+                what: err.to_string(),
+                code: 0,
+                sources: Vec::new(),
+            });
+            unsafe { to_c_error(parts) }
+        }
     }
 }
 
-unsafe fn to_c_error(msg: String) -> Result {
-    let ptr = msg.as_ptr();
-    let len = msg.len();
+unsafe fn to_c_error(err: ErrorParts) -> Result {
+    let sources: Vec<PtrLen> = err.sources.iter().map(|source| ptr_len(source)).collect();
+    let what = ptr_len(&err.what);
 
     extern "C" {
         #[link_name = "cxxbridge1$error"]
-        fn error(ptr: *const u8, len: usize) -> NonNull<u8>;
+        fn error(what: PtrLen, code: i32, sources: *const PtrLen, count: usize) -> NonNull<u8>;
     }
 
-    let copy = unsafe { error(ptr, len) };
-    let err = PtrLen { ptr: copy, len };
+    let copy = unsafe { error(what, err.code, sources.as_ptr(), sources.len()) };
+    let err = PtrLen {
+        ptr: copy,
+        len: what.len,
+    };
     Result { err }
 }
 
+fn ptr_len(s: &str) -> PtrLen {
+    PtrLen {
+        ptr: NonNull::from(s).cast::<u8>(),
+        len: s.len(),
+    }
+}
+
 impl Result {
     pub unsafe fn exception(self) -> StdResult<(), Exception> {
         unsafe {
             if self.ok.is_null() {
                 Ok(())
             } else {
-                let err = self.err;
-                let slice = slice::from_raw_parts_mut(err.ptr.as_ptr(), err.len);
-                let s = str::from_utf8_unchecked_mut(slice);
-                Err(Exception {
-                    what: Box::from_raw(s),
-                })
+                let exception = self.err.ptr.as_ptr().cast::<Exception>();
+                Err(*Box::from_raw(exception))
             }
         }
     }
 }
+
+// Picks the most informative conversion that the error type supports, by
+// autoref specialization on the concrete error type, in the wrapper around the
+// user's function where that type is still nameable:
+//
+//     (&&&&ErrorKind(err)).parts()
+//
+// A cxx::Exception keeps its code and sources, and a std::error::Error (or
+// something like anyhow::Error that can be viewed as one) contributes its
+// source() chain. For anything else there are no parts beyond the Display impl
+// that r#try requires.
+pub struct ErrorKind<'a, E: ?Sized>(pub &'a E);
+
+pub trait ExceptionParts {
+    fn parts(&self) -> Option<ErrorParts>;
+}
+
+impl<'a> ExceptionParts for &&&ErrorKind<'a, Exception> {
+    fn parts(&self) -> Option<ErrorParts> {
+        let exception = self.0;
+        Some(ErrorParts {
+            what: exception.what().to_string(),
+            code: exception.code().unwrap_or(0),
+            sources: exception.sources().map(str::to_string).collect(),
+        })
+    }
+}
+
+pub trait StdErrorParts {
+    fn parts(&self) -> Option<ErrorParts>;
+}
+
+#[cfg(any(not(no_error_in_core), feature = "std"))]
+impl<'a, E> StdErrorParts for &&ErrorKind<'a, E>
+where
+    E: StdError,
+{
+    fn parts(&self) -> Option<ErrorParts> {
+        Some(error_parts(self.0))
+    }
+}
+
+pub trait AsStdErrorParts {
+    fn parts(&self) -> Option<ErrorParts>;
+}
+
+#[cfg(any(not(no_error_in_core), feature = "std"))]
+impl<'a, E> AsStdErrorParts for &ErrorKind<'a, E>
+where
+    E: ?Sized + AsRef<dyn StdError>,
+{
+    fn parts(&self) -> Option<ErrorParts> {
+        Some(error_parts(self.0.as_ref()))
+    }
+}
+
+#[cfg(any(not(no_error_in_core), feature = "std"))]
+fn error_parts(error: &dyn StdError) -> ErrorParts {
+    let mut sources = Vec::new();
+    let mut next = error.source();
+    while let Some(source) = next {
+        sources.push(source.to_string());
+        next = source.source();
+    }
+    ErrorParts {
+        what: error.to_string(),
+        code: 0,
+        sources,
+    }
+}
+
+pub trait DisplayParts {
+    fn parts(&self) -> Option<ErrorParts>;
+}
+
+impl<'a, E: ?Sized> DisplayParts for ErrorKind<'a, E> {
+    fn parts(&self) -> Option<ErrorParts> {
+        None
+    }
+}
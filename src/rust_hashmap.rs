@@ -0,0 +1,79 @@
+#![cfg(feature = "std")]
+#![allow(missing_docs)]
+
+use core::ffi::c_void;
+use core::hash::Hash;
+use core::mem;
+use std::collections::HashMap;
+
+type Visit<K, V> = unsafe extern "C" fn(f: *mut c_void, key: *const K, value: *const V);
+
+// ABI compatible with C++ rust::HashMap<K, V> (not necessarily &HashMap<K, V>).
+//
+// The map's methods are monomorphized into the function pointers at the point
+// where the bridge borrows the map, so C++ gets to query any HashMap<K, V>
+// without a separately exported symbol per instantiation.
+#[repr(C)]
+pub struct RustHashMap<K, V> {
+    map: *const c_void,
+    len: unsafe extern "C" fn(map: *const c_void) -> usize,
+    lookup: unsafe extern "C" fn(map: *const c_void, key: *const K) -> *const V,
+    iterate: unsafe extern "C" fn(map: *const c_void, f: *mut c_void, visit: Visit<K, V>),
+}
+
+impl<K, V> RustHashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    pub fn from(map: &HashMap<K, V>) -> Self {
+        RustHashMap {
+            map: (map as *const HashMap<K, V>).cast(),
+            len: len::<K, V>,
+            lookup: lookup::<K, V>,
+            iterate: iterate::<K, V>,
+        }
+    }
+
+    pub unsafe fn as_hash_map<'a>(self) -> &'a HashMap<K, V> {
+        unsafe { &*self.map.cast::<HashMap<K, V>>() }
+    }
+}
+
+unsafe extern "C" fn len<K, V>(map: *const c_void) -> usize {
+    // No prevent_unwind: cannot panic.
+    let map = unsafe { &*map.cast::<HashMap<K, V>>() };
+    map.len()
+}
+
+unsafe extern "C" fn lookup<K, V>(map: *const c_void, key: *const K) -> *const V
+where
+    K: Eq + Hash,
+{
+    // Hashing and comparing the key runs the key type's Hash and Eq impls.
+    crate::unwind::prevent_unwind("rust::HashMap::get", || {
+        let map = unsafe { &*map.cast::<HashMap<K, V>>() };
+        let key = unsafe { &*key };
+        match map.get(key) {
+            Some(value) => value,
+            None => core::ptr::null(),
+        }
+    })
+}
+
+unsafe extern "C" fn iterate<K, V>(map: *const c_void, f: *mut c_void, visit: Visit<K, V>) {
+    // No prevent_unwind: the C++ callback is noexcept and iteration itself
+    // cannot panic.
+    let map = unsafe { &*map.cast::<HashMap<K, V>>() };
+    for (key, value) in map {
+        unsafe { visit(f, key, value) }
+    }
+}
+
+const_assert_eq!(
+    mem::size_of::<[usize; 4]>(),
+    mem::size_of::<RustHashMap<u8, u8>>(),
+);
+const_assert_eq!(
+    mem::align_of::<usize>(),
+    mem::align_of::<RustHashMap<u8, u8>>(),
+);
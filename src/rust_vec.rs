@@ -2,6 +2,7 @@
 #![allow(missing_docs)]
 
 use crate::rust_string::RustString;
+use crate::telemetry::{self, Origin};
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::ffi::c_void;
@@ -61,7 +62,15 @@ impl<T> RustVec<T> {
         let vec = self.as_mut_vec();
         if new_cap > vec.capacity() {
             let additional = new_cap - vec.len();
+            let before = telemetry::capacity_before(|| vec.capacity());
             vec.reserve(additional);
+            telemetry::record_growth(
+                Origin::CxxRequested,
+                "rust::Vec",
+                mem::size_of::<T>(),
+                before,
+                || vec.capacity(),
+            );
         }
     }
 
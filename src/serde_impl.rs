@@ -0,0 +1,290 @@
+use crate::cxx_vector::{CxxVector, VectorElement};
+use crate::kind::Trivial;
+use crate::memory::{SharedPtrTarget, UniquePtrTarget};
+use crate::shared_ptr::SharedPtr;
+use crate::string::CxxString;
+use crate::unique_ptr::UniquePtr;
+use crate::ExternType;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Serialized as a string if the contents are UTF-8, otherwise as bytes.
+///
+/// C++ strings are not required to hold any particular encoding, so the
+/// fallback keeps arbitrary contents round-trippable rather than failing or
+/// silently replacing invalid sequences.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for CxxString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.to_str() {
+            Ok(s) => serializer.serialize_str(s),
+            Err(_) => serializer.serialize_bytes(self.as_bytes()),
+        }
+    }
+}
+
+/// Serialized as a sequence of the elements.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<T> Serialize for CxxVector<T>
+where
+    T: VectorElement + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self)
+    }
+}
+
+/// Serialized as a sequence of the elements.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for CxxVector<bool> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self)
+    }
+}
+
+/// Serialized as an optional value, with null as none.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<T> Serialize for UniquePtr<T>
+where
+    T: UniquePtrTarget + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.as_ref() {
+            Some(value) => serializer.serialize_some(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Serialized as an optional value, with null as none.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<T> Serialize for SharedPtr<T>
+where
+    T: SharedPtrTarget + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.as_ref() {
+            Some(value) => serializer.serialize_some(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Deserialized from an optional string or bytes into a newly allocated C++
+/// string, with none as null.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for UniquePtr<CxxString> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(OptionVisitor(CxxStringVisitor))
+    }
+}
+
+/// Deserialized from an optional sequence into a newly allocated C++ vector,
+/// with none as null.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, T> Deserialize<'de> for UniquePtr<CxxVector<T>>
+where
+    T: VectorElement + ExternType<Kind = Trivial> + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(OptionVisitor(CxxVectorVisitor(PhantomData)))
+    }
+}
+
+/// Deserialized from an optional sequence into a newly allocated C++ vector,
+/// with none as null.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for UniquePtr<CxxVector<bool>> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(OptionVisitor(CxxVectorBoolVisitor))
+    }
+}
+
+struct OptionVisitor<V>(V);
+
+impl<'de, V, T> Visitor<'de> for OptionVisitor<V>
+where
+    V: Visitor<'de, Value = UniquePtr<T>> + InnerDeserialize<'de>,
+    T: UniquePtrTarget,
+{
+    type Value = UniquePtr<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("optional ")?;
+        self.0.expecting(formatter)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(UniquePtr::null())
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(UniquePtr::null())
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.0.deserialize_inner(deserializer)
+    }
+}
+
+// How the visitor for the pointee asks the deserializer for its data.
+trait InnerDeserialize<'de>: Visitor<'de> {
+    fn deserialize_inner<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+struct CxxStringVisitor;
+
+impl<'de> Visitor<'de> for CxxStringVisitor {
+    type Value = UniquePtr<CxxString>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("string or bytes")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(UniquePtr::from(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(UniquePtr::from(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // Bytes in a format without a native byte string type, such as JSON.
+        let mut string = UniquePtr::from(&[][..]);
+        if let Some(additional) = seq.size_hint() {
+            string.pin_mut().reserve(additional);
+        }
+        while let Some(byte) = seq.next_element::<u8>()? {
+            string.pin_mut().push_bytes(&[byte]);
+        }
+        Ok(string)
+    }
+}
+
+impl<'de> InnerDeserialize<'de> for CxxStringVisitor {
+    fn deserialize_inner<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(self)
+    }
+}
+
+struct CxxVectorVisitor<T>(PhantomData<fn() -> T>);
+
+impl<'de, T> Visitor<'de> for CxxVectorVisitor<T>
+where
+    T: VectorElement + ExternType<Kind = Trivial> + Deserialize<'de>,
+{
+    type Value = UniquePtr<CxxVector<T>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut vector = CxxVector::<T>::new();
+        if let Some(additional) = seq.size_hint() {
+            vector.pin_mut().reserve(additional);
+        }
+        while let Some(element) = seq.next_element()? {
+            vector.pin_mut().push(element);
+        }
+        Ok(vector)
+    }
+}
+
+impl<'de, T> InnerDeserialize<'de> for CxxVectorVisitor<T>
+where
+    T: VectorElement + ExternType<Kind = Trivial> + Deserialize<'de>,
+{
+    fn deserialize_inner<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+struct CxxVectorBoolVisitor;
+
+impl<'de> Visitor<'de> for CxxVectorBoolVisitor {
+    type Value = UniquePtr<CxxVector<bool>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("sequence of bool")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut vector = CxxVector::<bool>::new();
+        if let Some(additional) = seq.size_hint() {
+            vector.pin_mut().reserve(additional);
+        }
+        while let Some(element) = seq.next_element()? {
+            vector.pin_mut().push(element);
+        }
+        Ok(vector)
+    }
+}
+
+impl<'de> InnerDeserialize<'de> for CxxVectorBoolVisitor {
+    fn deserialize_inner<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
@@ -0,0 +1,424 @@
+use crate::extern_type::ExternType;
+use crate::fmt::display;
+use crate::kind::Trivial;
+use crate::unique_array::{UniqueArray, UniqueArrayElement};
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::{Bound, Deref, RangeBounds};
+use core::slice;
+
+/// Binding to C++ `std::shared_ptr<T[]>`.
+///
+/// A SharedArray shares ownership of an array allocated by `new T[n]`, which
+/// is destroyed with `delete[]` when the last owner goes away. As with
+/// [`SharedPtr`][crate::SharedPtr], the pointer stored in a SharedArray need
+/// not be the start of the managed array: [`offset`][SharedArray::offset]
+/// uses the aliasing constructor of std::shared\_ptr to make a SharedArray
+/// pointing into the middle of the array that keeps the whole array alive.
+///
+/// Like the C++ type, a SharedArray does not know how many elements the array
+/// has. An `extern "C++"` function declared with `#[array_len = n]` returns the
+/// array paired with its length as a [`SharedSlice`], which supports safe
+/// element access and sub-slicing without copying.
+///
+/// Array support in std::shared\_ptr requires C++17. The impls for primitive
+/// element types are only available with the `c++17` feature of this crate,
+/// which builds its side of the glue in that language mode.
+#[repr(C)]
+pub struct SharedArray<T>
+where
+    T: SharedArrayElement,
+{
+    repr: [MaybeUninit<*mut c_void>; 2],
+    ty: PhantomData<[T]>,
+}
+
+impl<T> SharedArray<T>
+where
+    T: SharedArrayElement,
+{
+    /// Makes a new SharedArray that is both empty and null.
+    ///
+    /// Matches the behavior of default-constructing a std::shared\_ptr.
+    pub fn null() -> Self {
+        let mut shared_array = MaybeUninit::<SharedArray<T>>::uninit();
+        let new = shared_array.as_mut_ptr().cast();
+        unsafe {
+            T::__shared_array_null(new);
+            shared_array.assume_init()
+        }
+    }
+
+    /// Creates a SharedArray taking ownership of an array allocated in C++.
+    ///
+    /// # Safety
+    ///
+    /// Pointer must either be null or point to an array heap-allocated in C++
+    /// by `new T[n]`.
+    pub unsafe fn from_raw(raw: *mut T) -> Self {
+        let mut shared_array = MaybeUninit::<SharedArray<T>>::uninit();
+        let new = shared_array.as_mut_ptr().cast();
+        unsafe {
+            T::__shared_array_raw(new, raw);
+            shared_array.assume_init()
+        }
+    }
+
+    /// Checks whether the SharedArray holds a null stored pointer.
+    pub fn is_null(&self) -> bool {
+        self.as_ptr().is_null()
+    }
+
+    /// Returns the SharedArray's stored pointer as a raw const pointer.
+    pub fn as_ptr(&self) -> *const T {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__shared_array_get(this) }
+    }
+
+    /// Returns the SharedArray's stored pointer as a raw mutable pointer.
+    ///
+    /// As with [`SharedPtr::as_mut_ptr`][crate::SharedPtr::as_mut_ptr], this
+    /// doesn't require that you hold an exclusive reference to the SharedArray,
+    /// and other owners may be accessing the same elements.
+    pub fn as_mut_ptr(&self) -> *mut T {
+        self.as_ptr() as *mut T
+    }
+
+    /// Returns the number of SharedArray instances sharing ownership of the
+    /// managed array, including this one, or 0 if this SharedArray is empty.
+    pub fn strong_count(&self) -> usize {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__shared_array_use_count(this) }
+    }
+
+    /// Makes a SharedArray that shares ownership with this one but whose
+    /// stored pointer is `count` elements further into the array.
+    ///
+    /// Matches the behavior of the std::shared\_ptr aliasing constructor
+    /// `shared_ptr(const shared_ptr<T[]>& r, T* ptr)` with `ptr` being
+    /// `r.get() + count`.
+    ///
+    /// # Safety
+    ///
+    /// The offset element must be within the array, or one past its end.
+    pub unsafe fn offset(&self, count: usize) -> Self {
+        let mut shared_array = MaybeUninit::<SharedArray<T>>::uninit();
+        let new = shared_array.as_mut_ptr().cast();
+        let this = self as *const Self as *const c_void;
+        unsafe {
+            T::__shared_array_alias(this, count, new);
+            shared_array.assume_init()
+        }
+    }
+
+    /// Returns a slice of the first `len` elements starting at the stored
+    /// pointer.
+    ///
+    /// # Safety
+    ///
+    /// The array must be non-null and have at least `len` elements from the
+    /// stored pointer onward, unless `len` is 0. No other owner may mutate
+    /// those elements while the slice is alive.
+    pub unsafe fn as_slice(&self, len: usize) -> &[T]
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        if len == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.as_ptr(), len) }
+        }
+    }
+}
+
+unsafe impl<T> Send for SharedArray<T> where T: Send + Sync + SharedArrayElement {}
+unsafe impl<T> Sync for SharedArray<T> where T: Send + Sync + SharedArrayElement {}
+
+impl<T> Clone for SharedArray<T>
+where
+    T: SharedArrayElement,
+{
+    fn clone(&self) -> Self {
+        let mut shared_array = MaybeUninit::<SharedArray<T>>::uninit();
+        let new = shared_array.as_mut_ptr().cast();
+        let this = self as *const Self as *const c_void;
+        unsafe {
+            T::__shared_array_clone(this, new);
+            shared_array.assume_init()
+        }
+    }
+}
+
+// SharedArray is not a self-referential type and is safe to move out of a Pin,
+// regardless whether the elements are Unpin.
+impl<T> Unpin for SharedArray<T> where T: SharedArrayElement {}
+
+impl<T> Drop for SharedArray<T>
+where
+    T: SharedArrayElement,
+{
+    fn drop(&mut self) {
+        let this = self as *mut Self as *mut c_void;
+        unsafe { T::__shared_array_drop(this) }
+    }
+}
+
+impl<T> Debug for SharedArray<T>
+where
+    T: SharedArrayElement,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_null() {
+            formatter.write_str("nullptr")
+        } else {
+            write!(
+                formatter,
+                "SharedArray<{}>({:p})",
+                display(T::__typename),
+                self.as_ptr(),
+            )
+        }
+    }
+}
+
+impl<T> From<UniqueArray<T>> for SharedArray<T>
+where
+    T: UniqueArrayElement + SharedArrayElement,
+{
+    fn from(unique: UniqueArray<T>) -> Self {
+        if unique.is_null() {
+            SharedArray::null()
+        } else {
+            unsafe { SharedArray::from_raw(unique.into_raw()) }
+        }
+    }
+}
+
+/// A [`SharedArray`] together with the number of elements from its stored
+/// pointer onward.
+///
+/// This is what an `extern "C++"` function declared with `#[array_len = n]`
+/// returns on the Rust side, with the length taken from the function's `n`
+/// argument. Sub-slices share ownership of the same array, so a buffer handed
+/// out by C++ can be split up and held in pieces without copying. For trivial
+/// element types it dereferences to a slice.
+pub struct SharedSlice<T>
+where
+    T: SharedArrayElement,
+{
+    array: SharedArray<T>,
+    len: usize,
+}
+
+impl<T> SharedSlice<T>
+where
+    T: SharedArrayElement,
+{
+    /// Pairs an array with its length.
+    ///
+    /// A null array is treated as empty regardless of `len`.
+    ///
+    /// # Safety
+    ///
+    /// A non-null array must have at least `len` elements from its stored
+    /// pointer onward.
+    pub unsafe fn from_raw_parts(array: SharedArray<T>, len: usize) -> Self {
+        let len = if array.is_null() { 0 } else { len };
+        SharedSlice { array, len }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the stored pointer of the underlying array.
+    pub fn as_ptr(&self) -> *const T {
+        self.array.as_ptr()
+    }
+
+    /// Makes a SharedSlice of a subrange of this one, sharing ownership of the
+    /// same array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, the same way as indexing a slice
+    /// by a range does.
+    pub fn slice<R>(&self, range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        if start > end || end > self.len {
+            panic!(
+                "range {}..{} out of bounds for SharedSlice<{}> of length {}",
+                start,
+                end,
+                display(T::__typename),
+                self.len,
+            );
+        }
+        let array = if start == 0 {
+            self.array.clone()
+        } else {
+            unsafe { self.array.offset(start) }
+        };
+        SharedSlice {
+            array,
+            len: end - start,
+        }
+    }
+
+    /// Separates the array from its length.
+    pub fn into_raw_parts(self) -> (SharedArray<T>, usize) {
+        (self.array, self.len)
+    }
+}
+
+impl<T> Clone for SharedSlice<T>
+where
+    T: SharedArrayElement,
+{
+    fn clone(&self) -> Self {
+        SharedSlice {
+            array: self.array.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Deref for SharedSlice<T>
+where
+    T: SharedArrayElement + ExternType<Kind = Trivial>,
+{
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.array.as_slice(self.len) }
+    }
+}
+
+impl<T> Debug for SharedSlice<T>
+where
+    T: SharedArrayElement + ExternType<Kind = Trivial> + Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&**self, formatter)
+    }
+}
+
+/// Trait bound for types which may be used as the `T` inside of a
+/// `SharedArray<T>` in generic code.
+///
+/// This trait has no publicly callable or implementable methods. Implementing
+/// it outside of the CXX codebase is done for you by the `cxx::bridge` macro
+/// for any type that appears as `SharedArray<MyType>` in the bridge, or
+/// explicitly by writing `impl SharedArray<MyType> {}`.
+pub unsafe trait SharedArrayElement: Sized {
+    #[doc(hidden)]
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result;
+    #[doc(hidden)]
+    unsafe fn __shared_array_null(new: *mut c_void);
+    #[doc(hidden)]
+    unsafe fn __shared_array_raw(new: *mut c_void, raw: *mut Self);
+    #[doc(hidden)]
+    unsafe fn __shared_array_clone(this: *const c_void, new: *mut c_void);
+    #[doc(hidden)]
+    unsafe fn __shared_array_alias(this: *const c_void, offset: usize, new: *mut c_void);
+    #[doc(hidden)]
+    unsafe fn __shared_array_get(this: *const c_void) -> *const Self;
+    #[doc(hidden)]
+    unsafe fn __shared_array_use_count(this: *const c_void) -> usize;
+    #[doc(hidden)]
+    unsafe fn __shared_array_drop(this: *mut c_void);
+}
+
+macro_rules! impl_shared_array_element_for_primitive {
+    ($ty:ident) => {
+        #[cfg(feature = "c++17")]
+        unsafe impl SharedArrayElement for $ty {
+            fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(stringify!($ty))
+            }
+            unsafe fn __shared_array_null(new: *mut c_void) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$shared_ptr$array$", stringify!($ty), "$null")]
+                    fn __null(new: *mut c_void);
+                }
+                unsafe { __null(new) }
+            }
+            unsafe fn __shared_array_raw(new: *mut c_void, raw: *mut Self) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$shared_ptr$array$", stringify!($ty), "$raw")]
+                    fn __raw(new: *mut c_void, raw: *mut $ty);
+                }
+                unsafe { __raw(new, raw) }
+            }
+            unsafe fn __shared_array_clone(this: *const c_void, new: *mut c_void) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$shared_ptr$array$", stringify!($ty), "$clone")]
+                    fn __clone(this: *const c_void, new: *mut c_void);
+                }
+                unsafe { __clone(this, new) }
+            }
+            unsafe fn __shared_array_alias(this: *const c_void, offset: usize, new: *mut c_void) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$shared_ptr$array$", stringify!($ty), "$alias")]
+                    fn __alias(this: *const c_void, offset: usize, new: *mut c_void);
+                }
+                unsafe { __alias(this, offset, new) }
+            }
+            unsafe fn __shared_array_get(this: *const c_void) -> *const Self {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$shared_ptr$array$", stringify!($ty), "$get")]
+                    fn __get(this: *const c_void) -> *const $ty;
+                }
+                unsafe { __get(this) }
+            }
+            unsafe fn __shared_array_use_count(this: *const c_void) -> usize {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$shared_ptr$array$", stringify!($ty), "$use_count")]
+                    fn __use_count(this: *const c_void) -> usize;
+                }
+                unsafe { __use_count(this) }
+            }
+            unsafe fn __shared_array_drop(this: *mut c_void) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$shared_ptr$array$", stringify!($ty), "$drop")]
+                    fn __drop(this: *mut c_void);
+                }
+                unsafe { __drop(this) }
+            }
+        }
+    };
+}
+
+impl_shared_array_element_for_primitive!(bool);
+impl_shared_array_element_for_primitive!(u8);
+impl_shared_array_element_for_primitive!(u16);
+impl_shared_array_element_for_primitive!(u32);
+impl_shared_array_element_for_primitive!(u64);
+impl_shared_array_element_for_primitive!(usize);
+impl_shared_array_element_for_primitive!(i8);
+impl_shared_array_element_for_primitive!(i16);
+impl_shared_array_element_for_primitive!(i32);
+impl_shared_array_element_for_primitive!(i64);
+impl_shared_array_element_for_primitive!(isize);
+impl_shared_array_element_for_primitive!(f32);
+impl_shared_array_element_for_primitive!(f64);
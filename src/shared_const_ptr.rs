@@ -0,0 +1,338 @@
+use crate::fmt::display;
+use crate::shared_ptr::{SharedPtr, SharedPtrTarget};
+use crate::string::CxxString;
+use core::cmp::Ordering;
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::Deref;
+
+/// Binding to C++ `std::shared_ptr<const T>`.
+///
+/// This is the type for C++ APIs that hand out shared ownership of an object
+/// while promising that nobody holding the pointer will modify it. Compared to
+/// [`SharedPtr<T>`], a SharedConstPtr only gives access to the target through a
+/// shared reference `&T`, so only methods taking `&self`, meaning const member
+/// functions in C++, can be called through it. There is no counterpart to
+/// [`SharedPtr::pin_mut_unchecked`] or [`SharedPtr::as_mut_ptr`].
+///
+/// ```
+/// #[cxx::bridge]
+/// mod ffi {
+///     unsafe extern "C++" {
+///         include!("example/include/config.h");
+///
+///         type Config;
+///
+///         fn get_name(self: &Config) -> &CxxString;
+///         fn set_name(self: Pin<&mut Config>, name: &str);
+///
+///         // std::shared_ptr<const Config> current_config();
+///         fn current_config() -> SharedConstPtr<Config>;
+///     }
+/// }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// A `SharedPtr<T>` converts into a `SharedConstPtr<T>` through `From`,
+/// sharing ownership with the original, the same way C++ converts
+/// `std::shared_ptr<T>` to `std::shared_ptr<const T>` implicitly. There is no
+/// conversion in the other direction.
+///
+/// The same caveats about the difference between the managed and stored
+/// pointer, and between **empty** and **null**, apply as described on
+/// [`SharedPtr`].
+#[repr(C)]
+pub struct SharedConstPtr<T>
+where
+    T: SharedConstPtrTarget,
+{
+    repr: [MaybeUninit<*mut c_void>; 2],
+    ty: PhantomData<T>,
+}
+
+impl<T> SharedConstPtr<T>
+where
+    T: SharedConstPtrTarget,
+{
+    /// Makes a new SharedConstPtr that is both **empty** and **null**.
+    ///
+    /// Matches the behavior of default-constructing a std::shared\_ptr.
+    pub fn null() -> Self {
+        let mut shared_ptr = MaybeUninit::<SharedConstPtr<T>>::uninit();
+        let new = shared_ptr.as_mut_ptr().cast();
+        unsafe {
+            T::__shared_const_ptr_null(new);
+            shared_ptr.assume_init()
+        }
+    }
+
+    /// Checks whether the SharedConstPtr holds a null stored pointer.
+    ///
+    /// This is the opposite of [std::shared_ptr\<T\>::operator bool](https://en.cppreference.com/w/cpp/memory/shared_ptr/operator_bool).
+    /// As with [`SharedPtr::is_null`], it is unrelated to the state of the
+    /// reference count.
+    pub fn is_null(&self) -> bool {
+        self.as_ptr().is_null()
+    }
+
+    /// Returns a reference to the object pointed to by the stored pointer if
+    /// nonnull, otherwise None.
+    ///
+    /// <div class="warning">
+    ///
+    /// The shared pointer's managed object may or may not already have been
+    /// destroyed.
+    ///
+    /// </div>
+    pub fn as_ref(&self) -> Option<&T> {
+        let ptr = self.as_ptr();
+        unsafe { ptr.as_ref() }
+    }
+
+    /// Returns the SharedConstPtr's stored pointer as a raw const pointer.
+    pub fn as_ptr(&self) -> *const T {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__shared_const_ptr_get(this) }
+    }
+
+    /// Returns the number of shared pointer instances sharing ownership of the
+    /// managed object, including this one, or 0 if this SharedConstPtr is
+    /// **empty**.
+    ///
+    /// Instances of `SharedPtr<T>` which share ownership with this one are
+    /// included in the count.
+    pub fn strong_count(&self) -> usize {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__shared_const_ptr_use_count(this) }
+    }
+}
+
+unsafe impl<T> Send for SharedConstPtr<T> where T: Send + Sync + SharedConstPtrTarget {}
+unsafe impl<T> Sync for SharedConstPtr<T> where T: Send + Sync + SharedConstPtrTarget {}
+
+impl<T> Clone for SharedConstPtr<T>
+where
+    T: SharedConstPtrTarget,
+{
+    fn clone(&self) -> Self {
+        let mut shared_ptr = MaybeUninit::<SharedConstPtr<T>>::uninit();
+        let new = shared_ptr.as_mut_ptr().cast();
+        let this = self as *const Self as *const c_void;
+        unsafe {
+            T::__shared_const_ptr_clone(this, new);
+            shared_ptr.assume_init()
+        }
+    }
+}
+
+// SharedConstPtr is not a self-referential type and is safe to move out of a
+// Pin, regardless whether the pointer's target is Unpin.
+impl<T> Unpin for SharedConstPtr<T> where T: SharedConstPtrTarget {}
+
+impl<T> Drop for SharedConstPtr<T>
+where
+    T: SharedConstPtrTarget,
+{
+    fn drop(&mut self) {
+        let this = self as *mut Self as *mut c_void;
+        unsafe { T::__shared_const_ptr_drop(this) }
+    }
+}
+
+impl<T> Deref for SharedConstPtr<T>
+where
+    T: SharedConstPtrTarget,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self.as_ref() {
+            Some(target) => target,
+            None => panic!(
+                "called deref on a null SharedConstPtr<{}>",
+                display(T::__typename),
+            ),
+        }
+    }
+}
+
+impl<T> Debug for SharedConstPtr<T>
+where
+    T: Debug + SharedConstPtrTarget,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_ref() {
+            None => formatter.write_str("nullptr"),
+            Some(value) => Debug::fmt(value, formatter),
+        }
+    }
+}
+
+impl<T> Display for SharedConstPtr<T>
+where
+    T: Display + SharedConstPtrTarget,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_ref() {
+            None => formatter.write_str("nullptr"),
+            Some(value) => Display::fmt(value, formatter),
+        }
+    }
+}
+
+impl<T> PartialEq for SharedConstPtr<T>
+where
+    T: PartialEq + SharedConstPtrTarget,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<T> Eq for SharedConstPtr<T> where T: Eq + SharedConstPtrTarget {}
+
+impl<T> PartialOrd for SharedConstPtr<T>
+where
+    T: PartialOrd + SharedConstPtrTarget,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&self.as_ref(), &other.as_ref())
+    }
+}
+
+impl<T> Ord for SharedConstPtr<T>
+where
+    T: Ord + SharedConstPtrTarget,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&self.as_ref(), &other.as_ref())
+    }
+}
+
+impl<T> Hash for SharedConstPtr<T>
+where
+    T: Hash + SharedConstPtrTarget,
+{
+    fn hash<H>(&self, hasher: &mut H)
+    where
+        H: Hasher,
+    {
+        self.as_ref().hash(hasher);
+    }
+}
+
+impl<T> From<SharedPtr<T>> for SharedConstPtr<T>
+where
+    T: SharedPtrTarget + SharedConstPtrTarget,
+{
+    fn from(shared: SharedPtr<T>) -> Self {
+        let mut shared = ManuallyDrop::new(shared);
+        let mutable = &mut *shared as *mut SharedPtr<T> as *mut c_void;
+        let mut shared_ptr = MaybeUninit::<SharedConstPtr<T>>::uninit();
+        let new = shared_ptr.as_mut_ptr().cast();
+        unsafe {
+            T::__shared_const_ptr_from_mut(mutable, new);
+            shared_ptr.assume_init()
+        }
+    }
+}
+
+/// Trait bound for types which may be used as the `T` inside of a
+/// `SharedConstPtr<T>` in generic code.
+///
+/// This trait has no publicly callable or implementable methods. Implementing
+/// it outside of the CXX codebase is not supported.
+pub unsafe trait SharedConstPtrTarget {
+    #[doc(hidden)]
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result;
+    #[doc(hidden)]
+    unsafe fn __shared_const_ptr_null(new: *mut c_void);
+    #[doc(hidden)]
+    unsafe fn __shared_const_ptr_from_mut(mutable: *mut c_void, new: *mut c_void);
+    #[doc(hidden)]
+    unsafe fn __shared_const_ptr_clone(this: *const c_void, new: *mut c_void);
+    #[doc(hidden)]
+    unsafe fn __shared_const_ptr_get(this: *const c_void) -> *const Self;
+    #[doc(hidden)]
+    unsafe fn __shared_const_ptr_use_count(this: *const c_void) -> usize;
+    #[doc(hidden)]
+    unsafe fn __shared_const_ptr_drop(this: *mut c_void);
+}
+
+macro_rules! impl_shared_const_ptr_target {
+    ($segment:expr, $name:expr, $ty:ty) => {
+        unsafe impl SharedConstPtrTarget for $ty {
+            fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str($name)
+            }
+            unsafe fn __shared_const_ptr_null(new: *mut c_void) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$shared_ptr$const$", $segment, "$null")]
+                    fn __null(new: *mut c_void);
+                }
+                unsafe { __null(new) }
+            }
+            unsafe fn __shared_const_ptr_from_mut(mutable: *mut c_void, new: *mut c_void) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$shared_ptr$const$", $segment, "$from_mut")]
+                    fn __from_mut(mutable: *mut c_void, new: *mut c_void);
+                }
+                unsafe { __from_mut(mutable, new) }
+            }
+            unsafe fn __shared_const_ptr_clone(this: *const c_void, new: *mut c_void) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$shared_ptr$const$", $segment, "$clone")]
+                    fn __clone(this: *const c_void, new: *mut c_void);
+                }
+                unsafe { __clone(this, new) }
+            }
+            unsafe fn __shared_const_ptr_get(this: *const c_void) -> *const Self {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$shared_ptr$const$", $segment, "$get")]
+                    fn __get(this: *const c_void) -> *const c_void;
+                }
+                unsafe { __get(this) }.cast()
+            }
+            unsafe fn __shared_const_ptr_use_count(this: *const c_void) -> usize {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$shared_ptr$const$", $segment, "$use_count")]
+                    fn __use_count(this: *const c_void) -> usize;
+                }
+                unsafe { __use_count(this) }
+            }
+            unsafe fn __shared_const_ptr_drop(this: *mut c_void) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$shared_ptr$const$", $segment, "$drop")]
+                    fn __drop(this: *mut c_void);
+                }
+                unsafe { __drop(this) }
+            }
+        }
+    };
+}
+
+macro_rules! impl_shared_const_ptr_target_for_primitive {
+    ($ty:ident) => {
+        impl_shared_const_ptr_target!(stringify!($ty), stringify!($ty), $ty);
+    };
+}
+
+impl_shared_const_ptr_target_for_primitive!(bool);
+impl_shared_const_ptr_target_for_primitive!(u8);
+impl_shared_const_ptr_target_for_primitive!(u16);
+impl_shared_const_ptr_target_for_primitive!(u32);
+impl_shared_const_ptr_target_for_primitive!(u64);
+impl_shared_const_ptr_target_for_primitive!(usize);
+impl_shared_const_ptr_target_for_primitive!(i8);
+impl_shared_const_ptr_target_for_primitive!(i16);
+impl_shared_const_ptr_target_for_primitive!(i32);
+impl_shared_const_ptr_target_for_primitive!(i64);
+impl_shared_const_ptr_target_for_primitive!(isize);
+impl_shared_const_ptr_target_for_primitive!(f32);
+impl_shared_const_ptr_target_for_primitive!(f64);
+
+impl_shared_const_ptr_target!("string", "CxxString", CxxString);
@@ -4,6 +4,8 @@ use crate::kind::Trivial;
 use crate::string::CxxString;
 use crate::unique_ptr::{UniquePtr, UniquePtrTarget};
 use crate::weak_ptr::{WeakPtr, WeakPtrTarget};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
 use core::cmp::Ordering;
 use core::ffi::c_void;
 use core::fmt::{self, Debug, Display};
@@ -199,6 +201,64 @@ where
         self.as_ptr() as *mut T
     }
 
+    /// Consumes the SharedPtr, moving it into an opaque heap allocation and
+    /// returning a raw pointer to it.
+    ///
+    /// A C++ shared pointer cannot relinquish its ownership into a plain `T*`
+    /// the way [`UniquePtr::into_raw`] can, since the reference count lives in
+    /// a separate control block. This method instead gives a single thin
+    /// pointer that keeps one strong reference alive, suitable for passing
+    /// through C APIs that carry a `void*` context argument. The original
+    /// SharedPtr is recovered by [`SharedPtr::from_boxed_raw`].
+    ///
+    /// The returned pointer is never null, even if the SharedPtr is **null**
+    /// or **empty**. It is allocated by Rust's global allocator and must not be
+    /// freed by C++.
+    ///
+    /// ```
+    /// # use cxx::{CxxString, SharedPtr, UniquePtr};
+    /// #
+    /// let shared: SharedPtr<CxxString> = UniquePtr::from("context").into_shared();
+    /// let raw = shared.clone().into_boxed_raw();
+    ///
+    /// // ... pass `raw` to C and get it back in a callback ...
+    ///
+    /// let recovered = unsafe { SharedPtr::<CxxString>::from_boxed_raw(raw) };
+    /// assert_eq!(recovered.as_ptr(), shared.as_ptr());
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn into_boxed_raw(self) -> *mut c_void {
+        Box::into_raw(Box::new(self)).cast()
+    }
+
+    /// Reconstructs a SharedPtr from a pointer previously returned by
+    /// [`SharedPtr::into_boxed_raw`], taking back the strong reference it
+    /// held.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must have been returned by `into_boxed_raw` on a
+    /// `SharedPtr` of the same `T`, and must not be passed to this function
+    /// more than once.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub unsafe fn from_boxed_raw(raw: *mut c_void) -> Self {
+        *unsafe { Box::from_raw(raw.cast::<SharedPtr<T>>()) }
+    }
+
+    /// Returns the number of SharedPtr instances sharing ownership of the
+    /// managed object, including this one, or 0 if this SharedPtr is
+    /// **empty**.
+    ///
+    /// Matches the behavior of [std::shared_ptr\<T\>::use_count](https://en.cppreference.com/w/cpp/memory/shared_ptr/use_count).
+    /// In a multithreaded program the value may be out of date as soon as it
+    /// is returned.
+    pub fn strong_count(&self) -> usize {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__use_count(this) }
+    }
+
     /// Constructs new WeakPtr as a non-owning reference to the object managed
     /// by `self`. If `self` manages no object, the WeakPtr manages no object
     /// too.
@@ -337,7 +397,7 @@ where
     T: UniquePtrTarget + SharedPtrTarget,
 {
     fn from(unique: UniquePtr<T>) -> Self {
-        unsafe { SharedPtr::from_raw(UniquePtr::into_raw(unique)) }
+        unique.into_shared()
     }
 }
 
@@ -389,6 +449,8 @@ pub unsafe trait SharedPtrTarget {
     #[doc(hidden)]
     unsafe fn __get(this: *const c_void) -> *const Self;
     #[doc(hidden)]
+    unsafe fn __use_count(this: *const c_void) -> usize;
+    #[doc(hidden)]
     unsafe fn __drop(this: *mut c_void);
 }
 
@@ -433,6 +495,13 @@ macro_rules! impl_shared_ptr_target {
                 }
                 unsafe { __get(this) }.cast()
             }
+            unsafe fn __use_count(this: *const c_void) -> usize {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$shared_ptr$", $segment, "$use_count")]
+                    fn __use_count(this: *const c_void) -> usize;
+                }
+                unsafe { __use_count(this) }
+            }
             unsafe fn __drop(this: *mut c_void) {
                 extern "C" {
                     #[link_name = concat!("cxxbridge1$std$shared_ptr$", $segment, "$drop")]
@@ -1,18 +1,66 @@
 #![cfg(feature = "alloc")]
 
+use crate::exception::Exception;
 use crate::result::PtrLen;
 use alloc::boxed::Box;
 use alloc::string::String;
 use core::ptr::NonNull;
 use core::slice;
+#[cfg(feature = "std")]
+use std::backtrace::{Backtrace, BacktraceStatus};
 
+// The sources are the messages of the exception's causes, outermost first. An
+// empty type name means the C++ side could not determine it.
 #[export_name = "cxxbridge1$exception"]
-unsafe extern "C" fn exception(ptr: *const u8, len: usize) -> PtrLen {
-    let slice = unsafe { slice::from_raw_parts(ptr, len) };
-    let string = String::from_utf8_lossy(slice);
-    let len = string.len();
-    let raw_str = Box::into_raw(string.into_owned().into_boxed_str());
-    let raw_u8 = raw_str.cast::<u8>();
-    let nonnull = unsafe { NonNull::new_unchecked(raw_u8) };
-    PtrLen { ptr: nonnull, len }
+unsafe extern "C" fn exception(
+    what: PtrLen,
+    type_name: PtrLen,
+    code: i32,
+    sources: *const PtrLen,
+    count: usize,
+) -> PtrLen {
+    let mut exception = None;
+    if count != 0 {
+        let sources = unsafe { slice::from_raw_parts(sources, count) };
+        for source in sources.iter().rev() {
+            let mut source = unsafe { new(*source) };
+            source.source = exception.map(Box::new);
+            exception = Some(source);
+        }
+    }
+    let mut top = unsafe { new(what) };
+    if type_name.len != 0 {
+        top.type_name = Some(unsafe { lossy_str(type_name) });
+    }
+    top.code = code;
+    #[cfg(feature = "std")]
+    {
+        let backtrace = Backtrace::capture();
+        if backtrace.status() == BacktraceStatus::Captured {
+            top.backtrace = Some(Box::new(backtrace));
+        }
+    }
+    top.source = exception.map(Box::new);
+    let raw = Box::into_raw(Box::new(top));
+    let nonnull = unsafe { NonNull::new_unchecked(raw.cast::<u8>()) };
+    PtrLen {
+        ptr: nonnull,
+        len: 0,
+    }
+}
+
+unsafe fn new(what: PtrLen) -> Exception {
+    Exception {
+        what: unsafe { lossy_str(what) },
+        type_name: None,
+        code: 0,
+        source: None,
+        #[cfg(feature = "std")]
+        backtrace: None,
+    }
+}
+
+unsafe fn lossy_str(s: PtrLen) -> Box<str> {
+    let slice = unsafe { slice::from_raw_parts(s.ptr.as_ptr(), s.len) };
+    String::from_utf8_lossy(slice).into_owned().into_boxed_str()
 }
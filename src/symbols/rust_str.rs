@@ -32,6 +32,13 @@ unsafe extern "C" fn str_from(this: &mut MaybeUninit<&str>, ptr: *const u8, len:
     }
 }
 
+#[export_name = "cxxbridge1$str$from_unchecked"]
+unsafe extern "C" fn str_from_unchecked(this: &mut MaybeUninit<&str>, ptr: *const u8, len: usize) {
+    let slice = unsafe { slice::from_raw_parts(ptr, len) };
+    let this = this.as_mut_ptr();
+    unsafe { ptr::write(this, str::from_utf8_unchecked(slice)) }
+}
+
 #[export_name = "cxxbridge1$str$ptr"]
 unsafe extern "C" fn str_ptr(this: &&str) -> *const u8 {
     this.as_ptr()
@@ -41,3 +48,8 @@ unsafe extern "C" fn str_ptr(this: &&str) -> *const u8 {
 unsafe extern "C" fn str_len(this: &&str) -> usize {
     this.len()
 }
+
+#[export_name = "cxxbridge1$str$hash"]
+unsafe extern "C" fn str_hash(this: &&str) -> usize {
+    crate::hash::hash(this)
+}
@@ -1,5 +1,6 @@
 #![cfg(feature = "alloc")]
 
+use crate::telemetry::{self, Origin};
 use alloc::borrow::ToOwned;
 use alloc::string::String;
 use core::mem::{ManuallyDrop, MaybeUninit};
@@ -18,6 +19,7 @@ unsafe extern "C" fn string_new(this: &mut MaybeUninit<String>) {
 unsafe extern "C" fn string_clone(this: &mut MaybeUninit<String>, other: &String) {
     let this = this.as_mut_ptr();
     let clone = other.clone();
+    record_growth(0, || clone.capacity());
     unsafe { ptr::write(this, clone) }
 }
 
@@ -32,6 +34,7 @@ unsafe extern "C" fn string_from_utf8(
         Ok(s) => {
             let this = this.as_mut_ptr();
             let owned = s.to_owned();
+            record_growth(0, || owned.capacity());
             unsafe { ptr::write(this, owned) }
             true
         }
@@ -47,6 +50,7 @@ unsafe extern "C" fn string_from_utf8_lossy(
 ) {
     let slice = unsafe { slice::from_raw_parts(ptr, len) };
     let owned = String::from_utf8_lossy(slice).into_owned();
+    record_growth(0, || owned.capacity());
     let this = this.as_mut_ptr();
     unsafe { ptr::write(this, owned) }
 }
@@ -60,6 +64,7 @@ unsafe extern "C" fn string_from_utf16(
     let slice = unsafe { slice::from_raw_parts(ptr, len) };
     match String::from_utf16(slice) {
         Ok(s) => {
+            record_growth(0, || s.capacity());
             let this = this.as_mut_ptr();
             unsafe { ptr::write(this, s) }
             true
@@ -76,6 +81,7 @@ unsafe extern "C" fn string_from_utf16_lossy(
 ) {
     let slice = unsafe { slice::from_raw_parts(ptr, len) };
     let owned = String::from_utf16_lossy(slice);
+    record_growth(0, || owned.capacity());
     let this = this.as_mut_ptr();
     unsafe { ptr::write(this, owned) }
 }
@@ -102,13 +108,35 @@ unsafe extern "C" fn string_capacity(this: &String) -> usize {
 
 #[export_name = "cxxbridge1$string$reserve_additional"]
 unsafe extern "C" fn string_reserve_additional(this: &mut String, additional: usize) {
+    let before = telemetry::capacity_before(|| this.capacity());
     this.reserve(additional);
+    record_growth(before, || this.capacity());
+}
+
+#[export_name = "cxxbridge1$string$push_utf8"]
+unsafe extern "C" fn string_push_utf8(this: &mut String, ptr: *const u8, len: usize) -> bool {
+    let slice = unsafe { slice::from_raw_parts(ptr, len) };
+    match str::from_utf8(slice) {
+        Ok(s) => {
+            let before = telemetry::capacity_before(|| this.capacity());
+            this.push_str(s);
+            record_growth(before, || this.capacity());
+            true
+        }
+        Err(_) => false,
+    }
 }
 
 #[export_name = "cxxbridge1$string$reserve_total"]
 unsafe extern "C" fn string_reserve_total(this: &mut String, new_cap: usize) {
     if new_cap > this.capacity() {
         let additional = new_cap - this.len();
+        let before = telemetry::capacity_before(|| this.capacity());
         this.reserve(additional);
+        record_growth(before, || this.capacity());
     }
 }
+
+fn record_growth(before: usize, after: impl FnOnce() -> usize) {
+    telemetry::record_growth(Origin::CxxRequested, "rust::String", 1, before, after);
+}
@@ -0,0 +1,176 @@
+//! Counters of allocations made by one language on behalf of the other.
+//!
+//! Memory that C++ code causes to be allocated through `rust::String` and
+//! `rust::Vec` comes from the Rust allocator, and memory that Rust code causes
+//! to be allocated through `CxxString` and `CxxVector` comes from the C++
+//! allocator, so a memory dashboard looking at only one of the two allocators
+//! attributes it to the wrong side, or loses it entirely. This module keeps
+//! process-wide totals of such allocations, and can additionally forward each
+//! one to a hook for finer grained reporting.
+//!
+//! Allocations are measured as growth in the capacity of the container,
+//! converted to bytes, at the moment the other language asks for it. Memory
+//! released later is not tracked, so the totals are cumulative rather than a
+//! measure of what is currently live. Creating the CXX containers themselves
+//! on the heap, as with `CxxString` in a `UniquePtr`, is not counted beyond
+//! the capacity of their contents.
+//!
+//! ```
+//! # #[cfg(feature = "telemetry")]
+//! # mod example {
+//! use cxx::telemetry::{self, Origin};
+//!
+//! fn report() {
+//!     let rust_requested = telemetry::totals(Origin::RustRequested);
+//!     println!(
+//!         "Rust has had C++ allocate {} bytes over {} allocations",
+//!         rust_requested.bytes, rust_requested.count,
+//!     );
+//! }
+//! # }
+//! ```
+
+#![cfg_attr(not(feature = "telemetry"), allow(dead_code))]
+
+use core::mem;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+/// Which language asked for an allocation made by the other one's allocator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Origin {
+    /// C++ code grew a Rust container, such as by pushing to a `rust::Vec` or
+    /// constructing a `rust::String`, so the Rust allocator did the work.
+    CxxRequested,
+    /// Rust code grew a C++ container, such as by pushing to a `CxxVector` or
+    /// reserving in a `CxxString`, so the C++ allocator did the work.
+    RustRequested,
+}
+
+/// One allocation, as seen by the hook installed by [`set_hook`].
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct Allocation {
+    /// Which language asked for the allocation.
+    pub origin: Origin,
+    /// The container that grew: `"rust::String"`, `"rust::Vec"`,
+    /// `"std::string"`, or `"std::vector"`.
+    pub container: &'static str,
+    /// The growth in capacity of the container, in bytes.
+    pub bytes: usize,
+}
+
+/// Cumulative totals of allocations with one [`Origin`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Totals {
+    /// Number of allocations.
+    pub count: usize,
+    /// Sum of their sizes in bytes.
+    pub bytes: usize,
+}
+
+/// Signature of a hook installed by [`set_hook`].
+pub type Hook = fn(&Allocation);
+
+struct Counters {
+    count: AtomicUsize,
+    bytes: AtomicUsize,
+}
+
+static CXX_REQUESTED: Counters = Counters::new();
+static RUST_REQUESTED: Counters = Counters::new();
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+impl Counters {
+    const fn new() -> Self {
+        Counters {
+            count: AtomicUsize::new(0),
+            bytes: AtomicUsize::new(0),
+        }
+    }
+
+    fn of(origin: Origin) -> &'static Self {
+        match origin {
+            Origin::CxxRequested => &CXX_REQUESTED,
+            Origin::RustRequested => &RUST_REQUESTED,
+        }
+    }
+}
+
+/// Returns the totals accumulated so far for allocations with the given
+/// origin.
+pub fn totals(origin: Origin) -> Totals {
+    let counters = Counters::of(origin);
+    Totals {
+        count: counters.count.load(Ordering::Relaxed),
+        bytes: counters.bytes.load(Ordering::Relaxed),
+    }
+}
+
+/// Installs a function to be called on every counted allocation, in addition
+/// to the totals being updated, and returns the previously installed one.
+///
+/// The hook runs synchronously on the thread making the allocation, possibly
+/// in the middle of a call from C++, so it must be quick and must not panic.
+/// Passing `None` removes the hook.
+pub fn set_hook(hook: Option<Hook>) -> Option<Hook> {
+    let new = match hook {
+        Some(hook) => hook as *mut (),
+        None => ptr::null_mut(),
+    };
+    let previous = HOOK.swap(new, Ordering::AcqRel);
+    if previous.is_null() {
+        None
+    } else {
+        Some(unsafe { mem::transmute::<*mut (), Hook>(previous) })
+    }
+}
+
+// Reads of the container's capacity before an operation are skipped entirely
+// when telemetry is disabled, so that the call sites cost nothing.
+#[inline]
+pub(crate) fn capacity_before(capacity: impl FnOnce() -> usize) -> usize {
+    if cfg!(feature = "telemetry") {
+        capacity()
+    } else {
+        0
+    }
+}
+
+#[inline]
+pub(crate) fn record_growth(
+    origin: Origin,
+    container: &'static str,
+    element_size: usize,
+    before: usize,
+    after: impl FnOnce() -> usize,
+) {
+    if cfg!(feature = "telemetry") {
+        let after = after();
+        if after > before {
+            record(
+                origin,
+                container,
+                (after - before).saturating_mul(element_size),
+            );
+        }
+    }
+}
+
+fn record(origin: Origin, container: &'static str, bytes: usize) {
+    let counters = Counters::of(origin);
+    counters.count.fetch_add(1, Ordering::Relaxed);
+    counters.bytes.fetch_add(bytes, Ordering::Relaxed);
+
+    let hook = HOOK.load(Ordering::Acquire);
+    if !hook.is_null() {
+        let hook = unsafe { mem::transmute::<*mut (), Hook>(hook) };
+        hook(&Allocation {
+            origin,
+            container,
+            bytes,
+        });
+    }
+}
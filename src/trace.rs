@@ -0,0 +1,227 @@
+//! Hooks around the calls that cross a bridge.
+//!
+//! With the `trace` feature enabled, every call from Rust into an extern
+//! "C++" function and every call from C++ into an extern "Rust" function runs
+//! inside of a [`tracing`] span at the trace level, with target `"cxx"` and
+//! name `"call"`. The span has a `function` field naming the function by the
+//! path of the bridge module followed by its Rust name, such as
+//! `"mycrate::ffi::Engine::run"`, and a `direction` field which is
+//! `"rust->c++"` or `"c++->rust"`. This is meant for measuring what crossing
+//! between the languages costs and for finding interfaces that are called more
+//! often than expected, with whichever `tracing` subscriber the application
+//! already uses.
+//!
+//! [`tracing`]: https://docs.rs/tracing
+//!
+//! In addition, the calls are reported to the hook installed by [`set_hook`]
+//! once when they start and again when they return, along with how long they
+//! took. This is mostly for C++ code, which can install a hook of its own
+//! with `rust::trace::set_hook` from rust/cxx.h, taking the place of the Rust
+//! one. With no hook installed, this costs one atomic load per call.
+//!
+//! ```
+//! # #[cfg(feature = "trace")]
+//! # mod example {
+//! use cxx::trace::{self, Call, Direction, Phase};
+//!
+//! fn hook(call: &Call) {
+//!     if let Phase::Exit(elapsed) = call.phase {
+//!         let arrow = match call.direction {
+//!             Direction::RustToCxx => "->",
+//!             _ => "<-",
+//!         };
+//!         eprintln!("{} {} took {:?}", arrow, call.function, elapsed);
+//!     }
+//! }
+//!
+//! pub fn install() {
+//!     trace::set_hook(Some(hook));
+//! }
+//! # }
+//! ```
+
+#![cfg_attr(not(feature = "trace"), allow(dead_code))]
+
+use crate::rust_str::RustStr;
+use core::mem;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use core::time::Duration;
+
+/// Which language is calling into which.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Direction {
+    /// Rust code called an extern "C++" function.
+    RustToCxx,
+    /// C++ code called an extern "Rust" function.
+    CxxToRust,
+}
+
+impl Direction {
+    /// The name of the direction as recorded in `tracing` spans,
+    /// `"rust->c++"` or `"c++->rust"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::RustToCxx => "rust->c++",
+            Direction::CxxToRust => "c++->rust",
+        }
+    }
+}
+
+/// Whether a call is starting or returning.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// The call is about to be made.
+    Enter,
+    /// The call has returned, or is unwinding, after the given time.
+    Exit(Duration),
+}
+
+/// One report of a call, as seen by the hook installed by [`set_hook`].
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct Call {
+    /// The path of the bridge module and the Rust name of the function.
+    pub function: &'static str,
+    /// Which language is calling into which.
+    pub direction: Direction,
+    /// Whether the call is starting or returning.
+    pub phase: Phase,
+}
+
+/// Signature of a hook installed by [`set_hook`].
+pub type Hook = fn(&Call);
+
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+static CXX_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Installs a function to be called at the start and end of every call across
+/// a bridge, and returns the previously installed one.
+///
+/// The hook runs synchronously on the calling thread, so it must be quick. A
+/// panic in the hook during a call from C++ into Rust is handled the same as a
+/// panic in the extern "Rust" function itself. Calls that are already in
+/// progress are reported as exiting to the hook that saw them enter. Passing
+/// `None` removes the hook.
+pub fn set_hook(hook: Option<Hook>) -> Option<Hook> {
+    let new = match hook {
+        Some(hook) => hook as *mut (),
+        None => ptr::null_mut(),
+    };
+    let previous = HOOK.swap(new, Ordering::AcqRel);
+    unsafe { as_hook(previous) }
+}
+
+unsafe fn as_hook(hook: *mut ()) -> Option<Hook> {
+    if hook.is_null() {
+        None
+    } else {
+        Some(unsafe { mem::transmute::<*mut (), Hook>(hook) })
+    }
+}
+
+// Held by the generated code for the duration of a call while the `trace`
+// feature is enabled.
+#[cfg(feature = "trace")]
+#[doc(hidden)]
+pub struct Span {
+    _timed: Option<Timed>,
+    _span: tracing::span::EnteredSpan,
+}
+
+#[cfg(feature = "trace")]
+struct Timed {
+    hook: Hook,
+    function: &'static str,
+    direction: Direction,
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "trace")]
+impl Span {
+    #[inline]
+    pub fn enter(function: &'static str, direction: Direction) -> Self {
+        let span =
+            tracing::trace_span!(target: "cxx", "call", function, direction = direction.as_str());
+        let timed = unsafe { as_hook(HOOK.load(Ordering::Acquire)) }.map(|hook| {
+            hook(&Call {
+                function,
+                direction,
+                phase: Phase::Enter,
+            });
+            Timed {
+                hook,
+                function,
+                direction,
+                start: std::time::Instant::now(),
+            }
+        });
+        Span {
+            _timed: timed,
+            _span: span.entered(),
+        }
+    }
+}
+
+#[cfg(feature = "trace")]
+impl Drop for Timed {
+    fn drop(&mut self) {
+        (self.hook)(&Call {
+            function: self.function,
+            direction: self.direction,
+            phase: Phase::Exit(self.start.elapsed()),
+        });
+    }
+}
+
+// ABI compatible with C++ rust::trace::Call.
+#[repr(C)]
+struct CxxCall {
+    function: RustStr,
+    direction: u8,
+    exit: bool,
+    elapsed_nanos: u64,
+}
+
+type CxxHook = unsafe extern "C" fn(&CxxCall);
+
+fn cxx_hook(call: &Call) {
+    let hook = CXX_HOOK.load(Ordering::Acquire);
+    if hook.is_null() {
+        return;
+    }
+    let hook = unsafe { mem::transmute::<*mut (), CxxHook>(hook) };
+    let (exit, elapsed) = match call.phase {
+        Phase::Enter => (false, Duration::ZERO),
+        Phase::Exit(elapsed) => (true, elapsed),
+    };
+    let call = CxxCall {
+        function: RustStr::from(call.function),
+        direction: match call.direction {
+            Direction::RustToCxx => 0,
+            Direction::CxxToRust => 1,
+        },
+        exit,
+        elapsed_nanos: u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX),
+    };
+    unsafe { hook(&call) }
+}
+
+// Installing a C++ hook installs `cxx_hook` in place of any Rust hook, which
+// forwards to the C++ one. The previous C++ hook is only returned if it was
+// still installed.
+#[export_name = "cxxbridge1$trace$set_hook"]
+unsafe extern "C" fn cxx_set_hook(hook: Option<CxxHook>) -> Option<CxxHook> {
+    let new = match hook {
+        Some(hook) => hook as *mut (),
+        None => ptr::null_mut(),
+    };
+    let previous = CXX_HOOK.swap(new, Ordering::AcqRel);
+    let rust_hook = set_hook(hook.map(|_| cxx_hook as Hook));
+    if previous.is_null() || rust_hook.map(|hook| hook as *mut ()) != Some(cxx_hook as *mut ()) {
+        None
+    } else {
+        Some(unsafe { mem::transmute::<*mut (), CxxHook>(previous) })
+    }
+}
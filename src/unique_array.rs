@@ -0,0 +1,317 @@
+use crate::extern_type::ExternType;
+use crate::fmt::display;
+use crate::kind::Trivial;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::slice;
+
+/// Binding to C++ `std::unique_ptr<T[], std::default_delete<T[]>>`.
+///
+/// Like the C++ type, a UniqueArray owns an array allocated by `new T[n]` and
+/// destroys it with `delete[]`, but does not know how many elements the array
+/// has. The elements are accessible only as raw pointers, or as a slice by
+/// supplying the length. A C++ function that returns the array together with
+/// its length in one of the arguments can be declared with `#[array_len = n]`
+/// to have the bridge pair them up into a safely accessible [`UniqueSlice`].
+#[repr(C)]
+pub struct UniqueArray<T>
+where
+    T: UniqueArrayElement,
+{
+    repr: MaybeUninit<*mut c_void>,
+    ty: PhantomData<[T]>,
+}
+
+impl<T> UniqueArray<T>
+where
+    T: UniqueArrayElement,
+{
+    /// Makes a new UniqueArray wrapping a null pointer.
+    ///
+    /// Matches the behavior of default-constructing a std::unique\_ptr.
+    pub fn null() -> Self {
+        UniqueArray {
+            repr: T::__unique_array_null(),
+            ty: PhantomData,
+        }
+    }
+
+    /// Checks whether the UniqueArray does not own an array.
+    pub fn is_null(&self) -> bool {
+        self.as_ptr().is_null()
+    }
+
+    /// Returns a raw const pointer to the first element of the array owned by
+    /// this UniqueArray if any, otherwise the null pointer.
+    pub fn as_ptr(&self) -> *const T {
+        unsafe { T::__unique_array_get(self.repr) }
+    }
+
+    /// Returns a raw mutable pointer to the first element of the array owned
+    /// by this UniqueArray if any, otherwise the null pointer.
+    ///
+    /// As with [`UniquePtr::as_mut_ptr`][crate::UniquePtr::as_mut_ptr], this
+    /// doesn't require that you hold an exclusive reference to the UniqueArray.
+    pub fn as_mut_ptr(&self) -> *mut T {
+        self.as_ptr() as *mut T
+    }
+
+    /// Returns a slice of the first `len` elements of the array.
+    ///
+    /// # Safety
+    ///
+    /// The array must be non-null and have at least `len` elements, unless
+    /// `len` is 0.
+    pub unsafe fn as_slice(&self, len: usize) -> &[T]
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        if len == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.as_ptr(), len) }
+        }
+    }
+
+    /// Returns a mutable slice of the first `len` elements of the array.
+    ///
+    /// # Safety
+    ///
+    /// The array must be non-null and have at least `len` elements, unless
+    /// `len` is 0.
+    pub unsafe fn as_mut_slice(&mut self, len: usize) -> &mut [T]
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        if len == 0 {
+            &mut []
+        } else {
+            unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), len) }
+        }
+    }
+
+    /// Consumes the UniqueArray, releasing its ownership of the heap-allocated
+    /// array.
+    ///
+    /// The caller becomes responsible for freeing the array with `delete[]`,
+    /// or handing it back to [`UniqueArray::from_raw`].
+    pub fn into_raw(self) -> *mut T {
+        let ptr = unsafe { T::__unique_array_release(self.repr) };
+        mem::forget(self);
+        ptr
+    }
+
+    /// Constructs a UniqueArray retaking ownership of a pointer previously
+    /// obtained from `into_raw`.
+    ///
+    /// Any pointer to an array allocated in C++ by `new T[n]`, or the null
+    /// pointer, is also accepted.
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe because improper use may lead to memory
+    /// problems. For example a double-free may occur if the function is called
+    /// twice on the same raw pointer.
+    pub unsafe fn from_raw(raw: *mut T) -> Self {
+        UniqueArray {
+            repr: unsafe { T::__unique_array_raw(raw) },
+            ty: PhantomData,
+        }
+    }
+}
+
+unsafe impl<T> Send for UniqueArray<T> where T: Send + UniqueArrayElement {}
+unsafe impl<T> Sync for UniqueArray<T> where T: Sync + UniqueArrayElement {}
+
+// UniqueArray is not a self-referential type and is safe to move out of a Pin,
+// regardless whether the elements are Unpin.
+impl<T> Unpin for UniqueArray<T> where T: UniqueArrayElement {}
+
+impl<T> Drop for UniqueArray<T>
+where
+    T: UniqueArrayElement,
+{
+    fn drop(&mut self) {
+        unsafe { T::__unique_array_drop(self.repr) }
+    }
+}
+
+impl<T> Debug for UniqueArray<T>
+where
+    T: UniqueArrayElement,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_null() {
+            formatter.write_str("nullptr")
+        } else {
+            write!(
+                formatter,
+                "UniqueArray<{}>({:p})",
+                display(T::__typename),
+                self.as_ptr(),
+            )
+        }
+    }
+}
+
+/// A [`UniqueArray`] together with the number of elements in its array.
+///
+/// This is what an `extern "C++"` function declared with `#[array_len = n]`
+/// returns on the Rust side, with the length taken from the function's `n`
+/// argument. For trivial element types it dereferences to a slice.
+pub struct UniqueSlice<T>
+where
+    T: UniqueArrayElement,
+{
+    array: UniqueArray<T>,
+    len: usize,
+}
+
+impl<T> UniqueSlice<T>
+where
+    T: UniqueArrayElement,
+{
+    /// Pairs an array with its length.
+    ///
+    /// A null array is treated as empty regardless of `len`.
+    ///
+    /// # Safety
+    ///
+    /// A non-null array must have at least `len` elements.
+    pub unsafe fn from_raw_parts(array: UniqueArray<T>, len: usize) -> Self {
+        let len = if array.is_null() { 0 } else { len };
+        UniqueSlice { array, len }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Separates the array from its length.
+    pub fn into_raw_parts(self) -> (UniqueArray<T>, usize) {
+        (self.array, self.len)
+    }
+}
+
+impl<T> Deref for UniqueSlice<T>
+where
+    T: UniqueArrayElement + ExternType<Kind = Trivial>,
+{
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.array.as_slice(self.len) }
+    }
+}
+
+impl<T> DerefMut for UniqueSlice<T>
+where
+    T: UniqueArrayElement + ExternType<Kind = Trivial>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.array.as_mut_slice(self.len) }
+    }
+}
+
+impl<T> Debug for UniqueSlice<T>
+where
+    T: UniqueArrayElement + ExternType<Kind = Trivial> + Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&**self, formatter)
+    }
+}
+
+/// Trait bound for types which may be used as the `T` inside of a
+/// `UniqueArray<T>` in generic code.
+///
+/// This trait has no publicly callable or implementable methods. Implementing
+/// it outside of the CXX codebase is done for you by the `cxx::bridge` macro
+/// for any type that appears as `UniqueArray<MyType>` in the bridge, or
+/// explicitly by writing `impl UniqueArray<MyType> {}`.
+pub unsafe trait UniqueArrayElement: Sized {
+    #[doc(hidden)]
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result;
+    #[doc(hidden)]
+    fn __unique_array_null() -> MaybeUninit<*mut c_void>;
+    #[doc(hidden)]
+    unsafe fn __unique_array_raw(raw: *mut Self) -> MaybeUninit<*mut c_void>;
+    #[doc(hidden)]
+    unsafe fn __unique_array_get(repr: MaybeUninit<*mut c_void>) -> *const Self;
+    #[doc(hidden)]
+    unsafe fn __unique_array_release(repr: MaybeUninit<*mut c_void>) -> *mut Self;
+    #[doc(hidden)]
+    unsafe fn __unique_array_drop(repr: MaybeUninit<*mut c_void>);
+}
+
+macro_rules! impl_unique_array_element_for_primitive {
+    ($ty:ident) => {
+        unsafe impl UniqueArrayElement for $ty {
+            fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(stringify!($ty))
+            }
+            fn __unique_array_null() -> MaybeUninit<*mut c_void> {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$unique_ptr$array$", stringify!($ty), "$null")]
+                    fn __null(this: *mut MaybeUninit<*mut c_void>);
+                }
+                let mut repr = MaybeUninit::uninit();
+                unsafe { __null(&mut repr) }
+                repr
+            }
+            unsafe fn __unique_array_raw(raw: *mut Self) -> MaybeUninit<*mut c_void> {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$unique_ptr$array$", stringify!($ty), "$raw")]
+                    fn __raw(this: *mut MaybeUninit<*mut c_void>, raw: *mut $ty);
+                }
+                let mut repr = MaybeUninit::uninit();
+                unsafe { __raw(&mut repr, raw) }
+                repr
+            }
+            unsafe fn __unique_array_get(repr: MaybeUninit<*mut c_void>) -> *const Self {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$unique_ptr$array$", stringify!($ty), "$get")]
+                    fn __get(this: *const MaybeUninit<*mut c_void>) -> *const $ty;
+                }
+                unsafe { __get(&repr) }
+            }
+            unsafe fn __unique_array_release(mut repr: MaybeUninit<*mut c_void>) -> *mut Self {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$unique_ptr$array$", stringify!($ty), "$release")]
+                    fn __release(this: *mut MaybeUninit<*mut c_void>) -> *mut $ty;
+                }
+                unsafe { __release(&mut repr) }
+            }
+            unsafe fn __unique_array_drop(mut repr: MaybeUninit<*mut c_void>) {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$unique_ptr$array$", stringify!($ty), "$drop")]
+                    fn __drop(this: *mut MaybeUninit<*mut c_void>);
+                }
+                unsafe { __drop(&mut repr) }
+            }
+        }
+    };
+}
+
+impl_unique_array_element_for_primitive!(bool);
+impl_unique_array_element_for_primitive!(u8);
+impl_unique_array_element_for_primitive!(u16);
+impl_unique_array_element_for_primitive!(u32);
+impl_unique_array_element_for_primitive!(u64);
+impl_unique_array_element_for_primitive!(usize);
+impl_unique_array_element_for_primitive!(i8);
+impl_unique_array_element_for_primitive!(i16);
+impl_unique_array_element_for_primitive!(i32);
+impl_unique_array_element_for_primitive!(i64);
+impl_unique_array_element_for_primitive!(isize);
+impl_unique_array_element_for_primitive!(f32);
+impl_unique_array_element_for_primitive!(f64);
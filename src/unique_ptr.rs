@@ -1,7 +1,12 @@
+#[cfg(feature = "c++17")]
+use crate::cxx_pmr_string::CxxPmrString;
+#[cfg(feature = "c++17")]
+use crate::cxx_pmr_vector::{CxxPmrVector, PmrVectorElement};
 use crate::cxx_vector::{CxxVector, VectorElement};
 use crate::extern_type::ExternType;
 use crate::fmt::display;
 use crate::kind::Trivial;
+use crate::shared_ptr::{SharedPtr, SharedPtrTarget};
 use crate::string::CxxString;
 #[cfg(feature = "std")]
 use alloc::string::String;
@@ -93,6 +98,62 @@ where
         }
     }
 
+    /// Returns a mutable pinned reference to the object owned by this
+    /// UniquePtr, first filling a null UniquePtr with the result of `f`.
+    ///
+    /// This is convenient for lazily constructed C++ objects:
+    ///
+    /// ```
+    /// # use cxx::{CxxString, UniquePtr};
+    /// #
+    /// # let mut unique = UniquePtr::<CxxString>::null();
+    /// # let make_default = || UniquePtr::from("");
+    /// unique.pin_mut_or_else(make_default).push_str("...");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the UniquePtr holds a null pointer and `f` returns a null
+    /// UniquePtr too.
+    pub fn pin_mut_or_else<F>(&mut self, f: F) -> Pin<&mut T>
+    where
+        F: FnOnce() -> UniquePtr<T>,
+    {
+        if self.is_null() {
+            *self = f();
+        }
+        match self.as_mut() {
+            Some(target) => target,
+            None => panic!(
+                "pin_mut_or_else produced a null UniquePtr<{}>",
+                display(T::__typename),
+            ),
+        }
+    }
+
+    /// Calls `f` with a mutable pinned reference to the object owned by this
+    /// UniquePtr if any, and returns its result, otherwise returns None
+    /// without calling `f`.
+    ///
+    /// Inside the closure, use [`Pin::as_mut`] to call more than one method
+    /// taking `self: Pin<&mut Self>` on the same reference.
+    ///
+    /// ```
+    /// # use cxx::{CxxString, UniquePtr};
+    /// #
+    /// # let mut unique = UniquePtr::<CxxString>::from("");
+    /// unique.with_pin_mut(|mut string| {
+    ///     string.as_mut().push_str("a");
+    ///     string.as_mut().push_str("b");
+    /// });
+    /// ```
+    pub fn with_pin_mut<F, R>(&mut self, f: F) -> Option<R>
+    where
+        F: FnOnce(Pin<&mut T>) -> R,
+    {
+        self.as_mut().map(f)
+    }
+
     /// Returns a raw const pointer to the object owned by this UniquePtr if
     /// any, otherwise the null pointer.
     pub fn as_ptr(&self) -> *const T {
@@ -113,6 +174,22 @@ where
     /// Consumes the UniquePtr, releasing its ownership of the heap-allocated T.
     ///
     /// Matches the behavior of [std::unique_ptr\<T\>::release](https://en.cppreference.com/w/cpp/memory/unique_ptr/release).
+    ///
+    /// The caller becomes responsible for the object. Ownership can be handed
+    /// to a C or C++ API that eventually frees it with `delete`, or taken back
+    /// later by [`UniquePtr::from_raw`].
+    ///
+    /// ```
+    /// # use cxx::{CxxString, UniquePtr};
+    /// #
+    /// let unique: UniquePtr<CxxString> = UniquePtr::from("owned");
+    /// let raw: *mut CxxString = unique.into_raw();
+    ///
+    /// // ... pass `raw` through an API that transfers ownership back ...
+    ///
+    /// let unique = unsafe { UniquePtr::from_raw(raw) };
+    /// assert_eq!(*unique, *"owned");
+    /// ```
     pub fn into_raw(self) -> *mut T {
         let ptr = unsafe { T::__release(self.repr) };
         mem::forget(self);
@@ -122,6 +199,10 @@ where
     /// Constructs a UniquePtr retaking ownership of a pointer previously
     /// obtained from `into_raw`.
     ///
+    /// Any pointer to a T allocated in C++ by `new`, or the null pointer, is
+    /// also accepted. The object will be destroyed with `delete` when the
+    /// UniquePtr is dropped.
+    ///
     /// # Safety
     ///
     /// This function is unsafe because improper use may lead to memory
@@ -133,6 +214,23 @@ where
             ty: PhantomData,
         }
     }
+
+    /// Transfers ownership of the object owned by this UniquePtr to a new
+    /// SharedPtr, without relocating the object.
+    ///
+    /// Matches the behavior of std::shared\_ptr's converting constructor
+    /// `shared_ptr(std::unique_ptr<T>&&)`. In particular if this UniquePtr is
+    /// null, the resulting shared pointer is both **empty** and **null**.
+    pub fn into_shared(self) -> SharedPtr<T>
+    where
+        T: SharedPtrTarget,
+    {
+        if self.is_null() {
+            SharedPtr::null()
+        } else {
+            unsafe { SharedPtr::from_raw(self.into_raw()) }
+        }
+    }
 }
 
 unsafe impl<T> Send for UniquePtr<T> where T: Send + UniquePtrTarget {}
@@ -252,6 +350,7 @@ where
 ///
 /// Note that the implementation will panic for null `UniquePtr<T>`.
 #[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl<T> Read for UniquePtr<T>
 where
     for<'a> Pin<&'a mut T>: Read,
@@ -285,6 +384,7 @@ where
 ///
 /// Note that the implementation will panic for null `UniquePtr<T>`.
 #[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl<T> Seek for UniquePtr<T>
 where
     for<'a> Pin<&'a mut T>: Seek,
@@ -320,6 +420,7 @@ where
 ///
 /// Note that the implementation will panic for null `UniquePtr<T>`.
 #[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl<T> Write for UniquePtr<T>
 where
     for<'a> Pin<&'a mut T>: Write,
@@ -444,6 +545,53 @@ unsafe impl UniquePtrTarget for CxxString {
     }
 }
 
+extern "C" {
+    #[link_name = "cxxbridge1$unique_ptr$std$vector$bool$null"]
+    fn unique_ptr_std_vector_bool_null(this: *mut MaybeUninit<*mut c_void>);
+    #[link_name = "cxxbridge1$unique_ptr$std$vector$bool$raw"]
+    fn unique_ptr_std_vector_bool_raw(
+        this: *mut MaybeUninit<*mut c_void>,
+        raw: *mut CxxVector<bool>,
+    );
+    #[link_name = "cxxbridge1$unique_ptr$std$vector$bool$get"]
+    fn unique_ptr_std_vector_bool_get(
+        this: *const MaybeUninit<*mut c_void>,
+    ) -> *const CxxVector<bool>;
+    #[link_name = "cxxbridge1$unique_ptr$std$vector$bool$release"]
+    fn unique_ptr_std_vector_bool_release(
+        this: *mut MaybeUninit<*mut c_void>,
+    ) -> *mut CxxVector<bool>;
+    #[link_name = "cxxbridge1$unique_ptr$std$vector$bool$drop"]
+    fn unique_ptr_std_vector_bool_drop(this: *mut MaybeUninit<*mut c_void>);
+}
+
+unsafe impl UniquePtrTarget for CxxVector<bool> {
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CxxVector<bool>")
+    }
+    fn __null() -> MaybeUninit<*mut c_void> {
+        let mut repr = MaybeUninit::uninit();
+        unsafe {
+            unique_ptr_std_vector_bool_null(&mut repr);
+        }
+        repr
+    }
+    unsafe fn __raw(raw: *mut Self) -> MaybeUninit<*mut c_void> {
+        let mut repr = MaybeUninit::uninit();
+        unsafe { unique_ptr_std_vector_bool_raw(&mut repr, raw) }
+        repr
+    }
+    unsafe fn __get(repr: MaybeUninit<*mut c_void>) -> *const Self {
+        unsafe { unique_ptr_std_vector_bool_get(&repr) }
+    }
+    unsafe fn __release(mut repr: MaybeUninit<*mut c_void>) -> *mut Self {
+        unsafe { unique_ptr_std_vector_bool_release(&mut repr) }
+    }
+    unsafe fn __drop(mut repr: MaybeUninit<*mut c_void>) {
+        unsafe { unique_ptr_std_vector_bool_drop(&mut repr) }
+    }
+}
+
 unsafe impl<T> UniquePtrTarget for CxxVector<T>
 where
     T: VectorElement,
@@ -467,3 +615,70 @@ where
         unsafe { T::__unique_ptr_drop(repr) }
     }
 }
+
+#[cfg(feature = "c++17")]
+extern "C" {
+    #[link_name = "cxxbridge1$unique_ptr$std$pmr$string$null"]
+    fn unique_ptr_std_pmr_string_null(this: *mut MaybeUninit<*mut c_void>);
+    #[link_name = "cxxbridge1$unique_ptr$std$pmr$string$raw"]
+    fn unique_ptr_std_pmr_string_raw(this: *mut MaybeUninit<*mut c_void>, raw: *mut CxxPmrString);
+    #[link_name = "cxxbridge1$unique_ptr$std$pmr$string$get"]
+    fn unique_ptr_std_pmr_string_get(this: *const MaybeUninit<*mut c_void>) -> *const CxxPmrString;
+    #[link_name = "cxxbridge1$unique_ptr$std$pmr$string$release"]
+    fn unique_ptr_std_pmr_string_release(this: *mut MaybeUninit<*mut c_void>) -> *mut CxxPmrString;
+    #[link_name = "cxxbridge1$unique_ptr$std$pmr$string$drop"]
+    fn unique_ptr_std_pmr_string_drop(this: *mut MaybeUninit<*mut c_void>);
+}
+
+#[cfg(feature = "c++17")]
+unsafe impl UniquePtrTarget for CxxPmrString {
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CxxPmrString")
+    }
+    fn __null() -> MaybeUninit<*mut c_void> {
+        let mut repr = MaybeUninit::uninit();
+        unsafe {
+            unique_ptr_std_pmr_string_null(&mut repr);
+        }
+        repr
+    }
+    unsafe fn __raw(raw: *mut Self) -> MaybeUninit<*mut c_void> {
+        let mut repr = MaybeUninit::uninit();
+        unsafe { unique_ptr_std_pmr_string_raw(&mut repr, raw) }
+        repr
+    }
+    unsafe fn __get(repr: MaybeUninit<*mut c_void>) -> *const Self {
+        unsafe { unique_ptr_std_pmr_string_get(&repr) }
+    }
+    unsafe fn __release(mut repr: MaybeUninit<*mut c_void>) -> *mut Self {
+        unsafe { unique_ptr_std_pmr_string_release(&mut repr) }
+    }
+    unsafe fn __drop(mut repr: MaybeUninit<*mut c_void>) {
+        unsafe { unique_ptr_std_pmr_string_drop(&mut repr) }
+    }
+}
+
+#[cfg(feature = "c++17")]
+unsafe impl<T> UniquePtrTarget for CxxPmrVector<T>
+where
+    T: PmrVectorElement,
+{
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CxxPmrVector<{}>", display(T::__typename))
+    }
+    fn __null() -> MaybeUninit<*mut c_void> {
+        T::__unique_ptr_null()
+    }
+    unsafe fn __raw(raw: *mut Self) -> MaybeUninit<*mut c_void> {
+        unsafe { T::__unique_ptr_raw(raw) }
+    }
+    unsafe fn __get(repr: MaybeUninit<*mut c_void>) -> *const Self {
+        unsafe { T::__unique_ptr_get(repr) }
+    }
+    unsafe fn __release(repr: MaybeUninit<*mut c_void>) -> *mut Self {
+        unsafe { T::__unique_ptr_release(repr) }
+    }
+    unsafe fn __drop(repr: MaybeUninit<*mut c_void>) {
+        unsafe { T::__unique_ptr_drop(repr) }
+    }
+}
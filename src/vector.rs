@@ -2,7 +2,10 @@
 //!
 //! `CxxVector` itself is exposed at the crate root.
 
-pub use crate::cxx_vector::{Iter, IterMut, VectorElement};
+pub use crate::cxx_vector::{BoolIter, Iter, IterMut, VectorElement};
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub use crate::par_iter::ParIter;
 #[doc(inline)]
 pub use crate::Vector;
 #[doc(no_inline)]
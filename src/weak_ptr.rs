@@ -52,6 +52,27 @@ where
             shared_ptr.assume_init()
         }
     }
+
+    /// Returns the number of SharedPtr instances that share ownership of the
+    /// managed object, or 0 if the managed object has already been deleted or
+    /// this WeakPtr is null.
+    ///
+    /// Matches the behavior of [std::weak_ptr\<T\>::use_count](https://en.cppreference.com/w/cpp/memory/weak_ptr/use_count).
+    pub fn use_count(&self) -> usize {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__use_count(this) }
+    }
+
+    /// Checks whether the managed object has already been deleted, i.e.
+    /// whether `use_count()` is 0.
+    ///
+    /// Matches the behavior of [std::weak_ptr\<T\>::expired](https://en.cppreference.com/w/cpp/memory/weak_ptr/expired).
+    /// A false result may be out of date by the time it is used if other
+    /// threads hold SharedPtrs to the same object; use `upgrade` to get a
+    /// SharedPtr that is guaranteed to keep the object alive.
+    pub fn expired(&self) -> bool {
+        self.use_count() == 0
+    }
 }
 
 unsafe impl<T> Send for WeakPtr<T> where T: Send + Sync + WeakPtrTarget {}
@@ -108,6 +129,8 @@ pub unsafe trait WeakPtrTarget {
     #[doc(hidden)]
     unsafe fn __upgrade(weak: *const c_void, shared: *mut c_void);
     #[doc(hidden)]
+    unsafe fn __use_count(this: *const c_void) -> usize;
+    #[doc(hidden)]
     unsafe fn __drop(this: *mut c_void);
 }
 
@@ -145,6 +168,13 @@ macro_rules! impl_weak_ptr_target {
                 }
                 unsafe { __upgrade(weak, shared) }
             }
+            unsafe fn __use_count(this: *const c_void) -> usize {
+                extern "C" {
+                    #[link_name = concat!("cxxbridge1$std$weak_ptr$", $segment, "$use_count")]
+                    fn __use_count(this: *const c_void) -> usize;
+                }
+                unsafe { __use_count(this) }
+            }
             unsafe fn __drop(this: *mut c_void) {
                 extern "C" {
                     #[link_name = concat!("cxxbridge1$std$weak_ptr$", $segment, "$drop")]
@@ -6,7 +6,9 @@ use crate::syntax::{cfg, Derive, Doc, ForeignName};
 use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
 use syn::parse::ParseStream;
-use syn::{Attribute, Error, Expr, Lit, LitStr, Meta, Path, Result, Token};
+use syn::{
+    Attribute, Error, Expr, Lit, LitStr, Meta, Path, Result, Token, Type as RustType, TypePath,
+};
 
 // Intended usage:
 //
@@ -36,6 +38,17 @@ pub(crate) struct Parser<'a> {
     pub cxx_name: Option<&'a mut Option<ForeignName>>,
     pub rust_name: Option<&'a mut Option<Ident>>,
     pub self_type: Option<&'a mut Option<Ident>>,
+    pub array_len: Option<&'a mut Option<Ident>>,
+    pub incomplete: Option<&'a mut bool>,
+    pub send: Option<&'a mut bool>,
+    pub sync: Option<&'a mut bool>,
+    pub smart_ptr: Option<&'a mut bool>,
+    pub com_interface: Option<&'a mut Option<u128>>,
+    pub private: Option<&'a mut bool>,
+    pub catch_foreign_exceptions: Option<&'a mut bool>,
+    pub serde_variant_names: Option<&'a mut bool>,
+    pub mock: Option<&'a mut Option<Ident>>,
+    pub vocabulary: Option<&'a mut Option<RustType>>,
     pub ignore_unrecognized: bool,
 
     // Suppress clippy needless_update lint ("struct update has no effect, all
@@ -142,6 +155,149 @@ pub(crate) fn parse(cx: &mut Errors, attrs: Vec<Attribute>, mut parser: Parser)
                     break;
                 }
             }
+        } else if attr_path.is_ident("array_len") {
+            match parse_rust_ident_attribute(&attr.meta) {
+                Ok(attr) => {
+                    if let Some(array_len) = &mut parser.array_len {
+                        **array_len = Some(attr);
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
+        } else if attr_path.is_ident("incomplete") {
+            match attr.meta.require_path_only() {
+                Ok(_) => {
+                    if let Some(incomplete) = &mut parser.incomplete {
+                        **incomplete = true;
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
+        } else if attr_path.is_ident("send") {
+            match attr.meta.require_path_only() {
+                Ok(_) => {
+                    if let Some(send) = &mut parser.send {
+                        **send = true;
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
+        } else if attr_path.is_ident("sync") {
+            match attr.meta.require_path_only() {
+                Ok(_) => {
+                    if let Some(sync) = &mut parser.sync {
+                        **sync = true;
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
+        } else if attr_path.is_ident("smart_ptr") {
+            match attr.meta.require_path_only() {
+                Ok(_) => {
+                    if let Some(smart_ptr) = &mut parser.smart_ptr {
+                        **smart_ptr = true;
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
+        } else if attr_path.is_ident("com_interface") {
+            match parse_com_interface_attribute(&attr.meta) {
+                Ok(iid) => {
+                    if let Some(com_interface) = &mut parser.com_interface {
+                        **com_interface = Some(iid);
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
+        } else if attr_path.is_ident("private") {
+            match attr.meta.require_path_only() {
+                Ok(_) => {
+                    if let Some(private) = &mut parser.private {
+                        **private = true;
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
+        } else if attr_path.is_ident("catch_foreign_exceptions") {
+            match attr.meta.require_path_only() {
+                Ok(_) => {
+                    if let Some(catch_foreign_exceptions) = &mut parser.catch_foreign_exceptions {
+                        **catch_foreign_exceptions = true;
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
+        } else if attr_path.is_ident("serde_variant_names") {
+            match attr.meta.require_path_only() {
+                Ok(_) => {
+                    if let Some(serde_variant_names) = &mut parser.serde_variant_names {
+                        **serde_variant_names = true;
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
+        } else if attr_path.is_ident("mock") {
+            match parse_rust_ident_attribute(&attr.meta) {
+                Ok(attr) => {
+                    if let Some(mock) = &mut parser.mock {
+                        **mock = Some(attr);
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
+        } else if attr_path.is_ident("vocabulary") {
+            match parse_vocabulary_attribute(&attr.meta) {
+                Ok(attr) => {
+                    if let Some(vocabulary) = &mut parser.vocabulary {
+                        **vocabulary = Some(attr);
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
         } else if attr_path.is_ident("cfg") {
             match cfg::parse_attribute(&attr) {
                 Ok(cfg_expr) => {
@@ -166,7 +322,7 @@ pub(crate) fn parse(cx: &mut Errors, attrs: Vec<Attribute>, mut parser: Parser)
             // https://doc.rust-lang.org/reference/attributes/diagnostics.html
             passthrough_attrs.push(attr);
             continue;
-        } else if attr_path.is_ident("serde") {
+        } else if attr_path.is_ident("serde") || attr_path.is_ident("arbitrary") {
             passthrough_attrs.push(attr);
             continue;
         } else if attr_path.segments.len() > 1 {
@@ -274,6 +430,64 @@ fn parse_rust_ident_attribute(meta: &Meta) -> Result<Ident> {
     ))
 }
 
+// The binding of a #[vocabulary] type is either a plain name like `String`, or
+// any type written as a string literal like `"Vec<u8>"`.
+fn parse_vocabulary_attribute(meta: &Meta) -> Result<RustType> {
+    if let Meta::NameValue(meta) = meta {
+        match &meta.value {
+            Expr::Lit(expr) => {
+                if let Lit::Str(lit) = &expr.lit {
+                    return lit.parse();
+                }
+            }
+            Expr::Path(expr) if expr.qself.is_none() => {
+                return Ok(RustType::Path(TypePath {
+                    qself: None,
+                    path: expr.path.clone(),
+                }));
+            }
+            _ => {}
+        }
+    }
+    Err(Error::new_spanned(
+        meta,
+        "unsupported `vocabulary` attribute",
+    ))
+}
+
+// Parses an interface identifier in the registry format used by COM headers,
+// such as "00000000-0000-0000-C000-000000000046", with or without the braces.
+fn parse_com_interface_attribute(meta: &Meta) -> Result<u128> {
+    if let Meta::NameValue(meta) = meta {
+        if let Expr::Lit(expr) = &meta.value {
+            if let Lit::Str(lit) = &expr.lit {
+                let value = lit.value();
+                let guid = value
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.strip_suffix('}'))
+                    .unwrap_or(&value);
+                let groups: Vec<&str> = guid.split('-').collect();
+                let well_formed = groups.len() == 5
+                    && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, len)| {
+                        group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit())
+                    });
+                if well_formed {
+                    let hex = groups.concat();
+                    return Ok(u128::from_str_radix(&hex, 16).unwrap());
+                }
+                return Err(Error::new_spanned(
+                    lit,
+                    "expected an interface id like \"00000000-0000-0000-C000-000000000046\"",
+                ));
+            }
+        }
+    }
+    Err(Error::new_spanned(
+        meta,
+        "unsupported com_interface attribute",
+    ))
+}
+
 #[derive(Clone)]
 pub(crate) struct OtherAttrs(Vec<Attribute>);
 
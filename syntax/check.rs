@@ -2,8 +2,9 @@ use crate::syntax::atom::Atom::{self, *};
 use crate::syntax::report::Errors;
 use crate::syntax::visit::{self, Visit};
 use crate::syntax::{
-    error, ident, trivial, Api, Array, Enum, ExternFn, ExternType, FnKind, Impl, Lang, Lifetimes,
-    NamedType, Ptr, Receiver, Ref, Signature, SliceRef, Struct, Trait, Ty1, Type, TypeAlias, Types,
+    error, ident, parse, suggest, trivial, Api, Array, CustomPtr, Enum, ExternFn, ExternType,
+    FnKind, HashMapRef, Impl, Lang, Lifetimes, NamedType, Ptr, Receiver, Ref, Signature, SliceRef,
+    Struct, Trait, Ty1, Type, TypeAlias, Types,
 };
 use proc_macro2::{Delimiter, Group, Ident, TokenStream};
 use quote::{quote, ToTokens};
@@ -48,16 +49,24 @@ fn do_typecheck(cx: &mut Check) {
             Type::RustBox(ptr) => check_type_box(cx, ptr),
             Type::RustVec(ty) => check_type_rust_vec(cx, ty),
             Type::UniquePtr(ptr) => check_type_unique_ptr(cx, ptr),
+            Type::UniqueArray(ptr) => check_type_unique_array(cx, ptr),
             Type::SharedPtr(ptr) => check_type_shared_ptr(cx, ptr),
+            Type::SharedArray(ptr) => check_type_shared_array(cx, ptr),
+            Type::SharedConstPtr(ptr) => check_type_shared_const_ptr(cx, ptr),
             Type::WeakPtr(ptr) => check_type_weak_ptr(cx, ptr),
             Type::CxxVector(ptr) => check_type_cxx_vector(cx, ptr),
+            Type::CxxPmrVector(ptr) => check_type_cxx_pmr_vector(cx, ptr),
             Type::Ref(ty) => check_type_ref(cx, ty),
             Type::Ptr(ty) => check_type_ptr(cx, ty),
             Type::Array(array) => check_type_array(cx, array),
             Type::Fn(ty) => check_type_fn(cx, ty),
             Type::SliceRef(ty) => check_type_slice_ref(cx, ty),
+            Type::HashMapRef(ty) => check_type_hash_map_ref(cx, ty),
+            Type::CustomPtr(ptr) => check_type_custom_ptr(cx, ptr),
+            Type::ComPtr(ptr) => check_type_com_ptr(cx, ptr),
             Type::Str(_) | Type::Void(_) => {}
         }
+        check_nested_hash_map_ref(cx, ty);
     }
 
     for api in cx.apis {
@@ -71,27 +80,83 @@ fn do_typecheck(cx: &mut Check) {
             Api::Impl(imp) => check_api_impl(cx, imp),
         }
     }
+
+    check_mocks(cx);
 }
 
 impl Check<'_> {
     pub(crate) fn error(&mut self, sp: impl ToTokens, msg: impl Display) {
-        self.errors.error(sp, msg);
+        match self.generator {
+            // cxx-build looks up and prints the hint itself, as a note attached
+            // to the diagnostic.
+            Generator::Build => self.errors.error(sp, msg),
+            // The proc macro API has no notes, so the hint becomes part of the
+            // message.
+            Generator::Macro => {
+                let msg = msg.to_string();
+                let hint = error::ERRORS
+                    .iter()
+                    .find(|info| msg.contains(info.msg))
+                    .and_then(|info| info.note.filter(|note| *note != info.msg));
+                match hint {
+                    Some(hint) => self.errors.error(sp, format!("{}\n{}", msg, hint)),
+                    None => self.errors.error(sp, msg),
+                }
+            }
+        }
     }
 }
 
 fn check_type_ident(cx: &mut Check, name: &NamedType) {
     let ident = &name.rust;
-    if Atom::from(ident).is_none()
+    if cx.types.smart_ptrs.contains(ident) {
+        let msg = format!(
+            "smart pointer family {} can only be used as CustomPtr<{}, T>",
+            ident, ident,
+        );
+        cx.error(ident, msg);
+    } else if cx.types.vocabularies.contains(ident) {
+        let msg = format!(
+            "vocabulary type {} can only be passed by value to or returned by value from an extern C++ function",
+            ident,
+        );
+        cx.error(ident, msg);
+    } else if Atom::from(ident).is_none()
         && !cx.types.structs.contains_key(ident)
         && !cx.types.enums.contains_key(ident)
         && !cx.types.cxx.contains(ident)
         && !cx.types.rust.contains(ident)
     {
-        let msg = format!("unsupported type: {}", ident);
+        let mut msg = format!("unsupported type: {}", ident);
+        let declared = declared_type_names(cx);
+        let candidates = suggest::BUILTIN_TYPES.iter().copied();
+        let candidates = candidates.chain(declared.iter().map(String::as_str));
+        if let Some(similar) = suggest::similar(&ident.to_string(), candidates) {
+            msg += &format!("; did you mean `{}`?", similar);
+        }
         cx.error(ident, msg);
     }
 }
 
+fn declared_type_names(cx: &Check) -> Vec<String> {
+    let mut names = Vec::new();
+    for api in cx.apis {
+        let name = match api {
+            Api::Struct(strct) => &strct.name,
+            Api::Enum(enm) => &enm.name,
+            Api::CxxType(ety) | Api::RustType(ety)
+                if !ety.smart_ptr && ety.vocabulary.is_none() =>
+            {
+                &ety.name
+            }
+            Api::TypeAlias(alias) => &alias.name,
+            _ => continue,
+        };
+        names.push(name.rust.to_string());
+    }
+    names
+}
+
 fn check_type_box(cx: &mut Check, ptr: &Ty1) {
     if let Type::Ident(ident) = &ptr.inner {
         if cx.types.cxx.contains(&ident.rust)
@@ -99,7 +164,7 @@ fn check_type_box(cx: &mut Check, ptr: &Ty1) {
             && !cx.types.structs.contains_key(&ident.rust)
             && !cx.types.enums.contains_key(&ident.rust)
         {
-            cx.error(ptr, error::BOX_CXX_TYPE.msg);
+            cx.error(&ptr.inner, error::BOX_CXX_TYPE.msg);
         }
 
         if Atom::from(&ident.rust).is_none() {
@@ -107,7 +172,7 @@ fn check_type_box(cx: &mut Check, ptr: &Ty1) {
         }
     }
 
-    cx.error(ptr, "unsupported target type of Box");
+    cx.error(&ptr.inner, "unsupported target type of Box");
 }
 
 fn check_type_rust_vec(cx: &mut Check, ty: &Ty1) {
@@ -118,7 +183,7 @@ fn check_type_rust_vec(cx: &mut Check, ty: &Ty1) {
                 && !cx.types.structs.contains_key(&ident.rust)
                 && !cx.types.enums.contains_key(&ident.rust)
             {
-                cx.error(ty, "Rust Vec containing C++ type is not supported yet");
+                cx.error(&ty.inner, error::VEC_CXX_TYPE.msg);
                 return;
             }
 
@@ -135,13 +200,13 @@ fn check_type_rust_vec(cx: &mut Check, ty: &Ty1) {
         _ => {}
     }
 
-    cx.error(ty, "unsupported element type of Vec");
+    cx.error(&ty.inner, "unsupported element type of Vec");
 }
 
 fn check_type_unique_ptr(cx: &mut Check, ptr: &Ty1) {
     if let Type::Ident(ident) = &ptr.inner {
         if cx.types.rust.contains(&ident.rust) {
-            cx.error(ptr, "unique_ptr of a Rust type is not supported yet");
+            cx.error(&ptr.inner, "unique_ptr of a Rust type is not supported yet");
             return;
         }
 
@@ -149,17 +214,39 @@ fn check_type_unique_ptr(cx: &mut Check, ptr: &Ty1) {
             None | Some(CxxString) => return,
             _ => {}
         }
-    } else if let Type::CxxVector(_) = &ptr.inner {
+    } else if let Type::CxxVector(_) | Type::CxxPmrVector(_) = &ptr.inner {
         return;
     }
 
-    cx.error(ptr, "unsupported unique_ptr target type");
+    cx.error(&ptr.inner, "unsupported unique_ptr target type");
+}
+
+fn check_type_unique_array(cx: &mut Check, ptr: &Ty1) {
+    if let Type::Ident(ident) = &ptr.inner {
+        if cx.types.rust.contains(&ident.rust) {
+            cx.error(
+                &ptr.inner,
+                "unique_ptr<T[]> of a Rust type is not supported yet",
+            );
+            return;
+        }
+
+        match Atom::from(&ident.rust) {
+            None
+            | Some(
+                Bool | U8 | U16 | U32 | U64 | Usize | I8 | I16 | I32 | I64 | Isize | F32 | F64,
+            ) => return,
+            Some(Char | CxxString | RustString) => {}
+        }
+    }
+
+    cx.error(&ptr.inner, "unsupported unique_ptr<T[]> element type");
 }
 
 fn check_type_shared_ptr(cx: &mut Check, ptr: &Ty1) {
     if let Type::Ident(ident) = &ptr.inner {
         if cx.types.rust.contains(&ident.rust) {
-            cx.error(ptr, "shared_ptr of a Rust type is not supported yet");
+            cx.error(&ptr.inner, "shared_ptr of a Rust type is not supported yet");
             return;
         }
 
@@ -176,13 +263,108 @@ fn check_type_shared_ptr(cx: &mut Check, ptr: &Ty1) {
         return;
     }
 
-    cx.error(ptr, "unsupported shared_ptr target type");
+    cx.error(&ptr.inner, "unsupported shared_ptr target type");
+}
+
+fn check_type_shared_const_ptr(cx: &mut Check, ptr: &Ty1) {
+    if let Type::Ident(ident) = &ptr.inner {
+        if cx.types.rust.contains(&ident.rust) {
+            cx.error(
+                &ptr.inner,
+                "shared_ptr<const T> of a Rust type is not supported yet",
+            );
+            return;
+        }
+
+        match Atom::from(&ident.rust) {
+            None
+            | Some(
+                Bool | U8 | U16 | U32 | U64 | Usize | I8 | I16 | I32 | I64 | Isize | F32 | F64
+                | CxxString,
+            ) => return,
+            Some(Char | RustString) => {}
+        }
+    } else if let Type::CxxVector(_) = &ptr.inner {
+        cx.error(
+            ptr,
+            "std::shared_ptr<const std::vector> is not supported yet",
+        );
+        return;
+    }
+
+    cx.error(&ptr.inner, "unsupported shared_ptr<const T> target type");
+}
+
+fn check_type_custom_ptr(cx: &mut Check, ptr: &CustomPtr) {
+    if !cx.types.smart_ptrs.contains(&ptr.family) {
+        let msg = format!(
+            "unsupported smart pointer family: {}; declare it in extern \"C++\" with #[smart_ptr]",
+            ptr.family,
+        );
+        cx.error(&ptr.family, msg);
+        return;
+    }
+
+    if let Type::Ident(ident) = &ptr.inner {
+        if cx.types.rust.contains(&ident.rust) {
+            cx.error(
+                &ptr.inner,
+                "custom smart pointer of a Rust type is not supported",
+            );
+            return;
+        }
+        if Atom::from(&ident.rust).is_none() && !cx.types.smart_ptrs.contains(&ident.rust) {
+            return;
+        }
+    }
+
+    cx.error(&ptr.inner, "unsupported custom smart pointer target type");
+}
+
+fn check_type_com_ptr(cx: &mut Check, ptr: &Ty1) {
+    if let Type::Ident(ident) = &ptr.inner {
+        if cx.types.com_interfaces.contains(&ident.rust) {
+            return;
+        }
+        if cx.types.cxx.contains(&ident.rust) && Atom::from(&ident.rust).is_none() {
+            let msg = format!(
+                "ComPtr target {} must be declared in extern \"C++\" with #[com_interface = \"...\"]",
+                ident.rust,
+            );
+            cx.error(&ptr.inner, msg);
+            return;
+        }
+    }
+
+    cx.error(&ptr.inner, "unsupported ComPtr target type");
+}
+
+fn check_type_shared_array(cx: &mut Check, ptr: &Ty1) {
+    if let Type::Ident(ident) = &ptr.inner {
+        if cx.types.rust.contains(&ident.rust) {
+            cx.error(
+                &ptr.inner,
+                "shared_ptr<T[]> of a Rust type is not supported yet",
+            );
+            return;
+        }
+
+        match Atom::from(&ident.rust) {
+            None
+            | Some(
+                Bool | U8 | U16 | U32 | U64 | Usize | I8 | I16 | I32 | I64 | Isize | F32 | F64,
+            ) => return,
+            Some(Char | CxxString | RustString) => {}
+        }
+    }
+
+    cx.error(&ptr.inner, "unsupported shared_ptr<T[]> element type");
 }
 
 fn check_type_weak_ptr(cx: &mut Check, ptr: &Ty1) {
     if let Type::Ident(ident) = &ptr.inner {
         if cx.types.rust.contains(&ident.rust) {
-            cx.error(ptr, "weak_ptr of a Rust type is not supported yet");
+            cx.error(&ptr.inner, "weak_ptr of a Rust type is not supported yet");
             return;
         }
 
@@ -199,14 +381,14 @@ fn check_type_weak_ptr(cx: &mut Check, ptr: &Ty1) {
         return;
     }
 
-    cx.error(ptr, "unsupported weak_ptr target type");
+    cx.error(&ptr.inner, "unsupported weak_ptr target type");
 }
 
 fn check_type_cxx_vector(cx: &mut Check, ptr: &Ty1) {
     if let Type::Ident(ident) = &ptr.inner {
         if cx.types.rust.contains(&ident.rust) {
             cx.error(
-                ptr,
+                &ptr.inner,
                 "C++ vector containing a Rust type is not supported yet",
             );
             return;
@@ -215,14 +397,36 @@ fn check_type_cxx_vector(cx: &mut Check, ptr: &Ty1) {
         match Atom::from(&ident.rust) {
             None
             | Some(
-                U8 | U16 | U32 | U64 | Usize | I8 | I16 | I32 | I64 | Isize | F32 | F64 | CxxString,
+                Bool | U8 | U16 | U32 | U64 | Usize | I8 | I16 | I32 | I64 | Isize | F32 | F64
+                | CxxString | RustString,
             ) => return,
             Some(Char) => { /* todo */ }
-            Some(Bool | RustString) => {}
         }
     }
 
-    cx.error(ptr, "unsupported vector element type");
+    cx.error(&ptr.inner, "unsupported vector element type");
+}
+
+fn check_type_cxx_pmr_vector(cx: &mut Check, ptr: &Ty1) {
+    if let Type::Ident(ident) = &ptr.inner {
+        if cx.types.rust.contains(&ident.rust) {
+            cx.error(
+                &ptr.inner,
+                "C++ pmr vector containing a Rust type is not supported yet",
+            );
+            return;
+        }
+
+        match Atom::from(&ident.rust) {
+            None
+            | Some(U8 | U16 | U32 | U64 | Usize | I8 | I16 | I32 | I64 | Isize | F32 | F64) => {
+                return
+            }
+            Some(Bool | Char | CxxString | RustString) => {}
+        }
+    }
+
+    cx.error(&ptr.inner, "unsupported pmr vector element type");
 }
 
 fn check_type_ref(cx: &mut Check, ty: &Ref) {
@@ -232,6 +436,7 @@ fn check_type_ref(cx: &mut Check, ty: &Ref) {
                 Some(ident.rust.to_string())
             }
             Type::CxxVector(_) => Some("CxxVector<...>".to_owned()),
+            Type::CxxPmrVector(_) => Some("CxxPmrVector<...>".to_owned()),
             _ => None,
         } {
             cx.error(
@@ -298,8 +503,60 @@ fn check_type_array(cx: &mut Check, ty: &Array) {
     }
 }
 
+fn check_type_hash_map_ref(cx: &mut Check, ty: &HashMapRef) {
+    for (elem, what) in [(&ty.key, "key"), (&ty.value, "value")] {
+        if let Type::Ident(ident) = elem {
+            if cx.types.cxx.contains(&ident.rust)
+                && !cx.types.aliases.contains_key(&ident.rust)
+                && !cx.types.structs.contains_key(&ident.rust)
+                && !cx.types.enums.contains_key(&ident.rust)
+            {
+                cx.error(ty, "Rust HashMap containing C++ type is not supported yet");
+                continue;
+            }
+
+            match Atom::from(&ident.rust) {
+                None
+                | Some(
+                    Bool | Char | U8 | U16 | U32 | U64 | Usize | I8 | I16 | I32 | I64 | Isize
+                    | RustString,
+                ) => continue,
+                Some(F32 | F64) if what == "value" => continue,
+                Some(F32 | F64 | CxxString) => {}
+            }
+        }
+
+        let msg = format!("unsupported {} type of HashMap", what);
+        cx.error(ty, msg);
+    }
+}
+
+// A &HashMap crosses the language boundary as a view object that the bridge
+// builds from the reference, which it only does for function arguments and
+// return values.
+fn check_nested_hash_map_ref(cx: &mut Check, ty: &Type) {
+    struct Children<'a>(Vec<&'a Type>);
+
+    impl<'a> Visit<'a> for Children<'a> {
+        fn visit_type(&mut self, ty: &'a Type) {
+            self.0.push(ty);
+        }
+    }
+
+    let mut children = Children(Vec::new());
+    visit::visit_type(&mut children, ty);
+    for child in children.0 {
+        if let Type::HashMapRef(_) = child {
+            cx.error(
+                child,
+                "&HashMap is only supported as a function argument or return value",
+            );
+        }
+    }
+}
+
 fn check_type_fn(cx: &mut Check, ty: &Signature) {
-    if ty.throws {
+    if ty.throws || ty.hresult.is_some() {
         cx.error(ty, "function pointer returning Result is not supported yet");
     }
 
@@ -307,7 +564,7 @@ fn check_type_fn(cx: &mut Check, ty: &Signature) {
         if let Type::Ptr(_) = arg.ty {
             if ty.unsafety.is_none() {
                 cx.error(
-                    arg,
+                    &arg.ty,
                     "pointer argument requires that the function pointer be marked unsafe",
                 );
             }
@@ -345,10 +602,12 @@ fn check_api_struct(cx: &mut Check, strct: &Struct) {
                 field,
                 "function pointers in a struct field are not implemented yet",
             );
+        } else if let Type::HashMapRef(_) = field.ty {
+            cx.error(field, "&HashMap in a struct field is not supported");
         } else if is_unsized(cx, &field.ty) {
             let desc = describe(cx, &field.ty);
             let msg = format!("using {} by value is not supported", desc);
-            cx.error(field, msg);
+            cx.error(&field.ty, msg);
         }
     }
 }
@@ -377,8 +636,37 @@ fn check_api_type(cx: &mut Check, ety: &ExternType) {
     check_reserved_name(cx, &ety.name.rust);
     check_lifetimes(cx, &ety.generics);
 
+    if ety.smart_ptr {
+        check_smart_ptr_family(cx, ety);
+        return;
+    }
+
+    if ety.vocabulary.is_some() {
+        check_vocabulary_type(cx, ety);
+        return;
+    }
+
+    if ety.com_iid.is_some() {
+        if ety.lang == Lang::Rust {
+            cx.error(
+                ety,
+                "#[com_interface] is only supported on extern \"C++\" types",
+            );
+        } else if !ety.generics.lifetimes.is_empty() {
+            cx.error(
+                &ety.generics,
+                "#[com_interface] type with lifetime parameters is not supported",
+            );
+        }
+    }
+
     for derive in &ety.derives {
-        if derive.what == Trait::ExternType && ety.lang == Lang::Rust {
+        if derive.what == Trait::Hash {
+            continue;
+        }
+        if (derive.what == Trait::ExternType || derive.what == Trait::Default)
+            && ety.lang == Lang::Rust
+        {
             continue;
         }
         let lang = match ety.lang {
@@ -398,6 +686,29 @@ fn check_api_type(cx: &mut Check, ety: &ExternType) {
         cx.error(span, "extern type bounds are not implemented yet");
     }
 
+    if ety.incomplete && ety.lang == Lang::Rust {
+        cx.error(
+            ety,
+            "#[incomplete] is only supported on extern \"C++\" types",
+        );
+    }
+
+    for (attr, present) in [("send", ety.send), ("sync", ety.sync)] {
+        if !present {
+            continue;
+        }
+        if ety.lang == Lang::Rust {
+            let msg = format!("#[{}] is only supported on extern \"C++\" types", attr);
+            cx.error(ety, msg);
+        } else if !ety.trusted {
+            let msg = format!(
+                "#[{}] asserts thread safety of a C++ type, which requires `unsafe extern \"C++\"`",
+                attr,
+            );
+            cx.error(ety, msg);
+        }
+    }
+
     if let Some(reasons) = cx.types.required_trivial.get(&ety.name.rust) {
         let msg = format!(
             "needs a cxx::ExternType impl in order to be used as {}",
@@ -407,6 +718,85 @@ fn check_api_type(cx: &mut Check, ety: &ExternType) {
     }
 }
 
+fn check_smart_ptr_family(cx: &mut Check, ety: &ExternType) {
+    if ety.lang == Lang::Rust {
+        cx.error(
+            ety,
+            "#[smart_ptr] is only supported on extern \"C++\" types",
+        );
+        return;
+    }
+
+    if !ety.generics.lifetimes.is_empty() {
+        cx.error(
+            &ety.generics,
+            "smart pointer family with lifetimes is not supported",
+        );
+    }
+
+    for derive in &ety.derives {
+        let msg = format!(
+            "derive({}) on smart pointer family is not supported",
+            derive
+        );
+        cx.error(derive, msg);
+    }
+
+    for (attr, present) in [
+        ("incomplete", ety.incomplete),
+        ("send", ety.send),
+        ("sync", ety.sync),
+        ("com_interface", ety.com_iid.is_some()),
+    ] {
+        if present {
+            let msg = format!("#[{}] is not supported on a smart pointer family", attr);
+            cx.error(ety, msg);
+        }
+    }
+}
+
+fn check_vocabulary_type(cx: &mut Check, ety: &ExternType) {
+    if ety.lang == Lang::Rust {
+        cx.error(
+            ety,
+            "#[vocabulary] is only supported on extern \"C++\" types",
+        );
+        return;
+    }
+
+    let binding = ety.vocabulary.as_ref().unwrap();
+    if parse::parse_vocabulary_binding(binding).is_none() {
+        cx.error(
+            binding,
+            "unsupported vocabulary binding; expected `String`, `str`, `&[T]`, `Vec<T>`, `Box<T>`, `UniquePtr<T>` or `SharedPtr<T>`",
+        );
+    }
+
+    if !ety.generics.lifetimes.is_empty() {
+        cx.error(
+            &ety.generics,
+            "vocabulary type with lifetimes is not supported",
+        );
+    }
+
+    for derive in &ety.derives {
+        let msg = format!("derive({}) on vocabulary type is not supported", derive);
+        cx.error(derive, msg);
+    }
+
+    for (attr, present) in [
+        ("incomplete", ety.incomplete),
+        ("send", ety.send),
+        ("sync", ety.sync),
+        ("com_interface", ety.com_iid.is_some()),
+    ] {
+        if present {
+            let msg = format!("#[{}] is not supported on a vocabulary type", attr);
+            cx.error(ety, msg);
+        }
+    }
+}
+
 fn check_api_fn(cx: &mut Check, efn: &ExternFn) {
     match efn.lang {
         Lang::Cxx | Lang::CxxUnwind => {
@@ -490,14 +880,14 @@ fn check_api_fn(cx: &mut Check, efn: &ExternFn) {
         } else if let Type::Ptr(_) = arg.ty {
             if efn.unsafety.is_none() {
                 cx.error(
-                    arg,
+                    &arg.ty,
                     "pointer argument requires that the function be marked unsafe",
                 );
             }
         } else if is_unsized(cx, &arg.ty) {
             let desc = describe(cx, &arg.ty);
             let msg = format!("passing {} by value is not supported", desc);
-            cx.error(arg, msg);
+            cx.error(&arg.ty, msg);
         }
     }
 
@@ -511,9 +901,80 @@ fn check_api_fn(cx: &mut Check, efn: &ExternFn) {
         }
     }
 
+    if let (Lang::Cxx | Lang::CxxUnwind, Some(ty @ Type::HashMapRef(_))) = (efn.lang, &efn.ret) {
+        cx.error(ty, "returning &HashMap from C++ is not supported");
+    }
+
     if efn.lang == Lang::Cxx {
         check_mut_return_restriction(cx, efn);
     }
+
+    if let Some(array_len) = &efn.array_len {
+        check_array_len(cx, efn, array_len);
+    }
+
+    if efn.hresult.is_some() {
+        check_hresult(cx, efn);
+    }
+}
+
+fn check_hresult(cx: &mut Check, efn: &ExternFn) {
+    let (_, error) = efn.hresult.as_ref().unwrap();
+    if efn.lang == Lang::Rust {
+        cx.error(
+            error,
+            "Result<T, HRESULT> is only supported on extern \"C++\" functions",
+        );
+        return;
+    }
+
+    // The success value is written by C++ through a trailing out-parameter of
+    // type T*, the way COM methods return values.
+    let supported = match &efn.ret {
+        None | Some(Type::ComPtr(_) | Type::Ptr(_)) => true,
+        Some(ty @ Type::Ident(ident)) => {
+            !matches!(Atom::from(&ident.rust), Some(Char | CxxString | RustString))
+                && cx.types.is_guaranteed_pod(ty)
+        }
+        Some(_) => false,
+    };
+    if !supported {
+        let ty = efn.ret.as_ref().unwrap();
+        let desc = describe(cx, ty);
+        let msg = format!(
+            "returning {} through Result<T, HRESULT> is not supported",
+            desc,
+        );
+        cx.error(ty, msg);
+    }
+}
+
+fn check_array_len(cx: &mut Check, efn: &ExternFn, array_len: &Ident) {
+    if efn.lang == Lang::Rust {
+        cx.error(
+            array_len,
+            "#[array_len] is only supported on extern \"C++\" functions",
+        );
+        return;
+    }
+
+    if !matches!(efn.ret, Some(Type::UniqueArray(_) | Type::SharedArray(_))) {
+        cx.error(
+            array_len,
+            "#[array_len] requires the function to return UniqueArray<T> or SharedArray<T>",
+        );
+        return;
+    }
+
+    let arg = efn.args.iter().find(|arg| arg.name.rust == *array_len);
+    match arg {
+        Some(arg) if arg.ty == Usize => {}
+        Some(arg) => cx.error(arg, "array length argument must be of type usize"),
+        None => cx.error(
+            array_len,
+            "#[array_len] must name an argument of the function",
+        ),
+    }
 }
 
 fn check_api_type_alias(cx: &mut Check, alias: &TypeAlias) {
@@ -540,32 +1001,77 @@ fn check_api_impl(cx: &mut Check, imp: &Impl) {
         Type::RustBox(ty)
         | Type::RustVec(ty)
         | Type::UniquePtr(ty)
+        | Type::UniqueArray(ty)
         | Type::SharedPtr(ty)
+        | Type::SharedArray(ty)
+        | Type::SharedConstPtr(ty)
         | Type::WeakPtr(ty)
-        | Type::CxxVector(ty) => {
+        | Type::CxxVector(ty)
+        | Type::CxxPmrVector(ty) => {
             if let Type::Ident(inner) = &ty.inner {
                 if Atom::from(&inner.rust).is_none() {
                     return;
                 }
             }
         }
+        Type::CustomPtr(ptr) => {
+            if let Type::Ident(inner) = &ptr.inner {
+                if Atom::from(&inner.rust).is_none() {
+                    return;
+                }
+            }
+        }
         _ => {}
     }
 
     cx.error(imp, "unsupported Self type of explicit impl");
 }
 
+// The functions of all the extern blocks with the same #[mock] name become the
+// methods of a single mock, so they need to be on the same side of the bridge
+// and have distinct names.
+fn check_mocks(cx: &mut Check) {
+    let mut seen: Vec<&ExternFn> = Vec::new();
+    for api in cx.apis {
+        let efn = match api {
+            Api::CxxFunction(efn) | Api::RustFunction(efn) => efn,
+            _ => continue,
+        };
+        let Some(mock) = &efn.mock else {
+            continue;
+        };
+        let mut same_mock = seen.iter().filter(|prev| prev.mock.as_ref() == Some(mock));
+        if let Some(prev) = same_mock.clone().next() {
+            if (prev.lang == Lang::Rust) != (efn.lang == Lang::Rust) {
+                let msg = format!(
+                    "mock `{}` cannot combine extern \"C++\" and extern \"Rust\" functions",
+                    mock,
+                );
+                cx.error(mock, msg);
+            }
+        }
+        if same_mock.any(|prev| prev.name.rust == efn.name.rust) {
+            let msg = format!(
+                "mock `{}` already has a method named `{}`",
+                mock, efn.name.rust,
+            );
+            cx.error(&efn.name.rust, msg);
+        }
+        seen.push(efn);
+    }
+}
+
 fn check_mut_return_restriction(cx: &mut Check, efn: &ExternFn) {
     if efn.unsafety.is_some() {
         // Unrestricted as long as the function is made unsafe-to-call.
         return;
     }
 
-    match &efn.ret {
-        Some(Type::Ref(ty)) if ty.mutable => {}
-        Some(Type::SliceRef(slice)) if slice.mutable => {}
+    let ret = match &efn.ret {
+        Some(ret @ Type::Ref(ty)) if ty.mutable => ret,
+        Some(ret @ Type::SliceRef(slice)) if slice.mutable => ret,
         _ => return,
-    }
+    };
 
     if let Some(receiver) = efn.receiver() {
         if receiver.mutable {
@@ -611,19 +1117,23 @@ fn check_mut_return_restriction(cx: &mut Check, efn: &ExternFn) {
         return;
     }
 
-    cx.error(
-        efn,
-        "&mut return type is not allowed unless there is a &mut argument",
-    );
+    cx.error(ret, error::MUT_RETURN.msg);
 }
 
 fn check_reserved_name(cx: &mut Check, ident: &Ident) {
     if ident == "Box"
         || ident == "UniquePtr"
+        || ident == "UniqueArray"
         || ident == "SharedPtr"
+        || ident == "SharedArray"
+        || ident == "SharedConstPtr"
         || ident == "WeakPtr"
         || ident == "Vec"
         || ident == "CxxVector"
+        || ident == "CxxPmrVector"
+        || ident == "CustomPtr"
+        || ident == "ComPtr"
+        || ident == "HRESULT"
         || ident == "str"
         || Atom::from(ident).is_some()
     {
@@ -663,16 +1173,22 @@ fn is_unsized(cx: &mut Check, ty: &Type) -> bool {
             ident == CxxString || is_opaque_cxx(cx, ident) || cx.types.rust.contains(ident)
         }
         Type::Array(array) => is_unsized(cx, &array.inner),
-        Type::CxxVector(_) | Type::Fn(_) | Type::Void(_) => true,
+        Type::CxxVector(_) | Type::CxxPmrVector(_) | Type::Fn(_) | Type::Void(_) => true,
         Type::RustBox(_)
         | Type::RustVec(_)
         | Type::UniquePtr(_)
+        | Type::UniqueArray(_)
         | Type::SharedPtr(_)
+        | Type::SharedArray(_)
+        | Type::SharedConstPtr(_)
         | Type::WeakPtr(_)
         | Type::Ref(_)
         | Type::Ptr(_)
         | Type::Str(_)
-        | Type::SliceRef(_) => false,
+        | Type::SliceRef(_)
+        | Type::HashMapRef(_)
+        | Type::CustomPtr(_)
+        | Type::ComPtr(_) => false,
     }
 }
 
@@ -741,13 +1257,20 @@ fn describe(cx: &mut Check, ty: &Type) -> String {
         Type::RustBox(_) => "Box".to_owned(),
         Type::RustVec(_) => "Vec".to_owned(),
         Type::UniquePtr(_) => "unique_ptr".to_owned(),
+        Type::UniqueArray(_) => "unique_ptr<T[]>".to_owned(),
         Type::SharedPtr(_) => "shared_ptr".to_owned(),
+        Type::SharedArray(_) => "shared_ptr<T[]>".to_owned(),
+        Type::SharedConstPtr(_) => "shared_ptr<const T>".to_owned(),
         Type::WeakPtr(_) => "weak_ptr".to_owned(),
         Type::Ref(_) => "reference".to_owned(),
         Type::Ptr(_) => "raw pointer".to_owned(),
         Type::Str(_) => "&str".to_owned(),
         Type::CxxVector(_) => "C++ vector".to_owned(),
+        Type::CxxPmrVector(_) => "C++ pmr vector".to_owned(),
         Type::SliceRef(_) => "slice".to_owned(),
+        Type::HashMapRef(_) => "&HashMap".to_owned(),
+        Type::CustomPtr(_) => "custom smart pointer".to_owned(),
+        Type::ComPtr(_) => "ComPtr".to_owned(),
         Type::Fn(_) => "function pointer".to_owned(),
         Type::Void(_) => "()".to_owned(),
         Type::Array(_) => "array".to_owned(),
@@ -21,6 +21,7 @@ pub(crate) enum Trait {
     PartialOrd,
     Serialize,
     Deserialize,
+    Arbitrary,
     Other(Ident),
 }
 
@@ -39,6 +40,7 @@ impl Derive {
             "PartialOrd" => Trait::PartialOrd,
             "Serialize" => Trait::Serialize,
             "Deserialize" => Trait::Deserialize,
+            "Arbitrary" => Trait::Arbitrary,
             _ => Trait::Other(ident.clone()),
         };
         let span = ident.span();
@@ -67,6 +69,7 @@ impl AsRef<str> for Trait {
             Trait::PartialOrd => "PartialOrd",
             Trait::Serialize => "Serialize",
             Trait::Deserialize => "Deserialize",
+            Trait::Arbitrary => "Arbitrary",
             Trait::Other(_) => "Other",
         }
     }
@@ -81,3 +84,7 @@ impl Display for Derive {
 pub(crate) fn contains(derives: &[Derive], query: Trait) -> bool {
     derives.iter().any(|derive| derive.what == query)
 }
+
+pub(crate) fn find(derives: &[Derive], query: Trait) -> Option<&Derive> {
+    derives.iter().find(|derive| derive.what == query)
+}
@@ -23,10 +23,15 @@ pub(crate) static ERRORS: &[Error] = &[
     DISCRIMINANT_OVERFLOW,
     DOT_INCLUDE,
     DOUBLE_UNDERSCORE,
+    MUT_RETURN,
+    POINTER_ARGUMENT,
     RESERVED_LIFETIME,
     RUST_TYPE_BY_VALUE,
+    SMART_POINTER_RUST_TYPE,
     UNSUPPORTED_TYPE,
     USE_NOT_ALLOWED,
+    VEC_CXX_TYPE,
+    VECTOR_RUST_TYPE,
 ];
 
 pub(crate) static BOX_CXX_TYPE: Error = Error {
@@ -71,6 +76,18 @@ pub(crate) static DOUBLE_UNDERSCORE: Error = Error {
     note: Some("identifiers containing double underscore are reserved in C++"),
 };
 
+pub(crate) static MUT_RETURN: Error = Error {
+    msg: "&mut return type is not allowed unless there is a &mut argument",
+    label: Some("&mut return type"),
+    note: Some("hint: take a &mut or Pin<&mut> argument, or declare the function `unsafe fn`"),
+};
+
+pub(crate) static POINTER_ARGUMENT: Error = Error {
+    msg: "pointer argument requires that the function",
+    label: Some("raw pointer argument"),
+    note: Some("hint: declare it as `unsafe fn`"),
+};
+
 pub(crate) static RESERVED_LIFETIME: Error = Error {
     msg: "invalid lifetime parameter name: `'static`",
     label: Some("'static is a reserved lifetime name"),
@@ -83,6 +100,12 @@ pub(crate) static RUST_TYPE_BY_VALUE: Error = Error {
     note: Some("hint: wrap it in a Box<>"),
 };
 
+pub(crate) static SMART_POINTER_RUST_TYPE: Error = Error {
+    msg: " of a Rust type is not supported yet",
+    label: Some("opaque Rust type"),
+    note: Some("hint: use Box<T> for owned opaque Rust types"),
+};
+
 pub(crate) static UNSUPPORTED_TYPE: Error = Error {
     msg: "unsupported type: ",
     label: Some("unsupported type"),
@@ -98,3 +121,15 @@ pub(crate) static USE_NOT_ALLOWED: Error = Error {
          crate may be used",
     ),
 };
+
+pub(crate) static VEC_CXX_TYPE: Error = Error {
+    msg: "Rust Vec containing C++ type is not supported yet",
+    label: Some("C++ type"),
+    note: Some("hint: use UniquePtr<CxxVector<T>> for a vector of C++ types"),
+};
+
+pub(crate) static VECTOR_RUST_TYPE: Error = Error {
+    msg: "vector containing a Rust type is not supported yet",
+    label: Some("opaque Rust type"),
+    note: Some("hint: use Vec<Box<T>> for a vector of opaque Rust types"),
+};
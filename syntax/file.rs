@@ -1,12 +1,30 @@
 use crate::syntax::cfg::CfgExpr;
 use crate::syntax::namespace::Namespace;
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
 use quote::quote;
-use syn::parse::{Error, Parse, ParseStream, Result};
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::path::{Path as FsPath, PathBuf};
+use syn::parse::{Error, ParseStream, Parser, Result};
 use syn::{
-    braced, token, Abi, Attribute, ForeignItem, Ident, Item as RustItem, ItemEnum, ItemImpl,
-    ItemStruct, ItemUse, LitStr, Token, Visibility,
+    braced, parenthesized, token, Abi, Attribute, ForeignItem, Ident, Item as RustItem, ItemEnum,
+    ItemImpl, ItemStruct, ItemUse, LitStr, Path, Token, Visibility,
 };
 
+mod kw {
+    syn::custom_keyword!(include);
+    syn::custom_keyword!(namespace);
+    syn::custom_keyword!(fake);
+}
+
+// The arguments of #[cxx::bridge(namespace = "...", fake = path::to::Fake)].
+pub(crate) struct BridgeArgs {
+    pub namespace: Namespace,
+    #[allow(dead_code)] // only used by cxxbridge-macro, not cxx-build
+    pub fake: Option<Path>,
+}
+
 pub(crate) struct Module {
     #[allow(dead_code)]
     pub cfg: CfgExpr,
@@ -22,12 +40,24 @@ pub(crate) struct Module {
     #[allow(dead_code)] // only used by cxxbridge-macro, not cxx-build
     pub brace_token: token::Brace,
     pub content: Vec<Item>,
+    // The `fake` argument of the bridge, whose extern "C++" functions are then
+    // faked instead of calling into C++ when the crate is built with cfg(test).
+    #[allow(dead_code)] // only used by cxxbridge-macro, not cxx-build
+    pub fake: Option<Path>,
+    // Whether to wrap every call across the bridge in a cxx::trace span.
+    #[allow(dead_code)] // only used by cxxbridge-macro, not cxx-build
+    pub trace: bool,
+    // Whether a derive(Arbitrary) implements arbitrary::Arbitrary, rather than
+    // being a derive of the user's own.
+    #[allow(dead_code)] // only used by cxxbridge-macro, not cxx-build
+    pub arbitrary: bool,
 }
 
 pub(crate) enum Item {
     Struct(ItemStruct),
     Enum(ItemEnum),
     ForeignMod(ItemForeignMod),
+    Mod(ItemMod),
     Use(ItemUse),
     Impl(ItemImpl),
     Other(RustItem),
@@ -42,8 +72,81 @@ pub(crate) struct ItemForeignMod {
     pub items: Vec<ForeignItem>,
 }
 
-impl Parse for Module {
-    fn parse(input: ParseStream) -> Result<Self> {
+// A module nested in the bridge, or the contents of a file included by
+// `include!("...")` among the items of the bridge, at a path relative to the
+// file containing the include!. Either way its items belong to the bridge the
+// same as if they were written directly in it.
+pub(crate) struct ItemMod {
+    pub attrs: Vec<Attribute>,
+    pub path: Option<PathBuf>,
+    pub content: Vec<Item>,
+}
+
+impl Module {
+    // The files that the bridge includes items from, which the generated code
+    // depends on along with the bridge's own file.
+    #[allow(dead_code)]
+    pub fn included_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        included_files(&self.content, &mut files);
+        files
+    }
+}
+
+fn included_files(items: &[Item], files: &mut Vec<PathBuf>) {
+    for item in items {
+        if let Item::Mod(item) = item {
+            files.extend(item.path.clone());
+            included_files(&item.content, files);
+        }
+    }
+}
+
+// The files read by include!("...") on this thread, for cxx-build and
+// cxxbridge-cmd to show errors within an included file along with its source.
+thread_local! {
+    static INCLUDED_SOURCES: RefCell<Vec<IncludedSource>> = const { RefCell::new(Vec::new()) };
+}
+
+#[allow(dead_code)] // only used by cxx-build and cxxbridge-cmd, not cxxbridge-macro
+pub(crate) struct IncludedSource {
+    pub path: PathBuf,
+    pub source: String,
+    // The span of some token of the file, which tells which file any other
+    // span points into.
+    pub span: Span,
+}
+
+#[allow(dead_code)] // only used by cxx-build and cxxbridge-cmd, not cxxbridge-macro
+pub(crate) fn with_included_sources<T>(f: impl FnOnce(&[IncludedSource]) -> T) -> T {
+    INCLUDED_SOURCES.with(|sources| f(&sources.borrow()))
+}
+
+// Where the paths of include!("...") in a bridge are relative to, and whether
+// the tokens read from the files get the span of the include!.
+pub(crate) struct Includes<'a> {
+    // The directory of the file containing the bridge, or else the paths are
+    // relative to the directory of the crate's Cargo.toml.
+    pub dir: Option<&'a FsPath>,
+    // Set by the procedural macro when it cannot find out the directory of the
+    // file, which it would then resolve differently than the code generator
+    // does. Rather than fall back to the crate's Cargo.toml, include! of a
+    // bridge file is rejected.
+    pub unknown_dir: bool,
+    // Set by the procedural macro, in which tokens parsed from a string have no
+    // location that rustc could point to. Elsewhere the tokens keep their
+    // location within the file, which is shown along with its source.
+    pub respan: bool,
+}
+
+impl Module {
+    pub(crate) fn parse(input: ParseStream, includes: &Includes) -> Result<Self> {
+        let dir = match includes.dir {
+            Some(dir) => Some(absolute(dir.to_owned())),
+            None if includes.unknown_dir => None,
+            None => Some(manifest_dir()),
+        };
+        let respan = includes.respan;
         let cfg = CfgExpr::Unconditional;
         let namespace = Namespace::ROOT;
         let mut attrs = input.call(Attribute::parse_outer)?;
@@ -67,7 +170,7 @@ impl Parse for Module {
 
         let mut items = Vec::new();
         while !content.is_empty() {
-            items.push(content.parse()?);
+            items.push(parse_item(&content, dir.as_deref(), respan)?);
         }
 
         Ok(Module {
@@ -80,54 +183,223 @@ impl Parse for Module {
             ident,
             brace_token,
             content: items,
+            fake: None,
+            trace: false,
+            arbitrary: false,
         })
     }
 }
 
-impl Parse for Item {
-    fn parse(input: ParseStream) -> Result<Self> {
-        let attrs = input.call(Attribute::parse_outer)?;
-
-        let ahead = input.fork();
-        let unsafety = if ahead.parse::<Option<Token![unsafe]>>()?.is_some()
-            && ahead.parse::<Option<Token![extern]>>()?.is_some()
-            && ahead.parse::<Option<LitStr>>().is_ok()
-            && ahead.peek(token::Brace)
-        {
-            Some(input.parse()?)
-        } else {
-            None
+impl BridgeArgs {
+    pub(crate) fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = BridgeArgs {
+            namespace: Namespace::ROOT,
+            fake: None,
         };
 
-        let item = input.parse()?;
-        match item {
-            RustItem::Struct(mut item) => {
-                item.attrs.splice(..0, attrs);
-                Ok(Item::Struct(item))
-            }
-            RustItem::Enum(mut item) => {
-                item.attrs.splice(..0, attrs);
-                Ok(Item::Enum(item))
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::namespace) {
+                input.parse::<kw::namespace>()?;
+                input.parse::<Token![=]>()?;
+                args.namespace = input.parse()?;
+            } else if lookahead.peek(kw::fake) {
+                input.parse::<kw::fake>()?;
+                input.parse::<Token![=]>()?;
+                args.fake = Some(input.parse()?);
+            } else {
+                return Err(lookahead.error());
             }
-            RustItem::ForeignMod(mut item) => {
-                item.attrs.splice(..0, attrs);
-                Ok(Item::ForeignMod(ItemForeignMod {
-                    attrs: item.attrs,
-                    unsafety,
-                    abi: item.abi,
-                    brace_token: item.brace_token,
-                    items: item.items,
-                }))
+            if input.is_empty() {
+                break;
             }
-            RustItem::Impl(mut item) => {
-                item.attrs.splice(..0, attrs);
-                Ok(Item::Impl(item))
-            }
-            RustItem::Use(mut item) => {
-                item.attrs.splice(..0, attrs);
-                Ok(Item::Use(item))
-            }
-            other => Ok(Item::Other(other)),
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(args)
+    }
+}
+
+fn parse_item(input: ParseStream, dir: Option<&FsPath>, respan: bool) -> Result<Item> {
+    let attrs = input.call(Attribute::parse_outer)?;
+
+    if input.peek(Token![mod]) {
+        return parse_mod(input, attrs, dir, respan).map(Item::Mod);
+    }
+    if input.peek(kw::include) && input.peek2(Token![!]) {
+        return parse_include(input, attrs, dir, respan).map(Item::Mod);
+    }
+
+    let ahead = input.fork();
+    let unsafety = if ahead.parse::<Option<Token![unsafe]>>()?.is_some()
+        && ahead.parse::<Option<Token![extern]>>()?.is_some()
+        && ahead.parse::<Option<LitStr>>().is_ok()
+        && ahead.peek(token::Brace)
+    {
+        Some(input.parse()?)
+    } else {
+        None
+    };
+
+    let item = input.parse()?;
+    match item {
+        RustItem::Struct(mut item) => {
+            item.attrs.splice(..0, attrs);
+            Ok(Item::Struct(item))
+        }
+        RustItem::Enum(mut item) => {
+            item.attrs.splice(..0, attrs);
+            Ok(Item::Enum(item))
+        }
+        RustItem::ForeignMod(mut item) => {
+            item.attrs.splice(..0, attrs);
+            Ok(Item::ForeignMod(ItemForeignMod {
+                attrs: item.attrs,
+                unsafety,
+                abi: item.abi,
+                brace_token: item.brace_token,
+                items: item.items,
+            }))
+        }
+        RustItem::Impl(mut item) => {
+            item.attrs.splice(..0, attrs);
+            Ok(Item::Impl(item))
+        }
+        RustItem::Use(mut item) => {
+            item.attrs.splice(..0, attrs);
+            Ok(Item::Use(item))
+        }
+        other => Ok(Item::Other(other)),
+    }
+}
+
+fn parse_mod(
+    input: ParseStream,
+    mut attrs: Vec<Attribute>,
+    dir: Option<&FsPath>,
+    respan: bool,
+) -> Result<ItemMod> {
+    let mod_token: Token![mod] = input.parse()?;
+    let ident: Ident = input.parse()?;
+
+    let semi: Option<Token![;]> = input.parse()?;
+    if let Some(semi) = semi {
+        let span = quote!(#mod_token #ident #semi);
+        return Err(Error::new_spanned(
+            span,
+            "module in #[cxx::bridge] must have inline contents",
+        ));
+    }
+
+    let content;
+    braced!(content in input);
+    let content = parse_mod_content(&content, &mut attrs, dir, respan)?;
+
+    Ok(ItemMod {
+        attrs,
+        path: None,
+        content,
+    })
+}
+
+fn parse_include(
+    input: ParseStream,
+    mut attrs: Vec<Attribute>,
+    dir: Option<&FsPath>,
+    respan: bool,
+) -> Result<ItemMod> {
+    input.parse::<kw::include>()?;
+    input.parse::<Token![!]>()?;
+    let paren;
+    parenthesized!(paren in input);
+    let lit: LitStr = paren.parse()?;
+    input.parse::<Token![;]>()?;
+
+    let Some(dir) = dir else {
+        let msg = format!(
+            "include!(\"{}\") of a bridge file in #[cxx::bridge] requires Rust 1.88 or newer, in which the path can be resolved relative to the file containing the bridge",
+            lit.value(),
+        );
+        return Err(Error::new(lit.span(), msg));
+    };
+    let path = dir.join(lit.value());
+    let source = fs::read_to_string(&path).map_err(|err| {
+        let msg = format!("failed to read {}: {}", path.display(), err);
+        Error::new(lit.span(), msg)
+    })?;
+    let tokens: TokenStream = source.parse().map_err(|err| {
+        let msg = format!("failed to parse {}: {}", path.display(), err);
+        Error::new(lit.span(), msg)
+    })?;
+    let tokens = if respan {
+        set_span(tokens, lit.span())
+    } else {
+        if let Some(token) = tokens.clone().into_iter().next() {
+            INCLUDED_SOURCES.with(|sources| {
+                let mut sources = sources.borrow_mut();
+                sources.retain(|included| included.path != path);
+                sources.push(IncludedSource {
+                    path: path.clone(),
+                    source,
+                    span: token.span(),
+                });
+            });
         }
+        tokens
+    };
+    let dir = path.parent().unwrap_or(dir);
+    let content = (|input: ParseStream| parse_mod_content(input, &mut attrs, Some(dir), respan))
+        .parse2(tokens)?;
+
+    Ok(ItemMod {
+        attrs,
+        path: Some(path),
+        content,
+    })
+}
+
+fn parse_mod_content(
+    input: ParseStream,
+    attrs: &mut Vec<Attribute>,
+    dir: Option<&FsPath>,
+    respan: bool,
+) -> Result<Vec<Item>> {
+    attrs.extend(input.call(Attribute::parse_inner)?);
+    let mut items = Vec::new();
+    while !input.is_empty() {
+        items.push(parse_item(input, dir, respan)?);
+    }
+    Ok(items)
+}
+
+fn manifest_dir() -> PathBuf {
+    let dir = env::var_os("CARGO_MANIFEST_DIR").map_or_else(PathBuf::new, PathBuf::from);
+    absolute(dir)
+}
+
+// Absolute, so that the path can be passed to include_str! in the expansion of
+// the bridge, which resolves relative paths differently.
+fn absolute(dir: PathBuf) -> PathBuf {
+    match env::current_dir() {
+        Ok(current_dir) => current_dir.join(dir),
+        Err(_) => dir,
     }
 }
+
+fn set_span(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let stream = set_span(group.stream(), span);
+                let mut group = Group::new(group.delimiter(), stream);
+                group.set_span(span);
+                TokenTree::Group(group)
+            }
+            mut token => {
+                token.set_span(span);
+                token
+            }
+        })
+        .collect()
+}
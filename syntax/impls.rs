@@ -1,5 +1,6 @@
 use crate::syntax::{
-    Array, ExternFn, Include, Lifetimes, Ptr, Receiver, Ref, Signature, SliceRef, Ty1, Type, Var,
+    Array, CustomPtr, ExternFn, HashMapRef, Include, Lifetimes, Ptr, Receiver, Ref, Signature,
+    SliceRef, Ty1, Type, Var,
 };
 use std::hash::{Hash, Hasher};
 use std::mem;
@@ -11,6 +12,7 @@ impl PartialEq for Include {
             cfg: _,
             path,
             kind,
+            private: _,
             begin_span: _,
             end_span: _,
         } = self;
@@ -18,6 +20,7 @@ impl PartialEq for Include {
             cfg: _,
             path: path2,
             kind: kind2,
+            private: _,
             begin_span: _,
             end_span: _,
         } = other;
@@ -46,16 +49,23 @@ impl Hash for Type {
             Type::Ident(t) => t.hash(state),
             Type::RustBox(t) => t.hash(state),
             Type::UniquePtr(t) => t.hash(state),
+            Type::UniqueArray(t) => t.hash(state),
             Type::SharedPtr(t) => t.hash(state),
+            Type::SharedArray(t) => t.hash(state),
+            Type::SharedConstPtr(t) => t.hash(state),
             Type::WeakPtr(t) => t.hash(state),
             Type::Ref(t) => t.hash(state),
             Type::Ptr(t) => t.hash(state),
             Type::Str(t) => t.hash(state),
             Type::RustVec(t) => t.hash(state),
             Type::CxxVector(t) => t.hash(state),
+            Type::CxxPmrVector(t) => t.hash(state),
             Type::Fn(t) => t.hash(state),
             Type::SliceRef(t) => t.hash(state),
             Type::Array(t) => t.hash(state),
+            Type::HashMapRef(t) => t.hash(state),
+            Type::CustomPtr(t) => t.hash(state),
+            Type::ComPtr(t) => t.hash(state),
             Type::Void(_) => {}
         }
     }
@@ -69,14 +79,21 @@ impl PartialEq for Type {
             (Type::Ident(lhs), Type::Ident(rhs)) => lhs == rhs,
             (Type::RustBox(lhs), Type::RustBox(rhs)) => lhs == rhs,
             (Type::UniquePtr(lhs), Type::UniquePtr(rhs)) => lhs == rhs,
+            (Type::UniqueArray(lhs), Type::UniqueArray(rhs)) => lhs == rhs,
             (Type::SharedPtr(lhs), Type::SharedPtr(rhs)) => lhs == rhs,
+            (Type::SharedArray(lhs), Type::SharedArray(rhs)) => lhs == rhs,
+            (Type::SharedConstPtr(lhs), Type::SharedConstPtr(rhs)) => lhs == rhs,
             (Type::WeakPtr(lhs), Type::WeakPtr(rhs)) => lhs == rhs,
             (Type::Ref(lhs), Type::Ref(rhs)) => lhs == rhs,
             (Type::Str(lhs), Type::Str(rhs)) => lhs == rhs,
             (Type::RustVec(lhs), Type::RustVec(rhs)) => lhs == rhs,
             (Type::CxxVector(lhs), Type::CxxVector(rhs)) => lhs == rhs,
+            (Type::CxxPmrVector(lhs), Type::CxxPmrVector(rhs)) => lhs == rhs,
             (Type::Fn(lhs), Type::Fn(rhs)) => lhs == rhs,
             (Type::SliceRef(lhs), Type::SliceRef(rhs)) => lhs == rhs,
+            (Type::HashMapRef(lhs), Type::HashMapRef(rhs)) => lhs == rhs,
+            (Type::CustomPtr(lhs), Type::CustomPtr(rhs)) => lhs == rhs,
+            (Type::ComPtr(lhs), Type::ComPtr(rhs)) => lhs == rhs,
             (Type::Void(_), Type::Void(_)) => true,
             (_, _) => false,
         }
@@ -268,6 +285,91 @@ impl Hash for SliceRef {
     }
 }
 
+impl Eq for HashMapRef {}
+
+impl PartialEq for HashMapRef {
+    fn eq(&self, other: &Self) -> bool {
+        let HashMapRef {
+            ampersand: _,
+            lifetime,
+            name: _,
+            langle: _,
+            key,
+            comma: _,
+            value,
+            rangle: _,
+        } = self;
+        let HashMapRef {
+            ampersand: _,
+            lifetime: lifetime2,
+            name: _,
+            langle: _,
+            key: key2,
+            comma: _,
+            value: value2,
+            rangle: _,
+        } = other;
+        lifetime == lifetime2 && key == key2 && value == value2
+    }
+}
+
+impl Hash for HashMapRef {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let HashMapRef {
+            ampersand: _,
+            lifetime,
+            name: _,
+            langle: _,
+            key,
+            comma: _,
+            value,
+            rangle: _,
+        } = self;
+        lifetime.hash(state);
+        key.hash(state);
+        value.hash(state);
+    }
+}
+
+impl Eq for CustomPtr {}
+
+impl PartialEq for CustomPtr {
+    fn eq(&self, other: &Self) -> bool {
+        let CustomPtr {
+            name: _,
+            langle: _,
+            family,
+            comma: _,
+            inner,
+            rangle: _,
+        } = self;
+        let CustomPtr {
+            name: _,
+            langle: _,
+            family: family2,
+            comma: _,
+            inner: inner2,
+            rangle: _,
+        } = other;
+        family == family2 && inner == inner2
+    }
+}
+
+impl Hash for CustomPtr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let CustomPtr {
+            name: _,
+            langle: _,
+            family,
+            comma: _,
+            inner,
+            rangle: _,
+        } = self;
+        family.hash(state);
+        inner.hash(state);
+    }
+}
+
 impl Eq for Array {}
 
 impl PartialEq for Array {
@@ -319,6 +421,7 @@ impl PartialEq for Signature {
             throws,
             paren_token: _,
             throws_tokens: _,
+            hresult,
         } = self;
         let Signature {
             asyncness: asyncness2,
@@ -331,12 +434,14 @@ impl PartialEq for Signature {
             throws: throws2,
             paren_token: _,
             throws_tokens: _,
+            hresult: hresult2,
         } = other;
         asyncness.is_some() == asyncness2.is_some()
             && unsafety.is_some() == unsafety2.is_some()
             && kind == kind2
             && ret == ret2
             && throws == throws2
+            && hresult.is_some() == hresult2.is_some()
             && args.len() == args2.len()
             && args.iter().zip(args2).all(|(arg, arg2)| {
                 let Var {
@@ -347,6 +452,7 @@ impl PartialEq for Signature {
                     name: _,
                     colon_token: _,
                     ty,
+                    vocabulary: _,
                 } = arg;
                 let Var {
                     cfg: _,
@@ -356,6 +462,7 @@ impl PartialEq for Signature {
                     name: _,
                     colon_token: _,
                     ty: ty2,
+                    vocabulary: _,
                 } = arg2;
                 ty == ty2
             })
@@ -375,6 +482,7 @@ impl Hash for Signature {
             throws,
             paren_token: _,
             throws_tokens: _,
+            hresult,
         } = self;
         asyncness.is_some().hash(state);
         unsafety.is_some().hash(state);
@@ -388,11 +496,13 @@ impl Hash for Signature {
                 name: _,
                 colon_token: _,
                 ty,
+                vocabulary: _,
             } = arg;
             ty.hash(state);
         }
         ret.hash(state);
         throws.hash(state);
+        hresult.is_some().hash(state);
     }
 }
 
@@ -27,10 +27,18 @@ impl<'a> Types<'a> {
             | Type::Str(_)
             | Type::Fn(_)
             | Type::Void(_)
-            | Type::SliceRef(_) => Definite(true),
-            Type::UniquePtr(_) | Type::SharedPtr(_) | Type::WeakPtr(_) | Type::CxxVector(_) => {
-                Definite(false)
-            }
+            | Type::SliceRef(_)
+            | Type::HashMapRef(_) => Definite(true),
+            Type::UniquePtr(_)
+            | Type::UniqueArray(_)
+            | Type::SharedPtr(_)
+            | Type::SharedArray(_)
+            | Type::SharedConstPtr(_)
+            | Type::WeakPtr(_)
+            | Type::CxxVector(_)
+            | Type::CxxPmrVector(_)
+            | Type::CustomPtr(_)
+            | Type::ComPtr(_) => Definite(false),
             Type::Ref(ty) => self.determine_improper_ctype(&ty.inner),
             Type::Ptr(ty) => self.determine_improper_ctype(&ty.inner),
             Type::Array(ty) => self.determine_improper_ctype(&ty.inner),
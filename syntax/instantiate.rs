@@ -1,4 +1,4 @@
-use crate::syntax::{NamedType, Ty1, Type};
+use crate::syntax::{CustomPtr, Derive, NamedType, Ty1, Type};
 use proc_macro2::{Ident, Span};
 use std::hash::{Hash, Hasher};
 use syn::Token;
@@ -8,9 +8,14 @@ pub(crate) enum ImplKey<'a> {
     RustBox(NamedImplKey<'a>),
     RustVec(NamedImplKey<'a>),
     UniquePtr(NamedImplKey<'a>),
+    UniqueArray(NamedImplKey<'a>),
     SharedPtr(NamedImplKey<'a>),
+    SharedArray(NamedImplKey<'a>),
+    SharedConstPtr(NamedImplKey<'a>),
     WeakPtr(NamedImplKey<'a>),
     CxxVector(NamedImplKey<'a>),
+    CxxPmrVector(NamedImplKey<'a>),
+    CustomPtr(&'a Ident, NamedImplKey<'a>),
 }
 
 pub(crate) struct NamedImplKey<'a> {
@@ -39,10 +44,22 @@ impl Type {
             if let Type::Ident(ident) = &ty.inner {
                 return Some(ImplKey::UniquePtr(NamedImplKey::new(ty, ident)));
             }
+        } else if let Type::UniqueArray(ty) = self {
+            if let Type::Ident(ident) = &ty.inner {
+                return Some(ImplKey::UniqueArray(NamedImplKey::new(ty, ident)));
+            }
         } else if let Type::SharedPtr(ty) = self {
             if let Type::Ident(ident) = &ty.inner {
                 return Some(ImplKey::SharedPtr(NamedImplKey::new(ty, ident)));
             }
+        } else if let Type::SharedArray(ty) = self {
+            if let Type::Ident(ident) = &ty.inner {
+                return Some(ImplKey::SharedArray(NamedImplKey::new(ty, ident)));
+            }
+        } else if let Type::SharedConstPtr(ty) = self {
+            if let Type::Ident(ident) = &ty.inner {
+                return Some(ImplKey::SharedConstPtr(NamedImplKey::new(ty, ident)));
+            }
         } else if let Type::WeakPtr(ty) = self {
             if let Type::Ident(ident) = &ty.inner {
                 return Some(ImplKey::WeakPtr(NamedImplKey::new(ty, ident)));
@@ -51,6 +68,15 @@ impl Type {
             if let Type::Ident(ident) = &ty.inner {
                 return Some(ImplKey::CxxVector(NamedImplKey::new(ty, ident)));
             }
+        } else if let Type::CxxPmrVector(ty) = self {
+            if let Type::Ident(ident) = &ty.inner {
+                return Some(ImplKey::CxxPmrVector(NamedImplKey::new(ty, ident)));
+            }
+        } else if let Type::CustomPtr(ptr) = self {
+            if let Type::Ident(ident) = &ptr.inner {
+                let key = NamedImplKey::custom_ptr(ptr, ident);
+                return Some(ImplKey::CustomPtr(&ptr.family, key));
+            }
         }
         None
     }
@@ -80,4 +106,26 @@ impl<'a> NamedImplKey<'a> {
             end_span: outer.rangle.span,
         }
     }
+
+    fn custom_ptr(outer: &CustomPtr, inner: &'a NamedType) -> Self {
+        NamedImplKey {
+            begin_span: outer.name.span(),
+            rust: &inner.rust,
+            lt_token: inner.generics.lt_token,
+            gt_token: inner.generics.gt_token,
+            end_span: outer.rangle.span,
+        }
+    }
+
+    // Instantiation required by a derive on the type's declaration rather than
+    // by any use of the generic type in the bridge.
+    pub(crate) fn derived(rust: &'a Ident, derive: &Derive) -> Self {
+        NamedImplKey {
+            begin_span: derive.span,
+            rust,
+            lt_token: None,
+            gt_token: None,
+            end_span: derive.span,
+        }
+    }
 }
@@ -26,6 +26,7 @@ pub(crate) mod repr;
 pub(crate) mod resolve;
 pub(crate) mod set;
 mod signature;
+mod suggest;
 pub(crate) mod symbol;
 mod tokens;
 mod toposort;
@@ -69,6 +70,8 @@ pub(crate) struct Include {
     pub path: String,
     pub kind: IncludeKind,
     #[allow(dead_code)] // only used by cxx-build, not cxxbridge-macro
+    pub private: bool,
+    #[allow(dead_code)] // only used by cxx-build, not cxxbridge-macro
     pub begin_span: Span,
     #[allow(dead_code)] // only used by cxx-build, not cxxbridge-macro
     pub end_span: Span,
@@ -102,6 +105,15 @@ pub(crate) struct ExternType {
     #[allow(dead_code)] // only used by cxxbridge-macro, not cxx-build
     pub semi_token: Token![;],
     pub trusted: bool,
+    pub incomplete: bool,
+    pub send: bool,
+    pub sync: bool,
+    pub smart_ptr: bool,
+    pub com_iid: Option<u128>,
+    // The binding, such as `String` or `Vec<u8>`, of a third-party C++ type
+    // declared with #[vocabulary = ...]. Functions that take or return the type
+    // by value are rewritten by the parser to use the binding instead.
+    pub vocabulary: Option<RustType>,
 }
 
 pub(crate) struct Struct {
@@ -137,6 +149,10 @@ pub(crate) struct Enum {
     pub variants: Vec<Variant>,
     pub repr: EnumRepr,
     pub explicit_repr: bool,
+    // Whether derive(Serialize, Deserialize) uses the names of the variants in
+    // place of serde's own derive on the struct holding the discriminant.
+    #[allow(dead_code)] // only used by cxxbridge-macro, not cxx-build
+    pub serde_variant_names: bool,
 }
 
 pub(crate) struct EnumRepr {
@@ -157,6 +173,13 @@ pub(crate) struct ExternFn {
     pub sig: Signature,
     pub semi_token: Token![;],
     pub trusted: bool,
+    pub array_len: Option<Ident>,
+    pub catch_foreign_exceptions: bool,
+    pub mock: Option<Ident>,
+    // The #[vocabulary] type that the return type was written as, if it was
+    // replaced by the vocabulary type's binding.
+    #[allow(dead_code)] // only used by cxx-build, not cxxbridge-macro
+    pub ret_vocabulary: Option<Pair>,
 }
 
 pub(crate) struct TypeAlias {
@@ -212,6 +235,10 @@ pub(crate) struct Signature {
     pub throws: bool,
     pub paren_token: Paren,
     pub throws_tokens: Option<(kw::Result, Token![<], Token![>])>,
+    // The `, HRESULT` of a `Result<T, HRESULT>` return type. Such a function
+    // reports failure through its COM status code rather than by throwing, so
+    // `throws` is false.
+    pub hresult: Option<(Token![,], Ident)>,
 }
 
 #[derive(PartialEq, Hash)]
@@ -236,6 +263,9 @@ pub(crate) struct Var {
     #[allow(dead_code)] // only used by cxxbridge-macro, not cxx-build
     pub colon_token: Token![:],
     pub ty: Type,
+    // Same as ExternFn::ret_vocabulary, for an argument of a function.
+    #[allow(dead_code)] // only used by cxx-build, not cxxbridge-macro
+    pub vocabulary: Option<Pair>,
 }
 
 pub(crate) struct Receiver {
@@ -270,16 +300,23 @@ pub(crate) enum Type {
     RustBox(Box<Ty1>),
     RustVec(Box<Ty1>),
     UniquePtr(Box<Ty1>),
+    UniqueArray(Box<Ty1>),
     SharedPtr(Box<Ty1>),
+    SharedArray(Box<Ty1>),
+    SharedConstPtr(Box<Ty1>),
     WeakPtr(Box<Ty1>),
     Ref(Box<Ref>),
     Ptr(Box<Ptr>),
     Str(Box<Ref>),
     CxxVector(Box<Ty1>),
+    CxxPmrVector(Box<Ty1>),
     Fn(Box<Signature>),
     Void(Span),
     SliceRef(Box<SliceRef>),
     Array(Box<Array>),
+    HashMapRef(Box<HashMapRef>),
+    CustomPtr(Box<CustomPtr>),
+    ComPtr(Box<Ty1>),
 }
 
 pub(crate) struct Ty1 {
@@ -316,6 +353,26 @@ pub(crate) struct SliceRef {
     pub mutability: Option<Token![mut]>,
 }
 
+pub(crate) struct HashMapRef {
+    pub ampersand: Token![&],
+    pub lifetime: Option<Lifetime>,
+    pub name: Ident,
+    pub langle: Token![<],
+    pub key: Type,
+    pub comma: Token![,],
+    pub value: Type,
+    pub rangle: Token![>],
+}
+
+pub(crate) struct CustomPtr {
+    pub name: Ident,
+    pub langle: Token![<],
+    pub family: Ident,
+    pub comma: Token![,],
+    pub inner: Type,
+    pub rangle: Token![>],
+}
+
 pub(crate) struct Array {
     pub bracket: Bracket,
     pub inner: Type,
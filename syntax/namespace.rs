@@ -3,13 +3,9 @@ use quote::IdentFragment;
 use std::fmt::{self, Display};
 use std::slice::Iter;
 use syn::parse::{Error, Parse, ParseStream, Result};
-use syn::{Expr, Ident, Lit, Meta, Token};
+use syn::{Expr, Ident, Lit, Meta};
 
-mod kw {
-    syn::custom_keyword!(namespace);
-}
-
-#[derive(Clone, Default, PartialEq)]
+#[derive(Clone, Default, PartialEq, Debug)]
 pub(crate) struct Namespace {
     segments: Vec<Ident>,
 }
@@ -23,18 +19,6 @@ impl Namespace {
         self.segments.iter()
     }
 
-    pub(crate) fn parse_bridge_attr_namespace(input: ParseStream) -> Result<Self> {
-        if input.is_empty() {
-            return Ok(Namespace::ROOT);
-        }
-
-        input.parse::<kw::namespace>()?;
-        input.parse::<Token![=]>()?;
-        let namespace = input.parse::<Namespace>()?;
-        input.parse::<Option<Token![,]>>()?;
-        Ok(namespace)
-    }
-
     pub(crate) fn parse_meta(meta: &Meta) -> Result<Self> {
         if let Meta::NameValue(meta) = meta {
             match &meta.value {
@@ -1,14 +1,15 @@
 use crate::syntax::attrs::OtherAttrs;
 use crate::syntax::cfg::CfgExpr;
 use crate::syntax::discriminant::DiscriminantSet;
-use crate::syntax::file::{Item, ItemForeignMod};
+use crate::syntax::file::{Item, ItemForeignMod, ItemMod};
 use crate::syntax::report::Errors;
 use crate::syntax::repr::Repr;
+use crate::syntax::suggest;
 use crate::syntax::Atom::*;
 use crate::syntax::{
-    attrs, error, Api, Array, Derive, Doc, Enum, EnumRepr, ExternFn, ExternType, FnKind,
-    ForeignName, Impl, Include, IncludeKind, Lang, Lifetimes, NamedType, Namespace, Pair, Ptr,
-    Receiver, Ref, Signature, SliceRef, Struct, Ty1, Type, TypeAlias, Var, Variant,
+    attrs, error, Api, Array, CustomPtr, Derive, Doc, Enum, EnumRepr, ExternFn, ExternType, FnKind,
+    ForeignName, HashMapRef, Impl, Include, IncludeKind, Lang, Lifetimes, NamedType, Namespace,
+    Pair, Ptr, Receiver, Ref, Signature, SliceRef, Struct, Ty1, Type, TypeAlias, Var, Variant,
 };
 use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
 use quote::{format_ident, quote, quote_spanned};
@@ -17,8 +18,8 @@ use syn::parse::{ParseStream, Parser};
 use syn::punctuated::Punctuated;
 use syn::{
     Abi, Attribute, Error, Expr, Fields, FnArg, ForeignItem, ForeignItemFn, ForeignItemType,
-    GenericArgument, GenericParam, Generics, Ident, ItemEnum, ItemImpl, ItemStruct, Lit, LitStr,
-    Pat, PathArguments, Result, ReturnType, Signature as RustSignature, Token, TraitBound,
+    GenericArgument, GenericParam, Generics, Ident, ItemEnum, ItemImpl, ItemStruct, Lifetime, Lit,
+    LitStr, Pat, PathArguments, Result, ReturnType, Signature as RustSignature, Token, TraitBound,
     TraitBoundModifier, Type as RustType, TypeArray, TypeBareFn, TypeParamBound, TypePath, TypePtr,
     TypeReference, Variant as RustVariant, Visibility,
 };
@@ -35,6 +36,18 @@ pub(crate) fn parse_items(
     namespace: &Namespace,
 ) -> Vec<Api> {
     let mut apis = Vec::new();
+    parse_items_into(cx, items, &mut apis, trusted, namespace);
+    replace_vocabulary_types(&mut apis);
+    apis
+}
+
+fn parse_items_into(
+    cx: &mut Errors,
+    items: Vec<Item>,
+    apis: &mut Vec<Api>,
+    trusted: bool,
+    namespace: &Namespace,
+) {
     for item in items {
         match item {
             Item::Struct(item) => match parse_struct(cx, item, namespace) {
@@ -43,8 +56,9 @@ pub(crate) fn parse_items(
             },
             Item::Enum(item) => apis.push(parse_enum(cx, item, namespace)),
             Item::ForeignMod(foreign_mod) => {
-                parse_foreign_mod(cx, foreign_mod, &mut apis, trusted, namespace);
+                parse_foreign_mod(cx, foreign_mod, apis, trusted, namespace);
             }
+            Item::Mod(item) => parse_mod(cx, item, apis, trusted, namespace),
             Item::Impl(item) => match parse_impl(cx, item) {
                 Ok(imp) => apis.push(imp),
                 Err(err) => cx.push(err),
@@ -53,7 +67,91 @@ pub(crate) fn parse_items(
             Item::Other(item) => cx.error(item, "unsupported item"),
         }
     }
-    apis
+}
+
+// The items of a nested module are parsed as items of the bridge. A #[cfg] on
+// the module applies to each of its items, and a #[namespace] is the default
+// namespace of its items in place of the bridge's.
+fn parse_mod(
+    cx: &mut Errors,
+    item: ItemMod,
+    apis: &mut Vec<Api>,
+    trusted: bool,
+    namespace: &Namespace,
+) {
+    let (cfg_attrs, attrs): (Vec<Attribute>, Vec<Attribute>) = item
+        .attrs
+        .into_iter()
+        .partition(|attr| attr.path().is_ident("cfg"));
+    let mut namespace = namespace.clone();
+    let mut doc = Doc::new();
+    attrs::parse(
+        cx,
+        attrs,
+        attrs::Parser {
+            namespace: Some(&mut namespace),
+            doc: Some(&mut doc),
+            ..Default::default()
+        },
+    );
+    let mut items = item.content;
+    for item in &mut items {
+        let attrs = match item {
+            Item::Struct(item) => &mut item.attrs,
+            Item::Enum(item) => &mut item.attrs,
+            Item::ForeignMod(item) => &mut item.attrs,
+            Item::Mod(item) => &mut item.attrs,
+            Item::Use(item) => &mut item.attrs,
+            Item::Impl(item) => &mut item.attrs,
+            Item::Other(_) => continue,
+        };
+        attrs.splice(..0, cfg_attrs.iter().cloned());
+    }
+    parse_items_into(cx, items, apis, trusted, &namespace);
+}
+
+// Extern C++ functions which take or return a #[vocabulary] type by value get
+// the vocabulary type's binding in its place, and remember the vocabulary type
+// for the generated C++ code to convert from and to. Any other use of a
+// vocabulary type is left alone, for the typecheck to reject.
+fn replace_vocabulary_types(apis: &mut [Api]) {
+    let mut vocabulary = Vec::new();
+    for api in apis.iter() {
+        if let Api::CxxType(ety) = api {
+            if let Some(binding) = &ety.vocabulary {
+                if parse_vocabulary_binding(binding).is_some() {
+                    vocabulary.push((ety.name.clone(), binding.clone()));
+                }
+            }
+        }
+    }
+    if vocabulary.is_empty() {
+        return;
+    }
+
+    let replace = |ty: &mut Type| -> Option<Pair> {
+        let ident = match ty {
+            Type::Ident(ident) if ident.generics.lifetimes.is_empty() => &ident.rust,
+            _ => return None,
+        };
+        let (name, binding) = vocabulary.iter().find(|(name, _)| name.rust == *ident)?;
+        *ty = parse_vocabulary_binding(binding).unwrap();
+        Some(name.clone())
+    };
+
+    for api in apis {
+        if let Api::CxxFunction(efn) = api {
+            if efn.sig.asyncness.is_some() || efn.sig.hresult.is_some() {
+                continue;
+            }
+            for arg in &mut efn.sig.args {
+                arg.vocabulary = replace(&mut arg.ty);
+            }
+            if let Some(ret) = &mut efn.sig.ret {
+                efn.ret_vocabulary = replace(ret);
+            }
+        }
+    }
 }
 
 fn parse_struct(cx: &mut Errors, mut item: ItemStruct, namespace: &Namespace) -> Result<Api> {
@@ -172,6 +270,7 @@ fn parse_struct(cx: &mut Errors, mut item: ItemStruct, namespace: &Namespace) ->
             name,
             colon_token,
             ty,
+            vocabulary: None,
         });
     }
 
@@ -208,6 +307,7 @@ fn parse_enum(cx: &mut Errors, item: ItemEnum, namespace: &Namespace) -> Api {
     let mut namespace = namespace.clone();
     let mut cxx_name = None;
     let mut rust_name = None;
+    let mut serde_variant_names = false;
     let attrs = attrs::parse(
         cx,
         item.attrs,
@@ -219,6 +319,7 @@ fn parse_enum(cx: &mut Errors, item: ItemEnum, namespace: &Namespace) -> Api {
             namespace: Some(&mut namespace),
             cxx_name: Some(&mut cxx_name),
             rust_name: Some(&mut rust_name),
+            serde_variant_names: Some(&mut serde_variant_names),
             ..Default::default()
         },
     );
@@ -293,6 +394,7 @@ fn parse_enum(cx: &mut Errors, item: ItemEnum, namespace: &Namespace) -> Api {
         variants,
         repr,
         explicit_repr,
+        serde_variant_names,
     })
 }
 
@@ -376,15 +478,25 @@ fn parse_foreign_mod(
 
     let mut cfg = CfgExpr::Unconditional;
     let mut namespace = namespace.clone();
+    let mut catch_foreign_exceptions = false;
+    let mut mock = None;
     let attrs = attrs::parse(
         cx,
         foreign_mod.attrs,
         attrs::Parser {
             cfg: Some(&mut cfg),
             namespace: Some(&mut namespace),
+            catch_foreign_exceptions: Some(&mut catch_foreign_exceptions),
+            mock: Some(&mut mock),
             ..Default::default()
         },
     );
+    if catch_foreign_exceptions && lang != Lang::Cxx {
+        cx.error(
+            &foreign_mod.abi,
+            "#[catch_foreign_exceptions] is only supported on extern \"C++\" blocks",
+        );
+    }
 
     let mut items = Vec::new();
     for foreign in foreign_mod.items {
@@ -403,6 +515,14 @@ fn parse_foreign_mod(
                 match foreign.mac.parse_body_with(parse_include) {
                     Ok(mut include) => {
                         include.cfg = cfg.clone();
+                        attrs::parse(
+                            cx,
+                            foreign.attrs,
+                            attrs::Parser {
+                                private: Some(&mut include.private),
+                                ..Default::default()
+                            },
+                        );
                         items.push(Api::Include(include));
                     }
                     Err(err) => cx.push(err),
@@ -418,6 +538,22 @@ fn parse_foreign_mod(
         }
     }
 
+    if catch_foreign_exceptions {
+        for api in &mut items {
+            if let Api::CxxFunction(efn) = api {
+                efn.catch_foreign_exceptions = true;
+            }
+        }
+    }
+
+    if let Some(mock) = &mock {
+        for api in &mut items {
+            if let Api::CxxFunction(efn) | Api::RustFunction(efn) = api {
+                efn.mock = Some(mock.clone());
+            }
+        }
+    }
+
     if !trusted
         && items.iter().any(|api| match api {
             Api::CxxFunction(efn) => efn.unsafety.is_none(),
@@ -485,6 +621,12 @@ fn parse_extern_type(
     let mut namespace = namespace.clone();
     let mut cxx_name = None;
     let mut rust_name = None;
+    let mut incomplete = false;
+    let mut send = false;
+    let mut sync = false;
+    let mut smart_ptr = false;
+    let mut com_iid = None;
+    let mut vocabulary = None;
     let mut attrs = attrs.clone();
     attrs.extend(attrs::parse(
         cx,
@@ -496,6 +638,12 @@ fn parse_extern_type(
             namespace: Some(&mut namespace),
             cxx_name: Some(&mut cxx_name),
             rust_name: Some(&mut rust_name),
+            incomplete: Some(&mut incomplete),
+            send: Some(&mut send),
+            sync: Some(&mut sync),
+            smart_ptr: Some(&mut smart_ptr),
+            com_interface: Some(&mut com_iid),
+            vocabulary: Some(&mut vocabulary),
             ..Default::default()
         },
     ));
@@ -525,6 +673,12 @@ fn parse_extern_type(
         bounds,
         semi_token,
         trusted,
+        incomplete,
+        send,
+        sync,
+        smart_ptr,
+        com_iid,
+        vocabulary,
     })
 }
 
@@ -543,6 +697,8 @@ fn parse_extern_fn(
     let mut cxx_name = None;
     let mut rust_name = None;
     let mut self_type = None;
+    let mut array_len = None;
+    let mut catch_foreign_exceptions = false;
     let mut attrs = attrs.clone();
     attrs.extend(attrs::parse(
         cx,
@@ -554,10 +710,19 @@ fn parse_extern_fn(
             cxx_name: Some(&mut cxx_name),
             rust_name: Some(&mut rust_name),
             self_type: Some(&mut self_type),
+            array_len: Some(&mut array_len),
+            catch_foreign_exceptions: Some(&mut catch_foreign_exceptions),
             ..Default::default()
         },
     ));
 
+    if catch_foreign_exceptions && lang != Lang::Cxx {
+        cx.error(
+            &foreign_fn.sig.ident,
+            "#[catch_foreign_exceptions] is only supported on extern \"C++\" functions",
+        );
+    }
+
     let generics = &foreign_fn.sig.generics;
     if generics.where_clause.is_some()
         || generics.params.iter().any(|param| match param {
@@ -668,6 +833,7 @@ fn parse_extern_fn(
                     name,
                     colon_token,
                     ty,
+                    vocabulary: None,
                 });
                 if let Some(comma) = comma {
                     args.push_punct(*comma);
@@ -688,8 +854,9 @@ fn parse_extern_fn(
     };
 
     let mut throws_tokens = None;
-    let ret = parse_return_type(&foreign_fn.sig.output, &mut throws_tokens)?;
-    let throws = throws_tokens.is_some();
+    let mut hresult = None;
+    let ret = parse_return_type(&foreign_fn.sig.output, &mut throws_tokens, &mut hresult)?;
+    let throws = throws_tokens.is_some() && hresult.is_none();
     let asyncness = foreign_fn.sig.asyncness;
     let unsafety = foreign_fn.sig.unsafety;
     let fn_token = foreign_fn.sig.fn_token;
@@ -721,9 +888,14 @@ fn parse_extern_fn(
             throws,
             paren_token,
             throws_tokens,
+            hresult,
         },
         semi_token,
         trusted,
+        array_len,
+        catch_foreign_exceptions,
+        mock: None,
+        ret_vocabulary: None,
     }))
 }
 
@@ -975,6 +1147,11 @@ fn parse_extern_type_bounded(
     let mut namespace = namespace.clone();
     let mut cxx_name = None;
     let mut rust_name = None;
+    let mut incomplete = false;
+    let mut send = false;
+    let mut sync = false;
+    let mut smart_ptr = false;
+    let mut com_iid = None;
     let mut attrs = attrs.clone();
     attrs.extend(attrs::parse(
         cx,
@@ -986,6 +1163,11 @@ fn parse_extern_type_bounded(
             namespace: Some(&mut namespace),
             cxx_name: Some(&mut cxx_name),
             rust_name: Some(&mut rust_name),
+            incomplete: Some(&mut incomplete),
+            send: Some(&mut send),
+            sync: Some(&mut sync),
+            smart_ptr: Some(&mut smart_ptr),
+            com_interface: Some(&mut com_iid),
             ..Default::default()
         },
     ));
@@ -1010,6 +1192,12 @@ fn parse_extern_type_bounded(
         bounds,
         semi_token,
         trusted,
+        incomplete,
+        send,
+        sync,
+        smart_ptr,
+        com_iid,
+        vocabulary: None,
     }))
 }
 
@@ -1091,9 +1279,18 @@ fn parse_impl(cx: &mut Errors, imp: ItemImpl) -> Result<Api> {
         Type::RustBox(ty)
         | Type::RustVec(ty)
         | Type::UniquePtr(ty)
+        | Type::UniqueArray(ty)
         | Type::SharedPtr(ty)
+        | Type::SharedArray(ty)
+        | Type::SharedConstPtr(ty)
         | Type::WeakPtr(ty)
-        | Type::CxxVector(ty) => match &ty.inner {
+        | Type::CxxVector(ty)
+        | Type::CxxPmrVector(ty)
+        | Type::ComPtr(ty) => match &ty.inner {
+            Type::Ident(ident) => ident.generics.clone(),
+            _ => Lifetimes::default(),
+        },
+        Type::CustomPtr(ptr) => match &ptr.inner {
             Type::Ident(ident) => ident.generics.clone(),
             _ => Lifetimes::default(),
         },
@@ -1104,6 +1301,7 @@ fn parse_impl(cx: &mut Errors, imp: ItemImpl) -> Result<Api> {
         | Type::Fn(_)
         | Type::Void(_)
         | Type::SliceRef(_)
+        | Type::HashMapRef(_)
         | Type::Array(_) => Lifetimes::default(),
     };
 
@@ -1130,6 +1328,7 @@ fn parse_include(input: ParseStream) -> Result<Include> {
             cfg: CfgExpr::Unconditional,
             path: lit.value(),
             kind: IncludeKind::Quoted,
+            private: false,
             begin_span: span,
             end_span: span,
         });
@@ -1160,6 +1359,7 @@ fn parse_include(input: ParseStream) -> Result<Include> {
             cfg: CfgExpr::Unconditional,
             path,
             kind: IncludeKind::Bracketed,
+            private: false,
             begin_span: langle.span,
             end_span: rangle.span,
         });
@@ -1168,6 +1368,29 @@ fn parse_include(input: ParseStream) -> Result<Include> {
     Err(input.error("expected \"quoted/path/to\" or <bracketed/path/to>"))
 }
 
+// The binding of a vocabulary type must be one of the builtin bindings that
+// C++ can hold by value, or else None for the typecheck to report. A bare `str`
+// stands for `&str`.
+pub(crate) fn parse_vocabulary_binding(binding: &RustType) -> Option<Type> {
+    let ty = match binding {
+        RustType::Path(path) if path.qself.is_none() && path.path.is_ident("str") => {
+            let span = path.path.segments[0].ident.span();
+            parse_type(&syn::parse_quote_spanned!(span=> &str)).ok()?
+        }
+        _ => parse_type(binding).ok()?,
+    };
+    match &ty {
+        Type::Ident(ident) if ident.rust == RustString => Some(ty),
+        Type::Str(_)
+        | Type::SliceRef(_)
+        | Type::RustVec(_)
+        | Type::RustBox(_)
+        | Type::UniquePtr(_)
+        | Type::SharedPtr(_) => Some(ty),
+        _ => None,
+    }
+}
+
 fn parse_type(ty: &RustType) -> Result<Type> {
     match ty {
         RustType::Reference(ty) => parse_type_reference(ty),
@@ -1176,6 +1399,18 @@ fn parse_type(ty: &RustType) -> Result<Type> {
         RustType::Array(ty) => parse_type_array(ty),
         RustType::BareFn(ty) => parse_type_fn(ty),
         RustType::Tuple(ty) if ty.elems.is_empty() => Ok(Type::Void(ty.paren_token.span.join())),
+        RustType::Tuple(_) => Err(Error::new_spanned(
+            ty,
+            "unsupported type: tuple; use a shared struct with a field for each element",
+        )),
+        RustType::Slice(_) => Err(Error::new_spanned(
+            ty,
+            "unsupported type: slice by value; use &[T] or &mut [T]",
+        )),
+        RustType::TraitObject(_) | RustType::ImplTrait(_) => Err(Error::new_spanned(
+            ty,
+            "unsupported type: trait object; wrap it in an opaque Rust type declared in extern \"Rust\"",
+        )),
         _ => Err(Error::new_spanned(ty, "unsupported type")),
     }
 }
@@ -1199,6 +1434,18 @@ fn parse_type_reference(ty: &TypeReference) -> Result<Type> {
         })));
     }
 
+    if let RustType::Path(path) = ty.elem.as_ref() {
+        if let Some(map) = parse_hash_map_ref(ampersand, &lifetime, path)? {
+            if ty.mutability.is_some() {
+                return Err(Error::new_spanned(
+                    ty,
+                    "unsupported type: &mut HashMap, only a shared reference to HashMap is supported",
+                ));
+            }
+            return Ok(Type::HashMapRef(Box::new(map)));
+        }
+    }
+
     let inner = parse_type(&ty.elem)?;
     let pinned = false;
     let pin_tokens = None;
@@ -1206,7 +1453,10 @@ fn parse_type_reference(ty: &TypeReference) -> Result<Type> {
     Ok(match &inner {
         Type::Ident(ident) if ident.rust == "str" => {
             if ty.mutability.is_some() {
-                return Err(Error::new_spanned(ty, "unsupported type"));
+                return Err(Error::new_spanned(
+                    ty,
+                    "unsupported type: &mut str; use &mut [u8], or Pin<&mut CxxString> for a C++ string",
+                ));
             } else {
                 Type::Str
             }
@@ -1223,6 +1473,43 @@ fn parse_type_reference(ty: &TypeReference) -> Result<Type> {
     })))
 }
 
+fn parse_hash_map_ref(
+    ampersand: Token![&],
+    lifetime: &Option<Lifetime>,
+    ty: &TypePath,
+) -> Result<Option<HashMapRef>> {
+    let path = &ty.path;
+    if ty.qself.is_some() || path.leading_colon.is_some() || path.segments.len() != 1 {
+        return Ok(None);
+    }
+    let segment = &path.segments[0];
+    if segment.ident != "HashMap" {
+        return Ok(None);
+    }
+    let PathArguments::AngleBracketed(generic) = &segment.arguments else {
+        return Ok(None);
+    };
+    let mut args = generic.args.pairs();
+    let (Some(first), Some(second), None) = (args.next(), args.next(), args.next()) else {
+        return Ok(None);
+    };
+    let (GenericArgument::Type(key), Some(comma), GenericArgument::Type(value)) =
+        (first.value(), first.punct(), second.value())
+    else {
+        return Ok(None);
+    };
+    Ok(Some(HashMapRef {
+        ampersand,
+        lifetime: lifetime.clone(),
+        name: segment.ident.clone(),
+        langle: generic.lt_token,
+        key: parse_type(key)?,
+        comma: **comma,
+        value: parse_type(value)?,
+        rangle: generic.gt_token,
+    }))
+}
+
 fn parse_type_ptr(ty: &TypePtr) -> Result<Type> {
     let star = ty.star_token;
     let mutable = ty.mutability.is_some();
@@ -1258,6 +1545,26 @@ fn parse_type_path(ty: &TypePath) -> Result<Type> {
                             rangle: generic.gt_token,
                         })));
                     }
+                } else if ident == "ComPtr" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::ComPtr(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "UniqueArray" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::UniqueArray(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
                 } else if ident == "SharedPtr" && generic.args.len() == 1 {
                     if let GenericArgument::Type(arg) = &generic.args[0] {
                         let inner = parse_type(arg)?;
@@ -1268,6 +1575,26 @@ fn parse_type_path(ty: &TypePath) -> Result<Type> {
                             rangle: generic.gt_token,
                         })));
                     }
+                } else if ident == "SharedArray" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::SharedArray(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "SharedConstPtr" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::SharedConstPtr(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
                 } else if ident == "WeakPtr" && generic.args.len() == 1 {
                     if let GenericArgument::Type(arg) = &generic.args[0] {
                         let inner = parse_type(arg)?;
@@ -1288,6 +1615,38 @@ fn parse_type_path(ty: &TypePath) -> Result<Type> {
                             rangle: generic.gt_token,
                         })));
                     }
+                } else if ident == "CxxPmrVector" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::CxxPmrVector(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "CustomPtr" && generic.args.len() == 2 {
+                    let mut args = generic.args.pairs();
+                    let first = args.next().unwrap();
+                    let second = args.next().unwrap();
+                    if let (
+                        GenericArgument::Type(RustType::Path(family)),
+                        Some(comma),
+                        GenericArgument::Type(arg),
+                    ) = (first.value(), first.punct(), second.value())
+                    {
+                        if let (None, Some(family)) = (&family.qself, family.path.get_ident()) {
+                            let inner = parse_type(arg)?;
+                            return Ok(Type::CustomPtr(Box::new(CustomPtr {
+                                name: ident,
+                                langle: generic.lt_token,
+                                family: family.clone(),
+                                comma: **comma,
+                                inner,
+                                rangle: generic.gt_token,
+                            })));
+                        }
+                    }
                 } else if ident == "Box" && generic.args.len() == 1 {
                     if let GenericArgument::Type(arg) = &generic.args[0] {
                         let inner = parse_type(arg)?;
@@ -1357,7 +1716,53 @@ fn parse_type_path(ty: &TypePath) -> Result<Type> {
         ));
     }
 
-    Err(Error::new_spanned(ty, "unsupported type"))
+    Err(unsupported_type_path(ty))
+}
+
+fn unsupported_type_path(ty: &TypePath) -> Error {
+    let path = &ty.path;
+    let Some(last) = path.segments.last() else {
+        return Error::new_spanned(ty, "unsupported type");
+    };
+    if ty.qself.is_some() {
+        return Error::new_spanned(ty, "unsupported type");
+    }
+
+    let name = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+    let candidates = match last.arguments {
+        PathArguments::AngleBracketed(_) => suggest::BUILTIN_GENERICS,
+        PathArguments::None | PathArguments::Parenthesized(_) => suggest::BUILTIN_TYPES,
+    };
+    let candidates = candidates.iter().copied();
+    let mut msg = format!("unsupported type: {}", name);
+    if path.segments.len() > 1 {
+        let unqualified = last.ident.to_string();
+        match suggest::similar(&name, None).or_else(|| suggest::similar(&unqualified, candidates)) {
+            Some(similar) => msg += &format!("; did you mean `{}`?", similar),
+            None => {
+                msg += &format!(
+                    "; declare `{}` in the bridge and refer to it without a path",
+                    unqualified,
+                );
+            }
+        }
+        Error::new_spanned(ty, msg)
+    } else if suggest::BUILTIN_GENERICS.contains(&name.as_str()) {
+        // A single path segment reaches here only with generic arguments
+        // that do not fit the type of that name.
+        msg += " with these generic arguments";
+        Error::new_spanned(&last.arguments, msg)
+    } else {
+        if let Some(similar) = suggest::similar(&name, candidates) {
+            msg += &format!("; did you mean `{}`?", similar);
+        }
+        Error::new_spanned(&last.ident, msg)
+    }
 }
 
 fn parse_type_array(ty: &TypeArray) -> Result<Type> {
@@ -1434,13 +1839,15 @@ fn parse_type_fn(ty: &TypeBareFn) -> Result<Type> {
                 name,
                 colon_token,
                 ty,
+                vocabulary: None,
             })
         })
         .collect::<Result<_>>()?;
 
     let mut throws_tokens = None;
-    let ret = parse_return_type(&ty.output, &mut throws_tokens)?;
-    let throws = throws_tokens.is_some();
+    let mut hresult = None;
+    let ret = parse_return_type(&ty.output, &mut throws_tokens, &mut hresult)?;
+    let throws = throws_tokens.is_some() && hresult.is_none();
 
     let asyncness = None;
     let unsafety = ty.unsafety;
@@ -1460,12 +1867,14 @@ fn parse_type_fn(ty: &TypeBareFn) -> Result<Type> {
         throws,
         paren_token,
         throws_tokens,
+        hresult,
     })))
 }
 
 fn parse_return_type(
     ty: &ReturnType,
     throws_tokens: &mut Option<(kw::Result, Token![<], Token![>])>,
+    hresult: &mut Option<(Token![,], Ident)>,
 ) -> Result<Option<Type>> {
     let mut ret = match ty {
         ReturnType::Default => return Ok(None),
@@ -1484,6 +1893,26 @@ fn parse_return_type(
                         *throws_tokens =
                             Some((kw::Result(ident.span()), generic.lt_token, generic.gt_token));
                     }
+                } else if ident == "Result" && generic.args.len() == 2 {
+                    let mut pairs = generic.args.pairs();
+                    let ok = pairs.next().unwrap();
+                    if let (
+                        GenericArgument::Type(arg),
+                        Some(comma),
+                        GenericArgument::Type(RustType::Path(err)),
+                    ) = (ok.value(), ok.punct(), &generic.args[1])
+                    {
+                        if err.qself.is_none() && err.path.is_ident("HRESULT") {
+                            ret = arg;
+                            *throws_tokens = Some((
+                                kw::Result(ident.span()),
+                                generic.lt_token,
+                                generic.gt_token,
+                            ));
+                            let error = err.path.segments[0].ident.clone();
+                            *hresult = Some((**comma, error));
+                        }
+                    }
                 }
             }
         }
@@ -23,11 +23,22 @@ impl<'a> Types<'a> {
             Type::RustBox(_)
             | Type::RustVec(_)
             | Type::UniquePtr(_)
+            | Type::UniqueArray(_)
             | Type::SharedPtr(_)
+            | Type::SharedArray(_)
+            | Type::SharedConstPtr(_)
             | Type::WeakPtr(_)
             | Type::CxxVector(_)
+            | Type::CxxPmrVector(_)
+            | Type::CustomPtr(_)
+            | Type::ComPtr(_)
             | Type::Void(_) => false,
-            Type::Ref(_) | Type::Str(_) | Type::Fn(_) | Type::SliceRef(_) | Type::Ptr(_) => true,
+            Type::Ref(_)
+            | Type::Str(_)
+            | Type::Fn(_)
+            | Type::SliceRef(_)
+            | Type::HashMapRef(_)
+            | Type::Ptr(_) => true,
             Type::Array(array) => self.is_guaranteed_pod(&array.inner),
         }
     }
@@ -0,0 +1,105 @@
+// Closest supported spelling for a type name that the bridge does not know,
+// offered as a "did you mean" suggestion in unsupported type errors.
+
+// C++ spellings which people write out of habit, along with the name of the
+// corresponding bridge type.
+static CXX_SPELLINGS: &[(&str, &str)] = &[
+    ("int", "i32"),
+    ("unsigned", "u32"),
+    ("short", "i16"),
+    ("long", "i64"),
+    ("float", "f32"),
+    ("double", "f64"),
+    ("char", "c_char"),
+    ("size_t", "usize"),
+    ("ssize_t", "isize"),
+    ("int8_t", "i8"),
+    ("int16_t", "i16"),
+    ("int32_t", "i32"),
+    ("int64_t", "i64"),
+    ("uint8_t", "u8"),
+    ("uint16_t", "u16"),
+    ("uint32_t", "u32"),
+    ("uint64_t", "u64"),
+    ("std::string", "CxxString"),
+    ("vector", "CxxVector"),
+    ("unique_ptr", "UniquePtr"),
+    ("shared_ptr", "SharedPtr"),
+    ("weak_ptr", "WeakPtr"),
+];
+
+pub(crate) static BUILTIN_TYPES: &[&str] = &[
+    "bool",
+    "c_char",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "usize",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "isize",
+    "f32",
+    "f64",
+    "CxxString",
+    "String",
+];
+
+pub(crate) static BUILTIN_GENERICS: &[&str] = &[
+    "Box",
+    "Vec",
+    "UniquePtr",
+    "UniqueArray",
+    "SharedPtr",
+    "SharedArray",
+    "SharedConstPtr",
+    "WeakPtr",
+    "CxxVector",
+    "CxxPmrVector",
+    "CustomPtr",
+    "ComPtr",
+    "Pin",
+    "HashMap",
+];
+
+// Returns the C++ spelling's bridge equivalent if there is one, otherwise the
+// candidate closest to `name` by edit distance, provided it is close enough to
+// plausibly be a typo.
+pub(crate) fn similar<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let unqualified = name.strip_prefix("std::").unwrap_or(name);
+    for cxx in [name, unqualified] {
+        if let Some(&(_, rust)) = CXX_SPELLINGS.iter().find(|spelling| spelling.0 == cxx) {
+            return Some(rust);
+        }
+    }
+
+    let max_distance = (name.chars().count() / 3).max(1);
+    let mut best = None;
+    for candidate in candidates {
+        let distance = edit_distance(&name.to_lowercase(), &candidate.to_lowercase());
+        if distance <= max_distance && best.map_or(true, |(min, _)| distance < min) {
+            best = Some((distance, candidate));
+        }
+    }
+    best.map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ch) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for j in 0..b.len() {
+            let substitution = diagonal + usize::from(ch != b[j]);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
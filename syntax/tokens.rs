@@ -1,7 +1,7 @@
 use crate::syntax::atom::Atom::*;
 use crate::syntax::{
-    Array, Atom, Derive, Enum, EnumRepr, ExternFn, ExternType, Impl, Lifetimes, NamedType, Ptr,
-    Ref, Signature, SliceRef, Struct, Ty1, Type, TypeAlias, Var,
+    Array, Atom, CustomPtr, Derive, Enum, EnumRepr, ExternFn, ExternType, HashMapRef, Impl,
+    Lifetimes, NamedType, Ptr, Ref, Signature, SliceRef, Struct, Ty1, Type, TypeAlias, Var,
 };
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote_spanned, ToTokens};
@@ -25,9 +25,14 @@ impl ToTokens for Type {
             }
             Type::RustBox(ty)
             | Type::UniquePtr(ty)
+            | Type::UniqueArray(ty)
             | Type::SharedPtr(ty)
+            | Type::SharedArray(ty)
+            | Type::SharedConstPtr(ty)
             | Type::WeakPtr(ty)
             | Type::CxxVector(ty)
+            | Type::CxxPmrVector(ty)
+            | Type::ComPtr(ty)
             | Type::RustVec(ty) => ty.to_tokens(tokens),
             Type::Ref(r) | Type::Str(r) => r.to_tokens(tokens),
             Type::Ptr(p) => p.to_tokens(tokens),
@@ -35,6 +40,8 @@ impl ToTokens for Type {
             Type::Fn(f) => f.to_tokens(tokens),
             Type::Void(span) => tokens.extend(quote_spanned!(*span=> ())),
             Type::SliceRef(r) => r.to_tokens(tokens),
+            Type::HashMapRef(m) => m.to_tokens(tokens),
+            Type::CustomPtr(p) => p.to_tokens(tokens),
         }
     }
 }
@@ -49,6 +56,7 @@ impl ToTokens for Var {
             name,
             colon_token: _,
             ty,
+            vocabulary: _,
         } = self;
         name.rust.to_tokens(tokens);
         Token![:](name.rust.span()).to_tokens(tokens);
@@ -66,7 +74,8 @@ impl ToTokens for Ty1 {
         } = self;
         let span = name.span();
         match name.to_string().as_str() {
-            "UniquePtr" | "SharedPtr" | "WeakPtr" | "CxxVector" => {
+            "UniquePtr" | "UniqueArray" | "SharedPtr" | "SharedArray" | "SharedConstPtr"
+            | "WeakPtr" | "CxxVector" | "CxxPmrVector" | "ComPtr" => {
                 tokens.extend(quote_spanned!(span=> ::cxx::));
             }
             "Box" => {
@@ -144,6 +153,52 @@ impl ToTokens for SliceRef {
     }
 }
 
+impl ToTokens for CustomPtr {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let CustomPtr {
+            name,
+            langle,
+            family,
+            comma,
+            inner,
+            rangle,
+        } = self;
+        let span = name.span();
+        tokens.extend(quote_spanned!(span=> ::cxx::));
+        name.to_tokens(tokens);
+        langle.to_tokens(tokens);
+        family.to_tokens(tokens);
+        comma.to_tokens(tokens);
+        inner.to_tokens(tokens);
+        rangle.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for HashMapRef {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let HashMapRef {
+            ampersand,
+            lifetime,
+            name,
+            langle,
+            key,
+            comma,
+            value,
+            rangle,
+        } = self;
+        ampersand.to_tokens(tokens);
+        lifetime.to_tokens(tokens);
+        let span = name.span();
+        tokens.extend(quote_spanned!(span=> ::cxx::private::));
+        name.to_tokens(tokens);
+        langle.to_tokens(tokens);
+        key.to_tokens(tokens);
+        comma.to_tokens(tokens);
+        value.to_tokens(tokens);
+        rangle.to_tokens(tokens);
+    }
+}
+
 impl ToTokens for Array {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Array {
@@ -264,6 +319,7 @@ impl ToTokens for Signature {
             throws: _,
             paren_token,
             throws_tokens,
+            hresult,
         } = self;
         fn_token.to_tokens(tokens);
         paren_token.surround(tokens, |tokens| {
@@ -275,6 +331,10 @@ impl ToTokens for Signature {
                 result.to_tokens(tokens);
                 langle.to_tokens(tokens);
                 ret.to_tokens(tokens);
+                if let Some((comma, error)) = hresult {
+                    comma.to_tokens(tokens);
+                    error.to_tokens(tokens);
+                }
                 rangle.to_tokens(tokens);
             } else {
                 ret.to_tokens(tokens);
@@ -284,6 +344,10 @@ impl ToTokens for Signature {
             result.to_tokens(tokens);
             langle.to_tokens(tokens);
             token::Paren(langle.span).surround(tokens, |_| ());
+            if let Some((comma, error)) = hresult {
+                comma.to_tokens(tokens);
+                error.to_tokens(tokens);
+            }
             rangle.to_tokens(tokens);
         }
     }
@@ -1,5 +1,5 @@
 use crate::syntax::improper::ImproperCtype;
-use crate::syntax::instantiate::ImplKey;
+use crate::syntax::instantiate::{ImplKey, NamedImplKey};
 use crate::syntax::map::{OrderedMap, UnorderedMap};
 use crate::syntax::report::Errors;
 use crate::syntax::resolve::Resolution;
@@ -7,7 +7,8 @@ use crate::syntax::set::{OrderedSet, UnorderedSet};
 use crate::syntax::trivial::{self, TrivialReason};
 use crate::syntax::visit::{self, Visit};
 use crate::syntax::{
-    toposort, Api, Atom, Enum, ExternType, Impl, Lifetimes, Pair, Struct, Type, TypeAlias,
+    derive, toposort, Api, Atom, Enum, ExternType, Impl, Lifetimes, Pair, Struct, Trait, Type,
+    TypeAlias,
 };
 use proc_macro2::Ident;
 use quote::ToTokens;
@@ -18,6 +19,22 @@ pub(crate) struct Types<'a> {
     pub enums: UnorderedMap<&'a Ident, &'a Enum>,
     pub cxx: UnorderedSet<&'a Ident>,
     pub rust: UnorderedSet<&'a Ident>,
+    // Opaque Rust types with #[derive(Default)], constructible from C++ by
+    // rust::Box<T>::make().
+    pub rust_default: UnorderedSet<&'a Ident>,
+    // Opaque C++ types with #[incomplete], whose definition is visible to the
+    // generated implementation file but not to includers of the header.
+    #[allow(dead_code)] // only used by cxx-build, not cxxbridge-macro
+    pub incomplete: UnorderedSet<&'a Ident>,
+    // Families of third-party C++ smart pointers declared with #[smart_ptr],
+    // usable only as the first argument of CustomPtr<P, T>.
+    pub smart_ptrs: UnorderedSet<&'a Ident>,
+    // Third-party C++ types declared with #[vocabulary], which only exist in
+    // the signatures of extern C++ functions before the parser replaces them.
+    pub vocabularies: UnorderedSet<&'a Ident>,
+    // Opaque C++ types with #[com_interface], reference counted through
+    // IUnknown and usable as the target of ComPtr<T>.
+    pub com_interfaces: UnorderedSet<&'a Ident>,
     pub aliases: UnorderedMap<&'a Ident, &'a TypeAlias>,
     pub untrusted: UnorderedMap<&'a Ident, &'a ExternType>,
     pub required_trivial: UnorderedMap<&'a Ident, Vec<TrivialReason<'a>>>,
@@ -34,6 +51,11 @@ impl<'a> Types<'a> {
         let mut enums = UnorderedMap::new();
         let mut cxx = UnorderedSet::new();
         let mut rust = UnorderedSet::new();
+        let mut rust_default = UnorderedSet::new();
+        let mut incomplete = UnorderedSet::new();
+        let mut smart_ptrs = UnorderedSet::new();
+        let mut vocabularies = UnorderedSet::new();
+        let mut com_interfaces = UnorderedSet::new();
         let mut aliases = UnorderedMap::new();
         let mut untrusted = UnorderedMap::new();
         let mut impls = OrderedMap::new();
@@ -103,6 +125,22 @@ impl<'a> Types<'a> {
                     enums.insert(ident, enm);
                     add_resolution(&enm.name, &enm.generics);
                 }
+                Api::CxxType(ety) if ety.smart_ptr => {
+                    let ident = &ety.name.rust;
+                    if !type_names.insert(ident) {
+                        duplicate_name(cx, ety, ItemName::Type(ident));
+                    }
+                    smart_ptrs.insert(ident);
+                    add_resolution(&ety.name, &ety.generics);
+                }
+                Api::CxxType(ety) if ety.vocabulary.is_some() => {
+                    let ident = &ety.name.rust;
+                    if !type_names.insert(ident) {
+                        duplicate_name(cx, ety, ItemName::Type(ident));
+                    }
+                    vocabularies.insert(ident);
+                    add_resolution(&ety.name, &ety.generics);
+                }
                 Api::CxxType(ety) => {
                     let ident = &ety.name.rust;
                     if !type_names.insert(ident)
@@ -118,6 +156,12 @@ impl<'a> Types<'a> {
                     if !ety.trusted {
                         untrusted.insert(ident, ety);
                     }
+                    if ety.incomplete {
+                        incomplete.insert(ident);
+                    }
+                    if ety.com_iid.is_some() {
+                        com_interfaces.insert(ident);
+                    }
                     add_resolution(&ety.name, &ety.generics);
                 }
                 Api::RustType(ety) => {
@@ -127,6 +171,13 @@ impl<'a> Types<'a> {
                     }
                     rust.insert(ident);
                     add_resolution(&ety.name, &ety.generics);
+                    if let Some(derive) = derive::find(&ety.derives, Trait::Default) {
+                        rust_default.insert(ident);
+                        let impl_key = ImplKey::RustBox(NamedImplKey::derived(ident, derive));
+                        if !impls.contains_key(&impl_key) {
+                            impls.insert(impl_key, None);
+                        }
+                    }
                 }
                 Api::CxxFunction(efn) | Api::RustFunction(efn) => {
                     // Note: duplication of the C++ name is fine because C++ has
@@ -174,9 +225,14 @@ impl<'a> Types<'a> {
                 ImplKey::RustBox(ident)
                 | ImplKey::RustVec(ident)
                 | ImplKey::UniquePtr(ident)
+                | ImplKey::UniqueArray(ident)
                 | ImplKey::SharedPtr(ident)
+                | ImplKey::SharedArray(ident)
+                | ImplKey::SharedConstPtr(ident)
                 | ImplKey::WeakPtr(ident)
-                | ImplKey::CxxVector(ident) => {
+                | ImplKey::CxxVector(ident)
+                | ImplKey::CxxPmrVector(ident)
+                | ImplKey::CustomPtr(_, ident) => {
                     Atom::from(ident.rust).is_none() && !aliases.contains_key(ident.rust)
                 }
             };
@@ -198,6 +254,11 @@ impl<'a> Types<'a> {
             enums,
             cxx,
             rust,
+            rust_default,
+            incomplete,
+            smart_ptrs,
+            vocabularies,
+            com_interfaces,
             aliases,
             untrusted,
             required_trivial,
@@ -240,12 +301,14 @@ impl<'a> Types<'a> {
         match ty {
             Type::RustBox(_)
             | Type::UniquePtr(_)
+            | Type::UniqueArray(_)
+            | Type::ComPtr(_)
             | Type::Ref(_)
             | Type::Ptr(_)
             | Type::Str(_)
             | Type::Fn(_)
             | Type::SliceRef(_) => false,
-            Type::Array(_) => true,
+            Type::Array(_) | Type::HashMapRef(_) => true,
             _ => !self.is_guaranteed_pod(ty) || self.is_considered_improper_ctype(ty),
         }
     }
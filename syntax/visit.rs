@@ -14,14 +14,24 @@ where
         Type::Ident(_) | Type::Str(_) | Type::Void(_) => {}
         Type::RustBox(ty)
         | Type::UniquePtr(ty)
+        | Type::UniqueArray(ty)
         | Type::SharedPtr(ty)
+        | Type::SharedArray(ty)
+        | Type::SharedConstPtr(ty)
         | Type::WeakPtr(ty)
         | Type::CxxVector(ty)
+        | Type::CxxPmrVector(ty)
+        | Type::ComPtr(ty)
         | Type::RustVec(ty) => visitor.visit_type(&ty.inner),
+        Type::CustomPtr(ptr) => visitor.visit_type(&ptr.inner),
         Type::Ref(r) => visitor.visit_type(&r.inner),
         Type::Ptr(p) => visitor.visit_type(&p.inner),
         Type::Array(a) => visitor.visit_type(&a.inner),
         Type::SliceRef(s) => visitor.visit_type(&s.inner),
+        Type::HashMapRef(m) => {
+            visitor.visit_type(&m.key);
+            visitor.visit_type(&m.value);
+        }
         Type::Fn(fun) => {
             if let Some(ret) = &fun.ret {
                 visitor.visit_type(ret);
@@ -83,6 +83,35 @@ impl Test {
     /// (Compilation errors will *not* result in a panic.)
     #[must_use]
     pub fn compile(&self) -> CompilationResult {
+        self.compile_file(&self.generated_cc, false)
+    }
+
+    /// Compiles the `.cc` file generated in `Self::new` the way a project
+    /// built without C++ exceptions would, with `-fno-exceptions`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is a problem with spawning the C++ compiler.
+    /// (Compilation errors will *not* result in a panic.)
+    #[must_use]
+    pub fn compile_without_exceptions(&self) -> CompilationResult {
+        self.compile_file(&self.generated_cc, true)
+    }
+
+    /// Compiles a `.cc` file previously placed by `Self::write_file`, for
+    /// example one that includes the generated `cxx_bridge.generated.h` the
+    /// way a consumer of the bridge would.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is a problem with spawning the C++ compiler.
+    /// (Compilation errors will *not* result in a panic.)
+    #[must_use]
+    pub fn compile_source(&self, filename: impl AsRef<Path>) -> CompilationResult {
+        self.compile_file(&self.temp_dir.path().join(filename), false)
+    }
+
+    fn compile_file(&self, source: &Path, no_exceptions: bool) -> CompilationResult {
         let mut build = cc::Build::new();
         build
             .include(self.temp_dir.path())
@@ -105,13 +134,21 @@ impl Test {
 
         // The `cc` crate does not currently expose the `Command` for building a
         // single C++ source file. Work around that by passing `-c <file.cc>`.
-        let mut command = build.get_compiler().to_command();
+        let compiler = build.get_compiler();
+        let mut command = compiler.to_command();
+        if no_exceptions {
+            command.arg(if compiler.is_like_msvc() {
+                "/EHs-c-"
+            } else {
+                "-fno-exceptions"
+            });
+        }
         command
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .current_dir(self.temp_dir.path())
             .arg("-c")
-            .arg(&self.generated_cc);
+            .arg(source);
         let output = command.spawn().unwrap().wait_with_output().unwrap();
         CompilationResult(output)
     }
@@ -191,4 +228,25 @@ impl CompilationResult {
         eprintln!("Got single error as expected: {single_error_line}");
         single_error_line
     }
+
+    /// Verifies that the compilation failed, and returns the stderr line
+    /// describing the first error.
+    ///
+    /// This is for errors such as failed overload resolution, where compilers
+    /// explain the failure with further error lines of their own.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there was no error.
+    #[must_use]
+    pub fn expect_first_error(&self) -> String {
+        let error_lines = self.error_lines();
+        if error_lines.is_empty() {
+            self.dump_output_and_panic("No error lines found, despite non-zero exit code?");
+        }
+
+        let first_error_line = error_lines.into_iter().next().unwrap();
+        eprintln!("Got first error: {first_error_line}");
+        first_error_line
+    }
 }
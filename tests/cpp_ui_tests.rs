@@ -26,3 +26,198 @@ fn test_unique_ptr_of_incomplete_foward_declared_pointee() {
     let err_msg = test.compile().expect_single_error();
     assert!(err_msg.contains("definition of `::ForwardDeclaredType` is required"));
 }
+
+/// A shared struct holding a `UniquePtr` to an `#[incomplete]` type can be
+/// used by code that only sees the generated header, with the type's
+/// definition coming from a `#[private]` include in the generated `.cc`.
+#[test]
+fn test_unique_ptr_of_incomplete_pimpl_type() {
+    let test = cpp_compile::Test::new(quote! {
+        #[cxx::bridge]
+        mod ffi {
+            struct Widget {
+                id: u32,
+                imp: UniquePtr<WidgetImpl>,
+            }
+            unsafe extern "C++" {
+                include!("widget.h");
+                #[private]
+                include!("widget_impl.h");
+                #[incomplete]
+                type WidgetImpl;
+                fn make_widget(id: u32) -> Widget;
+            }
+        }
+    });
+    test.write_file(
+        "widget.h",
+        indoc! {"
+            #pragma once
+            #include <cstdint>
+            class WidgetImpl;
+            struct Widget;
+            Widget make_widget(std::uint32_t id);
+        "},
+    );
+    test.write_file(
+        "widget_impl.h",
+        indoc! {"
+            #pragma once
+            #define WIDGET_IMPL_INCLUDED
+            #include \"widget.h\"
+            class WidgetImpl {
+              int secret;
+            };
+        "},
+    );
+    test.write_file(
+        "consumer.cc",
+        indoc! {"
+            #include \"cxx_bridge.generated.h\"
+            #include <utility>
+            #ifdef WIDGET_IMPL_INCLUDED
+            #error private header leaked into the generated header
+            #endif
+            std::uint32_t consume() {
+              Widget widget = make_widget(1);
+              Widget moved = std::move(widget);
+              widget = std::move(moved);
+              return widget.id;
+            }
+        "},
+    );
+    test.compile().assert_success();
+    test.compile_source("consumer.cc").assert_success();
+}
+
+/// A `rust::behavior::foreign_exception` defined in an included header takes
+/// the place of the default one that forwards to the process-wide handler.
+#[test]
+fn test_custom_foreign_exception_behavior() {
+    let test = cpp_compile::Test::new(quote! {
+        #[cxx::bridge]
+        mod ffi {
+            #[catch_foreign_exceptions]
+            unsafe extern "C++" {
+                include!("include.h");
+                fn may_throw() -> u32;
+            }
+        }
+    });
+    test.write_file(
+        "include.h",
+        indoc! {"
+            #pragma once
+            #include <cstdint>
+            #include <cstdio>
+            namespace rust {
+            namespace behavior {
+            static void foreign_exception(const char *function) noexcept {
+              std::fprintf(stderr, \"exception in %s\\n\", function);
+            }
+            } // namespace behavior
+            } // namespace rust
+            std::uint32_t may_throw();
+        "},
+    );
+    test.compile().assert_success();
+}
+
+/// The catch block of #[catch_foreign_exceptions] is left out of the build of
+/// a project that has C++ exceptions turned off.
+#[test]
+fn test_catch_foreign_exceptions_without_exceptions() {
+    let test = cpp_compile::Test::new(quote! {
+        #[cxx::bridge]
+        mod ffi {
+            #[catch_foreign_exceptions]
+            unsafe extern "C++" {
+                include!("include.h");
+                fn may_throw() -> u32;
+            }
+        }
+    });
+    test.write_file(
+        "include.h",
+        indoc! {"
+            #pragma once
+            #include <cstdint>
+            std::uint32_t may_throw();
+        "},
+    );
+    test.compile_without_exceptions().assert_success();
+}
+
+/// The `#[send]` and `#[sync]` claims of a bridge need to be confirmed by the
+/// C++ type, otherwise the bridge's unsafe impls are a C++ compile error.
+#[test]
+fn test_send_confirmed_by_cpp_type() {
+    let test = cpp_compile::Test::new(quote! {
+        #[cxx::bridge]
+        mod ffi {
+            unsafe extern "C++" {
+                include!("include.h");
+                #[send]
+                #[sync]
+                type Session;
+            }
+        }
+    });
+    test.write_file(
+        "include.h",
+        indoc! {"
+            #pragma once
+            #include <type_traits>
+            struct Session {
+              using IsSend = std::true_type;
+            };
+        "},
+    );
+    let err_msg = test.compile().expect_single_error();
+    assert!(err_msg
+        .contains("type Session is declared in the bridge as safe to be shared between threads"));
+
+    test.write_file(
+        "include.h",
+        indoc! {"
+            #pragma once
+            #include <type_traits>
+            struct Session {
+              using IsSend = std::true_type;
+              using IsSync = std::true_type;
+            };
+        "},
+    );
+    test.compile().assert_success();
+}
+
+/// `rust::Box<T>::make()` only exists for opaque Rust types declared with
+/// `#[derive(Default)]`, so calling it for any other type fails to compile
+/// rather than to link.
+#[test]
+fn test_box_make_requires_derive_default() {
+    let test = cpp_compile::Test::new(quote! {
+        #[cxx::bridge]
+        mod ffi {
+            extern "Rust" {
+                #[derive(Default)]
+                type Defaulted;
+                type Plain;
+            }
+            impl Box<Plain> {}
+        }
+    });
+    test.write_file(
+        "consumer.cc",
+        indoc! {"
+            #include \"cxx_bridge.generated.h\"
+            void consume() {
+              rust::Box<Defaulted> defaulted = rust::Box<Defaulted>::make();
+              rust::Box<Plain> plain = rust::Box<Plain>::make();
+            }
+        "},
+    );
+    test.compile().assert_success();
+    let err_msg = test.compile_source("consumer.cc").expect_first_error();
+    assert!(err_msg.contains("Box<Plain>::make"));
+}
@@ -0,0 +1,59 @@
+// The pmr runtime of cxx.cc is only compiled as C++17 or newer.
+#![cfg(feature = "c++17")]
+
+use cxx::pmr::{self, CxxPmrString, CxxPmrVector};
+
+#[test]
+fn test_cxx_pmr_vector_new() {
+    let vector = CxxPmrVector::<i32>::new();
+    assert!(vector.is_empty());
+    assert_eq!(vector.memory_resource(), pmr::get_default_resource());
+}
+
+#[test]
+fn test_cxx_pmr_vector_mutation() {
+    let mut vector = unsafe { CxxPmrVector::<u64>::new_in(pmr::new_delete_resource()) };
+    vector.pin_mut().extend([1, 2, 3]);
+    assert_eq!(vector.as_slice(), [1, 2, 3]);
+    assert_eq!(vector.pin_mut().pop(), Some(3));
+    vector.pin_mut().truncate(1);
+    assert_eq!(vector.as_slice(), [1]);
+    vector.pin_mut().clear();
+    assert!(vector.is_empty());
+    assert_eq!(vector.memory_resource(), pmr::new_delete_resource());
+}
+
+#[test]
+fn test_cxx_pmr_vector_null_resource() {
+    let vector = unsafe { CxxPmrVector::<u8>::new_in(pmr::null_memory_resource()) };
+    assert_eq!(vector.capacity(), 0);
+    assert_eq!(vector.memory_resource(), pmr::null_memory_resource());
+}
+
+#[test]
+fn test_cxx_pmr_string() {
+    let mut string = CxxPmrString::new();
+    string.pin_mut().push_str("hello");
+    string.pin_mut().push_bytes(b" world");
+    assert_eq!(*string, *"hello world");
+    assert_eq!(string.to_str(), Ok("hello world"));
+    string.pin_mut().truncate(5);
+    assert_eq!(format!("{:?}", string), "\"hello\"");
+    string.pin_mut().clear();
+    assert!(string.is_empty());
+}
+
+#[test]
+fn test_cxx_pmr_vector_of_strings() {
+    let resource = pmr::new_delete_resource();
+    let mut vector = unsafe { CxxPmrVector::<CxxPmrString>::new_in(resource) };
+    vector.pin_mut().push_str("first");
+    vector.pin_mut().push_bytes(b"second");
+    assert_eq!(vector.len(), 2);
+    for element in &*vector {
+        // Uses-allocator construction of elements propagates the resource.
+        assert_eq!(element.memory_resource(), resource);
+    }
+    assert_eq!(*vector.get(1).unwrap(), *"second");
+    assert_eq!(format!("{:?}", vector), r#"["first", "second"]"#);
+}
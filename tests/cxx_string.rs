@@ -4,7 +4,7 @@
     clippy::unused_async
 )]
 
-use cxx::{let_cxx_string, CxxString};
+use cxx::{let_cxx_string, CxxString, UniquePtr};
 use std::fmt::Write as _;
 
 #[test]
@@ -52,3 +52,58 @@ fn test_io_write() {
     std::io::copy(&mut reader, &mut s).unwrap();
     assert_eq!(s.to_str(), Ok("Hello, world!"));
 }
+
+#[test]
+fn test_mutation() {
+    let_cxx_string!(s = "");
+
+    s.as_mut().push_str("Hello");
+    s.as_mut().push_bytes(b", world!");
+    assert_eq!(s.to_str(), Ok("Hello, world!"));
+
+    s.as_mut().truncate(5);
+    assert_eq!(s.to_str(), Ok("Hello"));
+    s.as_mut().truncate(100);
+    assert_eq!(s.to_str(), Ok("Hello"));
+
+    s.as_mut().reserve(100);
+    s.as_mut().clear();
+    assert!(s.is_empty());
+}
+
+#[test]
+fn test_unique_ptr_from() {
+    let mut s = UniquePtr::<CxxString>::from("Hello");
+    s.pin_mut().push_str(", world!");
+    assert_eq!(s.to_str(), Ok("Hello, world!"));
+
+    let bytes = UniquePtr::<CxxString>::from(&b"\xF1\x80"[..]);
+    assert_eq!(bytes.as_bytes(), b"\xF1\x80");
+}
+
+#[test]
+fn test_comparisons_and_collections() {
+    use std::collections::{BTreeSet, HashSet};
+
+    let_cxx_string!(a = "apple");
+    let_cxx_string!(b = "banana");
+    let a: &CxxString = &a;
+    let b: &CxxString = &b;
+
+    assert_eq!(*a, *"apple");
+    assert_eq!(*"apple", *a);
+    assert_eq!(*a, b"apple"[..]);
+    assert_eq!(b"apple"[..], *a);
+    assert!(*a < *"b");
+    assert!(*"b" < *b);
+    assert!(a < b);
+    assert_eq!(a.as_ref(), b"apple");
+
+    let hash_set: HashSet<&CxxString> = [a, b].into_iter().collect();
+    assert!(hash_set.contains(&b"banana"[..]));
+    assert!(!hash_set.contains(&b"cherry"[..]));
+
+    let btree_set: BTreeSet<&CxxString> = [b, a].into_iter().collect();
+    assert!(btree_set.contains(&b"apple"[..]));
+    assert_eq!(btree_set.into_iter().next(), Some(a));
+}
@@ -5,3 +5,116 @@ fn test_cxx_vector_new() {
     let vector = CxxVector::<i32>::new();
     assert!(vector.is_empty());
 }
+
+#[test]
+fn test_cxx_vector_mutation() {
+    let mut vector = CxxVector::<i32>::new();
+    vector.pin_mut().push(1);
+    vector.pin_mut().push(2);
+    vector.pin_mut().push(3);
+    assert_eq!(vector.as_slice(), [1, 2, 3]);
+
+    vector.pin_mut().truncate(5);
+    assert_eq!(vector.as_slice(), [1, 2, 3]);
+    vector.pin_mut().truncate(1);
+    assert_eq!(vector.as_slice(), [1]);
+
+    vector.pin_mut().resize(4, 7);
+    assert_eq!(vector.as_slice(), [1, 7, 7, 7]);
+    let mut next = 0;
+    vector.pin_mut().resize_with(6, || {
+        next += 1;
+        next
+    });
+    assert_eq!(vector.as_slice(), [1, 7, 7, 7, 1, 2]);
+    vector.pin_mut().resize(2, 0);
+    assert_eq!(vector.as_slice(), [1, 7]);
+
+    vector.pin_mut().clear();
+    assert!(vector.is_empty());
+    assert_eq!(vector.pin_mut().pop(), None);
+}
+
+#[test]
+fn test_cxx_vector_of_rust_string() {
+    let mut vector = CxxVector::<String>::new();
+    vector.pin_mut().push("hello".to_owned());
+    vector.pin_mut().push("world".to_owned());
+    assert_eq!(vector.len(), 2);
+    assert_eq!(vector.get(1).unwrap(), "world");
+    assert_eq!(vector.pin_mut().pop().as_deref(), Some("world"));
+    vector.pin_mut().resize(3, "!".to_owned());
+    assert_eq!(vector.as_slice(), ["hello", "!", "!"]);
+    assert_eq!(vector.to_vec(), ["hello", "!", "!"]);
+    assert_eq!(vector.pin_mut().drain_to_vec(), ["hello", "!", "!"]);
+    assert!(vector.is_empty());
+}
+
+#[test]
+fn test_cxx_vector_iterators() {
+    let mut vector = CxxVector::<u8>::new();
+    vector.pin_mut().extend([1, 2, 3, 4, 5]);
+
+    let reversed: Vec<u8> = vector.iter().rev().copied().collect();
+    assert_eq!(reversed, [5, 4, 3, 2, 1]);
+
+    let mut iter = vector.iter();
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&5));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.as_slice(), [2, 3, 4]);
+    assert_eq!(iter.clone().sum::<u8>(), 9);
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    for (i, mut element) in vector.pin_mut().iter_mut().rev().enumerate() {
+        *element += i as u8 * 10;
+    }
+    assert_eq!(vector.as_slice(), [41, 32, 23, 14, 5]);
+    assert_eq!(vector.to_vec(), [41, 32, 23, 14, 5]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_cxx_vector_par_iter() {
+    use rayon::prelude::*;
+
+    let mut vector = CxxVector::<u64>::new();
+    vector.pin_mut().extend(0..10_000);
+    let sum: u64 = vector.par_iter().sum();
+    assert_eq!(sum, 49_995_000);
+    let evens: Vec<u64> = vector.par_iter().copied().filter(|n| n % 2 == 0).collect();
+    assert_eq!(evens.len(), 5_000);
+    assert!(evens.iter().copied().eq((0..10_000).step_by(2)));
+}
+
+#[test]
+fn test_cxx_vector_of_bool() {
+    let mut vector = CxxVector::<bool>::new();
+    vector.pin_mut().extend([true, false, true]);
+    assert_eq!(vector.len(), 3);
+    assert_eq!(vector.get(0), Some(true));
+    assert_eq!(vector.get(1), Some(false));
+    assert_eq!(vector.get(3), None);
+
+    vector.pin_mut().set(1, true);
+    assert_eq!(vector.to_vec(), [true, true, true]);
+    assert_eq!(vector.pin_mut().pop(), Some(true));
+    vector.pin_mut().resize(4, false);
+    assert_eq!(
+        vector.iter().collect::<Vec<_>>(),
+        [true, true, false, false]
+    );
+    assert_eq!(
+        vector.iter().rev().collect::<Vec<_>>(),
+        [false, false, true, true]
+    );
+    assert_eq!(format!("{:?}", vector), "[true, true, false, false]");
+
+    vector.pin_mut().clear();
+    assert!(vector.is_empty());
+    assert_eq!(vector.pin_mut().pop(), None);
+}
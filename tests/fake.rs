@@ -0,0 +1,73 @@
+use cxx::UniquePtr;
+
+pub struct FakeStorage;
+
+#[cxx::bridge(namespace = "tests", fake = crate::FakeStorage)]
+mod ffi {
+    struct Entry {
+        key: String,
+        value: Vec<u8>,
+    }
+
+    #[mock = Storage]
+    unsafe extern "C++" {
+        type Bucket;
+
+        fn open_bucket(name: &str) -> UniquePtr<Bucket>;
+        fn new_entry(key: &str) -> UniquePtr<Entry>;
+        fn key_len(self: &Entry) -> usize;
+        fn append(self: &mut Entry, value: &[u8]) -> Result<()>;
+        unsafe fn checksum(data: *const u8, len: usize) -> u32;
+    }
+
+    unsafe extern "C++" {
+        fn cxx_only() -> usize;
+    }
+}
+
+impl ffi::Storage for FakeStorage {
+    fn open_bucket(&self, name: &str) -> UniquePtr<ffi::Bucket> {
+        assert_eq!("fake", name);
+        UniquePtr::null()
+    }
+
+    fn new_entry(&self, key: &str) -> UniquePtr<ffi::Entry> {
+        UniquePtr::new(ffi::Entry {
+            key: key.to_owned(),
+            value: Vec::new(),
+        })
+    }
+
+    fn key_len(&self, this: &ffi::Entry) -> usize {
+        this.key.len()
+    }
+
+    fn append(&self, this: &mut ffi::Entry, value: &[u8]) -> Result<(), cxx::Exception> {
+        this.value.extend_from_slice(value);
+        Ok(())
+    }
+
+    unsafe fn checksum(&self, data: *const u8, len: usize) -> u32 {
+        let data = unsafe { std::slice::from_raw_parts(data, len) };
+        data.iter().map(|&byte| u32::from(byte)).sum()
+    }
+}
+
+#[test]
+fn test_fake() {
+    assert!(ffi::open_bucket("fake").is_null());
+
+    let mut entry = ffi::new_entry("key");
+    assert_eq!(3, entry.key_len());
+    entry.pin_mut().get_mut().append(b"value").unwrap();
+    assert_eq!(b"value", entry.value.as_slice());
+
+    let data = [1, 2, 3];
+    assert_eq!(6, unsafe { ffi::checksum(data.as_ptr(), data.len()) });
+}
+
+#[test]
+#[should_panic = "`cxx_only` is an extern \"C++\" function"]
+fn test_fake_missing() {
+    ffi::cxx_only();
+}
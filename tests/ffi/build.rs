@@ -4,12 +4,18 @@
 use cxx_build::CFG;
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(cxx_test_suite_cxx17)");
     if cfg!(trybuild) {
         return;
     }
 
     CFG.include_prefix = "tests/ffi";
-    let sources = vec!["lib.rs", "module.rs"];
+    let mut sources = vec!["lib.rs", "module.rs"];
+    // Built as C++17 or newer when the cxx crate's c++17 or c++20 feature is on.
+    if !matches!(cxxbridge_flags::STD, "c++11" | "c++14") {
+        sources.push("cxx17.rs");
+        println!("cargo:rustc-cfg=cxx_test_suite_cxx17");
+    }
     let mut build = cxx_build::bridges(sources);
     build.file("tests.cc");
     build.std(cxxbridge_flags::STD);
@@ -19,6 +25,11 @@ fn main() {
     }
     build.compile("cxx-test-suite");
 
+    cxx_build::tests(["cxx_tests.cc"])
+        .std(cxxbridge_flags::STD)
+        .warnings_into_errors(cfg!(deny_warnings))
+        .compile("cxx-test-suite-cxx-tests");
+
     println!("cargo:rerun-if-changed=tests.cc");
     println!("cargo:rerun-if-changed=tests.h");
 }
@@ -0,0 +1,22 @@
+#![deny(warnings)] // Check that expansion of `cxx::bridge` doesn't trigger warnings.
+
+// Test cases which need the runtime and the test suite to be built as C++17 or
+// newer. Run them with `cargo test --features c++17`.
+
+#[cxx::bridge(namespace = "tests")]
+pub mod ffi {
+    unsafe extern "C++" {
+        include!("tests/ffi/lib.rs.h");
+        include!("tests/ffi/tests.h");
+
+        type Shared = crate::ffi::Shared;
+
+        #[array_len = n]
+        fn c_return_shared_array(n: usize) -> SharedArray<u8>;
+        #[array_len = n]
+        fn c_return_shared_array_shared(n: usize) -> SharedArray<Shared>;
+        fn c_take_shared_array(a: SharedArray<u8>, n: usize);
+    }
+
+    impl SharedArray<Shared> {}
+}
@@ -0,0 +1,30 @@
+#include "tests/ffi/lib.rs.h"
+#include "tests/ffi/tests.h"
+#include "rust/cxx_test.h"
+#include <stdexcept>
+#include <string>
+
+namespace {
+void no_op() {}
+void throws_runtime_error() { throw std::runtime_error("caught by run"); }
+} // namespace
+
+CXX_TEST(cxx_test_calls_rust) {
+  CXX_ASSERT_EQ(tests::r_return_primitive(), std::size_t{2020});
+  CXX_ASSERT_EQ(tests::r_return_shared().z, std::size_t{2020});
+  CXX_ASSERT(std::string(tests::r_return_rust_string()) == "2020");
+}
+
+CXX_TEST(cxx_test_calls_cxx) {
+  auto c = tests::c_return_unique_ptr();
+  CXX_ASSERT(c != nullptr);
+  CXX_ASSERT_EQ(c->get(), std::size_t{2020});
+}
+
+CXX_TEST(cxx_test_reports_failure) {
+  const char *message = nullptr;
+  std::size_t len = 0;
+  CXX_ASSERT(rust::test::run(no_op, &message, &len));
+  CXX_ASSERT(!rust::test::run(throws_runtime_error, &message, &len));
+  CXX_ASSERT(std::string(message, len) == "caught by run");
+}
@@ -16,12 +16,17 @@
 #![deny(warnings)] // Check that expansion of `cxx::bridge` doesn't trigger warnings.
 
 pub mod cast;
+#[cfg(cxx_test_suite_cxx17)]
+pub mod cxx17;
 pub mod module;
 
-use cxx::{type_id, CxxString, CxxVector, ExternType, SharedPtr, UniquePtr};
+use cxx::{type_id, CxxString, CxxVector, Exception, ExternType, SharedPtr, UniquePtr};
+use std::collections::HashMap;
 use std::fmt::{self, Display};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::mem::MaybeUninit;
 use std::os::raw::c_char;
+use std::pin::Pin;
 
 #[cxx::bridge(namespace = "tests")]
 pub mod ffi {
@@ -100,6 +105,9 @@ pub mod ffi {
     unsafe extern "C++" {
         include!("tests/ffi/tests.h");
 
+        #[derive(Hash)]
+        #[send]
+        #[sync]
         type C;
 
         fn c_return_primitive() -> usize;
@@ -107,6 +115,7 @@ pub mod ffi {
         fn c_return_box() -> Box<R>;
         fn c_return_unique_ptr() -> UniquePtr<C>;
         fn c_return_shared_ptr() -> SharedPtr<C>;
+        fn c_return_shared_const_ptr() -> SharedConstPtr<C>;
         fn c_return_ref(shared: &Shared) -> &usize;
         fn c_return_mut(shared: &mut Shared) -> &mut usize;
         fn c_return_str(shared: &Shared) -> &str;
@@ -120,6 +129,12 @@ pub mod ffi {
         fn c_return_unique_ptr_vector_string() -> UniquePtr<CxxVector<CxxString>>;
         fn c_return_unique_ptr_vector_shared() -> UniquePtr<CxxVector<Shared>>;
         fn c_return_unique_ptr_vector_opaque() -> UniquePtr<CxxVector<C>>;
+        fn c_return_unique_ptr_vector_bool() -> UniquePtr<CxxVector<bool>>;
+        fn c_return_unique_array(n: usize) -> UniqueArray<u32>;
+        #[array_len = n]
+        fn c_return_unique_array_len(n: usize) -> UniqueArray<u32>;
+        #[array_len = n]
+        fn c_return_unique_array_shared(n: usize) -> UniqueArray<Shared>;
         fn c_return_ref_vector(c: &C) -> &CxxVector<u8>;
         fn c_return_mut_vector(c: Pin<&mut C>) -> Pin<&mut CxxVector<u8>>;
         fn c_return_rust_vec_u8() -> Vec<u8>;
@@ -127,8 +142,11 @@ pub mod ffi {
         fn c_return_mut_rust_vec(c: Pin<&mut C>) -> &mut Vec<u8>;
         fn c_return_rust_vec_string() -> Vec<String>;
         fn c_return_rust_vec_bool() -> Vec<bool>;
+        fn c_return_rust_vec_from_std_vector(n: usize) -> Vec<u8>;
         fn c_return_identity(_: usize) -> usize;
         fn c_return_sum(_: usize, _: usize) -> usize;
+        #[catch_foreign_exceptions]
+        fn c_return_sum_checked(_: usize, _: usize) -> usize;
         fn c_return_enum(n: u16) -> Enum;
         fn c_return_ns_ref(shared: &AShared) -> &usize;
         fn c_return_nested_ns_ref(shared: &ABShared) -> &usize;
@@ -154,15 +172,22 @@ pub mod ffi {
         fn c_take_unique_ptr_vector_f64(v: UniquePtr<CxxVector<f64>>);
         fn c_take_unique_ptr_vector_string(v: UniquePtr<CxxVector<CxxString>>);
         fn c_take_unique_ptr_vector_shared(v: UniquePtr<CxxVector<Shared>>);
+        fn c_take_unique_array(a: UniqueArray<u32>, n: usize);
+        fn c_take_shared_const_ptr(c: SharedConstPtr<C>);
         fn c_take_ref_vector(v: &CxxVector<u8>);
+        fn c_take_ref_vector_bool(v: &CxxVector<bool>);
+        fn c_take_rust_hash_map(m: &HashMap<String, u32>);
+        fn c_take_rust_hash_map_roundtrip(m: &HashMap<u8, i32>);
         fn c_take_rust_vec(v: Vec<u8>);
         fn c_take_rust_vec_shared(v: Vec<Shared>);
         fn c_take_rust_vec_string(v: Vec<String>);
+        fn c_take_rust_vec_into_std_vector(v: Vec<String>);
         fn c_take_rust_vec_index(v: Vec<u8>);
         fn c_take_rust_vec_shared_index(v: Vec<Shared>);
         fn c_take_rust_vec_shared_push(v: Vec<Shared>);
         fn c_take_rust_vec_shared_truncate(v: Vec<Shared>);
         fn c_take_rust_vec_shared_clear(v: Vec<Shared>);
+        fn c_take_rust_vec_shared_insert_erase(v: Vec<Shared>);
         fn c_take_rust_vec_shared_forward_iterator(v: Vec<Shared>);
         fn c_take_rust_vec_shared_sort(v: Vec<Shared>);
         fn c_take_ref_rust_vec(v: &Vec<u8>);
@@ -188,6 +213,11 @@ pub mod ffi {
         fn c_try_return_void() -> Result<()>;
         fn c_try_return_primitive() -> Result<usize>;
         fn c_fail_return_primitive() -> Result<usize>;
+        fn c_fail_return_system_error() -> Result<usize>;
+        fn c_fail_return_nested() -> Result<usize>;
+        fn c_fail_return_rust_error() -> Result<usize>;
+        #[catch_foreign_exceptions]
+        fn c_fail_return_foreign_exception() -> Result<usize>;
         fn c_try_return_box() -> Result<Box<R>>;
         fn c_try_return_ref(s: &String) -> Result<&String>;
         fn c_try_return_str(s: &str) -> Result<&str>;
@@ -215,6 +245,9 @@ pub mod ffi {
 
         fn c_get_use_count(weak: &WeakPtr<C>) -> usize;
 
+        fn c_sum_istream(input: Pin<&mut istream>) -> i64;
+        fn c_write_ostream(output: Pin<&mut ostream>, lines: usize);
+
         #[rust_name = "i32_overloaded_method"]
         fn cOverloadedMethod(&self, x: i32) -> String;
         #[rust_name = "str_overloaded_method"]
@@ -243,6 +276,7 @@ pub mod ffi {
         type Reference<'a>;
     }
 
+    #[mock = BorrowApi]
     unsafe extern "C++" {
         type Borrow<'a>;
 
@@ -256,6 +290,67 @@ pub mod ffi {
         fn nonconst_member(self: Pin<&mut Borrow>);
     }
 
+    #[mock = CountedApi]
+    unsafe extern "C++" {
+        #[smart_ptr]
+        type IntrusivePtr;
+        type Counted;
+
+        fn c_return_custom_ptr() -> CustomPtr<IntrusivePtr, Counted>;
+        fn c_take_custom_ptr(c: CustomPtr<IntrusivePtr, Counted>);
+
+        fn get(self: &Counted) -> usize;
+        fn refs(self: &Counted) -> usize;
+    }
+
+    unsafe extern "C++" {
+        #[vocabulary = String]
+        type Text;
+        #[vocabulary = str]
+        type Word;
+        #[vocabulary = "Vec<u8>"]
+        type Bytes;
+
+        fn c_vocabulary_greet(name: Text) -> Text;
+        fn c_vocabulary_first_word(text: Word) -> Word;
+        fn c_vocabulary_reverse(bytes: Bytes) -> Bytes;
+    }
+
+    #[namespace = "tests::nested"]
+    mod nested {
+        struct Nested {
+            value: u32,
+        }
+
+        unsafe extern "C++" {
+            fn c_nested(value: u32) -> Nested;
+        }
+
+        extern "Rust" {
+            fn r_nested(value: u32) -> Nested;
+        }
+    }
+
+    unsafe extern "C++" {
+        #[com_interface = "8F2E6C1A-3B4D-4E5F-9A0B-1C2D3E4F5A6B"]
+        type IShape;
+        #[com_interface = "{8F2E6C1A-3B4D-4E5F-9A0B-1C2D3E4F5A6C}"]
+        type IPolygon;
+        #[com_interface = "8F2E6C1A-3B4D-4E5F-9A0B-1C2D3E4F5A6D"]
+        type ICircle;
+
+        fn c_new_square(side: i32) -> Result<ComPtr<IShape>, HRESULT>;
+        fn c_take_com_ptr(shape: ComPtr<IShape>) -> i32;
+        fn c_com_refcount(shape: &IShape) -> u32;
+
+        #[cxx_name = "Area"]
+        fn area(self: &IShape) -> Result<i32, HRESULT>;
+        #[cxx_name = "Scale"]
+        fn scale(self: &IShape, factor: i32) -> Result<(), HRESULT>;
+        #[cxx_name = "Sides"]
+        fn sides(self: &IPolygon) -> Result<u8, HRESULT>;
+    }
+
     #[repr(u32)]
     #[derive(Hash)]
     enum COwnedEnum {
@@ -269,7 +364,17 @@ pub mod ffi {
         type Buffer = crate::Buffer;
     }
 
+    #[namespace = "std"]
+    extern "C++" {
+        include!(<istream>);
+        include!(<ostream>);
+
+        type istream = cxx::CxxIstream;
+        type ostream = cxx::CxxOstream;
+    }
+
     extern "Rust" {
+        #[derive(Default, Hash)]
         type R;
 
         fn r_return_primitive() -> usize;
@@ -290,6 +395,7 @@ pub mod ffi {
         fn r_return_rust_vec_extern_struct() -> Vec<Job>;
         fn r_return_ref_rust_vec(shared: &Shared) -> &Vec<u8>;
         fn r_return_mut_rust_vec(shared: &mut Shared) -> &mut Vec<u8>;
+        fn r_return_rust_hash_map(m: &HashMap<u8, i32>) -> &HashMap<u8, i32>;
         fn r_return_identity(_: usize) -> usize;
         fn r_return_sum(_: usize, _: usize) -> usize;
         fn r_return_enum(n: u32) -> Enum;
@@ -309,6 +415,7 @@ pub mod ffi {
         fn r_take_ref_empty_vector(v: &CxxVector<u64>);
         fn r_take_rust_vec(v: Vec<u8>);
         fn r_take_rust_vec_string(v: Vec<String>);
+        fn r_take_rust_hash_map(m: &HashMap<u8, i32>) -> usize;
         fn r_take_ref_rust_vec(v: &Vec<u8>);
         fn r_take_ref_rust_vec_string(v: &Vec<String>);
         fn r_take_enum(e: Enum);
@@ -317,8 +424,12 @@ pub mod ffi {
         fn r_try_return_primitive() -> Result<usize>;
         fn r_try_return_box() -> Result<Box<R>>;
         fn r_fail_return_primitive() -> Result<usize>;
+        fn r_fail_return_exception() -> Result<usize>;
+        fn r_fail_return_error_chain() -> Result<usize>;
         fn r_try_return_sliceu8(s: &[u8]) -> Result<&[u8]>;
         fn r_try_return_mutsliceu8(s: &mut [u8]) -> Result<&mut [u8]>;
+        fn r_copy_stream(input: Pin<&mut istream>, output: Pin<&mut ostream>) -> Result<usize>;
+        fn r_read_once(input: Pin<&mut istream>) -> Result<usize>;
 
         fn get(self: &R) -> usize;
         fn set(self: &mut R, n: usize) -> usize;
@@ -414,7 +525,7 @@ mod other {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Default, Hash)]
 pub struct R(pub usize);
 
 impl R {
@@ -477,10 +588,29 @@ impl Display for Error {
     }
 }
 
+#[derive(Debug)]
+struct OuterError(Error);
+
+impl std::error::Error for OuterError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl Display for OuterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("outer error")
+    }
+}
+
 fn r_return_primitive() -> usize {
     2020
 }
 
+fn r_nested(value: u32) -> ffi::Nested {
+    ffi::Nested { value: value * 2 }
+}
+
 fn r_return_shared() -> ffi::Shared {
     ffi::Shared { z: 2020 }
 }
@@ -566,6 +696,10 @@ fn r_return_ref_rust_vec(shared: &ffi::Shared) -> &Vec<u8> {
     unimplemented!()
 }
 
+fn r_return_rust_hash_map(m: &HashMap<u8, i32>) -> &HashMap<u8, i32> {
+    m
+}
+
 fn r_return_mut_rust_vec(shared: &mut ffi::Shared) -> &mut Vec<u8> {
     let _ = shared;
     unimplemented!()
@@ -653,6 +787,10 @@ fn r_take_rust_vec_string(v: Vec<String>) {
     let _ = v;
 }
 
+fn r_take_rust_hash_map(m: &HashMap<u8, i32>) -> usize {
+    m.len()
+}
+
 fn r_take_ref_rust_vec(v: &Vec<u8>) {
     let _ = v;
 }
@@ -681,6 +819,16 @@ fn r_fail_return_primitive() -> Result<usize, Error> {
     Err(Error)
 }
 
+fn r_fail_return_exception() -> Result<usize, Exception> {
+    let inner = Exception::new("inner");
+    let middle = Exception::new("middle").with_source(inner);
+    Err(Exception::new("outer").with_code(7).with_source(middle))
+}
+
+fn r_fail_return_error_chain() -> Result<usize, Box<dyn std::error::Error>> {
+    Err(Box::new(OuterError(Error)))
+}
+
 fn r_try_return_sliceu8(slice: &[u8]) -> Result<&[u8], Error> {
     Ok(slice)
 }
@@ -689,6 +837,27 @@ fn r_try_return_mutsliceu8(slice: &mut [u8]) -> Result<&mut [u8], Error> {
     Ok(slice)
 }
 
+fn r_copy_stream(
+    mut input: Pin<&mut ffi::istream>,
+    mut output: Pin<&mut ffi::ostream>,
+) -> io::Result<usize> {
+    input.seek(SeekFrom::Current(1))?;
+    let n = io::copy(&mut input, &mut output)?;
+    output.write_all(b"!")?;
+    output.flush()?;
+    Ok(n as usize)
+}
+
+fn r_read_once(mut input: Pin<&mut ffi::istream>) -> io::Result<usize> {
+    let mut buf = [0; 64];
+    input.read(&mut buf)
+}
+
 fn r_aliased_function(x: i32) -> String {
     x.to_string()
 }
+
+#[cfg(test)]
+mod cxx_tests {
+    cxx::cxx_tests!();
+}
@@ -1,17 +1,23 @@
 #include "tests/ffi/tests.h"
 #include "tests/ffi/lib.rs.h"
+#include "rust/cxx_format.h"
+#include <algorithm>
 #include <array>
 #include <cstdlib>
 #include <cstring>
 #include <iterator>
 #include <memory>
 #include <numeric>
+#include <set>
+#include <sstream>
 #ifdef __cpp_lib_span
 #include <span>
 #endif // __cpp_lib_span
 #include <stdexcept>
 #include <string>
+#include <system_error>
 #include <tuple>
+#include <unordered_set>
 
 extern "C" void cxx_test_suite_set_correct() noexcept;
 extern "C" tests::R *cxx_test_suite_get_box() noexcept;
@@ -42,6 +48,121 @@ size_t C::set_succeed(size_t n) { return this->set(n); }
 
 size_t C::get_fail() { throw std::runtime_error("unimplemented"); }
 
+Counted::Counted(size_t n) : n(n), count(0) {}
+
+size_t Counted::get() const { return this->n; }
+
+size_t Counted::refs() const { return this->count; }
+
+void Counted::retain() const noexcept { this->count++; }
+
+void Counted::release() const noexcept {
+  if (--this->count == 0) {
+    delete this;
+  }
+}
+
+namespace {
+const HRESULT S_OK = 0;
+const HRESULT E_NOINTERFACE = static_cast<HRESULT>(0x80004002);
+const HRESULT E_POINTER = static_cast<HRESULT>(0x80004003);
+const HRESULT E_INVALIDARG = static_cast<HRESULT>(0x80070057);
+
+const GUID IID_IUnknown = {
+    0x00000000, 0x0000, 0x0000, {0xC0, 0, 0, 0, 0, 0, 0, 0x46}};
+const GUID IID_IShape = {
+    0x8F2E6C1A,
+    0x3B4D,
+    0x4E5F,
+    {0x9A, 0x0B, 0x1C, 0x2D, 0x3E, 0x4F, 0x5A, 0x6B}};
+const GUID IID_IPolygon = {
+    0x8F2E6C1A,
+    0x3B4D,
+    0x4E5F,
+    {0x9A, 0x0B, 0x1C, 0x2D, 0x3E, 0x4F, 0x5A, 0x6C}};
+
+bool operator==(const GUID &lhs, const GUID &rhs) {
+  return std::memcmp(&lhs, &rhs, sizeof(GUID)) == 0;
+}
+
+class Square final : public IShape, public IPolygon {
+public:
+  explicit Square(int32_t side) : side(side), count(1) {}
+
+  HRESULT QueryInterface(const GUID &iid, void **out) override {
+    if (!out) {
+      return E_POINTER;
+    }
+    if (iid == IID_IUnknown || iid == IID_IShape) {
+      *out = static_cast<IShape *>(this);
+    } else if (iid == IID_IPolygon) {
+      *out = static_cast<IPolygon *>(this);
+    } else {
+      *out = nullptr;
+      return E_NOINTERFACE;
+    }
+    this->AddRef();
+    return S_OK;
+  }
+
+  uint32_t AddRef() override { return ++this->count; }
+
+  uint32_t Release() override {
+    uint32_t refs = --this->count;
+    if (refs == 0) {
+      delete this;
+    }
+    return refs;
+  }
+
+  HRESULT Area(int32_t *area) override {
+    *area = this->side * this->side;
+    return S_OK;
+  }
+
+  HRESULT Scale(int32_t factor) override {
+    if (factor <= 0) {
+      return E_INVALIDARG;
+    }
+    this->side *= factor;
+    return S_OK;
+  }
+
+  HRESULT Sides(uint8_t *sides) override {
+    *sides = 4;
+    return S_OK;
+  }
+
+private:
+  int32_t side;
+  uint32_t count;
+};
+
+// Hands out its contents a few bytes at a time, the way a pipe or terminal
+// would, with nothing more available until the buffered chunk is consumed.
+class ChunkedBuf final : public std::streambuf {
+public:
+  ChunkedBuf(std::string data, size_t chunk)
+      : data(std::move(data)), chunk(chunk), pos(0) {}
+
+protected:
+  int_type underflow() override {
+    if (this->pos == this->data.size()) {
+      return traits_type::eof();
+    }
+    char *begin = &this->data[this->pos];
+    this->pos = std::min(this->pos + this->chunk, this->data.size());
+    this->setg(begin, begin, &this->data[0] + this->pos);
+    return traits_type::to_int_type(*begin);
+  }
+
+private:
+  std::string data;
+  size_t chunk;
+  size_t pos;
+};
+} // namespace
+
 size_t Shared::c_method_on_shared() const noexcept { return 2021; }
 
 const size_t &Shared::c_method_ref_on_shared() const noexcept {
@@ -88,6 +209,22 @@ std::shared_ptr<C> c_return_shared_ptr() {
   return std::shared_ptr<C>(new C{2020});
 }
 
+std::shared_ptr<const C> c_return_shared_const_ptr() {
+  return std::shared_ptr<const C>(new C{2020});
+}
+
+IntrusivePtr<Counted> c_return_custom_ptr() {
+  return IntrusivePtr<Counted>(new Counted{2020});
+}
+
+HRESULT c_new_square(int32_t side, IShape **out) {
+  if (side <= 0) {
+    return E_INVALIDARG;
+  }
+  *out = new Square(side);
+  return S_OK;
+}
+
 std::unique_ptr<::H::H> c_return_ns_unique_ptr() {
   return std::unique_ptr<::H::H>(new ::H::H{"hello"});
 }
@@ -162,6 +299,52 @@ std::unique_ptr<std::vector<C>> c_return_unique_ptr_vector_opaque() {
   return std::unique_ptr<std::vector<C>>(new std::vector<C>());
 }
 
+std::unique_ptr<std::vector<bool>> c_return_unique_ptr_vector_bool() {
+  auto vec = std::unique_ptr<std::vector<bool>>(new std::vector<bool>());
+  vec->push_back(true);
+  vec->push_back(false);
+  vec->push_back(true);
+  return vec;
+}
+
+std::unique_ptr<uint32_t[]> c_return_unique_array(size_t n) {
+  std::unique_ptr<uint32_t[]> array(new uint32_t[n]);
+  for (size_t i = 0; i < n; i++) {
+    array[i] = 2020 + i;
+  }
+  return array;
+}
+
+std::unique_ptr<uint32_t[]> c_return_unique_array_len(size_t n) {
+  return c_return_unique_array(n);
+}
+
+std::unique_ptr<Shared[]> c_return_unique_array_shared(size_t n) {
+  std::unique_ptr<Shared[]> array(new Shared[n]);
+  for (size_t i = 0; i < n; i++) {
+    array[i].z = 2020 + i;
+  }
+  return array;
+}
+
+#ifdef __cpp_lib_shared_ptr_arrays
+std::shared_ptr<uint8_t[]> c_return_shared_array(size_t n) {
+  std::shared_ptr<uint8_t[]> array(new uint8_t[n]);
+  for (size_t i = 0; i < n; i++) {
+    array.get()[i] = static_cast<uint8_t>(i);
+  }
+  return array;
+}
+
+std::shared_ptr<Shared[]> c_return_shared_array_shared(size_t n) {
+  std::shared_ptr<Shared[]> array(new Shared[n]);
+  for (size_t i = 0; i < n; i++) {
+    array.get()[i].z = 2020 + i;
+  }
+  return array;
+}
+#endif // __cpp_lib_shared_ptr_arrays
+
 const std::vector<uint8_t> &c_return_ref_vector(const C &c) {
   return c.get_v();
 }
@@ -189,10 +372,31 @@ rust::Vec<rust::String> c_return_rust_vec_string() {
 
 rust::Vec<bool> c_return_rust_vec_bool() { return {true, true, false}; }
 
+rust::Vec<uint8_t> c_return_rust_vec_from_std_vector(size_t n) {
+  std::vector<uint8_t> v(n);
+  for (size_t i = 0; i < n; i++) {
+    v[i] = static_cast<uint8_t>(i);
+  }
+  auto vec = rust::Vec<uint8_t>::from_std_vector(std::move(v));
+  if (!v.empty() || v.capacity() != 0) {
+    throw std::runtime_error("std::vector not released");
+  }
+  return vec;
+}
+
 size_t c_return_identity(size_t n) { return n; }
 
 size_t c_return_sum(size_t n1, size_t n2) { return n1 + n2; }
 
+size_t c_return_sum_checked(size_t n1, size_t n2) {
+  if (n1 + n2 < n1) {
+    throw std::overflow_error("c_return_sum_checked");
+  }
+  return n1 + n2;
+}
+
+static void ignore_foreign_exception(const char *function) { (void)function; }
+
 Enum c_return_enum(uint16_t n) {
   if (n <= static_cast<uint16_t>(Enum::AVal)) {
     return Enum::AVal;
@@ -375,12 +579,106 @@ void c_take_unique_ptr_vector_shared(std::unique_ptr<std::vector<Shared>> v) {
   }
 }
 
+void c_take_unique_array(std::unique_ptr<uint32_t[]> a, size_t n) {
+  if (n == 3 && a[0] == 2020 && a[1] == 2021 && a[2] == 2050) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+#ifdef __cpp_lib_shared_ptr_arrays
+void c_take_shared_array(std::shared_ptr<uint8_t[]> a, size_t n) {
+  if (n == 2 && a.get()[0] == 4 && a.get()[1] == 5 && a.use_count() == 2) {
+    cxx_test_suite_set_correct();
+  }
+}
+#endif // __cpp_lib_shared_ptr_arrays
+
+void c_take_shared_const_ptr(std::shared_ptr<const C> c) {
+  if (c->get() == 2021 && c.use_count() == 2) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+void c_take_custom_ptr(IntrusivePtr<Counted> c) {
+  if (c.get()->get() == 2020 && c.get()->refs() == 2) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+Text::Text(std::string s) : s(std::move(s)) {}
+
+const std::string &Text::str() const { return this->s; }
+
+Text c_vocabulary_greet(Text name) { return Text("hello, " + name.str()); }
+
+Word c_vocabulary_first_word(Word text) {
+  size_t len = 0;
+  while (len < text.len && text.data[len] != ' ') {
+    len++;
+  }
+  return Word{text.data, len};
+}
+
+Bytes c_vocabulary_reverse(Bytes bytes) {
+  std::reverse(bytes.data.begin(), bytes.data.end());
+  return bytes;
+}
+
+int32_t c_take_com_ptr(rust::ComPtr<IShape> shape) {
+  int32_t area = 0;
+  shape->Area(&area);
+  return area;
+}
+
+uint32_t c_com_refcount(const IShape &shape) {
+  IShape &mut = const_cast<IShape &>(shape);
+  mut.AddRef();
+  return mut.Release();
+}
+
 void c_take_ref_vector(const std::vector<uint8_t> &v) {
   if (v.size() == 4) {
     cxx_test_suite_set_correct();
   }
 }
 
+void c_take_ref_vector_bool(const std::vector<bool> &v) {
+  if (v.size() == 4 && v[0] && !v[1] && !v[2] && v[3]) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+void c_take_rust_hash_map(rust::HashMap<rust::String, uint32_t> m) {
+  uint32_t sum = 0;
+  m.for_each([&](const rust::String &key, uint32_t value) {
+    if (key.size() == value) {
+      sum += value;
+    }
+  });
+  const uint32_t *three = m.get(rust::String("three"));
+  if (m.size() != 3 || m.empty() || !m.contains(rust::String("one")) ||
+      m.contains(rust::String("four")) || three == nullptr || *three != 5 ||
+      m.at(rust::String("seven")) != 5 || sum != 13) {
+    return;
+  }
+  try {
+    m.at(rust::String("four"));
+  } catch (const std::out_of_range &ex) {
+    std::string expected = "rust::HashMap key not found";
+    if (ex.what() == expected) {
+      cxx_test_suite_set_correct();
+    }
+  }
+}
+
+void c_take_rust_hash_map_roundtrip(rust::HashMap<uint8_t, int32_t> m) {
+  rust::HashMap<uint8_t, int32_t> same = r_return_rust_hash_map(m);
+  const int32_t *value = same.get(1);
+  if (r_take_rust_hash_map(same) == m.size() && value && *value == -1) {
+    cxx_test_suite_set_correct();
+  }
+}
+
 void c_take_rust_vec(rust::Vec<uint8_t> v) { c_take_ref_rust_vec(v); }
 
 void c_take_rust_vec_index(rust::Vec<uint8_t> v) {
@@ -429,6 +727,14 @@ void c_take_rust_vec_string(rust::Vec<rust::String> v) {
   cxx_test_suite_set_correct();
 }
 
+void c_take_rust_vec_into_std_vector(rust::Vec<rust::String> v) {
+  std::vector<rust::String> vector = v.into_std_vector();
+  if (v.empty() && v.capacity() == 0 && vector.size() == 3 &&
+      vector[0] == "first" && vector[2] == "third") {
+    cxx_test_suite_set_correct();
+  }
+}
+
 void c_take_rust_vec_shared_forward_iterator(rust::Vec<Shared> v) {
   // Exercise requirements of ForwardIterator
   // https://en.cppreference.com/w/cpp/named_req/ForwardIterator
@@ -485,6 +791,35 @@ void c_take_rust_vec_shared_clear(rust::Vec<Shared> v) {
   }
 }
 
+void c_take_rust_vec_shared_insert_erase(rust::Vec<Shared> v) {
+  // v = [1010, 1011]
+  auto it = v.insert(v.begin(), Shared{1009});
+  if (it != v.begin() || v.size() != 3) {
+    return;
+  }
+  std::vector<Shared> more{Shared{1012}, Shared{1013}};
+  v.insert(v.end(), more.begin(), more.end());
+  v.emplace(v.begin() + 1, Shared{2000});
+  // v = [1009, 2000, 1010, 1011, 1012, 1013]
+  it = v.erase(v.begin() + 1);
+  if (it->z != 1010) {
+    return;
+  }
+  it = v.erase(v.begin() + 3, v.end());
+  if (it != v.end()) {
+    return;
+  }
+  // v = [1009, 1010, 1011]
+  v.pop_back();
+  v.resize(4, Shared{7});
+  v.resize(5);
+  // v = [1009, 1010, 7, 7, 0]
+  if (v.size() == 5 && v.front().z == 1009 && v[1].z == 1010 && v[2].z == 7 &&
+      v[3].z == 7 && v.back().z == 0) {
+    cxx_test_suite_set_correct();
+  }
+}
+
 void c_take_ref_rust_vec(const rust::Vec<uint8_t> &v) {
   uint8_t sum = std::accumulate(v.begin(), v.end(), 0);
   if (sum == 200) {
@@ -569,6 +904,22 @@ size_t c_try_return_primitive() { return 2020; }
 
 size_t c_fail_return_primitive() { throw std::logic_error("logic error"); }
 
+size_t c_fail_return_system_error() {
+  throw std::system_error(42, std::generic_category(), "system error");
+}
+
+size_t c_fail_return_nested() {
+  try {
+    throw std::runtime_error("inner");
+  } catch (const std::exception &) {
+    std::throw_with_nested(std::runtime_error("outer"));
+  }
+}
+
+size_t c_fail_return_rust_error() { return r_fail_return_exception(); }
+
+size_t c_fail_return_foreign_exception() { throw 2020; }
+
 rust::Box<R> c_try_return_box() { return c_return_box(); }
 
 const rust::String &c_try_return_ref(const rust::String &s) { return s; }
@@ -606,6 +957,21 @@ size_t c_get_use_count(const std::weak_ptr<C> &weak) noexcept {
   return weak.use_count();
 }
 
+int64_t c_sum_istream(std::istream &input) {
+  int64_t sum = 0;
+  int64_t n;
+  while (input >> n) {
+    sum += n;
+  }
+  return input.eof() ? sum : -1;
+}
+
+void c_write_ostream(std::ostream &output, size_t lines) {
+  for (size_t i = 0; i < lines; i++) {
+    output << "line " << i << '\n';
+  }
+}
+
 extern "C" C *cxx_test_suite_get_unique_ptr() noexcept {
   return std::unique_ptr<C>(new C{2020}).release();
 }
@@ -782,9 +1148,17 @@ extern "C" const char *cxx_run_test() noexcept {
   ASSERT(rust::size_of<size_t>() == sizeof(size_t));
   ASSERT(rust::align_of<size_t>() == alignof(size_t));
 
+  ASSERT(rust::set_foreign_exception_handler(ignore_foreign_exception) ==
+         nullptr);
+  ASSERT(rust::set_foreign_exception_handler(nullptr) ==
+         ignore_foreign_exception);
+
   ASSERT(r_return_primitive() == 2020);
   ASSERT(r_return_shared().z == 2020);
   ASSERT(cxx_test_suite_r_is_correct(&*r_return_box()));
+  ASSERT(rust::Box<R>::make()->get() == 0);
+  ASSERT(std::hash<R>{}(*rust::Box<R>::make()) ==
+         std::hash<R>{}(*rust::Box<R>::make()));
   ASSERT(r_return_unique_ptr()->get() == 2020);
   ASSERT(r_return_shared_ptr()->get() == 2020);
   ASSERT(r_return_ref(Shared{2020}) == 2020);
@@ -822,6 +1196,52 @@ extern "C" const char *cxx_run_test() noexcept {
     ASSERT(false);
   } catch (const rust::Error &e) {
     ASSERT(std::strcmp(e.what(), "rust error") == 0);
+    ASSERT(e.code() == 0);
+    ASSERT(e.sources().empty());
+  }
+
+  try {
+    r_fail_return_exception();
+    ASSERT(false);
+  } catch (const rust::Error &e) {
+    ASSERT(std::strcmp(e.what(), "outer") == 0);
+    ASSERT(e.code() == 7);
+    ASSERT((e.sources() == std::vector<std::string>{"middle", "inner"}));
+    rust::Error copy = e;
+    ASSERT(std::strcmp(copy.what(), "outer") == 0);
+    ASSERT(copy.code() == 7);
+    ASSERT(copy.sources().size() == 2);
+  }
+
+  try {
+    r_fail_return_error_chain();
+    ASSERT(false);
+  } catch (const rust::Error &e) {
+    ASSERT(std::strcmp(e.what(), "outer error") == 0);
+    ASSERT(e.code() == 0);
+    ASSERT((e.sources() == std::vector<std::string>{"rust error"}));
+  }
+
+  ChunkedBuf chunked("hello world", 3);
+  std::istream chunked_in(&chunked);
+  ASSERT(r_read_once(chunked_in) == 3);
+  ASSERT(r_read_once(chunked_in) == 3);
+  ASSERT(r_read_once(chunked_in) == 3);
+  ASSERT(r_read_once(chunked_in) == 2);
+  ASSERT(r_read_once(chunked_in) == 0);
+
+  std::istringstream stream_in("hello world");
+  std::ostringstream stream_out;
+  ASSERT(r_copy_stream(stream_in, stream_out) == 10);
+  ASSERT(stream_out.str() == "ello world!");
+  ASSERT(stream_in.eof() && !stream_in.fail());
+  stream_in.seekg(0);
+  stream_out.setstate(std::ios_base::badbit);
+  try {
+    r_copy_stream(stream_in, stream_out);
+    ASSERT(false);
+  } catch (const rust::Error &e) {
+    ASSERT(std::strcmp(e.what(), "std::ostream write failed") == 0);
   }
 
   auto r = r_return_box();
@@ -860,6 +1280,9 @@ extern "C" const char *cxx_run_test() noexcept {
     ASSERT((sec.*cmp)(second) == sec_second);
     ASSERT((second.*cmp)(sec) == second_sec);
   }
+  ASSERT(rust::Str("z") < rust::Str("\xC3\xA9"));
+  ASSERT(rust::Str("z") <= rust::Str("\xC3\xA9"));
+  ASSERT(rust::String("\xC3\xA9") > rust::String("z"));
 
   rust::String cstring = "test";
   ASSERT(cstring.length() == 4);
@@ -889,6 +1312,17 @@ extern "C" const char *cxx_run_test() noexcept {
 #endif
   }
 
+  {
+    std::unordered_set<rust::String> set{"a", "b"};
+    ASSERT(set.count("a") == 1);
+    ASSERT(set.count("c") == 0);
+    ASSERT(std::hash<rust::String>{}("a") == std::hash<rust::Str>{}("a"));
+    rust::Vec<rust::String> vec1{"a", "b"};
+    rust::Vec<rust::String> vec2{"a", "b"};
+    ASSERT(std::hash<rust::Vec<rust::String>>{}(vec1) ==
+           std::hash<rust::Vec<rust::String>>{}(vec2));
+  }
+
   rust::Str cstr = "test";
   rust::Str other_cstr = "foo";
   swap(cstr, other_cstr);
@@ -908,6 +1342,75 @@ extern "C" const char *cxx_run_test() noexcept {
   rust::String bad_utf16_rstring = rust::String::lossy(bad_utf16_literal);
   ASSERT(bad_utf8_rstring == bad_utf16_rstring);
 
+  std::u16string utf16_string = u"\u00e9t\u00e9 \U0001F600 \u4e2d";
+  rust::String from_u16string = rust::String::from_utf16(utf16_string);
+  ASSERT(from_u16string == u8"\u00e9t\u00e9 \U0001F600 \u4e2d");
+  ASSERT(from_u16string.to_u16string() == utf16_string);
+  ASSERT(rust::String().to_u16string().empty());
+
+  rust::String appended = "rust";
+  appended += rust::Str("::");
+  appended += std::string("String");
+  appended += rust::String(" ");
+  appended += "ok";
+  ASSERT(appended == "rust::String ok");
+  try {
+    appended += std::string("\x80");
+    ASSERT(false);
+  } catch (const std::invalid_argument &) {
+    ASSERT(appended == "rust::String ok");
+  }
+  ASSERT(appended.starts_with("rust::"));
+  ASSERT(!appended.starts_with("String"));
+  ASSERT(appended.ends_with(" ok"));
+  ASSERT(!appended.ends_with("rust::String ok!"));
+  ASSERT(appended.find("::") == 4);
+  ASSERT(appended.find("S", 7) == std::string::npos);
+  ASSERT(appended.find("", 3) == 3);
+  ASSERT(appended.find("x") == std::string::npos);
+
+  {
+    using namespace rust::literals;
+    rust::Str from_literal = rust::Str::from_literal("2020");
+    rust::Str from_udl = "2020"_rs;
+    ASSERT(from_literal == rust::Str("2020"));
+    ASSERT(from_udl == from_literal);
+    ASSERT(from_udl.size() == 4);
+    ASSERT(""_rs.empty());
+    ASSERT("a\0b"_rs.size() == 3);
+    ASSERT(std::string("2020"_rs) == "2020");
+    r_take_str("2020"_rs);
+  }
+
+#ifdef __cpp_lib_three_way_comparison
+  {
+    ASSERT((rust::Str("a") <=> rust::Str("b")) < 0);
+    ASSERT((rust::String("b") <=> rust::String("ab")) > 0);
+    ASSERT((rust::String("ab") <=> rust::String("ab")) == 0);
+    ASSERT((rust::Str("z") <=> rust::Str("\xC3\xA9")) < 0);
+    ASSERT((rust::String("\xC3\xA9") <=> rust::String("z")) > 0);
+    const int lhs[] = {1, 2}, rhs[] = {1, 2, 3};
+    ASSERT(rust::Slice<const int>(lhs) < rust::Slice<const int>(rhs));
+    ASSERT(rust::Slice<const int>(lhs) != rust::Slice<const int>(rhs));
+    ASSERT((rust::Vec<int>{1, 2} == rust::Vec<int>{1, 2}));
+    std::set<rust::Vec<std::uint8_t>> set{{2}, {1, 2}, {1}};
+    ASSERT(set.begin()->size() == 1 && set.begin()->front() == 1);
+    ASSERT(std::prev(set.end())->front() == 2);
+  }
+#endif // __cpp_lib_three_way_comparison
+
+#ifdef __cpp_lib_format
+  {
+    const std::uint8_t bytes[] = {1, 2, 0xff};
+    ASSERT(std::format("{}", rust::Str("2020")) == "2020");
+    ASSERT(std::format("{:>6}", rust::String("2020")) == "  2020");
+    ASSERT(std::format("{}", rust::Slice<const std::uint8_t>(bytes)) ==
+           "[1, 2, 255]");
+    ASSERT(std::format("{:02x}", rust::Slice<const std::uint8_t>(bytes)) ==
+           "[01, 02, ff]");
+  }
+#endif // __cpp_lib_format
+
   // Test Slice<T> explicit constructor from container
   {
     std::vector<int> cpp_vec{1, 2, 3};
@@ -916,6 +1419,26 @@ extern "C" const char *cxx_run_test() noexcept {
     ASSERT(slice_of_cpp_vec.size() == cpp_vec.size());
     ASSERT(slice_of_cpp_vec[0] == 1);
   }
+  {
+    int c_array[] = {1, 2, 3};
+    rust::Slice<int> slice_of_c_array(c_array);
+    ASSERT(slice_of_c_array.data() == c_array);
+    ASSERT(slice_of_c_array.size() == 3);
+    rust::Slice<const int> const_slice_of_c_array(c_array);
+    ASSERT(const_slice_of_c_array.back() == 3);
+  }
+#ifdef __cpp_lib_span
+  {
+    std::array<int, 3> cpp_array{1, 2, 3};
+    rust::Slice<int> slice_of_span = std::span<int, 3>(cpp_array);
+    ASSERT(slice_of_span.data() == cpp_array.data());
+    std::span<int> span_of_slice = slice_of_span;
+    ASSERT(span_of_slice.data() == cpp_array.data());
+    ASSERT(span_of_slice.size() == 3);
+    std::span<const int> const_span = rust::Slice<const int>(cpp_array);
+    ASSERT(const_span[2] == 3);
+  }
+#endif // __cpp_lib_span
 
   // Test Slice<T> template deduction guides
 #ifdef __cpp_deduction_guides
@@ -954,6 +1477,20 @@ extern "C" const char *cxx_run_test() noexcept {
     static_assert(std::is_same_v<decltype(auto_slice_of_cpp_vec),
                                  rust::Slice<const int>>);
   }
+  {
+    // T[N] -> Slice<T>
+    int c_array[] = {1, 2, 3};
+    auto auto_slice_of_c_array = rust::Slice(c_array);
+    static_assert(
+        std::is_same_v<decltype(auto_slice_of_c_array), rust::Slice<int>>);
+  }
+  {
+    // const T[N] -> Slice<const T>
+    const int c_array[] = {1, 2, 3};
+    auto auto_slice_of_c_array = rust::Slice(c_array);
+    static_assert(std::is_same_v<decltype(auto_slice_of_c_array),
+                                 rust::Slice<const int>>);
+  }
 #ifdef __cpp_lib_span
   {
     // std::span<T> -> Slice<T>
@@ -1014,6 +1551,42 @@ void ns_c_take_ns_shared(::A::AShared shared) {
 }
 } // namespace other
 
+namespace tests {
+namespace nested {
+Nested c_nested(uint32_t value) { return r_nested(value + 1); }
+} // namespace nested
+} // namespace tests
+
+tests::Text rust::Vocabulary<tests::Text>::from_rust(rust::String s) {
+  return tests::Text(std::string(s));
+}
+
+rust::String rust::Vocabulary<tests::Text>::to_rust(const tests::Text &text) {
+  return rust::String(text.str());
+}
+
+tests::Word rust::Vocabulary<tests::Word>::from_rust(rust::Str s) noexcept {
+  return tests::Word{s.data(), s.size()};
+}
+
+rust::Str rust::Vocabulary<tests::Word>::to_rust(
+    const tests::Word &word) noexcept {
+  return rust::Str(word.data, word.len);
+}
+
+tests::Bytes rust::Vocabulary<tests::Bytes>::from_rust(rust::Vec<uint8_t> v) {
+  return tests::Bytes{std::vector<uint8_t>(v.begin(), v.end())};
+}
+
+rust::Vec<uint8_t>
+rust::Vocabulary<tests::Bytes>::to_rust(const tests::Bytes &bytes) {
+  rust::Vec<uint8_t> v;
+  for (uint8_t b : bytes.data) {
+    v.push_back(b);
+  }
+  return v;
+}
+
 namespace I {
 uint32_t I::get() const { return a; }
 
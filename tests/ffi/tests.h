@@ -3,6 +3,27 @@
 #include <memory>
 #include <string>
 
+// Stand-ins for the declarations of <unknwn.h>, so that COM interop is tested
+// on every platform.
+using HRESULT = int32_t;
+
+struct GUID {
+  uint32_t Data1;
+  uint16_t Data2;
+  uint16_t Data3;
+  uint8_t Data4[8];
+};
+
+class IUnknown {
+public:
+  virtual HRESULT QueryInterface(const GUID &iid, void **out) = 0;
+  virtual uint32_t AddRef() = 0;
+  virtual uint32_t Release() = 0;
+
+protected:
+  ~IUnknown() = default;
+};
+
 namespace A {
 struct AShared;
 enum class AEnum : uint16_t;
@@ -41,6 +62,9 @@ enum class Enum : uint16_t;
 
 class C {
 public:
+  using IsSend = std::true_type;
+  using IsSync = std::true_type;
+
   C(size_t n);
   size_t get() const;
   size_t set(size_t n);
@@ -82,6 +106,91 @@ enum COwnedEnum {
   CVAL2,
 };
 
+// A minimal intrusive reference counting pointer in the style of
+// boost::intrusive_ptr, bound in Rust as CustomPtr<IntrusivePtr, T>.
+template <typename T>
+class IntrusivePtr {
+public:
+  IntrusivePtr() noexcept : ptr(nullptr) {}
+  explicit IntrusivePtr(T *ptr) noexcept : ptr(ptr) {
+    if (ptr) {
+      ptr->retain();
+    }
+  }
+  IntrusivePtr(const IntrusivePtr &other) noexcept : IntrusivePtr(other.ptr) {}
+  IntrusivePtr(IntrusivePtr &&other) noexcept : ptr(other.ptr) {
+    other.ptr = nullptr;
+  }
+  IntrusivePtr &operator=(const IntrusivePtr &) = delete;
+  ~IntrusivePtr() noexcept {
+    if (ptr) {
+      ptr->release();
+    }
+  }
+  T *get() const noexcept { return ptr; }
+
+private:
+  T *ptr;
+};
+
+class Counted {
+public:
+  Counted(size_t n);
+  size_t get() const;
+  size_t refs() const;
+  void retain() const noexcept;
+  void release() const noexcept;
+
+private:
+  size_t n;
+  mutable size_t count;
+};
+
+// Stand-ins for third-party vocabulary types, an owning string like QString, a
+// string view like absl::string_view and a byte buffer like QByteArray, which
+// are bound in Rust as String, &str and Vec<u8> through the rust::Vocabulary
+// specializations below.
+class Text {
+public:
+  explicit Text(std::string s);
+  const std::string &str() const;
+
+private:
+  std::string s;
+};
+
+struct Word {
+  const char *data;
+  size_t len;
+};
+
+struct Bytes {
+  std::vector<uint8_t> data;
+};
+
+class IShape : public IUnknown {
+public:
+  virtual HRESULT Area(int32_t *area) = 0;
+  virtual HRESULT Scale(int32_t factor) = 0;
+
+protected:
+  ~IShape() = default;
+};
+
+class IPolygon : public IUnknown {
+public:
+  virtual HRESULT Sides(uint8_t *sides) = 0;
+
+protected:
+  ~IPolygon() = default;
+};
+
+// Implemented by nothing, for QueryInterface to fail.
+class ICircle : public IUnknown {
+protected:
+  ~ICircle() = default;
+};
+
 struct Borrow {
   Borrow(const std::string &s);
   void const_member() const;
@@ -98,6 +207,9 @@ Shared c_return_shared();
 rust::Box<R> c_return_box();
 std::unique_ptr<C> c_return_unique_ptr();
 std::shared_ptr<C> c_return_shared_ptr();
+std::shared_ptr<const C> c_return_shared_const_ptr();
+IntrusivePtr<Counted> c_return_custom_ptr();
+HRESULT c_new_square(int32_t side, IShape **out);
 std::unique_ptr<::H::H> c_return_ns_unique_ptr();
 const size_t &c_return_ref(const Shared &shared);
 const size_t &c_return_ns_ref(const ::A::AShared &shared);
@@ -114,6 +226,14 @@ std::unique_ptr<std::vector<double>> c_return_unique_ptr_vector_f64();
 std::unique_ptr<std::vector<std::string>> c_return_unique_ptr_vector_string();
 std::unique_ptr<std::vector<Shared>> c_return_unique_ptr_vector_shared();
 std::unique_ptr<std::vector<C>> c_return_unique_ptr_vector_opaque();
+std::unique_ptr<std::vector<bool>> c_return_unique_ptr_vector_bool();
+std::unique_ptr<uint32_t[]> c_return_unique_array(size_t n);
+std::unique_ptr<uint32_t[]> c_return_unique_array_len(size_t n);
+std::unique_ptr<Shared[]> c_return_unique_array_shared(size_t n);
+#ifdef __cpp_lib_shared_ptr_arrays
+std::shared_ptr<uint8_t[]> c_return_shared_array(size_t n);
+std::shared_ptr<Shared[]> c_return_shared_array_shared(size_t n);
+#endif // __cpp_lib_shared_ptr_arrays
 const std::vector<uint8_t> &c_return_ref_vector(const C &c);
 std::vector<uint8_t> &c_return_mut_vector(C &c);
 rust::Vec<uint8_t> c_return_rust_vec_u8();
@@ -121,7 +241,9 @@ const rust::Vec<uint8_t> &c_return_ref_rust_vec(const C &c);
 rust::Vec<uint8_t> &c_return_mut_rust_vec(C &c);
 rust::Vec<rust::String> c_return_rust_vec_string();
 rust::Vec<bool> c_return_rust_vec_bool();
+rust::Vec<uint8_t> c_return_rust_vec_from_std_vector(size_t n);
 size_t c_return_identity(size_t n);
+size_t c_return_sum_checked(size_t n1, size_t n2);
 size_t c_return_sum(size_t n1, size_t n2);
 Enum c_return_enum(uint16_t n);
 ::A::AEnum c_return_ns_enum(uint16_t n);
@@ -152,17 +274,33 @@ void c_take_unique_ptr_vector_f64(std::unique_ptr<std::vector<double>> v);
 void c_take_unique_ptr_vector_string(
     std::unique_ptr<std::vector<std::string>> v);
 void c_take_unique_ptr_vector_shared(std::unique_ptr<std::vector<Shared>> v);
+void c_take_unique_array(std::unique_ptr<uint32_t[]> a, size_t n);
+#ifdef __cpp_lib_shared_ptr_arrays
+void c_take_shared_array(std::shared_ptr<uint8_t[]> a, size_t n);
+#endif // __cpp_lib_shared_ptr_arrays
+void c_take_shared_const_ptr(std::shared_ptr<const C> c);
+void c_take_custom_ptr(IntrusivePtr<Counted> c);
+Text c_vocabulary_greet(Text name);
+Word c_vocabulary_first_word(Word text);
+Bytes c_vocabulary_reverse(Bytes bytes);
+int32_t c_take_com_ptr(rust::ComPtr<IShape> shape);
+uint32_t c_com_refcount(const IShape &shape);
 void c_take_ref_vector(const std::vector<uint8_t> &v);
+void c_take_ref_vector_bool(const std::vector<bool> &v);
+void c_take_rust_hash_map(rust::HashMap<rust::String, uint32_t> m);
+void c_take_rust_hash_map_roundtrip(rust::HashMap<uint8_t, int32_t> m);
 void c_take_rust_vec(rust::Vec<uint8_t> v);
 void c_take_rust_vec_index(rust::Vec<uint8_t> v);
 void c_take_rust_vec_shared(rust::Vec<Shared> v);
 void c_take_rust_vec_ns_shared(rust::Vec<::A::AShared> v);
 void c_take_rust_vec_nested_ns_shared(rust::Vec<::A::B::ABShared> v);
 void c_take_rust_vec_string(rust::Vec<rust::String> v);
+void c_take_rust_vec_into_std_vector(rust::Vec<rust::String> v);
 void c_take_rust_vec_shared_index(rust::Vec<Shared> v);
 void c_take_rust_vec_shared_push(rust::Vec<Shared> v);
 void c_take_rust_vec_shared_truncate(rust::Vec<Shared> v);
 void c_take_rust_vec_shared_clear(rust::Vec<Shared> v);
+void c_take_rust_vec_shared_insert_erase(rust::Vec<Shared> v);
 void c_take_rust_vec_shared_forward_iterator(rust::Vec<Shared> v);
 void c_take_rust_vec_shared_sort(rust::Vec<Shared> v);
 void c_take_ref_rust_vec(const rust::Vec<uint8_t> &v);
@@ -182,6 +320,10 @@ size_t c_take_mut_ptr(C *c);
 void c_try_return_void();
 size_t c_try_return_primitive();
 size_t c_fail_return_primitive();
+size_t c_fail_return_system_error();
+size_t c_fail_return_nested();
+size_t c_fail_return_rust_error();
+size_t c_fail_return_foreign_exception();
 rust::Box<R> c_try_return_box();
 const rust::String &c_try_return_ref(const rust::String &);
 rust::Str c_try_return_str(rust::Str);
@@ -195,6 +337,9 @@ const rust::Vec<uint8_t> &c_try_return_ref_rust_vec(const C &c);
 
 size_t c_get_use_count(const std::weak_ptr<C> &weak) noexcept;
 
+int64_t c_sum_istream(std::istream &input);
+void c_write_ostream(std::ostream &output, size_t lines);
+
 void c_take_trivial_ptr(std::unique_ptr<D> d);
 void c_take_trivial_ref(const D &d);
 void c_take_trivial_mut_ref(D &d);
@@ -222,6 +367,14 @@ rust::String cOverloadedFunction(rust::Str x);
 
 } // namespace tests
 
+namespace tests {
+namespace nested {
+struct Nested;
+
+Nested c_nested(uint32_t value);
+} // namespace nested
+} // namespace tests
+
 namespace other {
 void ns_c_take_trivial(::tests::D d);
 ::tests::D ns_c_return_trivial();
@@ -240,3 +393,30 @@ public:
 
 std::unique_ptr<I> ns_c_return_unique_ptr_ns();
 } // namespace I
+
+template <>
+struct rust::Vocabulary<tests::Text> {
+  static tests::Text from_rust(rust::String s);
+  static rust::String to_rust(const tests::Text &text);
+};
+
+template <>
+struct rust::Vocabulary<tests::Word> {
+  static tests::Word from_rust(rust::Str s) noexcept;
+  static rust::Str to_rust(const tests::Word &word) noexcept;
+};
+
+template <>
+struct rust::Vocabulary<tests::Bytes> {
+  static tests::Bytes from_rust(rust::Vec<uint8_t> v);
+  static rust::Vec<uint8_t> to_rust(const tests::Bytes &bytes);
+};
+
+namespace std {
+template <>
+struct hash<tests::C> {
+  size_t operator()(const tests::C &c) const noexcept {
+    return hash<size_t>{}(c.get());
+  }
+};
+} // namespace std
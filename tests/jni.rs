@@ -0,0 +1,161 @@
+#![cfg(feature = "jni")]
+
+use cxx::jni::{JClass, JNIEnv, JObject, JavaVM};
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::pin::Pin;
+use std::ptr;
+
+#[cxx::bridge(namespace = "tests")]
+mod ffi {
+    unsafe extern "C++" {
+        include!("jni.h");
+
+        #[namespace = ""]
+        type JavaVM = cxx::jni::JavaVM;
+        #[namespace = ""]
+        type JNIEnv = cxx::jni::JNIEnv;
+        #[namespace = ""]
+        type jobject<'local> = cxx::jni::JObject<'local>;
+        #[namespace = ""]
+        type jclass<'local> = cxx::jni::JClass<'local>;
+    }
+
+    extern "Rust" {
+        fn on_load(vm: &JavaVM) -> i32;
+        fn is_instance(env: Pin<&mut JNIEnv>, object: jobject<'_>, clazz: jclass<'_>) -> bool;
+    }
+}
+
+fn on_load(vm: &JavaVM) -> i32 {
+    let _ = vm;
+    0x0001_0006
+}
+
+fn is_instance(_env: Pin<&mut JNIEnv>, object: JObject, clazz: JClass) -> bool {
+    !object.is_null() && !JObject::from(clazz).is_null()
+}
+
+// Stand-ins for a JVM and a JNIEnv, which only have the function tables.
+#[repr(C)]
+struct Fake {
+    functions: *const *const c_void,
+}
+
+thread_local! {
+    static ATTACHED: Cell<bool> = const { Cell::new(false) };
+}
+
+static mut VM: Fake = Fake {
+    functions: ptr::null(),
+};
+static mut ENV: Fake = Fake {
+    functions: ptr::null(),
+};
+
+fn vm() -> &'static JavaVM {
+    unsafe { &*ptr::addr_of!(VM).cast::<JavaVM>() }
+}
+
+fn env() -> *mut JNIEnv {
+    ptr::addr_of_mut!(ENV).cast::<JNIEnv>()
+}
+
+extern "system" fn attach(_vm: *mut JavaVM, penv: *mut *mut JNIEnv, _args: *mut c_void) -> i32 {
+    ATTACHED.with(|attached| attached.set(true));
+    unsafe { *penv = env() };
+    0
+}
+
+extern "system" fn attach_fail(
+    _vm: *mut JavaVM,
+    _penv: *mut *mut JNIEnv,
+    _args: *mut c_void,
+) -> i32 {
+    -4
+}
+
+extern "system" fn detach(_vm: *mut JavaVM) -> i32 {
+    ATTACHED.with(|attached| attached.set(false));
+    0
+}
+
+extern "system" fn get_env(_vm: *mut JavaVM, penv: *mut *mut JNIEnv, _version: i32) -> i32 {
+    if !ATTACHED.with(Cell::get) {
+        return -2;
+    }
+    unsafe { *penv = env() };
+    0
+}
+
+extern "system" fn get_java_vm(_env: *mut JNIEnv, pvm: *mut *mut JavaVM) -> i32 {
+    unsafe { *pvm = vm() as *const JavaVM as *mut JavaVM };
+    0
+}
+
+fn install() {
+    let mut vm_functions = vec![ptr::null(); 8];
+    vm_functions[4] = attach as *const c_void;
+    vm_functions[5] = detach as *const c_void;
+    vm_functions[6] = get_env as *const c_void;
+    vm_functions[7] = attach_fail as *const c_void;
+    let mut env_functions = vec![ptr::null(); 220];
+    env_functions[219] = get_java_vm as *const c_void;
+    unsafe {
+        VM.functions = Box::leak(vm_functions.into_boxed_slice()).as_ptr();
+        ENV.functions = Box::leak(env_functions.into_boxed_slice()).as_ptr();
+    }
+}
+
+#[test]
+fn test_attach() {
+    install();
+    let vm = vm();
+
+    let mut guard = vm.attach_current_thread().unwrap();
+    assert!(ATTACHED.with(Cell::get));
+    assert!(ptr::eq(vm, guard.env().java_vm().unwrap()));
+    assert!(ptr::eq(vm, guard.java_vm()));
+
+    let nested = vm.attach_current_thread().unwrap();
+    drop(nested);
+    assert!(ATTACHED.with(Cell::get));
+
+    drop(guard);
+    assert!(!ATTACHED.with(Cell::get));
+
+    // The thread stays attached until the last guard is gone, even when the
+    // guard that attached it is dropped first.
+    let first = vm.attach_current_thread().unwrap();
+    let mut second = vm.attach_current_thread().unwrap();
+    drop(first);
+    assert!(ATTACHED.with(Cell::get));
+    assert!(ptr::eq(vm, second.env().java_vm().unwrap()));
+    drop(second);
+    assert!(!ATTACHED.with(Cell::get));
+
+    // A thread that was attached by someone else is never detached.
+    ATTACHED.with(|attached| attached.set(true));
+    drop(vm.attach_current_thread().unwrap());
+    assert!(ATTACHED.with(Cell::get));
+    ATTACHED.with(|attached| attached.set(false));
+
+    let error = vm.attach_current_thread_as_daemon().unwrap_err();
+    assert_eq!(-4, error.code());
+    assert_eq!("JNI_ENOMEM", error.to_string());
+    assert!(!ATTACHED.with(Cell::get));
+}
+
+#[test]
+fn test_local_reference() {
+    let mut object = 0u8;
+    let object = unsafe { JObject::from_raw(ptr::addr_of_mut!(object).cast()) };
+    assert!(!object.is_null());
+    assert!(JClass::null().is_null());
+    assert!(!is_instance(
+        unsafe { Pin::new_unchecked(&mut *env()) },
+        object,
+        JClass::null(),
+    ));
+    assert_eq!("JClass(0x0)", format!("{:?}", JClass::null()));
+}
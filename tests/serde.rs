@@ -0,0 +1,109 @@
+#[cxx::bridge]
+mod ffi {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        x: i32,
+        #[serde(rename = "y-coordinate")]
+        y: i32,
+        label: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    enum Color {
+        Red,
+        Green = 8,
+        #[cxx_name = "kBlue"]
+        Blue,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde_variant_names]
+    enum Level {
+        Low,
+        Medium = 8,
+        #[cxx_name = "kHigh"]
+        High,
+    }
+}
+
+#[test]
+fn test_shared_struct() {
+    let point = ffi::Point {
+        x: 1,
+        y: 2,
+        label: "origin".to_owned(),
+    };
+    let json = serde_json::to_string(&point).unwrap();
+    assert_eq!(r#"{"x":1,"y-coordinate":2,"label":"origin"}"#, json);
+    assert_eq!(point, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn test_shared_enum() {
+    let json = serde_json::to_string(&ffi::Color::Green).unwrap();
+    assert_eq!(r#"{"repr":8}"#, json);
+    assert_eq!(ffi::Color::Green, serde_json::from_str(&json).unwrap());
+    assert_eq!(
+        ffi::Color { repr: 5 },
+        serde_json::from_str(r#"{"repr":5}"#).unwrap(),
+    );
+    assert!(serde_json::from_str::<ffi::Color>(r#""Green""#).is_err());
+}
+
+#[test]
+fn test_shared_enum_variant_names() {
+    assert_eq!(
+        r#""Medium""#,
+        serde_json::to_string(&ffi::Level::Medium).unwrap()
+    );
+    assert_eq!(
+        r#""High""#,
+        serde_json::to_string(&ffi::Level::High).unwrap()
+    );
+    // Discriminant that C++ may produce without a Rust variant name for it.
+    assert_eq!("5", serde_json::to_string(&ffi::Level { repr: 5 }).unwrap());
+
+    assert_eq!(ffi::Level::High, serde_json::from_str(r#""High""#).unwrap());
+    assert_eq!(ffi::Level::Medium, serde_json::from_str("8").unwrap());
+    assert_eq!(ffi::Level { repr: 5 }, serde_json::from_str("5").unwrap());
+    assert!(serde_json::from_str::<ffi::Level>(r#""kHigh""#).is_err());
+    assert!(serde_json::from_str::<ffi::Level>("-1").is_err());
+}
+
+#[cfg(feature = "serde")]
+mod runtime {
+    use cxx::{CxxString, CxxVector, UniquePtr};
+
+    #[test]
+    fn test_cxx_string() {
+        let string = UniquePtr::<CxxString>::from("2020");
+        assert_eq!(r#""2020""#, serde_json::to_string(&*string).unwrap());
+        assert_eq!(r#""2020""#, serde_json::to_string(&string).unwrap());
+
+        let invalid_utf8 = UniquePtr::<CxxString>::from(&b"\xF1\x80"[..]);
+        let json = serde_json::to_string(&invalid_utf8).unwrap();
+        assert_eq!("[241,128]", json);
+        let roundtrip: UniquePtr<CxxString> = serde_json::from_str(&json).unwrap();
+        assert_eq!(b"\xF1\x80", roundtrip.as_bytes());
+
+        let string: UniquePtr<CxxString> = serde_json::from_str(r#""2021""#).unwrap();
+        assert_eq!(*string, *"2021");
+        let null: UniquePtr<CxxString> = serde_json::from_str("null").unwrap();
+        assert!(null.is_null());
+        assert_eq!("null", serde_json::to_string(&null).unwrap());
+    }
+
+    #[test]
+    fn test_cxx_vector() {
+        let mut vector = CxxVector::<u32>::new();
+        vector.pin_mut().push(1);
+        vector.pin_mut().push(2);
+        assert_eq!("[1,2]", serde_json::to_string(&vector).unwrap());
+
+        let vector: UniquePtr<CxxVector<u32>> = serde_json::from_str("[3,4,5]").unwrap();
+        assert_eq!([3, 4, 5], vector.as_slice());
+
+        let vector: UniquePtr<CxxVector<bool>> = serde_json::from_str("[true,false]").unwrap();
+        assert_eq!("[true,false]", serde_json::to_string(&vector).unwrap());
+    }
+}
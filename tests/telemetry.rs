@@ -0,0 +1,44 @@
+#![cfg(feature = "telemetry")]
+
+use cxx::telemetry::{self, Allocation, Origin};
+use cxx::{CxxString, CxxVector, UniquePtr};
+use cxx_test_suite::ffi;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn hook(allocation: &Allocation) {
+    if allocation.container == "std::vector" {
+        HOOK_CALLS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// One test function, since the counters are process-wide.
+#[test]
+fn test_telemetry() {
+    let before = telemetry::totals(Origin::CxxRequested);
+    let vec = ffi::c_return_rust_vec_from_std_vector(1000);
+    let string = ffi::c_return_rust_string();
+    let after = telemetry::totals(Origin::CxxRequested);
+    assert!(after.count >= before.count + 2);
+    assert!(after.bytes >= before.bytes + vec.capacity() + string.capacity());
+
+    assert!(telemetry::set_hook(Some(hook)).is_none());
+    let before = telemetry::totals(Origin::RustRequested);
+    let mut vector = CxxVector::<u64>::new();
+    vector.pin_mut().reserve(100);
+    let mut long = UniquePtr::<CxxString>::from("");
+    long.pin_mut().push_str(&"x".repeat(1000));
+    let after = telemetry::totals(Origin::RustRequested);
+    assert_eq!(before.count + 2, after.count);
+    assert!(after.bytes >= before.bytes + 800 + 1000);
+    assert_eq!(1, HOOK_CALLS.load(Ordering::Relaxed));
+
+    assert!(telemetry::set_hook(None).is_some());
+    vector.pin_mut().reserve(1000);
+    assert_eq!(1, HOOK_CALLS.load(Ordering::Relaxed));
+    assert_eq!(
+        after.count + 1,
+        telemetry::totals(Origin::RustRequested).count
+    );
+}
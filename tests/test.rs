@@ -8,11 +8,18 @@
     clippy::unit_cmp
 )]
 
-use cxx::{SharedPtr, UniquePtr};
+use cxx::{
+    ComPtr, CustomPtr, CxxIstream, CxxOstream, SharedConstPtr, SharedPtr, UniquePtr, WeakPtr,
+    HRESULT,
+};
 use cxx_test_suite::module::ffi2;
 use cxx_test_suite::{cast, ffi, R};
 use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
 use std::ffi::CStr;
+use std::hash::BuildHasher as _;
+use std::io::{self, Read, Write};
 use std::panic::{self, RefUnwindSafe, UnwindSafe};
 use std::ptr;
 
@@ -57,7 +64,23 @@ fn test_c_return() {
     assert_eq!("2020", ffi::c_return_unique_ptr_string().to_str().unwrap());
     assert_eq!(c"2020", ffi::c_return_unique_ptr_string().as_c_str());
     assert_eq!(4, ffi::c_return_unique_ptr_vector_u8().len());
+    assert_eq!(
+        [true, false, true],
+        *ffi::c_return_unique_ptr_vector_bool().to_vec(),
+    );
     assert!(4 <= ffi::c_return_unique_ptr_vector_u8().capacity());
+    assert_eq!([2020, 2021, 2022], unsafe {
+        ffi::c_return_unique_array(3).as_slice(3)
+    },);
+    assert_eq!([2020, 2021], *ffi::c_return_unique_array_len(2));
+    assert_eq!(
+        2022,
+        ffi::c_return_unique_array_shared(3)
+            .iter()
+            .map(|shared| shared.z)
+            .max()
+            .unwrap(),
+    );
     assert_eq!(
         200_u8,
         ffi::c_return_unique_ptr_vector_u8().into_iter().sum(),
@@ -76,9 +99,13 @@ fn test_c_return() {
             .sum(),
     );
     assert_eq!(b"\x02\0\x02\0"[..], ffi::c_return_rust_vec_u8());
+    let vec = ffi::c_return_rust_vec_from_std_vector(300);
+    assert_eq!(300, vec.len());
+    assert!(vec.iter().enumerate().all(|(i, &b)| b == i as u8));
     assert_eq!([true, true, false][..], ffi::c_return_rust_vec_bool());
     assert_eq!(2020, ffi::c_return_identity(2020));
     assert_eq!(2021, ffi::c_return_sum(2020, 1));
+    assert_eq!(2021, ffi::c_return_sum_checked(2020, 1));
     match ffi::c_return_enum(0) {
         enm @ ffi::Enum::AVal => assert_eq!(0, enm.repr),
         _ => assert!(false),
@@ -105,9 +132,43 @@ fn test_c_return() {
 fn test_c_try_return() {
     assert_eq!((), ffi::c_try_return_void().unwrap());
     assert_eq!(2020, ffi::c_try_return_primitive().unwrap());
+    let logic_error = ffi::c_fail_return_primitive().unwrap_err();
+    assert_eq!("logic error", logic_error.what());
+    // Demangled names differ across standard libraries, such as libc++'s
+    // std::__1::logic_error, and the inline namespace of rust::Error carries
+    // cxx's ABI version.
+    assert!(logic_error.type_name().unwrap().ends_with("::logic_error"));
+
+    let system_error = ffi::c_fail_return_system_error().unwrap_err();
+    assert!(system_error.what().starts_with("system error"));
+    assert!(system_error
+        .type_name()
+        .unwrap()
+        .ends_with("::system_error"));
+    assert_eq!(Some(42), system_error.code());
+    assert_eq!(0, system_error.sources().count());
+
+    let nested = ffi::c_fail_return_nested().unwrap_err();
+    assert_eq!("outer", nested.what());
+    assert_eq!(None, nested.code());
+    assert_eq!(vec!["inner"], nested.sources().collect::<Vec<_>>());
+    let source = std::error::Error::source(&nested).unwrap();
+    assert_eq!("inner", source.to_string());
+
+    let foreign = ffi::c_fail_return_foreign_exception().unwrap_err();
+    assert_eq!(
+        "::tests::c_fail_return_foreign_exception threw an exception that is not a std::exception",
+        foreign.what(),
+    );
+    assert_eq!(None, foreign.type_name());
+
+    let rust_error = ffi::c_fail_return_rust_error().unwrap_err();
+    assert_eq!("outer", rust_error.what());
+    assert!(rust_error.type_name().unwrap().ends_with("::Error"));
+    assert_eq!(Some(7), rust_error.code());
     assert_eq!(
-        "logic error",
-        ffi::c_fail_return_primitive().unwrap_err().what(),
+        vec!["middle", "inner"],
+        rust_error.sources().collect::<Vec<_>>(),
     );
     assert_eq!(2020, ffi::c_try_return_box().unwrap().0);
     assert_eq!("2020", *ffi::c_try_return_ref(&"2020".to_owned()).unwrap());
@@ -170,10 +231,27 @@ fn test_c_take() {
     let mut vector = ffi::c_return_unique_ptr_vector_shared();
     vector.pin_mut().push(ffi::Shared { z: 9 });
     check!(ffi::c_take_unique_ptr_vector_shared(vector));
+    let mut array = ffi::c_return_unique_array_len(3);
+    array[2] = 2050;
+    let (array, len) = array.into_raw_parts();
+    check!(ffi::c_take_unique_array(array, len));
     check!(ffi::c_take_ref_vector(&ffi::c_return_unique_ptr_vector_u8()));
+    let mut vector = ffi::c_return_unique_ptr_vector_bool();
+    vector.pin_mut().set(2, false);
+    vector.pin_mut().push(true);
+    check!(ffi::c_take_ref_vector_bool(&vector));
+    let mut hash_map = HashMap::new();
+    for key in ["one", "three", "seven"] {
+        hash_map.insert(key.to_owned(), key.len() as u32);
+    }
+    check!(ffi::c_take_rust_hash_map(&hash_map));
+    let hash_map = HashMap::from([(1, -1), (2, -2)]);
+    check!(ffi::c_take_rust_hash_map_roundtrip(&hash_map));
     let test_vec = [86_u8, 75_u8, 30_u8, 9_u8].to_vec();
     check!(ffi::c_take_rust_vec(test_vec.clone()));
     check!(ffi::c_take_rust_vec_index(test_vec.clone()));
+    let string_vec = ["first", "second", "third"].map(str::to_owned).to_vec();
+    check!(ffi::c_take_rust_vec_into_std_vector(string_vec));
     let shared_test_vec = vec![ffi::Shared { z: 1010 }, ffi::Shared { z: 1011 }];
     check!(ffi::c_take_rust_vec_shared(shared_test_vec.clone()));
     check!(ffi::c_take_rust_vec_shared_index(shared_test_vec.clone()));
@@ -182,6 +260,9 @@ fn test_c_take() {
         shared_test_vec.clone()
     ));
     check!(ffi::c_take_rust_vec_shared_clear(shared_test_vec.clone()));
+    check!(ffi::c_take_rust_vec_shared_insert_erase(
+        shared_test_vec.clone()
+    ));
     check!(ffi::c_take_rust_vec_shared_forward_iterator(
         shared_test_vec,
     ));
@@ -210,6 +291,23 @@ fn test_c_take() {
     check!(ffi::c_take_nested_ns_enum(ffi::ABEnum::ABAVal));
 }
 
+#[cfg(feature = "c++17")]
+#[test]
+fn test_c_shared_array() {
+    use cxx_test_suite::cxx17::ffi;
+
+    let buffer = ffi::c_return_shared_array(8);
+    let frame = buffer.slice(4..6);
+    assert_eq!([4, 5], *frame);
+    assert_eq!([7], *buffer.slice(7..));
+    assert!(buffer.slice(8..).is_empty());
+    let (array, len) = frame.into_raw_parts();
+    assert_eq!(2, array.strong_count());
+    check!(ffi::c_take_shared_array(array, len));
+    assert_eq!(1, buffer.into_raw_parts().0.strong_count());
+    assert_eq!(2022, ffi::c_return_shared_array_shared(3)[2].z);
+}
+
 #[test]
 fn test_c_callback() {
     fn callback(s: String) -> usize {
@@ -253,6 +351,21 @@ fn test_c_call_r() {
     check!(cxx_run_test());
 }
 
+#[test]
+fn test_c_send_sync() {
+    fn assert_sync<T: Sync + ?Sized>(_: &T) {}
+
+    let mut unique_ptr = ffi::c_return_unique_ptr();
+    assert_sync(&unique_ptr);
+    let unique_ptr = std::thread::spawn(move || {
+        unique_ptr.pin_mut().set(2021);
+        unique_ptr
+    })
+    .join()
+    .unwrap();
+    assert_eq!(2021, unique_ptr.get());
+}
+
 #[test]
 fn test_c_method_calls() {
     let mut unique_ptr = ffi::c_return_unique_ptr();
@@ -283,6 +396,16 @@ fn test_c_method_calls() {
     assert_eq!(array.a.len() as i32 * val, array.r_get_array_sum());
 }
 
+#[test]
+fn test_c_hash() {
+    let hasher = RandomState::new();
+    let mut unique_ptr = ffi::c_return_unique_ptr();
+    let other = ffi::c_return_unique_ptr();
+    assert_eq!(hasher.hash_one(&*unique_ptr), hasher.hash_one(&*other));
+    unique_ptr.pin_mut().set(2021);
+    assert_ne!(hasher.hash_one(&*unique_ptr), hasher.hash_one(&*other));
+}
+
 #[test]
 fn test_shared_ptr_weak_ptr() {
     let shared_ptr = ffi::c_return_shared_ptr();
@@ -297,6 +420,136 @@ fn test_shared_ptr_weak_ptr() {
     assert!(weak_ptr.upgrade().is_null());
 }
 
+#[test]
+fn test_shared_const_ptr() {
+    let shared_const_ptr = ffi::c_return_shared_const_ptr();
+    assert_eq!(2020, shared_const_ptr.get());
+    assert_eq!(1, shared_const_ptr.strong_count());
+
+    let mut shared_ptr = ffi::c_return_shared_ptr();
+    unsafe { shared_ptr.pin_mut_unchecked() }.set(2021);
+    let clone = shared_ptr.clone();
+    let shared_const_ptr = SharedConstPtr::from(shared_ptr);
+    assert_eq!(2, shared_const_ptr.strong_count());
+    assert_eq!(shared_const_ptr.as_ptr(), clone.as_ptr());
+    drop(clone);
+
+    check!(ffi::c_take_shared_const_ptr(shared_const_ptr.clone()));
+    assert_eq!(1, shared_const_ptr.strong_count());
+    assert!(SharedConstPtr::<ffi::C>::null().is_null());
+}
+
+#[test]
+fn test_custom_ptr() {
+    let custom_ptr = ffi::c_return_custom_ptr();
+    assert_eq!(2020, custom_ptr.get());
+    assert_eq!(1, custom_ptr.refs());
+
+    let clone = custom_ptr.clone();
+    assert_eq!(2, custom_ptr.refs());
+    assert_eq!(custom_ptr.as_ptr(), clone.as_ptr());
+    drop(clone);
+    assert_eq!(1, custom_ptr.refs());
+
+    check!(ffi::c_take_custom_ptr(custom_ptr.clone()));
+    assert_eq!(1, custom_ptr.refs());
+    assert!(CustomPtr::<ffi::IntrusivePtr, ffi::Counted>::null().is_null());
+}
+
+#[test]
+fn test_vocabulary() {
+    assert_eq!("hello, world", ffi::c_vocabulary_greet("world".to_owned()));
+    assert_eq!("hello", ffi::c_vocabulary_first_word("hello world"));
+    assert_eq!(vec![3, 2, 1], ffi::c_vocabulary_reverse(vec![1, 2, 3]));
+}
+
+#[test]
+fn test_nested_module() {
+    assert_eq!(42, ffi::c_nested(20).value);
+}
+
+#[test]
+fn test_mock_trait() {
+    use ffi::CountedApi as _;
+
+    fn total(api: &impl ffi::CountedApi, counted: &ffi::Counted) -> usize {
+        api.get(counted) + api.refs(counted)
+    }
+
+    struct MockCounted;
+
+    impl ffi::CountedApi for MockCounted {
+        fn c_return_custom_ptr(&self) -> CustomPtr<ffi::IntrusivePtr, ffi::Counted> {
+            CustomPtr::null()
+        }
+        fn c_take_custom_ptr(&self, _c: CustomPtr<ffi::IntrusivePtr, ffi::Counted>) {}
+        fn get(&self, _this: &ffi::Counted) -> usize {
+            1
+        }
+        fn refs(&self, _this: &ffi::Counted) -> usize {
+            0
+        }
+    }
+
+    let custom_ptr = ffi::CxxCountedApi.c_return_custom_ptr();
+    assert_eq!(2021, total(&ffi::CxxCountedApi, &custom_ptr));
+    assert_eq!(1, total(&MockCounted, &custom_ptr));
+    assert!(MockCounted.c_return_custom_ptr().is_null());
+}
+
+#[test]
+fn test_com_interface() {
+    let shape = ffi::c_new_square(3).unwrap();
+    assert_eq!(Ok(9), shape.area());
+    assert_eq!(1, ffi::c_com_refcount(&shape));
+
+    let clone = shape.clone();
+    assert_eq!(2, ffi::c_com_refcount(&shape));
+    assert_eq!(shape.as_ptr(), clone.as_ptr());
+    drop(clone);
+    assert_eq!(1, ffi::c_com_refcount(&shape));
+
+    assert_eq!(Err(HRESULT::E_INVALIDARG), shape.scale(0));
+    shape.scale(2).unwrap();
+    assert_eq!(36, ffi::c_take_com_ptr(shape.clone()));
+    assert_eq!(1, ffi::c_com_refcount(&shape));
+
+    let polygon = shape.cast::<ffi::IPolygon>().unwrap();
+    assert_eq!(Ok(4), polygon.sides());
+    assert_eq!(2, ffi::c_com_refcount(&shape));
+    drop(polygon);
+    assert_eq!(
+        Some(HRESULT::E_NOINTERFACE),
+        shape.cast::<ffi::ICircle>().err(),
+    );
+    assert_eq!(1, ffi::c_com_refcount(&shape));
+
+    assert_eq!(Some(HRESULT::E_INVALIDARG), ffi::c_new_square(0).err());
+    let null = ComPtr::<ffi::IShape>::null();
+    assert_eq!(Some(HRESULT::E_POINTER), null.cast::<ffi::IPolygon>().err());
+}
+
+#[test]
+fn test_shared_ptr_weak_ptr_use_count() {
+    let shared_ptr = ffi::c_return_shared_ptr();
+    assert_eq!(1, shared_ptr.strong_count());
+    let weak_ptr = shared_ptr.downgrade();
+    assert_eq!(1, weak_ptr.use_count());
+    assert!(!weak_ptr.expired());
+
+    let clone = shared_ptr.clone();
+    assert_eq!(2, shared_ptr.strong_count());
+    assert_eq!(2, weak_ptr.use_count());
+
+    drop(clone);
+    drop(shared_ptr);
+    assert_eq!(0, weak_ptr.use_count());
+    assert!(weak_ptr.expired());
+
+    assert_eq!(0, SharedPtr::<ffi::C>::null().strong_count());
+    assert!(WeakPtr::<ffi::C>::null().expired());
+}
+
 #[test]
 fn test_unique_to_shared_ptr_string() {
     let unique = ffi::c_return_unique_ptr_string();
@@ -320,6 +573,18 @@ fn test_unique_to_shared_ptr_null() {
     assert!(unique.is_null());
     let shared = SharedPtr::from(unique);
     assert!(shared.is_null());
+    let weak_ptr = shared.downgrade();
+    assert_eq!(0, ffi::c_get_use_count(&weak_ptr));
+}
+
+#[test]
+fn test_unique_into_shared() {
+    let unique = ffi::c_return_unique_ptr();
+    let ptr = unique.as_ptr();
+    let shared = unique.into_shared();
+    assert_eq!(shared.as_ptr(), ptr);
+    let weak_ptr = shared.downgrade();
+    assert_eq!(1, ffi::c_get_use_count(&weak_ptr));
 }
 
 #[test]
@@ -433,3 +698,43 @@ fn test_unwind_safe() {
     fn require_ref_unwind_safe<T: RefUnwindSafe>() {}
     require_ref_unwind_safe::<ffi::C>();
 }
+
+#[test]
+fn test_rust_stream_adapters() {
+    let input = "1 2 3\n40";
+    let sum = CxxIstream::with_reader(input.as_bytes(), ffi::c_sum_istream).unwrap();
+    assert_eq!(46, sum);
+
+    let mut output = Vec::new();
+    CxxOstream::with_writer(&mut output, |os| ffi::c_write_ostream(os, 2000)).unwrap();
+    let expected: String = (0..2000).map(|i| format!("line {}\n", i)).collect();
+    assert_eq!(expected.as_bytes(), output);
+
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "unreadable"))
+        }
+    }
+    impl Write for Failing {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "unwritable"))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    let err = CxxIstream::with_reader(Failing, ffi::c_sum_istream).unwrap_err();
+    assert_eq!("unreadable", err.to_string());
+    let err = CxxOstream::with_writer(Failing, |os| ffi::c_write_ostream(os, 1)).unwrap_err();
+    assert_eq!("unwritable", err.to_string());
+
+    let result = panic::catch_unwind(|| {
+        CxxOstream::with_writer(io::sink(), |os| {
+            ffi::c_write_ostream(os, 1);
+            panic!("panic inside with_writer");
+        })
+    });
+    let payload = result.unwrap_err();
+    assert_eq!(Some(&"panic inside with_writer"), payload.downcast_ref());
+}
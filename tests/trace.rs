@@ -0,0 +1,144 @@
+#![cfg(feature = "trace")]
+
+use cxx::trace::{self, Call, Direction, Phase};
+use cxx_test_suite::ffi;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+static CALLS: Mutex<Vec<(&str, Direction, bool)>> = Mutex::new(Vec::new());
+
+// Held by each test, since the hook sees the calls of every thread.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn hook(call: &Call) {
+    let function = call.function.strip_prefix("cxx_test_suite::ffi::").unwrap();
+    let exit = matches!(call.phase, Phase::Exit(_));
+    CALLS.lock().unwrap().push((function, call.direction, exit));
+}
+
+// Records the `function` and `direction` of every span, and whether it is
+// being entered or exited.
+#[derive(Default)]
+struct Spans {
+    next_id: AtomicU64,
+    fields: Mutex<Vec<(String, String)>>,
+    events: Mutex<Vec<(String, bool)>>,
+}
+
+#[derive(Default)]
+struct SpanFields {
+    function: String,
+    direction: String,
+}
+
+impl Visit for SpanFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "function" => self.function = value.to_owned(),
+            "direction" => self.direction = value.to_owned(),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn Debug) {}
+}
+
+impl Subscriber for Spans {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() == "cxx"
+    }
+
+    fn new_span(&self, span: &Attributes) -> Id {
+        assert_eq!("call", span.metadata().name());
+        let mut fields = SpanFields::default();
+        span.record(&mut fields);
+        let function = fields.function.strip_prefix("cxx_test_suite::ffi::");
+        let function = function.unwrap().to_owned();
+        self.fields
+            .lock()
+            .unwrap()
+            .push((function, fields.direction));
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event) {}
+
+    fn enter(&self, span: &Id) {
+        self.push(span, false);
+    }
+
+    fn exit(&self, span: &Id) {
+        self.push(span, true);
+    }
+}
+
+impl Spans {
+    fn push(&self, span: &Id, exit: bool) {
+        let index = usize::try_from(span.into_u64() - 1).unwrap();
+        let function = self.fields.lock().unwrap()[index].0.clone();
+        self.events.lock().unwrap().push((function, exit));
+    }
+}
+
+#[test]
+fn test_tracing() {
+    let _serial = SERIAL.lock().unwrap();
+    let spans = std::sync::Arc::new(Spans::default());
+    tracing::subscriber::with_default(spans.clone(), || {
+        ffi::c_fail_return_rust_error().unwrap_err();
+    });
+
+    assert_eq!(
+        *spans.fields.lock().unwrap(),
+        [
+            (
+                "c_fail_return_rust_error".to_owned(),
+                "rust->c++".to_owned()
+            ),
+            ("r_fail_return_exception".to_owned(), "c++->rust".to_owned()),
+        ],
+    );
+    assert_eq!(
+        *spans.events.lock().unwrap(),
+        [
+            ("c_fail_return_rust_error".to_owned(), false),
+            ("r_fail_return_exception".to_owned(), false),
+            ("r_fail_return_exception".to_owned(), true),
+            ("c_fail_return_rust_error".to_owned(), true),
+        ],
+    );
+}
+
+#[test]
+fn test_trace() {
+    let _serial = SERIAL.lock().unwrap();
+    ffi::c_return_primitive();
+    assert!(CALLS.lock().unwrap().is_empty());
+
+    assert!(trace::set_hook(Some(hook)).is_none());
+    assert_eq!(2020, ffi::c_return_primitive());
+    ffi::c_fail_return_rust_error().unwrap_err();
+    assert!(trace::set_hook(None).is_some());
+    ffi::c_return_primitive();
+
+    let calls = CALLS.lock().unwrap();
+    assert_eq!(
+        *calls,
+        [
+            ("c_return_primitive", Direction::RustToCxx, false),
+            ("c_return_primitive", Direction::RustToCxx, true),
+            ("c_fail_return_rust_error", Direction::RustToCxx, false),
+            ("r_fail_return_exception", Direction::CxxToRust, false),
+            ("r_fail_return_exception", Direction::CxxToRust, true),
+            ("c_fail_return_rust_error", Direction::RustToCxx, true),
+        ],
+    );
+}
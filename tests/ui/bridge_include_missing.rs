@@ -0,0 +1,6 @@
+#[cxx::bridge]
+mod ffi {
+    include!("missing.rs");
+}
+
+fn main() {}
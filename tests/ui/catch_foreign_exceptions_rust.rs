@@ -0,0 +1,11 @@
+#[cxx::bridge]
+mod ffi {
+    extern "Rust" {
+        #[catch_foreign_exceptions]
+        fn f();
+    }
+}
+
+fn f() {}
+
+fn main() {}
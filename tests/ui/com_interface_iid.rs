@@ -0,0 +1,9 @@
+#[cxx::bridge]
+mod ffi {
+    unsafe extern "C++" {
+        #[com_interface = "0000-0000"]
+        type IBroken;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+#[cxx::bridge]
+mod ffi {
+    unsafe extern "C++" {
+        #[com_interface = "00000000-0000-0000-C000-000000000046"]
+        type IUnknown;
+        type Thing;
+
+        fn f() -> ComPtr<Thing>;
+        fn g() -> Result<UniquePtr<Thing>, HRESULT>;
+        fn h(self: &IUnknown) -> Result<ComPtr<IUnknown>, HRESULT>;
+    }
+
+    extern "Rust" {
+        #[com_interface = "00000000-0000-0000-C000-000000000046"]
+        type Local;
+
+        fn r() -> Result<(), HRESULT>;
+    }
+}
+
+struct Local;
+
+fn r() -> Result<(), cxx::HRESULT> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[cxx::bridge]
+mod ffi {
+    extern "Rust" {
+        fn f(map: &mut HashMap<u8, u8>);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+#[cxx::bridge]
+mod ffi {
+    struct S {
+        map: &'static HashMap<u8, u8>,
+    }
+
+    unsafe extern "C++" {
+        fn f() -> &'static HashMap<u8, u8>;
+        fn g(map: &HashMap<u8, CxxString>);
+        fn h(map: &HashMap<f64, u8>);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[cxx::bridge]
+mod ffi {
+    extern "Rust" {
+        #[incomplete]
+        type Opaque;
+    }
+}
+
+pub struct Opaque;
+
+fn main() {}
@@ -0,0 +1,23 @@
+#[cxx::bridge]
+mod ffi {
+    #[mock = Api]
+    unsafe extern "C++" {
+        type C;
+        fn get(self: &C) -> usize;
+    }
+
+    #[mock = Api]
+    unsafe extern "C++" {
+        type D;
+        fn get(self: &D) -> usize;
+    }
+
+    #[mock = Api]
+    extern "Rust" {
+        fn f();
+    }
+}
+
+fn f() {}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[cxx::bridge]
+mod ffi {
+    extern "C++" {
+        #[send]
+        type Untrusted;
+    }
+
+    extern "Rust" {
+        #[sync]
+        type Local;
+    }
+}
+
+struct Local;
+
+fn main() {}
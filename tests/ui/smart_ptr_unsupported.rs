@@ -0,0 +1,20 @@
+#[cxx::bridge]
+mod ffi {
+    unsafe extern "C++" {
+        #[smart_ptr]
+        type Handle;
+        type Thing;
+
+        fn f() -> CustomPtr<Thing, Thing>;
+        fn g(handle: &Handle);
+    }
+
+    extern "Rust" {
+        #[smart_ptr]
+        type Local;
+    }
+}
+
+struct Local;
+
+fn main() {}
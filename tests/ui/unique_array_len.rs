@@ -0,0 +1,22 @@
+#[cxx::bridge]
+mod ffi {
+    unsafe extern "C++" {
+        #[array_len = n]
+        fn not_array(n: usize) -> UniquePtr<CxxString>;
+
+        #[array_len = len]
+        fn missing_arg(n: usize) -> UniqueArray<u8>;
+
+        #[array_len = n]
+        fn wrong_type(n: i32) -> UniqueArray<u8>;
+
+        fn string_array() -> UniqueArray<CxxString>;
+    }
+
+    extern "Rust" {
+        #[array_len = n]
+        fn rust_array(n: usize) -> UniqueArray<u8>;
+    }
+}
+
+fn main() {}
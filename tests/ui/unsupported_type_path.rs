@@ -0,0 +1,15 @@
+#[cxx::bridge]
+mod ffi {
+    extern "C++" {
+        type Widget;
+
+        fn f() -> std::string;
+        fn g() -> UniquePointer<Widget>;
+        fn h() -> std::ffi::c_char;
+        fn i() -> UniquePtr<Widget, Widget>;
+        fn j() -> (i32, i32);
+        fn k() -> engine::Widget;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+#[cxx::bridge]
+mod ffi {
+    struct Shared {
+        x: int,
+        y: Strng,
+    }
+
+    extern "Rust" {
+        type Handle;
+
+        fn f(widget: &Widgit);
+        fn g(handles: &CxxVector<Handle>);
+    }
+
+    unsafe extern "C++" {
+        type Widget;
+
+        fn h() -> UniquePtr<Handle>;
+        fn i() -> Vec<Widget>;
+        fn j(widget: Widget);
+        fn k(widget: *mut Widget);
+        fn l(data: &[u8]) -> &mut [u8];
+    }
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+#[cxx::bridge]
+mod ffi {
+    unsafe extern "C++" {
+        #[vocabulary = String]
+        type QString;
+        #[vocabulary = str]
+        type QStringView;
+        #[vocabulary = "Vec<QString>"]
+        type QStringList;
+        #[vocabulary = "&QString"]
+        type QStringRef;
+
+        fn by_reference(s: &QStringView);
+        fn list() -> QStringList;
+    }
+
+    extern "Rust" {
+        fn to_rust(s: QString);
+    }
+}
+
+fn to_rust(_s: ffi::QString) {}
+
+fn main() {}
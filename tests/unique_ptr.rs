@@ -1,4 +1,8 @@
-use cxx::{CxxString, UniquePtr};
+#[cfg(feature = "c++17")]
+use cxx::memory::SharedSlice;
+#[cfg(feature = "c++17")]
+use cxx::SharedArray;
+use cxx::{CxxString, SharedPtr, UniqueArray, UniquePtr};
 
 #[test]
 #[should_panic = "called deref on a null UniquePtr<CxxString>"]
@@ -6,3 +10,73 @@ fn test_deref_null() {
     let unique_ptr = UniquePtr::<CxxString>::null();
     let _: &CxxString = &unique_ptr;
 }
+
+#[test]
+fn test_raw_round_trip() {
+    let unique = UniquePtr::<CxxString>::from("2020");
+    let raw = unique.into_raw();
+    assert!(!raw.is_null());
+    let unique = unsafe { UniquePtr::from_raw(raw) };
+    assert_eq!(unique.as_ptr(), raw);
+    assert_eq!(*unique, *"2020");
+
+    let shared = unique.into_shared();
+    let raw = shared.clone().into_boxed_raw();
+    let recovered = unsafe { SharedPtr::<CxxString>::from_boxed_raw(raw) };
+    assert_eq!(recovered.as_ptr(), shared.as_ptr());
+    assert_eq!(*recovered, *"2020");
+}
+
+#[test]
+fn test_null_arrays() {
+    let unique = UniqueArray::<u32>::null();
+    assert!(unique.is_null());
+    assert!(unique.into_raw().is_null());
+}
+
+#[cfg(feature = "c++17")]
+#[test]
+fn test_null_shared_arrays() {
+    let shared = SharedArray::<u8>::from(UniqueArray::null());
+    assert!(shared.is_null());
+    let slice = unsafe { SharedSlice::from_raw_parts(shared, 4) };
+    assert!(slice.is_empty());
+    assert!(slice.slice(..0).is_empty());
+}
+
+#[cfg(feature = "c++17")]
+#[test]
+#[should_panic = "range 1..2 out of bounds for SharedSlice<u8> of length 0"]
+fn test_shared_slice_out_of_bounds() {
+    let slice = unsafe { SharedSlice::from_raw_parts(SharedArray::<u8>::null(), 4) };
+    let _ = slice.slice(1..2);
+}
+
+#[test]
+fn test_pin_mut_helpers() {
+    let mut unique = UniquePtr::<CxxString>::null();
+    assert_eq!(None, unique.with_pin_mut(|string| string.len()));
+    unique
+        .pin_mut_or_else(|| UniquePtr::from("a"))
+        .push_str("b");
+    unique.pin_mut_or_else(|| unreachable!()).push_str("c");
+    assert_eq!(Some(3), unique.with_pin_mut(|string| string.len()));
+
+    let len = cxx::pin_mut!(unique => clear(), push_str("xy"), len());
+    assert_eq!(2, len);
+    let mut pin = unique.pin_mut();
+    cxx::pin_mut!(pin => push_str("z"));
+    exclaim(&mut unique);
+    assert_eq!(*unique, *"xyz!");
+}
+
+fn exclaim(unique: &mut UniquePtr<CxxString>) {
+    cxx::pin_mut!(unique => push_bytes(b"!"));
+}
+
+#[test]
+#[should_panic = "pin_mut_or_else produced a null UniquePtr<CxxString>"]
+fn test_pin_mut_or_else_null() {
+    let mut unique = UniquePtr::<CxxString>::null();
+    unique.pin_mut_or_else(UniquePtr::null);
+}
@@ -0,0 +1,88 @@
+#!/bin/bash
+#
+# Measures what a large #[cxx::bridge] costs an incremental build. Generates a
+# scratch crate whose bridge has the given number of functions (default 900),
+# in the mix of extern "C++" free functions, methods on opaque C++ types, and
+# extern "Rust" functions that a typical large bridge has, then reports:
+#
+#   - the size of the macro expansion of the bridge, from `cxxbridge --emit rust`
+#   - the median CPU time (user + system) of `cargo build` after touching the
+#     file containing the bridge, which is what the bridge adds to every
+#     incremental build of its crate. CPU time varies less than wall time on a
+#     busy machine.
+#
+# Usage: tools/bench/large_bridge.sh [functions] [runs]
+#
+# The crate only builds an rlib, so no C++ is compiled or linked. Compare a
+# before and after by running the script on each of the two checkouts.
+
+set -euo pipefail
+
+functions=${1:-900}
+runs=${2:-11}
+repo=$(cd "$(dirname "$0")/../.." && pwd)
+dir=$repo/target/bench/large_bridge
+
+mkdir -p "$dir/src"
+cat >"$dir/Cargo.toml" <<TOML
+[package]
+name = "large_bridge"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[lib]
+path = "src/lib.rs"
+
+[dependencies]
+cxx = { path = "$repo" }
+
+[workspace]
+TOML
+
+{
+    echo '#[cxx::bridge(namespace = "bench")]'
+    echo 'pub mod ffi {'
+    echo '    unsafe extern "C++" {'
+    echo '        include!("bench.h");'
+    for ((i = 0; i < functions / 3; i += 1)); do
+        if ((i % 10 == 0)); then
+            echo "        type Object$i;"
+            echo "        fn new_object$i() -> UniquePtr<Object$i>;"
+        fi
+        object=Object$((i / 10 * 10))
+        echo "        fn name$i(self: &$object) -> String;"
+        echo "        fn set_name$i(self: Pin<&mut $object>, name: &str) -> Result<()>;"
+    done
+    for ((i = 0; i < functions / 3; i += 1)); do
+        echo "        fn sum$i(a: i32, b: i32) -> i32;"
+    done
+    echo '    }'
+    echo '    extern "Rust" {'
+    for ((i = 0; i < functions / 3; i += 1)); do
+        echo "        fn rust$i(value: u32, text: &str) -> String;"
+    done
+    echo '    }'
+    echo '}'
+    for ((i = 0; i < functions / 3; i += 1)); do
+        echo "fn rust$i(value: u32, text: &str) -> String { format!(\"{text}{value}\") }"
+    done
+} >"$dir/src/lib.rs"
+
+cd "$repo"
+cargo build --quiet --offline --release --package cxxbridge-cmd
+expansion=$(target/release/cxxbridge "$dir/src/lib.rs" --emit rust | wc -c)
+echo "functions: $functions"
+echo "macro expansion: $expansion bytes"
+
+cd "$dir"
+cargo build --quiet --offline
+TIMEFORMAT='%U %S'
+times=()
+for ((run = 1; run <= runs; run += 1)); do
+    touch src/lib.rs
+    cpu=$({ time cargo build --quiet --offline; } 2>&1)
+    times+=("$(echo "$cpu" | awk '{printf "%d", ($1 + $2) * 1000}')")
+done
+median=$(printf '%s\n' "${times[@]}" | sort -n | sed -n "$(((runs + 1) / 2))p")
+echo "incremental build: $median ms (median of $runs)"